@@ -13,10 +13,14 @@
 // limitations under the License.
 
 use std::alloc::Layout;
-use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicI64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
+use common_exception::Result;
+
 #[thread_local]
 static mut TRACKER: *const ThreadTracker = std::ptr::null();
 
@@ -49,7 +53,14 @@ impl ThreadTracker {
 }
 
 pub struct MemoryTracker {
-    memory_usage: AtomicUsize,
+    // Signed, because memory may be freed by a thread of another tracker.
+    memory_usage: AtomicI64,
+    peak_memory_usage: AtomicI64,
+    // The max memory(bytes) can be used (0 is no limited).
+    limit: AtomicI64,
+    // The allocator cannot fail an allocation, so it only marks the overrun here
+    // and the owner checks it by `check_limit`.
+    limit_exceeded: AtomicBool,
     parent_memory_tracker: Option<Arc<MemoryTracker>>,
 }
 
@@ -57,22 +68,27 @@ impl MemoryTracker {
     pub fn create(parent_memory_tracker: Option<Arc<MemoryTracker>>) -> Arc<MemoryTracker> {
         Arc::new(MemoryTracker {
             parent_memory_tracker,
-            memory_usage: AtomicUsize::new(0),
+            memory_usage: AtomicI64::new(0),
+            peak_memory_usage: AtomicI64::new(0),
+            limit: AtomicI64::new(0),
+            limit_exceeded: AtomicBool::new(false),
         })
     }
 
     #[inline]
     pub fn alloc_memory(&self, size: usize) {
-        self.memory_usage.fetch_add(size, Ordering::Relaxed);
+        let size = size as i64;
+        let memory_usage = self.memory_usage.fetch_add(size, Ordering::Relaxed) + size;
+        self.on_memory_usage_increased(memory_usage);
 
         if let Some(parent_memory_tracker) = &self.parent_memory_tracker {
-            parent_memory_tracker.alloc_memory(size);
+            parent_memory_tracker.alloc_memory(size as usize);
         }
     }
 
     #[inline]
     pub fn dealloc_memory(&self, size: usize) {
-        self.memory_usage.fetch_sub(size, Ordering::Relaxed);
+        self.memory_usage.fetch_sub(size as i64, Ordering::Relaxed);
 
         if let Some(parent_memory_tracker) = &self.parent_memory_tracker {
             parent_memory_tracker.dealloc_memory(size);
@@ -81,14 +97,29 @@ impl MemoryTracker {
 
     #[inline]
     pub fn realloc_memory(&self, old_size: usize, new_size: usize) {
-        self.memory_usage.fetch_sub(old_size, Ordering::Relaxed);
-        self.memory_usage.fetch_add(new_size, Ordering::Relaxed);
+        let diff = new_size as i64 - old_size as i64;
+        let memory_usage = self.memory_usage.fetch_add(diff, Ordering::Relaxed) + diff;
+
+        if diff > 0 {
+            self.on_memory_usage_increased(memory_usage);
+        }
 
         if let Some(parent_memory_tracker) = &self.parent_memory_tracker {
             parent_memory_tracker.realloc_memory(old_size, new_size);
         }
     }
 
+    #[inline]
+    fn on_memory_usage_increased(&self, memory_usage: i64) {
+        self.peak_memory_usage
+            .fetch_max(memory_usage, Ordering::Relaxed);
+
+        let limit = self.limit.load(Ordering::Relaxed);
+        if limit > 0 && memory_usage > limit {
+            self.limit_exceeded.store(true, Ordering::Relaxed);
+        }
+    }
+
     pub fn current() -> Option<Arc<MemoryTracker>> {
         let thread_trckcer = ThreadTracker::current();
         match thread_trckcer.is_null() {
@@ -98,7 +129,31 @@ impl MemoryTracker {
     }
 
     pub fn get_memory_usage(&self) -> usize {
-        self.memory_usage.load(Ordering::Relaxed)
+        self.memory_usage.load(Ordering::Relaxed).max(0) as usize
+    }
+
+    pub fn get_peak_memory_usage(&self) -> usize {
+        self.peak_memory_usage.load(Ordering::Relaxed).max(0) as usize
+    }
+
+    pub fn get_limit(&self) -> usize {
+        self.limit.load(Ordering::Relaxed) as usize
+    }
+
+    // Set the max memory(bytes) can be used, 0 is no limited.
+    pub fn set_limit(&self, limit: usize) {
+        self.limit.store(limit as i64, Ordering::Relaxed);
+    }
+
+    pub fn check_limit(&self) -> Result<()> {
+        match self.limit_exceeded.load(Ordering::Relaxed) {
+            false => Ok(()),
+            true => Err(ErrorCode::MemoryLimitExceeded(format!(
+                "Memory limit exceeded: peak memory usage {} bytes, maximum {} bytes",
+                self.get_peak_memory_usage(),
+                self.get_limit()
+            ))),
+        }
     }
 }
 
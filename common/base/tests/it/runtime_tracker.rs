@@ -0,0 +1,45 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::*;
+use common_exception::Result;
+
+#[test]
+fn test_memory_tracker_limit() -> Result<()> {
+    let parent = MemoryTracker::create(None);
+    let tracker = MemoryTracker::create(Some(parent.clone()));
+    tracker.set_limit(1024);
+
+    tracker.alloc_memory(512);
+    tracker.realloc_memory(512, 1024);
+    assert!(tracker.check_limit().is_ok());
+    assert_eq!(parent.get_memory_usage(), 1024);
+
+    tracker.alloc_memory(1024);
+    tracker.dealloc_memory(1024);
+    assert_eq!(tracker.get_memory_usage(), 1024);
+    assert_eq!(tracker.get_peak_memory_usage(), 2048);
+    assert!(parent.check_limit().is_ok());
+
+    match tracker.check_limit() {
+        Ok(_) => panic!("memory limit should be exceeded"),
+        Err(cause) => assert_eq!(cause.code(), 60),
+    }
+
+    // Memory freed by a thread of another tracker does not underflow.
+    tracker.dealloc_memory(4096);
+    assert_eq!(tracker.get_memory_usage(), 0);
+
+    Ok(())
+}
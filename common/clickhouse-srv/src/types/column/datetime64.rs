@@ -21,11 +21,19 @@ use crate::errors::Result;
 use crate::types::column::column_data::BoxColumnData;
 use crate::types::column::column_data::ColumnData;
 use crate::types::column::list::List;
+use crate::types::column::nullable::NullableColumnData;
+use crate::types::column::ArcColumnWrapper;
+use crate::types::column::ColumnFrom;
+use crate::types::column::ColumnWrapper;
 use crate::types::DateTimeType;
 use crate::types::SqlType;
 use crate::types::Value;
 use crate::types::ValueRef;
 
+/// Raw DateTime64 ticks together with their precision and timezone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DateTime64(pub i64, pub u32, pub Tz);
+
 pub struct DateTime64ColumnData {
     data: List<i64>,
     params: (u32, Tz),
@@ -57,6 +65,49 @@ impl DateTime64ColumnData {
     }
 }
 
+impl ColumnFrom for Vec<DateTime64> {
+    fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
+        let (precision, tz) = source.first().map(|v| (v.1, v.2)).unwrap_or((3, Tz::Zulu));
+        let mut data = List::with_capacity(source.len());
+        for DateTime64(value, _, _) in source {
+            data.push(value);
+        }
+        W::wrap(DateTime64ColumnData {
+            data,
+            params: (precision, tz),
+        })
+    }
+}
+
+impl ColumnFrom for Vec<Option<DateTime64>> {
+    fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
+        let (precision, tz) = source
+            .iter()
+            .find_map(|v| v.map(|v| (v.1, v.2)))
+            .unwrap_or((3, Tz::Zulu));
+
+        let mut values = Vec::with_capacity(source.len());
+        let mut nulls = Vec::with_capacity(source.len());
+        for value in source {
+            match value {
+                None => {
+                    nulls.push(1);
+                    values.push(DateTime64(0, precision, tz));
+                }
+                Some(value) => {
+                    nulls.push(0);
+                    values.push(value);
+                }
+            }
+        }
+
+        W::wrap(NullableColumnData {
+            inner: Vec::column_from::<ArcColumnWrapper>(values),
+            nulls,
+        })
+    }
+}
+
 impl ColumnData for DateTime64ColumnData {
     fn sql_type(&self) -> SqlType {
         let (precision, tz) = self.params;
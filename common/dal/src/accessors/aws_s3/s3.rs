@@ -15,6 +15,8 @@
 
 use std::str::FromStr;
 
+use chrono::DateTime;
+use chrono::Utc;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use futures::Stream;
@@ -24,12 +26,15 @@ use rusoto_core::ByteStream;
 use rusoto_core::Client;
 use rusoto_core::HttpClient;
 use rusoto_core::Region;
+use rusoto_s3::DeleteObjectRequest;
+use rusoto_s3::ListObjectsV2Request;
 use rusoto_s3::PutObjectRequest;
 use rusoto_s3::S3Client;
 use rusoto_s3::S3 as RusotoS3;
 
 use crate::DataAccessor;
 use crate::InputStream;
+use crate::ObjectMeta;
 use crate::S3InputStream;
 
 pub struct S3 {
@@ -156,4 +161,62 @@ impl DataAccessor for S3 {
         self.put_byte_stream(path, ByteStream::new_with_size(input_stream, stream_len))
             .await
     }
+
+    async fn remove(&self, path: &str) -> common_exception::Result<()> {
+        // deleting a nonexistent key is not an error in s3
+        let req = DeleteObjectRequest {
+            key: path.to_string(),
+            bucket: self.bucket.to_string(),
+            ..Default::default()
+        };
+        self.client
+            .delete_object(req)
+            .await
+            .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> common_exception::Result<Vec<ObjectMeta>> {
+        let prefix = format!("{}/", prefix.trim_end_matches('/'));
+        let mut objects = vec![];
+        let mut continuation_token = None;
+        loop {
+            let req = ListObjectsV2Request {
+                bucket: self.bucket.to_string(),
+                prefix: Some(prefix.clone()),
+                continuation_token: continuation_token.take(),
+                ..Default::default()
+            };
+            let output = self
+                .client
+                .list_objects_v2(req)
+                .await
+                .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?;
+
+            for object in output.contents.unwrap_or_default() {
+                if let (Some(key), Some(last_modified)) = (object.key, object.last_modified) {
+                    let last_modified = DateTime::parse_from_rfc3339(&last_modified)
+                        .map_err(|e| {
+                            ErrorCode::DALTransportError(format!(
+                                "invalid last modified time {} of object {}, {}",
+                                last_modified, key, e
+                            ))
+                        })?
+                        .with_timezone(&Utc);
+                    objects.push(ObjectMeta {
+                        path: key,
+                        last_modified,
+                    });
+                }
+            }
+
+            match output.next_continuation_token {
+                Some(token) if output.is_truncated == Some(true) => {
+                    continuation_token = Some(token)
+                }
+                _ => break,
+            }
+        }
+        Ok(objects)
+    }
 }
@@ -27,6 +27,7 @@ use crate::AzureBlobInputStream;
 use crate::Bytes;
 use crate::DataAccessor;
 use crate::InputStream;
+use crate::ObjectMeta;
 
 pub struct AzureBlobAccessor {
     client: Arc<StorageClient>,
@@ -160,4 +161,52 @@ impl DataAccessor for AzureBlobAccessor {
         }
         self.put_blob(path, data).await
     }
+
+    async fn remove(&self, path: &str) -> common_exception::Result<()> {
+        let blob = self
+            .client
+            .as_container_client(&self.container)
+            .as_blob_client(path);
+
+        match blob.delete().execute().await {
+            Err(e) if e.to_string().contains("BlobNotFound") => Ok(()),
+            Err(e) => Err(ErrorCode::DALTransportError(format!(
+                "Failed on azure blob delete operation, {}",
+                e.to_string()
+            ))),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> common_exception::Result<Vec<ObjectMeta>> {
+        let container = self.client.as_container_client(&self.container);
+        let prefix = format!("{}/", prefix.trim_end_matches('/'));
+        let mut objects = vec![];
+        let mut next_marker = None;
+        loop {
+            let mut request = container.list_blobs().prefix(prefix.as_str());
+            if let Some(marker) = next_marker.take() {
+                request = request.next_marker(marker);
+            }
+            let response = request.execute().await.map_err(|e| {
+                ErrorCode::DALTransportError(format!(
+                    "Failed on azure blob list operation, {}",
+                    e.to_string()
+                ))
+            })?;
+
+            for blob in response.blobs.blobs {
+                objects.push(ObjectMeta {
+                    path: blob.name,
+                    last_modified: blob.properties.last_modified,
+                });
+            }
+
+            match response.next_marker {
+                Some(marker) => next_marker = Some(marker),
+                None => break,
+            }
+        }
+        Ok(objects)
+    }
 }
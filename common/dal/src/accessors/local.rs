@@ -20,6 +20,8 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use async_compat::CompatExt;
+use chrono::DateTime;
+use chrono::Utc;
 use common_base::tokio;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -29,6 +31,7 @@ use tokio::io::AsyncWriteExt;
 
 use crate::DataAccessor;
 use crate::InputStream;
+use crate::ObjectMeta;
 
 pub struct Local {
     root: PathBuf,
@@ -108,6 +111,47 @@ impl DataAccessor for Local {
         new_file.flush().await?;
         Ok(())
     }
+
+    async fn remove(&self, path: &str) -> common_exception::Result<()> {
+        let path = self.prefix_with_root(path)?;
+        match tokio::fs::remove_file(path).await {
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            r => Ok(r?),
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> common_exception::Result<Vec<ObjectMeta>> {
+        let mut objects = vec![];
+        let mut dirs = vec![self.prefix_with_root(prefix)?];
+        while let Some(dir) = dirs.pop() {
+            let mut entries = match tokio::fs::read_dir(&dir).await {
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                r => r?,
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let metadata = entry.metadata().await?;
+                if metadata.is_dir() {
+                    dirs.push(entry.path());
+                    continue;
+                }
+
+                // paths of the objects are relative to the root, and separated by '/'
+                let path = entry.path();
+                let relative = path.strip_prefix(&self.root).map_err(|e| {
+                    ErrorCode::UnknownException(format!("invalid path {:?}, {}", path, e))
+                })?;
+                let components = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>();
+                objects.push(ObjectMeta {
+                    path: components.join("/"),
+                    last_modified: DateTime::<Utc>::from(metadata.modified()?),
+                });
+            }
+        }
+        Ok(objects)
+    }
 }
 
 // from cargo::util::path
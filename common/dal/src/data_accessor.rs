@@ -16,6 +16,8 @@ use std::io::Read;
 use std::io::Seek;
 use std::sync::Arc;
 
+use chrono::DateTime;
+use chrono::Utc;
 use common_exception::Result;
 use futures::stream::Stream;
 use futures::AsyncRead;
@@ -35,6 +37,13 @@ pub trait SeekableReader: Read + Seek {}
 
 impl<T> SeekableReader for T where T: Read + Seek {}
 
+/// Meta of the objects listed by [`DataAccessor::list`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectMeta {
+    pub path: String,
+    pub last_modified: DateTime<Utc>,
+}
+
 #[async_trait::async_trait]
 pub trait DataAccessor: Send + Sync {
     fn get_input_stream(&self, path: &str, stream_len: Option<u64>) -> Result<InputStream>;
@@ -53,6 +62,13 @@ pub trait DataAccessor: Send + Sync {
         stream_len: usize,
     ) -> Result<()>;
 
+    /// Removes the object at the given path, it is not an error if the object does not exist
+    async fn remove(&self, path: &str) -> Result<()>;
+
+    /// Lists the objects under the directory `prefix` recursively, it is not an error if there is
+    /// no such directory
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>>;
+
     async fn read(&self, location: &str) -> Result<Vec<u8>> {
         let mut input_stream = self.get_input_stream(location, None)?;
         let mut buffer = vec![];
@@ -22,6 +22,7 @@ use crate::DalContext;
 use crate::DataAccessor;
 use crate::InputStream;
 use crate::InputStreamInterceptor;
+use crate::ObjectMeta;
 
 /// A interceptor for data accessor.
 pub struct DataAccessorInterceptor {
@@ -71,4 +72,12 @@ impl DataAccessor for DataAccessorInterceptor {
             .await
             .map(|_| self.ctx.inc_write_bytes(stream_len as usize))
     }
+
+    async fn remove(&self, path: &str) -> common_exception::Result<()> {
+        self.inner.remove(path).await
+    }
+
+    async fn list(&self, prefix: &str) -> common_exception::Result<Vec<ObjectMeta>> {
+        self.inner.list(prefix).await
+    }
 }
@@ -50,3 +50,45 @@ async fn test_da_local_normal() -> common_exception::Result<()> {
     let read_fut = local_read(1000);
     read_fut.await
 }
+
+#[tokio::test]
+async fn test_da_local_remove() -> common_exception::Result<()> {
+    let tmp_root_dir = TempDir::new().unwrap();
+    let root_path = tmp_root_dir.path().to_str().unwrap();
+    let local_da = Local::new(root_path);
+
+    let file = "test_remove";
+    local_da.put(file, vec![1, 2, 3]).await?;
+    assert_eq!(vec![1, 2, 3], local_da.read(file).await?);
+
+    local_da.remove(file).await?;
+    assert!(local_da.read(file).await.is_err());
+
+    // removing a nonexistent object is ok
+    local_da.remove(file).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_da_local_list() -> common_exception::Result<()> {
+    let tmp_root_dir = TempDir::new().unwrap();
+    let root_path = tmp_root_dir.path().to_str().unwrap();
+    let local_da = Local::new(root_path);
+
+    local_da.put("t/_b/1", vec![1]).await?;
+    local_da.put("t/_sg/2", vec![2]).await?;
+    local_da.put("u/_b/3", vec![3]).await?;
+
+    let mut paths = local_da
+        .list("t")
+        .await?
+        .into_iter()
+        .map(|object| object.path)
+        .collect::<Vec<_>>();
+    paths.sort();
+    assert_eq!(paths, vec!["t/_b/1".to_string(), "t/_sg/2".to_string()]);
+
+    // listing a nonexistent directory is ok
+    assert!(local_da.list("v").await?.is_empty());
+    Ok(())
+}
@@ -24,6 +24,7 @@ use common_datavalues::series::IntoSeries;
 use common_datavalues::series::Series;
 use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
 use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -146,7 +147,19 @@ impl TryFrom<DataBlock> for RecordBatch {
         let arrays = v
             .columns()
             .iter()
-            .map(|c| c.to_array().map(|series| series.get_array_ref()))
+            .zip(v.schema().fields().iter())
+            .map(|(c, f)| {
+                let series = c.to_array()?;
+                match f.data_type() {
+                    // Decimal arrays share one physical type, label them with the precision
+                    // and scale of the field.
+                    DataType::Decimal128(_, _) => {
+                        let array = series.decimal128()?.inner().clone();
+                        Ok(Arc::new(array.to(f.data_type().to_arrow())) as ArrayRef)
+                    }
+                    _ => Ok(series.get_array_ref()),
+                }
+            })
             .collect::<Result<Vec<_>>>()?;
 
         Ok(RecordBatch::try_new(Arc::new(v.schema.to_arrow()), arrays)?)
@@ -14,6 +14,7 @@
 
 use comfy_table::Cell;
 use comfy_table::Table;
+use common_datavalues::DataType;
 use common_exception::Result;
 
 use crate::DataBlock;
@@ -95,7 +96,13 @@ fn create_table(results: &[DataBlock]) -> Result<Table> {
             let mut cells = Vec::new();
             for col in 0..batch.num_columns() {
                 let series = batch.column(col).to_array()?;
-                let str = format!("{}", series.try_get(row)?);
+                let value = series.try_get(row)?;
+                let str = match schema.field(col).data_type() {
+                    data_type @ (DataType::Decimal128(_, _) | DataType::Decimal256(_, _)) => {
+                        data_type.create_serializer().serialize_value(&value)?
+                    }
+                    _ => format!("{}", value),
+                };
                 cells.push(Cell::new(&str));
             }
             table.add_row(cells);
@@ -39,6 +39,7 @@ impl NumComp for u8 {}
 impl NumComp for u16 {}
 impl NumComp for u32 {}
 impl NumComp for u64 {}
+impl NumComp for i128 {}
 
 pub trait ArrayCompare<Rhs>: Debug {
     /// Check for equality.
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use common_arrow::arrow::array::*;
+use common_arrow::arrow::bitmap::Bitmap;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
@@ -48,6 +49,20 @@ impl DFListArray {
         )
     }
 
+    /// Build a list array from the flattened items of all rows,
+    /// the items of the row `i` are in `offsets[i]..offsets[i + 1]`.
+    pub fn from_parts(values: &Series, offsets: Vec<i64>, validity: Option<Bitmap>) -> Self {
+        let item = DataField::new("item", values.data_type().clone(), true);
+        let offsets = AlignedVec::<i64>::from_trusted_len_iter(offsets.into_iter());
+        let array = LargeListArray::from_data(
+            DataType::List(Box::new(item)).to_arrow(),
+            offsets.into(),
+            values.get_array_ref(),
+            validity,
+        );
+        Self::new(array)
+    }
+
     pub fn data_type(&self) -> &DataType {
         &self.data_type
     }
@@ -94,6 +109,22 @@ impl DFListArray {
         self.array.is_null(i)
     }
 
+    #[inline]
+    pub fn validity(&self) -> Option<&Bitmap> {
+        self.array.validity()
+    }
+
+    /// The items of the row `i` are in `offsets[i]..offsets[i + 1]` of the values.
+    #[inline]
+    pub fn offsets(&self) -> &[i64] {
+        self.array.offsets().as_slice()
+    }
+
+    /// The flattened items of all rows.
+    pub fn values(&self) -> Series {
+        self.array.values().clone().into_series()
+    }
+
     /// Take a view of top n elements
     pub fn limit(&self, num_elements: usize) -> Self {
         self.slice(0, num_elements)
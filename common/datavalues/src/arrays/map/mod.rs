@@ -0,0 +1,184 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_arrow::arrow::array::*;
+use common_arrow::arrow::bitmap::Bitmap;
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_arrow::arrow::datatypes::Field as ArrowField;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::prelude::*;
+
+/// A Map(K, V) array, which is stored as its physical LargeListArray of Struct(key, value) entries.
+#[derive(Debug, Clone)]
+pub struct DFMapArray {
+    pub(crate) array: LargeListArray,
+    pub data_type: DataType,
+}
+
+impl DFMapArray {
+    /// View a series of the logical type Map(K, V) as a map array.
+    pub fn try_create(series: &Series, data_type: &DataType) -> Result<Self> {
+        let array = series.get_array_ref();
+        match (data_type, array.as_any().downcast_ref::<LargeListArray>()) {
+            (DataType::Map(_, _), Some(array)) => Ok(Self {
+                array: array.clone(),
+                data_type: data_type.clone(),
+            }),
+            _ => Err(ErrorCode::IllegalDataType(format!(
+                "cannot view array {:?} as {:?}",
+                series.data_type(),
+                data_type
+            ))),
+        }
+    }
+
+    /// Build a map array from the flattened keys and values of all rows,
+    /// the entries of the row `i` are in `offsets[i]..offsets[i + 1]`.
+    pub fn from_parts(
+        data_type: &DataType,
+        keys: &Series,
+        values: &Series,
+        offsets: Vec<i64>,
+        validity: Option<Bitmap>,
+    ) -> Result<Self> {
+        let (key_type, value_type) = match data_type {
+            DataType::Map(key_type, value_type) => (key_type, value_type),
+            other => {
+                return Err(ErrorCode::IllegalDataType(format!(
+                    "Expected map type, but got {:?}",
+                    other
+                )))
+            }
+        };
+        let entries_type = map_entries_type(key_type, value_type);
+        let struct_type = match &entries_type {
+            DataType::List(field) => field.data_type().to_arrow(),
+            _ => unreachable!(),
+        };
+
+        let entries = StructArray::from_data(
+            struct_type,
+            vec![keys.get_array_ref(), values.get_array_ref()],
+            None,
+        );
+        let offsets = AlignedVec::<i64>::from_trusted_len_iter(offsets.into_iter());
+        let array = LargeListArray::from_data(
+            entries_type.to_arrow(),
+            offsets.into(),
+            Arc::new(entries),
+            validity,
+        );
+
+        Ok(Self {
+            array,
+            data_type: data_type.clone(),
+        })
+    }
+
+    pub fn data_type(&self) -> &DataType {
+        &self.data_type
+    }
+
+    pub fn inner(&self) -> &LargeListArray {
+        &self.array
+    }
+
+    pub fn key_type(&self) -> &DataType {
+        match self.data_type() {
+            DataType::Map(key_type, _) => key_type,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn value_type(&self) -> &DataType {
+        match self.data_type() {
+            DataType::Map(_, value_type) => value_type,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub fn is_null(&self, i: usize) -> bool {
+        self.array.is_null(i)
+    }
+
+    #[inline]
+    pub fn validity(&self) -> Option<&Bitmap> {
+        self.array.validity()
+    }
+
+    /// The entries of the row `i` are in `offsets[i]..offsets[i + 1]` of the keys and values.
+    #[inline]
+    pub fn offsets(&self) -> &[i64] {
+        self.array.offsets().as_slice()
+    }
+
+    /// The flattened keys of all rows.
+    pub fn keys(&self) -> Series {
+        self.entries().values()[0].clone().into_series()
+    }
+
+    /// The flattened values of all rows.
+    pub fn values(&self) -> Series {
+        self.entries().values()[1].clone().into_series()
+    }
+
+    /// The keys of each row as a list array.
+    pub fn keys_list(&self) -> DFListArray {
+        self.entries_list(0)
+    }
+
+    /// The values of each row as a list array.
+    pub fn values_list(&self) -> DFListArray {
+        self.entries_list(1)
+    }
+
+    /// The physical series of the map, a list of Struct(key, value) entries.
+    pub fn into_series(self) -> Series {
+        DFListArray::new(self.array).into_series()
+    }
+
+    fn entries_list(&self, index: usize) -> DFListArray {
+        let values = self.entries().values()[index].clone();
+        let item = ArrowField::new("item", values.data_type().clone(), true);
+        let array = LargeListArray::from_data(
+            ArrowDataType::LargeList(Box::new(item)),
+            self.array.offsets().clone(),
+            values,
+            self.validity().cloned(),
+        );
+        DFListArray::new(array)
+    }
+
+    fn entries(&self) -> &StructArray {
+        self.array
+            .values()
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .unwrap()
+    }
+}
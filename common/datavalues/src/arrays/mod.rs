@@ -22,6 +22,7 @@ mod upstream_traits;
 
 mod boolean;
 mod list;
+mod map;
 mod null;
 mod primitive;
 mod string;
@@ -32,6 +33,7 @@ pub use boolean::*;
 pub use builder::*;
 pub use comparison::*;
 pub use list::*;
+pub use map::*;
 pub use null::*;
 pub use ops::*;
 pub use primitive::*;
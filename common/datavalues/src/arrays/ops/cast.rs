@@ -101,6 +101,9 @@ impl ArrayCast for DFNullArray {
             DataType::Int64 => Ok(DFInt64Array::full_null(self.len()).into_series()),
             DataType::Float32 => Ok(DFFloat32Array::full_null(self.len()).into_series()),
             DataType::Float64 => Ok(DFFloat64Array::full_null(self.len()).into_series()),
+            DataType::Decimal128(_, _) => {
+                Ok(DFDecimal128Array::full_null(self.len()).into_series())
+            }
             DataType::String => Ok(DFStringArray::full_null(self.len()).into_series()),
             DataType::List(_) => Ok(DFListArray::full_null(self.len()).into_series()),
 
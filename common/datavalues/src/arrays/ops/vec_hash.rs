@@ -56,6 +56,16 @@ impl VecHash for DFBooleanArray {
     }
 }
 
+impl VecHash for DFDecimal128Array {
+    fn vec_hash(&self, hasher: DFHasher) -> Result<DFUInt64Array> {
+        Ok(self.apply_cast_numeric(|v| {
+            let mut h = hasher.clone_initial();
+            v.hash(&mut h);
+            h.finish()
+        }))
+    }
+}
+
 impl VecHash for DFFloat32Array {
     fn vec_hash(&self, hasher: DFHasher) -> Result<DFUInt64Array> {
         Ok(self.apply_cast_numeric(|v| {
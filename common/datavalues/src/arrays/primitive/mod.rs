@@ -57,6 +57,12 @@ impl<T: DFPrimitiveType> DFPrimitiveArray<T> {
     pub fn new(array: PrimitiveArray<T>) -> Self {
         let data_type: DataType = array.data_type().into();
         let data_type: DataType = data_type_physical(data_type);
+        // Decimals of any precision and scale share one physical arrow type,
+        // the logical precision and scale are kept by the DataField.
+        let array = match array.data_type() {
+            ArrowDataType::Decimal(_, _) => array.to(data_type.to_arrow()),
+            _ => array,
+        };
         Self { array, data_type }
     }
 
@@ -119,6 +125,14 @@ impl<T: DFPrimitiveType> DFPrimitiveArray<T> {
 
                     Self::from_arrow_array(&array)
                 }
+                ArrowDataType::Decimal(_, _) => {
+                    let array = array
+                        .as_any()
+                        .downcast_ref::<PrimitiveArray<T>>()
+                        .expect("primitive cast should be ok");
+
+                    Self::new(array.clone())
+                }
                 _ => unreachable!(),
             }
         } else {
@@ -155,6 +169,7 @@ impl<T: DFPrimitiveType> DFPrimitiveArray<T> {
             DataType::Int64 => DataValue::Int64(v.to_i64()),
             DataType::Float32 => DataValue::Float32(v.to_f32()),
             DataType::Float64 => DataValue::Float64(v.to_f64()),
+            DataType::Decimal128(_, _) => DataValue::Decimal128(v.to_i128()),
             _ => unreachable!(),
         };
 
@@ -258,6 +273,8 @@ pub type DFInt64Array = DFPrimitiveArray<i64>;
 pub type DFFloat32Array = DFPrimitiveArray<f32>;
 pub type DFFloat64Array = DFPrimitiveArray<f64>;
 
+pub type DFDecimal128Array = DFPrimitiveArray<i128>;
+
 /// # Safety
 /// Note this doesn't do any bound checking, for performance reason.
 /// Take kernel for single chunk without nulls and an iterator as index.
@@ -18,6 +18,8 @@ use common_arrow::arrow::datatypes::Field as ArrowField;
 use common_macros::MallocSizeOf;
 
 use crate::DataType;
+use crate::DATETIME64_DEFAULT_PRECISION;
+use crate::DECIMAL256_MAX_PRECISION;
 
 #[derive(
     serde::Serialize,
@@ -78,11 +80,16 @@ impl DataField {
             DataType::Date16 => Some("Date16"),
             DataType::Date32 => Some("Date32"),
             DataType::DateTime32(_) => Some("DateTime32"),
+            DataType::DateTime64(_, _) => Some("DateTime64"),
+            DataType::Variant => Some("Variant"),
+            DataType::Map(_, _) => Some("Map"),
+            DataType::Decimal256(_, _) => Some("Decimal256"),
             _ => None,
         };
 
         let custom_metadata = match self.data_type() {
-            DataType::DateTime32(tz) => tz.clone(),
+            DataType::DateTime32(tz) | DataType::DateTime64(_, tz) => tz.clone(),
+            DataType::Decimal256(_, scale) => Some(scale.to_string()),
             _ => None,
         };
 
@@ -97,6 +104,15 @@ impl DataField {
             if let Some(m) = custom_metadata {
                 mp.insert("ARROW:extension:databend_metadata".to_string(), m);
             }
+
+            let custom_precision = match self.data_type() {
+                DataType::DateTime64(precision, _) => Some(precision.to_string()),
+                DataType::Decimal256(precision, _) => Some(precision.to_string()),
+                _ => None,
+            };
+            if let Some(precision) = custom_precision {
+                mp.insert("ARROW:extension:databend_precision".to_string(), precision);
+            }
             f = f.with_metadata(mp);
         }
 
@@ -114,6 +130,34 @@ impl From<&ArrowField> for DataField {
                     "Date16" => dt = DataType::Date16,
                     "Date32" => dt = DataType::Date32,
                     "DateTime32" => dt = DataType::DateTime32(metatada.cloned()),
+                    "DateTime64" => {
+                        let precision = m
+                            .get("ARROW:extension:databend_precision")
+                            .and_then(|p| p.parse::<u32>().ok())
+                            .unwrap_or(DATETIME64_DEFAULT_PRECISION);
+                        dt = DataType::DateTime64(precision, metatada.cloned())
+                    }
+                    "Variant" => dt = DataType::Variant,
+                    "Decimal256" => {
+                        let precision = m
+                            .get("ARROW:extension:databend_precision")
+                            .and_then(|p| p.parse::<usize>().ok())
+                            .unwrap_or(DECIMAL256_MAX_PRECISION);
+                        let scale = metatada.and_then(|s| s.parse::<usize>().ok()).unwrap_or(0);
+                        dt = DataType::Decimal256(precision, scale)
+                    }
+                    "Map" => {
+                        if let DataType::List(entries) = &dt {
+                            if let DataType::Struct(fields) = entries.data_type() {
+                                if let [key, value] = fields.as_slice() {
+                                    dt = DataType::Map(
+                                        Box::new(key.data_type().clone()),
+                                        Box::new(value.data_type().clone()),
+                                    )
+                                }
+                            }
+                        }
+                    }
                     _ => {}
                 }
             }
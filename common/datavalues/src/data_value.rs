@@ -52,6 +52,8 @@ pub enum DataValue {
     UInt64(Option<u64>),
     Float32(Option<f32>),
     Float64(Option<f64>),
+    /// The unscaled value of a decimal, precision and scale live in the DataType
+    Decimal128(Option<i128>),
     String(Option<Vec<u8>>),
 
     // Container struct.
@@ -76,6 +78,7 @@ impl DataValue {
                 | DataValue::UInt64(None)
                 | DataValue::Float32(None)
                 | DataValue::Float64(None)
+                | DataValue::Decimal128(None)
                 | DataValue::String(None)
                 | DataValue::Null
                 | DataValue::List(None, _)
@@ -96,6 +99,7 @@ impl DataValue {
             DataValue::UInt64(_) => DataType::UInt64,
             DataValue::Float32(_) => DataType::Float32,
             DataValue::Float64(_) => DataType::Float64,
+            DataValue::Decimal128(_) => DataType::Decimal128(38, 0),
             DataValue::List(_, data_type) => {
                 DataType::List(Box::new(DataField::new("item", data_type.clone(), true)))
             }
@@ -170,6 +174,9 @@ impl DataValue {
             DataValue::UInt64(values) => Ok(build_constant_series! {DFUInt64Array, values, size}),
             DataValue::Float32(values) => Ok(build_constant_series! {DFFloat32Array, values, size}),
             DataValue::Float64(values) => Ok(build_constant_series! {DFFloat64Array, values, size}),
+            DataValue::Decimal128(values) => {
+                Ok(build_constant_series! {DFDecimal128Array, values, size})
+            }
             DataValue::String(values) => match values {
                 None => Ok(DFStringArray::full_null(size).into_series()),
                 Some(v) => Ok(DFStringArray::full(v.deref(), size).into_series()),
@@ -297,6 +304,7 @@ impl DataValue {
             DataValue::UInt64(v) => Ok(v.map_or(false, |v| v != 0)),
             DataValue::Float32(v) => Ok(v.map_or(false, |v| v != 0f32)),
             DataValue::Float64(v) => Ok(v.map_or(false, |v| v != 0f64)),
+            DataValue::Decimal128(v) => Ok(v.map_or(false, |v| v != 0)),
             other => Result::Err(ErrorCode::BadDataValueType(format!(
                 "Unexpected type:{:?} to get boolean",
                 other.data_type()
@@ -321,6 +329,7 @@ typed_cast_from_data_value_to_std!(UInt32, u32);
 typed_cast_from_data_value_to_std!(UInt64, u64);
 typed_cast_from_data_value_to_std!(Float32, f32);
 typed_cast_from_data_value_to_std!(Float64, f64);
+typed_cast_from_data_value_to_std!(Decimal128, i128);
 typed_cast_from_data_value_to_std!(Boolean, bool);
 
 impl DFTryFrom<DataValue> for Vec<u8> {
@@ -346,6 +355,7 @@ std_to_data_value!(UInt32, u32);
 std_to_data_value!(UInt64, u64);
 std_to_data_value!(Float32, f32);
 std_to_data_value!(Float64, f64);
+std_to_data_value!(Decimal128, i128);
 std_to_data_value!(Boolean, bool);
 
 impl From<&[u8]> for DataValue {
@@ -388,12 +398,19 @@ impl From<&DataType> for DataValue {
             DataType::UInt64 => DataValue::UInt64(None),
             DataType::Float32 => DataValue::Float32(None),
             DataType::Float64 => DataValue::Float64(None),
+            DataType::Decimal128(_, _) => DataValue::Decimal128(None),
+            DataType::Decimal256(_, _) => DataValue::String(None),
             DataType::Date16 => DataValue::UInt16(None),
             DataType::Date32 => DataValue::Int32(None),
             DataType::DateTime32(_) => DataValue::UInt32(None),
+            DataType::DateTime64(_, _) => DataValue::Int64(None),
             DataType::List(f) => DataValue::List(None, f.data_type().clone()),
             DataType::Struct(_) => DataValue::Struct(vec![]),
-            DataType::String => DataValue::String(None),
+            DataType::Map(key_type, value_type) => {
+                let entries_type = map_entries_type(key_type, value_type);
+                DataValue::from(&entries_type)
+            }
+            DataType::String | DataType::Variant => DataValue::String(None),
             DataType::Interval(_) => DataValue::Int64(None),
         }
     }
@@ -423,6 +440,7 @@ impl fmt::Display for DataValue {
             DataValue::UInt16(v) => format_data_value_with_option!(f, v),
             DataValue::UInt32(v) => format_data_value_with_option!(f, v),
             DataValue::UInt64(v) => format_data_value_with_option!(f, v),
+            DataValue::Decimal128(v) => format_data_value_with_option!(f, v),
             DataValue::String(None) => write!(f, "NULL"),
             DataValue::String(Some(v)) => match std::str::from_utf8(v) {
                 Ok(v) => write!(f, "{}", v),
@@ -467,6 +485,7 @@ impl fmt::Debug for DataValue {
             DataValue::UInt64(v) => format_data_value_with_option!(f, v),
             DataValue::Float32(v) => format_data_value_with_option!(f, v),
             DataValue::Float64(v) => format_data_value_with_option!(f, v),
+            DataValue::Decimal128(v) => format_data_value_with_option!(f, v),
             DataValue::String(None) => write!(f, "{}", self),
             DataValue::String(Some(_)) => write!(f, "{}", self),
             DataValue::List(_, _) => write!(f, "[{}]", self),
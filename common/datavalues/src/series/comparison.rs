@@ -43,6 +43,10 @@ macro_rules! impl_compare {
             DataType::Int64 => $self.i64().unwrap().$method($rhs.i64().unwrap()),
             DataType::Float32 => $self.f32().unwrap().$method($rhs.f32().unwrap()),
             DataType::Float64 => $self.f64().unwrap().$method($rhs.f64().unwrap()),
+            DataType::Decimal128(_, _) => $self
+                .decimal128()
+                .unwrap()
+                .$method($rhs.decimal128().unwrap()),
             DataType::Date16 => $self.u16().unwrap().$method($rhs.u16().unwrap()),
             DataType::Date32 => $self.i32().unwrap().$method($rhs.i32().unwrap()),
             _ => unimplemented!(),
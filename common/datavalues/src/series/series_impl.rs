@@ -127,6 +127,14 @@ pub trait SeriesTrait: Send + Sync + fmt::Debug {
         )))
     }
 
+    /// Unpack to DFArray of data_type decimal128
+    fn decimal128(&self) -> Result<&DFDecimal128Array> {
+        Err(ErrorCode::IllegalDataType(format!(
+            "{:?} != decimal128",
+            self.data_type()
+        )))
+    }
+
     /// Unpack to DFArray of data_type u8
     fn u8(&self) -> Result<&DFUInt8Array> {
         Err(ErrorCode::IllegalDataType(format!(
@@ -255,6 +263,7 @@ impl_from!([i8], DFInt8Array, new_from_slice);
 impl_from!([i16], DFInt16Array, new_from_slice);
 impl_from!([i32], DFInt32Array, new_from_slice);
 impl_from!([i64], DFInt64Array, new_from_slice);
+impl_from!([i128], DFDecimal128Array, new_from_slice);
 impl_from!([f32], DFFloat32Array, new_from_slice);
 impl_from!([f64], DFFloat64Array, new_from_slice);
 impl_from!([Vec<u8>], DFStringArray, new_from_slice);
@@ -268,6 +277,7 @@ impl_from!([Option<i8>], DFInt8Array, new_from_opt_slice);
 impl_from!([Option<i16>], DFInt16Array, new_from_opt_slice);
 impl_from!([Option<i32>], DFInt32Array, new_from_opt_slice);
 impl_from!([Option<i64>], DFInt64Array, new_from_opt_slice);
+impl_from!([Option<i128>], DFDecimal128Array, new_from_opt_slice);
 impl_from!([Option<f32>], DFFloat32Array, new_from_opt_slice);
 impl_from!([Option<f64>], DFFloat64Array, new_from_opt_slice);
 impl_from!([Option<Vec<u8>>], DFStringArray, new_from_opt_slice);
@@ -334,6 +344,7 @@ impl IntoSeries for ArrayRef {
 
             Float32 => DFFloat32Array::from_arrow_array(self.as_ref()).into_series(),
             Float64 => DFFloat64Array::from_arrow_array(self.as_ref()).into_series(),
+            Decimal128 => DFDecimal128Array::from_arrow_array(self.as_ref()).into_series(),
 
             List(_) => DFListArray::from_arrow_array(self.as_ref()).into_series(),
             Struct(_) => DFStructArray::from_arrow_array(self.as_ref()).into_series(),
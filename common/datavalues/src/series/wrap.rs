@@ -233,6 +233,17 @@ macro_rules! impl_dyn_array {
                 }
             }
 
+            fn decimal128(&self) -> Result<&DFDecimal128Array> {
+                if matches!(self.0.data_type(), &DataType::Decimal128(_, _)) {
+                    unsafe { Ok(&*(self as *const dyn SeriesTrait as *const DFDecimal128Array)) }
+                } else {
+                    Err(ErrorCode::IllegalDataType(format!(
+                        "cannot unpack Series of type {:?} into decimal128",
+                        self.data_type(),
+                    )))
+                }
+            }
+
             fn u8(&self) -> Result<&DFUInt8Array> {
                 if matches!(self.0.data_type(), &DataType::UInt8) {
                     unsafe { Ok(&*(self as *const dyn SeriesTrait as *const DFUInt8Array)) }
@@ -342,6 +353,7 @@ macro_rules! impl_dyn_array {
 impl_dyn_array!(DFNullArray);
 impl_dyn_array!(DFFloat32Array);
 impl_dyn_array!(DFFloat64Array);
+impl_dyn_array!(DFDecimal128Array);
 impl_dyn_array!(DFUInt8Array);
 impl_dyn_array!(DFUInt16Array);
 impl_dyn_array!(DFUInt32Array);
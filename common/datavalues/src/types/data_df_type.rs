@@ -48,6 +48,13 @@ impl_df_datatype!(f32, Float32);
 impl_df_datatype!(f64, Float64);
 impl_df_datatype!(bool, Boolean);
 
+impl DFDataType for i128 {
+    fn data_type() -> DataType {
+        // precision and scale live in the DataField, the physical type carries the widest one.
+        DataType::Decimal128(38, 0)
+    }
+}
+
 #[derive(Debug)]
 pub struct Null;
 impl_df_datatype!(Null, Null);
@@ -114,6 +121,7 @@ impl_primitive!(i32, i64, true, false, 4);
 impl_primitive!(i64, i64, true, false, 8);
 impl_primitive!(f32, f64, true, true, 4);
 impl_primitive!(f64, f64, true, true, 8);
+impl_primitive!(i128, i128, true, false, 16);
 
 pub trait DFIntegerType: DFPrimitiveType {}
 
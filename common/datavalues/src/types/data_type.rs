@@ -21,6 +21,7 @@ use common_macros::MallocSizeOf;
 
 use crate::DataField;
 use crate::PhysicalDataType;
+use crate::DATETIME64_DEFAULT_PRECISION;
 
 #[derive(
     serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, MallocSizeOf,
@@ -38,6 +39,13 @@ pub enum DataType {
     Int64,
     Float32,
     Float64,
+    /// A fixed-point decimal stored as a 128-bit integer,
+    /// the arguments are precision (up to 38 digits) and scale, its physical type is Decimal128
+    Decimal128(usize, usize),
+    /// A fixed-point decimal stored as a 256-bit integer, the arguments are precision
+    /// (39 up to 76 digits) and scale, its physical type is String holding the 32 bytes
+    /// of I256::to_ordered_bytes
+    Decimal256(usize, usize),
     /// A 32-bit date representing the elapsed time since UNIX epoch (1970-01-01)
     /// in days (16 bits), it's physical type is UInt16
     Date16,
@@ -50,11 +58,22 @@ pub enum DataType {
     /// Option<String> indicates the timezone, if it's None, it's UTC
     DateTime32(Option<String>),
 
+    /// A 64-bit datetime representing the elapsed time since UNIX epoch (1970-01-01)
+    /// in 10^-precision seconds (precision up to 9), it's physical type is Int64
+    /// Option<String> indicates the timezone, if it's None, it's UTC
+    DateTime64(u32, Option<String>),
+
     Interval(IntervalUnit),
 
     List(Box<DataField>),
     Struct(Vec<DataField>),
+    /// A map of keys to values, the arguments are the key type and the value type,
+    /// its physical type is a List of Struct(key, value) entries, like the arrow Map layout
+    Map(Box<DataType>, Box<DataType>),
     String,
+
+    /// A semi-structured JSON value, its physical type is String holding the JSON text
+    Variant,
 }
 
 #[derive(
@@ -129,7 +148,10 @@ impl DataType {
     pub fn is_date_or_date_time(&self) -> bool {
         matches!(
             self,
-            DataType::Date16 | DataType::Date32 | DataType::DateTime32(_)
+            DataType::Date16
+                | DataType::Date32
+                | DataType::DateTime32(_)
+                | DataType::DateTime64(_, _)
         )
     }
 
@@ -157,6 +179,14 @@ impl DataType {
             )
     }
 
+    #[inline]
+    pub fn is_decimal(&self) -> bool {
+        matches!(
+            self,
+            DataType::Decimal128(_, _) | DataType::Decimal256(_, _)
+        )
+    }
+
     #[inline]
     pub fn is_interval(&self) -> bool {
         matches!(self, DataType::Interval(_))
@@ -191,21 +221,48 @@ impl DataType {
             Int64 => ArrowDataType::Int64,
             Float32 => ArrowDataType::Float32,
             Float64 => ArrowDataType::Float64,
+            Decimal128(precision, scale) => ArrowDataType::Decimal(*precision, *scale),
             Date16 => ArrowDataType::UInt16,
             Date32 => ArrowDataType::Int32,
             // we don't use DataType::Extension because extension types are not supported in parquet
             DateTime32(_) => ArrowDataType::UInt32,
+            DateTime64(_, _) => ArrowDataType::Int64,
             List(dt) => ArrowDataType::LargeList(Box::new(dt.to_arrow())),
             Struct(fs) => {
                 let arrows_fields = fs.iter().map(|f| f.to_arrow()).collect();
                 ArrowDataType::Struct(arrows_fields)
             }
-            String => ArrowDataType::LargeBinary,
+            Map(key_type, value_type) => map_entries_type(key_type, value_type).to_arrow(),
+            String | Variant | Decimal256(_, _) => ArrowDataType::LargeBinary,
             Interval(_) => ArrowDataType::Int64,
         }
     }
 }
 
+/// The physical type of Map(K, V), a List of non-null Struct(key, value) entries.
+pub fn map_entries_type(key_type: &DataType, value_type: &DataType) -> DataType {
+    let entries = DataType::Struct(vec![
+        DataField::new("key", key_type.clone(), false),
+        DataField::new("value", value_type.clone(), true),
+    ]);
+    DataType::List(Box::new(DataField::new("entries", entries, false)))
+}
+
+/// Check the key type of a Map(K, V) declaration, the keys must be comparable scalars.
+pub fn check_map_type(key_type: DataType, value_type: DataType) -> Result<DataType> {
+    match key_type {
+        DataType::Boolean | DataType::String => {}
+        ref t if t.is_numeric() || t.is_date_or_date_time() || t.is_decimal() => {}
+        other => {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "Map key type must be a number, string, boolean or date, but got {:?}",
+                other
+            )))
+        }
+    }
+    Ok(DataType::Map(Box::new(key_type), Box::new(value_type)))
+}
+
 impl PartialEq<ArrowDataType> for DataType {
     fn eq(&self, other: &ArrowDataType) -> bool {
         let arrow_type = self.to_arrow();
@@ -228,6 +285,7 @@ impl From<&ArrowDataType> for DataType {
             ArrowDataType::Boolean => DataType::Boolean,
             ArrowDataType::Float32 => DataType::Float32,
             ArrowDataType::Float64 => DataType::Float64,
+            ArrowDataType::Decimal(precision, scale) => DataType::Decimal128(*precision, *scale),
             ArrowDataType::List(f) | ArrowDataType::LargeList(f) => {
                 let f: DataField = (f.as_ref()).into();
                 DataType::List(Box::new(f))
@@ -243,6 +301,8 @@ impl From<&ArrowDataType> for DataType {
                 "Date16" => DataType::Date16,
                 "Date32" => DataType::Date32,
                 "DateTime32" => DataType::DateTime32(extra.clone()),
+                "DateTime64" => DataType::DateTime64(DATETIME64_DEFAULT_PRECISION, extra.clone()),
+                "Variant" => DataType::Variant,
                 _ => unimplemented!("data_type: {}", dt),
             },
 
@@ -282,6 +342,8 @@ impl fmt::Debug for DataType {
             Self::Int64 => write!(f, "Int64"),
             Self::Float32 => write!(f, "Float32"),
             Self::Float64 => write!(f, "Float64"),
+            Self::Decimal128(precision, scale) => write!(f, "Decimal128({}, {})", precision, scale),
+            Self::Decimal256(precision, scale) => write!(f, "Decimal256({}, {})", precision, scale),
             Self::Date16 => write!(f, "Date16"),
             Self::Date32 => write!(f, "Date32"),
             Self::DateTime32(arg0) => {
@@ -291,9 +353,18 @@ impl fmt::Debug for DataType {
                     write!(f, "DateTime32")
                 }
             }
+            Self::DateTime64(precision, tz) => {
+                if let Some(tz) = tz {
+                    write!(f, "DateTime64({}, {:?})", precision, tz)
+                } else {
+                    write!(f, "DateTime64({})", precision)
+                }
+            }
             Self::List(arg0) => f.debug_tuple("List").field(arg0).finish(),
             Self::Struct(arg0) => f.debug_tuple("Struct").field(arg0).finish(),
+            Self::Map(key_type, value_type) => write!(f, "Map({:?}, {:?})", key_type, value_type),
             Self::String => write!(f, "String"),
+            Self::Variant => write!(f, "Variant"),
             Self::Interval(unit) => write!(f, "Interval({})", unit.to_string()),
         }
     }
@@ -18,6 +18,9 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::prelude::DataType;
+use crate::types::decimal::decimal_precision_scale;
+use crate::types::decimal::DECIMAL128_MAX_PRECISION;
+use crate::types::decimal::DECIMAL256_MAX_PRECISION;
 use crate::DataField;
 use crate::DataValueArithmeticOperator;
 
@@ -157,6 +160,10 @@ pub fn numerical_arithmetic_coercion(
     lhs_type: &DataType,
     rhs_type: &DataType,
 ) -> Result<DataType> {
+    if lhs_type.is_decimal() || rhs_type.is_decimal() {
+        return decimal_arithmetic_coercion(op, lhs_type, rhs_type);
+    }
+
     // error on any non-numeric type
     if !lhs_type.is_numeric() || !rhs_type.is_numeric() {
         return Result::Err(ErrorCode::BadDataValueType(format!(
@@ -197,6 +204,119 @@ pub fn numerical_arithmetic_coercion(
     }
 }
 
+/// Coercion rule for arithmetic on decimals, integers are treated as decimals of scale 0.
+/// Like ClickHouse, the result keeps every digit of plus, minus and multiply,
+/// division keeps the scale of the dividend and floats turn the result into Float64.
+/// The result is a Decimal256 if one of the sides is, otherwise a Decimal128.
+pub fn decimal_arithmetic_coercion(
+    op: &DataValueArithmeticOperator,
+    lhs_type: &DataType,
+    rhs_type: &DataType,
+) -> Result<DataType> {
+    if (lhs_type.is_floating() && rhs_type.is_decimal())
+        || (lhs_type.is_decimal() && rhs_type.is_floating())
+    {
+        return Ok(DataType::Float64);
+    }
+
+    let (lhs_precision, lhs_scale, rhs_precision, rhs_scale) = match (
+        decimal_precision_scale(lhs_type),
+        decimal_precision_scale(rhs_type),
+    ) {
+        (Some((p1, s1)), Some((p2, s2))) => (p1, s1, p2, s2),
+        _ => {
+            return Result::Err(ErrorCode::BadDataValueType(format!(
+                "DataValue Error: Unsupported ({:?}) {} ({:?})",
+                lhs_type, op, rhs_type
+            )))
+        }
+    };
+
+    let max_precision = decimal_max_precision(lhs_type, rhs_type);
+    let max_integer_digits = cmp::max(lhs_precision - lhs_scale, rhs_precision - rhs_scale);
+    let (precision, scale) = match op {
+        DataValueArithmeticOperator::Plus | DataValueArithmeticOperator::Minus => {
+            let scale = cmp::max(lhs_scale, rhs_scale);
+            (max_integer_digits + scale + 1, scale)
+        }
+        DataValueArithmeticOperator::Modulo => {
+            let scale = cmp::max(lhs_scale, rhs_scale);
+            (max_integer_digits + scale, scale)
+        }
+        DataValueArithmeticOperator::Mul => (lhs_precision + rhs_precision, lhs_scale + rhs_scale),
+        DataValueArithmeticOperator::Div => {
+            // At least 6 fraction digits, and more for the narrower divisors. If it does not fit,
+            // the integer digits are kept, and the fraction digits are reduced down to 6.
+            let scale = cmp::max(6, lhs_scale + rhs_precision + 1);
+            let integer_digits = lhs_precision - lhs_scale + rhs_scale;
+            match integer_digits + scale <= max_precision {
+                true => (integer_digits + scale, scale),
+                false => {
+                    let scale = cmp::max(6, max_precision.saturating_sub(integer_digits));
+                    (max_precision, scale)
+                }
+            }
+        }
+    };
+
+    if scale > max_precision {
+        return Result::Err(ErrorCode::BadDataValueType(format!(
+            "DataValue Error: The scale of ({:?}) {} ({:?}) exceeds {}",
+            lhs_type, op, rhs_type, max_precision
+        )));
+    }
+
+    Ok(decimal_type(
+        cmp::min(precision, max_precision),
+        scale,
+        max_precision,
+    ))
+}
+
+/// The precision decimal operations on the two types are capped at.
+fn decimal_max_precision(lhs_type: &DataType, rhs_type: &DataType) -> usize {
+    let is_decimal256 = |t: &DataType| matches!(t, DataType::Decimal256(_, _));
+    match is_decimal256(lhs_type) || is_decimal256(rhs_type) {
+        true => DECIMAL256_MAX_PRECISION,
+        false => DECIMAL128_MAX_PRECISION,
+    }
+}
+
+fn decimal_type(precision: usize, scale: usize, max_precision: usize) -> DataType {
+    match max_precision {
+        DECIMAL256_MAX_PRECISION => DataType::Decimal256(precision, scale),
+        _ => DataType::Decimal128(precision, scale),
+    }
+}
+
+/// The decimal type both sides can be casted to without losing digits,
+/// or Float64 if one of them is a float.
+pub fn decimal_common_type(lhs_type: &DataType, rhs_type: &DataType) -> Result<DataType> {
+    if lhs_type.is_floating() || rhs_type.is_floating() {
+        return Ok(DataType::Float64);
+    }
+
+    match (
+        decimal_precision_scale(lhs_type),
+        decimal_precision_scale(rhs_type),
+    ) {
+        (Some((p1, s1)), Some((p2, s2))) => {
+            let scale = cmp::max(s1, s2);
+            let precision = cmp::max(p1 - s1, p2 - s2) + scale;
+            let max_precision = decimal_max_precision(lhs_type, rhs_type);
+            Ok(decimal_type(
+                cmp::min(precision, max_precision),
+                scale,
+                max_precision,
+            ))
+        }
+        _ => Result::Err(ErrorCode::BadDataValueType(format!(
+            "Can't construct decimal type from {} and {}",
+            lhs_type, rhs_type
+        ))),
+    }
+}
+
 #[inline]
 pub fn datetime_arithmetic_coercion(
     op: &DataValueArithmeticOperator,
@@ -225,6 +345,12 @@ pub fn datetime_arithmetic_coercion(
         DataValueArithmeticOperator::Minus => {
             if b.is_numeric() || b.is_interval() {
                 Ok(a)
+            } else if let DataType::DateTime64(precision, _) = a {
+                // DateTime64 minus DateTime64 of the same precision, the difference in ticks
+                match b {
+                    DataType::DateTime64(p, _) if p == precision => Ok(DataType::Int64),
+                    _ => e,
+                }
             } else {
                 // Date minus Date or DateTime minus DateTime
                 Ok(DataType::Int32)
@@ -269,6 +395,18 @@ pub fn numerical_unary_arithmetic_coercion(
     op: &DataValueArithmeticOperator,
     val_type: &DataType,
 ) -> Result<DataType> {
+    if val_type.is_decimal() {
+        return match op {
+            DataValueArithmeticOperator::Plus | DataValueArithmeticOperator::Minus => {
+                Ok(val_type.clone())
+            }
+            other => Result::Err(ErrorCode::UnknownFunction(format!(
+                "Unexpected operator:{:?} to unary function",
+                other
+            ))),
+        };
+    }
+
     // error on any non-numeric type
     if !val_type.is_numeric() {
         return Result::Err(ErrorCode::BadDataValueType(format!(
@@ -308,6 +446,13 @@ pub fn compare_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Result<Data
         return numerical_coercion(lhs_type, rhs_type, true);
     }
 
+    // one of is decimal and other is number or decimal
+    if (lhs_type.is_decimal() && (rhs_type.is_numeric() || rhs_type.is_decimal()))
+        || (rhs_type.is_decimal() && lhs_type.is_numeric())
+    {
+        return decimal_common_type(lhs_type, rhs_type);
+    }
+
     //  one of is null
     {
         if rhs_type == &DataType::Null {
@@ -318,6 +463,18 @@ pub fn compare_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Result<Data
         }
     }
 
+    // one of is Variant, compare its scalar value with the string or number
+    for (variant, other) in [(lhs_type, rhs_type), (rhs_type, lhs_type)] {
+        if variant == &DataType::Variant {
+            if other == &DataType::String {
+                return Ok(DataType::String);
+            }
+            if other.is_numeric() {
+                return Ok(DataType::Float64);
+            }
+        }
+    }
+
     // one of is String and other is number
     if (lhs_type.is_numeric() && rhs_type == &DataType::String)
         || (rhs_type.is_numeric() && lhs_type == &DataType::String)
@@ -325,6 +482,14 @@ pub fn compare_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Result<Data
         return Ok(DataType::Float64);
     }
 
+    // one of is String and other is decimal, compare as the decimal
+    if lhs_type.is_decimal() && rhs_type == &DataType::String {
+        return Ok(lhs_type.clone());
+    }
+    if rhs_type.is_decimal() && lhs_type == &DataType::String {
+        return Ok(rhs_type.clone());
+    }
+
     // one of is datetime and other is number or string
     {
         if (lhs_type.is_numeric() || lhs_type == &DataType::String)
@@ -342,6 +507,18 @@ pub fn compare_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Result<Data
 
     // one of is datetime and other is number or string
     if lhs_type.is_date_or_date_time() || rhs_type.is_date_or_date_time() {
+        // one of is datetime64, compare with the higher precision
+        let precision = [lhs_type, rhs_type]
+            .iter()
+            .filter_map(|t| match t {
+                DataType::DateTime64(precision, _) => Some(*precision),
+                _ => None,
+            })
+            .max();
+        if let Some(precision) = precision {
+            return Ok(DataType::DateTime64(precision, None));
+        }
+
         // one of is datetime
         if matches!(lhs_type, DataType::DateTime32(_))
             || matches!(rhs_type, DataType::DateTime32(_))
@@ -423,6 +600,11 @@ pub fn merge_types(lhs_type: &DataType, rhs_type: &DataType) -> Result<DataType>
             }
             if lhs_type.is_numeric() && rhs_type.is_numeric() {
                 numerical_coercion(lhs_type, rhs_type, false)
+            } else if (lhs_type.is_decimal() || rhs_type.is_decimal())
+                && (lhs_type.is_numeric() || lhs_type.is_decimal())
+                && (rhs_type.is_numeric() || rhs_type.is_decimal())
+            {
+                decimal_common_type(lhs_type, rhs_type)
             } else {
                 Result::Err(ErrorCode::BadDataValueType(format!(
                     "Can't merge types from {} and {}",
@@ -17,11 +17,19 @@ use chrono::DateTime;
 use chrono::Duration;
 use chrono::TimeZone;
 use chrono_tz::Tz;
+use common_exception::ErrorCode;
+use common_exception::Result;
 use num::cast::AsPrimitive;
 
+/// DateTime64 keeps at most nanoseconds.
+pub const DATETIME64_MAX_PRECISION: u32 = 9;
+pub const DATETIME64_DEFAULT_PRECISION: u32 = 3;
+
 pub trait DateConverter {
     fn to_date(&self, tz: &Tz) -> Date<Tz>;
     fn to_date_time(&self, tz: &Tz) -> DateTime<Tz>;
+    /// The value is the number of 10^-precision seconds since UNIX epoch.
+    fn to_date_time64(&self, precision: u32, tz: &Tz) -> DateTime<Tz>;
 }
 
 impl<T> DateConverter for T
@@ -36,4 +44,65 @@ where T: AsPrimitive<i64>
     fn to_date_time(&self, tz: &Tz) -> DateTime<Tz> {
         tz.timestamp_millis(self.as_() * 1000)
     }
+
+    fn to_date_time64(&self, precision: u32, tz: &Tz) -> DateTime<Tz> {
+        let factor = datetime64_ticks_per_second(precision);
+        let ticks: i64 = self.as_();
+        let nanos = ticks.rem_euclid(factor) * (1_000_000_000 / factor);
+        tz.timestamp(ticks.div_euclid(factor), nanos as u32)
+    }
+}
+
+/// The number of DateTime64 ticks in one second.
+#[inline]
+pub fn datetime64_ticks_per_second(precision: u32) -> i64 {
+    10_i64.pow(precision)
+}
+
+/// Convert a chrono DateTime to DateTime64 ticks, the extra sub-second digits are dropped.
+#[inline]
+pub fn date_time_to_datetime64<T: TimeZone>(dt: &DateTime<T>, precision: u32) -> i64 {
+    let factor = datetime64_ticks_per_second(precision);
+    dt.timestamp() * factor + dt.timestamp_subsec_nanos() as i64 / (1_000_000_000 / factor)
+}
+
+/// Format DateTime64 ticks in the timezone, with `precision` digits of fractional seconds.
+pub fn datetime64_to_string(ticks: i64, precision: u32, tz: &Tz) -> String {
+    let dt = ticks.to_date_time64(precision, tz);
+    match precision {
+        0 => dt.format("%Y-%m-%d %H:%M:%S").to_string(),
+        _ => {
+            let nanos = format!("{:09}", dt.timestamp_subsec_nanos());
+            let fraction = &nanos[..precision as usize];
+            format!("{}.{}", dt.format("%Y-%m-%d %H:%M:%S"), fraction)
+        }
+    }
+}
+
+/// Parse a `%Y-%m-%d %H:%M:%S[.f]` string in the timezone to DateTime64 ticks.
+pub fn datetime64_from_str(s: &str, precision: u32, tz: &Tz) -> Option<i64> {
+    tz.datetime_from_str(s, "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|dt| date_time_to_datetime64(&dt, precision))
+}
+
+/// Get the timezone of a DateTime type, UTC if none is set.
+pub fn get_timezone(tz: &Option<String>) -> Result<Tz> {
+    match tz {
+        None => Ok(Tz::UTC),
+        Some(tz) => tz
+            .parse::<Tz>()
+            .map_err(|_| ErrorCode::BadArguments(format!("Unknown timezone: {}", tz))),
+    }
+}
+
+/// Check the precision of a DateTime64 type is in the supported range.
+pub fn check_datetime64_precision(precision: u32) -> Result<()> {
+    if precision > DATETIME64_MAX_PRECISION {
+        return Err(ErrorCode::IllegalDataType(format!(
+            "DateTime64 precision must be between 0 and {}, but got {}",
+            DATETIME64_MAX_PRECISION, precision
+        )));
+    }
+    Ok(())
 }
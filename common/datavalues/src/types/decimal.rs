@@ -0,0 +1,782 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::prelude::*;
+
+/// The maximum number of decimal digits a Decimal128 can hold.
+pub const DECIMAL128_MAX_PRECISION: usize = 38;
+
+/// The maximum number of decimal digits a Decimal256 can hold.
+pub const DECIMAL256_MAX_PRECISION: usize = 76;
+
+/// Returns 10 to the power of `exp`, `exp` must not be greater than DECIMAL128_MAX_PRECISION.
+#[inline]
+pub fn decimal_pow10(exp: usize) -> i128 {
+    10_i128.pow(exp as u32)
+}
+
+/// The largest unscaled value that fits in the given precision.
+#[inline]
+pub fn decimal_max_value(precision: usize) -> i128 {
+    decimal_pow10(precision) - 1
+}
+
+/// Returns 10 to the power of `exp`, `exp` must not be greater than DECIMAL256_MAX_PRECISION.
+#[inline]
+pub fn decimal256_pow10(exp: usize) -> I256 {
+    I256::checked_pow10(exp).unwrap_or(I256::MAX)
+}
+
+/// Precision and scale a type takes part in decimal arithmetic with,
+/// integers are decimals of scale 0 wide enough to hold any of their values.
+pub fn decimal_precision_scale(data_type: &DataType) -> Option<(usize, usize)> {
+    match data_type {
+        DataType::Decimal128(precision, scale) | DataType::Decimal256(precision, scale) => {
+            Some((*precision, *scale))
+        }
+        DataType::Int8 | DataType::UInt8 => Some((3, 0)),
+        DataType::Int16 | DataType::UInt16 => Some((5, 0)),
+        DataType::Int32 | DataType::UInt32 => Some((10, 0)),
+        DataType::Int64 => Some((19, 0)),
+        DataType::UInt64 => Some((20, 0)),
+        _ => None,
+    }
+}
+
+/// Check the precision and scale of a DECIMAL(P, S) declaration,
+/// precisions above 38 need a Decimal256.
+pub fn check_decimal_type(precision: usize, scale: usize) -> Result<DataType> {
+    if precision == 0 || precision > DECIMAL256_MAX_PRECISION {
+        return Err(ErrorCode::IllegalDataType(format!(
+            "Decimal precision must be between 1 and {}, but got {}",
+            DECIMAL256_MAX_PRECISION, precision
+        )));
+    }
+    if scale > precision {
+        return Err(ErrorCode::IllegalDataType(format!(
+            "Decimal scale {} must not be greater than precision {}",
+            scale, precision
+        )));
+    }
+    match precision <= DECIMAL128_MAX_PRECISION {
+        true => Ok(DataType::Decimal128(precision, scale)),
+        false => Ok(DataType::Decimal256(precision, scale)),
+    }
+}
+
+/// Change the scale of an unscaled value, rounding half away from zero when digits are dropped.
+/// Returns None on overflow.
+pub fn decimal_rescale(value: i128, from_scale: usize, to_scale: usize) -> Option<i128> {
+    match from_scale.cmp(&to_scale) {
+        cmp::Ordering::Equal => Some(value),
+        cmp::Ordering::Less => value.checked_mul(decimal_pow10(to_scale - from_scale)),
+        cmp::Ordering::Greater => {
+            let divisor = decimal_pow10(from_scale - to_scale);
+            let quotient = value / divisor;
+            let remainder = value % divisor;
+            if remainder.unsigned_abs() * 2 >= divisor as u128 {
+                Some(quotient + value.signum())
+            } else {
+                Some(quotient)
+            }
+        }
+    }
+}
+
+/// Division rounding half away from zero, None on overflow.
+fn decimal_div_round(a: i128, b: i128) -> Option<i128> {
+    let quotient = a.checked_div(b)?;
+    let remainder = a.checked_rem(b)?;
+    if remainder.unsigned_abs() * 2 >= b.unsigned_abs() {
+        quotient.checked_add(a.signum() * b.signum())
+    } else {
+        Some(quotient)
+    }
+}
+
+/// Make sure the unscaled value fits in the given precision.
+#[inline]
+pub fn check_decimal_value(value: i128, precision: usize) -> Result<i128> {
+    if value.unsigned_abs() > decimal_max_value(precision) as u128 {
+        return Err(ErrorCode::Overflow(format!(
+            "Decimal value overflows precision {}",
+            precision
+        )));
+    }
+    Ok(value)
+}
+
+/// Decimal256 version of decimal_div_round.
+fn decimal256_div_round(a: I256, b: I256) -> Option<I256> {
+    let quotient = a.checked_div(b)?;
+    let remainder = a.checked_rem(b)?;
+    if remainder.checked_add(remainder)?.cmp_abs(&b) != cmp::Ordering::Less {
+        let signum = match a.is_negative() == b.is_negative() {
+            true => 1,
+            false => -1,
+        };
+        quotient.checked_add(I256::from_i128(signum))
+    } else {
+        Some(quotient)
+    }
+}
+
+/// Decimal256 version of decimal_rescale.
+pub fn decimal256_rescale(value: I256, from_scale: usize, to_scale: usize) -> Option<I256> {
+    match from_scale.cmp(&to_scale) {
+        cmp::Ordering::Equal => Some(value),
+        cmp::Ordering::Less => value.checked_mul(decimal256_pow10(to_scale - from_scale)),
+        cmp::Ordering::Greater => {
+            let divisor = decimal256_pow10(from_scale - to_scale);
+            let quotient = value.checked_div(divisor)?;
+            let remainder = value.checked_rem(divisor)?;
+            if remainder.checked_add(remainder)?.cmp_abs(&divisor) != cmp::Ordering::Less {
+                let signum = if value.is_negative() { -1 } else { 1 };
+                quotient.checked_add(I256::from_i128(signum))
+            } else {
+                Some(quotient)
+            }
+        }
+    }
+}
+
+/// Decimal256 version of check_decimal_value.
+#[inline]
+pub fn check_decimal256_value(value: I256, precision: usize) -> Result<I256> {
+    if value.cmp_abs(&decimal256_pow10(precision)) != cmp::Ordering::Less {
+        return Err(ErrorCode::Overflow(format!(
+            "Decimal value overflows precision {}",
+            precision
+        )));
+    }
+    Ok(value)
+}
+
+/// Render an unscaled value with the given scale, e.g. (-12345, 2) is "-123.45".
+pub fn decimal_to_string(value: i128, scale: usize) -> String {
+    format_decimal(value < 0, value.unsigned_abs().to_string(), scale)
+}
+
+/// Decimal256 version of decimal_to_string.
+pub fn decimal256_to_string(value: I256, scale: usize) -> String {
+    format_decimal(value.is_negative(), value.abs_to_string(), scale)
+}
+
+fn format_decimal(negative: bool, digits: String, scale: usize) -> String {
+    let sign = if negative { "-" } else { "" };
+    if scale == 0 {
+        return format!("{}{}", sign, digits);
+    }
+
+    let digits = if digits.len() <= scale {
+        format!("{}{}", "0".repeat(scale + 1 - digits.len()), digits)
+    } else {
+        digits
+    };
+
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    format!("{}{}.{}", sign, integer, fraction)
+}
+
+/// Parse a decimal literal such as "-123.45" into an unscaled value
+/// of the given precision and scale.
+/// Extra fraction digits are rounded half away from zero.
+pub fn decimal_from_str(literal: &str, precision: usize, scale: usize) -> Result<i128> {
+    let (negative, digits, round_up) = parse_decimal_literal(literal, precision, scale)?;
+    let mut value = 0_i128;
+    for digit in digits.bytes() {
+        value = value * 10 + (digit - b'0') as i128;
+    }
+    if round_up {
+        value += 1;
+    }
+
+    let value = if negative { -value } else { value };
+    check_decimal_value(value, precision).map_err(|_| decimal_literal_overflow(literal))
+}
+
+/// Decimal256 version of decimal_from_str.
+pub fn decimal256_from_str(literal: &str, precision: usize, scale: usize) -> Result<I256> {
+    let (negative, digits, round_up) = parse_decimal_literal(literal, precision, scale)?;
+    let overflow = || decimal_literal_overflow(literal);
+    let ten = I256::from_i128(10);
+    let mut value = I256::ZERO;
+    for digit in digits.bytes() {
+        value = value
+            .checked_mul(ten)
+            .and_then(|v| v.checked_add(I256::from_i128((digit - b'0') as i128)))
+            .ok_or_else(overflow)?;
+    }
+    if round_up {
+        value = value.checked_add(I256::from_i128(1)).ok_or_else(overflow)?;
+    }
+
+    let value = if negative {
+        value.checked_neg().ok_or_else(overflow)?
+    } else {
+        value
+    };
+    check_decimal256_value(value, precision).map_err(|_| overflow())
+}
+
+fn decimal_literal_overflow(literal: &str) -> ErrorCode {
+    ErrorCode::Overflow(format!("Decimal value '{}' overflows", literal))
+}
+
+/// Split a decimal literal into its sign, the digits of the unscaled value
+/// and whether the dropped fraction digits round it up.
+fn parse_decimal_literal(
+    literal: &str,
+    precision: usize,
+    scale: usize,
+) -> Result<(bool, String, bool)> {
+    let bad_literal = || {
+        ErrorCode::BadDataValueType(format!(
+            "Cannot parse '{}' as Decimal({}, {})",
+            literal, precision, scale
+        ))
+    };
+
+    let trimmed = literal.trim();
+    let (negative, unsigned) = match trimmed.as_bytes().first() {
+        Some(b'-') => (true, &trimmed[1..]),
+        Some(b'+') => (false, &trimmed[1..]),
+        _ => (false, trimmed),
+    };
+
+    let (integer, fraction) = match unsigned.find('.') {
+        Some(pos) => (&unsigned[..pos], &unsigned[pos + 1..]),
+        None => (unsigned, ""),
+    };
+
+    let is_digits = |s: &str| s.bytes().all(|c| c.is_ascii_digit());
+    if (integer.is_empty() && fraction.is_empty()) || !is_digits(integer) || !is_digits(fraction) {
+        return Err(bad_literal());
+    }
+
+    let integer_digits = integer.trim_start_matches('0');
+    if integer_digits.len() > precision - scale {
+        return Err(decimal_literal_overflow(literal));
+    }
+    let digits = integer_digits
+        .chars()
+        .chain(fraction.chars().chain(std::iter::repeat('0')).take(scale))
+        .collect::<String>();
+    let round_up = fraction.len() > scale && fraction.as_bytes()[scale] >= b'5';
+    Ok((negative, digits, round_up))
+}
+
+#[inline]
+pub fn decimal_to_f64(value: i128, scale: usize) -> f64 {
+    value as f64 / decimal_pow10(scale) as f64
+}
+
+pub fn decimal_from_f64(value: f64, precision: usize, scale: usize) -> Result<i128> {
+    let scaled = (value * decimal_pow10(scale) as f64).round();
+    if !scaled.is_finite() || scaled.abs() > decimal_max_value(precision) as f64 {
+        return Err(ErrorCode::Overflow(format!(
+            "Value {} overflows Decimal128({}, {})",
+            value, precision, scale
+        )));
+    }
+    Ok(scaled as i128)
+}
+
+#[inline]
+pub fn decimal256_to_f64(value: I256, scale: usize) -> f64 {
+    value.to_f64() / 10_f64.powi(scale as i32)
+}
+
+pub fn decimal256_from_f64(value: f64, precision: usize, scale: usize) -> Result<I256> {
+    let scaled = (value * 10_f64.powi(scale as i32)).round();
+    I256::from_f64(scaled)
+        .and_then(|v| check_decimal256_value(v, precision).ok())
+        .ok_or_else(|| {
+            ErrorCode::Overflow(format!(
+                "Value {} overflows Decimal256({}, {})",
+                value, precision, scale
+            ))
+        })
+}
+
+/// Decode a Decimal256 value, they are stored in a String array as the bytes of
+/// I256::to_ordered_bytes, so comparing and hashing the bytes works on the values.
+pub fn decimal256_from_bytes(bytes: &[u8]) -> Result<I256> {
+    I256::from_ordered_bytes(bytes).ok_or_else(|| {
+        ErrorCode::BadBytes(format!(
+            "Decimal256 value must be {} bytes, but got {}",
+            I256::BYTES,
+            bytes.len()
+        ))
+    })
+}
+
+fn collect_decimal<I>(iter: I) -> Result<Series>
+where I: Iterator<Item = Result<Option<i128>>> {
+    let values = iter.collect::<Result<Vec<_>>>()?;
+    Ok(DFDecimal128Array::new_from_opt_slice(&values).into_series())
+}
+
+fn collect_decimal256<I>(iter: I) -> Result<Series>
+where I: Iterator<Item = Result<Option<I256>>> {
+    let values = iter.collect::<Result<Vec<_>>>()?;
+    Ok(
+        DFStringArray::new_from_opt_iter(values.iter().map(|v| v.map(|v| v.to_ordered_bytes())))
+            .into_series(),
+    )
+}
+
+fn decimal256_values(series: &Series) -> Result<Vec<Option<I256>>> {
+    series
+        .string()?
+        .into_iter()
+        .map(|v| v.map(decimal256_from_bytes).transpose())
+        .collect()
+}
+
+/// Cast a series whose logical type is `from_type` to Decimal128(precision, scale).
+pub fn cast_series_to_decimal(
+    series: &Series,
+    from_type: &DataType,
+    precision: usize,
+    scale: usize,
+) -> Result<Series> {
+    match from_type {
+        DataType::Decimal128(_, from_scale) => {
+            let from_scale = *from_scale;
+            collect_decimal(series.decimal128()?.into_iter().map(|v| {
+                v.map(|v| match decimal_rescale(*v, from_scale, scale) {
+                    Some(v) => check_decimal_value(v, precision),
+                    None => check_decimal_value(i128::MAX, precision),
+                })
+                .transpose()
+            }))
+        }
+        DataType::Decimal256(_, from_scale) => {
+            let from_scale = *from_scale;
+            collect_decimal(decimal256_values(series)?.into_iter().map(|v| {
+                v.map(
+                    |v| match decimal256_rescale(v, from_scale, scale).and_then(I256::to_i128) {
+                        Some(v) => check_decimal_value(v, precision),
+                        None => check_decimal_value(i128::MAX, precision),
+                    },
+                )
+                .transpose()
+            }))
+        }
+        DataType::UInt64 => collect_decimal(series.u64()?.into_iter().map(|v| {
+            v.map(|v| integer_to_decimal(*v as i128, precision, scale))
+                .transpose()
+        })),
+        t if t.is_integer() => {
+            let series = series.cast_with_type(&DataType::Int64)?;
+            collect_decimal(series.i64()?.into_iter().map(|v| {
+                v.map(|v| integer_to_decimal(*v as i128, precision, scale))
+                    .transpose()
+            }))
+        }
+        t if t.is_floating() => {
+            let series = series.cast_with_type(&DataType::Float64)?;
+            collect_decimal(series.f64()?.into_iter().map(|v| {
+                v.map(|v| decimal_from_f64(*v, precision, scale))
+                    .transpose()
+            }))
+        }
+        DataType::String => collect_decimal(series.string()?.into_iter().map(|v| {
+            v.map(|v| {
+                let literal = std::str::from_utf8(v).map_err(|_| {
+                    ErrorCode::BadDataValueType("Cannot parse non-utf8 bytes as Decimal128")
+                })?;
+                decimal_from_str(literal, precision, scale)
+            })
+            .transpose()
+        })),
+        DataType::Null => Ok(DFDecimal128Array::full_null(series.len()).into_series()),
+        other => Err(ErrorCode::BadDataValueType(format!(
+            "Unsupported cast from {:?} to Decimal128({}, {})",
+            other, precision, scale
+        ))),
+    }
+}
+
+#[inline]
+fn integer_to_decimal(value: i128, precision: usize, scale: usize) -> Result<i128> {
+    match value.checked_mul(decimal_pow10(scale)) {
+        Some(v) => check_decimal_value(v, precision),
+        None => check_decimal_value(i128::MAX, precision),
+    }
+}
+
+/// Cast a series whose logical type is `from_type` to Decimal256(precision, scale).
+pub fn cast_series_to_decimal256(
+    series: &Series,
+    from_type: &DataType,
+    precision: usize,
+    scale: usize,
+) -> Result<Series> {
+    let rescale = |value: I256, from_scale: usize| -> Result<I256> {
+        match decimal256_rescale(value, from_scale, scale) {
+            Some(v) => check_decimal256_value(v, precision),
+            None => check_decimal256_value(I256::MAX, precision),
+        }
+    };
+
+    match from_type {
+        DataType::Decimal128(_, from_scale) => {
+            collect_decimal256(series.decimal128()?.into_iter().map(|v| {
+                v.map(|v| rescale(I256::from_i128(*v), *from_scale))
+                    .transpose()
+            }))
+        }
+        DataType::Decimal256(_, from_scale) => collect_decimal256(
+            decimal256_values(series)?
+                .into_iter()
+                .map(|v| v.map(|v| rescale(v, *from_scale)).transpose()),
+        ),
+        DataType::UInt64 => collect_decimal256(series.u64()?.into_iter().map(|v| {
+            v.map(|v| rescale(I256::from_i128(*v as i128), 0))
+                .transpose()
+        })),
+        t if t.is_integer() => {
+            let series = series.cast_with_type(&DataType::Int64)?;
+            collect_decimal256(series.i64()?.into_iter().map(|v| {
+                v.map(|v| rescale(I256::from_i128(*v as i128), 0))
+                    .transpose()
+            }))
+        }
+        t if t.is_floating() => {
+            let series = series.cast_with_type(&DataType::Float64)?;
+            collect_decimal256(series.f64()?.into_iter().map(|v| {
+                v.map(|v| decimal256_from_f64(*v, precision, scale))
+                    .transpose()
+            }))
+        }
+        DataType::String => collect_decimal256(series.string()?.into_iter().map(|v| {
+            v.map(|v| {
+                let literal = std::str::from_utf8(v).map_err(|_| {
+                    ErrorCode::BadDataValueType("Cannot parse non-utf8 bytes as Decimal256")
+                })?;
+                decimal256_from_str(literal, precision, scale)
+            })
+            .transpose()
+        })),
+        DataType::Null => Ok(DFStringArray::full_null(series.len()).into_series()),
+        other => Err(ErrorCode::BadDataValueType(format!(
+            "Unsupported cast from {:?} to Decimal256({}, {})",
+            other, precision, scale
+        ))),
+    }
+}
+
+/// Cast a decimal series of the given scale to a non-decimal type.
+/// Integers are truncated toward zero.
+pub fn cast_series_from_decimal(
+    series: &Series,
+    scale: usize,
+    to_type: &DataType,
+) -> Result<Series> {
+    let array = series.decimal128()?;
+    let divisor = decimal_pow10(scale);
+
+    match to_type {
+        DataType::String => Ok(DFStringArray::new_from_opt_iter(
+            array
+                .into_iter()
+                .map(|v| v.map(|v| decimal_to_string(*v, scale))),
+        )
+        .into_series()),
+        t if t.is_floating() => array
+            .apply_cast_numeric(|v| decimal_to_f64(v, scale))
+            .into_series()
+            .cast_with_type(to_type),
+        DataType::UInt64 => {
+            let values = array
+                .into_iter()
+                .map(|v| {
+                    v.map(|v| integer_from_decimal::<u64>(*v / divisor))
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(DFUInt64Array::new_from_opt_slice(&values).into_series())
+        }
+        t if t.is_integer() => {
+            let values = array
+                .into_iter()
+                .map(|v| {
+                    v.map(|v| integer_from_decimal::<i64>(*v / divisor))
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>>>()?;
+            DFInt64Array::new_from_opt_slice(&values)
+                .into_series()
+                .cast_with_type(to_type)
+        }
+        DataType::Boolean => Ok(DFBooleanArray::new_from_opt_iter(
+            array.into_iter().map(|v| v.map(|v| *v != 0)),
+        )
+        .into_series()),
+        other => Err(ErrorCode::BadDataValueType(format!(
+            "Unsupported cast from Decimal128 to {:?}",
+            other
+        ))),
+    }
+}
+
+/// Decimal256 version of cast_series_from_decimal.
+pub fn cast_series_from_decimal256(
+    series: &Series,
+    scale: usize,
+    to_type: &DataType,
+) -> Result<Series> {
+    let values = decimal256_values(series)?;
+    let divisor = decimal256_pow10(scale);
+    let truncate = |v: &I256| -> Result<i128> {
+        v.checked_div(divisor)
+            .and_then(I256::to_i128)
+            .ok_or_else(|| ErrorCode::Overflow(format!("Decimal value {} overflows i128", v)))
+    };
+
+    match to_type {
+        DataType::String => Ok(DFStringArray::new_from_opt_iter(
+            values
+                .iter()
+                .map(|v| v.map(|v| decimal256_to_string(v, scale))),
+        )
+        .into_series()),
+        t if t.is_floating() => DFFloat64Array::new_from_opt_iter(
+            values
+                .iter()
+                .map(|v| v.map(|v| decimal256_to_f64(v, scale))),
+        )
+        .into_series()
+        .cast_with_type(to_type),
+        DataType::UInt64 => {
+            let values = values
+                .iter()
+                .map(|v| {
+                    v.as_ref()
+                        .map(|v| integer_from_decimal::<u64>(truncate(v)?))
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(DFUInt64Array::new_from_opt_slice(&values).into_series())
+        }
+        t if t.is_integer() => {
+            let values = values
+                .iter()
+                .map(|v| {
+                    v.as_ref()
+                        .map(|v| integer_from_decimal::<i64>(truncate(v)?))
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>>>()?;
+            DFInt64Array::new_from_opt_slice(&values)
+                .into_series()
+                .cast_with_type(to_type)
+        }
+        DataType::Boolean => Ok(DFBooleanArray::new_from_opt_iter(
+            values.iter().map(|v| v.map(|v| !v.is_zero())),
+        )
+        .into_series()),
+        other => Err(ErrorCode::BadDataValueType(format!(
+            "Unsupported cast from Decimal256 to {:?}",
+            other
+        ))),
+    }
+}
+
+#[inline]
+fn integer_from_decimal<T: TryFrom<i128>>(value: i128) -> Result<T> {
+    T::try_from(value).map_err(|_| {
+        ErrorCode::Overflow(format!(
+            "Decimal value {} overflows {}",
+            value,
+            std::any::type_name::<T>()
+        ))
+    })
+}
+
+/// Evaluate `lhs op rhs` where at least one side is a decimal, `lhs_type` and `rhs_type`
+/// are the logical types of the series and the result has the type given by
+/// decimal_arithmetic_coercion.
+pub fn decimal_arithmetic(
+    op: &DataValueArithmeticOperator,
+    lhs: &Series,
+    lhs_type: &DataType,
+    rhs: &Series,
+    rhs_type: &DataType,
+) -> Result<Series> {
+    let result_type = decimal_arithmetic_coercion(op, lhs_type, rhs_type)?;
+    let (precision, scale) = match result_type {
+        DataType::Decimal128(precision, scale) => (precision, scale),
+        DataType::Decimal256(precision, scale) => {
+            return decimal256_arithmetic(op, lhs, lhs_type, rhs, rhs_type, precision, scale);
+        }
+        _ => {
+            let lhs = to_float64(lhs, lhs_type)?;
+            let rhs = to_float64(rhs, rhs_type)?;
+            return match op {
+                DataValueArithmeticOperator::Plus => &lhs + &rhs,
+                DataValueArithmeticOperator::Minus => &lhs - &rhs,
+                DataValueArithmeticOperator::Mul => &lhs * &rhs,
+                DataValueArithmeticOperator::Div => &lhs / &rhs,
+                DataValueArithmeticOperator::Modulo => &lhs % &rhs,
+            };
+        }
+    };
+
+    let (_, lhs_scale) = decimal_precision_scale(lhs_type).unwrap_or((0, 0));
+    let (_, rhs_scale) = decimal_precision_scale(rhs_type).unwrap_or((0, 0));
+
+    // Bring both sides to the scale the operator works on, multiplication and division
+    // work on the operands' own scales.
+    let (lhs_scale, rhs_scale) = match op {
+        DataValueArithmeticOperator::Mul | DataValueArithmeticOperator::Div => {
+            (lhs_scale, rhs_scale)
+        }
+        _ => (scale, scale),
+    };
+    let lhs = cast_series_to_decimal(lhs, lhs_type, DECIMAL128_MAX_PRECISION, lhs_scale)?;
+    let rhs = cast_series_to_decimal(rhs, rhs_type, DECIMAL128_MAX_PRECISION, rhs_scale)?;
+
+    let overflow = || ErrorCode::Overflow(format!("Decimal {} overflows", op));
+    // The dividend is scaled up, so that the quotient has the scale of the result.
+    let div_factor = 10_i128.checked_pow((scale + rhs_scale - lhs_scale) as u32);
+    let apply = |a: i128, b: i128| -> Result<i128> {
+        let value = match op {
+            DataValueArithmeticOperator::Plus => a.checked_add(b),
+            DataValueArithmeticOperator::Minus => a.checked_sub(b),
+            DataValueArithmeticOperator::Mul => a.checked_mul(b),
+            DataValueArithmeticOperator::Div | DataValueArithmeticOperator::Modulo if b == 0 => {
+                return Err(ErrorCode::BadArguments("Division by zero"));
+            }
+            DataValueArithmeticOperator::Div => div_factor
+                .and_then(|factor| a.checked_mul(factor))
+                .and_then(|a| decimal_div_round(a, b)),
+            DataValueArithmeticOperator::Modulo => a.checked_rem(b),
+        };
+        let value = value.ok_or_else(overflow)?;
+        check_decimal_value(value, precision).map_err(|_| overflow())
+    };
+
+    let lhs = lhs.decimal128()?;
+    let rhs = rhs.decimal128()?;
+    if lhs.len() != rhs.len() {
+        return Err(ErrorCode::BadDataArrayLength(format!(
+            "Decimal {} requires arrays of the same length, got {} and {}",
+            op,
+            lhs.len(),
+            rhs.len()
+        )));
+    }
+    collect_decimal(
+        lhs.into_iter()
+            .zip(rhs.into_iter())
+            .map(|(a, b)| match (a, b) {
+                (Some(a), Some(b)) => apply(*a, *b).map(Some),
+                _ => Ok(None),
+            }),
+    )
+}
+
+/// Decimal256 version of decimal_arithmetic, the result has the given precision and scale.
+fn decimal256_arithmetic(
+    op: &DataValueArithmeticOperator,
+    lhs: &Series,
+    lhs_type: &DataType,
+    rhs: &Series,
+    rhs_type: &DataType,
+    precision: usize,
+    scale: usize,
+) -> Result<Series> {
+    let (_, lhs_scale) = decimal_precision_scale(lhs_type).unwrap_or((0, 0));
+    let (_, rhs_scale) = decimal_precision_scale(rhs_type).unwrap_or((0, 0));
+    let (lhs_scale, rhs_scale) = match op {
+        DataValueArithmeticOperator::Mul | DataValueArithmeticOperator::Div => {
+            (lhs_scale, rhs_scale)
+        }
+        _ => (scale, scale),
+    };
+    let lhs = cast_series_to_decimal256(lhs, lhs_type, DECIMAL256_MAX_PRECISION, lhs_scale)?;
+    let rhs = cast_series_to_decimal256(rhs, rhs_type, DECIMAL256_MAX_PRECISION, rhs_scale)?;
+
+    let overflow = || ErrorCode::Overflow(format!("Decimal {} overflows", op));
+    let div_factor = I256::checked_pow10(scale + rhs_scale - lhs_scale);
+    let apply = |a: I256, b: I256| -> Result<I256> {
+        let value = match op {
+            DataValueArithmeticOperator::Plus => a.checked_add(b),
+            DataValueArithmeticOperator::Minus => a.checked_sub(b),
+            DataValueArithmeticOperator::Mul => a.checked_mul(b),
+            DataValueArithmeticOperator::Div | DataValueArithmeticOperator::Modulo
+                if b.is_zero() =>
+            {
+                return Err(ErrorCode::BadArguments("Division by zero"));
+            }
+            DataValueArithmeticOperator::Div => div_factor
+                .and_then(|factor| a.checked_mul(factor))
+                .and_then(|a| decimal256_div_round(a, b)),
+            DataValueArithmeticOperator::Modulo => a.checked_rem(b),
+        };
+        let value = value.ok_or_else(overflow)?;
+        check_decimal256_value(value, precision).map_err(|_| overflow())
+    };
+
+    let lhs = decimal256_values(&lhs)?;
+    let rhs = decimal256_values(&rhs)?;
+    if lhs.len() != rhs.len() {
+        return Err(ErrorCode::BadDataArrayLength(format!(
+            "Decimal {} requires arrays of the same length, got {} and {}",
+            op,
+            lhs.len(),
+            rhs.len()
+        )));
+    }
+    collect_decimal256(
+        lhs.into_iter()
+            .zip(rhs.into_iter())
+            .map(|(a, b)| match (a, b) {
+                (Some(a), Some(b)) => apply(a, b).map(Some),
+                _ => Ok(None),
+            }),
+    )
+}
+
+/// Negate a decimal series of the given type, the type does not change.
+pub fn decimal_negative(series: &Series, data_type: &DataType) -> Result<Series> {
+    match data_type {
+        DataType::Decimal256(_, _) => collect_decimal256(
+            decimal256_values(series)?
+                .into_iter()
+                .map(|v| v.and_then(I256::checked_neg).map(Ok).transpose()),
+        ),
+        _ => Ok(series.decimal128()?.apply(|v| -v).into_series()),
+    }
+}
+
+fn to_float64(series: &Series, data_type: &DataType) -> Result<Series> {
+    match data_type {
+        DataType::Decimal128(_, scale) => {
+            cast_series_from_decimal(series, *scale, &DataType::Float64)
+        }
+        DataType::Decimal256(_, scale) => {
+            cast_series_from_decimal256(series, *scale, &DataType::Float64)
+        }
+        _ => series.cast_with_type(&DataType::Float64),
+    }
+}
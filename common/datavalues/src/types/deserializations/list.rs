@@ -0,0 +1,114 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_arrow::arrow::bitmap::MutableBitmap;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde_json::Value as JsonValue;
+
+use crate::prelude::*;
+
+/// Deserialize an Array(T) from a JSON array text like `["a", "b"]`,
+/// the elements are deserialized from their texts by the deserializer of T.
+pub struct ListDeserializer {
+    pub inner: Box<dyn TypeDeserializer>,
+    pub offsets: Vec<i64>,
+    pub validity: MutableBitmap,
+}
+
+impl ListDeserializer {
+    pub fn try_create(data_type: &DataType, capacity: usize) -> Result<Self> {
+        let inner = match data_type {
+            DataType::List(item) => item.data_type().create_deserializer(capacity)?,
+            other => {
+                return Err(ErrorCode::BadDataValueType(format!(
+                    "Expected array type, but got {:?}",
+                    other
+                )))
+            }
+        };
+
+        let mut offsets = Vec::with_capacity(capacity + 1);
+        offsets.push(0);
+        Ok(Self {
+            inner,
+            offsets,
+            validity: MutableBitmap::with_capacity(capacity),
+        })
+    }
+
+    fn push_items(&mut self, items: usize, is_valid: bool) {
+        let last = self.offsets[self.offsets.len() - 1];
+        self.offsets.push(last + items as i64);
+        self.validity.push(is_valid);
+    }
+}
+
+impl TypeDeserializer for ListDeserializer {
+    fn de(&mut self, _reader: &mut &[u8]) -> Result<()> {
+        Err(ErrorCode::BadBytes(
+            "Array type does not support binary deserialization",
+        ))
+    }
+
+    fn de_batch(&mut self, _reader: &[u8], _step: usize, _rows: usize) -> Result<()> {
+        Err(ErrorCode::BadBytes(
+            "Array type does not support binary deserialization",
+        ))
+    }
+
+    fn de_text(&mut self, reader: &[u8]) -> Result<()> {
+        if reader.eq_ignore_ascii_case(b"null") || reader.is_empty() {
+            self.de_null();
+            return Ok(());
+        }
+
+        let items = match serde_json::from_slice::<JsonValue>(reader) {
+            Ok(JsonValue::Array(items)) => items,
+            _ => {
+                return Err(ErrorCode::BadBytes(format!(
+                    "Incorrect Array value: {}",
+                    String::from_utf8_lossy(reader)
+                )))
+            }
+        };
+
+        for item in items.iter() {
+            match item {
+                JsonValue::Null => self.inner.de_null(),
+                JsonValue::String(v) => self.inner.de_text(v.as_bytes())?,
+                v => self.inner.de_text(v.to_string().as_bytes())?,
+            }
+        }
+        self.push_items(items.len(), true);
+        Ok(())
+    }
+
+    fn de_null(&mut self) {
+        self.push_items(0, false);
+    }
+
+    fn finish_to_series(&mut self) -> Series {
+        let values = self.inner.finish_to_series();
+        let offsets = std::mem::replace(&mut self.offsets, vec![0]);
+        let validity: Bitmap = std::mem::replace(&mut self.validity, MutableBitmap::new()).into();
+        let validity = match validity.null_count() {
+            0 => None,
+            _ => Some(validity),
+        };
+
+        DFListArray::from_parts(&values, offsets, validity).into_series()
+    }
+}
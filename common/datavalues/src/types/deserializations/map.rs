@@ -0,0 +1,125 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_arrow::arrow::bitmap::MutableBitmap;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde_json::Value as JsonValue;
+
+use crate::prelude::*;
+
+/// Deserialize a Map(K, V) from a JSON object text like `{"a": 1, "b": 2}`,
+/// the keys and values are deserialized from their texts by the deserializers of K and V.
+pub struct MapDeserializer {
+    pub data_type: DataType,
+    pub key_deserializer: Box<dyn TypeDeserializer>,
+    pub value_deserializer: Box<dyn TypeDeserializer>,
+    pub offsets: Vec<i64>,
+    pub validity: MutableBitmap,
+}
+
+impl MapDeserializer {
+    pub fn try_create(data_type: &DataType, capacity: usize) -> Result<Self> {
+        let (key_deserializer, value_deserializer) = match data_type {
+            DataType::Map(key_type, value_type) => (
+                key_type.create_deserializer(capacity)?,
+                value_type.create_deserializer(capacity)?,
+            ),
+            other => {
+                return Err(ErrorCode::BadDataValueType(format!(
+                    "Expected map type, but got {:?}",
+                    other
+                )))
+            }
+        };
+
+        let mut offsets = Vec::with_capacity(capacity + 1);
+        offsets.push(0);
+        Ok(Self {
+            data_type: data_type.clone(),
+            key_deserializer,
+            value_deserializer,
+            offsets,
+            validity: MutableBitmap::with_capacity(capacity),
+        })
+    }
+
+    fn push_entries(&mut self, entries: usize, is_valid: bool) {
+        let last = self.offsets[self.offsets.len() - 1];
+        self.offsets.push(last + entries as i64);
+        self.validity.push(is_valid);
+    }
+}
+
+impl TypeDeserializer for MapDeserializer {
+    fn de(&mut self, _reader: &mut &[u8]) -> Result<()> {
+        Err(ErrorCode::BadBytes(
+            "Map type does not support binary deserialization",
+        ))
+    }
+
+    fn de_batch(&mut self, _reader: &[u8], _step: usize, _rows: usize) -> Result<()> {
+        Err(ErrorCode::BadBytes(
+            "Map type does not support binary deserialization",
+        ))
+    }
+
+    fn de_text(&mut self, reader: &[u8]) -> Result<()> {
+        if reader.eq_ignore_ascii_case(b"null") || reader.is_empty() {
+            self.de_null();
+            return Ok(());
+        }
+
+        let object = match serde_json::from_slice::<JsonValue>(reader) {
+            Ok(JsonValue::Object(object)) => object,
+            _ => {
+                return Err(ErrorCode::BadBytes(format!(
+                    "Incorrect Map value: {}",
+                    String::from_utf8_lossy(reader)
+                )))
+            }
+        };
+
+        for (key, value) in object.iter() {
+            self.key_deserializer.de_text(key.as_bytes())?;
+            match value {
+                JsonValue::Null => self.value_deserializer.de_null(),
+                JsonValue::String(v) => self.value_deserializer.de_text(v.as_bytes())?,
+                v => self.value_deserializer.de_text(v.to_string().as_bytes())?,
+            }
+        }
+        self.push_entries(object.len(), true);
+        Ok(())
+    }
+
+    fn de_null(&mut self) {
+        self.push_entries(0, false);
+    }
+
+    fn finish_to_series(&mut self) -> Series {
+        let keys = self.key_deserializer.finish_to_series();
+        let values = self.value_deserializer.finish_to_series();
+        let offsets = std::mem::replace(&mut self.offsets, vec![0]);
+        let validity: Bitmap = std::mem::replace(&mut self.validity, MutableBitmap::new()).into();
+        let validity = match validity.null_count() {
+            0 => None,
+            _ => Some(validity),
+        };
+
+        DFMapArray::from_parts(&self.data_type, &keys, &values, offsets, validity)
+            .unwrap()
+            .into_series()
+    }
+}
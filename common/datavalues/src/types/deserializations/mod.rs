@@ -21,14 +21,22 @@ use crate::prelude::*;
 mod boolean;
 mod date;
 mod date_time;
+mod decimal;
+mod list;
+mod map;
 mod number;
 mod string;
+mod variant;
 
 pub use boolean::*;
 pub use date::*;
 pub use date_time::*;
+pub use decimal::*;
+pub use list::*;
+pub use map::*;
 pub use number::*;
 pub use string::*;
+pub use variant::*;
 
 pub trait TypeDeserializer: Send + Sync {
     fn de(&mut self, reader: &mut &[u8]) -> Result<()>;
@@ -66,9 +74,33 @@ impl DataType {
                         tz: tz.parse::<Tz>().unwrap(),
                     }))
                 }
+                DataType::DateTime64(precision, tz) => Ok(Box::new(DateTime64Deserializer {
+                    builder: PrimitiveArrayBuilder::<i64>::with_capacity(capacity),
+                    precision,
+                    tz: get_timezone(&tz)?,
+                })),
+                DataType::Decimal128(precision, scale) => Ok(Box::new(DecimalDeserializer {
+                    builder: PrimitiveArrayBuilder::<i128>::with_capacity(capacity),
+                    precision,
+                    scale,
+                })),
+                DataType::Decimal256(precision, scale) => Ok(Box::new(Decimal256Deserializer {
+                    builder: StringArrayBuilder::with_capacity(capacity),
+                    precision,
+                    scale,
+                })),
                 DataType::String => Ok(Box::new(StringDeserializer {
                     builder: StringArrayBuilder::with_capacity(capacity),
                 })),
+                DataType::Variant => Ok(Box::new(VariantDeserializer {
+                    builder: StringArrayBuilder::with_capacity(capacity),
+                })),
+                DataType::List(_) => Ok(Box::new(ListDeserializer::try_create(
+                    &data_type, capacity,
+                )?)),
+                DataType::Map(_, _) => Ok(Box::new(MapDeserializer::try_create(
+                    &data_type, capacity,
+                )?)),
                 DataType::Interval(_) => Ok(Box::new(DateDeserializer::<i64> {
                     builder: PrimitiveArrayBuilder::<i64>::with_capacity(capacity),
                 })),
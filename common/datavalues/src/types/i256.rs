@@ -0,0 +1,357 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A 256-bit signed integer in two's complement, the unscaled value of a Decimal256.
+///
+/// The fields are ordered so the derived ordering is the numeric ordering.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct I256 {
+    high: i128,
+    low: u128,
+}
+
+/// The magnitude of an I256, used by multiplication and division.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct U256 {
+    high: u128,
+    low: u128,
+}
+
+impl U256 {
+    const ZERO: U256 = U256 { high: 0, low: 0 };
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.high == 0 && self.low == 0
+    }
+
+    fn wrapping_neg(self) -> U256 {
+        let low = (!self.low).wrapping_add(1);
+        let high = (!self.high).wrapping_add((low == 0) as u128);
+        U256 { high, low }
+    }
+
+    fn wrapping_sub(self, other: U256) -> U256 {
+        let (low, borrow) = self.low.overflowing_sub(other.low);
+        let high = self
+            .high
+            .wrapping_sub(other.high)
+            .wrapping_sub(borrow as u128);
+        U256 { high, low }
+    }
+
+    /// The 64-bit limbs, the least significant first.
+    fn limbs(&self) -> [u64; 4] {
+        [
+            self.low as u64,
+            (self.low >> 64) as u64,
+            self.high as u64,
+            (self.high >> 64) as u64,
+        ]
+    }
+
+    fn from_limbs(limbs: &[u64]) -> U256 {
+        U256 {
+            low: limbs[0] as u128 | (limbs[1] as u128) << 64,
+            high: limbs[2] as u128 | (limbs[3] as u128) << 64,
+        }
+    }
+
+    fn checked_mul(self, other: U256) -> Option<U256> {
+        let a = self.limbs();
+        let b = other.limbs();
+        let mut product = [0_u64; 8];
+        for (i, x) in a.iter().enumerate() {
+            let mut carry = 0_u128;
+            for (j, y) in b.iter().enumerate() {
+                let t = *x as u128 * *y as u128 + product[i + j] as u128 + carry;
+                product[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            product[i + 4] = carry as u64;
+        }
+
+        match product[4..].iter().all(|limb| *limb == 0) {
+            true => Some(U256::from_limbs(&product[..4])),
+            false => None,
+        }
+    }
+
+    /// Divide by a small divisor, returns the quotient and the remainder.
+    fn div_rem_u64(self, divisor: u64) -> (U256, u64) {
+        let mut limbs = self.limbs();
+        let mut remainder = 0_u128;
+        for limb in limbs.iter_mut().rev() {
+            let current = remainder << 64 | *limb as u128;
+            *limb = (current / divisor as u128) as u64;
+            remainder = current % divisor as u128;
+        }
+        (U256::from_limbs(&limbs), remainder as u64)
+    }
+
+    /// Binary long division, the divisor must not be zero.
+    fn div_rem(self, divisor: U256) -> (U256, U256) {
+        if self.high == 0 && divisor.high == 0 {
+            return (
+                U256 {
+                    high: 0,
+                    low: self.low / divisor.low,
+                },
+                U256 {
+                    high: 0,
+                    low: self.low % divisor.low,
+                },
+            );
+        }
+
+        let mut quotient = U256::ZERO;
+        let mut remainder = U256::ZERO;
+        for bit in (0..256).rev() {
+            let overflow = remainder.high >> 127 == 1;
+            remainder.high = remainder.high << 1 | remainder.low >> 127;
+            remainder.low = remainder.low << 1 | self.bit(bit);
+            if overflow || remainder >= divisor {
+                remainder = remainder.wrapping_sub(divisor);
+                quotient.set_bit(bit);
+            }
+        }
+        (quotient, remainder)
+    }
+
+    #[inline]
+    fn bit(&self, bit: usize) -> u128 {
+        match bit {
+            0..=127 => self.low >> bit & 1,
+            _ => self.high >> (bit - 128) & 1,
+        }
+    }
+
+    #[inline]
+    fn set_bit(&mut self, bit: usize) {
+        match bit {
+            0..=127 => self.low |= 1 << bit,
+            _ => self.high |= 1 << (bit - 128),
+        }
+    }
+}
+
+impl I256 {
+    pub const ZERO: I256 = I256 { high: 0, low: 0 };
+    pub const MAX: I256 = I256 {
+        high: i128::MAX,
+        low: u128::MAX,
+    };
+    pub const MIN: I256 = I256 {
+        high: i128::MIN,
+        low: 0,
+    };
+
+    /// The number of bytes of the encoding returned by `to_ordered_bytes`.
+    pub const BYTES: usize = 32;
+
+    #[inline]
+    pub fn from_i128(value: i128) -> I256 {
+        I256 {
+            high: if value < 0 { -1 } else { 0 },
+            low: value as u128,
+        }
+    }
+
+    /// Returns None if the value does not fit in an i128.
+    pub fn to_i128(self) -> Option<i128> {
+        let low = self.low as i128;
+        match (self.high, low < 0) {
+            (0, false) | (-1, true) => Some(low),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        self.high < 0
+    }
+
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.high == 0 && self.low == 0
+    }
+
+    fn magnitude(self) -> U256 {
+        let bits = U256 {
+            high: self.high as u128,
+            low: self.low,
+        };
+        match self.is_negative() {
+            true => bits.wrapping_neg(),
+            false => bits,
+        }
+    }
+
+    fn from_magnitude(negative: bool, magnitude: U256) -> Option<I256> {
+        let bits = match negative {
+            true if magnitude <= I256::MIN.magnitude() => magnitude.wrapping_neg(),
+            false if magnitude.high >> 127 == 0 => magnitude,
+            _ => return None,
+        };
+        Some(I256 {
+            high: bits.high as i128,
+            low: bits.low,
+        })
+    }
+
+    pub fn checked_add(self, other: I256) -> Option<I256> {
+        let (low, carry) = self.low.overflowing_add(other.low);
+        let (high, overflow) = self.high.overflowing_add(other.high);
+        let (high, carry_overflow) = high.overflowing_add(carry as i128);
+        match overflow == carry_overflow {
+            true => Some(I256 { high, low }),
+            false => None,
+        }
+    }
+
+    pub fn checked_sub(self, other: I256) -> Option<I256> {
+        let (low, borrow) = self.low.overflowing_sub(other.low);
+        let (high, overflow) = self.high.overflowing_sub(other.high);
+        let (high, borrow_overflow) = high.overflowing_sub(borrow as i128);
+        match overflow == borrow_overflow {
+            true => Some(I256 { high, low }),
+            false => None,
+        }
+    }
+
+    #[inline]
+    pub fn checked_neg(self) -> Option<I256> {
+        I256::ZERO.checked_sub(self)
+    }
+
+    pub fn checked_mul(self, other: I256) -> Option<I256> {
+        let magnitude = self.magnitude().checked_mul(other.magnitude())?;
+        I256::from_magnitude(self.is_negative() != other.is_negative(), magnitude)
+    }
+
+    /// Division truncating toward zero, None on division by zero or overflow.
+    pub fn checked_div(self, other: I256) -> Option<I256> {
+        if other.is_zero() {
+            return None;
+        }
+        let (quotient, _) = self.magnitude().div_rem(other.magnitude());
+        I256::from_magnitude(self.is_negative() != other.is_negative(), quotient)
+    }
+
+    /// The remainder has the sign of the dividend, None on division by zero.
+    pub fn checked_rem(self, other: I256) -> Option<I256> {
+        if other.is_zero() {
+            return None;
+        }
+        let (_, remainder) = self.magnitude().div_rem(other.magnitude());
+        I256::from_magnitude(self.is_negative(), remainder)
+    }
+
+    /// Returns 10 to the power of `exp`, None if it overflows.
+    pub fn checked_pow10(exp: usize) -> Option<I256> {
+        let ten = I256::from_i128(10);
+        (0..exp).try_fold(I256::from_i128(1), |acc, _| acc.checked_mul(ten))
+    }
+
+    /// Compare the absolute values.
+    pub fn cmp_abs(&self, other: &I256) -> Ordering {
+        self.magnitude().cmp(&other.magnitude())
+    }
+
+    /// The decimal digits of the absolute value.
+    pub fn abs_to_string(&self) -> String {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+        let mut magnitude = self.magnitude();
+        let mut chunks = vec![];
+        loop {
+            let (quotient, remainder) = magnitude.div_rem_u64(CHUNK);
+            chunks.push(remainder);
+            magnitude = quotient;
+            if magnitude.is_zero() {
+                break;
+            }
+        }
+
+        let mut digits = chunks.pop().unwrap_or_default().to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:019}", chunk));
+        }
+        digits
+    }
+
+    pub fn to_f64(self) -> f64 {
+        let magnitude = self.magnitude();
+        let value = magnitude.high as f64 * 2_f64.powi(128) + magnitude.low as f64;
+        if self.is_negative() {
+            -value
+        } else {
+            value
+        }
+    }
+
+    /// Convert a float truncating toward zero, None if it is not finite or out of range.
+    pub fn from_f64(value: f64) -> Option<I256> {
+        if !value.is_finite() || value.abs() >= 2_f64.powi(255) {
+            return None;
+        }
+        let abs = value.abs().trunc();
+        let high = (abs / 2_f64.powi(128)).floor();
+        let low = abs - high * 2_f64.powi(128);
+        let magnitude = U256 {
+            high: high as u128,
+            low: low as u128,
+        };
+        I256::from_magnitude(value < 0.0, magnitude)
+    }
+
+    /// Big-endian bytes with the sign bit flipped, so the bytes sort like the values.
+    pub fn to_ordered_bytes(self) -> [u8; 32] {
+        let mut bytes = [0_u8; 32];
+        bytes[..16].copy_from_slice(&((self.high as u128) ^ 1 << 127).to_be_bytes());
+        bytes[16..].copy_from_slice(&self.low.to_be_bytes());
+        bytes
+    }
+
+    /// Decode the bytes of `to_ordered_bytes`, None if the length is not 32.
+    pub fn from_ordered_bytes(bytes: &[u8]) -> Option<I256> {
+        if bytes.len() != I256::BYTES {
+            return None;
+        }
+        let mut high = [0_u8; 16];
+        let mut low = [0_u8; 16];
+        high.copy_from_slice(&bytes[..16]);
+        low.copy_from_slice(&bytes[16..]);
+        Some(I256 {
+            high: (u128::from_be_bytes(high) ^ 1 << 127) as i128,
+            low: u128::from_be_bytes(low),
+        })
+    }
+}
+
+impl From<i128> for I256 {
+    fn from(value: i128) -> Self {
+        I256::from_i128(value)
+    }
+}
+
+impl fmt::Display for I256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.is_negative() { "-" } else { "" };
+        write!(f, "{}{}", sign, self.abs_to_string())
+    }
+}
@@ -16,14 +16,20 @@ mod data_df_type;
 mod data_type;
 mod data_type_coercion;
 mod date_converter;
+mod decimal;
 mod deserializations;
+mod i256;
 mod physical_data_type;
 mod serializations;
+mod variant;
 
 pub use data_df_type::*;
 pub use data_type::*;
 pub use data_type_coercion::*;
 pub use date_converter::*;
+pub use decimal::*;
 pub use deserializations::*;
+pub use i256::*;
 pub use physical_data_type::*;
 pub use serializations::*;
+pub use variant::*;
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::map_entries_type;
 use crate::DataField;
 use crate::DataType;
 
@@ -31,6 +32,7 @@ pub enum PhysicalDataType {
     Int64,
     Float32,
     Float64,
+    Decimal128,
     List(Box<DataField>),
     Struct(Vec<DataField>),
     String,
@@ -49,12 +51,14 @@ impl From<DataType> for PhysicalDataType {
             DataType::Int8 => Int8,
             DataType::Int16 => Int16,
             DataType::Int32 | DataType::Date32 => Int32,
-            DataType::Int64 => Int64,
+            DataType::Int64 | DataType::DateTime64(_, _) => Int64,
             DataType::Float32 => Float32,
             DataType::Float64 => Float64,
+            DataType::Decimal128(_, _) => Decimal128,
             DataType::List(x) => List(x),
             DataType::Struct(x) => Struct(x),
-            DataType::String => String,
+            DataType::Map(key_type, value_type) => map_entries_type(&key_type, &value_type).into(),
+            DataType::String | DataType::Variant | DataType::Decimal256(_, _) => String,
             DataType::Interval(_) => Int64,
         }
     }
@@ -76,6 +80,7 @@ impl From<PhysicalDataType> for DataType {
             PhysicalDataType::Int64 => Int64,
             PhysicalDataType::Float32 => Float32,
             PhysicalDataType::Float64 => Float64,
+            PhysicalDataType::Decimal128 => Decimal128(38, 0),
             PhysicalDataType::List(x) => List(x),
             PhysicalDataType::Struct(x) => Struct(x),
             PhysicalDataType::String => String,
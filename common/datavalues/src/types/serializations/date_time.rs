@@ -13,22 +13,22 @@
 // limitations under the License.
 
 use std::marker::PhantomData;
-use std::ops::AddAssign;
 
-use chrono::Duration;
-use chrono::NaiveDateTime;
+use chrono_tz::Tz;
 use common_exception::*;
 
 use crate::prelude::*;
 
 pub struct DateTimeSerializer<T: DFPrimitiveType> {
     t: PhantomData<T>,
+    tz: Tz,
 }
 
-impl<T: DFPrimitiveType> Default for DateTimeSerializer<T> {
-    fn default() -> Self {
+impl<T: DFPrimitiveType> DateTimeSerializer<T> {
+    pub fn create(tz: Tz) -> Self {
         Self {
             t: Default::default(),
+            tz,
         }
     }
 }
@@ -39,9 +39,7 @@ impl<T: DFPrimitiveType> TypeSerializer for DateTimeSerializer<T> {
             return Ok("NULL".to_owned());
         }
 
-        let mut dt = NaiveDateTime::from_timestamp(0, 0);
-        let d = Duration::seconds(value.as_i64()?);
-        dt.add_assign(d);
+        let dt = value.as_i64()?.to_date_time(&self.tz);
         Ok(dt.format("%Y-%m-%d %H:%M:%S").to_string())
     }
 
@@ -53,9 +51,7 @@ impl<T: DFPrimitiveType> TypeSerializer for DateTimeSerializer<T> {
             .iter()
             .map(|x| {
                 x.map(|v| {
-                    let mut dt = NaiveDateTime::from_timestamp(0, 0);
-                    let d = Duration::seconds(v.to_i64().unwrap());
-                    dt.add_assign(d);
+                    let dt = v.to_i64().unwrap().to_date_time(&self.tz);
                     dt.format("%Y-%m-%d %H:%M:%S").to_string()
                 })
                 .unwrap_or_else(|| "NULL".to_owned())
@@ -64,3 +60,35 @@ impl<T: DFPrimitiveType> TypeSerializer for DateTimeSerializer<T> {
         Ok(result)
     }
 }
+
+pub struct DateTime64Serializer {
+    pub precision: u32,
+    pub tz: Tz,
+}
+
+impl TypeSerializer for DateTime64Serializer {
+    fn serialize_value(&self, value: &DataValue) -> Result<String> {
+        if value.is_null() {
+            return Ok("NULL".to_owned());
+        }
+        Ok(datetime64_to_string(
+            value.as_i64()?,
+            self.precision,
+            &self.tz,
+        ))
+    }
+
+    fn serialize_column(&self, column: &DataColumn) -> Result<Vec<String>> {
+        let array = column.to_array()?;
+        let array = array.i64()?;
+
+        let result: Vec<String> = array
+            .iter()
+            .map(|x| {
+                x.map(|v| datetime64_to_string(*v, self.precision, &self.tz))
+                    .unwrap_or_else(|| "NULL".to_owned())
+            })
+            .collect();
+        Ok(result)
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use crate::prelude::*;
+
+pub struct DecimalSerializer {
+    pub scale: usize,
+}
+
+impl TypeSerializer for DecimalSerializer {
+    fn serialize_value(&self, value: &DataValue) -> Result<String> {
+        match value {
+            DataValue::Decimal128(Some(v)) => Ok(decimal_to_string(*v, self.scale)),
+            _ => Ok("NULL".to_owned()),
+        }
+    }
+
+    fn serialize_column(&self, column: &DataColumn) -> Result<Vec<String>> {
+        let array = column.to_array()?;
+        let array = array.decimal128()?;
+
+        let result: Vec<String> = array
+            .iter()
+            .map(|x| {
+                x.map(|v| decimal_to_string(*v, self.scale))
+                    .unwrap_or_else(|| "NULL".to_owned())
+            })
+            .collect();
+        Ok(result)
+    }
+}
+
+pub struct Decimal256Serializer {
+    pub scale: usize,
+}
+
+impl TypeSerializer for Decimal256Serializer {
+    fn serialize_value(&self, value: &DataValue) -> Result<String> {
+        match value {
+            DataValue::String(Some(v)) => {
+                Ok(decimal256_to_string(decimal256_from_bytes(v)?, self.scale))
+            }
+            _ => Ok("NULL".to_owned()),
+        }
+    }
+
+    fn serialize_column(&self, column: &DataColumn) -> Result<Vec<String>> {
+        let array = column.to_array()?;
+        let array = array.string()?;
+
+        array
+            .into_iter()
+            .map(|x| match x {
+                Some(v) => Ok(decimal256_to_string(decimal256_from_bytes(v)?, self.scale)),
+                None => Ok("NULL".to_owned()),
+            })
+            .collect()
+    }
+}
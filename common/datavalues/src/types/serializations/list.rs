@@ -0,0 +1,62 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::prelude::*;
+
+pub struct ListSerializer {
+    pub inner: DataType,
+}
+
+impl TypeSerializer for ListSerializer {
+    fn serialize_value(&self, value: &DataValue) -> Result<String> {
+        match value {
+            DataValue::List(None, _) => Ok("NULL".to_string()),
+            DataValue::List(Some(items), _) => {
+                let items = items
+                    .iter()
+                    .map(|item| serialize_nested_item(&self.inner, item))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(format!("[{}]", items.join(",")))
+            }
+            _ => Err(ErrorCode::BadBytes("Incorrect List value")),
+        }
+    }
+
+    fn serialize_column(&self, column: &DataColumn) -> Result<Vec<String>> {
+        let array = column.to_array()?;
+        (0..array.len())
+            .map(|row| self.serialize_value(&array.try_get(row)?))
+            .collect()
+    }
+}
+
+/// Serialize an item of a nested value, string and date items are quoted.
+pub(crate) fn serialize_nested_item(data_type: &DataType, value: &DataValue) -> Result<String> {
+    if value.is_null() {
+        return Ok("NULL".to_string());
+    }
+
+    let s = data_type.create_serializer().serialize_value(value)?;
+    match data_type {
+        DataType::String
+        | DataType::Date16
+        | DataType::Date32
+        | DataType::DateTime32(_)
+        | DataType::DateTime64(_, _) => Ok(format!("'{}'", s)),
+        _ => Ok(s),
+    }
+}
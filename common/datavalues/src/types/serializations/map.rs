@@ -0,0 +1,54 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use super::list::serialize_nested_item;
+use crate::prelude::*;
+
+pub struct MapSerializer {
+    pub key_type: DataType,
+    pub value_type: DataType,
+}
+
+impl TypeSerializer for MapSerializer {
+    fn serialize_value(&self, value: &DataValue) -> Result<String> {
+        match value {
+            DataValue::List(None, _) => Ok("NULL".to_string()),
+            DataValue::List(Some(entries), _) => {
+                let mut items = Vec::with_capacity(entries.len());
+                for entry in entries {
+                    match entry {
+                        DataValue::Struct(kv) if kv.len() == 2 => items.push(format!(
+                            "{}:{}",
+                            serialize_nested_item(&self.key_type, &kv[0])?,
+                            serialize_nested_item(&self.value_type, &kv[1])?
+                        )),
+                        _ => return Err(ErrorCode::BadBytes("Incorrect Map entry")),
+                    }
+                }
+                Ok(format!("{{{}}}", items.join(",")))
+            }
+            _ => Err(ErrorCode::BadBytes("Incorrect Map value")),
+        }
+    }
+
+    fn serialize_column(&self, column: &DataColumn) -> Result<Vec<String>> {
+        let array = column.to_array()?;
+        (0..array.len())
+            .map(|row| self.serialize_value(&array.try_get(row)?))
+            .collect()
+    }
+}
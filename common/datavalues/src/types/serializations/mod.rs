@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use chrono_tz::Tz;
 use common_exception::Result;
 
 use crate::prelude::*;
@@ -19,6 +20,9 @@ use crate::prelude::*;
 mod boolean;
 mod date;
 mod date_time;
+mod decimal;
+mod list;
+mod map;
 mod nulls;
 mod number;
 mod string;
@@ -27,6 +31,9 @@ mod r#struct;
 pub use boolean::*;
 pub use date::*;
 pub use date_time::*;
+pub use decimal::*;
+pub use list::*;
+pub use map::*;
 pub use nulls::*;
 pub use number::*;
 pub use r#struct::*;
@@ -52,13 +59,29 @@ impl DataType {
             DataType::Int64 => Box::new(NumberSerializer::<i64>::default()),
             DataType::Float32 => Box::new(NumberSerializer::<f32>::default()),
             DataType::Float64 => Box::new(NumberSerializer::<f64>::default()),
+            DataType::Decimal128(_, scale) => Box::new(DecimalSerializer { scale: *scale }),
+            DataType::Decimal256(_, scale) => Box::new(Decimal256Serializer { scale: *scale }),
             DataType::Date16 => Box::new(DateSerializer::<u16>::default()),
             DataType::Date32 => Box::new(DateSerializer::<i32>::default()),
-            DataType::DateTime32(_) => Box::new(DateTimeSerializer::<u32>::default()),
-            DataType::String => Box::new(StringSerializer {}),
+            DataType::DateTime32(tz) => {
+                let tz = get_timezone(tz).unwrap_or(Tz::UTC);
+                Box::new(DateTimeSerializer::<u32>::create(tz))
+            }
+            DataType::DateTime64(precision, tz) => Box::new(DateTime64Serializer {
+                precision: *precision,
+                tz: get_timezone(tz).unwrap_or(Tz::UTC),
+            }),
+            DataType::String | DataType::Variant => Box::new(StringSerializer {}),
             DataType::Struct(fields) => Box::new(StructSerializer {
                 fields: fields.to_vec(),
             }),
+            DataType::List(field) => Box::new(ListSerializer {
+                inner: field.data_type().clone(),
+            }),
+            DataType::Map(key_type, value_type) => Box::new(MapSerializer {
+                key_type: key_type.as_ref().clone(),
+                value_type: value_type.as_ref().clone(),
+            }),
             _ => todo!(),
         }
     }
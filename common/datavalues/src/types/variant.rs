@@ -0,0 +1,129 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde_json::Value as JsonValue;
+
+use crate::prelude::*;
+
+/// A step of a path into a Variant value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum VariantPathElem {
+    Field(String),
+    Index(usize),
+}
+
+/// Parse a JSON text, the value is stored in its compact form.
+pub fn variant_from_slice(bytes: &[u8]) -> Result<Vec<u8>> {
+    let value: JsonValue = serde_json::from_slice(bytes)
+        .map_err(|e| ErrorCode::BadBytes(format!("Cannot parse value to Variant type: {}", e)))?;
+    Ok(value.to_string().into_bytes())
+}
+
+/// The text of a Variant as a scalar, strings are unquoted and JSON null is NULL.
+pub fn variant_to_string(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::Null => None,
+        JsonValue::String(v) => Some(v.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Parse a path like `a.b[0]` or `a["b.c"]`, the empty path is the value itself.
+pub fn parse_variant_path(path: &str) -> Result<Vec<VariantPathElem>> {
+    let invalid = || ErrorCode::BadArguments(format!("Invalid Variant path: {}", path));
+
+    let mut elems = vec![];
+    let mut rest = path.trim();
+    while !rest.is_empty() {
+        if let Some(tail) = rest.strip_prefix('[') {
+            let end = tail.find(']').ok_or_else(invalid)?;
+            let key = tail[..end].trim();
+            let quoted = key.len() >= 2
+                && ((key.starts_with('"') && key.ends_with('"'))
+                    || (key.starts_with('\'') && key.ends_with('\'')));
+            match quoted {
+                true => elems.push(VariantPathElem::Field(key[1..key.len() - 1].to_string())),
+                false => elems.push(VariantPathElem::Index(
+                    key.parse::<usize>().map_err(|_| invalid())?,
+                )),
+            }
+            rest = &tail[end + 1..];
+        } else {
+            if !elems.is_empty() {
+                rest = rest.strip_prefix('.').ok_or_else(invalid)?;
+            }
+            let end = rest.find(|c| c == '.' || c == '[').unwrap_or(rest.len());
+            if end == 0 {
+                return Err(invalid());
+            }
+            elems.push(VariantPathElem::Field(rest[..end].to_string()));
+            rest = &rest[end..];
+        }
+    }
+    Ok(elems)
+}
+
+/// Get the value at the path, None if the path doesn't exist.
+pub fn variant_get_path<'a>(
+    value: &'a JsonValue,
+    path: &[VariantPathElem],
+) -> Option<&'a JsonValue> {
+    path.iter().try_fold(value, |value, elem| match elem {
+        VariantPathElem::Field(name) => value.get(name.as_str()),
+        VariantPathElem::Index(index) => value.get(*index),
+    })
+}
+
+/// Cast a series whose logical type is `from_type` to Variant.
+/// Strings are parsed as JSON texts, other scalars become JSON numbers, booleans or strings.
+pub fn cast_series_to_variant(series: &Series, from_type: &DataType) -> Result<Series> {
+    match from_type {
+        DataType::String => {
+            let values = series
+                .string()?
+                .into_iter()
+                .map(|v| v.map(variant_from_slice).transpose())
+                .collect::<Result<Vec<_>>>()?;
+            Ok(DFStringArray::new_from_opt_iter(values.into_iter()).into_series())
+        }
+        DataType::Null => Ok(DFStringArray::full_null(series.len()).into_series()),
+        t if t.is_numeric() || t == &DataType::Boolean => {
+            // NaN and infinity are not valid JSON numbers, they become null
+            let strings = series.cast_with_type(&DataType::String)?;
+            let values = strings.string()?.into_iter().map(|v| {
+                v.map(|v| {
+                    serde_json::from_slice::<JsonValue>(v)
+                        .unwrap_or(JsonValue::Null)
+                        .to_string()
+                })
+            });
+            Ok(DFStringArray::new_from_opt_iter(values).into_series())
+        }
+        other => Err(ErrorCode::BadDataValueType(format!(
+            "Unsupported cast from {:?} to Variant",
+            other
+        ))),
+    }
+}
+
+/// Cast a Variant series to the scalar text of its values.
+pub fn cast_series_from_variant(series: &Series) -> Result<Series> {
+    let values = series.string()?.into_iter().map(|v| {
+        v.and_then(|v| serde_json::from_slice::<JsonValue>(v).ok())
+            .and_then(|v| variant_to_string(&v))
+    });
+    Ok(DFStringArray::new_from_opt_iter(values).into_series())
+}
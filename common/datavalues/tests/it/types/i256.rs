@@ -0,0 +1,81 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+#[test]
+fn test_i256_arithmetic() {
+    let max_i128 = I256::from_i128(i128::MAX);
+    let square = max_i128.checked_mul(max_i128).unwrap();
+    assert_eq!(
+        square.to_string(),
+        "28948022309329048855892746252171976962977213799489202546401021394546514198529"
+    );
+    assert_eq!(square.checked_div(max_i128), Some(max_i128));
+    assert_eq!(square.to_i128(), None);
+
+    let minus_seven = I256::from_i128(-7);
+    let two = I256::from_i128(2);
+    assert_eq!(minus_seven.checked_div(two), Some(I256::from_i128(-3)));
+    assert_eq!(minus_seven.checked_rem(two), Some(I256::from_i128(-1)));
+    assert_eq!(minus_seven.checked_div(I256::ZERO), None);
+
+    assert_eq!(I256::MAX.checked_add(I256::from_i128(1)), None);
+    assert_eq!(I256::MIN.checked_sub(I256::from_i128(1)), None);
+    assert_eq!(I256::MIN.checked_neg(), None);
+    assert_eq!(
+        I256::from_i128(-1).checked_add(I256::from_i128(1)),
+        Some(I256::ZERO)
+    );
+    assert_eq!(I256::checked_pow10(77), None);
+}
+
+#[test]
+fn test_i256_ordered_bytes() {
+    let values = vec![
+        I256::MIN,
+        I256::checked_pow10(76).unwrap().checked_neg().unwrap(),
+        I256::from_i128(-7),
+        I256::ZERO,
+        I256::from_i128(i128::MAX),
+        I256::MAX,
+    ];
+
+    for pair in values.windows(2) {
+        assert!(pair[0] < pair[1]);
+        assert!(pair[0].to_ordered_bytes() < pair[1].to_ordered_bytes());
+    }
+    for value in values {
+        assert_eq!(
+            I256::from_ordered_bytes(&value.to_ordered_bytes()),
+            Some(value)
+        );
+    }
+}
+
+#[test]
+fn test_decimal256_from_str() -> Result<()> {
+    let value = decimal256_from_str("-12345678901234567890123456789012345678901.235", 50, 2)?;
+    assert_eq!(
+        decimal256_to_string(value, 2),
+        "-12345678901234567890123456789012345678901.24"
+    );
+    assert_eq!(decimal256_to_f64(I256::from_i128(-105), 2), -1.05);
+    assert!(decimal256_from_str("1e3", 50, 2).is_err());
+    assert!(decimal256_from_str("123456789", 10, 2).is_err());
+    assert_eq!(check_decimal_type(50, 2)?, DataType::Decimal256(50, 2));
+    assert!(check_decimal_type(77, 2).is_err());
+    Ok(())
+}
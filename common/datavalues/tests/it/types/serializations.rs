@@ -55,6 +55,18 @@ fn test_serializers() -> Result<()> {
                 "NULL".to_owned(),
             ],
         },
+        Test {
+            name: "datetime64",
+            data_type: DataType::DateTime64(3, None),
+            value: DataValue::Int64(Some(1630320462123)),
+            column: Series::new(vec![Some(1630320462123i64), Some(1637117572001i64), None]).into(),
+            val_str: "2021-08-30 10:47:42.123",
+            col_str: vec![
+                "2021-08-30 10:47:42.123".to_owned(),
+                "2021-11-17 02:52:52.001".to_owned(),
+                "NULL".to_owned(),
+            ],
+        },
         Test {
             name: "date32",
             data_type: DataType::Date32,
@@ -67,6 +79,31 @@ fn test_serializers() -> Result<()> {
                 "NULL".to_owned(),
             ],
         },
+        Test {
+            name: "decimal128",
+            data_type: DataType::Decimal128(10, 2),
+            value: DataValue::Decimal128(Some(-105)),
+            column: Series::new(vec![Some(12345i128), Some(7i128), None]).into(),
+            val_str: "-1.05",
+            col_str: vec!["123.45".to_owned(), "0.07".to_owned(), "NULL".to_owned()],
+        },
+        Test {
+            name: "decimal256",
+            data_type: DataType::Decimal256(50, 2),
+            value: DataValue::String(Some(I256::from_i128(-105).to_ordered_bytes().to_vec())),
+            column: Series::new(vec![
+                Some(I256::checked_pow10(40).unwrap().to_ordered_bytes().to_vec()),
+                Some(I256::from_i128(7).to_ordered_bytes().to_vec()),
+                None,
+            ])
+            .into(),
+            val_str: "-1.05",
+            col_str: vec![
+                "100000000000000000000000000000000000000.00".to_owned(),
+                "0.07".to_owned(),
+                "NULL".to_owned(),
+            ],
+        },
         Test {
             name: "string",
             data_type: DataType::String,
@@ -75,6 +112,14 @@ fn test_serializers() -> Result<()> {
             val_str: "hello",
             col_str: vec!["hello".to_owned(), "world".to_owned(), "NULL".to_owned()],
         },
+        Test {
+            name: "variant",
+            data_type: DataType::Variant,
+            value: DataValue::String(Some(r#"{"a":1}"#.as_bytes().to_vec())),
+            column: Series::new(vec![Some("[1,2]"), Some(r#""s""#), None]).into(),
+            val_str: r#"{"a":1}"#,
+            col_str: vec!["[1,2]".to_owned(), r#""s""#.to_owned(), "NULL".to_owned()],
+        },
     ];
 
     for test in tests {
@@ -105,5 +150,38 @@ fn test_serializers() -> Result<()> {
         assert_eq!(&result, expect);
     }
 
+    {
+        let data_type = DataType::List(Box::new(DataField::new("item", DataType::String, true)));
+        let serializer = data_type.create_serializer();
+        let value = DataValue::List(
+            Some(vec![
+                DataValue::String(Some("a".as_bytes().to_vec())),
+                DataValue::String(None),
+            ]),
+            DataType::String,
+        );
+        let result = serializer.serialize_value(&value)?;
+        let expect = "['a',NULL]";
+        assert_eq!(&result, expect);
+    }
+
+    {
+        let data_type = DataType::Map(Box::new(DataType::String), Box::new(DataType::Int64));
+        let serializer = data_type.create_serializer();
+        let entry = |k: &str, v: Option<i64>| {
+            DataValue::Struct(vec![
+                DataValue::String(Some(k.as_bytes().to_vec())),
+                DataValue::Int64(v),
+            ])
+        };
+        let value = DataValue::List(
+            Some(vec![entry("a", Some(1)), entry("b", None)]),
+            DataType::Struct(vec![]),
+        );
+        let result = serializer.serialize_value(&value)?;
+        let expect = "{'a':1,'b':NULL}";
+        assert_eq!(&result, expect);
+    }
+
     Ok(())
 }
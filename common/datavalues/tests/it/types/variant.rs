@@ -0,0 +1,46 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+#[test]
+fn test_parse_variant_path() -> Result<()> {
+    use VariantPathElem::*;
+
+    let tests = vec![
+        ("a", vec![Field("a".to_string())]),
+        ("a.b[0]", vec![
+            Field("a".to_string()),
+            Field("b".to_string()),
+            Index(0),
+        ]),
+        (r#"[1]["b.c"].d"#, vec![
+            Index(1),
+            Field("b.c".to_string()),
+            Field("d".to_string()),
+        ]),
+        ("['x']", vec![Field("x".to_string())]),
+    ];
+
+    for (path, expect) in tests {
+        assert_eq!(parse_variant_path(path)?, expect, "{}", path);
+    }
+
+    for path in ["a..b", "a[x]", "a[0", ".a"] {
+        assert!(parse_variant_path(path).is_err(), "{}", path);
+    }
+
+    Ok(())
+}
@@ -188,6 +188,7 @@ build_exceptions! {
     SHA1CheckFailed(57),
     UnknownColumn(58),
     InvalidSourceFormat(59),
+    MemoryLimitExceeded(60),
 
     // uncategorized
     UnexpectedResponseType(600),
@@ -292,6 +293,9 @@ build_exceptions! {
     UnknownDatabaseEngine(8001),
     UnknownTableEngine(8002),
     DuplicatedDatabaseEngineProvider(8003),
+    TableHistoricalDataNotFound(8004),
+    OCCRetryFailure(8005),
+    UnknownFormatVersion(8006),
 
     // http query error
     HttpNotFound(9404),
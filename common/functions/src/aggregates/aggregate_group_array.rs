@@ -0,0 +1,187 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::alloc::Layout;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+use std::sync::Arc;
+
+use bytes::BytesMut;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_io::prelude::*;
+
+use super::StateAddr;
+use crate::aggregates::aggregate_function_factory::AggregateFunctionDescription;
+use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::AggregateFunction;
+
+pub struct AggregateGroupArrayState {
+    values: Vec<DataValue>,
+    // The distinct values of groupUniqArray.
+    set: HashSet<DataGroupValue>,
+}
+
+impl AggregateGroupArrayState {
+    fn add(&mut self, value: DataValue, uniq: bool) -> Result<()> {
+        if value.is_null() {
+            return Ok(());
+        }
+
+        if !uniq || self.set.insert(DataGroupValue::try_from(&value)?) {
+            self.values.push(value);
+        }
+        Ok(())
+    }
+}
+
+/// `groupArray(x)` collects the values into an array, `groupUniqArray(x)` collects the
+/// distinct values. NULLs are skipped.
+#[derive(Clone)]
+pub struct AggregateGroupArrayFunction {
+    display_name: String,
+    arguments: Vec<DataField>,
+    uniq: bool,
+}
+
+impl AggregateGroupArrayFunction {
+    pub fn try_create(
+        display_name: &str,
+        _params: Vec<DataValue>,
+        arguments: Vec<DataField>,
+    ) -> Result<Arc<dyn AggregateFunction>> {
+        Self::try_create_with_uniq(display_name, arguments, false)
+    }
+
+    pub fn try_create_uniq(
+        display_name: &str,
+        _params: Vec<DataValue>,
+        arguments: Vec<DataField>,
+    ) -> Result<Arc<dyn AggregateFunction>> {
+        Self::try_create_with_uniq(display_name, arguments, true)
+    }
+
+    fn try_create_with_uniq(
+        display_name: &str,
+        arguments: Vec<DataField>,
+        uniq: bool,
+    ) -> Result<Arc<dyn AggregateFunction>> {
+        assert_unary_arguments(display_name, arguments.len())?;
+        Ok(Arc::new(AggregateGroupArrayFunction {
+            display_name: display_name.to_string(),
+            arguments,
+            uniq,
+        }))
+    }
+
+    pub fn desc() -> AggregateFunctionDescription {
+        AggregateFunctionDescription::creator(Box::new(Self::try_create))
+    }
+
+    pub fn uniq_desc() -> AggregateFunctionDescription {
+        AggregateFunctionDescription::creator(Box::new(Self::try_create_uniq))
+    }
+}
+
+impl AggregateFunction for AggregateGroupArrayFunction {
+    fn name(&self) -> &str {
+        "AggregateGroupArrayFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        let item = DataField::new("item", self.arguments[0].data_type().clone(), true);
+        Ok(DataType::List(Box::new(item)))
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(|| AggregateGroupArrayState {
+            values: vec![],
+            set: HashSet::new(),
+        });
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateGroupArrayState>()
+    }
+
+    fn accumulate(&self, place: StateAddr, arrays: &[Series], input_rows: usize) -> Result<()> {
+        let state = place.get::<AggregateGroupArrayState>();
+        for row in 0..input_rows {
+            state.add(arrays[0].try_get(row)?, self.uniq)?;
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        arrays: &[Series],
+        _input_rows: usize,
+    ) -> Result<()> {
+        for (row, place) in places.iter().enumerate() {
+            let place = place.next(offset);
+            let state = place.get::<AggregateGroupArrayState>();
+            state.add(arrays[0].try_get(row)?, self.uniq)?;
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut BytesMut) -> Result<()> {
+        let state = place.get::<AggregateGroupArrayState>();
+        writer.write_uvarint(state.values.len() as u64)?;
+        for value in state.values.iter() {
+            value.serialize_to_buf(writer)?;
+        }
+        Ok(())
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateGroupArrayState>();
+        state.values.clear();
+        state.set.clear();
+
+        let size = reader.read_uvarint()?;
+        for _i in 0..size {
+            state.add(DataValue::deserialize(reader)?, self.uniq)?;
+        }
+        Ok(())
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = place.get::<AggregateGroupArrayState>();
+        let rhs = rhs.get::<AggregateGroupArrayState>();
+        for value in rhs.values.iter() {
+            state.add(value.clone(), self.uniq)?;
+        }
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = place.get::<AggregateGroupArrayState>();
+        let item_type = data_type_physical(self.arguments[0].data_type().clone());
+        Ok(DataValue::List(Some(state.values.clone()), item_type))
+    }
+}
+
+impl fmt::Display for AggregateGroupArrayFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -82,6 +82,23 @@ impl Function for ArithmeticFunction {
     }
 
     fn eval(&self, columns: &DataColumnsWithField, _input_rows: usize) -> Result<DataColumn> {
+        // Decimal arithmetic needs the precision and scale kept in the fields.
+        if columns.iter().any(|c| c.data_type().is_decimal()) {
+            let lhs = columns[0].column().to_array()?;
+            let result = match (columns.len(), &self.op) {
+                (1, Minus) => decimal_negative(&lhs, columns[0].data_type())?,
+                (1, _) => lhs,
+                _ => decimal_arithmetic(
+                    &self.op,
+                    &lhs,
+                    columns[0].data_type(),
+                    &columns[1].column().to_array()?,
+                    columns[1].data_type(),
+                )?,
+            };
+            return Ok(result.into());
+        }
+
         let result: DataColumn = {
             // Some logic type need DateType information, try arithmetic on column with field first.
             if let Some(f) = IntervalFunctionFactory::try_get_arithmetic_func(columns) {
@@ -0,0 +1,183 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::CastFunction;
+use crate::scalars::Function;
+
+/// `array(x1, x2, ...)` builds an array of the arguments, they are casted to their common type.
+/// `array()` is an empty array of NULL items.
+#[derive(Clone)]
+pub struct ArrayFunction {
+    display_name: String,
+}
+
+impl ArrayFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(ArrayFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic())
+    }
+}
+
+impl Function for ArrayFunction {
+    fn name(&self) -> &str {
+        &*self.display_name
+    }
+
+    fn variadic_arguments(&self) -> Option<(usize, usize)> {
+        Some((0, usize::MAX))
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        let item_type = match args.is_empty() {
+            true => DataType::Null,
+            false => aggregate_types(args)?,
+        };
+        let item = DataField::new("item", item_type, true);
+        Ok(DataType::List(Box::new(item)))
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, input_rows: usize) -> Result<DataColumn> {
+        let values = match columns.is_empty() {
+            true => DataValue::Null.to_series_with_size(0)?,
+            false => {
+                let args = columns
+                    .iter()
+                    .map(|column| column.data_type().clone())
+                    .collect::<Vec<_>>();
+                let item_type = aggregate_types(&args)?;
+
+                let cast = CastFunction::create("cast".to_string(), item_type)?;
+                let items = columns
+                    .iter()
+                    .map(|column| cast.eval(&[column.clone()], input_rows))
+                    .collect::<Result<Vec<_>>>()?;
+                DataColumnCommon::concat(&items)?.to_array()?
+            }
+        };
+
+        // The i-th argument of the row is at `i * input_rows + row` of the concatenated values.
+        let size = columns.len();
+        let positions = (0..input_rows)
+            .flat_map(|row| (0..size).map(move |i| i * input_rows + row))
+            .collect::<Vec<_>>();
+        let offsets = (0..=input_rows).map(|row| (row * size) as i64).collect();
+
+        let array = take_list(&values, positions, offsets, None)?;
+        Ok(array.into_series().into())
+    }
+}
+
+impl fmt::Display for ArrayFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
+/// The item type of the array argument.
+pub(crate) fn array_item_type<'a>(display_name: &str, arg: &'a DataType) -> Result<&'a DataType> {
+    match arg {
+        DataType::List(item) => Ok(item.data_type()),
+        other => Err(ErrorCode::IllegalDataType(format!(
+            "Expected array type as the argument of {}, but got {}",
+            display_name, other
+        ))),
+    }
+}
+
+pub(crate) fn array_arg(column: &DataColumnWithField) -> Result<DFListArray> {
+    let series = column.column().to_array()?;
+    match series.data_type() {
+        DataType::List(_) => Ok(DFListArray::from_arrow_array(
+            series.get_array_ref().as_ref(),
+        )),
+        other => Err(ErrorCode::IllegalDataType(format!(
+            "Expected array type, but got {}",
+            other
+        ))),
+    }
+}
+
+/// Cast the flattened items of an array to the data type.
+pub(crate) fn cast_items(items: &Series, item_type: &DataType, to: &DataType) -> Result<Series> {
+    if item_type == to {
+        return Ok(items.clone());
+    }
+
+    let field = DataField::new("item", item_type.clone(), true);
+    let column = DataColumnWithField::new(items.clone().into(), field);
+    let cast = CastFunction::create("cast".to_string(), to.clone())?;
+    cast.eval(&[column], items.len())?.to_array()
+}
+
+/// Build an array of the values at the positions,
+/// the items of the row `i` are the positions in `offsets[i]..offsets[i + 1]`.
+pub(crate) fn take_list(
+    values: &Series,
+    positions: Vec<usize>,
+    offsets: Vec<i64>,
+    validity: Option<Bitmap>,
+) -> Result<DFListArray> {
+    let mut positions = positions.into_iter();
+    let values = unsafe { values.take_iter_unchecked(&mut positions) }?;
+    Ok(DFListArray::from_parts(&values, offsets, validity))
+}
+
+/// The 1-based position of the needle in each row of the array, 0 if the needle doesn't exist.
+/// The needle is casted to the item type first.
+pub(crate) fn find_array_items(
+    array: &DFListArray,
+    needle: &DataColumnWithField,
+    input_rows: usize,
+) -> Result<Vec<Option<u64>>> {
+    let cast = CastFunction::create("cast".to_string(), array.sub_data_type().clone())?;
+    let needles = cast.eval(&[needle.clone()], input_rows)?.to_array()?;
+
+    let values = array.values();
+    let offsets = array.offsets();
+    (0..array.len())
+        .map(|row| {
+            let needle = needles.try_get(row)?;
+            if array.is_null(row) || needle.is_null() {
+                return Ok(None);
+            }
+
+            let (start, end) = (offsets[row] as usize, offsets[row + 1] as usize);
+            for index in start..end {
+                if values.try_get(index)? == needle {
+                    return Ok(Some((index - start + 1) as u64));
+                }
+            }
+            Ok(Some(0))
+        })
+        .collect()
+}
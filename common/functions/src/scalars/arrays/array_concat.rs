@@ -0,0 +1,134 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use super::array::array_arg;
+use super::array::array_item_type;
+use super::array::cast_items;
+use super::array::take_list;
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::Function;
+
+/// `arrayConcat(array1, array2, ...)` concatenates the arrays, the result is NULL if any of
+/// the arrays is NULL.
+#[derive(Clone)]
+pub struct ArrayConcatFunction {
+    display_name: String,
+}
+
+impl ArrayConcatFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(ArrayConcatFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic())
+    }
+
+    fn item_type(&self, args: &[DataType]) -> Result<DataType> {
+        let item_types = args
+            .iter()
+            .map(|arg| array_item_type(&self.display_name, arg).cloned())
+            .collect::<Result<Vec<_>>>()?;
+        aggregate_types(&item_types)
+    }
+}
+
+impl Function for ArrayConcatFunction {
+    fn name(&self) -> &str {
+        &*self.display_name
+    }
+
+    fn variadic_arguments(&self) -> Option<(usize, usize)> {
+        Some((1, usize::MAX))
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        let item = DataField::new("item", self.item_type(args)?, true);
+        Ok(DataType::List(Box::new(item)))
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, input_rows: usize) -> Result<DataColumn> {
+        let args = columns
+            .iter()
+            .map(|column| column.data_type().clone())
+            .collect::<Vec<_>>();
+        let item_type = self.item_type(&args)?;
+
+        let arrays = columns.iter().map(array_arg).collect::<Result<Vec<_>>>()?;
+        let items = arrays
+            .iter()
+            .zip(args.iter())
+            .map(|(array, arg)| {
+                let items = array.values();
+                let items = cast_items(
+                    &items,
+                    array_item_type(&self.display_name, arg)?,
+                    &item_type,
+                )?;
+                Ok(DataColumn::Array(items))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // The items of the i-th array start at `bases[i]` of the concatenated values.
+        let mut bases = Vec::with_capacity(items.len());
+        let mut base = 0;
+        for item in &items {
+            bases.push(base);
+            base += item.len();
+        }
+        let values = DataColumnCommon::concat(&items)?.to_array()?;
+
+        let mut positions = vec![];
+        let mut offsets = Vec::with_capacity(input_rows + 1);
+        offsets.push(0);
+        for row in 0..input_rows {
+            for (array, base) in arrays.iter().zip(bases.iter()) {
+                let array_offsets = array.offsets();
+                let (start, end) = (array_offsets[row] as usize, array_offsets[row + 1] as usize);
+                positions.extend((start..end).map(|index| base + index));
+            }
+            offsets.push(positions.len() as i64);
+        }
+
+        let validity = match arrays.iter().any(|array| array.null_count() > 0) {
+            true => Some(Bitmap::from_iter(
+                (0..input_rows).map(|row| arrays.iter().all(|array| !array.is_null(row))),
+            )),
+            false => None,
+        };
+
+        let array = take_list(&values, positions, offsets, validity)?;
+        Ok(array.into_series().into())
+    }
+}
+
+impl fmt::Display for ArrayConcatFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
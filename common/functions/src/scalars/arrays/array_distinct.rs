@@ -0,0 +1,96 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use super::array::array_arg;
+use super::array::array_item_type;
+use super::array::take_list;
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::Function;
+
+/// `arrayDistinct(array)` returns the distinct elements of the array in the order of their first
+/// occurrence, NULLs are removed.
+#[derive(Clone)]
+pub struct ArrayDistinctFunction {
+    display_name: String,
+}
+
+impl ArrayDistinctFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(ArrayDistinctFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic())
+    }
+}
+
+impl Function for ArrayDistinctFunction {
+    fn name(&self) -> &str {
+        &*self.display_name
+    }
+
+    fn num_arguments(&self) -> usize {
+        1
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        array_item_type(&self.display_name, &args[0])?;
+        Ok(args[0].clone())
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, _input_rows: usize) -> Result<DataColumn> {
+        let array = array_arg(&columns[0])?;
+        let values = array.values();
+        let array_offsets = array.offsets();
+
+        let mut positions = vec![];
+        let mut offsets = Vec::with_capacity(array.len() + 1);
+        offsets.push(0);
+        for row in 0..array.len() {
+            let (start, end) = (array_offsets[row] as usize, array_offsets[row + 1] as usize);
+            let mut distinct_values = Vec::new();
+            for index in start..end {
+                let value = values.try_get(index)?;
+                if !value.is_null() && !distinct_values.contains(&value) {
+                    distinct_values.push(value);
+                    positions.push(index);
+                }
+            }
+            offsets.push(positions.len() as i64);
+        }
+
+        let validity = array.validity().cloned();
+        let array = take_list(&values, positions, offsets, validity)?;
+        Ok(array.into_series().into())
+    }
+}
+
+impl fmt::Display for ArrayDistinctFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_arrow::arrow::array::ArrayRef;
+use common_arrow::arrow::compute::take;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use super::array::array_arg;
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::CastFunction;
+use crate::scalars::Function;
+use crate::scalars::MapElementFunction;
+
+/// `element_at(array, n)` returns the n-th element of the array, NULL if the index is out of
+/// range. The index is 1-based, a negative index counts from the end of the array.
+/// `array[n]` is rewritten to it, `element_at` of maps is evaluated by `MapElementFunction`.
+#[derive(Clone)]
+pub struct ArrayElementFunction {
+    display_name: String,
+}
+
+impl ArrayElementFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(ArrayElementFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic())
+    }
+}
+
+impl Function for ArrayElementFunction {
+    fn name(&self) -> &str {
+        &*self.display_name
+    }
+
+    fn num_arguments(&self) -> usize {
+        2
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        match &args[0] {
+            DataType::Map(_, _) => {
+                MapElementFunction::try_create(&self.display_name)?.return_type(args)
+            }
+            DataType::List(item) if args[1].is_integer() => Ok(item.data_type().clone()),
+            DataType::List(_) => Err(ErrorCode::IllegalDataType(format!(
+                "Expected integer index of {}, but got {}",
+                self.display_name, args[1]
+            ))),
+            other => Err(ErrorCode::IllegalDataType(format!(
+                "Expected array or map type as the first argument of {}, but got {}",
+                self.display_name, other
+            ))),
+        }
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, input_rows: usize) -> Result<DataColumn> {
+        if let DataType::Map(_, _) = columns[0].data_type() {
+            let map_element = MapElementFunction::try_create(&self.display_name)?;
+            return map_element.eval(columns, input_rows);
+        }
+
+        let array = array_arg(&columns[0])?;
+        let cast = CastFunction::create("cast".to_string(), DataType::Int64)?;
+        let indices = cast.eval(&[columns[1].clone()], input_rows)?.to_array()?;
+
+        let offsets = array.offsets();
+        let positions = (0..input_rows)
+            .map(|row| {
+                let index = indices.try_get(row)?;
+                if array.is_null(row) || index.is_null() {
+                    return Ok(None);
+                }
+
+                let (start, end) = (offsets[row], offsets[row + 1]);
+                let position = match index.as_i64()? {
+                    index if index > 0 => start + index - 1,
+                    index if index < 0 => end + index,
+                    _ => return Ok(None),
+                };
+                match position >= start && position < end {
+                    true => Ok(Some(position as u32)),
+                    false => Ok(None),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let positions = DFUInt32Array::new_from_opt_slice(&positions);
+
+        let values = array.values().get_array_ref();
+        let array: ArrayRef = Arc::from(take::take(values.as_ref(), positions.inner())?);
+        Ok(array.into_series().into())
+    }
+}
+
+impl fmt::Display for ArrayElementFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use super::array::array_arg;
+use super::array::array_item_type;
+use super::array::take_list;
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::CastFunction;
+use crate::scalars::Function;
+
+/// `arraySlice(array, offset[, length])` returns the slice of the array.
+///
+/// The offset is 1-based, a negative offset counts from the end of the array. A negative length
+/// excludes the elements at the end, the slice lasts to the end if the length is omitted.
+#[derive(Clone)]
+pub struct ArraySliceFunction {
+    display_name: String,
+}
+
+impl ArraySliceFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(ArraySliceFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic())
+    }
+
+    // The range of the slice in an array of the size.
+    fn slice_range(size: i64, offset: i64, length: Option<i64>) -> (i64, i64) {
+        let start = match offset {
+            0 => return (0, 0),
+            offset if offset > 0 => (offset - 1).min(size),
+            offset => (size + offset).max(0),
+        };
+        let end = match length {
+            None => size,
+            Some(length) if length >= 0 => (start + length).min(size),
+            Some(length) => (size + length).max(start),
+        };
+        (start, end)
+    }
+}
+
+impl Function for ArraySliceFunction {
+    fn name(&self) -> &str {
+        &*self.display_name
+    }
+
+    fn variadic_arguments(&self) -> Option<(usize, usize)> {
+        Some((2, 3))
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        array_item_type(&self.display_name, &args[0])?;
+        for arg in &args[1..] {
+            if !arg.is_integer() && arg != &DataType::Null {
+                return Err(ErrorCode::IllegalDataType(format!(
+                    "Expected integer offset and length of {}, but got {}",
+                    self.display_name, arg
+                )));
+            }
+        }
+        Ok(args[0].clone())
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, input_rows: usize) -> Result<DataColumn> {
+        let array = array_arg(&columns[0])?;
+        let cast = CastFunction::create("cast".to_string(), DataType::Int64)?;
+        let bounds = columns[1..]
+            .iter()
+            .map(|column| cast.eval(&[column.clone()], input_rows)?.to_array())
+            .collect::<Result<Vec<_>>>()?;
+
+        let array_offsets = array.offsets();
+        let mut positions = vec![];
+        let mut offsets = Vec::with_capacity(input_rows + 1);
+        let mut validity = Vec::with_capacity(input_rows);
+        offsets.push(0);
+        for row in 0..input_rows {
+            let bounds = bounds
+                .iter()
+                .map(|bound| bound.try_get(row))
+                .collect::<Result<Vec<_>>>()?;
+            let is_null = array.is_null(row) || bounds.iter().any(|bound| bound.is_null());
+            validity.push(!is_null);
+
+            if !is_null {
+                let start = array_offsets[row];
+                let size = array_offsets[row + 1] - start;
+                let length = match bounds.get(1) {
+                    Some(length) => Some(length.as_i64()?),
+                    None => None,
+                };
+                let (from, to) = Self::slice_range(size, bounds[0].as_i64()?, length);
+                positions.extend((start + from..start + to).map(|index| index as usize));
+            }
+            offsets.push(positions.len() as i64);
+        }
+
+        let validity = Some(Bitmap::from_iter(validity));
+        let array = take_list(&array.values(), positions, offsets, validity)?;
+        Ok(array.into_series().into())
+    }
+}
+
+impl fmt::Display for ArraySliceFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_arrow::arrow::compute::sort as arrow_sort;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use super::array::array_arg;
+use super::array::array_item_type;
+use super::array::take_list;
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::Function;
+
+/// `arraySort(array)` sorts the elements of the array in ascending order, NULLs are placed last.
+#[derive(Clone)]
+pub struct ArraySortFunction {
+    display_name: String,
+}
+
+impl ArraySortFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(ArraySortFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic())
+    }
+}
+
+impl Function for ArraySortFunction {
+    fn name(&self) -> &str {
+        &*self.display_name
+    }
+
+    fn num_arguments(&self) -> usize {
+        1
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        array_item_type(&self.display_name, &args[0])?;
+        Ok(args[0].clone())
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, _input_rows: usize) -> Result<DataColumn> {
+        let array = array_arg(&columns[0])?;
+        let values = array.values();
+        let array_offsets = array.offsets();
+
+        // The elements are sorted by their rows first, so the elements stay in their rows.
+        let mut rows = vec![];
+        let mut positions = vec![];
+        for row in 0..array.len() {
+            let (start, end) = (array_offsets[row] as usize, array_offsets[row + 1] as usize);
+            rows.extend((start..end).map(|_| row as u32));
+            positions.extend(start..end);
+        }
+
+        let rows = DFUInt32Array::new_from_slice(&rows);
+        let mut elements = positions.iter().copied();
+        let elements = unsafe { values.take_iter_unchecked(&mut elements) }?;
+        let elements = elements.get_array_ref();
+        let sort_columns = [
+            arrow_sort::SortColumn {
+                values: rows.inner(),
+                options: None,
+            },
+            arrow_sort::SortColumn {
+                values: elements.as_ref(),
+                options: Some(arrow_sort::SortOptions {
+                    descending: false,
+                    nulls_first: false,
+                }),
+            },
+        ];
+        let indices = arrow_sort::lexsort_to_indices(&sort_columns, None)?;
+        let sorted_positions = indices
+            .values()
+            .iter()
+            .map(|index| positions[*index as usize])
+            .collect();
+
+        let offsets = array_offsets
+            .iter()
+            .map(|offset| offset - array_offsets[0])
+            .collect();
+        let validity = array.validity().cloned();
+        let array = take_list(&values, sorted_positions, offsets, validity)?;
+        Ok(array.into_series().into())
+    }
+}
+
+impl fmt::Display for ArraySortFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
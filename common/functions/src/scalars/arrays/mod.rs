@@ -0,0 +1,37 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod array;
+mod array_class;
+mod array_concat;
+mod array_distinct;
+mod array_element;
+mod array_has;
+mod array_index_of;
+mod array_join;
+mod array_length;
+mod array_slice;
+mod array_sort;
+
+pub use array::ArrayFunction;
+pub use array_class::ArrayClassFunction;
+pub use array_concat::ArrayConcatFunction;
+pub use array_distinct::ArrayDistinctFunction;
+pub use array_element::ArrayElementFunction;
+pub use array_has::ArrayHasFunction;
+pub use array_index_of::ArrayIndexOfFunction;
+pub use array_join::ArrayJoinFunction;
+pub use array_length::ArrayLengthFunction;
+pub use array_slice::ArraySliceFunction;
+pub use array_sort::ArraySortFunction;
@@ -20,6 +20,7 @@ use super::interval_function::SecondsArithmeticFunction;
 use super::now::NowFunction;
 use super::number_function::ToMondayFunction;
 use super::RoundFunction;
+use super::TimeZoneFunction;
 use super::ToDayOfMonthFunction;
 use super::ToDayOfWeekFunction;
 use super::ToDayOfYearFunction;
@@ -125,6 +126,10 @@ impl DateFunction {
         factory.register("toSecond", ToSecondFunction::desc());
         factory.register("toMonday", ToMondayFunction::desc());
 
+        // timezones
+        factory.register("toTimeZone", TimeZoneFunction::to_timezone_desc());
+        factory.register("convert_tz", TimeZoneFunction::convert_tz_desc());
+
         // rounders
         factory.register("toStartOfSecond", Self::round_function_creator(1));
         factory.register("toStartOfMinute", Self::round_function_creator(60));
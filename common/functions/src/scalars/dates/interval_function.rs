@@ -192,9 +192,10 @@ impl IntervalFunctionFactory {
         let mut date_datetime_opt = None;
         columns.iter().for_each(|column| match column.data_type() {
             DataType::Interval(_) => interval_opt = Some(column),
-            DataType::Date16 | DataType::Date32 | DataType::DateTime32(_) => {
-                date_datetime_opt = Some(column)
-            }
+            DataType::Date16
+            | DataType::Date32
+            | DataType::DateTime32(_)
+            | DataType::DateTime64(_, _) => date_datetime_opt = Some(column),
             _ => {}
         });
 
@@ -237,6 +238,8 @@ impl IntervalFunctionFactory {
     //   4. interval_month_plus_minus_date16 --------- Interval(YearMonth) +/-  Date16
     //   5. interval_month_plus_minus_date32 --------- Interval(YearMonth) +/-  Date32
     //   6. interval_month_plus_minus_datetime32 ----- Interval(YearMonth) +/-  DateTime32
+    //   7. interval_daytime_plus_minus_datetime64 --- Interval(DayTime)   +/-  DateTime64
+    //   8. interval_month_plus_minus_datetime64 ----- Interval(YearMonth) +/-  DateTime64
 
     fn get_interval_arithmetic_func(
         interval: &DataType,
@@ -247,12 +250,14 @@ impl IntervalFunctionFactory {
                 DataType::Date16 => Self::interval_month_plus_minus_date16,
                 DataType::Date32 => Self::interval_month_plus_minus_date32,
                 DataType::DateTime32(_) => Self::interval_month_plus_minus_datetime32,
+                DataType::DateTime64(_, _) => Self::interval_month_plus_minus_datetime64,
                 _ => unreachable!(),
             },
             DataType::Interval(IntervalUnit::DayTime) => match date_datetime {
                 DataType::Date16 => Self::interval_daytime_plus_minus_date16,
                 DataType::Date32 => Self::interval_daytime_plus_minus_date32,
                 DataType::DateTime32(_) => Self::interval_daytime_plus_minus_datetime32,
+                DataType::DateTime64(_, _) => Self::interval_daytime_plus_minus_datetime64,
                 _ => unreachable!(),
             },
             _ => unreachable!(),
@@ -332,6 +337,32 @@ impl IntervalFunctionFactory {
         Ok(res.into())
     }
 
+    fn interval_daytime_plus_minus_datetime64(
+        op: &DataValueArithmeticOperator,
+        a: &DataColumnWithField,
+        b: &DataColumnWithField,
+    ) -> Result<DataColumn> {
+        let (interval, datetime) = Self::validate_input(op, a, b)?;
+        let factor = Self::datetime64_factor(datetime.data_type());
+        let res = Self::interval_operation(
+            interval.column().to_array()?.i64()?,
+            datetime.column().to_array()?.i64()?,
+            |ms: &i64, ticks: &i64| {
+                let delta = match factor >= 1000 {
+                    true => *ms * (factor / 1000),
+                    false => *ms / (1000 / factor),
+                };
+                let r = match op {
+                    DataValueArithmeticOperator::Plus => *ticks + delta,
+                    DataValueArithmeticOperator::Minus => *ticks - delta,
+                    _ => unreachable!(),
+                };
+                Ok(r)
+            },
+        )?;
+        Ok(res.into())
+    }
+
     fn interval_month_plus_minus_date16(
         op: &DataValueArithmeticOperator,
         a: &DataColumnWithField,
@@ -359,6 +390,15 @@ impl IntervalFunctionFactory {
         Self::month_i64_plus_minus_datetime32(op, interval, datetime, 1)
     }
 
+    fn interval_month_plus_minus_datetime64(
+        op: &DataValueArithmeticOperator,
+        a: &DataColumnWithField,
+        b: &DataColumnWithField,
+    ) -> Result<DataColumn> {
+        let (interval, datetime) = Self::validate_input(op, a, b)?;
+        Self::month_i64_plus_minus_datetime64(op, interval, datetime, 1)
+    }
+
     //  End of interval typed arithmetic functions.
     //////////////////////////////////////////////////////////////////////////////////
 
@@ -405,6 +445,17 @@ impl IntervalFunctionFactory {
                 DataType::Int64 => Self::month_i64_plus_minus_datetime32,
                 _ => unreachable!(),
             },
+            DataType::DateTime64(_, _) => match integer {
+                DataType::UInt8 => Self::month_u8_plus_minus_datetime64,
+                DataType::UInt16 => Self::month_u16_plus_minus_datetime64,
+                DataType::UInt32 => Self::month_u32_plus_minus_datetime64,
+                DataType::UInt64 => Self::month_u64_plus_minus_datetime64,
+                DataType::Int8 => Self::month_i8_plus_minus_datetime64,
+                DataType::Int16 => Self::month_i16_plus_minus_datetime64,
+                DataType::Int32 => Self::month_i32_plus_minus_datetime64,
+                DataType::Int64 => Self::month_i64_plus_minus_datetime64,
+                _ => unreachable!(),
+            },
             _ => unreachable!(),
         }
     }
@@ -438,6 +489,15 @@ impl IntervalFunctionFactory {
     crate::define_month_plus_minus_datetime32!(month_u16_plus_minus_datetime32, u16);
     crate::define_month_plus_minus_datetime32!(month_u8_plus_minus_datetime32, u8);
 
+    crate::define_month_plus_minus_datetime64!(month_i64_plus_minus_datetime64, i64);
+    crate::define_month_plus_minus_datetime64!(month_i32_plus_minus_datetime64, i32);
+    crate::define_month_plus_minus_datetime64!(month_i16_plus_minus_datetime64, i16);
+    crate::define_month_plus_minus_datetime64!(month_i8_plus_minus_datetime64, i8);
+    crate::define_month_plus_minus_datetime64!(month_u64_plus_minus_datetime64, u64);
+    crate::define_month_plus_minus_datetime64!(month_u32_plus_minus_datetime64, u32);
+    crate::define_month_plus_minus_datetime64!(month_u16_plus_minus_datetime64, u16);
+    crate::define_month_plus_minus_datetime64!(month_u8_plus_minus_datetime64, u8);
+
     //  End of months integer arithmetic functions
     //////////////////////////////////////////////////////////////////////////////////
 
@@ -484,6 +544,17 @@ impl IntervalFunctionFactory {
                 DataType::Int64 => Self::time_secs_i64_plus_minus_datetime32,
                 _ => unreachable!(),
             },
+            DataType::DateTime64(_, _) => match integer {
+                DataType::UInt8 => Self::time_secs_u8_plus_minus_datetime64,
+                DataType::UInt16 => Self::time_secs_u16_plus_minus_datetime64,
+                DataType::UInt32 => Self::time_secs_u32_plus_minus_datetime64,
+                DataType::UInt64 => Self::time_secs_u64_plus_minus_datetime64,
+                DataType::Int8 => Self::time_secs_i8_plus_minus_datetime64,
+                DataType::Int16 => Self::time_secs_i16_plus_minus_datetime64,
+                DataType::Int32 => Self::time_secs_i32_plus_minus_datetime64,
+                DataType::Int64 => Self::time_secs_i64_plus_minus_datetime64,
+                _ => unreachable!(),
+            },
             _ => unreachable!(),
         }
     }
@@ -517,11 +588,20 @@ impl IntervalFunctionFactory {
     crate::define_time_secs_plus_minus_datetime32!(time_secs_u16_plus_minus_datetime32, u16);
     crate::define_time_secs_plus_minus_datetime32!(time_secs_u8_plus_minus_datetime32, u8);
 
+    crate::define_time_secs_plus_minus_datetime64!(time_secs_i64_plus_minus_datetime64, i64);
+    crate::define_time_secs_plus_minus_datetime64!(time_secs_i32_plus_minus_datetime64, i32);
+    crate::define_time_secs_plus_minus_datetime64!(time_secs_i16_plus_minus_datetime64, i16);
+    crate::define_time_secs_plus_minus_datetime64!(time_secs_i8_plus_minus_datetime64, i8);
+    crate::define_time_secs_plus_minus_datetime64!(time_secs_u64_plus_minus_datetime64, u64);
+    crate::define_time_secs_plus_minus_datetime64!(time_secs_u32_plus_minus_datetime64, u32);
+    crate::define_time_secs_plus_minus_datetime64!(time_secs_u16_plus_minus_datetime64, u16);
+    crate::define_time_secs_plus_minus_datetime64!(time_secs_u8_plus_minus_datetime64, u8);
+
     // End of seconds integer arithmetic functions
     //////////////////////////////////////////////////////////////////////////////////
 
     // A private helper function for validate operator, returns a tuple of
-    // (interval|integer, date16|date32|datetime32)
+    // (interval|integer, date16|date32|datetime32|datetime64)
     fn validate_input<'a>(
         op: &DataValueArithmeticOperator,
         col0: &'a DataColumnWithField,
@@ -542,6 +622,14 @@ impl IntervalFunctionFactory {
         }
     }
 
+    // A private helper function to get the number of DateTime64 ticks in one second
+    fn datetime64_factor(data_type: &DataType) -> i64 {
+        match data_type {
+            DataType::DateTime64(precision, _) => datetime64_ticks_per_second(*precision),
+            _ => 1,
+        }
+    }
+
     // A private helper function to add/subtract month to/from days
     fn days_plus_signed_months(days: i64, months: i64) -> Result<u32> {
         let naive = NaiveDateTime::from_timestamp(0, 0).checked_add_signed(Duration::days(days));
@@ -653,6 +741,40 @@ macro_rules! define_month_plus_minus_datetime32 {
     };
 }
 
+#[macro_export]
+macro_rules! define_month_plus_minus_datetime64 {
+    ($fn_name:ident, $type:ident) => {
+        fn $fn_name(
+            op: &DataValueArithmeticOperator,
+            a: &DataColumnWithField,
+            b: &DataColumnWithField,
+            mul: i64,
+        ) -> Result<DataColumn> {
+            let (interval_months, datetime64) = Self::validate_input(op, a, b)?;
+            let factor = Self::datetime64_factor(datetime64.data_type());
+
+            let res = Self::interval_operation(
+                interval_months.column().to_array()?.$type()?,
+                datetime64.column().to_array()?.i64()?,
+                |months: &$type, ticks: &i64| {
+                    let dt = Self::seconds_to_datetime(ticks.div_euclid(factor))?;
+                    let new_dt = match op {
+                        DataValueArithmeticOperator::Plus => {
+                            Self::datetime_plus_signed_months(&dt, (*months as i64) * mul)?
+                        }
+                        DataValueArithmeticOperator::Minus => {
+                            Self::datetime_plus_signed_months(&dt, -(*months as i64) * mul)?
+                        }
+                        _ => unreachable!(),
+                    };
+                    Ok(new_dt.timestamp() * factor + ticks.rem_euclid(factor))
+                },
+            )?;
+            Ok(res.into())
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! define_month_plus_minus_date {
     ($fn_name:ident, $month_type:ident, $date_type:ident) => {
@@ -713,6 +835,33 @@ macro_rules! define_time_secs_plus_minus_datetime32 {
     };
 }
 
+#[macro_export]
+macro_rules! define_time_secs_plus_minus_datetime64 {
+    ($fn_name:ident, $type:ident) => {
+        fn $fn_name(
+            op: &DataValueArithmeticOperator,
+            interval: &DataColumnWithField,
+            datetime: &DataColumnWithField,
+            mul: i64,
+        ) -> Result<DataColumn> {
+            let factor = Self::datetime64_factor(datetime.data_type());
+            let res = Self::interval_operation(
+                interval.column().to_array()?.$type()?,
+                datetime.column().to_array()?.i64()?,
+                |secs: &$type, dt: &i64| {
+                    let r = match op {
+                        DataValueArithmeticOperator::Plus => *dt + *secs as i64 * mul * factor,
+                        DataValueArithmeticOperator::Minus => *dt - *secs as i64 * mul * factor,
+                        _ => unreachable!(),
+                    };
+                    Ok(r)
+                },
+            )?;
+            Ok(res.into())
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! define_time_secs_plus_minus_date {
     ($fn_name:ident, $seconds_type:ident, $date_type:ident) => {
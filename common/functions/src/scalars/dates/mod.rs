@@ -18,6 +18,7 @@ mod now;
 mod number_function;
 mod round_function;
 mod simple_date;
+mod timezone_function;
 mod week_date;
 
 pub use date::DateFunction;
@@ -44,4 +45,5 @@ pub use round_function::RoundFunction;
 pub use simple_date::TodayFunction;
 pub use simple_date::TomorrowFunction;
 pub use simple_date::YesterdayFunction;
+pub use timezone_function::TimeZoneFunction;
 pub use week_date::ToStartOfWeekFunction;
@@ -14,7 +14,11 @@
 
 use std::fmt;
 
+use common_datavalues::chrono::NaiveDateTime;
+use common_datavalues::chrono::Offset;
+use common_datavalues::chrono::TimeZone;
 use common_datavalues::prelude::*;
+use common_datavalues::Tz;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
@@ -36,12 +40,15 @@ impl RoundFunction {
         Ok(Box::new(s))
     }
 
-    // TODO: (sundy-li)
-    // Consider about the timezones/offsets
-    // Currently: assuming timezone offset is a multiple of round.
+    // Round the local time of the timezone, `time` is in seconds.
     #[inline]
-    fn execute(&self, time: u32) -> u32 {
-        time / self.round * self.round
+    fn execute(&self, time: i64, tz: &Tz) -> i64 {
+        let round = self.round as i64;
+        let offset = tz
+            .offset_from_utc_datetime(&NaiveDateTime::from_timestamp(time, 0))
+            .fix()
+            .local_minus_utc() as i64;
+        (time + offset).div_euclid(round) * round - offset
     }
 }
 
@@ -51,8 +58,8 @@ impl Function for RoundFunction {
     }
 
     fn return_type(&self, args: &[DataType]) -> Result<DataType> {
-        match args[0] {
-            DataType::DateTime32(_) => Ok(DataType::DateTime32(None)),
+        match &args[0] {
+            DataType::DateTime32(_) | DataType::DateTime64(_, _) => Ok(args[0].clone()),
             _ => Err(ErrorCode::BadDataValueType(format!(
                 "Function {} must have a DateTime type as argument, but got {}",
                 self.display_name, args[0],
@@ -65,10 +72,33 @@ impl Function for RoundFunction {
     }
 
     fn eval(&self, columns: &DataColumnsWithField, _input_rows: usize) -> Result<DataColumn> {
+        if let DataType::DateTime64(precision, tz) = columns[0].data_type() {
+            let tz = get_timezone(tz)?;
+            let factor = datetime64_ticks_per_second(*precision);
+            let execute = |x: i64| self.execute(x.div_euclid(factor), &tz) * factor;
+            return match columns[0].column() {
+                DataColumn::Array(array) => {
+                    let arr = array.i64()?.apply(execute);
+                    Ok(DataColumn::Array(arr.into_series()))
+                }
+                DataColumn::Constant(v, rows) => {
+                    if v.is_null() {
+                        return Ok(DataColumn::Constant(DataValue::Int64(None), *rows));
+                    }
+                    let value = execute(v.as_i64()?);
+                    Ok(DataColumn::Constant(DataValue::Int64(Some(value)), *rows))
+                }
+            };
+        }
+
+        let tz = match columns[0].data_type() {
+            DataType::DateTime32(tz) => get_timezone(tz)?,
+            _ => Tz::UTC,
+        };
         match columns[0].column() {
             DataColumn::Array(array) => {
                 let array = array.u32()?;
-                let arr = array.apply(|x| self.execute(x));
+                let arr = array.apply(|x| self.execute(x as i64, &tz) as u32);
                 Ok(DataColumn::Array(arr.into_series()))
             }
             DataColumn::Constant(v, rows) => {
@@ -77,7 +107,7 @@ impl Function for RoundFunction {
                 }
                 let value = v.as_u64()?;
                 Ok(DataColumn::Constant(
-                    DataValue::UInt32(Some(self.execute(value as u32))),
+                    DataValue::UInt32(Some(self.execute(value as i64, &tz) as u32)),
                     *rows,
                 ))
             }
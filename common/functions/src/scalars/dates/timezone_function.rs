@@ -0,0 +1,172 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::chrono::NaiveDateTime;
+use common_datavalues::chrono::Offset;
+use common_datavalues::chrono::TimeZone;
+use common_datavalues::prelude::*;
+use common_datavalues::Tz;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::Function;
+
+/// `toTimeZone(datetime, to_tz)` and `convert_tz(datetime, from_tz, to_tz)`.
+///
+/// Both return the wall clock time in `to_tz` as a DateTime without timezone, so that it
+/// prints as the local time. `convert_tz` reads the wall clock of the argument as a time in
+/// `from_tz` first, like MySQL does.
+#[derive(Clone)]
+pub struct TimeZoneFunction {
+    display_name: String,
+    with_source: bool,
+}
+
+impl TimeZoneFunction {
+    pub fn try_create_to_timezone(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(TimeZoneFunction {
+            display_name: display_name.to_string(),
+            with_source: false,
+        }))
+    }
+
+    pub fn try_create_convert_tz(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(TimeZoneFunction {
+            display_name: display_name.to_string(),
+            with_source: true,
+        }))
+    }
+
+    pub fn to_timezone_desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create_to_timezone))
+            .features(FunctionFeatures::default().deterministic())
+    }
+
+    pub fn convert_tz_desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create_convert_tz))
+            .features(FunctionFeatures::default().deterministic())
+    }
+
+    fn timezone_arg(&self, column: &DataColumnWithField) -> Result<Tz> {
+        match column.column() {
+            DataColumn::Constant(DataValue::String(Some(tz)), _) => {
+                let tz = String::from_utf8(tz.clone())?;
+                get_timezone(&Some(tz))
+            }
+            _ => Err(ErrorCode::BadArguments(format!(
+                "The timezone of function {} must be a constant string",
+                self.display_name
+            ))),
+        }
+    }
+
+    // Shift the seconds of a DateTime in timezone `tz` to the wall clock in `to`.
+    fn execute(&self, seconds: i64, tz: &Tz, from: &Tz, to: &Tz) -> i64 {
+        let instant = match self.with_source {
+            true => utc_seconds(local_seconds(seconds, tz), from),
+            false => seconds,
+        };
+        local_seconds(instant, to)
+    }
+}
+
+impl Function for TimeZoneFunction {
+    fn name(&self) -> &str {
+        self.display_name.as_str()
+    }
+
+    fn num_arguments(&self) -> usize {
+        match self.with_source {
+            true => 3,
+            false => 2,
+        }
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        match &args[0] {
+            DataType::DateTime32(_) => Ok(DataType::DateTime32(None)),
+            DataType::DateTime64(precision, _) => Ok(DataType::DateTime64(*precision, None)),
+            other => Err(ErrorCode::IllegalDataType(format!(
+                "Illegal type {:?} of argument of function {}. Should be a dateTime32/dateTime64",
+                other, self.display_name
+            ))),
+        }
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, input_rows: usize) -> Result<DataColumn> {
+        let to = self.timezone_arg(&columns[columns.len() - 1])?;
+        let from = match self.with_source {
+            true => self.timezone_arg(&columns[1])?,
+            false => Tz::UTC,
+        };
+
+        let column = columns[0].column();
+        let result: DataColumn = match columns[0].data_type() {
+            DataType::DateTime32(tz) => {
+                let tz = get_timezone(tz)?;
+                let array = column.to_array()?;
+                let array = array
+                    .u32()?
+                    .apply(|v| self.execute(v as i64, &tz, &from, &to) as u32);
+                array.into()
+            }
+            DataType::DateTime64(precision, tz) => {
+                let tz = get_timezone(tz)?;
+                let factor = datetime64_ticks_per_second(*precision);
+                let array = column.to_array()?;
+                let array = array.i64()?.apply(|v| {
+                    let seconds = self.execute(v.div_euclid(factor), &tz, &from, &to);
+                    seconds * factor + v.rem_euclid(factor)
+                });
+                array.into()
+            }
+            other => {
+                return Err(ErrorCode::IllegalDataType(format!(
+                "Illegal type {:?} of argument of function {}. Should be a dateTime32/dateTime64",
+                other, self.display_name
+            )))
+            }
+        };
+        Ok(result.resize_constant(input_rows))
+    }
+}
+
+impl fmt::Display for TimeZoneFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}()", self.display_name)
+    }
+}
+
+// The wall clock of the instant in the timezone, as the seconds of a DateTime in UTC.
+fn local_seconds(seconds: i64, tz: &Tz) -> i64 {
+    tz.timestamp(seconds, 0).naive_local().timestamp()
+}
+
+// The instant of the wall clock in the timezone, the earlier one for ambiguous time.
+fn utc_seconds(local: i64, tz: &Tz) -> i64 {
+    let naive = NaiveDateTime::from_timestamp(local, 0);
+    match tz.from_local_datetime(&naive).earliest() {
+        Some(dt) => dt.timestamp(),
+        // The wall clock is skipped by a DST transition.
+        None => local - tz.offset_from_utc_datetime(&naive).fix().local_minus_utc() as i64,
+    }
+}
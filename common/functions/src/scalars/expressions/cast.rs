@@ -18,19 +18,32 @@ use common_arrow::arrow::temporal_conversions::EPOCH_DAYS_FROM_CE;
 use common_datavalues::chrono::DateTime;
 use common_datavalues::chrono::Datelike;
 use common_datavalues::chrono::NaiveDate;
-use common_datavalues::chrono::NaiveDateTime;
 use common_datavalues::chrono::TimeZone;
 use common_datavalues::chrono::Utc;
 use common_datavalues::columns::DataColumn;
+use common_datavalues::prelude::cast_series_from_decimal;
+use common_datavalues::prelude::cast_series_from_decimal256;
+use common_datavalues::prelude::cast_series_from_variant;
+use common_datavalues::prelude::cast_series_to_decimal;
+use common_datavalues::prelude::cast_series_to_decimal256;
+use common_datavalues::prelude::cast_series_to_variant;
+use common_datavalues::prelude::datetime64_from_str;
+use common_datavalues::prelude::datetime64_ticks_per_second;
+use common_datavalues::prelude::datetime64_to_string;
+use common_datavalues::prelude::get_timezone;
 use common_datavalues::prelude::ArrayApply;
 use common_datavalues::prelude::DFInt32Array;
+use common_datavalues::prelude::DFInt64Array;
 use common_datavalues::prelude::DFStringArray;
 use common_datavalues::prelude::DFUInt16Array;
 use common_datavalues::prelude::DFUInt32Array;
+use common_datavalues::prelude::DataColumnWithField;
 use common_datavalues::prelude::DataColumnsWithField;
 use common_datavalues::series::IntoSeries;
+use common_datavalues::DataField;
 use common_datavalues::DataSchema;
 use common_datavalues::DataType;
+use common_datavalues::Tz;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
@@ -72,6 +85,14 @@ impl Function for CastFunction {
             return Ok(columns[0].column().clone());
         }
 
+        // Variant to others, cast the scalar text of the values as a string
+        if columns[0].data_type() == &DataType::Variant {
+            let series = columns[0].column().to_minimal_array()?;
+            let field = DataField::new(columns[0].field().name(), DataType::String, true);
+            let column = DataColumnWithField::new(cast_series_from_variant(&series)?.into(), field);
+            return self.eval(&[column], input_rows);
+        }
+
         let series = columns[0].column().clone().to_minimal_array()?;
         const DATE_FMT: &str = "%Y-%m-%d";
         const TIME_FMT: &str = "%Y-%m-%d %H:%M:%S";
@@ -82,6 +103,25 @@ impl Function for CastFunction {
         ));
 
         let array = match (columns[0].data_type(), &self.cast_type) {
+            // Decimal to others
+            (DataType::Decimal128(_, scale), cast_type) if !cast_type.is_decimal() => {
+                cast_series_from_decimal(&series, *scale, cast_type)
+            }
+            (DataType::Decimal256(_, scale), cast_type) if !cast_type.is_decimal() => {
+                cast_series_from_decimal256(&series, *scale, cast_type)
+            }
+
+            // others to Variant
+            (from_type, DataType::Variant) => cast_series_to_variant(&series, from_type),
+
+            // others to Decimal
+            (from_type, DataType::Decimal128(precision, scale)) => {
+                cast_series_to_decimal(&series, from_type, *precision, *scale)
+            }
+            (from_type, DataType::Decimal256(precision, scale)) => {
+                cast_series_to_decimal256(&series, from_type, *precision, *scale)
+            }
+
             // Date/DateTime to others
             (DataType::Date16, _) => with_match_primitive_type!(&self.cast_type, |$T| {
                 series.cast_with_type(&self.cast_type)
@@ -90,6 +130,7 @@ impl Function for CastFunction {
                match &self.cast_type {
                 Date32 => Ok(arr.apply_cast_numeric(|v| v as i32).into_series()),
                 DateTime32(_) => Ok(arr.apply_cast_numeric(|v|  Utc.timestamp(v as i64 * 24 * 3600, 0_u32).timestamp() as u32 ).into_series() ),
+                DateTime64(precision, _) => Ok(arr.apply_cast_numeric(|v| v as i64 * 24 * 3600 * datetime64_ticks_per_second(*precision)).into_series()),
                 String => Ok(DFStringArray::from_iter(arr.into_iter().map(|v| v.map(|x| datetime_to_string( Utc.timestamp(*x as i64 * 24 * 3600, 0_u32), DATE_FMT))) ).into_series()),
                 _ =>  Err(error)
                }
@@ -102,23 +143,53 @@ impl Function for CastFunction {
                match &self.cast_type {
                 Date32 => Ok(arr.apply_cast_numeric(|v| v as i32).into_series()),
                 DateTime32(_) => Ok(arr.apply_cast_numeric(|v|  Utc.timestamp(v as i64 * 24 * 3600, 0_u32).timestamp()  as u32).into_series() ),
+                DateTime64(precision, _) => Ok(arr.apply_cast_numeric(|v| v as i64 * 24 * 3600 * datetime64_ticks_per_second(*precision)).into_series()),
                 String => Ok(DFStringArray::from_iter(arr.into_iter().map(|v| v.map(|x| datetime_to_string( Utc.timestamp(*x as i64 * 24 * 3600, 0_u32), DATE_FMT))) ).into_series()),
                 _ =>  Err(error)
                }
             }),
 
-            (DataType::DateTime32(_), _) => with_match_primitive_type!(&self.cast_type, |$T| {
+            (DataType::DateTime32(tz), _) => with_match_primitive_type!(&self.cast_type, |$T| {
                 series.cast_with_type(&self.cast_type)
             }, {
                let arr = series.u32()?;
+               let tz = get_timezone(tz)?;
                match &self.cast_type {
                 Date16 => Ok(arr.apply_cast_numeric(|v| (v as i64 / 24/ 3600) as u16).into_series()),
                 Date32 => Ok(arr.apply_cast_numeric(|v| (v as i64 / 24/ 3600) as i32).into_series()),
-                String => Ok(DFStringArray::from_iter(arr.into_iter().map(|v| v.map(|x| datetime_to_string( Utc.timestamp(*x as i64, 0_u32), TIME_FMT))) ).into_series()),
+                DateTime32(_) => Ok(series.clone()),
+                DateTime64(precision, _) => Ok(arr.apply_cast_numeric(|v| v as i64 * datetime64_ticks_per_second(*precision)).into_series()),
+                String => Ok(DFStringArray::from_iter(arr.into_iter().map(|v| v.map(|x| datetime_to_string( tz.timestamp(*x as i64, 0_u32), TIME_FMT))) ).into_series()),
                 _ =>  Err(error)
                }
             }),
 
+            (DataType::DateTime64(precision, tz), _) => {
+                with_match_primitive_type!(&self.cast_type, |$T| {
+                    series.cast_with_type(&self.cast_type)
+                }, {
+                   let arr = series.i64()?;
+                   let tz = get_timezone(tz)?;
+                   let factor = datetime64_ticks_per_second(*precision);
+                   let seconds_per_day = 24 * 3600 * factor;
+                   match &self.cast_type {
+                    Date16 => Ok(arr.apply_cast_numeric(|v| v.div_euclid(seconds_per_day) as u16).into_series()),
+                    Date32 => Ok(arr.apply_cast_numeric(|v| v.div_euclid(seconds_per_day) as i32).into_series()),
+                    DateTime32(_) => Ok(arr.apply_cast_numeric(|v| v.div_euclid(factor) as u32).into_series()),
+                    DateTime64(to_precision, _) => {
+                        let to_factor = datetime64_ticks_per_second(*to_precision);
+                        if to_factor >= factor {
+                            Ok(arr.apply(|v| v * (to_factor / factor)).into_series())
+                        } else {
+                            Ok(arr.apply(|v| v.div_euclid(factor / to_factor)).into_series())
+                        }
+                    }
+                    String => Ok(DFStringArray::from_iter(arr.into_iter().map(|v| v.map(|x| datetime64_to_string(*x, *precision, &tz))) ).into_series()),
+                    _ =>  Err(error)
+                   }
+                })
+            }
+
             // others to Date/DateTime
             (_, DataType::Date16) => with_match_primitive_type!(columns[0].data_type(), |$T| {
                 series.cast_with_type(&self.cast_type)
@@ -148,14 +219,15 @@ impl Function for CastFunction {
                }
             }),
 
-            (_, DataType::DateTime32(_)) => {
+            (_, DataType::DateTime32(tz)) => {
                 with_match_primitive_type!(columns[0].data_type(), |$T| {
                     series.cast_with_type(&self.cast_type)
                 }, {
+                   let tz = get_timezone(tz)?;
                    match columns[0].data_type() {
                     String => {
                         let it = series.string()?.into_iter().map(|v| {
-                            v.and_then(string_to_datetime).map(|t| t.timestamp() as u32)
+                            v.and_then(|v| string_to_datetime(v, &tz)).map(|t| t.timestamp() as u32)
                         });
                         Ok(DFUInt32Array::from_iter(it).into_series())
                     },
@@ -164,6 +236,24 @@ impl Function for CastFunction {
                 })
             }
 
+            (_, DataType::DateTime64(precision, tz)) => {
+                with_match_primitive_type!(columns[0].data_type(), |$T| {
+                    series.cast_with_type(&self.cast_type)
+                }, {
+                   let tz = get_timezone(tz)?;
+                   match columns[0].data_type() {
+                    String => {
+                        let it = series.string()?.into_iter().map(|v| {
+                            v.and_then(|v| std::str::from_utf8(v).ok())
+                                .and_then(|v| datetime64_from_str(v, *precision, &tz))
+                        });
+                        Ok(DFInt64Array::from_iter(it).into_series())
+                    },
+                    _ =>  Err(error)
+                   }
+                })
+            }
+
             _ => series.cast_with_type(&self.cast_type),
         }?;
 
@@ -183,16 +273,15 @@ impl fmt::Display for CastFunction {
 }
 
 #[inline]
-fn datetime_to_string(date: DateTime<Utc>, fmt: &str) -> String {
+fn datetime_to_string<T: TimeZone>(date: DateTime<T>, fmt: &str) -> String
+where T::Offset: fmt::Display {
     date.format(fmt).to_string()
 }
 
-// currently use UTC by default
-// TODO support timezone
 #[inline]
-fn string_to_datetime(date_str: impl AsRef<[u8]>) -> Option<NaiveDateTime> {
+fn string_to_datetime(date_str: impl AsRef<[u8]>, tz: &Tz) -> Option<DateTime<Tz>> {
     let s = std::str::from_utf8(date_str.as_ref()).ok();
-    s.and_then(|c| NaiveDateTime::parse_from_str(c, "%Y-%m-%d %H:%M:%S").ok())
+    s.and_then(|c| tz.datetime_from_str(c, "%Y-%m-%d %H:%M:%S").ok())
 }
 
 #[inline]
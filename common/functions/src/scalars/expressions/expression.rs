@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use common_datavalues::DataType;
+use common_datavalues::DATETIME64_DEFAULT_PRECISION;
 
 use crate::scalars::function_factory::FactoryCreator;
 use crate::scalars::function_factory::FunctionDescription;
@@ -52,6 +53,10 @@ impl ToCastFunction {
         factory.register("toDate16", Self::cast_function_creator(DataType::Date16));
         factory.register("toDate32", Self::cast_function_creator(DataType::Date32));
         factory.register("toString", Self::cast_function_creator(DataType::String));
+        factory.register(
+            "toDateTime64",
+            Self::cast_function_creator(DataType::DateTime64(DATETIME64_DEFAULT_PRECISION, None)),
+        );
 
         // aliases
         factory.register("toDate", Self::cast_function_creator(DataType::Date16));
@@ -20,15 +20,18 @@ use common_exception::Result;
 use lazy_static::lazy_static;
 
 use crate::scalars::ArithmeticFunction;
+use crate::scalars::ArrayClassFunction;
 use crate::scalars::ComparisonFunction;
 use crate::scalars::ConditionalFunction;
 use crate::scalars::DateFunction;
 use crate::scalars::Function;
 use crate::scalars::HashesFunction;
 use crate::scalars::LogicFunction;
+use crate::scalars::MapFunction;
 use crate::scalars::MathsFunction;
 use crate::scalars::NullableFunction;
 use crate::scalars::OtherFunction;
+use crate::scalars::SemiStructuredFunction;
 use crate::scalars::StringFunction;
 use crate::scalars::ToCastFunction;
 use crate::scalars::TupleClassFunction;
@@ -123,6 +126,9 @@ lazy_static! {
         OtherFunction::register(&mut function_factory);
         MathsFunction::register(&mut function_factory);
         TupleClassFunction::register(&mut function_factory);
+        SemiStructuredFunction::register(&mut function_factory);
+        MapFunction::register(&mut function_factory);
+        ArrayClassFunction::register(&mut function_factory);
 
         Arc::new(function_factory)
     };
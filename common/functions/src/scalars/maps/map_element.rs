@@ -0,0 +1,133 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_arrow::arrow::array::ArrayRef;
+use common_arrow::arrow::compute::take;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::CastFunction;
+use crate::scalars::Function;
+
+/// `element_at(map, key)` returns the value of the key, NULL if the key doesn't exist.
+/// `map['key']` is rewritten to it.
+#[derive(Clone)]
+pub struct MapElementFunction {
+    display_name: String,
+}
+
+impl MapElementFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(MapElementFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic())
+    }
+}
+
+impl Function for MapElementFunction {
+    fn name(&self) -> &str {
+        &*self.display_name
+    }
+
+    fn num_arguments(&self) -> usize {
+        2
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        let (_, value_type) = map_arg_types(&self.display_name, args)?;
+        Ok(value_type.clone())
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, input_rows: usize) -> Result<DataColumn> {
+        let map = map_arg(&columns[0])?;
+        let indices = find_map_keys(&map, &columns[1], input_rows)?
+            .into_iter()
+            .map(|index| index.map(|index| index as u32))
+            .collect::<Vec<_>>();
+        let indices = DFUInt32Array::new_from_opt_slice(&indices);
+
+        let values = map.values().get_array_ref();
+        let array: ArrayRef = Arc::from(take::take(values.as_ref(), indices.inner())?);
+        Ok(array.into_series().into())
+    }
+}
+
+impl fmt::Display for MapElementFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
+/// The key and value types of the map argument.
+pub(crate) fn map_arg_types<'a>(
+    display_name: &str,
+    args: &'a [DataType],
+) -> Result<(&'a DataType, &'a DataType)> {
+    match &args[0] {
+        DataType::Map(key_type, value_type) => Ok((key_type.as_ref(), value_type.as_ref())),
+        other => Err(ErrorCode::IllegalDataType(format!(
+            "Expected map type as the first argument of {}, but got {}",
+            display_name, other
+        ))),
+    }
+}
+
+pub(crate) fn map_arg(column: &DataColumnWithField) -> Result<DFMapArray> {
+    let series = column.column().to_array()?;
+    DFMapArray::try_create(&series, column.data_type())
+}
+
+/// Find the entry of the key in each row of the map, the key is casted to the key type first.
+pub(crate) fn find_map_keys(
+    map: &DFMapArray,
+    key: &DataColumnWithField,
+    input_rows: usize,
+) -> Result<Vec<Option<usize>>> {
+    let cast = CastFunction::create("cast".to_string(), map.key_type().clone())?;
+    let key_values = cast.eval(&[key.clone()], input_rows)?.to_array()?;
+
+    let keys = map.keys();
+    let offsets = map.offsets();
+    (0..map.len())
+        .map(|row| {
+            let key_value = key_values.try_get(row)?;
+            if map.is_null(row) || key_value.is_null() {
+                return Ok(None);
+            }
+
+            let (start, end) = (offsets[row] as usize, offsets[row + 1] as usize);
+            for index in start..end {
+                if keys.try_get(index)? == key_value {
+                    return Ok(Some(index));
+                }
+            }
+            Ok(None)
+        })
+        .collect()
+}
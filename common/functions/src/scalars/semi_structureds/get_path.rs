@@ -0,0 +1,120 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde_json::Value as JsonValue;
+
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::Function;
+
+/// `get_path(json, path)` returns the Variant at a path like `a.b[0]`,
+/// NULL if the path doesn't exist or the JSON text is invalid.
+#[derive(Clone)]
+pub struct GetPathFunction {
+    display_name: String,
+}
+
+impl GetPathFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(GetPathFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic())
+    }
+}
+
+impl Function for GetPathFunction {
+    fn name(&self) -> &str {
+        &*self.display_name
+    }
+
+    fn num_arguments(&self) -> usize {
+        2
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        check_json_args(&self.display_name, args)?;
+        Ok(DataType::Variant)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, input_rows: usize) -> Result<DataColumn> {
+        let column: DataColumn = eval_get_path(&self.display_name, columns)?.into();
+        Ok(column.resize_constant(input_rows))
+    }
+}
+
+impl fmt::Display for GetPathFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
+/// The arguments are a JSON text or Variant, and a path.
+pub(crate) fn check_json_args(display_name: &str, args: &[DataType]) -> Result<()> {
+    if !matches!(
+        args[0],
+        DataType::String | DataType::Variant | DataType::Null
+    ) {
+        return Err(ErrorCode::IllegalDataType(format!(
+            "Expected string, variant or null type as the first argument of {}, but got {}",
+            display_name, args[0]
+        )));
+    }
+    if args[1] != DataType::String {
+        return Err(ErrorCode::IllegalDataType(format!(
+            "Expected string type as the path of {}, but got {}",
+            display_name, args[1]
+        )));
+    }
+    Ok(())
+}
+
+/// Get the Variant values at the constant path of the second column.
+pub(crate) fn eval_get_path(display_name: &str, columns: &DataColumnsWithField) -> Result<Series> {
+    let path = match columns[1].column() {
+        DataColumn::Constant(DataValue::String(Some(path)), _) => {
+            parse_variant_path(&String::from_utf8(path.clone())?)?
+        }
+        _ => {
+            return Err(ErrorCode::BadArguments(format!(
+                "The path of function {} must be a constant string",
+                display_name
+            )))
+        }
+    };
+
+    let series = columns[0]
+        .column()
+        .to_minimal_array()?
+        .cast_with_type(&DataType::String)?;
+
+    let values = series.string()?.into_iter().map(|v| {
+        v.and_then(|v| serde_json::from_slice::<JsonValue>(v).ok())
+            .and_then(|v| variant_get_path(&v, &path).map(|v| v.to_string()))
+    });
+    Ok(DFStringArray::new_from_opt_iter(values).into_series())
+}
@@ -0,0 +1,85 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use super::get_path::check_json_args;
+use super::get_path::eval_get_path;
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::CastFunction;
+use crate::scalars::FactoryCreator;
+use crate::scalars::Function;
+
+/// `json_extract_*(json, path)` returns the value at the path cast to a scalar type,
+/// NULL if the path doesn't exist.
+#[derive(Clone)]
+pub struct JsonExtractFunction {
+    display_name: String,
+    data_type: DataType,
+}
+
+impl JsonExtractFunction {
+    pub fn try_create(display_name: &str, data_type: DataType) -> Result<Box<dyn Function>> {
+        Ok(Box::new(JsonExtractFunction {
+            display_name: display_name.to_string(),
+            data_type,
+        }))
+    }
+
+    pub fn desc(data_type: DataType) -> FunctionDescription {
+        let function_creator: FactoryCreator =
+            Box::new(move |display_name| Self::try_create(display_name, data_type.clone()));
+
+        FunctionDescription::creator(function_creator)
+            .features(FunctionFeatures::default().deterministic())
+    }
+}
+
+impl Function for JsonExtractFunction {
+    fn name(&self) -> &str {
+        &*self.display_name
+    }
+
+    fn num_arguments(&self) -> usize {
+        2
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        check_json_args(&self.display_name, args)?;
+        Ok(self.data_type.clone())
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, input_rows: usize) -> Result<DataColumn> {
+        let series = eval_get_path(&self.display_name, columns)?;
+        let field = DataField::new(columns[0].field().name(), DataType::Variant, true);
+        let column = DataColumnWithField::new(series.into(), field);
+
+        let cast = CastFunction::create("cast".to_string(), self.data_type.clone())?;
+        cast.eval(&[column], input_rows)
+    }
+}
+
+impl fmt::Display for JsonExtractFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::function_factory::FunctionDescription;
+use crate::scalars::function_factory::FunctionFeatures;
+use crate::scalars::Function;
+
+/// `parse_json(str)` parses a JSON text into a Variant, an invalid text is an error.
+/// `try_parse_json(str)` returns NULL for an invalid text instead.
+#[derive(Clone)]
+pub struct ParseJsonFunction {
+    display_name: String,
+    suppress_error: bool,
+}
+
+impl ParseJsonFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(ParseJsonFunction {
+            display_name: display_name.to_string(),
+            suppress_error: false,
+        }))
+    }
+
+    pub fn try_create_suppress_error(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(ParseJsonFunction {
+            display_name: display_name.to_string(),
+            suppress_error: true,
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic())
+    }
+
+    pub fn try_desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create_suppress_error))
+            .features(FunctionFeatures::default().deterministic())
+    }
+}
+
+impl Function for ParseJsonFunction {
+    fn name(&self) -> &str {
+        &*self.display_name
+    }
+
+    fn num_arguments(&self) -> usize {
+        1
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        match args[0] {
+            DataType::String | DataType::Variant | DataType::Null => Ok(DataType::Variant),
+            _ => Err(ErrorCode::IllegalDataType(format!(
+                "Expected string or null type, but got {}",
+                args[0]
+            ))),
+        }
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(self.suppress_error)
+    }
+
+    fn eval(&self, columns: &DataColumnsWithField, input_rows: usize) -> Result<DataColumn> {
+        let series = columns[0]
+            .column()
+            .to_minimal_array()?
+            .cast_with_type(&DataType::String)?;
+
+        let values = series
+            .string()?
+            .into_iter()
+            .map(|v| match (v, self.suppress_error) {
+                (None, _) => Ok(None),
+                (Some(v), false) => variant_from_slice(v).map(Some),
+                (Some(v), true) => Ok(variant_from_slice(v).ok()),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let column: DataColumn = DFStringArray::new_from_opt_slice(&values).into();
+        Ok(column.resize_constant(input_rows))
+    }
+}
+
+impl fmt::Display for ParseJsonFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
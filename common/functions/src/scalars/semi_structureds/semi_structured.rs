@@ -0,0 +1,56 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataType;
+
+use crate::scalars::function_factory::FunctionFactory;
+use crate::scalars::GetPathFunction;
+use crate::scalars::JsonExtractFunction;
+use crate::scalars::ParseJsonFunction;
+
+#[derive(Clone)]
+pub struct SemiStructuredFunction;
+
+impl SemiStructuredFunction {
+    pub fn register(factory: &mut FunctionFactory) {
+        factory.register("parse_json", ParseJsonFunction::desc());
+        factory.register("try_parse_json", ParseJsonFunction::try_desc());
+        factory.register("get_path", GetPathFunction::desc());
+
+        factory.register(
+            "json_extract_raw",
+            JsonExtractFunction::desc(DataType::Variant),
+        );
+        factory.register(
+            "json_extract_string",
+            JsonExtractFunction::desc(DataType::String),
+        );
+        factory.register(
+            "json_extract_int",
+            JsonExtractFunction::desc(DataType::Int64),
+        );
+        factory.register(
+            "json_extract_uint",
+            JsonExtractFunction::desc(DataType::UInt64),
+        );
+        factory.register(
+            "json_extract_float",
+            JsonExtractFunction::desc(DataType::Float64),
+        );
+        factory.register(
+            "json_extract_bool",
+            JsonExtractFunction::desc(DataType::Boolean),
+        );
+    }
+}
@@ -53,6 +53,12 @@ fn test_aggregate_function() -> Result<()> {
         DataField::new("event = 1003", DataType::Boolean, false),
     ];
 
+    // the result of groupArray functions
+    let int64_list = |values: Vec<i64>| {
+        let values = values.into_iter().map(DataValue::from).collect();
+        DataValue::List(Some(values), DataType::Int64)
+    };
+
     let tests = vec![
         Test {
             name: "count-passed",
@@ -229,6 +235,28 @@ fn test_aggregate_function() -> Result<()> {
             expect: DataValue::UInt8(Some(3)),
             error: "",
         },
+        Test {
+            name: "groupArray-passed",
+            eval_nums: 1,
+            params: vec![],
+            args: vec![args[0].clone()],
+            display: "groupArray",
+            func_name: "groupArray",
+            arrays: vec![arrays[0].clone()],
+            expect: int64_list(vec![4, 3, 2, 1]),
+            error: "",
+        },
+        Test {
+            name: "groupUniqArray-passed",
+            eval_nums: 2,
+            params: vec![],
+            args: vec![args[0].clone()],
+            display: "groupUniqArray",
+            func_name: "groupUniqArray",
+            arrays: vec![arrays[0].clone()],
+            expect: int64_list(vec![4, 3, 2, 1]),
+            error: "",
+        },
     ];
 
     for t in tests {
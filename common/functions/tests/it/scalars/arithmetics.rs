@@ -168,6 +168,147 @@ fn test_arithmetic_function() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_arithmetic_decimal() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        func: Box<dyn Function>,
+        expect_type: DataType,
+        expect: DataColumn,
+    }
+
+    // 1.50, -0.25 and 1.5, 0.3
+    let lhs = DataColumnWithField::new(
+        Series::new(vec![150i128, -25]).into(),
+        DataField::new("a", DataType::Decimal128(10, 2), false),
+    );
+    let rhs = DataColumnWithField::new(
+        Series::new(vec![15i128, 3]).into(),
+        DataField::new("b", DataType::Decimal128(5, 1), false),
+    );
+
+    let tests = vec![
+        Test {
+            name: "plus-decimal-passed",
+            func: ArithmeticPlusFunction::try_create_func("")?,
+            expect_type: DataType::Decimal128(11, 2),
+            expect: Series::new(vec![300i128, 5]).into(),
+        },
+        Test {
+            name: "minus-decimal-passed",
+            func: ArithmeticMinusFunction::try_create_func("")?,
+            expect_type: DataType::Decimal128(11, 2),
+            expect: Series::new(vec![0i128, -55]).into(),
+        },
+        Test {
+            name: "mul-decimal-passed",
+            func: ArithmeticMulFunction::try_create_func("")?,
+            expect_type: DataType::Decimal128(15, 3),
+            expect: Series::new(vec![2250i128, -75]).into(),
+        },
+        Test {
+            name: "div-decimal-passed",
+            func: ArithmeticDivFunction::try_create_func("")?,
+            expect_type: DataType::Decimal128(17, 8),
+            expect: Series::new(vec![100000000i128, -83333333]).into(),
+        },
+    ];
+
+    for t in tests {
+        let args = vec![lhs.data_type().clone(), rhs.data_type().clone()];
+        let actual_type = t.func.return_type(&args)?;
+        assert_eq!(t.expect_type, actual_type, "{}", t.name);
+
+        let v = t.func.eval(&[lhs.clone(), rhs.clone()], 2)?;
+        assert_eq!(v, t.expect, "{}", t.name);
+    }
+
+    // the quotient is rounded half away from zero, 2.00 / 3.0 and -2.00 / 3.0
+    let lhs = DataColumnWithField::new(
+        Series::new(vec![200i128, -200]).into(),
+        DataField::new("a", DataType::Decimal128(10, 2), false),
+    );
+    let rhs = DataColumnWithField::new(
+        Series::new(vec![30i128, 30]).into(),
+        DataField::new("b", DataType::Decimal128(5, 1), false),
+    );
+    let func = ArithmeticDivFunction::try_create_func("")?;
+    let v = func.eval(&[lhs, rhs], 2)?;
+    assert_eq!(v, Series::new(vec![66666667i128, -66666667]).into());
+
+    // the integer digits are kept, and the fraction digits are reduced down to 6
+    let args = vec![DataType::Decimal128(38, 10), DataType::Decimal128(10, 5)];
+    let actual_type = func.return_type(&args)?;
+    assert_eq!(actual_type, DataType::Decimal128(38, 6));
+    Ok(())
+}
+
+#[test]
+fn test_arithmetic_decimal256() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        func: Box<dyn Function>,
+        expect_type: DataType,
+        expect: DataColumn,
+    }
+
+    let decimal256 = |values: &[i128]| {
+        Series::new(
+            values
+                .iter()
+                .map(|v| I256::from_i128(*v).to_ordered_bytes().to_vec())
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    // 1.50, -0.25 and 1.5, 0.3
+    let lhs = DataColumnWithField::new(
+        decimal256(&[150, -25]).into(),
+        DataField::new("a", DataType::Decimal256(40, 2), false),
+    );
+    let rhs = DataColumnWithField::new(
+        Series::new(vec![15i128, 3]).into(),
+        DataField::new("b", DataType::Decimal128(5, 1), false),
+    );
+
+    let tests = vec![
+        Test {
+            name: "plus-decimal256-passed",
+            func: ArithmeticPlusFunction::try_create_func("")?,
+            expect_type: DataType::Decimal256(41, 2),
+            expect: decimal256(&[300, 5]).into(),
+        },
+        Test {
+            name: "minus-decimal256-passed",
+            func: ArithmeticMinusFunction::try_create_func("")?,
+            expect_type: DataType::Decimal256(41, 2),
+            expect: decimal256(&[0, -55]).into(),
+        },
+        Test {
+            name: "mul-decimal256-passed",
+            func: ArithmeticMulFunction::try_create_func("")?,
+            expect_type: DataType::Decimal256(45, 3),
+            expect: decimal256(&[2250, -75]).into(),
+        },
+        Test {
+            name: "div-decimal256-passed",
+            func: ArithmeticDivFunction::try_create_func("")?,
+            expect_type: DataType::Decimal256(47, 8),
+            expect: decimal256(&[100000000, -83333333]).into(),
+        },
+    ];
+
+    for t in tests {
+        let args = vec![lhs.data_type().clone(), rhs.data_type().clone()];
+        let actual_type = t.func.return_type(&args)?;
+        assert_eq!(t.expect_type, actual_type, "{}", t.name);
+
+        let v = t.func.eval(&[lhs.clone(), rhs.clone()], 2)?;
+        assert_eq!(v, t.expect, "{}", t.name);
+    }
+    Ok(())
+}
+
 #[test]
 fn test_arithmetic_date_interval() -> Result<()> {
     let to_seconds = |y: i32, m: u32, d: u32, h: u32, min: u32, s: u32| -> u32 {
@@ -0,0 +1,159 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_functions::scalars::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_array_functions() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        nullable: bool,
+        args: Vec<DataColumnWithField>,
+        expect: Vec<&'static str>,
+        func: Result<Box<dyn Function>>,
+    }
+
+    // ['b','a','b'], ['c'], NULL
+    let item = DataField::new("item", DataType::String, true);
+    let array_type = DataType::List(Box::new(item));
+    let array = DFListArray::from_parts(
+        &Series::new(vec!["b", "a", "b", "c"]),
+        vec![0, 3, 4, 4],
+        Some(Bitmap::from_iter(vec![true, true, false])),
+    );
+    let array_column = DataColumnWithField::new(
+        array.into_series().into(),
+        DataField::new("arr", array_type, true),
+    );
+    let string = |value: &str| {
+        DataColumnWithField::new(
+            DataColumn::Constant(DataValue::String(Some(value.into())), 3),
+            DataField::new("s", DataType::String, false),
+        )
+    };
+    let int64 = |value: i64| {
+        DataColumnWithField::new(
+            DataColumn::Constant(DataValue::Int64(Some(value)), 3),
+            DataField::new("n", DataType::Int64, false),
+        )
+    };
+
+    let tests = vec![
+        Test {
+            name: "array-passed",
+            nullable: false,
+            args: vec![string("x"), string("y")],
+            func: ArrayFunction::try_create("array"),
+            expect: vec!["[x, y]", "[x, y]", "[x, y]"],
+        },
+        Test {
+            name: "length-passed",
+            nullable: true,
+            args: vec![array_column.clone()],
+            func: ArrayLengthFunction::try_create("length"),
+            expect: vec!["3", "1", "NULL"],
+        },
+        Test {
+            name: "has-passed",
+            nullable: true,
+            args: vec![array_column.clone(), string("a")],
+            func: ArrayHasFunction::try_create("has"),
+            expect: vec!["true", "false", "NULL"],
+        },
+        Test {
+            name: "indexOf-passed",
+            nullable: true,
+            args: vec![array_column.clone(), string("b")],
+            func: ArrayIndexOfFunction::try_create("indexOf"),
+            expect: vec!["1", "0", "NULL"],
+        },
+        Test {
+            name: "arrayConcat-passed",
+            nullable: true,
+            args: vec![array_column.clone(), array_column.clone()],
+            func: ArrayConcatFunction::try_create("arrayConcat"),
+            expect: vec!["[b, a, b, b, a, b]", "[c, c]", "NULL"],
+        },
+        Test {
+            name: "arraySlice-passed",
+            nullable: true,
+            args: vec![array_column.clone(), int64(2)],
+            func: ArraySliceFunction::try_create("arraySlice"),
+            expect: vec!["[a, b]", "[]", "NULL"],
+        },
+        Test {
+            name: "arraySlice-negative-passed",
+            nullable: true,
+            args: vec![array_column.clone(), int64(-3), int64(-1)],
+            func: ArraySliceFunction::try_create("arraySlice"),
+            expect: vec!["[b, a]", "[]", "NULL"],
+        },
+        Test {
+            name: "arrayDistinct-passed",
+            nullable: true,
+            args: vec![array_column.clone()],
+            func: ArrayDistinctFunction::try_create("arrayDistinct"),
+            expect: vec!["[b, a]", "[c]", "NULL"],
+        },
+        Test {
+            name: "arraySort-passed",
+            nullable: true,
+            args: vec![array_column.clone()],
+            func: ArraySortFunction::try_create("arraySort"),
+            expect: vec!["[a, b, b]", "[c]", "NULL"],
+        },
+        Test {
+            name: "element_at-passed",
+            nullable: true,
+            args: vec![array_column.clone(), int64(-1)],
+            func: ArrayElementFunction::try_create("element_at"),
+            expect: vec!["b", "c", "NULL"],
+        },
+    ];
+
+    for t in tests {
+        let func = t.func?;
+        assert_eq!(
+            t.nullable,
+            func.nullable(&DataSchema::empty())?,
+            "{}",
+            t.name
+        );
+
+        let values = func.eval(&t.args, 3)?.to_values()?;
+        let actual: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+        assert_eq!(t.expect, actual, "{}", t.name);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_array_join_function() -> Result<()> {
+    let item = DataField::new("item", DataType::UInt64, true);
+    let func = ArrayJoinFunction::try_create("arrayJoin")?;
+    assert_eq!(
+        DataType::UInt64,
+        func.return_type(&[DataType::List(Box::new(item))])?
+    );
+
+    let expect =
+        "Code: 7, displayText = Expected array type as the argument of arrayJoin, but got UInt64.";
+    let actual = func.return_type(&[DataType::UInt64]);
+    assert_eq!(expect, actual.unwrap_err().to_string());
+    Ok(())
+}
@@ -66,6 +66,25 @@ fn test_to_start_of_function() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_to_timezone_function() -> Result<()> {
+    let test = Test {
+        name: "test-toTimeZone",
+        display: "toTimeZone()",
+        nullable: false,
+        columns: vec![
+            Series::new(vec![1634551542u32, 1635070210]).into(),
+            DataColumn::Constant(DataValue::String(Some(b"Asia/Shanghai".to_vec())), 2),
+        ],
+        func: TimeZoneFunction::try_create_to_timezone("toTimeZone"),
+        expect: Series::new(vec![1634580342u32, 1635099010]),
+        error: "",
+    };
+
+    do_test(test)?;
+    Ok(())
+}
+
 fn do_test(t: Test) -> Result<()> {
     let dummy = DataField::new("dummy", DataType::DateTime32(None), false);
     let rows = t.columns[0].len();
@@ -31,6 +31,15 @@ fn test_cast_function() -> Result<()> {
         func: Result<Box<dyn Function>>,
     }
 
+    let decimal256 = |values: &[I256]| {
+        Series::new(
+            values
+                .iter()
+                .map(|v| v.to_ordered_bytes().to_vec())
+                .collect::<Vec<_>>(),
+        )
+    };
+
     let tests = vec![
         Test {
             name: "cast-int64-to-int8-passed",
@@ -132,6 +141,154 @@ fn test_cast_function() -> Result<()> {
             expect: Series::new(vec!["2021-03-05 01:01:01", "2021-10-24 10:10:10"]),
             error: "",
         },
+        Test {
+            name: "cast-string-to-datetime32-timezone-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![Series::new(vec!["2021-03-05 09:01:01"]).into()],
+            column_types: vec![DataType::String],
+            func: CastFunction::create(
+                "cast".to_string(),
+                DataType::DateTime32(Some("Asia/Shanghai".to_string())),
+            ),
+            expect: Series::new(vec![1614906061u32]),
+            error: "",
+        },
+        Test {
+            name: "cast-string-to-datetime64-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![Series::new(vec!["2021-03-05 01:01:01.5", "2021-10-24 10:10:10"]).into()],
+            column_types: vec![DataType::String],
+            func: CastFunction::create("cast".to_string(), DataType::DateTime64(3, None)),
+            expect: Series::new(vec![1614906061500i64, 1635070210000]),
+            error: "",
+        },
+        Test {
+            name: "cast-datetime64-to-string-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![Series::new(vec![1614906061000001i64]).into()],
+            column_types: vec![DataType::DateTime64(6, None)],
+            func: CastFunction::create("cast".to_string(), DataType::String),
+            expect: Series::new(vec!["2021-03-05 01:01:01.000001"]),
+            error: "",
+        },
+        Test {
+            name: "cast-datetime32-to-datetime64-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![Series::new(vec![1614906061u32]).into()],
+            column_types: vec![DataType::DateTime32(None)],
+            func: CastFunction::create("cast".to_string(), DataType::DateTime64(3, None)),
+            expect: Series::new(vec![1614906061000i64]),
+            error: "",
+        },
+        Test {
+            name: "cast-string-to-decimal-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![Series::new(vec!["1.235", "-0.5", "12"]).into()],
+            column_types: vec![DataType::String],
+            func: CastFunction::create("cast".to_string(), DataType::Decimal128(10, 2)),
+            expect: Series::new(vec![124i128, -50, 1200]),
+            error: "",
+        },
+        Test {
+            name: "cast-decimal-to-string-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![Series::new(vec![124i128, -50]).into()],
+            column_types: vec![DataType::Decimal128(10, 2)],
+            func: CastFunction::create("cast".to_string(), DataType::String),
+            expect: Series::new(vec!["1.24", "-0.50"]),
+            error: "",
+        },
+        Test {
+            name: "cast-decimal-to-int64-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![Series::new(vec![199i128, -199]).into()],
+            column_types: vec![DataType::Decimal128(10, 2)],
+            func: CastFunction::create("cast".to_string(), DataType::Int64),
+            expect: Series::new(vec![1i64, -1]),
+            error: "",
+        },
+        Test {
+            name: "cast-string-to-decimal256-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![Series::new(vec!["1.235", "-0.5"]).into()],
+            column_types: vec![DataType::String],
+            func: CastFunction::create("cast".to_string(), DataType::Decimal256(50, 2)),
+            expect: decimal256(&[I256::from_i128(124), I256::from_i128(-50)]),
+            error: "",
+        },
+        Test {
+            name: "cast-decimal256-to-string-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![
+                decimal256(&[I256::checked_pow10(44).unwrap(), I256::from_i128(-7)]).into(),
+            ],
+            column_types: vec![DataType::Decimal256(50, 2)],
+            func: CastFunction::create("cast".to_string(), DataType::String),
+            expect: Series::new(vec![
+                "1000000000000000000000000000000000000000000.00",
+                "-0.07",
+            ]),
+            error: "",
+        },
+        Test {
+            name: "cast-decimal128-to-decimal256-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![Series::new(vec![124i128, -50]).into()],
+            column_types: vec![DataType::Decimal128(10, 2)],
+            func: CastFunction::create("cast".to_string(), DataType::Decimal256(40, 3)),
+            expect: decimal256(&[I256::from_i128(1240), I256::from_i128(-500)]),
+            error: "",
+        },
+        Test {
+            name: "cast-string-to-variant-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![Series::new(vec![r#"{"a": [1, "b"]}"#, "null"]).into()],
+            column_types: vec![DataType::String],
+            func: CastFunction::create("cast".to_string(), DataType::Variant),
+            expect: Series::new(vec![r#"{"a":[1,"b"]}"#, "null"]),
+            error: "",
+        },
+        Test {
+            name: "cast-int64-to-variant-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![Series::new(vec![1i64, -2]).into()],
+            column_types: vec![DataType::Int64],
+            func: CastFunction::create("cast".to_string(), DataType::Variant),
+            expect: Series::new(vec!["1", "-2"]),
+            error: "",
+        },
+        Test {
+            name: "cast-variant-to-int64-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![Series::new(vec!["3", r#""4""#]).into()],
+            column_types: vec![DataType::Variant],
+            func: CastFunction::create("cast".to_string(), DataType::Int64),
+            expect: Series::new(vec![3i64, 4]),
+            error: "",
+        },
+        Test {
+            name: "cast-variant-to-string-passed",
+            display: "CAST",
+            nullable: false,
+            columns: vec![Series::new(vec![r#""abc""#, "[1,2]"]).into()],
+            column_types: vec![DataType::Variant],
+            func: CastFunction::create("cast".to_string(), DataType::String),
+            expect: Series::new(vec!["abc", "[1,2]"]),
+            error: "",
+        },
     ];
 
     for t in tests {
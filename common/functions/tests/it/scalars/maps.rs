@@ -0,0 +1,97 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_functions::scalars::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_map_functions() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        nullable: bool,
+        key: Option<DataColumn>,
+        expect: Vec<&'static str>,
+        func: Result<Box<dyn Function>>,
+    }
+
+    // {'a':1,'b':2}, {'c':3}, NULL
+    let map_type = DataType::Map(Box::new(DataType::String), Box::new(DataType::Int64));
+    let map = DFMapArray::from_parts(
+        &map_type,
+        &Series::new(vec!["a", "b", "c"]),
+        &Series::new(vec![1i64, 2, 3]),
+        vec![0, 2, 3, 3],
+        Some(Bitmap::from_iter(vec![true, true, false])),
+    )?;
+    let map_column = DataColumnWithField::new(
+        map.into_series().into(),
+        DataField::new("m", map_type, true),
+    );
+    let key = |key: &str| DataColumn::Constant(DataValue::String(Some(key.into())), 3);
+
+    let tests = vec![
+        Test {
+            name: "mapKeys-passed",
+            nullable: true,
+            key: None,
+            func: MapKeysFunction::try_create("mapKeys"),
+            expect: vec!["[a, b]", "[c]", "NULL"],
+        },
+        Test {
+            name: "mapValues-passed",
+            nullable: true,
+            key: None,
+            func: MapValuesFunction::try_create("mapValues"),
+            expect: vec!["[1, 2]", "[3]", "NULL"],
+        },
+        Test {
+            name: "mapContains-passed",
+            nullable: false,
+            key: Some(key("b")),
+            func: MapContainsFunction::try_create("mapContains"),
+            expect: vec!["true", "false", "false"],
+        },
+        Test {
+            name: "element_at-passed",
+            nullable: true,
+            key: Some(key("c")),
+            func: MapElementFunction::try_create("element_at"),
+            expect: vec!["NULL", "3", "NULL"],
+        },
+    ];
+
+    for t in tests {
+        let func = t.func?;
+        assert_eq!(
+            t.nullable,
+            func.nullable(&DataSchema::empty())?,
+            "{}",
+            t.name
+        );
+
+        let mut columns = vec![map_column.clone()];
+        if let Some(key) = t.key {
+            let key_field = DataField::new("key", DataType::String, false);
+            columns.push(DataColumnWithField::new(key, key_field));
+        }
+
+        let values = func.eval(&columns, 3)?.to_values()?;
+        let actual: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+        assert_eq!(t.expect, actual, "{}", t.name);
+    }
+    Ok(())
+}
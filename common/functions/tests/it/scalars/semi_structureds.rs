@@ -0,0 +1,130 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_functions::scalars::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_semi_structured_functions() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        nullable: bool,
+        columns: Vec<DataColumn>,
+        column_types: Vec<DataType>,
+        expect: Series,
+        error: &'static str,
+        func: Result<Box<dyn Function>>,
+    }
+
+    let path = |path: &str| DataColumn::Constant(DataValue::String(Some(path.into())), 3);
+
+    let tests = vec![
+        Test {
+            name: "parse_json-passed",
+            nullable: false,
+            columns: vec![Series::new(vec![r#"{"a": 1}"#, "[1, 2 ]", r#""s""#]).into()],
+            column_types: vec![DataType::String],
+            func: ParseJsonFunction::try_create("parse_json"),
+            expect: Series::new(vec![r#"{"a":1}"#, "[1,2]", r#""s""#]),
+            error: "",
+        },
+        Test {
+            name: "parse_json-invalid",
+            nullable: false,
+            columns: vec![Series::new(vec!["abc"]).into()],
+            column_types: vec![DataType::String],
+            func: ParseJsonFunction::try_create("parse_json"),
+            expect: Series::new(vec![""]),
+            error: "Code: 46, displayText = Cannot parse value to Variant type: expected value at line 1 column 1.",
+        },
+        Test {
+            name: "try_parse_json-invalid",
+            nullable: true,
+            columns: vec![Series::new(vec!["abc", "1.5"]).into()],
+            column_types: vec![DataType::String],
+            func: ParseJsonFunction::try_create_suppress_error("try_parse_json"),
+            expect: Series::new(vec![None, Some("1.5")]),
+            error: "",
+        },
+        Test {
+            name: "get_path-passed",
+            nullable: true,
+            columns: vec![
+                Series::new(vec![r#"{"a":{"b":[1,2]}}"#, r#"{"a":{}}"#, "[]"]).into(),
+                path("a.b[1]"),
+            ],
+            column_types: vec![DataType::Variant, DataType::String],
+            func: GetPathFunction::try_create("get_path"),
+            expect: Series::new(vec![Some("2"), None, None]),
+            error: "",
+        },
+        Test {
+            name: "json_extract_int-passed",
+            nullable: true,
+            columns: vec![
+                Series::new(vec![r#"{"a":3}"#, r#"{"a":"4"}"#, r#"{"b":1}"#]).into(),
+                path("a"),
+            ],
+            column_types: vec![DataType::String, DataType::String],
+            func: JsonExtractFunction::try_create("json_extract_int", DataType::Int64),
+            expect: Series::new(vec![Some(3i64), Some(4), None]),
+            error: "",
+        },
+        Test {
+            name: "json_extract_string-passed",
+            nullable: true,
+            columns: vec![
+                Series::new(vec![r#"{"a":"x"}"#, r#"{"a":[1]}"#, r#"{"a":null}"#]).into(),
+                path("a"),
+            ],
+            column_types: vec![DataType::Variant, DataType::String],
+            func: JsonExtractFunction::try_create("json_extract_string", DataType::String),
+            expect: Series::new(vec![Some("x"), Some("[1]"), None]),
+            error: "",
+        },
+    ];
+
+    for t in tests {
+        let rows = t.columns[0].len();
+
+        let columns: Vec<DataColumnWithField> = t
+            .columns
+            .iter()
+            .zip(t.column_types.iter())
+            .map(|(c, t)| {
+                let dummy = DataField::new("dummy", t.clone(), false);
+                DataColumnWithField::new(c.clone(), dummy)
+            })
+            .collect();
+
+        let func = t.func?;
+        assert_eq!(
+            t.nullable,
+            func.nullable(&DataSchema::empty())?,
+            "{}",
+            t.name
+        );
+
+        match func.eval(&columns, rows) {
+            Ok(v) => {
+                let c: DataColumn = t.expect.into();
+                assert_eq!(v, c, "{}", t.name);
+            }
+            Err(e) => assert_eq!(t.error, e.to_string(), "{}", t.name),
+        }
+    }
+    Ok(())
+}
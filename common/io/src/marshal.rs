@@ -88,6 +88,12 @@ impl Marshal for i64 {
     }
 }
 
+impl Marshal for i128 {
+    fn marshal(&self, scratch: &mut [u8]) {
+        scratch[..16].copy_from_slice(&self.to_le_bytes());
+    }
+}
+
 impl Marshal for f32 {
     fn marshal(&self, scratch: &mut [u8]) {
         let bits = self.to_bits();
@@ -14,14 +14,17 @@
 
 mod plan_aggregator_final;
 mod plan_aggregator_partial;
+mod plan_array_join;
 mod plan_broadcast;
 mod plan_builder;
 mod plan_copy;
 mod plan_database_create;
 mod plan_database_drop;
+mod plan_delete;
 mod plan_describe_table;
 mod plan_display;
 mod plan_display_indent;
+mod plan_distinct;
 mod plan_empty;
 mod plan_explain;
 mod plan_expression;
@@ -40,16 +43,20 @@ mod plan_filter;
 mod plan_grant_privilege;
 mod plan_having;
 mod plan_insert_into;
+mod plan_join;
 mod plan_kill;
 mod plan_limit;
 mod plan_limit_by;
+mod plan_materialized_cte;
 mod plan_node;
+mod plan_optimize_table;
 mod plan_partition;
 mod plan_projection;
 mod plan_read_datasource;
 mod plan_remote;
 mod plan_rewriter;
 mod plan_select;
+mod plan_set_operation;
 mod plan_setting;
 mod plan_show_table_create;
 mod plan_sink;
@@ -59,22 +66,31 @@ mod plan_statistics;
 mod plan_subqueries_set;
 mod plan_table_create;
 mod plan_table_drop;
+mod plan_time_travel;
 mod plan_truncate_table;
+mod plan_union;
+mod plan_update;
 mod plan_use_database;
 mod plan_user_alter;
 mod plan_user_create;
 mod plan_user_drop;
+mod plan_vacuum_table;
 mod plan_visitor;
+mod plan_window;
 
 pub use plan_aggregator_final::AggregatorFinalPlan;
 pub use plan_aggregator_partial::AggregatorPartialPlan;
+pub use plan_array_join::is_array_join_function;
+pub use plan_array_join::ArrayJoinPlan;
 pub use plan_broadcast::BroadcastPlan;
 pub use plan_builder::PlanBuilder;
 pub use plan_copy::CopyPlan;
 pub use plan_database_create::CreateDatabasePlan;
 pub use plan_database_create::DatabaseOptions;
 pub use plan_database_drop::DropDatabasePlan;
+pub use plan_delete::DeletePlan;
 pub use plan_describe_table::DescribeTablePlan;
+pub use plan_distinct::DistinctPlan;
 pub use plan_empty::EmptyPlan;
 pub use plan_explain::ExplainPlan;
 pub use plan_explain::ExplainType;
@@ -86,11 +102,16 @@ pub use plan_expression_chain::ExpressionChain;
 pub use plan_expression_column::col;
 pub use plan_expression_common::expand_aggregate_arg_exprs;
 pub use plan_expression_common::expand_wildcard;
+pub use plan_expression_common::expand_window_arg_exprs;
 pub use plan_expression_common::expr_as_column_expr;
 pub use plan_expression_common::extract_aliases;
 pub use plan_expression_common::find_aggregate_exprs;
 pub use plan_expression_common::find_aggregate_exprs_in_expr;
+pub use plan_expression_common::find_array_join_exprs;
+pub use plan_expression_common::find_array_join_exprs_in_expr;
 pub use plan_expression_common::find_columns_not_satisfy_exprs;
+pub use plan_expression_common::find_window_exprs;
+pub use plan_expression_common::find_window_exprs_in_expr;
 pub use plan_expression_common::rebase_expr;
 pub use plan_expression_common::rebase_expr_from_input;
 pub use plan_expression_common::resolve_aliases_to_exprs;
@@ -113,10 +134,15 @@ pub use plan_filter::FilterPlan;
 pub use plan_grant_privilege::GrantPrivilegePlan;
 pub use plan_having::HavingPlan;
 pub use plan_insert_into::InsertIntoPlan;
+pub use plan_join::JoinPlan;
+pub use plan_join::JoinType;
 pub use plan_kill::KillPlan;
 pub use plan_limit::LimitPlan;
 pub use plan_limit_by::LimitByPlan;
+pub use plan_materialized_cte::MaterializedCtePlan;
 pub use plan_node::PlanNode;
+pub use plan_optimize_table::Optimization;
+pub use plan_optimize_table::OptimizeTablePlan;
 pub use plan_partition::Part;
 pub use plan_partition::Partitions;
 pub use plan_projection::ProjectionPlan;
@@ -125,6 +151,8 @@ pub use plan_remote::RemotePlan;
 pub use plan_rewriter::PlanRewriter;
 pub use plan_rewriter::RewriteHelper;
 pub use plan_select::SelectPlan;
+pub use plan_set_operation::SetOperationPlan;
+pub use plan_set_operation::SetOperator;
 pub use plan_setting::SettingPlan;
 pub use plan_setting::VarValue;
 pub use plan_show_table_create::ShowCreateTablePlan;
@@ -133,14 +161,24 @@ pub use plan_sink::SINK_SCHEMA;
 pub use plan_sort::SortPlan;
 pub use plan_stage::StageKind;
 pub use plan_stage::StagePlan;
+pub use plan_statistics::ColumnStatistics;
 pub use plan_statistics::Statistics;
 pub use plan_subqueries_set::SubQueriesSetPlan;
 pub use plan_table_create::CreateTablePlan;
 pub use plan_table_create::TableOptions;
 pub use plan_table_drop::DropTablePlan;
+pub use plan_time_travel::TimeTravelPoint;
 pub use plan_truncate_table::TruncateTablePlan;
+pub use plan_union::UnionPlan;
+pub use plan_update::UpdatePlan;
 pub use plan_use_database::UseDatabasePlan;
 pub use plan_user_alter::AlterUserPlan;
 pub use plan_user_create::CreateUserPlan;
 pub use plan_user_drop::DropUserPlan;
+pub use plan_vacuum_table::VacuumTablePlan;
 pub use plan_visitor::PlanVisitor;
+pub use plan_window::is_window_only_function;
+pub use plan_window::WindowFrame;
+pub use plan_window::WindowFrameBound;
+pub use plan_window::WindowFrameUnits;
+pub use plan_window::WindowPlan;
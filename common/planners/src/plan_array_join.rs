@@ -0,0 +1,46 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+
+use crate::Expression;
+use crate::PlanNode;
+
+/// `unnest` is the alias of `arrayJoin`.
+pub fn is_array_join_function(name: &str) -> bool {
+    matches!(name.to_lowercase().as_str(), "arrayjoin" | "unnest")
+}
+
+/// Flattens the arrays of the `arrayJoin` functions into rows, the other columns are repeated
+/// for each element. The elements are appended to the input columns, rows with empty or
+/// NULL arrays are dropped, and the arrays of a row are flattened side by side.
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub struct ArrayJoinPlan {
+    /// The `arrayJoin` functions, their arguments are columns of the input.
+    pub array_join_exprs: Vec<Expression>,
+    pub schema: DataSchemaRef,
+    pub input: Arc<PlanNode>,
+}
+
+impl ArrayJoinPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        self.schema.clone()
+    }
+
+    pub fn set_input(&mut self, node: &PlanNode) {
+        self.input = Arc::new(node.clone());
+    }
+}
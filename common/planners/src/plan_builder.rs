@@ -15,18 +15,23 @@
 use std::sync::Arc;
 
 use common_datablocks::DataBlock;
+use common_datavalues::compare_coercion;
 use common_datavalues::DataField;
 use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataType;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::col;
+use crate::is_array_join_function;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::validate_expression;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::ArrayJoinPlan;
+use crate::DistinctPlan;
 use crate::EmptyPlan;
 use crate::ExplainPlan;
 use crate::ExplainType;
@@ -34,13 +39,20 @@ use crate::Expression;
 use crate::ExpressionPlan;
 use crate::FilterPlan;
 use crate::HavingPlan;
+use crate::JoinPlan;
+use crate::JoinType;
 use crate::LimitByPlan;
 use crate::LimitPlan;
+use crate::MaterializedCtePlan;
 use crate::PlanNode;
 use crate::ProjectionPlan;
 use crate::RewriteHelper;
 use crate::SelectPlan;
+use crate::SetOperationPlan;
+use crate::SetOperator;
 use crate::SortPlan;
+use crate::UnionPlan;
+use crate::WindowPlan;
 
 pub enum AggregateMode {
     Partial,
@@ -218,6 +230,238 @@ impl PlanBuilder {
         })))
     }
 
+    /// Apply a join, `self` is the left (probe) side, `right` is the build side.
+    /// Keys of different types are casted to the common type of them.
+    pub fn join(
+        &self,
+        right: &PlanNode,
+        join_type: JoinType,
+        left_keys: &[Expression],
+        right_keys: &[Expression],
+        other_conditions: Option<Expression>,
+    ) -> Result<Self> {
+        if left_keys.len() != right_keys.len() {
+            return Err(ErrorCode::BadArguments(format!(
+                "Join keys mismatch, left keys: {}, right keys: {}",
+                left_keys.len(),
+                right_keys.len()
+            )));
+        }
+
+        let left_schema = self.plan.schema();
+        let right_schema = right.schema();
+        for field in right_schema.fields() {
+            if left_schema.column_with_name(field.name()).is_some() {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Duplicate column name {} in both sides of join",
+                    field.name()
+                )));
+            }
+        }
+
+        let mut coerced_left_keys = Vec::with_capacity(left_keys.len());
+        let mut coerced_right_keys = Vec::with_capacity(right_keys.len());
+        for (left_key, right_key) in left_keys.iter().zip(right_keys.iter()) {
+            validate_expression(left_key)?;
+            validate_expression(right_key)?;
+            let left_type = left_key.to_data_type(&left_schema)?;
+            let right_type = right_key.to_data_type(&right_schema)?;
+            let key_type = compare_coercion(&left_type, &right_type)?;
+            let coerce =
+                |key: &Expression, key_type_before: DataType| match key_type_before == key_type {
+                    true => key.clone(),
+                    false => Expression::Cast {
+                        expr: Box::new(key.clone()),
+                        data_type: key_type.clone(),
+                    },
+                };
+            coerced_left_keys.push(coerce(left_key, left_type));
+            coerced_right_keys.push(coerce(right_key, right_type));
+        }
+
+        if let Some(conditions) = &other_conditions {
+            validate_expression(conditions)?;
+            conditions.to_data_type(&JoinPlan::joined_schema(&left_schema, &right_schema))?;
+        }
+
+        Ok(Self::from(&PlanNode::Join(JoinPlan {
+            join_type,
+            left_keys: coerced_left_keys,
+            right_keys: coerced_right_keys,
+            other_conditions,
+            schema: JoinPlan::output_schema(join_type, &left_schema, &right_schema),
+            left: Arc::new(self.plan.clone()),
+            right: Arc::new(right.clone()),
+        })))
+    }
+
+    /// Apply an array join plan, the arrays of the same row must have the same length.
+    pub fn array_join(&self, array_join_exprs: &[Expression]) -> Result<Self> {
+        let input_schema = self.plan.schema();
+        let mut fields = input_schema.fields().clone();
+
+        for array_join_expr in array_join_exprs {
+            match array_join_expr {
+                Expression::ScalarFunction { op, args }
+                    if is_array_join_function(op)
+                        && matches!(args.as_slice(), [Expression::Column(_)]) => {}
+                _ => {
+                    return Err(ErrorCode::LogicalError(format!(
+                        "Array join plan expects arrayJoin of columns, but got {:?}",
+                        array_join_expr
+                    )));
+                }
+            }
+
+            validate_expression(array_join_expr)?;
+            fields.push(array_join_expr.to_data_field(&input_schema)?);
+        }
+
+        Ok(Self::from(&PlanNode::ArrayJoin(ArrayJoinPlan {
+            array_join_exprs: array_join_exprs.to_vec(),
+            schema: DataSchemaRefExt::create(fields),
+            input: Arc::new(self.plan.clone()),
+        })))
+    }
+
+    /// Apply a window plan, the window functions must have the same PARTITION BY and ORDER BY.
+    pub fn window(&self, window_exprs: &[Expression]) -> Result<Self> {
+        let input_schema = self.plan.schema();
+        let mut fields = input_schema.fields().clone();
+        let mut window = None;
+
+        for window_expr in window_exprs {
+            let op = match window_expr {
+                Expression::WindowFunction {
+                    op,
+                    partition_by,
+                    order_by,
+                    ..
+                } => match window.replace((partition_by, order_by)) {
+                    Some(previous) if previous != (partition_by, order_by) => {
+                        return Err(ErrorCode::LogicalError(
+                            "Window functions of the same window plan must have the same window",
+                        ));
+                    }
+                    _ => op,
+                },
+                _ => {
+                    return Err(ErrorCode::LogicalError(format!(
+                        "Window plan expects window functions, but got {:?}",
+                        window_expr
+                    )));
+                }
+            };
+
+            validate_expression(window_expr)?;
+            let field = window_expr.to_data_field(&input_schema)?;
+            // Rows without enough preceding or following rows and empty frames are NULLs.
+            let nullable = !matches!(
+                op.to_lowercase().as_str(),
+                "row_number" | "rank" | "dense_rank"
+            );
+            fields.push(DataField::new(
+                field.name(),
+                field.data_type().clone(),
+                nullable,
+            ));
+        }
+
+        Ok(Self::from(&PlanNode::Window(WindowPlan {
+            window_exprs: window_exprs.to_vec(),
+            schema: DataSchemaRefExt::create(fields),
+            input: Arc::new(self.plan.clone()),
+        })))
+    }
+
+    /// Apply a UNION ALL of `self` and the others. The columns are matched by position, and
+    /// the inputs are projected to the columns of the union if their names or types differ.
+    pub fn union(&self, others: &[PlanNode]) -> Result<Self> {
+        let mut inputs = Vec::with_capacity(others.len() + 1);
+        inputs.push(self.plan.clone());
+        inputs.extend(others.iter().cloned());
+
+        let schemas = inputs
+            .iter()
+            .map(|input| input.schema())
+            .collect::<Vec<_>>();
+        let schema = SetOperationPlan::output_schema(SetOperator::Union, &schemas)?;
+        let inputs = inputs
+            .iter()
+            .map(|input| Ok(Arc::new(Self::set_operation_input(input, &schema)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self::from(&PlanNode::Union(UnionPlan { inputs, schema })))
+    }
+
+    pub fn distinct(&self) -> Result<Self> {
+        Ok(Self::from(&PlanNode::Distinct(DistinctPlan {
+            input: Arc::new(self.plan.clone()),
+        })))
+    }
+
+    pub fn materialized_cte(&self, name: &str) -> Result<Self> {
+        Ok(Self::from(&PlanNode::MaterializedCte(
+            MaterializedCtePlan {
+                name: name.to_string(),
+                input: Arc::new(self.plan.clone()),
+            },
+        )))
+    }
+
+    /// Apply a set operation, `self` is the left input.
+    pub fn set_operation(&self, op: SetOperator, all: bool, right: &PlanNode) -> Result<Self> {
+        if let SetOperator::Union = op {
+            let union = self.union(&[right.clone()])?;
+            return match all {
+                true => Ok(union),
+                false => union.distinct(),
+            };
+        }
+
+        let schemas = [self.plan.schema(), right.schema()];
+        let schema = SetOperationPlan::output_schema(op, &schemas)?;
+        Ok(Self::from(&PlanNode::SetOperation(SetOperationPlan {
+            op,
+            all,
+            left: Arc::new(Self::set_operation_input(&self.plan, &schema)?),
+            right: Arc::new(Self::set_operation_input(right, &schema)?),
+            schema,
+        })))
+    }
+
+    // Cast and rename the columns of the input to the columns of the set operation.
+    fn set_operation_input(input: &PlanNode, schema: &DataSchemaRef) -> Result<PlanNode> {
+        let input_schema = input.schema();
+        let fields = input_schema.fields().iter().zip(schema.fields().iter());
+        let require_project = fields.clone().any(|(input_field, field)| {
+            input_field.name() != field.name() || input_field.data_type() != field.data_type()
+        });
+
+        if !require_project {
+            return Ok(input.clone());
+        }
+
+        let exprs = fields
+            .map(|(input_field, field)| {
+                let mut expr = col(input_field.name());
+                if input_field.data_type() != field.data_type() {
+                    expr = Expression::Cast {
+                        expr: Box::new(expr),
+                        data_type: field.data_type().clone(),
+                    };
+                }
+
+                match &expr.column_name() == field.name() {
+                    true => expr,
+                    false => expr.alias(field.name()),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Self::from(input).project(&exprs)?.build()
+    }
+
     pub fn sort(&self, exprs: &[Expression]) -> Result<Self> {
         Ok(Self::from(&PlanNode::Sort(SortPlan {
             order_by: exprs.to_vec(),
@@ -27,6 +27,13 @@ impl PlanNode {
         PlanNodeIndentFormatDisplay::create(0, self, false)
     }
 
+    pub fn display_indent_format_with_annotation<'a>(
+        &'a self,
+        annotation: &'a dyn Fn(&PlanNode) -> String,
+    ) -> impl fmt::Display + 'a {
+        PlanNodeIndentFormatDisplay::create(0, self, false).with_annotation(annotation)
+    }
+
     pub fn display_graphviz(&self) -> impl fmt::Display + '_ {
         struct Wrapper<'a>(&'a PlanNode);
         impl<'a> fmt::Display for Wrapper<'a> {
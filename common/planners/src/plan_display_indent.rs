@@ -18,24 +18,29 @@ use std::fmt::Formatter;
 use crate::plan_broadcast::BroadcastPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::ArrayJoinPlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::DropDatabasePlan;
 use crate::DropTablePlan;
 use crate::Expression;
 use crate::ExpressionPlan;
+use crate::JoinPlan;
 use crate::LimitPlan;
 use crate::PlanNode;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
+use crate::SetOperationPlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::SubQueriesSetPlan;
+use crate::WindowPlan;
 
 pub struct PlanNodeIndentFormatDisplay<'a> {
     indent: usize,
     node: &'a PlanNode,
     printed_indent: bool,
+    annotation: Option<&'a dyn Fn(&PlanNode) -> String>,
 }
 
 impl<'a> PlanNodeIndentFormatDisplay<'a> {
@@ -44,6 +49,30 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
             indent,
             node,
             printed_indent: printed,
+            annotation: None,
+        }
+    }
+
+    /// Appends the annotation to the line of each node.
+    pub fn with_annotation(mut self, annotation: &'a dyn Fn(&PlanNode) -> String) -> Self {
+        self.annotation = Some(annotation);
+        self
+    }
+
+    fn create_input<'b>(
+        &self,
+        indent: usize,
+        input: &'b PlanNode,
+        printed: bool,
+    ) -> PlanNodeIndentFormatDisplay<'b>
+    where
+        'a: 'b,
+    {
+        PlanNodeIndentFormatDisplay {
+            indent,
+            node: input,
+            printed_indent: printed,
+            annotation: self.annotation,
         }
     }
 }
@@ -63,6 +92,13 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             PlanNode::AggregatorFinal(plan) => Self::format_aggregator_final(f, plan),
             PlanNode::Filter(plan) => write!(f, "Filter: {:?}", plan.predicate),
             PlanNode::Having(plan) => write!(f, "Having: {:?}", plan.predicate),
+            PlanNode::Join(plan) => Self::format_join(f, plan),
+            PlanNode::ArrayJoin(plan) => Self::format_array_join(f, plan),
+            PlanNode::Window(plan) => Self::format_window(f, plan),
+            PlanNode::Union(plan) => write!(f, "UnionAll: {} inputs", plan.inputs.len()),
+            PlanNode::Distinct(_) => write!(f, "Distinct"),
+            PlanNode::SetOperation(plan) => Self::format_set_operation(f, plan),
+            PlanNode::MaterializedCte(plan) => write!(f, "MaterializedCte: {}", plan.name),
             PlanNode::Sort(plan) => Self::format_sort(f, plan),
             PlanNode::Limit(plan) => Self::format_limit(f, plan),
             PlanNode::SubQueryExpression(plan) => Self::format_subquery_expr(f, plan),
@@ -83,7 +119,7 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
                         writeln!(f)?;
                     }
 
-                    PlanNodeIndentFormatDisplay::create(self.indent, input.as_ref(), printed)
+                    self.create_input(self.indent, input.as_ref(), printed)
                         .fmt(f)?;
                     printed = true;
                 }
@@ -92,6 +128,10 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             }
         }?;
 
+        if let Some(annotation) = self.annotation {
+            write!(f, "{}", annotation(self.node))?;
+        }
+
         let new_indent = self.indent + 1;
         for input in self.node.inputs() {
             if matches!(input.as_ref(), PlanNode::Empty(_)) {
@@ -99,7 +139,7 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             }
 
             writeln!(f)?;
-            PlanNodeIndentFormatDisplay::create(new_indent, &input, false).fmt(f)?;
+            self.create_input(new_indent, &input, false).fmt(f)?;
         }
 
         fmt::Result::Ok(())
@@ -165,6 +205,38 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
         )
     }
 
+    fn format_array_join(f: &mut Formatter, plan: &ArrayJoinPlan) -> fmt::Result {
+        write!(f, "ArrayJoin: ")?;
+        for (i, expr) in plan.array_join_exprs.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:?}", expr)?;
+        }
+
+        fmt::Result::Ok(())
+    }
+
+    fn format_window(f: &mut Formatter, plan: &WindowPlan) -> fmt::Result {
+        write!(f, "Window: ")?;
+        for (i, expr) in plan.window_exprs.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            let data_type = expr.to_data_type(&plan.schema()).map_err(|_| fmt::Error)?;
+            write!(f, "{:?}:{:?}", expr, data_type)?;
+        }
+
+        fmt::Result::Ok(())
+    }
+
+    fn format_set_operation(f: &mut Formatter, plan: &SetOperationPlan) -> fmt::Result {
+        match plan.all {
+            true => write!(f, "SetOperation: {} ALL", plan.op),
+            false => write!(f, "SetOperation: {}", plan.op),
+        }
+    }
+
     fn format_sort(f: &mut Formatter, plan: &SortPlan) -> fmt::Result {
         write!(f, "Sort: ")?;
         for i in 0..plan.order_by.len() {
@@ -191,6 +263,28 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
         }
     }
 
+    fn format_join(f: &mut Formatter, plan: &JoinPlan) -> fmt::Result {
+        write!(f, "HashJoin: {} JOIN, keys: [", plan.join_type)?;
+        for (i, (left, right)) in plan
+            .left_keys
+            .iter()
+            .zip(plan.right_keys.iter())
+            .enumerate()
+        {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:?} = {:?}", left, right)?;
+        }
+        write!(f, "]")?;
+
+        if let Some(conditions) = &plan.other_conditions {
+            write!(f, ", other conditions: {:?}", conditions)?;
+        }
+
+        fmt::Result::Ok(())
+    }
+
     fn format_subquery_expr(f: &mut Formatter, plan: &SubQueriesSetPlan) -> fmt::Result {
         let mut names = Vec::with_capacity(plan.expressions.len());
         for expression in &plan.expressions {
@@ -26,6 +26,8 @@ pub enum ExplainType {
     Syntax,
     Graph,
     Pipeline,
+    /// The plan with the estimated rows and cost of each node.
+    Cost,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
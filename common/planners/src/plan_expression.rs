@@ -28,6 +28,7 @@ use common_functions::scalars::FunctionFactory;
 use lazy_static::lazy_static;
 
 use crate::PlanNode;
+use crate::WindowFrame;
 
 lazy_static! {
     static ref OP_SET: HashSet<&'static str> = ["database", "version", "current_user"]
@@ -96,6 +97,20 @@ pub enum Expression {
         args: Vec<Expression>,
     },
 
+    /// WindowFunction with a set of arguments, evaluated for each row over the rows of
+    /// the same partition within the frame, the function is either a window only function
+    /// (e.g. row_number) or an aggregate function.
+    WindowFunction {
+        op: String,
+        params: Vec<DataValue>,
+        args: Vec<Expression>,
+        partition_by: Vec<Expression>,
+        /// The sort expressions of the window.
+        order_by: Vec<Expression>,
+        /// The default frame is used if it's None, see `WindowFrame::default_frame`.
+        frame: Option<WindowFrame>,
+    },
+
     /// A sort expression, that can be used to sort values.
     Sort {
         /// The expression to sort on
@@ -211,6 +226,7 @@ impl Expression {
                     false => format!("{}({})", prefix, args_column_name.join(", ")),
                 }
             }
+            Expression::WindowFunction { .. } => format!("{:?}", self),
             Expression::Sort { expr, .. } => expr.column_name(),
             Expression::Cast { expr, data_type } => {
                 format!("cast({} as {:?})", expr.column_name(), data_type)
@@ -304,6 +320,20 @@ impl Expression {
                 let func = self.to_aggregate_function(input_schema)?;
                 func.return_type()
             }
+            Expression::WindowFunction { op, args, .. } => match op.to_lowercase().as_str() {
+                "row_number" | "rank" | "dense_rank" => Ok(DataType::UInt64),
+                "lag" | "lead" | "first_value" | "last_value" => match args.first() {
+                    Some(arg) => arg.to_data_type(input_schema),
+                    None => Err(ErrorCode::NumberArgumentsNotMatch(format!(
+                        "{} expect to have at least 1 argument, but got 0",
+                        op
+                    ))),
+                },
+                _ => {
+                    let func = self.to_aggregate_function(input_schema)?;
+                    func.return_type()
+                }
+            },
             Expression::Wildcard => Result::Err(ErrorCode::IllegalDataType(
                 "Wildcard expressions are not valid to get return type",
             )),
@@ -313,7 +343,7 @@ impl Expression {
     }
 
     pub fn to_aggregate_function(&self, schema: &DataSchemaRef) -> Result<AggregateFunctionRef> {
-        match self {
+        let (func_name, params, args) = match self {
             Expression::AggregateFunction {
                 op,
                 distinct,
@@ -324,22 +354,30 @@ impl Expression {
                 if *distinct {
                     func_name += "Distinct";
                 }
-
-                let mut fields = Vec::with_capacity(args.len());
-                for arg in args.iter() {
-                    fields.push(arg.to_data_field(schema)?);
-                }
-                AggregateFunctionFactory::instance().get(&func_name, params.clone(), fields)
+                (func_name, params, args)
             }
-            _ => Err(ErrorCode::LogicalError(
-                "Expression must be aggregated function",
-            )),
+            // Aggregate functions over window.
+            Expression::WindowFunction {
+                op, params, args, ..
+            } => (op.clone(), params, args),
+            _ => {
+                return Err(ErrorCode::LogicalError(
+                    "Expression must be aggregated function",
+                ))
+            }
+        };
+
+        let mut fields = Vec::with_capacity(args.len());
+        for arg in args.iter() {
+            fields.push(arg.to_data_field(schema)?);
         }
+        AggregateFunctionFactory::instance().get(&func_name, params.clone(), fields)
     }
 
     pub fn to_aggregate_function_names(&self) -> Result<Vec<String>> {
         match self {
-            Expression::AggregateFunction { args, .. } => {
+            Expression::AggregateFunction { args, .. }
+            | Expression::WindowFunction { args, .. } => {
                 let mut names = Vec::with_capacity(args.len());
                 for arg in args.iter() {
                     names.push(arg.column_name());
@@ -426,6 +464,53 @@ impl fmt::Debug for Expression {
                 Ok(())
             }
 
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+                frame,
+            } => {
+                let args_column_name = args.iter().map(Expression::column_name).collect::<Vec<_>>();
+                let params_name = params
+                    .iter()
+                    .map(|v| DataValue::custom_display(v, true))
+                    .collect::<Vec<_>>();
+
+                if params.is_empty() {
+                    write!(f, "{}", op)?;
+                } else {
+                    write!(f, "{}({})", op, params_name.join(", "))?;
+                };
+                write!(f, "({}) OVER (", args_column_name.join(", "))?;
+
+                let mut clauses = vec![];
+                if !partition_by.is_empty() {
+                    let names = partition_by
+                        .iter()
+                        .map(Expression::column_name)
+                        .collect::<Vec<_>>();
+                    clauses.push(format!("PARTITION BY {}", names.join(", ")));
+                }
+                if !order_by.is_empty() {
+                    let names = order_by
+                        .iter()
+                        .map(|expr| match expr {
+                            Expression::Sort {
+                                expr, asc: false, ..
+                            } => format!("{} DESC", expr.column_name()),
+                            _ => expr.column_name(),
+                        })
+                        .collect::<Vec<_>>();
+                    clauses.push(format!("ORDER BY {}", names.join(", ")));
+                }
+                if let Some(frame) = frame {
+                    clauses.push(frame.to_string());
+                }
+                write!(f, "{})", clauses.join(" "))
+            }
+
             Expression::Sort { expr, .. } => write!(f, "{:?}", expr),
             Expression::Wildcard => write!(f, "*"),
             Expression::Cast { expr, data_type } => {
@@ -195,6 +195,13 @@ impl ExpressionChain {
 
                 self.actions.push(ExpressionAction::Function(function));
             }
+            Expression::WindowFunction { .. } => {
+                // Window function results are ready in the expression input
+                let name = expr.column_name();
+                let return_type = self.schema.field_with_name(&name)?.data_type().clone();
+                self.actions
+                    .push(ExpressionAction::Input(ActionInput { name, return_type }));
+            }
             Expression::Sort { expr, .. } => {
                 self.add_expr(expr)?;
             }
@@ -18,6 +18,7 @@ use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
+use crate::is_array_join_function;
 use crate::Expression;
 use crate::ExpressionVisitor;
 use crate::Recursion;
@@ -73,6 +74,57 @@ pub fn expand_aggregate_arg_exprs(exprs: &[Expression]) -> Vec<Expression> {
     res
 }
 
+/// Collect all deeply nested `Expression::WindowFunction`. They are returned in order of
+/// occurrence (depth first), with duplicates omitted.
+pub fn find_window_exprs(exprs: &[Expression]) -> Vec<Expression> {
+    find_exprs_in_exprs(exprs, &|nest_exprs| {
+        matches!(nest_exprs, Expression::WindowFunction { .. })
+    })
+}
+
+pub fn find_window_exprs_in_expr(expr: &Expression) -> Vec<Expression> {
+    find_exprs_in_expr(expr, &|nest_exprs| {
+        matches!(nest_exprs, Expression::WindowFunction { .. })
+    })
+}
+
+/// Collect all deeply nested `arrayJoin` functions. They are returned in order of
+/// occurrence (depth first), with duplicates omitted.
+pub fn find_array_join_exprs(exprs: &[Expression]) -> Vec<Expression> {
+    find_exprs_in_exprs(exprs, &is_array_join_expr)
+}
+
+pub fn find_array_join_exprs_in_expr(expr: &Expression) -> Vec<Expression> {
+    find_exprs_in_expr(expr, &is_array_join_expr)
+}
+
+fn is_array_join_expr(expr: &Expression) -> bool {
+    matches!(expr, Expression::ScalarFunction { op, .. } if is_array_join_function(op))
+}
+
+/// Collect all arguments, partition by and order by expressions from window functions
+/// [row_number() OVER (PARTITION BY a ORDER BY b), sum(c) OVER ()] ---> [a, b, c]
+pub fn expand_window_arg_exprs(exprs: &[Expression]) -> Vec<Expression> {
+    let mut res = vec![];
+    for expr in exprs {
+        if let Expression::WindowFunction {
+            args,
+            partition_by,
+            order_by,
+            ..
+        } = expr
+        {
+            let order_by = order_by.iter().map(sort_to_inner_expr).collect::<Vec<_>>();
+            for arg in args.iter().chain(partition_by).chain(&order_by) {
+                if !res.contains(arg) {
+                    res.push(arg.clone());
+                }
+            }
+        }
+    }
+    res
+}
+
 /// Collect all deeply nested `Expression::Column`'s. They are returned in order of
 /// appearance (depth first), with duplicates omitted.
 pub fn find_column_exprs(exprs: &[Expression]) -> Vec<Expression> {
@@ -303,6 +355,31 @@ where F: Fn(&Expression) -> Result<Option<Expression>> {
                     .collect::<Result<Vec<Expression>>>()?,
             }),
 
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+                frame,
+            } => {
+                let clone_exprs = |exprs: &[Expression]| {
+                    exprs
+                        .iter()
+                        .map(|e| clone_with_replacement(e, replacement_fn))
+                        .collect::<Result<Vec<Expression>>>()
+                };
+
+                Ok(Expression::WindowFunction {
+                    op: op.clone(),
+                    params: params.clone(),
+                    args: clone_exprs(args)?,
+                    partition_by: clone_exprs(partition_by)?,
+                    order_by: clone_exprs(order_by)?,
+                    frame: *frame,
+                })
+            }
+
             Expression::Sort {
                 expr: nested_expr,
                 asc,
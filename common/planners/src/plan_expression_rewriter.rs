@@ -106,6 +106,30 @@ impl Expression {
                     args: new_args,
                 }
             }
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+                frame,
+            } => {
+                let mut rewrite_exprs = |exprs: Vec<Expression>| {
+                    exprs
+                        .into_iter()
+                        .map(|expr| expr.rewrite(rewriter))
+                        .collect::<Result<Vec<_>>>()
+                };
+
+                Expression::WindowFunction {
+                    op,
+                    params,
+                    args: rewrite_exprs(args)?,
+                    partition_by: rewrite_exprs(partition_by)?,
+                    order_by: rewrite_exprs(order_by)?,
+                    frame,
+                }
+            }
             Expression::Cast { expr, data_type } => {
                 let expr = expr.rewrite(rewriter)?;
                 Expression::Cast {
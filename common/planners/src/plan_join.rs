@@ -0,0 +1,133 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+
+use crate::Expression;
+use crate::PlanNode;
+
+/// The kinds of join, a cross join is an inner join without keys and conditions.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Full,
+    /// Rows of the left side that have matches in the right side, outputs the left side only.
+    Semi,
+    /// Rows of the left side that have no matches in the right side, outputs the left side only.
+    Anti,
+    /// The ANTI join of `NOT IN`, which is never true if a key is NULL: no rows if any key of
+    /// the right side is NULL, and the rows with NULL keys are dropped unless the right side is
+    /// empty.
+    NullAwareAnti,
+}
+
+impl JoinType {
+    /// Whether the rows of the left side without matches are kept.
+    pub fn keeps_unmatched_left(&self) -> bool {
+        matches!(self, JoinType::Left | JoinType::Full)
+    }
+
+    /// Whether the rows of the right side without matches are kept.
+    pub fn keeps_unmatched_right(&self) -> bool {
+        matches!(self, JoinType::Right | JoinType::Full)
+    }
+}
+
+impl fmt::Display for JoinType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            JoinType::Inner => write!(f, "INNER"),
+            JoinType::Left => write!(f, "LEFT"),
+            JoinType::Right => write!(f, "RIGHT"),
+            JoinType::Full => write!(f, "FULL"),
+            JoinType::Semi => write!(f, "SEMI"),
+            JoinType::Anti => write!(f, "ANTI"),
+            JoinType::NullAwareAnti => write!(f, "NULL AWARE ANTI"),
+        }
+    }
+}
+
+/// Joins two inputs by the equality of the keys, and the other (residual) conditions.
+/// The left side is the probe side, the right side is the build side, which is
+/// collected into a hash table.
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub struct JoinPlan {
+    pub join_type: JoinType,
+    /// The keys of the left side, evaluated against the left schema.
+    pub left_keys: Vec<Expression>,
+    /// The keys of the right side, evaluated against the right schema.
+    pub right_keys: Vec<Expression>,
+    /// The conditions other than the keys, evaluated against the joined rows.
+    pub other_conditions: Option<Expression>,
+    pub left: Arc<PlanNode>,
+    pub right: Arc<PlanNode>,
+    pub schema: DataSchemaRef,
+}
+
+impl JoinPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        self.schema.clone()
+    }
+
+    pub fn get_inputs(&self) -> Vec<Arc<PlanNode>> {
+        vec![self.left.clone(), self.right.clone()]
+    }
+
+    pub fn set_inputs(&mut self, inputs: Vec<&PlanNode>) {
+        assert_eq!(inputs.len(), 2);
+        self.left = Arc::new(inputs[0].clone());
+        self.right = Arc::new(inputs[1].clone());
+    }
+
+    /// The fields of both sides, which the other conditions are evaluated against.
+    pub fn joined_schema(left: &DataSchemaRef, right: &DataSchemaRef) -> DataSchemaRef {
+        let mut fields = left.fields().clone();
+        fields.extend(right.fields().iter().cloned());
+        DataSchemaRefExt::create(fields)
+    }
+
+    /// The output schema, the fields of the side that may be filled by NULLs are nullable.
+    pub fn output_schema(
+        join_type: JoinType,
+        left: &DataSchemaRef,
+        right: &DataSchemaRef,
+    ) -> DataSchemaRef {
+        let nullable_fields = |schema: &DataSchemaRef, nullable: bool| {
+            schema
+                .fields()
+                .iter()
+                .map(|f| {
+                    DataField::new(f.name(), f.data_type().clone(), f.is_nullable() || nullable)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        match join_type {
+            JoinType::Semi | JoinType::Anti | JoinType::NullAwareAnti => left.clone(),
+            _ => {
+                let mut fields = nullable_fields(left, join_type.keeps_unmatched_right());
+                fields.extend(nullable_fields(right, join_type.keeps_unmatched_left()));
+                DataSchemaRefExt::create(fields)
+            }
+        }
+    }
+}
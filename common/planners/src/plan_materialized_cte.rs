@@ -0,0 +1,39 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+
+use crate::PlanNode;
+
+/// Reads the result of a common table expression which is referenced more than once.
+///
+/// The references of the same CTE have the same name in a query, the input is executed by
+/// the first one of them and the result is shared by the others.
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub struct MaterializedCtePlan {
+    pub name: String,
+    pub input: Arc<PlanNode>,
+}
+
+impl MaterializedCtePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        self.input.schema()
+    }
+
+    pub fn set_input(&mut self, node: &PlanNode) {
+        self.input = Arc::new(node.clone());
+    }
+}
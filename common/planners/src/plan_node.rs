@@ -21,11 +21,14 @@ use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
 use crate::AlterUserPlan;
+use crate::ArrayJoinPlan;
 use crate::CopyPlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::CreateUserPlan;
+use crate::DeletePlan;
 use crate::DescribeTablePlan;
+use crate::DistinctPlan;
 use crate::DropDatabasePlan;
 use crate::DropTablePlan;
 use crate::DropUserPlan;
@@ -36,20 +39,28 @@ use crate::FilterPlan;
 use crate::GrantPrivilegePlan;
 use crate::HavingPlan;
 use crate::InsertIntoPlan;
+use crate::JoinPlan;
 use crate::KillPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
+use crate::MaterializedCtePlan;
+use crate::OptimizeTablePlan;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
 use crate::RemotePlan;
 use crate::SelectPlan;
+use crate::SetOperationPlan;
 use crate::SettingPlan;
 use crate::ShowCreateTablePlan;
 use crate::SinkPlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
+use crate::UnionPlan;
+use crate::UpdatePlan;
 use crate::UseDatabasePlan;
+use crate::VacuumTablePlan;
+use crate::WindowPlan;
 
 #[allow(clippy::large_enum_variant)]
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
@@ -64,6 +75,13 @@ pub enum PlanNode {
     AggregatorFinal(AggregatorFinalPlan),
     Filter(FilterPlan),
     Having(HavingPlan),
+    Join(JoinPlan),
+    ArrayJoin(ArrayJoinPlan),
+    Window(WindowPlan),
+    Union(UnionPlan),
+    Distinct(DistinctPlan),
+    SetOperation(SetOperationPlan),
+    MaterializedCte(MaterializedCtePlan),
     Sort(SortPlan),
     Limit(LimitPlan),
     LimitBy(LimitByPlan),
@@ -77,9 +95,13 @@ pub enum PlanNode {
     DescribeTable(DescribeTablePlan),
     DropTable(DropTablePlan),
     TruncateTable(TruncateTablePlan),
+    OptimizeTable(OptimizeTablePlan),
+    VacuumTable(VacuumTablePlan),
     UseDatabase(UseDatabasePlan),
     SetVariable(SettingPlan),
     InsertInto(InsertIntoPlan),
+    Delete(DeletePlan),
+    Update(UpdatePlan),
     Copy(CopyPlan),
     ShowCreateTable(ShowCreateTablePlan),
     SubQueryExpression(SubQueriesSetPlan),
@@ -104,6 +126,13 @@ impl PlanNode {
             PlanNode::AggregatorFinal(v) => v.schema(),
             PlanNode::Filter(v) => v.schema(),
             PlanNode::Having(v) => v.schema(),
+            PlanNode::Join(v) => v.schema(),
+            PlanNode::ArrayJoin(v) => v.schema(),
+            PlanNode::Window(v) => v.schema(),
+            PlanNode::Union(v) => v.schema(),
+            PlanNode::Distinct(v) => v.schema(),
+            PlanNode::SetOperation(v) => v.schema(),
+            PlanNode::MaterializedCte(v) => v.schema(),
             PlanNode::Limit(v) => v.schema(),
             PlanNode::LimitBy(v) => v.schema(),
             PlanNode::ReadSource(v) => v.schema(),
@@ -115,10 +144,14 @@ impl PlanNode {
             PlanNode::DropTable(v) => v.schema(),
             PlanNode::DescribeTable(v) => v.schema(),
             PlanNode::TruncateTable(v) => v.schema(),
+            PlanNode::OptimizeTable(v) => v.schema(),
+            PlanNode::VacuumTable(v) => v.schema(),
             PlanNode::SetVariable(v) => v.schema(),
             PlanNode::Sort(v) => v.schema(),
             PlanNode::UseDatabase(v) => v.schema(),
             PlanNode::InsertInto(v) => v.schema(),
+            PlanNode::Delete(v) => v.schema(),
+            PlanNode::Update(v) => v.schema(),
             PlanNode::ShowCreateTable(v) => v.schema(),
             PlanNode::SubQueryExpression(v) => v.schema(),
             PlanNode::Kill(v) => v.schema(),
@@ -143,6 +176,13 @@ impl PlanNode {
             PlanNode::AggregatorFinal(_) => "AggregatorFinalPlan",
             PlanNode::Filter(_) => "FilterPlan",
             PlanNode::Having(_) => "HavingPlan",
+            PlanNode::Join(_) => "JoinPlan",
+            PlanNode::ArrayJoin(_) => "ArrayJoinPlan",
+            PlanNode::Window(_) => "WindowPlan",
+            PlanNode::Union(_) => "UnionPlan",
+            PlanNode::Distinct(_) => "DistinctPlan",
+            PlanNode::SetOperation(_) => "SetOperationPlan",
+            PlanNode::MaterializedCte(_) => "MaterializedCtePlan",
             PlanNode::Limit(_) => "LimitPlan",
             PlanNode::LimitBy(_) => "LimitByPlan",
             PlanNode::ReadSource(_) => "ReadSourcePlan",
@@ -154,10 +194,14 @@ impl PlanNode {
             PlanNode::DescribeTable(_) => "DescribeTablePlan",
             PlanNode::DropTable(_) => "DropTablePlan",
             PlanNode::TruncateTable(_) => "TruncateTablePlan",
+            PlanNode::OptimizeTable(_) => "OptimizeTablePlan",
+            PlanNode::VacuumTable(_) => "VacuumTablePlan",
             PlanNode::SetVariable(_) => "SetVariablePlan",
             PlanNode::Sort(_) => "SortPlan",
             PlanNode::UseDatabase(_) => "UseDatabasePlan",
             PlanNode::InsertInto(_) => "InsertIntoPlan",
+            PlanNode::Delete(_) => "DeletePlan",
+            PlanNode::Update(_) => "UpdatePlan",
             PlanNode::ShowCreateTable(_) => "ShowCreateTablePlan",
             PlanNode::SubQueryExpression(_) => "CreateSubQueriesSets",
             PlanNode::Kill(_) => "KillQuery",
@@ -180,6 +224,13 @@ impl PlanNode {
             PlanNode::AggregatorFinal(v) => vec![v.input.clone()],
             PlanNode::Filter(v) => vec![v.input.clone()],
             PlanNode::Having(v) => vec![v.input.clone()],
+            PlanNode::Join(v) => v.get_inputs(),
+            PlanNode::ArrayJoin(v) => vec![v.input.clone()],
+            PlanNode::Window(v) => vec![v.input.clone()],
+            PlanNode::Union(v) => v.get_inputs(),
+            PlanNode::Distinct(v) => vec![v.input.clone()],
+            PlanNode::SetOperation(v) => v.get_inputs(),
+            PlanNode::MaterializedCte(v) => vec![v.input.clone()],
             PlanNode::Limit(v) => vec![v.input.clone()],
             PlanNode::Explain(v) => vec![v.input.clone()],
             PlanNode::Select(v) => vec![v.input.clone()],
@@ -26,11 +26,14 @@ use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
 use crate::AlterUserPlan;
+use crate::ArrayJoinPlan;
 use crate::CopyPlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::CreateUserPlan;
+use crate::DeletePlan;
 use crate::DescribeTablePlan;
+use crate::DistinctPlan;
 use crate::DropDatabasePlan;
 use crate::DropTablePlan;
 use crate::DropUserPlan;
@@ -43,22 +46,30 @@ use crate::FilterPlan;
 use crate::GrantPrivilegePlan;
 use crate::HavingPlan;
 use crate::InsertIntoPlan;
+use crate::JoinPlan;
 use crate::KillPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
+use crate::MaterializedCtePlan;
+use crate::OptimizeTablePlan;
 use crate::PlanBuilder;
 use crate::PlanNode;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
 use crate::RemotePlan;
 use crate::SelectPlan;
+use crate::SetOperationPlan;
 use crate::SettingPlan;
 use crate::ShowCreateTablePlan;
 use crate::SinkPlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
+use crate::UnionPlan;
+use crate::UpdatePlan;
 use crate::UseDatabasePlan;
+use crate::VacuumTablePlan;
+use crate::WindowPlan;
 
 /// `PlanRewriter` is a visitor that can help to rewrite `PlanNode`
 /// By default, a `PlanRewriter` will traverse the plan tree in pre-order and return rewritten plan tree.
@@ -101,15 +112,26 @@ pub trait PlanRewriter {
             PlanNode::Broadcast(plan) => self.rewrite_broadcast(plan),
             PlanNode::Remote(plan) => self.rewrite_remote(plan),
             PlanNode::Having(plan) => self.rewrite_having(plan),
+            PlanNode::Join(plan) => self.rewrite_join(plan),
+            PlanNode::ArrayJoin(plan) => self.rewrite_array_join(plan),
+            PlanNode::Window(plan) => self.rewrite_window(plan),
+            PlanNode::Union(plan) => self.rewrite_union(plan),
+            PlanNode::Distinct(plan) => self.rewrite_distinct(plan),
+            PlanNode::SetOperation(plan) => self.rewrite_set_operation(plan),
+            PlanNode::MaterializedCte(plan) => self.rewrite_materialized_cte(plan),
             PlanNode::Expression(plan) => self.rewrite_expression(plan),
             PlanNode::DescribeTable(plan) => self.rewrite_describe_table(plan),
             PlanNode::DropTable(plan) => self.rewrite_drop_table(plan),
             PlanNode::DropDatabase(plan) => self.rewrite_drop_database(plan),
             PlanNode::InsertInto(plan) => self.rewrite_insert_into(plan),
+            PlanNode::Delete(plan) => self.rewrite_delete(plan),
+            PlanNode::Update(plan) => self.rewrite_update(plan),
             PlanNode::Copy(plan) => self.rewrite_copy(plan),
             PlanNode::ShowCreateTable(plan) => self.rewrite_show_create_table(plan),
             PlanNode::SubQueryExpression(plan) => self.rewrite_sub_queries_sets(plan),
             PlanNode::TruncateTable(plan) => self.rewrite_truncate_table(plan),
+            PlanNode::OptimizeTable(plan) => self.rewrite_optimize_table(plan),
+            PlanNode::VacuumTable(plan) => self.rewrite_vacuum_table(plan),
             PlanNode::Kill(plan) => self.rewrite_kill(plan),
             PlanNode::CreateUser(plan) => self.create_user(plan),
             PlanNode::AlterUser(plan) => self.alter_user(plan),
@@ -154,6 +176,21 @@ pub trait PlanRewriter {
                 params: params.clone(),
                 args: self.rewrite_exprs(schema, args)?,
             }),
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+                frame,
+            } => Ok(Expression::WindowFunction {
+                op: op.clone(),
+                params: params.clone(),
+                args: self.rewrite_exprs(schema, args)?,
+                partition_by: self.rewrite_exprs(schema, partition_by)?,
+                order_by: self.rewrite_exprs(schema, order_by)?,
+                frame: *frame,
+            }),
             Expression::Sort {
                 expr,
                 asc,
@@ -269,6 +306,79 @@ pub trait PlanRewriter {
         PlanBuilder::from(&new_input).having(new_predicate)?.build()
     }
 
+    fn rewrite_join(&mut self, plan: &JoinPlan) -> Result<PlanNode> {
+        let new_left = self.rewrite_plan_node(plan.left.as_ref())?;
+        let new_right = self.rewrite_plan_node(plan.right.as_ref())?;
+        let new_left_keys = self.rewrite_exprs(&new_left.schema(), &plan.left_keys)?;
+        let new_right_keys = self.rewrite_exprs(&new_right.schema(), &plan.right_keys)?;
+        let new_other_conditions = match &plan.other_conditions {
+            None => None,
+            Some(conditions) => {
+                let joined_schema =
+                    JoinPlan::joined_schema(&new_left.schema(), &new_right.schema());
+                Some(self.rewrite_expr(&joined_schema, conditions)?)
+            }
+        };
+
+        PlanBuilder::from(&new_left)
+            .join(
+                &new_right,
+                plan.join_type,
+                &new_left_keys,
+                &new_right_keys,
+                new_other_conditions,
+            )?
+            .build()
+    }
+
+    fn rewrite_array_join(&mut self, plan: &ArrayJoinPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        let new_array_join_exprs =
+            self.rewrite_exprs(&new_input.schema(), &plan.array_join_exprs)?;
+        PlanBuilder::from(&new_input)
+            .array_join(&new_array_join_exprs)?
+            .build()
+    }
+
+    fn rewrite_window(&mut self, plan: &WindowPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        let new_window_exprs = self.rewrite_exprs(&new_input.schema(), &plan.window_exprs)?;
+        PlanBuilder::from(&new_input)
+            .window(&new_window_exprs)?
+            .build()
+    }
+
+    fn rewrite_union(&mut self, plan: &UnionPlan) -> Result<PlanNode> {
+        let mut new_inputs = Vec::with_capacity(plan.inputs.len());
+        for input in &plan.inputs {
+            new_inputs.push(self.rewrite_plan_node(input.as_ref())?);
+        }
+
+        PlanBuilder::from(&new_inputs[0])
+            .union(&new_inputs[1..])?
+            .build()
+    }
+
+    fn rewrite_distinct(&mut self, plan: &DistinctPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        PlanBuilder::from(&new_input).distinct()?.build()
+    }
+
+    fn rewrite_set_operation(&mut self, plan: &SetOperationPlan) -> Result<PlanNode> {
+        let new_left = self.rewrite_plan_node(plan.left.as_ref())?;
+        let new_right = self.rewrite_plan_node(plan.right.as_ref())?;
+        PlanBuilder::from(&new_left)
+            .set_operation(plan.op, plan.all, &new_right)?
+            .build()
+    }
+
+    fn rewrite_materialized_cte(&mut self, plan: &MaterializedCtePlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        PlanBuilder::from(&new_input)
+            .materialized_cte(&plan.name)?
+            .build()
+    }
+
     fn rewrite_sort(&mut self, plan: &SortPlan) -> Result<PlanNode> {
         let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
         let new_order_by = self.rewrite_exprs(&new_input.schema(), &plan.order_by)?;
@@ -338,6 +448,14 @@ pub trait PlanRewriter {
         Ok(PlanNode::InsertInto(plan.clone()))
     }
 
+    fn rewrite_delete(&mut self, plan: &DeletePlan) -> Result<PlanNode> {
+        Ok(PlanNode::Delete(plan.clone()))
+    }
+
+    fn rewrite_update(&mut self, plan: &UpdatePlan) -> Result<PlanNode> {
+        Ok(PlanNode::Update(plan.clone()))
+    }
+
     fn rewrite_copy(&mut self, plan: &CopyPlan) -> Result<PlanNode> {
         Ok(PlanNode::Copy(plan.clone()))
     }
@@ -350,6 +468,14 @@ pub trait PlanRewriter {
         Ok(PlanNode::TruncateTable(plan.clone()))
     }
 
+    fn rewrite_optimize_table(&mut self, plan: &OptimizeTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::OptimizeTable(plan.clone()))
+    }
+
+    fn rewrite_vacuum_table(&mut self, plan: &VacuumTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::VacuumTable(plan.clone()))
+    }
+
     fn rewrite_kill(&mut self, plan: &KillPlan) -> Result<PlanNode> {
         Ok(PlanNode::Kill(plan.clone()))
     }
@@ -517,6 +643,31 @@ impl RewriteHelper {
                 }
             }
 
+            Expression::WindowFunction {
+                op,
+                params,
+                args,
+                partition_by,
+                order_by,
+                frame,
+            } => {
+                let rewrite_exprs = |exprs: &[Expression], data: &mut QueryAliasData| {
+                    exprs
+                        .iter()
+                        .map(|v| RewriteHelper::expr_rewrite_alias(v, data))
+                        .collect::<Result<Vec<_>>>()
+                };
+
+                Ok(Expression::WindowFunction {
+                    op: op.clone(),
+                    params: params.clone(),
+                    args: rewrite_exprs(args, data)?,
+                    partition_by: rewrite_exprs(partition_by, data)?,
+                    order_by: rewrite_exprs(order_by, data)?,
+                    frame: *frame,
+                })
+            }
+
             Expression::Alias(alias, plan) => {
                 if data.inside_aliases.contains(alias) {
                     return Result::Err(ErrorCode::SyntaxException(format!(
@@ -608,6 +759,17 @@ impl RewriteHelper {
             }
             Expression::ScalarFunction { args, .. } => args.clone(),
             Expression::AggregateFunction { args, .. } => args.clone(),
+            Expression::WindowFunction {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                let mut children = args.clone();
+                children.extend(partition_by.iter().cloned());
+                children.extend(order_by.iter().cloned());
+                children
+            }
             Expression::Wildcard => vec![],
             Expression::Sort { expr, .. } => vec![expr.as_ref().clone()],
             Expression::Cast { expr, .. } => vec![expr.as_ref().clone()],
@@ -646,6 +808,19 @@ impl RewriteHelper {
                 }
                 v
             }
+            Expression::WindowFunction {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                let mut v = vec![];
+                for arg in args.iter().chain(partition_by).chain(order_by) {
+                    let mut col = Self::expression_plan_columns(arg)?;
+                    v.append(&mut col);
+                }
+                v
+            }
             Expression::Wildcard => vec![],
             Expression::Sort { expr, .. } => Self::expression_plan_columns(expr)?,
             Expression::Cast { expr, .. } => Self::expression_plan_columns(expr)?,
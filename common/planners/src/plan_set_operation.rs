@@ -0,0 +1,106 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use common_datavalues::merge_types;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::PlanNode;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum SetOperator {
+    Union,
+    Intersect,
+    Except,
+}
+
+impl fmt::Display for SetOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SetOperator::Union => write!(f, "UNION"),
+            SetOperator::Intersect => write!(f, "INTERSECT"),
+            SetOperator::Except => write!(f, "EXCEPT"),
+        }
+    }
+}
+
+/// The rows of the left input that are (INTERSECT) or are not (EXCEPT) in the right input,
+/// the right input is collected into a hash set. UNION is planned as `UnionPlan`.
+///
+/// With ALL, a row that appears m times in the left and n times in the right appears
+/// min(m, n) times for INTERSECT and max(m - n, 0) times for EXCEPT. Otherwise the rows
+/// are distinct. NULLs are equal to each other.
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub struct SetOperationPlan {
+    pub op: SetOperator,
+    pub all: bool,
+    pub left: Arc<PlanNode>,
+    pub right: Arc<PlanNode>,
+    pub schema: DataSchemaRef,
+}
+
+impl SetOperationPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        self.schema.clone()
+    }
+
+    pub fn get_inputs(&self) -> Vec<Arc<PlanNode>> {
+        vec![self.left.clone(), self.right.clone()]
+    }
+
+    pub fn set_inputs(&mut self, inputs: Vec<&PlanNode>) {
+        assert_eq!(inputs.len(), 2);
+        self.left = Arc::new(inputs[0].clone());
+        self.right = Arc::new(inputs[1].clone());
+    }
+
+    /// The columns of the inputs are matched by position and named by the first input,
+    /// the type of a column is the common type of the inputs.
+    pub fn output_schema(op: SetOperator, inputs: &[DataSchemaRef]) -> Result<DataSchemaRef> {
+        let first = match inputs.first() {
+            Some(first) => first,
+            None => return Err(ErrorCode::LogicalError(format!("{} without inputs", op))),
+        };
+
+        let mut fields = first.fields().clone();
+        for input in &inputs[1..] {
+            if input.fields().len() != fields.len() {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Each {} query must have the same number of columns, but got {} and {}",
+                    op,
+                    fields.len(),
+                    input.fields().len()
+                )));
+            }
+
+            for (field, input_field) in fields.iter_mut().zip(input.fields().iter()) {
+                let data_type = merge_types(field.data_type(), input_field.data_type())?;
+                let nullable = field.is_nullable()
+                    || input_field.is_nullable()
+                    || input_field.data_type() == &DataType::Null;
+                *field = DataField::new(field.name(), data_type, nullable);
+            }
+        }
+
+        Ok(DataSchemaRefExt::create(fields))
+    }
+}
@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Debug, Default)]
+use std::collections::HashMap;
+
+use common_datavalues::DataValue;
+
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Clone, Debug, Default)]
 pub struct Statistics {
     /// Total rows of the query read.
     pub read_rows: usize,
@@ -20,6 +24,17 @@ pub struct Statistics {
     pub read_bytes: usize,
     /// Is the statistics exact.
     pub is_exact: bool,
+    /// Statistics of the columns read by name, if the source keeps them.
+    pub column_statistics: HashMap<String, ColumnStatistics>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Clone, Debug)]
+pub struct ColumnStatistics {
+    pub min: DataValue,
+    pub max: DataValue,
+    pub null_count: u64,
+    /// The number of distinct values, None if it is unknown.
+    pub distinct_count: Option<u64>,
 }
 
 impl Statistics {
@@ -28,6 +43,7 @@ impl Statistics {
             read_rows,
             read_bytes,
             is_exact: false,
+            column_statistics: HashMap::new(),
         }
     }
 
@@ -36,9 +52,15 @@ impl Statistics {
             read_rows,
             read_bytes,
             is_exact: true,
+            column_statistics: HashMap::new(),
         }
     }
 
+    pub fn with_column_statistics(mut self, name: &str, statistics: ColumnStatistics) -> Self {
+        self.column_statistics.insert(name.to_string(), statistics);
+        self
+    }
+
     pub fn clear(&mut self) {
         *self = Self::default();
     }
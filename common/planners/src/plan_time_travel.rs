@@ -0,0 +1,24 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// A point in the history of a table, which a time travel query reads at.
+///
+/// e.g. `SELECT * FROM t AT (SNAPSHOT => '<snapshot id>')`
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub enum TimeTravelPoint {
+    /// Id of the snapshot to read
+    Snapshot(String),
+    /// Seconds since the unix epoch, the latest snapshot committed at or before it is read
+    Timestamp(u32),
+}
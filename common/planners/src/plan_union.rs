@@ -0,0 +1,45 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+
+use crate::PlanNode;
+
+/// Concatenates the rows of all the inputs (UNION ALL). The inputs have the same columns as
+/// the union, which is ensured by `PlanBuilder::union`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub struct UnionPlan {
+    pub inputs: Vec<Arc<PlanNode>>,
+    pub schema: DataSchemaRef,
+}
+
+impl UnionPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        self.schema.clone()
+    }
+
+    pub fn get_inputs(&self) -> Vec<Arc<PlanNode>> {
+        self.inputs.clone()
+    }
+
+    pub fn set_inputs(&mut self, inputs: Vec<&PlanNode>) {
+        assert_eq!(inputs.len(), self.inputs.len());
+        self.inputs = inputs
+            .into_iter()
+            .map(|input| Arc::new(input.clone()))
+            .collect();
+    }
+}
@@ -19,11 +19,14 @@ use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
 use crate::AlterUserPlan;
+use crate::ArrayJoinPlan;
 use crate::CopyPlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::CreateUserPlan;
+use crate::DeletePlan;
 use crate::DescribeTablePlan;
+use crate::DistinctPlan;
 use crate::DropDatabasePlan;
 use crate::DropTablePlan;
 use crate::DropUserPlan;
@@ -35,21 +38,29 @@ use crate::FilterPlan;
 use crate::GrantPrivilegePlan;
 use crate::HavingPlan;
 use crate::InsertIntoPlan;
+use crate::JoinPlan;
 use crate::KillPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
+use crate::MaterializedCtePlan;
+use crate::OptimizeTablePlan;
 use crate::PlanNode;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
 use crate::RemotePlan;
 use crate::SelectPlan;
+use crate::SetOperationPlan;
 use crate::SettingPlan;
 use crate::ShowCreateTablePlan;
 use crate::SinkPlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
+use crate::UnionPlan;
+use crate::UpdatePlan;
 use crate::UseDatabasePlan;
+use crate::VacuumTablePlan;
+use crate::WindowPlan;
 
 /// `PlanVisitor` implements visitor pattern(reference [syn](https://docs.rs/syn/1.0.72/syn/visit/trait.Visit.html)) for `PlanNode`.
 ///
@@ -112,14 +123,25 @@ pub trait PlanVisitor {
             PlanNode::DropTable(plan) => self.visit_drop_table(plan),
             PlanNode::DescribeTable(plan) => self.visit_describe_table(plan),
             PlanNode::TruncateTable(plan) => self.visit_truncate_table(plan),
+            PlanNode::OptimizeTable(plan) => self.visit_optimize_table(plan),
+            PlanNode::VacuumTable(plan) => self.visit_vacuum_table(plan),
             PlanNode::UseDatabase(plan) => self.visit_use_database(plan),
             PlanNode::SetVariable(plan) => self.visit_set_variable(plan),
             PlanNode::Stage(plan) => self.visit_stage(plan),
             PlanNode::Broadcast(plan) => self.visit_broadcast(plan),
             PlanNode::Remote(plan) => self.visit_remote(plan),
             PlanNode::Having(plan) => self.visit_having(plan),
+            PlanNode::Join(plan) => self.visit_join(plan),
+            PlanNode::ArrayJoin(plan) => self.visit_array_join(plan),
+            PlanNode::Window(plan) => self.visit_window(plan),
+            PlanNode::Union(plan) => self.visit_union(plan),
+            PlanNode::Distinct(plan) => self.visit_distinct(plan),
+            PlanNode::SetOperation(plan) => self.visit_set_operation(plan),
+            PlanNode::MaterializedCte(plan) => self.visit_materialized_cte(plan),
             PlanNode::Expression(plan) => self.visit_expression(plan),
             PlanNode::InsertInto(plan) => self.visit_insert_into(plan),
+            PlanNode::Delete(plan) => self.visit_delete(plan),
+            PlanNode::Update(plan) => self.visit_update(plan),
             PlanNode::Copy(plan) => self.visit_copy(plan),
             PlanNode::ShowCreateTable(plan) => self.visit_show_create_table(plan),
             PlanNode::SubQueryExpression(plan) => self.visit_sub_queries_sets(plan),
@@ -211,6 +233,48 @@ pub trait PlanVisitor {
         self.visit_expr(&plan.predicate)
     }
 
+    fn visit_join(&mut self, plan: &JoinPlan) -> Result<()> {
+        self.visit_plan_node(plan.left.as_ref())?;
+        self.visit_plan_node(plan.right.as_ref())?;
+        self.visit_exprs(&plan.left_keys)?;
+        self.visit_exprs(&plan.right_keys)?;
+        match &plan.other_conditions {
+            None => Ok(()),
+            Some(conditions) => self.visit_expr(conditions),
+        }
+    }
+
+    fn visit_array_join(&mut self, plan: &ArrayJoinPlan) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref())?;
+        self.visit_exprs(&plan.array_join_exprs)
+    }
+
+    fn visit_window(&mut self, plan: &WindowPlan) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref())?;
+        self.visit_exprs(&plan.window_exprs)
+    }
+
+    fn visit_union(&mut self, plan: &UnionPlan) -> Result<()> {
+        for input in &plan.inputs {
+            self.visit_plan_node(input.as_ref())?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_distinct(&mut self, plan: &DistinctPlan) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref())
+    }
+
+    fn visit_set_operation(&mut self, plan: &SetOperationPlan) -> Result<()> {
+        self.visit_plan_node(plan.left.as_ref())?;
+        self.visit_plan_node(plan.right.as_ref())
+    }
+
+    fn visit_materialized_cte(&mut self, plan: &MaterializedCtePlan) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref())
+    }
+
     fn visit_sort(&mut self, plan: &SortPlan) -> Result<()> {
         self.visit_plan_node(plan.input.as_ref())?;
         self.visit_exprs(&plan.order_by)
@@ -284,6 +348,14 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_delete(&mut self, _: &DeletePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_update(&mut self, _: &UpdatePlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_copy(&mut self, _: &CopyPlan) -> Result<()> {
         Ok(())
     }
@@ -296,6 +368,14 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_optimize_table(&mut self, _: &OptimizeTablePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_vacuum_table(&mut self, _: &VacuumTablePlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_kill_query(&mut self, _: &KillPlan) -> Result<()> {
         Ok(())
     }
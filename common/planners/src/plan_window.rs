@@ -0,0 +1,144 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+
+use crate::Expression;
+use crate::PlanNode;
+
+/// The functions which can only be evaluated over a window, the others of window
+/// functions are the aggregate functions.
+const WINDOW_ONLY_FUNCTIONS: [&str; 7] = [
+    "row_number",
+    "rank",
+    "dense_rank",
+    "lag",
+    "lead",
+    "first_value",
+    "last_value",
+];
+
+pub fn is_window_only_function(name: &str) -> bool {
+    WINDOW_ONLY_FUNCTIONS.contains(&name.to_lowercase().as_str())
+}
+
+/// ROWS counts the rows from the current row, RANGE counts the values of the ORDER BY
+/// expression from the value of the current row, the peers of the current row
+/// (rows with the same ORDER BY values) are always in the same RANGE frame.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+}
+
+/// `None` offset is UNBOUNDED.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum WindowFrameBound {
+    Preceding(Option<u64>),
+    CurrentRow,
+    Following(Option<u64>),
+}
+
+impl WindowFrameBound {
+    pub fn has_offset(&self) -> bool {
+        matches!(
+            self,
+            WindowFrameBound::Preceding(Some(_)) | WindowFrameBound::Following(Some(_))
+        )
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct WindowFrame {
+    pub units: WindowFrameUnits,
+    pub start_bound: WindowFrameBound,
+    pub end_bound: WindowFrameBound,
+}
+
+impl WindowFrame {
+    /// The frame if it's not specified: from the start of the partition to the last peer of
+    /// the current row, which is the whole partition if there is no ORDER BY.
+    pub fn default_frame() -> WindowFrame {
+        WindowFrame {
+            units: WindowFrameUnits::Range,
+            start_bound: WindowFrameBound::Preceding(None),
+            end_bound: WindowFrameBound::CurrentRow,
+        }
+    }
+}
+
+impl fmt::Display for WindowFrameBound {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            WindowFrameBound::Preceding(None) => write!(f, "UNBOUNDED PRECEDING"),
+            WindowFrameBound::Preceding(Some(n)) => write!(f, "{} PRECEDING", n),
+            WindowFrameBound::CurrentRow => write!(f, "CURRENT ROW"),
+            WindowFrameBound::Following(None) => write!(f, "UNBOUNDED FOLLOWING"),
+            WindowFrameBound::Following(Some(n)) => write!(f, "{} FOLLOWING", n),
+        }
+    }
+}
+
+impl fmt::Display for WindowFrame {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let units = match self.units {
+            WindowFrameUnits::Rows => "ROWS",
+            WindowFrameUnits::Range => "RANGE",
+        };
+
+        write!(
+            f,
+            "{} BETWEEN {} AND {}",
+            units, self.start_bound, self.end_bound
+        )
+    }
+}
+
+/// Evaluates the window functions with the same PARTITION BY and ORDER BY, the results are
+/// appended to the input columns.
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub struct WindowPlan {
+    /// The window functions, all of them are `Expression::WindowFunction`.
+    pub window_exprs: Vec<Expression>,
+    pub schema: DataSchemaRef,
+    pub input: Arc<PlanNode>,
+}
+
+impl WindowPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        self.schema.clone()
+    }
+
+    pub fn set_input(&mut self, node: &PlanNode) {
+        self.input = Arc::new(node.clone());
+    }
+
+    pub fn partition_by(&self) -> &[Expression] {
+        match self.window_exprs.first() {
+            Some(Expression::WindowFunction { partition_by, .. }) => partition_by,
+            _ => &[],
+        }
+    }
+
+    pub fn order_by(&self) -> &[Expression] {
+        match self.window_exprs.first() {
+            Some(Expression::WindowFunction { order_by, .. }) => order_by,
+            _ => &[],
+        }
+    }
+}
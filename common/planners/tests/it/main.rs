@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod plan_aggregator;
+mod plan_array_join;
 mod plan_builder;
 mod plan_describe_table;
 mod plan_display;
@@ -21,8 +22,11 @@ mod plan_expression;
 mod plan_extras;
 mod plan_filter;
 mod plan_having;
+mod plan_join;
 mod plan_limit;
 mod plan_projection;
 mod plan_rewriter;
 mod plan_select;
+mod plan_set_operation;
+mod plan_window;
 mod test;
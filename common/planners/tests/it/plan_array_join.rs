@@ -0,0 +1,32 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::test::Test;
+
+#[test]
+fn test_array_join_plan_with_non_column_argument() -> Result<()> {
+    let source = Test::create().generate_source_plan_for_test(10000)?;
+    let result = PlanBuilder::from(&source).array_join(&[Expression::ScalarFunction {
+        op: String::from("arrayJoin"),
+        args: vec![add(col("number"), lit(1i64))],
+    }]);
+
+    let expect = "Code: 15, displayText = Array join plan expects arrayJoin of columns, but got arrayJoin((number + 1)).";
+    assert_eq!(expect, format!("{}", result.err().unwrap()));
+    Ok(())
+}
@@ -0,0 +1,102 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::test::Test;
+
+#[test]
+fn test_join_plan() -> Result<()> {
+    let left = Test::create().generate_source_plan_for_test(10000)?;
+    let right = PlanBuilder::from(&Test::create().generate_source_plan_for_test(10000)?)
+        .project(&[col("number").alias("r")])?
+        .build()?;
+
+    let plan = PlanBuilder::from(&left)
+        .join(
+            &right,
+            JoinType::Left,
+            &[col("number")],
+            &[col("r")],
+            Some(col("number").gt(lit(1i64))),
+        )?
+        .build()?;
+
+    let expect = "\
+    HashJoin: LEFT JOIN, keys: [number = r], other conditions: (number > 1)\
+    \n  ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]\
+    \n  Projection: number as r:UInt64\
+    \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]";
+    assert_eq!(expect, format!("{:?}", plan));
+
+    // The columns of the outer side are nullable.
+    let schema = plan.schema();
+    assert!(!schema.field_with_name("number")?.is_nullable());
+    assert!(schema.field_with_name("r")?.is_nullable());
+    Ok(())
+}
+
+#[test]
+fn test_join_plan_with_coerced_keys() -> Result<()> {
+    let left = Test::create().generate_source_plan_for_test(10000)?;
+    let right = PlanBuilder::from(&Test::create().generate_source_plan_for_test(10000)?)
+        .project(&[Expression::Cast {
+            expr: Box::new(col("number")),
+            data_type: DataType::Int32,
+        }
+        .alias("r")])?
+        .build()?;
+
+    let plan = PlanBuilder::from(&left)
+        .join(&right, JoinType::Semi, &[col("number")], &[col("r")], None)?
+        .build()?;
+
+    match &plan {
+        PlanNode::Join(join) => {
+            assert_eq!(
+                DataType::Int64,
+                join.left_keys[0].to_data_type(&left.schema())?
+            );
+            assert_eq!(
+                DataType::Int64,
+                join.right_keys[0].to_data_type(&right.schema())?
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    // SEMI JOIN outputs the columns of the left side only.
+    assert_eq!(left.schema(), plan.schema());
+    Ok(())
+}
+
+#[test]
+fn test_join_plan_with_duplicate_columns() -> Result<()> {
+    let left = Test::create().generate_source_plan_for_test(10000)?;
+    let right = Test::create().generate_source_plan_for_test(10000)?;
+    let result = PlanBuilder::from(&left).join(
+        &right,
+        JoinType::Inner,
+        &[col("number")],
+        &[col("number")],
+        None,
+    );
+
+    let expect = "Code: 6, displayText = Duplicate column name number in both sides of join.";
+    assert_eq!(expect, format!("{}", result.err().unwrap()));
+    Ok(())
+}
@@ -0,0 +1,73 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::test::Test;
+
+#[test]
+fn test_union_plan() -> Result<()> {
+    let left = Test::create().generate_source_plan_for_test(10000)?;
+    let right = PlanBuilder::from(&Test::create().generate_source_plan_for_test(10000)?)
+        .project(&[col("number").alias("r")])?
+        .build()?;
+
+    let plan = PlanBuilder::from(&left)
+        .set_operation(SetOperator::Union, false, &right)?
+        .build()?;
+
+    // The columns are named by the first input.
+    let expect = "\
+    Distinct\
+    \n  UnionAll: 2 inputs\
+    \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]\
+    \n    Projection: r as number:UInt64\
+    \n      Projection: number as r:UInt64\
+    \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]";
+    assert_eq!(expect, format!("{:?}", plan));
+    Ok(())
+}
+
+#[test]
+fn test_set_operation_plan() -> Result<()> {
+    let left = Test::create().generate_source_plan_for_test(10000)?;
+    let right = Test::create().generate_source_plan_for_test(10000)?;
+
+    let plan = PlanBuilder::from(&left)
+        .set_operation(SetOperator::Except, true, &right)?
+        .build()?;
+
+    let expect = "\
+    SetOperation: EXCEPT ALL\
+    \n  ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]\
+    \n  ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]";
+    assert_eq!(expect, format!("{:?}", plan));
+    Ok(())
+}
+
+#[test]
+fn test_set_operation_plan_with_different_columns() -> Result<()> {
+    let left = Test::create().generate_source_plan_for_test(10000)?;
+    let right = PlanBuilder::from(&Test::create().generate_source_plan_for_test(10000)?)
+        .project(&[col("number"), col("number").alias("r")])?
+        .build()?;
+
+    let result = PlanBuilder::from(&left).set_operation(SetOperator::Intersect, false, &right);
+    let actual = result.err().unwrap();
+    let expect = "Code: 6, displayText = Each INTERSECT query must have the same number of columns, but got 1 and 2.";
+    assert_eq!(expect, format!("{}", actual));
+    Ok(())
+}
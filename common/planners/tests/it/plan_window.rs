@@ -0,0 +1,87 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::test::Test;
+
+fn window_function(op: &str, args: Vec<Expression>, frame: Option<WindowFrame>) -> Expression {
+    Expression::WindowFunction {
+        op: op.to_string(),
+        params: vec![],
+        args,
+        partition_by: vec![col("number")],
+        order_by: vec![Expression::Sort {
+            expr: Box::new(col("number")),
+            asc: false,
+            nulls_first: false,
+            origin_expr: Box::new(col("number")),
+        }],
+        frame,
+    }
+}
+
+#[test]
+fn test_window_plan() -> Result<()> {
+    let source = Test::create().generate_source_plan_for_test(10000)?;
+    let frame = WindowFrame {
+        units: WindowFrameUnits::Rows,
+        start_bound: WindowFrameBound::Preceding(Some(1)),
+        end_bound: WindowFrameBound::CurrentRow,
+    };
+
+    let plan = PlanBuilder::from(&source)
+        .window(&[
+            window_function("row_number", vec![], None),
+            window_function("sum", vec![col("number")], Some(frame)),
+        ])?
+        .build()?;
+
+    let expect = "\
+    Window: row_number() OVER (PARTITION BY number ORDER BY number DESC):UInt64, sum(number) OVER (PARTITION BY number ORDER BY number DESC ROWS BETWEEN 1 PRECEDING AND CURRENT ROW):UInt64\
+    \n  ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]";
+    assert_eq!(expect, format!("{:?}", plan));
+
+    // The window functions are appended to the input columns.
+    let schema = plan.schema();
+    assert_eq!(3, schema.fields().len());
+    assert_eq!("number", schema.field(0).name());
+    assert!(!schema.field(1).is_nullable());
+    assert!(schema.field(2).is_nullable());
+    assert_eq!(&DataType::UInt64, schema.field(2).data_type());
+    Ok(())
+}
+
+#[test]
+fn test_window_plan_with_different_windows() -> Result<()> {
+    let source = Test::create().generate_source_plan_for_test(10000)?;
+    let result = PlanBuilder::from(&source).window(&[
+        window_function("rank", vec![], None),
+        Expression::WindowFunction {
+            op: String::from("rank"),
+            params: vec![],
+            args: vec![],
+            partition_by: vec![],
+            order_by: vec![],
+            frame: None,
+        },
+    ]);
+
+    let expect = "Code: 15, displayText = Window functions of the same window plan must have the same window.";
+    assert_eq!(expect, format!("{}", result.err().unwrap()));
+    Ok(())
+}
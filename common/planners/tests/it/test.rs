@@ -34,11 +34,7 @@ impl Test {
         let schema =
             DataSchemaRefExt::create(vec![DataField::new("number", DataType::UInt64, false)]);
 
-        let statistics = Statistics {
-            read_rows: total,
-            read_bytes: total * 8,
-            is_exact: true,
-        };
+        let statistics = Statistics::new_exact(total, total * 8);
 
         Ok(PlanNode::ReadSource(ReadDataSourcePlan {
             table_info: TableInfo::simple("system", "numbers_mt", schema),
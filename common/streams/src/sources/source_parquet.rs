@@ -42,7 +42,7 @@ pub struct ParquetSource {
     projection: Vec<usize>,
     row_group: usize,
     row_groups: usize,
-    metadata: Option<FileMetaData>,
+    metadata: Option<Arc<FileMetaData>>,
 }
 
 impl ParquetSource {
@@ -64,6 +64,14 @@ impl ParquetSource {
             metadata: None,
         }
     }
+
+    /// Uses the given metadata (e.g. a cached one) of the parquet file, instead of reading it
+    pub fn with_metadata(mut self, metadata: Arc<FileMetaData>) -> Self {
+        self.row_groups = metadata.row_groups.len();
+        self.row_group = 0;
+        self.metadata = Some(metadata);
+        self
+    }
 }
 
 #[async_trait]
@@ -78,6 +86,7 @@ impl Source for ParquetSource {
                 let m = read_metadata_async(&mut reader)
                     .await
                     .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+                let m = Arc::new(m);
                 self.metadata = Some(m.clone());
                 self.row_groups = m.row_groups.len();
                 self.row_group = 0;
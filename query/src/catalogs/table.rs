@@ -22,13 +22,18 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::MetaId;
 use common_meta_types::TableInfo;
+use common_planners::DeletePlan;
 use common_planners::Expression;
 use common_planners::Extras;
+use common_planners::OptimizeTablePlan;
 use common_planners::Part;
 use common_planners::Partitions;
 use common_planners::ReadDataSourcePlan;
 use common_planners::Statistics;
+use common_planners::TimeTravelPoint;
 use common_planners::TruncateTablePlan;
+use common_planners::UpdatePlan;
+use common_planners::VacuumTablePlan;
 use common_streams::SendableDataBlockStream;
 
 use crate::sessions::QueryContext;
@@ -103,6 +108,22 @@ pub trait Table: Sync + Send {
         Ok(())
     }
 
+    async fn delete(&self, _ctx: Arc<QueryContext>, _delete_plan: DeletePlan) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "delete for table {} is not implemented, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    async fn update(&self, _ctx: Arc<QueryContext>, _update_plan: UpdatePlan) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "update for table {} is not implemented, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
     async fn truncate(
         &self,
         _ctx: Arc<QueryContext>,
@@ -113,6 +134,45 @@ pub trait Table: Sync + Send {
             self.name()
         )))
     }
+
+    async fn optimize(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _optimize_plan: OptimizeTablePlan,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "optimize for table {} is not implemented, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    /// Removes the data which are no longer reachable from the history within the retention period,
+    /// returns the locations of the removed files (or the files to be removed, if dry run)
+    async fn vacuum(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _vacuum_plan: VacuumTablePlan,
+    ) -> Result<Vec<String>> {
+        Err(ErrorCode::UnImplement(format!(
+            "vacuum for table {} is not implemented, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    /// Returns the table as it was at the given point of its history
+    async fn navigate_to(
+        &self,
+        _ctx: Arc<QueryContext>,
+        _point: &TimeTravelPoint,
+    ) -> Result<Arc<dyn Table>> {
+        Err(ErrorCode::UnImplement(format!(
+            "time travel for table {} is not implemented, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
 }
 
 pub type TablePtr = Arc<dyn Table>;
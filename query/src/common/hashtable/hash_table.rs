@@ -82,6 +82,12 @@ impl<Key: HashTableKeyable, Entity: HashTableEntity<Key>> HashTable<Key, Entity>
         self.size == 0
     }
 
+    /// The bytes of the allocated entities, which grow as the table is resized.
+    #[inline(always)]
+    pub fn allocated_bytes(&self) -> usize {
+        (self.grower.max_size() as usize) * mem::size_of::<Entity>()
+    }
+
     #[inline(always)]
     pub fn iter(&self) -> HashTableIter<Key, Entity> {
         HashTableIter::create(self.grower.max_size(), self.entities, self.zero_entity)
@@ -15,6 +15,10 @@
 mod hashtable;
 mod meta;
 pub mod service;
+mod spill;
 
 pub use hashtable::*;
 pub use meta::MetaClientProvider;
+pub use spill::SortedRun;
+pub use spill::SortedRunsMerger;
+pub use spill::SpillFile;
@@ -0,0 +1,206 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::ArrayRef;
+use common_arrow::arrow::compute::merge_sort::build_comparator;
+use common_arrow::arrow::compute::merge_sort::MergeSlice;
+use common_arrow::arrow::compute::merge_sort::SortOptions;
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
+
+/// The sorted blocks of a run, a run is sorted as a whole.
+pub type SortedRun = SendableDataBlockStream;
+
+/// K-way merge of the sorted runs, only the current block of each run is kept in memory.
+pub struct SortedRunsMerger {
+    schema: DataSchemaRef,
+    sort_columns_descriptions: Vec<SortColumnDescription>,
+    max_block_size: usize,
+    limit: Option<usize>,
+    runs: Vec<SortedRun>,
+    // The current block of each run and the next row to merge, None if the run is finished.
+    heads: Vec<Option<(DataBlock, usize)>>,
+    initialized: bool,
+    merged_rows: usize,
+}
+
+impl SortedRunsMerger {
+    pub fn create(
+        schema: DataSchemaRef,
+        sort_columns_descriptions: Vec<SortColumnDescription>,
+        max_block_size: usize,
+        limit: Option<usize>,
+        runs: Vec<SortedRun>,
+    ) -> SortedRunsMerger {
+        SortedRunsMerger {
+            schema,
+            sort_columns_descriptions,
+            max_block_size: max_block_size.max(1),
+            limit,
+            heads: (0..runs.len()).map(|_| None).collect(),
+            runs,
+            initialized: false,
+            merged_rows: 0,
+        }
+    }
+
+    async fn next_run_block(&mut self, run: usize) -> Result<Option<(DataBlock, usize)>> {
+        while let Some(block) = self.runs[run].next().await {
+            let block = block?;
+            if !block.is_empty() {
+                return Ok(Some((block, 0)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn merge_next_block(&mut self) -> Result<Option<DataBlock>> {
+        if !self.initialized {
+            self.initialized = true;
+            for run in 0..self.runs.len() {
+                self.heads[run] = self.next_run_block(run).await?;
+            }
+        }
+
+        let max_rows = match self.limit {
+            Some(limit) => self.max_block_size.min(limit - self.merged_rows),
+            None => self.max_block_size,
+        };
+
+        let active = (0..self.heads.len())
+            .filter(|run| self.heads[*run].is_some())
+            .collect::<Vec<_>>();
+
+        if max_rows == 0 || active.is_empty() {
+            return Ok(None);
+        }
+
+        let (blocks, mut positions): (Vec<_>, Vec<_>) = active
+            .iter()
+            .filter_map(|run| self.heads[*run].clone())
+            .unzip();
+
+        let slices = {
+            let sort_arrays = self
+                .sort_columns_descriptions
+                .iter()
+                .map(|f| {
+                    blocks
+                        .iter()
+                        .map(|block| Ok(block.try_array_by_name(&f.column_name)?.get_array_ref()))
+                        .collect::<Result<Vec<_>>>()
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let sort_dyn_arrays = sort_arrays
+                .iter()
+                .map(|arrays| arrays.iter().map(|a| a.as_ref()).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            let sort_options = self
+                .sort_columns_descriptions
+                .iter()
+                .map(|f| SortOptions {
+                    descending: !f.asc,
+                    nulls_first: f.nulls_first,
+                })
+                .collect::<Vec<_>>();
+            let pairs = sort_dyn_arrays
+                .iter()
+                .zip(sort_options.iter())
+                .map(|(arrays, options)| (arrays.as_slice(), options))
+                .collect::<Vec<(&[&dyn Array], &SortOptions)>>();
+            let comparator = build_comparator(&pairs)?;
+
+            // Merge until any block is exhausted, the next block of its run has to be read
+            // before going on.
+            let mut slices: Vec<MergeSlice> = vec![];
+            let mut rows = 0;
+            while rows < max_rows {
+                let mut min = 0;
+                for (index, position) in positions.iter().enumerate().skip(1) {
+                    if comparator(index, *position, min, positions[min]) == Ordering::Less {
+                        min = index;
+                    }
+                }
+
+                match slices.last_mut() {
+                    Some((index, start, len))
+                        if *index == min && *start + *len == positions[min] =>
+                    {
+                        *len += 1
+                    }
+                    _ => slices.push((min, positions[min], 1)),
+                }
+
+                rows += 1;
+                positions[min] += 1;
+                if positions[min] == blocks[min].num_rows() {
+                    break;
+                }
+            }
+
+            slices
+        };
+
+        let columns = self
+            .schema
+            .fields()
+            .iter()
+            .map(|f| {
+                let arrays = blocks
+                    .iter()
+                    .map(|block| block.try_column_by_name(f.name())?.to_array())
+                    .collect::<Result<Vec<_>>>()?;
+                let arrays = arrays
+                    .iter()
+                    .map(|array| array.get_array_ref())
+                    .collect::<Vec<_>>();
+                let arrays = arrays.iter().map(|a| a.as_ref()).collect::<Vec<_>>();
+
+                let taked = DataBlock::take_arrays_by_slices(&arrays, &slices, None);
+                let taked: ArrayRef = Arc::from(taked);
+                Ok(DataColumn::Array(taked.into_series()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let block = DataBlock::create(self.schema.clone(), columns);
+        self.merged_rows += block.num_rows();
+
+        for (index, run) in active.into_iter().enumerate() {
+            match positions[index] == blocks[index].num_rows() {
+                true => self.heads[run] = self.next_run_block(run).await?,
+                false => self.heads[run] = Some((blocks[index].clone(), positions[index])),
+            }
+        }
+
+        Ok(Some(block))
+    }
+
+    /// The merged blocks, the runs are read as the merging goes on.
+    pub fn into_stream(self) -> SendableDataBlockStream {
+        Box::pin(futures::stream::try_unfold(self, |mut merger| async move {
+            match merger.merge_next_block().await? {
+                None => Ok(None),
+                Some(block) => Ok(Some((block, merger))),
+            }
+        }))
+    }
+}
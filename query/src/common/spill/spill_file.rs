@@ -0,0 +1,184 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::convert::TryInto;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::path::PathBuf;
+
+use common_arrow::arrow::io::ipc::read::read_file_metadata;
+use common_arrow::arrow::io::ipc::read::FileReader;
+use common_arrow::arrow::io::ipc::write::FileWriter;
+use common_arrow::arrow::io::ipc::write::WriteOptions;
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_base::tokio::runtime::Handle;
+use common_base::tokio::task;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+
+use crate::sessions::QueryContext;
+
+/// A temporary file of blocks in the Arrow IPC file format.
+///
+/// The file is created in the `temp_data_path` of the disk storage, or in the temporary
+/// directory of the system if it is not configured, and it is removed when dropped.
+///
+/// The file is written and read by the blocking threads, not to block the async ones.
+pub struct SpillFile {
+    path: PathBuf,
+    schema: DataSchemaRef,
+}
+
+impl SpillFile {
+    pub async fn create(
+        ctx: &QueryContext,
+        schema: &DataSchemaRef,
+        blocks: Vec<DataBlock>,
+    ) -> Result<SpillFile> {
+        let temp_data_path = ctx.get_config().storage.disk.temp_data_path;
+        let dir = match temp_data_path.is_empty() {
+            true => std::env::temp_dir(),
+            false => PathBuf::from(temp_data_path),
+        };
+
+        let path = dir.join(format!("_spill_{}.arrow", uuid::Uuid::new_v4()));
+        tracing::debug!("Spill {} blocks to {:?}", blocks.len(), path);
+
+        // Remove the file if the writing fails.
+        let spill_file = SpillFile {
+            path,
+            schema: schema.clone(),
+        };
+
+        spawn_blocking(move || {
+            std::fs::create_dir_all(&dir)?;
+            spill_file.write(&blocks)?;
+            Ok(spill_file)
+        })
+        .await
+    }
+
+    fn write(&self, blocks: &[DataBlock]) -> Result<()> {
+        let file = BufWriter::new(File::create(&self.path)?);
+        let options = WriteOptions { compression: None };
+        let mut writer = FileWriter::try_new(file, &self.schema.to_arrow(), options)?;
+        for block in blocks {
+            let batch: RecordBatch = block.clone().try_into()?;
+            writer.write(&batch)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Read the blocks back in the order they were written, the file is removed once all the
+    /// blocks are read or the stream is dropped.
+    pub async fn into_stream(self) -> Result<SendableDataBlockStream> {
+        let reader = spawn_blocking(move || SpillFileReader::try_create(self)).await?;
+        let stream = futures::stream::try_unfold(reader, |mut reader| {
+            spawn_blocking(move || match reader.next().transpose()? {
+                None => Ok(None),
+                Some(block) => Ok(Some((block, reader))),
+            })
+        });
+
+        Ok(Box::pin(stream))
+    }
+}
+
+impl SpillFile {
+    fn remove(path: PathBuf) {
+        if let Err(cause) = std::fs::remove_file(&path) {
+            tracing::warn!("Cannot remove spill file {:?}, cause: {}", path, cause);
+        }
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        // Already removed once all the blocks are read.
+        let path = std::mem::take(&mut self.path);
+        if path.as_os_str().is_empty() {
+            return;
+        }
+
+        // Not to block the async threads, if dropped by one of them.
+        match Handle::try_current() {
+            Ok(handle) => drop(handle.spawn_blocking(move || SpillFile::remove(path))),
+            Err(_) => SpillFile::remove(path),
+        }
+    }
+}
+
+/// Reads the blocks of the spill file, in the blocking threads.
+struct SpillFileReader {
+    // The reader is dropped before the file, which closes the file before it is removed.
+    inner: Option<(FileReader<BufReader<File>>, SpillFile)>,
+}
+
+impl SpillFileReader {
+    fn try_create(spill_file: SpillFile) -> Result<SpillFileReader> {
+        let mut file = BufReader::new(File::open(&spill_file.path)?);
+        let metadata = read_file_metadata(&mut file)?;
+        let reader = FileReader::new(file, metadata, None);
+
+        Ok(SpillFileReader {
+            inner: Some((reader, spill_file)),
+        })
+    }
+}
+
+impl Iterator for SpillFileReader {
+    type Item = Result<DataBlock>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (reader, file) = self.inner.as_mut()?;
+        match reader.next() {
+            None => {
+                // Close the file and remove it, in the blocking thread reading it.
+                if let Some((reader, mut file)) = self.inner.take() {
+                    drop(reader);
+                    SpillFile::remove(std::mem::take(&mut file.path));
+                }
+                None
+            }
+            Some(batch) => Some(batch.map_err(ErrorCode::from).and_then(|batch| {
+                let block = DataBlock::try_from(batch)?;
+                Ok(DataBlock::create(
+                    file.schema.clone(),
+                    block.columns().to_vec(),
+                ))
+            })),
+        }
+    }
+}
+
+async fn spawn_blocking<F, R>(f: F) -> Result<R>
+where
+    F: FnOnce() -> Result<R> + Send + 'static,
+    R: Send + 'static,
+{
+    match task::spawn_blocking(f).await {
+        Ok(res) => res,
+        Err(cause) => Err(ErrorCode::TokioError(format!(
+            "Cannot join the spill task. cause: {}",
+            cause
+        ))),
+    }
+}
@@ -32,6 +32,12 @@ pub const QUERY_FLIGHT_API_ADDRESS: &str = "QUERY_FLIGHT_API_ADDRESS";
 pub const QUERY_HTTP_API_ADDRESS: &str = "QUERY_HTTP_API_ADDRESS";
 pub const QUERY_METRICS_API_ADDRESS: &str = "QUERY_METRIC_API_ADDRESS";
 pub const QUERY_WAIT_TIMEOUT_MILLS: &str = "QUERY_WAIT_TIMEOUT_MILLS";
+pub const QUERY_TABLE_CACHE_ENABLED: &str = "QUERY_TABLE_CACHE_ENABLED";
+pub const QUERY_TABLE_CACHE_SNAPSHOT_MB_SIZE: &str = "QUERY_TABLE_CACHE_SNAPSHOT_MB_SIZE";
+pub const QUERY_TABLE_CACHE_SEGMENT_MB_SIZE: &str = "QUERY_TABLE_CACHE_SEGMENT_MB_SIZE";
+pub const QUERY_TABLE_CACHE_BLOCK_META_MB_SIZE: &str = "QUERY_TABLE_CACHE_BLOCK_META_MB_SIZE";
+pub const QUERY_TABLE_DISK_CACHE_ROOT: &str = "QUERY_TABLE_DISK_CACHE_ROOT";
+pub const QUERY_TABLE_DISK_CACHE_MB_SIZE: &str = "QUERY_TABLE_DISK_CACHE_MB_SIZE";
 const QUERY_API_TLS_SERVER_CERT: &str = "QUERY_API_TLS_SERVER_CERT";
 const QUERY_API_TLS_SERVER_KEY: &str = "QUERY_API_TLS_SERVER_KEY";
 const QUERY_API_TLS_SERVER_ROOT_CA_CERT: &str = "QUERY_API_TLS_SERVER_ROOT_CA_CERT";
@@ -200,6 +206,61 @@ pub struct QueryConfig {
     )]
     #[serde(default)]
     pub wait_timeout_mills: u64,
+
+    #[structopt(
+        long,
+        env = QUERY_TABLE_CACHE_ENABLED,
+        parse(try_from_str),
+        default_value = "true",
+        help = "Table cache enabled"
+    )]
+    #[serde(default)]
+    pub table_cache_enabled: bool,
+
+    #[structopt(
+        long,
+        env = QUERY_TABLE_CACHE_SNAPSHOT_MB_SIZE,
+        default_value = "16",
+        help = "Max size (in MB) of the cached table snapshots, 0 to disable"
+    )]
+    #[serde(default)]
+    pub table_cache_snapshot_mb_size: u64,
+
+    #[structopt(
+        long,
+        env = QUERY_TABLE_CACHE_SEGMENT_MB_SIZE,
+        default_value = "256",
+        help = "Max size (in MB) of the cached table segments, 0 to disable"
+    )]
+    #[serde(default)]
+    pub table_cache_segment_mb_size: u64,
+
+    #[structopt(
+        long,
+        env = QUERY_TABLE_CACHE_BLOCK_META_MB_SIZE,
+        default_value = "256",
+        help = "Max size (in MB) of the cached parquet footers of table blocks, 0 to disable"
+    )]
+    #[serde(default)]
+    pub table_cache_block_meta_mb_size: u64,
+
+    #[structopt(
+        long,
+        env = QUERY_TABLE_DISK_CACHE_ROOT,
+        default_value = "_cache",
+        help = "Directory of the disk tier of the table cache"
+    )]
+    #[serde(default)]
+    pub table_disk_cache_root: String,
+
+    #[structopt(
+        long,
+        env = QUERY_TABLE_DISK_CACHE_MB_SIZE,
+        default_value = "1024",
+        help = "Max size (in MB) of the disk tier of the table cache, 0 to disable"
+    )]
+    #[serde(default)]
+    pub table_disk_cache_mb_size: u64,
 }
 
 impl QueryConfig {
@@ -229,6 +290,12 @@ impl QueryConfig {
             table_engine_parquet_enabled: false,
             table_engine_memory_enabled: true,
             wait_timeout_mills: 5000,
+            table_cache_enabled: true,
+            table_cache_snapshot_mb_size: 16,
+            table_cache_segment_mb_size: 256,
+            table_cache_block_meta_mb_size: 256,
+            table_disk_cache_root: "_cache".to_string(),
+            table_disk_cache_mb_size: 1024,
         }
     }
 
@@ -349,5 +416,49 @@ impl QueryConfig {
             u64,
             QUERY_WAIT_TIMEOUT_MILLS
         );
+
+        // for table cache
+        env_helper!(
+            mut_config,
+            query,
+            table_cache_enabled,
+            bool,
+            QUERY_TABLE_CACHE_ENABLED
+        );
+        env_helper!(
+            mut_config,
+            query,
+            table_cache_snapshot_mb_size,
+            u64,
+            QUERY_TABLE_CACHE_SNAPSHOT_MB_SIZE
+        );
+        env_helper!(
+            mut_config,
+            query,
+            table_cache_segment_mb_size,
+            u64,
+            QUERY_TABLE_CACHE_SEGMENT_MB_SIZE
+        );
+        env_helper!(
+            mut_config,
+            query,
+            table_cache_block_meta_mb_size,
+            u64,
+            QUERY_TABLE_CACHE_BLOCK_META_MB_SIZE
+        );
+        env_helper!(
+            mut_config,
+            query,
+            table_disk_cache_root,
+            String,
+            QUERY_TABLE_DISK_CACHE_ROOT
+        );
+        env_helper!(
+            mut_config,
+            query,
+            table_disk_cache_mb_size,
+            u64,
+            QUERY_TABLE_DISK_CACHE_MB_SIZE
+        );
     }
 }
@@ -67,7 +67,7 @@ pub struct DiskStorageConfig {
     #[structopt(long, env = DISK_STORAGE_DATA_PATH, default_value = "_data", help = "Disk storage backend data path")]
     #[serde(default)]
     pub data_path: String,
-    #[structopt(long, env = DISK_STORAGE_TEMP_DATA_PATH, default_value = "", help = "Disk storage temporary data path for external data")]
+    #[structopt(long, env = DISK_STORAGE_TEMP_DATA_PATH, default_value = "", help = "Disk storage temporary data path for external data and the spill files of sort and group by, which are created in the temporary directory of the system if it is empty")]
     #[serde(default)]
     pub temp_data_path: String,
 }
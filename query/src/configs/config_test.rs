@@ -61,6 +61,12 @@ table_engine_csv_enabled = false
 table_engine_parquet_enabled = false
 table_engine_memory_enabled = true
 wait_timeout_mills = 5000
+table_cache_enabled = true
+table_cache_snapshot_mb_size = 16
+table_cache_segment_mb_size = 256
+table_cache_block_meta_mb_size = 256
+table_disk_cache_root = \"_cache\"
+table_disk_cache_mb_size = 1024
 
 [log]
 log_level = \"INFO\"
@@ -124,6 +130,8 @@ fn test_env_config() -> Result<()> {
     std::env::set_var("TABLE_ENGINE_CSV_ENABLED", "true");
     std::env::set_var("TABLE_ENGINE_PARQUET_ENABLED", "true");
     std::env::set_var("TABLE_ENGINE_MEMORY_ENABLED", "true");
+    std::env::set_var("QUERY_TABLE_CACHE_ENABLED", "false");
+    std::env::set_var("QUERY_TABLE_CACHE_SEGMENT_MB_SIZE", "100");
     std::env::remove_var("CONFIG_FILE");
 
     let default = Config::default();
@@ -156,6 +164,10 @@ fn test_env_config() -> Result<()> {
     assert!(!configured.query.table_engine_parquet_enabled);
     assert!(configured.query.table_engine_memory_enabled);
 
+    assert!(!configured.query.table_cache_enabled);
+    assert_eq!(16, configured.query.table_cache_snapshot_mb_size);
+    assert_eq!(100, configured.query.table_cache_segment_mb_size);
+
     // clean up
     std::env::remove_var("LOG_LEVEL");
     std::env::remove_var("QUERY_TENANT_ID");
@@ -178,6 +190,8 @@ fn test_env_config() -> Result<()> {
     std::env::remove_var("TABLE_ENGINE_CSV_ENABLED");
     std::env::remove_var("TABLE_ENGINE_PARQUET_ENABLED");
     std::env::remove_var("TABLE_ENGINE_MEMORY_ENABLED");
+    std::env::remove_var("QUERY_TABLE_CACHE_ENABLED");
+    std::env::remove_var("QUERY_TABLE_CACHE_SEGMENT_MB_SIZE");
     Ok(())
 }
 
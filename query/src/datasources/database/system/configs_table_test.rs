@@ -38,7 +38,7 @@ async fn test_configs_table() -> Result<()> {
     let result = stream.try_collect::<Vec<_>>().await?;
     let block = &result[0];
     assert_eq!(block.num_columns(), 4);
-    assert_eq!(block.num_rows(), 33);
+    assert_eq!(block.num_rows(), 39);
 
     let expected = vec![
         "+-----------------------------------+------------------+-------+-------------+",
@@ -72,6 +72,12 @@ async fn test_configs_table() -> Result<()> {
         "| rpc_tls_query_service_domain_name | localhost        | query |             |",
         "| rpc_tls_server_cert               |                  | query |             |",
         "| rpc_tls_server_key                |                  | query |             |",
+        "| table_cache_block_meta_mb_size    | 256              | query |             |",
+        "| table_cache_enabled               | true             | query |             |",
+        "| table_cache_segment_mb_size       | 256              | query |             |",
+        "| table_cache_snapshot_mb_size      | 16               | query |             |",
+        "| table_disk_cache_mb_size          | 1024             | query |             |",
+        "| table_disk_cache_root             | _cache           | query |             |",
         "| table_engine_csv_enabled          | false            | query |             |",
         "| table_engine_memory_enabled       | true             | query |             |",
         "| table_engine_parquet_enabled      | false            | query |             |",
@@ -0,0 +1,254 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::hash::Hasher;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::Expression;
+use siphasher::sip128::Hasher128;
+use siphasher::sip128::SipHasher13;
+
+use crate::datasources::table::fuse::ColumnId;
+
+/// Bloom filter of the values of a column (of a block), which tells that a value is definitely
+/// absent, or may be present in the column.
+///
+/// Values are hashed by SipHash-1-3 with fixed keys, the output of which is stable, so that the
+/// filters persisted could be checked by later versions. Integers and floats of integral values
+/// are hashed the same way regardless of their types, e.g. `Int32(1)`, `UInt8(1)` and `Float64(1.0)`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BloomFilter {
+    num_hashes: u32,
+    bits: Vec<u64>,
+}
+
+impl BloomFilter {
+    /// Creates a filter for the given number of distinct values, and the expected false positive rate
+    pub fn create(num_values: usize, false_positive_rate: f64) -> Self {
+        let num_values = num_values.max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let num_bits = (-num_values * false_positive_rate.ln() / (ln2 * ln2)).ceil() as usize;
+        let num_words = ((num_bits + 63) / 64).max(1);
+        let num_hashes = ((num_words * 64) as f64 / num_values * ln2).round() as u32;
+        BloomFilter {
+            num_hashes: num_hashes.clamp(1, 16),
+            bits: vec![0; num_words],
+        }
+    }
+
+    /// Builds the filter of the values of the column, NULLs are ignored
+    pub fn from_column(column: &DataColumn, false_positive_rate: f64) -> Result<Self> {
+        let mut hashes = HashSet::with_capacity(column.len());
+        for row in 0..column.len() {
+            if let Some(hash) = Self::hash(&column.try_get(row)?) {
+                hashes.insert(hash);
+            }
+        }
+
+        let mut filter = Self::create(hashes.len(), false_positive_rate);
+        for hash in hashes {
+            filter.add_hash(hash);
+        }
+        Ok(filter)
+    }
+
+    /// Whether the filter could be built for the columns of the given type
+    pub fn is_supported_type(data_type: &DataType) -> bool {
+        matches!(data_type, DataType::Boolean | DataType::String) || data_type.is_numeric()
+    }
+
+    /// Returns false if the value is definitely absent.
+    ///
+    /// NULLs and the values of unsupported types are taken as present.
+    pub fn find(&self, value: &DataValue) -> bool {
+        match Self::hash(value) {
+            None => true,
+            Some(hash) => self
+                .bit_positions(hash)
+                .all(|pos| self.bits[pos / 64] & (1 << (pos % 64)) != 0),
+        }
+    }
+
+    /// Size (in bytes) of the bits of the filter
+    pub fn heap_size(&self) -> usize {
+        self.bits.len() * std::mem::size_of::<u64>()
+    }
+
+    fn add_hash(&mut self, hash: (u64, u64)) {
+        for pos in self.bit_positions(hash).collect::<Vec<_>>() {
+            self.bits[pos / 64] |= 1 << (pos % 64);
+        }
+    }
+
+    // double hashing, see "Less Hashing, Same Performance: Building a Better Bloom Filter"
+    fn bit_positions(&self, (h1, h2): (u64, u64)) -> impl Iterator<Item = usize> {
+        let num_bits = (self.bits.len() * 64) as u64;
+        (0..self.num_hashes as u64)
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % num_bits) as usize)
+    }
+
+    fn hash(value: &DataValue) -> Option<(u64, u64)> {
+        let mut hasher = SipHasher13::new();
+        match value {
+            DataValue::Boolean(Some(v)) => {
+                hasher.write_u8(0);
+                hasher.write_u8(*v as u8);
+            }
+            DataValue::String(Some(v)) => {
+                hasher.write_u8(1);
+                hasher.write(v);
+            }
+            v if v.is_integer() => {
+                hasher.write_u8(2);
+                hasher.write_i128(Self::integer_value(v)?);
+            }
+            DataValue::Float32(Some(v)) => Self::write_float(&mut hasher, *v as f64),
+            DataValue::Float64(Some(v)) => Self::write_float(&mut hasher, *v),
+            _ => return None,
+        }
+        let hash = hasher.finish128();
+        Some((hash.h1, hash.h2))
+    }
+
+    fn write_float(hasher: &mut SipHasher13, v: f64) {
+        // floats of integral values are hashed as integers, so that they could be found by
+        // the integers of the same values, and vice versa
+        if v.fract() == 0.0 && v.abs() < i128::MAX as f64 {
+            hasher.write_u8(2);
+            hasher.write_i128(v as i128);
+        } else {
+            hasher.write_u8(3);
+            hasher.write_u64(v.to_bits());
+        }
+    }
+
+    fn integer_value(value: &DataValue) -> Option<i128> {
+        match value {
+            DataValue::Int8(Some(v)) => Some(*v as i128),
+            DataValue::Int16(Some(v)) => Some(*v as i128),
+            DataValue::Int32(Some(v)) => Some(*v as i128),
+            DataValue::Int64(Some(v)) => Some(*v as i128),
+            DataValue::UInt8(Some(v)) => Some(*v as i128),
+            DataValue::UInt16(Some(v)) => Some(*v as i128),
+            DataValue::UInt32(Some(v)) => Some(*v as i128),
+            DataValue::UInt64(Some(v)) => Some(*v as i128),
+            _ => None,
+        }
+    }
+}
+
+/// Prunes blocks by the bloom filters of columns, for the equality predicates `col = literal` (and
+/// `col IN (..)`, which is rewritten to the disjunction of equalities), combined by `AND` and `OR`.
+#[derive(Debug, Clone)]
+pub struct BloomFilterPruner {
+    predicate: BloomPredicate,
+}
+
+#[derive(Debug, Clone)]
+enum BloomPredicate {
+    Eq(ColumnId, DataValue),
+    And(Box<BloomPredicate>, Box<BloomPredicate>),
+    Or(Box<BloomPredicate>, Box<BloomPredicate>),
+    Unhandled,
+}
+
+impl BloomFilterPruner {
+    /// Returns None if there is no predicate could be checked by bloom filters
+    pub fn try_create(expr: &Expression, schema: &DataSchemaRef) -> Option<Self> {
+        let predicate = Self::build_predicate(expr, schema);
+        match predicate {
+            BloomPredicate::Unhandled => None,
+            _ => Some(Self { predicate }),
+        }
+    }
+
+    /// Returns false if the block definitely has no rows matching the predicate
+    pub fn may_match(&self, filters: &HashMap<ColumnId, BloomFilter>) -> bool {
+        Self::eval(&self.predicate, filters)
+    }
+
+    fn eval(predicate: &BloomPredicate, filters: &HashMap<ColumnId, BloomFilter>) -> bool {
+        match predicate {
+            BloomPredicate::Eq(column_id, value) => filters
+                .get(column_id)
+                .map_or(true, |filter| filter.find(value)),
+            BloomPredicate::And(left, right) => {
+                Self::eval(left, filters) && Self::eval(right, filters)
+            }
+            BloomPredicate::Or(left, right) => {
+                Self::eval(left, filters) || Self::eval(right, filters)
+            }
+            BloomPredicate::Unhandled => true,
+        }
+    }
+
+    fn build_predicate(expr: &Expression, schema: &DataSchemaRef) -> BloomPredicate {
+        let (left, op, right) = match expr {
+            Expression::BinaryExpression { left, op, right } => (left, op.to_lowercase(), right),
+            _ => return BloomPredicate::Unhandled,
+        };
+
+        match op.as_str() {
+            "and" => match (
+                Self::build_predicate(left, schema),
+                Self::build_predicate(right, schema),
+            ) {
+                (BloomPredicate::Unhandled, p) | (p, BloomPredicate::Unhandled) => p,
+                (l, r) => BloomPredicate::And(Box::new(l), Box::new(r)),
+            },
+            "or" => match (
+                Self::build_predicate(left, schema),
+                Self::build_predicate(right, schema),
+            ) {
+                (BloomPredicate::Unhandled, _) | (_, BloomPredicate::Unhandled) => {
+                    BloomPredicate::Unhandled
+                }
+                (l, r) => BloomPredicate::Or(Box::new(l), Box::new(r)),
+            },
+            "=" => match (left.as_ref(), right.as_ref()) {
+                (Expression::Column(name), Expression::Literal { value, .. })
+                | (Expression::Literal { value, .. }, Expression::Column(name)) => {
+                    Self::build_equality(name, value, schema)
+                }
+                _ => BloomPredicate::Unhandled,
+            },
+            _ => BloomPredicate::Unhandled,
+        }
+    }
+
+    fn build_equality(name: &str, value: &DataValue, schema: &DataSchemaRef) -> BloomPredicate {
+        let column_id = match schema.index_of(name) {
+            Ok(index) => index as ColumnId,
+            Err(_) => return BloomPredicate::Unhandled,
+        };
+
+        // the literal is compared with the column after being casted, only the values of the
+        // same kind as the column are checked, e.g. the numbers for the numeric columns
+        let data_type = schema.field(column_id as usize).data_type();
+        let comparable = match value {
+            DataValue::Boolean(Some(_)) => data_type == &DataType::Boolean,
+            DataValue::String(Some(_)) => data_type == &DataType::String,
+            DataValue::Float32(Some(_)) | DataValue::Float64(Some(_)) => data_type.is_numeric(),
+            v if v.is_integer() && !v.is_null() => data_type.is_numeric(),
+            _ => false,
+        };
+        match comparable {
+            true => BloomPredicate::Eq(column_id, value.clone()),
+            false => BloomPredicate::Unhandled,
+        }
+    }
+}
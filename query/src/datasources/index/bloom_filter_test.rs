@@ -0,0 +1,153 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::col;
+use common_planners::lit;
+
+use crate::datasources::index::BloomFilter;
+use crate::datasources::index::BloomFilterPruner;
+
+#[test]
+fn test_bloom_filter() -> Result<()> {
+    let strings: DataColumn = Series::new(vec!["jack", "ace", "bohu", "ace"]).into();
+    let filter = BloomFilter::from_column(&strings, 0.01)?;
+    for v in ["jack", "ace", "bohu"] {
+        assert!(filter.find(&DataValue::String(Some(v.as_bytes().to_vec()))));
+    }
+    assert!(!filter.find(&DataValue::String(Some("xjack".as_bytes().to_vec()))));
+    // NULLs are taken as present
+    assert!(filter.find(&DataValue::String(None)));
+
+    // integers and floats of the same values could be found by each other
+    let numbers: DataColumn = Series::new(vec![1i32, 20, 300]).into();
+    let filter = BloomFilter::from_column(&numbers, 0.01)?;
+    assert!(filter.find(&DataValue::UInt8(Some(1))));
+    assert!(filter.find(&DataValue::Int64(Some(300))));
+    assert!(filter.find(&DataValue::Float64(Some(20.0))));
+    assert!(!filter.find(&DataValue::Int32(Some(4000))));
+    assert!(!filter.find(&DataValue::Float64(Some(1.5))));
+
+    let floats: DataColumn = Series::new(vec![1.5f64, 2.0]).into();
+    let filter = BloomFilter::from_column(&floats, 0.01)?;
+    assert!(filter.find(&DataValue::Float64(Some(1.5))));
+    assert!(filter.find(&DataValue::UInt64(Some(2))));
+    assert!(!filter.find(&DataValue::Float64(Some(2.5))));
+
+    Ok(())
+}
+
+#[test]
+fn test_bloom_filter_false_positive_rate() -> Result<()> {
+    let values = (0..10000u64).collect::<Vec<_>>();
+    let column: DataColumn = Series::new(values).into();
+    let filter = BloomFilter::from_column(&column, 0.01)?;
+    for v in 0..10000u64 {
+        assert!(filter.find(&DataValue::UInt64(Some(v))));
+    }
+
+    let false_positives = (10000..20000u64)
+        .filter(|v| filter.find(&DataValue::UInt64(Some(*v))))
+        .count();
+    assert!(
+        false_positives < 200,
+        "false positives: {}",
+        false_positives
+    );
+
+    assert!(BloomFilter::is_supported_type(&DataType::String));
+    assert!(BloomFilter::is_supported_type(&DataType::Int32));
+    assert!(!BloomFilter::is_supported_type(&DataType::Date16));
+    Ok(())
+}
+
+#[test]
+fn test_bloom_filter_pruner() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, false),
+        DataField::new("b", DataType::String, false),
+        DataField::new("c", DataType::Int32, false),
+    ]);
+    let a: DataColumn = Series::new(vec![1, 2, 3]).into();
+    let b: DataColumn = Series::new(vec!["x", "y", "z"]).into();
+    let mut filters = HashMap::new();
+    filters.insert(0, BloomFilter::from_column(&a, 0.01)?);
+    filters.insert(1, BloomFilter::from_column(&b, 0.01)?);
+
+    struct Test {
+        name: &'static str,
+        expr: common_planners::Expression,
+        expect: Option<bool>,
+    }
+
+    let tests = vec![
+        Test {
+            name: "a = 2",
+            expr: col("a").eq(lit(2)),
+            expect: Some(true),
+        },
+        Test {
+            name: "4 = a",
+            expr: lit(4).eq(col("a")),
+            expect: Some(false),
+        },
+        Test {
+            name: "b = 'w' or b = 'z'",
+            expr: col("b")
+                .eq(lit("w".as_bytes()))
+                .or(col("b").eq(lit("z".as_bytes()))),
+            expect: Some(true),
+        },
+        Test {
+            name: "b = 'w' or b = 'v'",
+            expr: col("b")
+                .eq(lit("w".as_bytes()))
+                .or(col("b").eq(lit("v".as_bytes()))),
+            expect: Some(false),
+        },
+        Test {
+            name: "b = 'w' and a > 1",
+            expr: col("b").eq(lit("w".as_bytes())).and(col("a").gt(lit(1))),
+            expect: Some(false),
+        },
+        Test {
+            name: "b = 'w' or a > 1",
+            expr: col("b").eq(lit("w".as_bytes())).or(col("a").gt(lit(1))),
+            expect: None,
+        },
+        Test {
+            // column without bloom filter
+            name: "c = 4",
+            expr: col("c").eq(lit(4)),
+            expect: Some(true),
+        },
+        Test {
+            // literal of other kind than the column
+            name: "a = '4'",
+            expr: col("a").eq(lit("4".as_bytes())),
+            expect: None,
+        },
+    ];
+
+    for test in tests {
+        let pruner = BloomFilterPruner::try_create(&test.expr, &schema);
+        let actual = pruner.map(|p| p.may_match(&filters));
+        assert_eq!(actual, test.expect, "{:#?}", test.name);
+    }
+
+    Ok(())
+}
@@ -13,6 +13,8 @@
 // limitations under the License.
 //
 
+#[cfg(test)]
+mod bloom_filter_test;
 #[cfg(test)]
 mod index_min_max_test;
 #[cfg(test)]
@@ -20,10 +22,13 @@ mod index_sparse_test;
 #[cfg(test)]
 mod range_filter_test;
 
+mod bloom_filter;
 mod index_min_max;
 mod index_sparse;
 mod range_filter;
 
+pub use bloom_filter::BloomFilter;
+pub use bloom_filter::BloomFilterPruner;
 pub use index_min_max::MinMaxIndex;
 pub use index_sparse::SparseIndex;
 pub use index_sparse::SparseIndexValue;
@@ -17,7 +17,6 @@ mod database_engine_registry_test;
 
 mod database_engine;
 mod database_engine_registry;
-mod index;
 mod table_engine;
 mod table_engine_registry;
 mod table_func_engine_registry;
@@ -25,6 +24,7 @@ mod table_func_engine_registry;
 pub(crate) mod common;
 pub(crate) mod context;
 pub(crate) mod database;
+pub(crate) mod index;
 pub(crate) mod table;
 pub(crate) mod table_func;
 pub(crate) mod table_func_engine;
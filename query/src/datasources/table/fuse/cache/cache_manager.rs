@@ -0,0 +1,130 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_arrow::arrow::io::parquet::read::schema::FileMetaData;
+use common_exception::Result;
+
+use crate::configs::QueryConfig;
+use crate::datasources::table::fuse::cache::metrics::*;
+use crate::datasources::table::fuse::cache::CacheSize;
+use crate::datasources::table::fuse::cache::TableCache;
+use crate::datasources::table::fuse::cache::TableDiskCache;
+use crate::datasources::table::fuse::meta::SegmentInfo;
+use crate::datasources::table::fuse::meta::TableSnapshot;
+
+pub type TableSnapshotCache = TableCache<TableSnapshot>;
+pub type SegmentInfoCache = TableCache<SegmentInfo>;
+pub type BlockMetaCache = TableCache<FileMetaData>;
+
+const MB: u64 = 1024 * 1024;
+
+/// Caches of the Fuse tables, shared by all the queries of the node.
+///
+/// Snapshots, segments and blocks are never modified once written (a new version always goes
+/// to a new location), so the cached objects never go stale; objects removed from the storage
+/// (e.g. by vacuum) are evicted explicitly. The in-memory caches share the disk tier, if any.
+pub struct CacheManager {
+    table_snapshot_cache: Option<Arc<TableSnapshotCache>>,
+    segment_info_cache: Option<Arc<SegmentInfoCache>>,
+    block_meta_cache: Option<Arc<BlockMetaCache>>,
+    disk_cache: Option<Arc<TableDiskCache>>,
+}
+
+impl CacheManager {
+    pub fn init(conf: &QueryConfig) -> Result<Arc<CacheManager>> {
+        if !conf.table_cache_enabled {
+            return Ok(Arc::new(CacheManager {
+                table_snapshot_cache: None,
+                segment_info_cache: None,
+                block_meta_cache: None,
+                disk_cache: None,
+            }));
+        }
+
+        let disk_cache = match conf.table_disk_cache_mb_size {
+            0 => None,
+            mb => Some(Arc::new(TableDiskCache::try_create(
+                &conf.table_disk_cache_root,
+                mb * MB,
+            )?)),
+        };
+
+        Ok(Arc::new(CacheManager {
+            table_snapshot_cache: Self::new_cache(
+                conf.table_cache_snapshot_mb_size * MB,
+                &disk_cache,
+                METRIC_TABLE_SNAPSHOT_CACHE_HITS,
+                METRIC_TABLE_SNAPSHOT_CACHE_MISSES,
+            ),
+            segment_info_cache: Self::new_cache(
+                conf.table_cache_segment_mb_size * MB,
+                &disk_cache,
+                METRIC_SEGMENT_INFO_CACHE_HITS,
+                METRIC_SEGMENT_INFO_CACHE_MISSES,
+            ),
+            block_meta_cache: Self::new_cache(
+                conf.table_cache_block_meta_mb_size * MB,
+                &disk_cache,
+                METRIC_BLOCK_META_CACHE_HITS,
+                METRIC_BLOCK_META_CACHE_MISSES,
+            ),
+            disk_cache,
+        }))
+    }
+
+    pub fn get_table_snapshot_cache(&self) -> Option<Arc<TableSnapshotCache>> {
+        self.table_snapshot_cache.clone()
+    }
+
+    pub fn get_segment_info_cache(&self) -> Option<Arc<SegmentInfoCache>> {
+        self.segment_info_cache.clone()
+    }
+
+    pub fn get_block_meta_cache(&self) -> Option<Arc<BlockMetaCache>> {
+        self.block_meta_cache.clone()
+    }
+
+    /// Evicts the object of the given location (which has been removed from the storage), if cached
+    pub async fn evict(&self, location: &str) {
+        if let Some(cache) = &self.table_snapshot_cache {
+            cache.evict(location);
+        }
+        if let Some(cache) = &self.segment_info_cache {
+            cache.evict(location);
+        }
+        if let Some(cache) = &self.block_meta_cache {
+            cache.evict(location);
+        }
+        if let Some(cache) = &self.disk_cache {
+            cache.evict(location).await;
+        }
+    }
+
+    fn new_cache<V: CacheSize>(
+        capacity: u64,
+        disk_cache: &Option<Arc<TableDiskCache>>,
+        hits_metric: &'static str,
+        misses_metric: &'static str,
+    ) -> Option<Arc<TableCache<V>>> {
+        match capacity {
+            0 => None,
+            _ => Some(Arc::new(
+                TableCache::create(capacity, hits_metric, misses_metric)
+                    .with_disk_cache(disk_cache.clone()),
+            )),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::mem::size_of_val;
+
+use common_arrow::arrow::io::parquet::read::schema::FileMetaData;
+use common_datavalues::DataValue;
+
+use crate::datasources::index::BloomFilter;
+use crate::datasources::table::fuse::meta::BlockMeta;
+use crate::datasources::table::fuse::meta::ColStats;
+use crate::datasources::table::fuse::meta::ColumnId;
+use crate::datasources::table::fuse::meta::SegmentInfo;
+use crate::datasources::table::fuse::meta::Stats;
+use crate::datasources::table::fuse::meta::TableSnapshot;
+
+/// Approximate size (in bytes) of the memory taken by the objects being cached, which the
+/// capacities of the [`TableCache`]s are measured by.
+///
+/// [`TableCache`]: crate::datasources::table::fuse::cache::TableCache
+pub trait CacheSize {
+    fn cache_size(&self) -> usize;
+}
+
+impl CacheSize for TableSnapshot {
+    fn cache_size(&self) -> usize {
+        let fields = self.schema.fields();
+        size_of::<Self>()
+            + self.summary.heap_size()
+            + fields
+                .iter()
+                .map(|f| size_of_val(f) + f.name().len())
+                .sum::<usize>()
+            + self
+                .segments
+                .iter()
+                .map(|s| size_of_val(s) + s.len())
+                .sum::<usize>()
+    }
+}
+
+impl CacheSize for SegmentInfo {
+    fn cache_size(&self) -> usize {
+        size_of::<Self>()
+            + self.summary.heap_size()
+            + self.blocks.iter().map(block_meta_size).sum::<usize>()
+    }
+}
+
+impl CacheSize for FileMetaData {
+    fn cache_size(&self) -> usize {
+        // the column chunks carry the encodings, paths and statistics of the columns, which are
+        // allocated on the heap, counted as a fixed overhead for each of them
+        const COLUMN_CHUNK_HEAP_SIZE: usize = 256;
+
+        let row_groups = self.row_groups.iter().map(|rg| {
+            size_of_val(rg)
+                + rg.columns()
+                    .iter()
+                    .map(|c| size_of_val(c) + COLUMN_CHUNK_HEAP_SIZE)
+                    .sum::<usize>()
+        });
+        let key_values = self.key_value_metadata.iter().flatten().map(|kv| {
+            size_of_val(kv) + kv.key.len() + kv.value.as_ref().map(|v| v.len()).unwrap_or(0)
+        });
+        size_of::<Self>() + row_groups.sum::<usize>() + key_values.sum::<usize>()
+    }
+}
+
+impl Stats {
+    fn heap_size(&self) -> usize {
+        col_stats_heap_size(&self.col_stats)
+    }
+}
+
+fn block_meta_size(block: &BlockMeta) -> usize {
+    let cluster_stats = block.cluster_stats.iter().map(|stats| {
+        let values = stats.min.iter().chain(stats.max.iter());
+        values
+            .map(|v| size_of_val(v) + data_value_heap_size(v))
+            .sum::<usize>()
+    });
+    let bloom_filters = block
+        .bloom_filters
+        .values()
+        .map(|f| size_of::<(ColumnId, BloomFilter)>() + f.heap_size());
+    size_of::<BlockMeta>()
+        + block.location.location.len()
+        + col_stats_heap_size(&block.col_stats)
+        + cluster_stats.sum::<usize>()
+        + bloom_filters.sum::<usize>()
+}
+
+fn col_stats_heap_size(col_stats: &HashMap<ColumnId, ColStats>) -> usize {
+    col_stats
+        .values()
+        .map(|s| {
+            size_of::<(ColumnId, ColStats)>()
+                + data_value_heap_size(&s.min)
+                + data_value_heap_size(&s.max)
+        })
+        .sum()
+}
+
+fn data_value_heap_size(value: &DataValue) -> usize {
+    match value {
+        DataValue::String(Some(v)) => v.len(),
+        DataValue::List(Some(values), _) | DataValue::Struct(values) => values
+            .iter()
+            .map(|v| size_of_val(v) + data_value_heap_size(v))
+            .sum(),
+        _ => 0,
+    }
+}
@@ -0,0 +1,22 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub static METRIC_TABLE_SNAPSHOT_CACHE_HITS: &str = "fuse.cache.table_snapshot.hits";
+pub static METRIC_TABLE_SNAPSHOT_CACHE_MISSES: &str = "fuse.cache.table_snapshot.misses";
+pub static METRIC_SEGMENT_INFO_CACHE_HITS: &str = "fuse.cache.segment_info.hits";
+pub static METRIC_SEGMENT_INFO_CACHE_MISSES: &str = "fuse.cache.segment_info.misses";
+pub static METRIC_BLOCK_META_CACHE_HITS: &str = "fuse.cache.block_meta.hits";
+pub static METRIC_BLOCK_META_CACHE_MISSES: &str = "fuse.cache.block_meta.misses";
+pub static METRIC_DISK_CACHE_HITS: &str = "fuse.cache.disk.hits";
+pub static METRIC_DISK_CACHE_MISSES: &str = "fuse.cache.disk.misses";
@@ -0,0 +1,30 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub use cache_manager::BlockMetaCache;
+pub use cache_manager::CacheManager;
+pub use cache_manager::SegmentInfoCache;
+pub use cache_manager::TableSnapshotCache;
+pub use cache_size::CacheSize;
+pub use table_cache::TableCache;
+pub use table_disk_cache::TableDiskCache;
+
+mod cache_manager;
+mod cache_size;
+mod metrics;
+mod table_cache;
+mod table_disk_cache;
+
+#[cfg(test)]
+mod table_cache_test;
@@ -0,0 +1,111 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Borrow;
+use std::sync::Arc;
+
+use common_cache::Cache;
+use common_cache::DefaultHashBuilder;
+use common_cache::LruCache;
+use common_cache::Meter;
+use common_infallible::Mutex;
+use metrics::counter;
+
+use crate::datasources::table::fuse::cache::CacheSize;
+use crate::datasources::table::fuse::cache::TableDiskCache;
+
+/// An in-memory LRU cache of the (immutable) meta objects of tables, keyed by their locations.
+///
+/// The capacity is the (approximate) number of bytes taken by the objects being cached, see
+/// [`CacheSize`], hits and misses are reported by the given metrics. The encoded objects are
+/// kept in the disk tier (if any) as well, which outlives the evictions and the restarts.
+pub struct TableCache<V> {
+    inner: Mutex<LruCache<String, Arc<V>, DefaultHashBuilder, MetaSize>>,
+    disk_cache: Option<Arc<TableDiskCache>>,
+    hits_metric: &'static str,
+    misses_metric: &'static str,
+}
+
+impl<V: CacheSize> TableCache<V> {
+    pub fn create(capacity: u64, hits_metric: &'static str, misses_metric: &'static str) -> Self {
+        Self {
+            inner: Mutex::new(LruCache::with_meter(capacity, MetaSize)),
+            disk_cache: None,
+            hits_metric,
+            misses_metric,
+        }
+    }
+
+    pub fn with_disk_cache(mut self, disk_cache: Option<Arc<TableDiskCache>>) -> Self {
+        self.disk_cache = disk_cache;
+        self
+    }
+
+    pub fn get(&self, location: &str) -> Option<Arc<V>> {
+        let cached = self.inner.lock().get(location).cloned();
+        match cached {
+            Some(_) => counter!(self.hits_metric, 1),
+            None => counter!(self.misses_metric, 1),
+        }
+        cached
+    }
+
+    pub fn put(&self, location: String, value: Arc<V>) {
+        self.inner.lock().put(location, value);
+    }
+
+    /// Gets the encoded object from the disk tier, if any
+    pub async fn get_encoded(&self, location: &str) -> Option<Vec<u8>> {
+        match &self.disk_cache {
+            None => None,
+            Some(disk_cache) => disk_cache.get(location).await,
+        }
+    }
+
+    /// Puts the encoded object into the disk tier, if any
+    pub async fn put_encoded(&self, location: &str, bytes: Vec<u8>) {
+        if let Some(disk_cache) = &self.disk_cache {
+            disk_cache.put(location, bytes).await;
+        }
+    }
+
+    pub fn evict(&self, location: &str) {
+        self.inner.lock().pop(location);
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.lock().is_empty()
+    }
+
+    /// The (approximate) number of bytes taken by the objects being cached
+    pub fn size(&self) -> u64 {
+        self.inner.lock().size()
+    }
+}
+
+/// Measures the cached objects by their [`CacheSize`]
+pub struct MetaSize;
+
+impl<V: CacheSize> Meter<String, Arc<V>> for MetaSize {
+    type Measure = usize;
+
+    fn measure<Q: ?Sized>(&self, _: &Q, value: &Arc<V>) -> usize
+    where String: Borrow<Q> {
+        value.cache_size()
+    }
+}
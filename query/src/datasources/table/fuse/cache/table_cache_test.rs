@@ -0,0 +1,117 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::Result;
+use tempfile::TempDir;
+
+use crate::configs::QueryConfig;
+use crate::datasources::table::fuse::cache::CacheManager;
+use crate::datasources::table::fuse::cache::CacheSize;
+use crate::datasources::table::fuse::cache::TableCache;
+use crate::datasources::table::fuse::cache::TableDiskCache;
+
+#[derive(Debug, PartialEq)]
+struct Object(usize);
+
+impl CacheSize for Object {
+    fn cache_size(&self) -> usize {
+        self.0
+    }
+}
+
+#[test]
+fn test_table_cache() -> Result<()> {
+    let cache = TableCache::create(10, "test.cache.hits", "test.cache.misses");
+    assert!(cache.is_empty());
+    assert!(cache.get("a").is_none());
+
+    cache.put("a".to_string(), Arc::new(Object(4)));
+    cache.put("b".to_string(), Arc::new(Object(4)));
+    assert_eq!(8, cache.size());
+    assert_eq!(Some(Arc::new(Object(4))), cache.get("a"));
+
+    // "b" is the least recently used one, evicted to make room for "c"
+    cache.put("c".to_string(), Arc::new(Object(5)));
+    assert_eq!(2, cache.len());
+    assert_eq!(9, cache.size());
+    assert!(cache.get("b").is_none());
+    assert!(cache.get("a").is_some());
+    assert!(cache.get("c").is_some());
+
+    cache.evict("a");
+    assert!(cache.get("a").is_none());
+    assert_eq!(1, cache.len());
+    assert_eq!(5, cache.size());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_table_cache_disk_tier() -> Result<()> {
+    let dir = TempDir::new()?;
+    let root = dir.path().to_str().unwrap();
+    let disk_cache = Arc::new(TableDiskCache::try_create(root, 10)?);
+    let cache = TableCache::<Object>::create(10, "test.cache.hits", "test.cache.misses")
+        .with_disk_cache(Some(disk_cache.clone()));
+
+    assert!(cache.get_encoded("1/_sg/a").await.is_none());
+    cache.put_encoded("1/_sg/a", vec![1, 2, 3]).await;
+    cache.put_encoded("/1/_sg/b", vec![4, 5, 6]).await;
+    assert_eq!(Some(vec![1, 2, 3]), cache.get_encoded("1/_sg/a").await);
+    assert_eq!(Some(vec![4, 5, 6]), cache.get_encoded("/1/_sg/b").await);
+
+    // "a" is the least recently used one, evicted to make room for "c"
+    cache.put_encoded("1/_sg/c", vec![7, 8, 9, 10, 11]).await;
+    assert!(cache.get_encoded("1/_sg/a").await.is_none());
+    assert!(cache.get_encoded("1/_sg/c").await.is_some());
+
+    disk_cache.evict("1/_sg/b").await;
+    assert!(cache.get_encoded("/1/_sg/b").await.is_none());
+
+    // the files are kept across restarts
+    let disk_cache = TableDiskCache::try_create(root, 10)?;
+    assert_eq!(Some(vec![7, 8, 9, 10, 11]), disk_cache.get("1/_sg/c").await);
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_manager() -> Result<()> {
+    // disabled
+    let conf = QueryConfig {
+        table_cache_enabled: false,
+        ..QueryConfig::default()
+    };
+    let cache_manager = CacheManager::init(&conf)?;
+    assert!(cache_manager.get_table_snapshot_cache().is_none());
+    assert!(cache_manager.get_segment_info_cache().is_none());
+    assert!(cache_manager.get_block_meta_cache().is_none());
+
+    // enabled by default, the caches of zero capacity are disabled
+    let dir = TempDir::new()?;
+    let conf = QueryConfig {
+        table_cache_block_meta_mb_size: 0,
+        table_disk_cache_root: dir.path().to_str().unwrap().to_string(),
+        ..QueryConfig::default()
+    };
+    let cache_manager = CacheManager::init(&conf)?;
+    assert!(cache_manager.get_table_snapshot_cache().is_some());
+    assert!(cache_manager.get_segment_info_cache().is_some());
+    assert!(cache_manager.get_block_meta_cache().is_none());
+
+    Ok(())
+}
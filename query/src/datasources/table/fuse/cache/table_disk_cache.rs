@@ -0,0 +1,115 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Read;
+use std::sync::Arc;
+
+use common_base::tokio::task::spawn_blocking;
+use common_cache::DiskCacheResult;
+use common_cache::LruDiskCache;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_tracing::tracing;
+use metrics::counter;
+
+use crate::datasources::table::fuse::cache::metrics::METRIC_DISK_CACHE_HITS;
+use crate::datasources::table::fuse::cache::metrics::METRIC_DISK_CACHE_MISSES;
+
+/// The disk tier of the table caches, the encoded objects are kept in the files (under the
+/// root directory) of their locations, and evicted in LRU order once the capacity (in bytes)
+/// is exceeded.
+///
+/// The files are read and written by the blocking threads, failures are logged and taken as
+/// misses, the objects are always available in the storage.
+pub struct TableDiskCache {
+    inner: Arc<Mutex<LruDiskCache>>,
+}
+
+impl TableDiskCache {
+    /// Creates the cache of the given root, the files left by the previous runs are kept
+    pub fn try_create(root: &str, capacity: u64) -> Result<Self> {
+        let cache = LruDiskCache::new(root, capacity).map_err(|e| {
+            ErrorCode::CannotReadFile(format!("Cannot create the disk cache {}: {}", root, e))
+        })?;
+        Ok(Self {
+            inner: Arc::new(Mutex::new(cache)),
+        })
+    }
+
+    pub async fn get(&self, location: &str) -> Option<Vec<u8>> {
+        let inner = self.inner.clone();
+        let key = Self::key(location);
+        let bytes = spawn_blocking(move || -> DiskCacheResult<Option<Vec<u8>>> {
+            let mut cache = inner.lock();
+            if !cache.contains_key(&key) {
+                return Ok(None);
+            }
+            let mut bytes = vec![];
+            cache.get(&key)?.read_to_end(&mut bytes)?;
+            Ok(Some(bytes))
+        });
+
+        let bytes = match bytes.await {
+            Ok(Ok(bytes)) => bytes,
+            Ok(Err(e)) => {
+                tracing::warn!("failed to read {} from the disk cache: {}", location, e);
+                None
+            }
+            Err(e) => {
+                tracing::warn!("failed to read {} from the disk cache: {}", location, e);
+                None
+            }
+        };
+        match bytes {
+            Some(_) => counter!(METRIC_DISK_CACHE_HITS, 1),
+            None => counter!(METRIC_DISK_CACHE_MISSES, 1),
+        }
+        bytes
+    }
+
+    pub async fn put(&self, location: &str, bytes: Vec<u8>) {
+        let inner = self.inner.clone();
+        let key = Self::key(location);
+        let res = spawn_blocking(move || inner.lock().insert_bytes(&key, &bytes)).await;
+        match res {
+            Ok(Err(e)) => tracing::warn!("failed to write {} to the disk cache: {}", location, e),
+            Err(e) => tracing::warn!("failed to write {} to the disk cache: {}", location, e),
+            Ok(Ok(_)) => {}
+        }
+    }
+
+    pub async fn evict(&self, location: &str) {
+        let inner = self.inner.clone();
+        let key = Self::key(location);
+        let res = spawn_blocking(move || {
+            let mut cache = inner.lock();
+            match cache.contains_key(&key) {
+                true => cache.remove(&key),
+                false => Ok(()),
+            }
+        });
+        match res.await {
+            Ok(Err(e)) => tracing::warn!("failed to evict {} from the disk cache: {}", location, e),
+            Err(e) => tracing::warn!("failed to evict {} from the disk cache: {}", location, e),
+            Ok(Ok(_)) => {}
+        }
+    }
+
+    /// Locations are relative to the root of the storage, a leading '/' would escape the root
+    /// of the cache
+    fn key(location: &str) -> String {
+        location.trim_start_matches('/').to_owned()
+    }
+}
@@ -22,26 +22,32 @@ use common_planners::Extras;
 use futures::StreamExt;
 use futures::TryStreamExt;
 
+use crate::datasources::index::BloomFilterPruner;
 use crate::datasources::index::RangeFilter;
-use crate::datasources::table::fuse::io::snapshot_location;
+use crate::datasources::table::fuse::cache::SegmentInfoCache;
+use crate::datasources::table::fuse::io::read_cached_meta;
 use crate::datasources::table::fuse::meta::BlockMeta;
 use crate::datasources::table::fuse::meta::SegmentInfo;
 use crate::datasources::table::fuse::meta::TableSnapshot;
 use crate::datasources::table::fuse::statistics::BlockStats;
 
 pub struct MinMaxIndex {
-    table_snapshot_loc: String,
+    segment_locs: Vec<String>,
     da: Arc<dyn DataAccessor>,
+    segment_cache: Option<Arc<SegmentInfoCache>>,
 }
 
 type Pred = Box<dyn Fn(&BlockStats) -> Result<bool> + Send + Sync + Unpin>;
 impl MinMaxIndex {
-    pub fn new(table_snapshot: &TableSnapshot, da: Arc<dyn DataAccessor>) -> Self {
+    pub fn new(
+        table_snapshot: &TableSnapshot,
+        da: Arc<dyn DataAccessor>,
+        segment_cache: Option<Arc<SegmentInfoCache>>,
+    ) -> Self {
         Self {
-            table_snapshot_loc: snapshot_location(
-                table_snapshot.snapshot_id.to_simple().to_string(),
-            ),
+            segment_locs: table_snapshot.segments.clone(),
             da,
+            segment_cache,
         }
     }
 
@@ -51,6 +57,12 @@ impl MinMaxIndex {
         schema: DataSchemaRef,
         push_down: Option<Extras>,
     ) -> Result<Vec<BlockMeta>> {
+        let bloom_pruner = match &push_down {
+            Some(exprs) if !exprs.filters.is_empty() => {
+                BloomFilterPruner::try_create(&exprs.filters[0], &schema)
+            }
+            _ => None,
+        };
         let block_pred: Pred = match push_down {
             Some(exprs) if !exprs.filters.is_empty() => {
                 // for the time being, we only handle the first expr
@@ -60,21 +72,16 @@ impl MinMaxIndex {
             _ => Box::new(|_: &BlockStats| Ok(true)),
         };
 
-        let snapshot =
-            common_dal::read_obj::<TableSnapshot>(self.da.clone(), self.table_snapshot_loc.clone())
-                .await?;
-        let segment_num = snapshot.segments.len();
-        let segment_locs = snapshot.segments;
-
-        if segment_locs.is_empty() {
+        let segment_num = self.segment_locs.len();
+        if segment_num == 0 {
             return Ok(vec![]);
         };
 
-        let res = futures::stream::iter(segment_locs)
+        let res = futures::stream::iter(&self.segment_locs)
             .map(|seg_loc| async {
                 let segment_info =
-                    common_dal::read_obj::<SegmentInfo>(self.da.clone(), seg_loc).await?;
-                Self::filter_segment(segment_info, &block_pred)
+                    read_cached_meta(self.da.clone(), seg_loc, &self.segment_cache).await?;
+                Self::filter_segment(&segment_info, &block_pred, &bloom_pruner)
             })
             // configuration of the max size of buffered futures
             .buffered(std::cmp::min(10, segment_num))
@@ -87,14 +94,23 @@ impl MinMaxIndex {
     }
 
     #[inline]
-    fn filter_segment(segment_info: SegmentInfo, pred: &Pred) -> Result<Vec<BlockMeta>> {
+    fn filter_segment(
+        segment_info: &SegmentInfo,
+        pred: &Pred,
+        bloom_pruner: &Option<BloomFilterPruner>,
+    ) -> Result<Vec<BlockMeta>> {
         if pred(&segment_info.summary.col_stats)? {
             let block_num = segment_info.blocks.len();
-            segment_info.blocks.into_iter().try_fold(
+            segment_info.blocks.iter().try_fold(
                 Vec::with_capacity(block_num),
                 |mut acc, block_meta| {
-                    if pred(&block_meta.col_stats)? {
-                        acc.push(block_meta)
+                    // the bloom filters are checked after the min/max, which are much cheaper
+                    if pred(&block_meta.col_stats)?
+                        && bloom_pruner
+                            .as_ref()
+                            .map_or(true, |p| p.may_match(&block_meta.bloom_filters))
+                    {
+                        acc.push(block_meta.clone())
                     }
                     Ok(acc)
                 },
@@ -110,7 +126,8 @@ pub async fn range_filter(
     schema: DataSchemaRef,
     push_down: Option<Extras>,
     data_accessor: Arc<dyn DataAccessor>,
+    segment_cache: Option<Arc<SegmentInfoCache>>,
 ) -> common_exception::Result<Vec<BlockMeta>> {
-    let range_index = MinMaxIndex::new(table_snapshot, data_accessor);
+    let range_index = MinMaxIndex::new(table_snapshot, data_accessor, segment_cache);
     range_index.apply(schema, push_down).await
 }
@@ -14,7 +14,6 @@
 //
 
 use common_base::tokio;
-use common_dal::read_obj;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::SeriesFrom;
 use common_datavalues::series::Series;
@@ -31,6 +30,7 @@ use futures::TryStreamExt;
 
 use crate::catalogs::Catalog;
 use crate::datasources::table::fuse::index::min_max::range_filter;
+use crate::datasources::table::fuse::io::read_meta;
 use crate::datasources::table::fuse::table_test_fixture::TestFixture;
 use crate::datasources::table::fuse::TBL_OPT_KEY_SNAPSHOT_LOC;
 
@@ -100,7 +100,7 @@ async fn test_min_max_index() -> Result<()> {
         .options()
         .get(TBL_OPT_KEY_SNAPSHOT_LOC)
         .unwrap();
-    let snapshot = read_obj(da.clone(), snapshot_loc.clone()).await?;
+    let snapshot = read_meta(da.clone(), snapshot_loc.clone()).await?;
 
     // no pruning
     let push_downs = None;
@@ -109,6 +109,7 @@ async fn test_min_max_index() -> Result<()> {
         table.get_table_info().schema(),
         push_downs,
         da.clone(),
+        None,
     )
     .await?;
     let rows: u64 = blocks.iter().map(|b| b.row_count).sum();
@@ -125,6 +126,7 @@ async fn test_min_max_index() -> Result<()> {
         table.get_table_info().schema(),
         Some(extra),
         da.clone(),
+        None,
     )
     .await?;
     assert_eq!(0, blocks.len());
@@ -134,7 +136,14 @@ async fn test_min_max_index() -> Result<()> {
     let pred = col("a").gt(lit(3)).and(col("b").gt(lit(3)));
     extra.filters = vec![pred];
 
-    let blocks = range_filter(&snapshot, table.get_table_info().schema(), Some(extra), da).await?;
+    let blocks = range_filter(
+        &snapshot,
+        table.get_table_info().schema(),
+        Some(extra),
+        da,
+        None,
+    )
+    .await?;
     assert_eq!(num - 1, blocks.len() as u64);
 
     Ok(())
@@ -18,15 +18,21 @@ use common_arrow::arrow::datatypes::Schema as ArrowSchema;
 use common_arrow::arrow::io::parquet::write::WriteOptions;
 use common_arrow::arrow::io::parquet::write::*;
 use common_arrow::arrow::record_batch::RecordBatch;
+use common_base::MemoryTracker;
 use common_dal::DataAccessor;
 use common_datablocks::DataBlock;
-use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::Expression;
 use common_streams::SendableDataBlockStream;
 use futures::StreamExt;
 
+use crate::datasources::index::BloomFilter;
 use crate::datasources::table::fuse::io;
+use crate::datasources::table::fuse::io::ClusterKeySorter;
+use crate::datasources::table::fuse::meta::ClusterStats;
+use crate::datasources::table::fuse::meta::ColumnId;
 use crate::datasources::table::fuse::meta::SegmentInfo;
 use crate::datasources::table::fuse::meta::Stats;
 use crate::datasources::table::fuse::statistics;
@@ -37,27 +43,83 @@ use crate::datasources::table::fuse::statistics::StatisticsAccumulator;
 pub struct BlockAppender;
 
 impl BlockAppender {
+    /// For table with cluster key, the blocks are buffered and sorted in batches of (about)
+    /// `sort_batch_bytes`, each batch is written as soon as it is full.
     // TODO should return a stream of SegmentInfo (batch blocks into segments)
     pub async fn append_blocks(
         data_accessor: Arc<dyn DataAccessor>,
         mut stream: SendableDataBlockStream,
-        data_schema: &DataSchema,
+        data_schema: &DataSchemaRef,
+        cluster_keys: &[Expression],
+        bloom_filter_columns: &[ColumnId],
+        location_prefix: &str,
+        sort_batch_bytes: usize,
     ) -> Result<Option<SegmentInfo>> {
         let mut stats_acc = StatisticsAccumulator::new();
         let mut block_meta_acc = BlockMetaAccumulator::new();
 
         let mut block_nums = 0;
-        // accumulate the stats and save the blocks
-        while let Some(block) = stream.next().await {
-            let block = block?;
-            if block.num_rows() != 0 {
-                stats_acc.acc(&block)?;
-                let schema = block.schema().to_arrow();
-                let location = io::gen_block_location();
-                let file_size = Self::save_block(&schema, block, &data_accessor, &location).await?;
-                block_meta_acc.acc(file_size, location, &mut stats_acc);
-                block_nums += 1;
+        if cluster_keys.is_empty() {
+            // accumulate the stats and save the blocks
+            while let Some(block) = stream.next().await {
+                let block = block?;
+                if block.num_rows() != 0 {
+                    Self::append_block(
+                        &data_accessor,
+                        block,
+                        None,
+                        bloom_filter_columns,
+                        location_prefix,
+                        &mut stats_acc,
+                        &mut block_meta_acc,
+                    )
+                    .await?;
+                    block_nums += 1;
+                }
             }
+        } else {
+            // sorts the rows of each batch by the cluster key before splitting them into blocks, so
+            // that the key ranges of these blocks do not overlap, and the min/max of the key columns
+            // are as tight as possible. blocks of different batches may overlap with each other,
+            // which are reclustered by OPTIMIZE TABLE
+            let sorter = ClusterKeySorter::try_create(data_schema, cluster_keys)?;
+            // the buffered blocks are not pulled by the sources, so the memory limit is checked here
+            let memory_tracker = MemoryTracker::current();
+            let mut batch = vec![];
+            let mut batch_bytes = 0;
+            while let Some(block) = stream.next().await {
+                let block = block?;
+                batch_bytes += block.memory_size();
+                batch.push(block);
+                if let Some(tracker) = &memory_tracker {
+                    tracker.check_limit()?;
+                }
+
+                if batch_bytes >= sort_batch_bytes {
+                    block_nums += Self::append_sorted_blocks(
+                        &data_accessor,
+                        &sorter,
+                        &batch,
+                        bloom_filter_columns,
+                        location_prefix,
+                        &mut stats_acc,
+                        &mut block_meta_acc,
+                    )
+                    .await?;
+                    batch.clear();
+                    batch_bytes = 0;
+                }
+            }
+            block_nums += Self::append_sorted_blocks(
+                &data_accessor,
+                &sorter,
+                &batch,
+                bloom_filter_columns,
+                location_prefix,
+                &mut stats_acc,
+                &mut block_meta_acc,
+            )
+            .await?;
         }
 
         let segment = if block_nums > 0 {
@@ -65,16 +127,13 @@ impl BlockAppender {
             // we need to send back a stream of segment latter
             let block_metas = block_meta_acc.blocks_metas;
             let summary = statistics::reduce_block_stats(&stats_acc.blocks_stats, data_schema)?;
-            Some(SegmentInfo {
-                blocks: block_metas,
-                summary: Stats {
-                    row_count: stats_acc.summary_row_count,
-                    block_count: stats_acc.summary_block_count,
-                    uncompressed_byte_size: stats_acc.in_memory_size,
-                    compressed_byte_size: stats_acc.file_size,
-                    col_stats: summary,
-                },
-            })
+            Some(SegmentInfo::new(block_metas, Stats {
+                row_count: stats_acc.summary_row_count,
+                block_count: stats_acc.summary_block_count,
+                uncompressed_byte_size: stats_acc.in_memory_size,
+                compressed_byte_size: stats_acc.file_size,
+                col_stats: summary,
+            }))
         } else {
             None
         };
@@ -82,6 +141,60 @@ impl BlockAppender {
         Ok(segment)
     }
 
+    /// Sorts the blocks by the cluster key as a whole and saves them, returns the number of blocks
+    async fn append_sorted_blocks(
+        data_accessor: &Arc<dyn DataAccessor>,
+        sorter: &ClusterKeySorter,
+        blocks: &[DataBlock],
+        bloom_filter_columns: &[ColumnId],
+        location_prefix: &str,
+        stats_acc: &mut StatisticsAccumulator,
+        block_meta_acc: &mut BlockMetaAccumulator,
+    ) -> Result<usize> {
+        let sorted = sorter.sort(blocks)?;
+        let block_nums = sorted.len();
+        for (block, cluster_stats) in sorted {
+            Self::append_block(
+                data_accessor,
+                block,
+                Some(cluster_stats),
+                bloom_filter_columns,
+                location_prefix,
+                stats_acc,
+                block_meta_acc,
+            )
+            .await?;
+        }
+        Ok(block_nums)
+    }
+
+    async fn append_block(
+        data_accessor: &Arc<dyn DataAccessor>,
+        block: DataBlock,
+        cluster_stats: Option<ClusterStats>,
+        bloom_filter_columns: &[ColumnId],
+        location_prefix: &str,
+        stats_acc: &mut StatisticsAccumulator,
+        block_meta_acc: &mut BlockMetaAccumulator,
+    ) -> Result<()> {
+        stats_acc.last_block_cluster_stats = cluster_stats;
+        for column_id in bloom_filter_columns {
+            let filter = BloomFilter::from_column(
+                block.column(*column_id as usize),
+                io::BLOOM_FILTER_FALSE_POSITIVE_RATE,
+            )?;
+            stats_acc
+                .last_block_bloom_filters
+                .insert(*column_id, filter);
+        }
+        stats_acc.acc(&block)?;
+        let schema = block.schema().to_arrow();
+        let location = io::gen_block_location(location_prefix);
+        let file_size = Self::save_block(&schema, block, data_accessor, &location).await?;
+        block_meta_acc.acc(file_size, location, stats_acc);
+        Ok(())
+    }
+
     pub(super) async fn save_block(
         arrow_schema: &ArrowSchema,
         block: DataBlock,
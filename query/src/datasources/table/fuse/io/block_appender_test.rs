@@ -22,9 +22,14 @@ use common_datavalues::series::Series;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::col;
 use tempfile::TempDir;
 
 use crate::datasources::table::fuse::io::BlockAppender;
+use crate::datasources::table::fuse::meta::ClusterStats;
 
 #[tokio::test]
 async fn test_fuse_table_block_appender() {
@@ -35,13 +40,154 @@ async fn test_fuse_table_block_appender() {
     let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
     let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![1, 2, 3])]);
     let block_stream = futures::stream::iter(vec![Ok(block)]);
-    let r = BlockAppender::append_blocks(local_fs.clone(), Box::pin(block_stream), schema.as_ref())
-        .await;
+    let r = BlockAppender::append_blocks(
+        local_fs.clone(),
+        Box::pin(block_stream),
+        &schema,
+        &[],
+        &[],
+        "",
+        usize::MAX,
+    )
+    .await;
     assert!(r.is_ok());
 
     // non blocks
     let block_stream = futures::stream::iter(vec![]);
-    let r = BlockAppender::append_blocks(local_fs, Box::pin(block_stream), schema.as_ref()).await;
+    let r = BlockAppender::append_blocks(
+        local_fs,
+        Box::pin(block_stream),
+        &schema,
+        &[],
+        &[],
+        "",
+        usize::MAX,
+    )
+    .await;
     assert!(r.is_ok());
     assert!(r.unwrap().is_none())
 }
+
+#[tokio::test]
+async fn test_fuse_table_block_appender_cluster_key() -> Result<()> {
+    let tmp_dir = TempDir::new().unwrap();
+    let local_fs = common_dal::Local::with_path(tmp_dir.path().to_owned());
+    let local_fs = Arc::new(local_fs);
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, false),
+        DataField::new("b", DataType::Int32, false),
+    ]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![2, 1, 2, 1]),
+        Series::new(vec![3, 4, 1, 2]),
+    ]);
+    let block_stream = Box::pin(futures::stream::iter(vec![Ok(block)]));
+    let cluster_keys = vec![col("a"), col("b")];
+    let segment = BlockAppender::append_blocks(
+        local_fs,
+        block_stream,
+        &schema,
+        &cluster_keys,
+        &[],
+        "",
+        usize::MAX,
+    )
+    .await?
+    .unwrap();
+
+    // the block is sorted by (a, b), thus the min/max of the cluster key are the first/last rows
+    let block_meta = &segment.blocks[0];
+    assert_eq!(
+        block_meta.cluster_stats,
+        Some(ClusterStats {
+            min: vec![DataValue::Int32(Some(1)), DataValue::Int32(Some(2))],
+            max: vec![DataValue::Int32(Some(2)), DataValue::Int32(Some(3))],
+        })
+    );
+    let col_stats = block_meta.col_stats.get(&1).unwrap();
+    assert_eq!(col_stats.min, DataValue::Int32(Some(1)));
+    assert_eq!(col_stats.max, DataValue::Int32(Some(4)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_block_appender_cluster_key_batch() -> Result<()> {
+    let tmp_dir = TempDir::new().unwrap();
+    let local_fs = common_dal::Local::with_path(tmp_dir.path().to_owned());
+    let local_fs = Arc::new(local_fs);
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
+    let cluster_key_ranges = |sort_batch_bytes: usize| {
+        let blocks = vec![
+            Ok(DataBlock::create_by_array(schema.clone(), vec![
+                Series::new(vec![3, 1, 2]),
+            ])),
+            Ok(DataBlock::create_by_array(schema.clone(), vec![
+                Series::new(vec![4, 2, 5]),
+            ])),
+        ];
+        let block_stream = Box::pin(futures::stream::iter(blocks));
+        let local_fs = local_fs.clone();
+        let schema = schema.clone();
+        async move {
+            let segment = BlockAppender::append_blocks(
+                local_fs,
+                block_stream,
+                &schema,
+                &[col("a")],
+                &[],
+                "",
+                sort_batch_bytes,
+            )
+            .await?
+            .unwrap();
+            Ok::<_, ErrorCode>(
+                segment
+                    .blocks
+                    .iter()
+                    .map(|block_meta| block_meta.cluster_stats.clone().unwrap())
+                    .collect::<Vec<_>>(),
+            )
+        }
+    };
+    let range = |min: i32, max: i32| ClusterStats {
+        min: vec![DataValue::Int32(Some(min))],
+        max: vec![DataValue::Int32(Some(max))],
+    };
+
+    // rows of all the blocks are sorted as a whole, the key ranges of the blocks do not overlap
+    let ranges = cluster_key_ranges(usize::MAX).await?;
+    assert_eq!(ranges, vec![range(1, 2), range(3, 5)]);
+
+    // each block fills a batch, and is sorted on its own
+    let ranges = cluster_key_ranges(1).await?;
+    assert_eq!(ranges, vec![range(1, 3), range(2, 5)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_block_appender_bloom_filter() -> Result<()> {
+    let tmp_dir = TempDir::new().unwrap();
+    let local_fs = common_dal::Local::with_path(tmp_dir.path().to_owned());
+    let local_fs = Arc::new(local_fs);
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![1, 2, 3]),
+        Series::new(vec!["x", "y", "z"]),
+    ]);
+    let block_stream = Box::pin(futures::stream::iter(vec![Ok(block)]));
+    let segment =
+        BlockAppender::append_blocks(local_fs, block_stream, &schema, &[], &[1], "", usize::MAX)
+            .await?
+            .unwrap();
+
+    // only the bloom filters of the specified columns are built
+    let bloom_filters = &segment.blocks[0].bloom_filters;
+    assert_eq!(bloom_filters.len(), 1);
+    let filter = bloom_filters.get(&1).unwrap();
+    assert!(filter.find(&DataValue::String(Some("y".as_bytes().to_vec()))));
+    assert!(!filter.find(&DataValue::String(Some("w".as_bytes().to_vec()))));
+    Ok(())
+}
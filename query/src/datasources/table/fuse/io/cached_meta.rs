@@ -0,0 +1,146 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Cursor;
+use std::io::SeekFrom;
+use std::sync::Arc;
+
+use common_arrow::arrow::io::parquet::read::read_metadata;
+use common_arrow::arrow::io::parquet::read::schema::FileMetaData;
+use common_dal::DataAccessor;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::AsyncReadExt;
+use futures::AsyncSeekExt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::datasources::table::fuse::cache::CacheSize;
+use crate::datasources::table::fuse::cache::TableCache;
+use crate::datasources::table::fuse::io::decode_meta;
+use crate::datasources::table::fuse::io::encode_meta;
+use crate::datasources::table::fuse::io::meta_format_version;
+use crate::datasources::table::fuse::io::read_meta;
+use crate::datasources::table::fuse::io::MetaCompression;
+
+// the length of the metadata (u32, little endian) and the magic, at the end of parquet files
+const PARQUET_TAIL_LEN: usize = 8;
+const PARQUET_MAGIC: &[u8] = b"PAR1";
+
+/// Reads the meta object (snapshot or segment) of the given location, through the cache if any.
+///
+/// Objects missing from the memory are looked up in the disk tier, before reading the storage.
+pub async fn read_cached_meta<T: DeserializeOwned + CacheSize>(
+    da: Arc<dyn DataAccessor>,
+    location: impl AsRef<str>,
+    cache: &Option<Arc<TableCache<T>>>,
+) -> Result<Arc<T>> {
+    let location = location.as_ref();
+    let cache = match cache {
+        None => return Ok(Arc::new(read_meta(da, location).await?)),
+        Some(cache) => cache,
+    };
+    if let Some(meta) = cache.get(location) {
+        return Ok(meta);
+    }
+
+    let version = meta_format_version(location);
+    let meta = match cache.get_encoded(location).await {
+        Some(bytes) => decode_meta(&bytes, version)?,
+        None => {
+            let bytes = da.read(location).await?;
+            let meta = decode_meta(&bytes, version)?;
+            cache.put_encoded(location, bytes).await;
+            meta
+        }
+    };
+    let meta = Arc::new(meta);
+    cache.put(location.to_owned(), meta.clone());
+    Ok(meta)
+}
+
+/// Writes the meta object to the given location, and puts it in the cache if any
+pub async fn write_cached_meta<T: Serialize + CacheSize>(
+    da: Arc<dyn DataAccessor>,
+    location: &str,
+    meta: T,
+    cache: &Option<Arc<TableCache<T>>>,
+) -> Result<()> {
+    let bytes = encode_meta(&meta, meta_format_version(location), MetaCompression::Lz4)?;
+    da.put(location, bytes.clone()).await?;
+    if let Some(cache) = cache {
+        cache.put_encoded(location, bytes).await;
+        cache.put(location.to_owned(), Arc::new(meta));
+    }
+    Ok(())
+}
+
+/// Reads the parquet metadata (footer) of the given block, through the cache if any
+pub async fn read_cached_block_meta(
+    da: Arc<dyn DataAccessor>,
+    location: &str,
+    cache: &Option<Arc<TableCache<FileMetaData>>>,
+) -> Result<Arc<FileMetaData>> {
+    if let Some(meta) = cache.as_ref().and_then(|c| c.get(location)) {
+        return Ok(meta);
+    }
+
+    let encoded = match cache {
+        Some(cache) => cache.get_encoded(location).await,
+        None => None,
+    };
+    let meta = match encoded {
+        Some(footer) => decode_block_footer(&footer)?,
+        None => {
+            let footer = read_block_footer(da, location).await?;
+            let meta = decode_block_footer(&footer)?;
+            if let Some(cache) = cache {
+                cache.put_encoded(location, footer).await;
+            }
+            meta
+        }
+    };
+    let meta = Arc::new(meta);
+    if let Some(cache) = cache {
+        cache.put(location.to_owned(), meta.clone());
+    }
+    Ok(meta)
+}
+
+/// Reads the footer (the metadata, followed by its length and the magic) of the block, which
+/// could be decoded as a parquet file on its own
+async fn read_block_footer(da: Arc<dyn DataAccessor>, location: &str) -> Result<Vec<u8>> {
+    let mut reader = da.get_input_stream(location, None)?;
+    let mut tail = [0u8; PARQUET_TAIL_LEN];
+    reader
+        .seek(SeekFrom::End(-(PARQUET_TAIL_LEN as i64)))
+        .await?;
+    reader.read_exact(&mut tail).await?;
+    if &tail[4..] != PARQUET_MAGIC {
+        return Err(ErrorCode::ParquetError(format!(
+            "invalid parquet file {}, bad magic",
+            location
+        )));
+    }
+
+    let meta_len = u32::from_le_bytes([tail[0], tail[1], tail[2], tail[3]]) as usize;
+    let mut footer = vec![0u8; meta_len + PARQUET_TAIL_LEN];
+    reader.seek(SeekFrom::End(-(footer.len() as i64))).await?;
+    reader.read_exact(&mut footer).await?;
+    Ok(footer)
+}
+
+fn decode_block_footer(footer: &[u8]) -> Result<FileMetaData> {
+    read_metadata(&mut Cursor::new(footer)).map_err(|e| ErrorCode::ParquetError(e.to_string()))
+}
@@ -0,0 +1,119 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::Result;
+use common_planners::Expression;
+
+use crate::datasources::table::fuse::meta::ClusterStats;
+use crate::pipelines::transforms::ExpressionExecutor;
+
+/// Sorts blocks by the cluster key of table, before they are written
+pub struct ClusterKeySorter {
+    /// Evaluates the expressions of the cluster key, as columns named `_cluster_key_{index}`
+    executor: ExpressionExecutor,
+    sort_columns: Vec<SortColumnDescription>,
+}
+
+impl ClusterKeySorter {
+    pub fn try_create(schema: &DataSchemaRef, cluster_keys: &[Expression]) -> Result<Self> {
+        let mut fields = Vec::with_capacity(cluster_keys.len());
+        let mut exprs = Vec::with_capacity(cluster_keys.len());
+        let mut sort_columns = Vec::with_capacity(cluster_keys.len());
+        for (idx, key) in cluster_keys.iter().enumerate() {
+            let name = format!("_cluster_key_{}", idx);
+            let field = key.to_data_field(schema)?;
+            fields.push(DataField::new(
+                &name,
+                field.data_type().clone(),
+                field.is_nullable(),
+            ));
+            exprs.push(Expression::Alias(name.clone(), Box::new(key.clone())));
+            sort_columns.push(SortColumnDescription {
+                column_name: name,
+                asc: true,
+                nulls_first: true,
+            });
+        }
+
+        let executor = ExpressionExecutor::try_create(
+            "cluster key executor",
+            schema.clone(),
+            DataSchemaRefExt::create(fields),
+            exprs,
+            true,
+        )?;
+        executor.validate()?;
+
+        Ok(Self {
+            executor,
+            sort_columns,
+        })
+    }
+
+    /// Sorts the rows of all the blocks by the cluster key as a whole, and splits them into blocks
+    /// of the same sizes as the given ones, along with the min/max of the key of each block.
+    ///
+    /// Sorting the blocks one by one leaves their key ranges overlapping with each other, which
+    /// prunes nothing more than the unsorted ones.
+    pub fn sort(&self, blocks: &[DataBlock]) -> Result<Vec<(DataBlock, ClusterStats)>> {
+        let blocks = blocks
+            .iter()
+            .filter(|block| !block.is_empty())
+            .collect::<Vec<_>>();
+        if blocks.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let schema = blocks[0].schema().clone();
+        let mut with_keys = Vec::with_capacity(blocks.len());
+        for block in &blocks {
+            let keys = self.executor.execute(block)?;
+            let mut fields = block.schema().fields().clone();
+            fields.extend(keys.schema().fields().iter().cloned());
+            let mut columns = block.columns().to_vec();
+            columns.extend(keys.columns().iter().cloned());
+            with_keys.push(DataBlock::create(DataSchemaRefExt::create(fields), columns));
+        }
+        let sorted = DataBlock::sort_block(
+            &DataBlock::concat_blocks(&with_keys)?,
+            &self.sort_columns,
+            None,
+        )?;
+
+        let num_columns = schema.fields().len();
+        let mut offset = 0;
+        let mut result = Vec::with_capacity(blocks.len());
+        for block in &blocks {
+            let part = DataBlock::slice_block(&sorted, offset, block.num_rows());
+            offset += block.num_rows();
+
+            let last = part.num_rows() - 1;
+            let mut min = Vec::with_capacity(self.sort_columns.len());
+            let mut max = Vec::with_capacity(self.sort_columns.len());
+            for key in &part.columns()[num_columns..] {
+                min.push(key.try_get(0)?);
+                max.push(key.try_get(last)?);
+            }
+
+            let part = DataBlock::create(schema.clone(), part.columns()[..num_columns].to_vec());
+            result.push((part, ClusterStats { min, max }));
+        }
+        Ok(result)
+    }
+}
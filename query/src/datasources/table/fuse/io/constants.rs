@@ -14,6 +14,9 @@
 //
 
 pub const TBL_OPT_KEY_SNAPSHOT_LOC: &str = "SNAPSHOT_LOC";
+pub const TBL_OPT_KEY_CLUSTER_KEY: &str = "CLUSTER_KEY";
+pub const TBL_OPT_KEY_BLOOM_FILTER_COLUMNS: &str = "BLOOM_FILTER_COLUMNS";
+pub const BLOOM_FILTER_FALSE_POSITIVE_RATE: f64 = 0.01;
 pub const FUSE_TBL_BLOCK_PREFIX: &str = "_b";
 pub const FUSE_TBL_SEGMENT_PREFIX: &str = "_sg";
 pub const FUSE_TBL_SNAPSHOT_PREFIX: &str = "_ss";
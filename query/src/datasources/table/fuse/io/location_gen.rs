@@ -18,17 +18,79 @@ use uuid::Uuid;
 use super::constants::FUSE_TBL_BLOCK_PREFIX;
 use super::constants::FUSE_TBL_SEGMENT_PREFIX;
 use super::constants::FUSE_TBL_SNAPSHOT_PREFIX;
+use crate::datasources::table::fuse::meta::FormatVersion;
+use crate::datasources::table::fuse::meta::CURRENT_FORMAT_VERSION;
+use crate::datasources::table::fuse::meta::FORMAT_VERSION_JSON;
 
-pub fn gen_block_location() -> String {
+/// Objects of a table are located under the prefix of it, i.e. `{prefix}/_b/..`, `{prefix}/_sg/..`
+/// and `{prefix}/_ss/..`. Tables written before the objects were prefixed have an empty prefix.
+pub fn gen_block_location(prefix: &str) -> String {
     let part_uuid = Uuid::new_v4().to_simple().to_string() + ".parquet";
-    format!("{}/{}", FUSE_TBL_BLOCK_PREFIX, part_uuid)
+    format!(
+        "{}/{}",
+        object_dir(prefix, FUSE_TBL_BLOCK_PREFIX),
+        part_uuid
+    )
 }
 
-pub fn gen_segment_info_location() -> String {
+pub fn gen_segment_info_location(prefix: &str) -> String {
     let segment_uuid = Uuid::new_v4().to_simple().to_string();
-    format!("{}/{}", FUSE_TBL_SEGMENT_PREFIX, segment_uuid)
+    versioned_location(
+        &object_dir(prefix, FUSE_TBL_SEGMENT_PREFIX),
+        &segment_uuid,
+        CURRENT_FORMAT_VERSION,
+    )
 }
 
-pub fn snapshot_location(name: impl AsRef<str>) -> String {
-    format!("{}/{}", FUSE_TBL_SNAPSHOT_PREFIX, name.as_ref())
+pub fn snapshot_location(prefix: &str, name: impl AsRef<str>, version: FormatVersion) -> String {
+    versioned_location(
+        &object_dir(prefix, FUSE_TBL_SNAPSHOT_PREFIX),
+        name.as_ref(),
+        version,
+    )
+}
+
+/// The directories of the blocks, segments and snapshots of the table with the given prefix
+pub fn object_dirs(prefix: &str) -> [String; 3] {
+    [
+        object_dir(prefix, FUSE_TBL_BLOCK_PREFIX),
+        object_dir(prefix, FUSE_TBL_SEGMENT_PREFIX),
+        object_dir(prefix, FUSE_TBL_SNAPSHOT_PREFIX),
+    ]
+}
+
+/// Extracts the prefix of the table from the location of a snapshot of it, e.g. `1/_ss/a1b2` => `1`
+pub fn location_prefix(snapshot_location: &str) -> &str {
+    let dir = snapshot_location
+        .rsplit_once('/')
+        .map_or("", |(dir, _)| dir);
+    dir.strip_suffix(FUSE_TBL_SNAPSHOT_PREFIX)
+        .unwrap_or(dir)
+        .trim_end_matches('/')
+}
+
+/// Extracts the format version of the meta object (snapshot or segment) at `location`.
+///
+/// Objects of the JSON format are located without any suffix, the others are suffixed by `_v{version}`.
+pub fn meta_format_version(location: &str) -> FormatVersion {
+    let name = location.rsplit('/').next().unwrap_or(location);
+    name.rsplit_once("_v")
+        .and_then(|(_, version)| version.parse().ok())
+        .unwrap_or(FORMAT_VERSION_JSON)
+}
+
+fn object_dir(prefix: &str, dir: &str) -> String {
+    if prefix.is_empty() {
+        dir.to_string()
+    } else {
+        format!("{}/{}", prefix, dir)
+    }
+}
+
+fn versioned_location(dir: &str, name: &str, version: FormatVersion) -> String {
+    if version == FORMAT_VERSION_JSON {
+        format!("{}/{}", dir, name)
+    } else {
+        format!("{}/{}_v{}", dir, name, version)
+    }
 }
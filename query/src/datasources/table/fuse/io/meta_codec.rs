@@ -0,0 +1,139 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_dal::DataAccessor;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::datasources::table::fuse::io::meta_format_version;
+use crate::datasources::table::fuse::meta::FormatVersion;
+use crate::datasources::table::fuse::meta::FORMAT_VERSION_BINARY;
+use crate::datasources::table::fuse::meta::FORMAT_VERSION_JSON;
+
+// Layout of the binary format:
+//
+// | magic (4 bytes) | format version (u64, little endian) | compression (1 byte) | CBOR payload |
+const META_MAGIC: &[u8] = b"FUSE";
+const META_HEADER_LEN: usize = META_MAGIC.len() + 8 + 1;
+
+/// Compression of the payload, of meta objects in the binary format
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MetaCompression {
+    None = 0,
+    Lz4 = 1,
+}
+
+impl MetaCompression {
+    fn from_u8(v: u8) -> Result<Self> {
+        match v {
+            0 => Ok(MetaCompression::None),
+            1 => Ok(MetaCompression::Lz4),
+            _ => Err(ErrorCode::BadBytes(format!(
+                "unknown compression {} of meta object",
+                v
+            ))),
+        }
+    }
+}
+
+/// Reads the meta object (snapshot or segment) at `location`, which is decoded according
+/// to the format version of the location
+pub async fn read_meta<T: DeserializeOwned>(
+    da: Arc<dyn DataAccessor>,
+    location: impl AsRef<str>,
+) -> Result<T> {
+    let location = location.as_ref();
+    let bytes = da.read(location).await?;
+    decode_meta(&bytes, meta_format_version(location))
+}
+
+/// Writes the meta object (snapshot or segment) to `location`, which is encoded according
+/// to the format version of the location
+pub async fn write_meta<T: Serialize>(
+    da: Arc<dyn DataAccessor>,
+    location: &str,
+    meta: &T,
+) -> Result<()> {
+    let bytes = encode_meta(meta, meta_format_version(location), MetaCompression::Lz4)?;
+    da.put(location, bytes).await
+}
+
+pub fn encode_meta<T: Serialize>(
+    meta: &T,
+    version: FormatVersion,
+    compression: MetaCompression,
+) -> Result<Vec<u8>> {
+    match version {
+        FORMAT_VERSION_JSON => Ok(serde_json::to_vec(meta)?),
+        FORMAT_VERSION_BINARY => {
+            let payload = serde_cbor::to_vec(meta)
+                .map_err_to_code(ErrorCode::BadBytes, || "failed to encode meta object")?;
+            let payload = match compression {
+                MetaCompression::None => payload,
+                MetaCompression::Lz4 => lz4::block::compress(&payload, None, true)?,
+            };
+
+            let mut bytes = Vec::with_capacity(META_HEADER_LEN + payload.len());
+            bytes.extend_from_slice(META_MAGIC);
+            bytes.extend_from_slice(&version.to_le_bytes());
+            bytes.push(compression as u8);
+            bytes.extend_from_slice(&payload);
+            Ok(bytes)
+        }
+        _ => Err(unknown_format_version(version)),
+    }
+}
+
+pub fn decode_meta<T: DeserializeOwned>(bytes: &[u8], version: FormatVersion) -> Result<T> {
+    match version {
+        FORMAT_VERSION_JSON => Ok(serde_json::from_slice(bytes)?),
+        FORMAT_VERSION_BINARY => {
+            if bytes.len() < META_HEADER_LEN || &bytes[..META_MAGIC.len()] != META_MAGIC {
+                return Err(ErrorCode::BadBytes(format!(
+                    "invalid header of meta object, format version {} expected",
+                    version
+                )));
+            }
+
+            let mut header_version = [0u8; 8];
+            header_version.copy_from_slice(&bytes[META_MAGIC.len()..META_HEADER_LEN - 1]);
+            let header_version = FormatVersion::from_le_bytes(header_version);
+            if header_version != version {
+                return Err(ErrorCode::BadBytes(format!(
+                    "format version of meta object mismatch, expected {}, found {}",
+                    version, header_version
+                )));
+            }
+
+            let payload = &bytes[META_HEADER_LEN..];
+            let decoded = match MetaCompression::from_u8(bytes[META_HEADER_LEN - 1])? {
+                MetaCompression::None => serde_cbor::from_slice(payload),
+                MetaCompression::Lz4 => {
+                    serde_cbor::from_slice(&lz4::block::decompress(payload, None)?)
+                }
+            };
+            decoded.map_err_to_code(ErrorCode::BadBytes, || "failed to decode meta object")
+        }
+        _ => Err(unknown_format_version(version)),
+    }
+}
+
+fn unknown_format_version(version: FormatVersion) -> ErrorCode {
+    ErrorCode::UnknownFormatVersion(format!("unknown format version {} of meta object", version))
+}
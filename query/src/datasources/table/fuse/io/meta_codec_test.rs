@@ -0,0 +1,190 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_dal::DataAccessor;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use tempfile::TempDir;
+
+use crate::datasources::table::fuse::io::decode_meta;
+use crate::datasources::table::fuse::io::encode_meta;
+use crate::datasources::table::fuse::io::gen_block_location;
+use crate::datasources::table::fuse::io::gen_segment_info_location;
+use crate::datasources::table::fuse::io::location_prefix;
+use crate::datasources::table::fuse::io::meta_format_version;
+use crate::datasources::table::fuse::io::read_meta;
+use crate::datasources::table::fuse::io::snapshot_location;
+use crate::datasources::table::fuse::io::write_meta;
+use crate::datasources::table::fuse::io::MetaCompression;
+use crate::datasources::table::fuse::meta::BlockLocation;
+use crate::datasources::table::fuse::meta::BlockMeta;
+use crate::datasources::table::fuse::meta::ColStats;
+use crate::datasources::table::fuse::meta::SegmentInfo;
+use crate::datasources::table::fuse::meta::Stats;
+use crate::datasources::table::fuse::meta::CURRENT_FORMAT_VERSION;
+use crate::datasources::table::fuse::meta::FORMAT_VERSION_BINARY;
+use crate::datasources::table::fuse::meta::FORMAT_VERSION_JSON;
+
+fn test_segment() -> SegmentInfo {
+    let col_stats = HashMap::from([(0, ColStats {
+        min: DataValue::Int32(Some(1)),
+        max: DataValue::Int32(Some(3)),
+        null_count: 0,
+        in_memory_size: 12,
+    })]);
+    let block_meta = BlockMeta {
+        row_count: 3,
+        block_size: 12,
+        file_size: 100,
+        col_stats: col_stats.clone(),
+        location: BlockLocation {
+            location: "_b/test.parquet".to_string(),
+            meta_size: 0,
+        },
+        cluster_stats: None,
+        bloom_filters: HashMap::new(),
+    };
+    SegmentInfo::new(vec![block_meta], Stats {
+        row_count: 3,
+        block_count: 1,
+        uncompressed_byte_size: 12,
+        compressed_byte_size: 100,
+        col_stats,
+    })
+}
+
+fn assert_segment_eq(left: &SegmentInfo, right: &SegmentInfo) {
+    assert_eq!(left.format_version, right.format_version);
+    assert_eq!(left.blocks.len(), right.blocks.len());
+    assert_eq!(
+        left.blocks[0].location.location,
+        right.blocks[0].location.location
+    );
+    assert_eq!(left.summary.row_count, right.summary.row_count);
+    assert_eq!(
+        left.summary.col_stats[&0].max,
+        right.summary.col_stats[&0].max
+    );
+}
+
+#[test]
+fn test_fuse_meta_location_version() {
+    assert_eq!(meta_format_version("_ss/a1b2"), FORMAT_VERSION_JSON);
+    assert_eq!(meta_format_version("_sg/a1b2"), FORMAT_VERSION_JSON);
+    assert_eq!(meta_format_version("_ss/a1b2_v1"), FORMAT_VERSION_BINARY);
+    assert_eq!(
+        meta_format_version(&snapshot_location("1", "a1b2", FORMAT_VERSION_BINARY)),
+        FORMAT_VERSION_BINARY
+    );
+    assert_eq!(
+        snapshot_location("", "a1b2", FORMAT_VERSION_JSON),
+        "_ss/a1b2"
+    );
+    assert_eq!(
+        meta_format_version(&gen_segment_info_location("1")),
+        CURRENT_FORMAT_VERSION
+    );
+}
+
+#[test]
+fn test_fuse_meta_location_prefix() {
+    let loc = snapshot_location("1", "a1b2", FORMAT_VERSION_BINARY);
+    assert_eq!(loc, "1/_ss/a1b2_v1");
+    assert_eq!(location_prefix(&loc), "1");
+    assert!(gen_block_location("1").starts_with("1/_b/"));
+    assert!(gen_segment_info_location("1").starts_with("1/_sg/"));
+
+    // legacy locations are not prefixed
+    assert_eq!(location_prefix("_ss/a1b2"), "");
+    assert!(gen_block_location("").starts_with("_b/"));
+}
+
+#[test]
+fn test_fuse_meta_codec() -> Result<()> {
+    let segment = test_segment();
+
+    for compression in [MetaCompression::None, MetaCompression::Lz4] {
+        let bytes = encode_meta(&segment, FORMAT_VERSION_BINARY, compression)?;
+        let decoded: SegmentInfo = decode_meta(&bytes, FORMAT_VERSION_BINARY)?;
+        assert_segment_eq(&segment, &decoded);
+    }
+
+    // the binary format is more compact than JSON
+    let json = encode_meta(&segment, FORMAT_VERSION_JSON, MetaCompression::None)?;
+    let binary = encode_meta(&segment, FORMAT_VERSION_BINARY, MetaCompression::None)?;
+    assert!(binary.len() < json.len());
+
+    // objects of the JSON format can not be decoded as binary ones, and vice versa
+    let r = decode_meta::<SegmentInfo>(&json, FORMAT_VERSION_BINARY);
+    assert_eq!(r.unwrap_err().code(), ErrorCode::BadBytes("").code());
+    let r = decode_meta::<SegmentInfo>(&binary, FORMAT_VERSION_JSON);
+    assert!(r.is_err());
+
+    // unknown versions
+    let r = encode_meta(&segment, 42, MetaCompression::None);
+    assert_eq!(
+        r.unwrap_err().code(),
+        ErrorCode::UnknownFormatVersion("").code()
+    );
+    let r = decode_meta::<SegmentInfo>(&binary, 42);
+    assert_eq!(
+        r.unwrap_err().code(),
+        ErrorCode::UnknownFormatVersion("").code()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_fuse_meta_codec_legacy_json() -> Result<()> {
+    // segments written by earlier versions have no format version
+    let mut value = serde_json::to_value(&test_segment())?;
+    value.as_object_mut().unwrap().remove("format_version");
+    let bytes = serde_json::to_vec(&value)?;
+
+    let decoded: SegmentInfo = decode_meta(&bytes, FORMAT_VERSION_JSON)?;
+    assert_eq!(decoded.format_version, FORMAT_VERSION_JSON);
+    assert_eq!(decoded.blocks.len(), 1);
+    assert_eq!(decoded.summary.row_count, 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_meta_read_write() -> Result<()> {
+    let tmp_dir = TempDir::new().unwrap();
+    let local_fs = common_dal::Local::with_path(tmp_dir.path().to_owned());
+    let da: Arc<dyn DataAccessor> = Arc::new(local_fs);
+    let segment = test_segment();
+
+    // written in the format of the location
+    let loc = gen_segment_info_location("");
+    write_meta(da.clone(), &loc, &segment).await?;
+    let bytes = da.read(&loc).await?;
+    assert!(bytes.starts_with(b"FUSE"));
+    let read: SegmentInfo = read_meta(da.clone(), &loc).await?;
+    assert_segment_eq(&segment, &read);
+
+    // legacy locations are read as JSON
+    let loc = "_sg/legacy".to_string();
+    da.put(&loc, serde_json::to_vec(&segment)?).await?;
+    let read: SegmentInfo = read_meta(da.clone(), &loc).await?;
+    assert_segment_eq(&segment, &read);
+
+    Ok(())
+}
@@ -14,16 +14,36 @@
 //
 
 pub use block_appender::BlockAppender;
+pub use cached_meta::read_cached_block_meta;
+pub use cached_meta::read_cached_meta;
+pub use cached_meta::write_cached_meta;
+pub use cluster_key_sorter::ClusterKeySorter;
 pub use col_encoding::col_encoding;
+pub use constants::BLOOM_FILTER_FALSE_POSITIVE_RATE;
+pub use constants::TBL_OPT_KEY_BLOOM_FILTER_COLUMNS;
+pub use constants::TBL_OPT_KEY_CLUSTER_KEY;
 pub use constants::TBL_OPT_KEY_SNAPSHOT_LOC;
 pub use location_gen::gen_block_location;
 pub use location_gen::gen_segment_info_location;
+pub use location_gen::location_prefix;
+pub use location_gen::meta_format_version;
+pub use location_gen::object_dirs;
 pub use location_gen::snapshot_location;
+pub use meta_codec::decode_meta;
+pub use meta_codec::encode_meta;
+pub use meta_codec::read_meta;
+pub use meta_codec::write_meta;
+pub use meta_codec::MetaCompression;
 
 mod block_appender;
+mod cached_meta;
+mod cluster_key_sorter;
 mod col_encoding;
 mod constants;
 mod location_gen;
+mod meta_codec;
 
 #[cfg(test)]
 mod block_appender_test;
+#[cfg(test)]
+mod meta_codec_test;
@@ -15,6 +15,9 @@
 
 use std::collections::HashMap;
 
+use common_datavalues::DataValue;
+
+use crate::datasources::index::BloomFilter;
 use crate::datasources::table::fuse::meta::ColumnId;
 use crate::datasources::table::fuse::meta::Location;
 use crate::datasources::table::fuse::ColStats;
@@ -25,8 +28,19 @@ pub struct BlockMeta {
     /// Pointer of the data Block
     pub row_count: u64,
     pub block_size: u64,
+    /// Size of the (compressed) file, absent in blocks written by earlier versions
+    #[serde(default)]
+    pub file_size: u64,
     pub col_stats: HashMap<ColumnId, ColStats>,
     pub location: BlockLocation,
+    /// Min/max of the cluster key, absent if the table has no cluster key, or in blocks
+    /// written by earlier versions
+    #[serde(default)]
+    pub cluster_stats: Option<ClusterStats>,
+    /// Bloom filters of the columns specified by the table option `BLOOM_FILTER_COLUMNS`,
+    /// absent in blocks written by earlier versions
+    #[serde(default)]
+    pub bloom_filters: HashMap<ColumnId, BloomFilter>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
@@ -35,3 +49,11 @@ pub struct BlockLocation {
     // for parquet, this filed can be used to fetch the meta data without seeking around
     pub meta_size: u64,
 }
+
+/// Min/max of the cluster key of a block, which is sorted by the cluster key while being written,
+/// i.e. the values of the key of the first row and the last row of the block
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ClusterStats {
+    pub min: Vec<DataValue>,
+    pub max: Vec<DataValue>,
+}
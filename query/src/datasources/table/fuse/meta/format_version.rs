@@ -0,0 +1,25 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Version of the layout, in which the meta objects (snapshots and segments) are persisted
+pub type FormatVersion = u64;
+
+/// Plain JSON, written by the versions which did not record the format version
+pub const FORMAT_VERSION_JSON: FormatVersion = 0;
+
+/// CBOR, behind a binary header, optionally compressed
+pub const FORMAT_VERSION_BINARY: FormatVersion = 1;
+
+/// The format version of the meta objects being written
+pub const CURRENT_FORMAT_VERSION: FormatVersion = FORMAT_VERSION_BINARY;
@@ -15,7 +15,12 @@
 
 pub use block::BlockLocation;
 pub use block::BlockMeta;
+pub use block::ClusterStats;
 pub use column_stats::ColStats;
+pub use format_version::FormatVersion;
+pub use format_version::CURRENT_FORMAT_VERSION;
+pub use format_version::FORMAT_VERSION_BINARY;
+pub use format_version::FORMAT_VERSION_JSON;
 pub use segment::SegmentInfo;
 pub use snapshot::ColumnId;
 pub use snapshot::Location;
@@ -25,5 +30,6 @@ pub use snapshot::TableSnapshot;
 
 mod block;
 mod column_stats;
+mod format_version;
 mod segment;
 mod snapshot;
@@ -14,16 +14,30 @@
 //
 
 use crate::datasources::table::fuse::meta::block::BlockMeta;
+use crate::datasources::table::fuse::meta::FormatVersion;
 use crate::datasources::table::fuse::meta::Stats;
+use crate::datasources::table::fuse::meta::CURRENT_FORMAT_VERSION;
 
 /// A segment comprised of one or more blocks
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 pub struct SegmentInfo {
-    // TODO format_version
-    // pub format_version: u32,
+    /// Format version of this segment, absent (JSON) in segments written by earlier versions
+    #[serde(default)]
+    pub format_version: FormatVersion,
+
     /// blocks belong to this segment
     pub blocks: Vec<BlockMeta>,
 
     /// summary statistics
     pub summary: Stats,
 }
+
+impl SegmentInfo {
+    pub fn new(blocks: Vec<BlockMeta>, summary: Stats) -> Self {
+        Self {
+            format_version: CURRENT_FORMAT_VERSION,
+            blocks,
+            summary,
+        }
+    }
+}
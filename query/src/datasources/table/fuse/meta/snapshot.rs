@@ -14,6 +14,8 @@
 
 use std::collections::HashMap;
 
+use chrono::DateTime;
+use chrono::Utc;
 use common_base::uuid;
 use common_datavalues::DataSchema;
 use serde::Deserialize;
@@ -21,6 +23,7 @@ use serde::Serialize;
 use uuid::Uuid;
 
 use crate::datasources::table::fuse::meta::column_stats::ColStats;
+use crate::datasources::table::fuse::meta::FormatVersion;
 
 pub type SnapshotId = Uuid; // TODO String might be better
 pub type ColumnId = u32;
@@ -28,13 +31,23 @@ pub type Location = String;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TableSnapshot {
-    // TODO format_version
-    // pub format_version: u32,
+    /// Format version of this snapshot, absent (JSON) in snapshots written by earlier versions
+    #[serde(default)]
+    pub format_version: FormatVersion,
+
     /// id of snapshot
     pub snapshot_id: SnapshotId,
 
     pub prev_snapshot_id: Option<SnapshotId>,
 
+    /// Format version of the previous snapshot, which is needed to locate it
+    #[serde(default)]
+    pub prev_snapshot_format_version: FormatVersion,
+
+    /// Commit time of this snapshot, absent in snapshots written by earlier versions
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
+
     /// For each snapshot, we keep a schema for it (in case of schema evolution)
     pub schema: DataSchema,
 
@@ -43,8 +56,8 @@ pub struct TableSnapshot {
 
     /// Pointers to SegmentInfos (may be of different format)
     ///
-    /// We rely on background merge tasks to keep merging segments, so that
-    /// this the size of this vector could be kept reasonable
+    /// Segments (and the small blocks of them) are merged by `OPTIMIZE TABLE`, so that
+    /// the size of this vector could be kept reasonable
     pub segments: Vec<Location>,
 }
 
@@ -13,11 +13,17 @@
 //  limitations under the License.
 //
 
+pub use cache::CacheManager;
+pub use io::TBL_OPT_KEY_BLOOM_FILTER_COLUMNS;
+pub use io::TBL_OPT_KEY_CLUSTER_KEY;
 pub use io::TBL_OPT_KEY_SNAPSHOT_LOC;
 pub use meta::ColStats;
+pub use meta::ColumnId;
 pub use statistics::BlockStats;
+pub use statistics::ClusteringInformation;
 pub use table::FuseTable;
 
+mod cache;
 mod index;
 mod io;
 mod meta;
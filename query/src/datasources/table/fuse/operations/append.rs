@@ -32,15 +32,23 @@ impl FuseTable {
         stream: SendableDataBlockStream,
     ) -> Result<Option<AppendOperationLogEntry>> {
         let da = ctx.get_data_accessor()?;
-        let segment =
-            BlockAppender::append_blocks(da.clone(), stream, self.table_info.schema().as_ref())
-                .await?;
+        let cluster_keys = self.cluster_keys(ctx.clone()).await?;
+        let sort_batch_bytes = ctx.get_settings().get_cluster_key_sort_batch_bytes()? as usize;
+        let segment = BlockAppender::append_blocks(
+            da.clone(),
+            stream,
+            &self.table_info.schema(),
+            &cluster_keys,
+            &self.bloom_filter_columns()?,
+            &self.meta_location_prefix(),
+            sort_batch_bytes,
+        )
+        .await?;
 
         match segment {
             Some(seg) => {
-                let seg_loc = io::gen_segment_info_location();
-                let bytes = serde_json::to_vec(&seg)?;
-                da.put(&seg_loc, bytes).await?;
+                let seg_loc = io::gen_segment_info_location(&self.meta_location_prefix());
+                io::write_meta(da, &seg_loc, &seg).await?;
                 Ok(Some(AppendOperationLogEntry::new(seg_loc, seg)))
             }
             _ => Ok(None),
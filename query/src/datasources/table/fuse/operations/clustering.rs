@@ -0,0 +1,54 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::datasources::table::fuse::io;
+use crate::datasources::table::fuse::statistics;
+use crate::datasources::table::fuse::statistics::ClusteringInformation;
+use crate::datasources::table::fuse::FuseTable;
+use crate::sessions::QueryContext;
+
+impl FuseTable {
+    /// Clustering information of the blocks of the latest snapshot, by which we can tell
+    /// if a recluster is needed
+    pub async fn clustering_information(
+        &self,
+        ctx: Arc<QueryContext>,
+    ) -> Result<ClusteringInformation> {
+        if self.cluster_key_def().is_none() {
+            return Err(ErrorCode::BadArguments(format!(
+                "table {} has no cluster key",
+                self.table_info.name
+            )));
+        }
+
+        let snapshot = match self.table_snapshot(ctx.clone()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(ClusteringInformation::default()),
+        };
+
+        let da = ctx.get_data_accessor()?;
+        let segment_cache = ctx.get_storage_cache_manager().get_segment_info_cache();
+        let mut block_metas = Vec::with_capacity(snapshot.summary.block_count as usize);
+        for loc in &snapshot.segments {
+            let segment = io::read_cached_meta(da.clone(), loc, &segment_cache).await?;
+            block_metas.extend(segment.blocks.iter().cloned());
+        }
+        Ok(statistics::clustering_information(&block_metas))
+    }
+}
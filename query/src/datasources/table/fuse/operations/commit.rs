@@ -13,24 +13,41 @@
 //  limitations under the License.
 //
 
+use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
 
+use chrono::Utc;
+use common_base::tokio::time::sleep;
+use common_dal::DataAccessor;
 use common_datavalues::DataSchema;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::TableIdent;
+use common_meta_types::TableInfo;
 use common_meta_types::UpsertTableOptionReply;
 use common_meta_types::UpsertTableOptionReq;
+use rand::thread_rng;
+use rand::Rng;
 use uuid::Uuid;
 
 use crate::catalogs::Catalog;
 use crate::datasources::table::fuse::io;
+use crate::datasources::table::fuse::meta::Location;
+use crate::datasources::table::fuse::meta::SegmentInfo;
+use crate::datasources::table::fuse::meta::Stats;
 use crate::datasources::table::fuse::meta::TableSnapshot;
+use crate::datasources::table::fuse::meta::CURRENT_FORMAT_VERSION;
 use crate::datasources::table::fuse::operations::TableOperationLog;
 use crate::datasources::table::fuse::statistics;
 use crate::datasources::table::fuse::FuseTable;
 use crate::datasources::table::fuse::TBL_OPT_KEY_SNAPSHOT_LOC;
 use crate::sessions::QueryContext;
 
+const OCC_DEFAULT_BACKOFF_INIT_DELAY_MS: u64 = 5;
+const OCC_DEFAULT_BACKOFF_MAX_DELAY_MS: u64 = 20 * 1000;
+const OCC_DEFAULT_MAX_RETRIES: u32 = 10;
+
 impl FuseTable {
     #[inline]
     pub async fn do_commit(
@@ -38,8 +55,38 @@ impl FuseTable {
         ctx: Arc<QueryContext>,
         operation_log: TableOperationLog,
     ) -> Result<()> {
-        // TODO OCC retry & resolves conflicts if applicable
+        // The version of table is used as a compare-and-swap token while committing to the meta server.
+        // If someone else committed in between, we merge the operations onto the latest snapshot and retry.
+        let mut latest: Option<FuseTable> = None;
+        let mut backoff = CommitBackoff::new();
+
+        loop {
+            let table = latest.as_ref().unwrap_or(self);
+            let r = table.try_commit(ctx.clone(), &operation_log).await;
+            match r {
+                Err(e) if is_version_conflict(&e) => {
+                    if !backoff.wait().await {
+                        return Err(ErrorCode::OCCRetryFailure(format!(
+                            "can not commit to table {} after {} retries, {}",
+                            self.table_info.name,
+                            backoff.retries(),
+                            e.message()
+                        )));
+                    }
+                    latest = Some(FuseTable {
+                        table_info: self.latest_table_info(ctx.clone()).await?,
+                    });
+                }
+                r => return r,
+            }
+        }
+    }
 
+    async fn try_commit(
+        &self,
+        ctx: Arc<QueryContext>,
+        operation_log: &TableOperationLog,
+    ) -> Result<()> {
         let prev = self.table_snapshot(ctx.clone()).await?;
         let new_snapshot = Self::merge_table_operations(
             self.table_info.meta.schema.as_ref(),
@@ -48,19 +95,35 @@ impl FuseTable {
         )?;
 
         let uuid = new_snapshot.snapshot_id;
-        let snapshot_loc = io::snapshot_location(uuid.to_simple().to_string().as_str());
-        let bytes = serde_json::to_vec(&new_snapshot)?;
+        let snapshot_loc = io::snapshot_location(
+            &self.meta_location_prefix(),
+            uuid.to_simple().to_string().as_str(),
+            new_snapshot.format_version,
+        );
         let da = ctx.get_data_accessor()?;
-        da.put(&snapshot_loc, bytes).await?;
+        let cache = ctx.get_storage_cache_manager().get_table_snapshot_cache();
+        io::write_cached_meta(da, &snapshot_loc, new_snapshot, &cache).await?;
 
         self.commit_to_meta_server(ctx, snapshot_loc).await?;
         Ok(())
     }
 
+    pub(crate) async fn latest_table_info(&self, ctx: Arc<QueryContext>) -> Result<TableInfo> {
+        let catalog = ctx.get_catalog();
+        let (ident, meta) = catalog
+            .get_table_meta_by_id(self.table_info.ident.table_id)
+            .await?;
+        Ok(TableInfo {
+            ident,
+            meta: meta.as_ref().clone(),
+            ..self.table_info.clone()
+        })
+    }
+
     fn merge_table_operations(
         schema: &DataSchema,
         prev: Option<TableSnapshot>,
-        ops: TableOperationLog,
+        ops: &TableOperationLog,
     ) -> Result<TableSnapshot> {
         // 1. merge operations(appends, currently) of this ops
         let (mut segs, stats) = statistics::merge_append_operations(schema, ops)?;
@@ -72,6 +135,7 @@ impl FuseTable {
             stats
         };
         let prev_snapshot_id = prev.as_ref().map(|v| v.snapshot_id);
+        let prev_snapshot_format_version = prev.as_ref().map(|v| v.format_version);
 
         // 3. merge segment locations with previous snapshot, if any
         if let Some(TableSnapshot { mut segments, .. }) = prev {
@@ -79,8 +143,11 @@ impl FuseTable {
         };
 
         let new_snapshot = TableSnapshot {
+            format_version: CURRENT_FORMAT_VERSION,
             snapshot_id: Uuid::new_v4(),
             prev_snapshot_id,
+            prev_snapshot_format_version: prev_snapshot_format_version.unwrap_or_default(),
+            timestamp: Some(Utc::now()),
             schema: schema.clone(),
             summary: stats,
             segments: segs,
@@ -88,7 +155,91 @@ impl FuseTable {
         Ok(new_snapshot)
     }
 
-    async fn commit_to_meta_server(
+    /// Commits a snapshot, in which the `replaced` segments are replaced by the given ones (with
+    /// the given summary), and the segments appended concurrently are kept.
+    ///
+    /// The `operation` is aborted if any of the `replaced` segments has been removed concurrently.
+    pub(crate) async fn commit_replacement(
+        &self,
+        ctx: Arc<QueryContext>,
+        da: Arc<dyn DataAccessor>,
+        replaced: &[Location],
+        segments: Vec<Location>,
+        summary: Stats,
+        operation: &str,
+    ) -> Result<()> {
+        let schema = self.table_info.schema();
+        let replaced: HashSet<&Location> = replaced.iter().collect();
+        let mut latest: Option<FuseTable> = None;
+        let mut backoff = CommitBackoff::new();
+
+        loop {
+            let table = latest.as_ref().unwrap_or(self);
+            let conflict = || {
+                ErrorCode::OCCRetryFailure(format!(
+                    "table {} has been modified concurrently, {} aborted",
+                    self.table_info.name, operation
+                ))
+            };
+
+            let prev = table
+                .table_snapshot(ctx.clone())
+                .await?
+                .ok_or_else(conflict)?;
+            let prev_segments: HashSet<&Location> = prev.segments.iter().collect();
+            if !replaced.iter().all(|loc| prev_segments.contains(loc)) {
+                return Err(conflict());
+            }
+
+            let mut new_segments = vec![];
+            let mut new_summary = summary.clone();
+            for loc in prev.segments.iter().filter(|loc| !replaced.contains(loc)) {
+                let segment: SegmentInfo = io::read_meta(da.clone(), loc).await?;
+                new_summary = statistics::merge_stats(&schema, &new_summary, &segment.summary)?;
+                new_segments.push(loc.clone());
+            }
+            new_segments.extend(segments.iter().cloned());
+
+            let new_snapshot = TableSnapshot {
+                format_version: CURRENT_FORMAT_VERSION,
+                snapshot_id: Uuid::new_v4(),
+                prev_snapshot_id: Some(prev.snapshot_id),
+                prev_snapshot_format_version: prev.format_version,
+                timestamp: Some(Utc::now()),
+                schema: schema.as_ref().clone(),
+                summary: new_summary,
+                segments: new_segments,
+            };
+            let snapshot_loc = io::snapshot_location(
+                &table.meta_location_prefix(),
+                new_snapshot.snapshot_id.to_simple().to_string().as_str(),
+                new_snapshot.format_version,
+            );
+            let cache = ctx.get_storage_cache_manager().get_table_snapshot_cache();
+            io::write_cached_meta(da.clone(), &snapshot_loc, new_snapshot, &cache).await?;
+
+            let r = table.commit_to_meta_server(ctx.clone(), snapshot_loc).await;
+            match r {
+                Err(e) if is_version_conflict(&e) => {
+                    if !backoff.wait().await {
+                        return Err(ErrorCode::OCCRetryFailure(format!(
+                            "can not commit {} to table {} after {} retries, {}",
+                            operation,
+                            self.table_info.name,
+                            backoff.retries(),
+                            e.message()
+                        )));
+                    }
+                    latest = Some(FuseTable {
+                        table_info: self.latest_table_info(ctx.clone()).await?,
+                    });
+                }
+                r => return r.map(|_| ()),
+            }
+        }
+    }
+
+    pub(crate) async fn commit_to_meta_server(
         &self,
         ctx: Arc<QueryContext>,
         new_snapshot_location: String,
@@ -108,3 +259,42 @@ impl FuseTable {
             .await
     }
 }
+
+pub(crate) fn is_version_conflict(e: &ErrorCode) -> bool {
+    e.code() == ErrorCode::TableVersionMissMatch("").code()
+}
+
+/// Exponential backoff with jitter, for retrying commits which conflict with others
+pub(crate) struct CommitBackoff {
+    retries: u32,
+    delay_ms: u64,
+}
+
+impl CommitBackoff {
+    pub fn new() -> Self {
+        Self {
+            retries: 0,
+            delay_ms: OCC_DEFAULT_BACKOFF_INIT_DELAY_MS,
+        }
+    }
+
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Sleeps before the next retry, returns false if no more retries are allowed
+    pub async fn wait(&mut self) -> bool {
+        if self.retries >= OCC_DEFAULT_MAX_RETRIES {
+            return false;
+        }
+
+        let delay_ms = {
+            let mut rng = thread_rng();
+            rng.gen_range(self.delay_ms / 2..=self.delay_ms)
+        };
+        sleep(Duration::from_millis(delay_ms)).await;
+        self.delay_ms = std::cmp::min(self.delay_ms * 2, OCC_DEFAULT_BACKOFF_MAX_DELAY_MS);
+        self.retries += 1;
+        true
+    }
+}
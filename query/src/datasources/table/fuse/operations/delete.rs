@@ -0,0 +1,80 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::DeletePlan;
+
+use crate::datasources::table::fuse::meta::Stats;
+use crate::datasources::table::fuse::operations::mutation::BlockMutator;
+use crate::datasources::table::fuse::operations::mutation::Selection;
+use crate::datasources::table::fuse::FuseTable;
+use crate::sessions::QueryContext;
+
+/// Deletes the selected rows of blocks
+struct DeleteMutator {
+    selection: Selection,
+}
+
+impl BlockMutator for DeleteMutator {
+    fn mutate(&self, block: &DataBlock) -> Result<Option<DataBlock>> {
+        let (selected, selected_rows) = self.selection.eval(block)?;
+        match selected_rows {
+            0 => Ok(None),
+            n if n == block.num_rows() => {
+                Ok(Some(DataBlock::empty_with_schema(block.schema().clone())))
+            }
+            _ => {
+                let kept: DFBooleanArray = selected.into_no_null_iter().map(|v| !v).collect();
+                Ok(Some(DataBlock::filter_block(block, kept.into_series())?))
+            }
+        }
+    }
+}
+
+impl FuseTable {
+    #[inline]
+    pub async fn do_delete(&self, ctx: Arc<QueryContext>, delete_plan: DeletePlan) -> Result<()> {
+        match &delete_plan.selection {
+            Some(selection) => {
+                let mutator = DeleteMutator {
+                    selection: Selection::try_create(&self.table_info.schema(), selection)?,
+                };
+                self.mutate_blocks(ctx, Some(selection), &mutator, "deletion")
+                    .await
+            }
+            None => {
+                // everything is deleted, except for the segments appended concurrently
+                let snapshot = match self.table_snapshot(ctx.clone()).await? {
+                    Some(snapshot) if !snapshot.segments.is_empty() => snapshot,
+                    _ => return Ok(()),
+                };
+                let da = ctx.get_data_accessor()?;
+                self.commit_replacement(
+                    ctx,
+                    da,
+                    &snapshot.segments,
+                    vec![],
+                    Stats::default(),
+                    "deletion",
+                )
+                .await
+            }
+        }
+    }
+}
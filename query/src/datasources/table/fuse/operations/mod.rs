@@ -17,11 +17,18 @@ pub use operation_log::AppendOperationLogEntry;
 pub use operation_log::TableOperationLog;
 
 mod append;
+mod clustering;
 mod commit;
+mod delete;
+mod mutation;
+mod navigate;
 mod operation_log;
+mod optimize;
 mod read;
 mod read_plan;
 mod truncate;
+mod update;
+mod vacuum;
 
 #[cfg(test)]
 mod read_plan_test;
@@ -0,0 +1,222 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_dal::DataAccessor;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::Expression;
+use common_streams::ParquetSource;
+use common_streams::Source;
+
+use crate::datasources::index::BloomFilterPruner;
+use crate::datasources::index::RangeFilter;
+use crate::datasources::table::fuse::io;
+use crate::datasources::table::fuse::io::BlockAppender;
+use crate::datasources::table::fuse::meta::BlockMeta;
+use crate::datasources::table::fuse::meta::SegmentInfo;
+use crate::datasources::table::fuse::meta::Stats;
+use crate::datasources::table::fuse::statistics;
+use crate::datasources::table::fuse::statistics::BlockStats;
+use crate::datasources::table::fuse::FuseTable;
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::QueryContext;
+
+/// Mutates the rows of blocks, e.g. deletes or updates the rows which satisfy a selection
+pub trait BlockMutator: Send + Sync {
+    /// Returns the mutated block (which is empty if all the rows are deleted), or None if
+    /// none of the rows of the block are touched
+    fn mutate(&self, block: &DataBlock) -> Result<Option<DataBlock>>;
+}
+
+/// Evaluates the selection (the WHERE clause) of a mutation against blocks
+pub struct Selection {
+    executor: ExpressionExecutor,
+}
+
+impl Selection {
+    pub fn try_create(schema: &DataSchemaRef, selection: &Expression) -> Result<Self> {
+        let expr_field = selection.to_data_field(schema)?;
+        let expr_schema = DataSchemaRefExt::create(vec![expr_field]);
+        let executor = ExpressionExecutor::try_create(
+            "mutation selection executor",
+            schema.clone(),
+            expr_schema,
+            vec![selection.clone()],
+            false,
+        )?;
+        executor.validate()?;
+        Ok(Self { executor })
+    }
+
+    /// Returns the mask of the selected rows, and the number of them.
+    ///
+    /// Rows that the selection evaluates to NULL are not selected.
+    pub fn eval(&self, block: &DataBlock) -> Result<(DFBooleanArray, usize)> {
+        let selected = self.executor.execute(block)?.column(0).to_array()?;
+        let selected = selected.cast_with_type(&DataType::Boolean)?;
+        let mask: DFBooleanArray = selected
+            .bool()?
+            .into_iter()
+            .map(|v| v.unwrap_or(false))
+            .collect();
+        let selected_rows = mask.into_no_null_iter().filter(|v| *v).count();
+        Ok((mask, selected_rows))
+    }
+}
+
+impl FuseTable {
+    /// Applies the mutator to the blocks which may contain the rows satisfying the selection,
+    /// and commits the segments that have been modified.
+    ///
+    /// Segments and blocks are pruned by the min/max of columns (and blocks by the bloom filters
+    /// of columns as well), if the selection is given.
+    pub(crate) async fn mutate_blocks(
+        &self,
+        ctx: Arc<QueryContext>,
+        selection: Option<&Expression>,
+        mutator: &dyn BlockMutator,
+        operation: &str,
+    ) -> Result<()> {
+        let snapshot = match self.table_snapshot(ctx.clone()).await? {
+            Some(snapshot) if !snapshot.segments.is_empty() => snapshot,
+            _ => return Ok(()),
+        };
+
+        let da = ctx.get_data_accessor()?;
+        let cluster_keys = self.cluster_keys(ctx.clone()).await?;
+        let schema = self.table_info.schema();
+        let range_filter = match selection {
+            Some(expr) => Some(RangeFilter::try_create(expr, schema.clone())?),
+            None => None,
+        };
+        let may_match = |stats: &BlockStats| match &range_filter {
+            Some(filter) => filter.eval(stats),
+            None => Ok(true),
+        };
+        let bloom_pruner = selection.and_then(|expr| BloomFilterPruner::try_create(expr, &schema));
+
+        let mut replaced = vec![];
+        let mut segments = vec![];
+        let mut summary = Stats::default();
+        for seg_loc in &snapshot.segments {
+            let segment: SegmentInfo = io::read_meta(da.clone(), seg_loc).await?;
+            if !may_match(&segment.summary.col_stats)? {
+                continue;
+            }
+
+            let mut modified = false;
+            let mut block_metas = Vec::with_capacity(segment.blocks.len());
+            for block_meta in segment.blocks {
+                if !may_match(&block_meta.col_stats)?
+                    || !bloom_pruner
+                        .as_ref()
+                        .map_or(true, |p| p.may_match(&block_meta.bloom_filters))
+                {
+                    block_metas.push(block_meta);
+                    continue;
+                }
+
+                let block = self.read_block(da.clone(), &block_meta).await?;
+                match mutator.mutate(&block)? {
+                    None => block_metas.push(block_meta),
+                    Some(block) => {
+                        modified = true;
+                        if let Some(new_block_meta) =
+                            self.write_block(da.clone(), block, &cluster_keys).await?
+                        {
+                            block_metas.push(new_block_meta);
+                        }
+                    }
+                }
+            }
+
+            if !modified {
+                continue;
+            }
+
+            replaced.push(seg_loc.clone());
+            if block_metas.is_empty() {
+                continue;
+            }
+
+            // re-calculate the statistics of the modified segment, the summary of the snapshot
+            // is merged from the segments while committing
+            let new_summary = statistics::reduce_block_metas(&block_metas, schema.as_ref())?;
+            let new_segment = SegmentInfo::new(block_metas, new_summary);
+            summary = statistics::merge_stats(&schema, &summary, &new_segment.summary)?;
+            let new_seg_loc = io::gen_segment_info_location(&self.meta_location_prefix());
+            io::write_meta(da.clone(), &new_seg_loc, &new_segment).await?;
+            segments.push(new_seg_loc);
+        }
+
+        if replaced.is_empty() {
+            // nothing is touched
+            return Ok(());
+        }
+
+        self.commit_replacement(ctx, da, &replaced, segments, summary, operation)
+            .await
+    }
+
+    async fn read_block(
+        &self,
+        da: Arc<dyn DataAccessor>,
+        block_meta: &BlockMeta,
+    ) -> Result<DataBlock> {
+        let arrow_schema = self.table_info.schema().to_arrow();
+        let table_schema = Arc::new(DataSchema::from(arrow_schema));
+        let projection = (0..table_schema.fields().len()).collect::<Vec<usize>>();
+
+        let mut source = ParquetSource::new(
+            da,
+            block_meta.location.location.clone(),
+            table_schema,
+            projection,
+        );
+        let mut blocks = vec![];
+        while let Some(block) = source.read().await? {
+            blocks.push(block);
+        }
+        DataBlock::concat_blocks(&blocks)
+    }
+
+    /// Writes the block (if not empty), the statistics of it are calculated by the appender,
+    /// which sorts the block by the cluster key, and builds the bloom filters as well
+    async fn write_block(
+        &self,
+        da: Arc<dyn DataAccessor>,
+        block: DataBlock,
+        cluster_keys: &[Expression],
+    ) -> Result<Option<BlockMeta>> {
+        let arrow_schema = self.table_info.schema().to_arrow();
+        let table_schema = Arc::new(DataSchema::from(arrow_schema));
+        let stream = futures::stream::iter(vec![Ok(block)]);
+        let segment = BlockAppender::append_blocks(
+            da,
+            Box::pin(stream),
+            &table_schema,
+            cluster_keys,
+            &self.bloom_filter_columns()?,
+            &self.meta_location_prefix(),
+            // the block is in memory already, sort it as a whole
+            usize::MAX,
+        )
+        .await?;
+        Ok(segment.and_then(|mut seg| seg.blocks.pop()))
+    }
+}
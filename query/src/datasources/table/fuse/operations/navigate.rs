@@ -0,0 +1,124 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_dal::DataAccessor;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::TimeTravelPoint;
+use uuid::Uuid;
+
+use crate::catalogs::Table;
+use crate::datasources::table::fuse::io;
+use crate::datasources::table::fuse::meta::TableSnapshot;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::TBL_OPT_KEY_SNAPSHOT_LOC;
+use crate::sessions::QueryContext;
+
+impl FuseTable {
+    #[inline]
+    pub async fn do_navigate_to(
+        &self,
+        ctx: Arc<QueryContext>,
+        point: &TimeTravelPoint,
+    ) -> Result<Arc<dyn Table>> {
+        let latest = self.table_snapshot(ctx.clone()).await?.ok_or_else(|| {
+            ErrorCode::TableHistoricalDataNotFound(format!(
+                "table {} has no snapshot to travel to",
+                self.table_info.name
+            ))
+        })?;
+
+        let da = ctx.get_data_accessor()?;
+        let snapshot = match point {
+            TimeTravelPoint::Snapshot(id) => {
+                let snapshot_id = Uuid::parse_str(id).map_err(|e| {
+                    ErrorCode::BadArguments(format!("invalid snapshot id {}, {}", id, e))
+                })?;
+                self.walk_snapshot_chain(da, latest, point, |s| Ok(s.snapshot_id == snapshot_id))
+                    .await?
+            }
+            TimeTravelPoint::Timestamp(secs) => {
+                self.walk_snapshot_chain(da, latest, point, |s| match &s.timestamp {
+                    Some(ts) => Ok(ts.timestamp() <= *secs as i64),
+                    // snapshots written by earlier versions have no commit time
+                    None => Err(ErrorCode::TableHistoricalDataNotFound(format!(
+                        "snapshot {} has no commit timestamp",
+                        s.snapshot_id
+                    ))),
+                })
+                .await?
+            }
+        };
+
+        match snapshot {
+            None => Err(ErrorCode::TableHistoricalDataNotFound(format!(
+                "no snapshot of table {} matches {:?}",
+                self.table_info.name, point
+            ))),
+            Some(snapshot) => {
+                let snapshot_loc = io::snapshot_location(
+                    &self.meta_location_prefix(),
+                    snapshot.snapshot_id.to_simple().to_string().as_str(),
+                    snapshot.format_version,
+                );
+                let mut table_info = self.table_info.clone();
+                table_info.meta.schema = Arc::new(snapshot.schema);
+                table_info
+                    .meta
+                    .options
+                    .insert(TBL_OPT_KEY_SNAPSHOT_LOC.to_string(), snapshot_loc);
+                Ok(Arc::new(FuseTable { table_info }))
+            }
+        }
+    }
+
+    /// Walks the snapshot chain backwards, starting from (and including) `start`,
+    /// returns the first snapshot which satisfies the `pred`
+    async fn walk_snapshot_chain(
+        &self,
+        da: Arc<dyn DataAccessor>,
+        start: TableSnapshot,
+        point: &TimeTravelPoint,
+        pred: impl Fn(&TableSnapshot) -> Result<bool>,
+    ) -> Result<Option<TableSnapshot>> {
+        let mut current = start;
+        loop {
+            if pred(&current)? {
+                return Ok(Some(current));
+            }
+
+            match current.prev_snapshot_id {
+                None => return Ok(None),
+                Some(prev_id) => {
+                    let loc = io::snapshot_location(
+                        &self.meta_location_prefix(),
+                        prev_id.to_simple().to_string().as_str(),
+                        current.prev_snapshot_format_version,
+                    );
+                    // the earlier history may have been removed by VACUUM TABLE
+                    current = io::read_meta(da.clone(), &loc).await.map_err(|e| {
+                        ErrorCode::TableHistoricalDataNotFound(format!(
+                            "no snapshot of table {} matches {:?}, the history before \
+                             snapshot {} is not available, {}",
+                            self.table_info.name, point, current.snapshot_id, e
+                        ))
+                    })?;
+                }
+            }
+        }
+    }
+}
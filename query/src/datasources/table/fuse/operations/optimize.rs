@@ -0,0 +1,207 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use async_stream::try_stream;
+use common_dal::DataAccessor;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::Optimization;
+use common_planners::OptimizeTablePlan;
+use common_streams::ParquetSource;
+use common_streams::Source;
+
+use crate::datasources::table::fuse::io;
+use crate::datasources::table::fuse::io::BlockAppender;
+use crate::datasources::table::fuse::meta::BlockMeta;
+use crate::datasources::table::fuse::meta::SegmentInfo;
+use crate::datasources::table::fuse::statistics;
+use crate::datasources::table::fuse::FuseTable;
+use crate::sessions::QueryContext;
+
+/// Thresholds of compaction, taken from the settings `compact_*_threshold`
+struct CompactThresholds {
+    /// Blocks which have fewer rows than this are considered small, and will be merged
+    block_row_count: u64,
+    /// Blocks which are larger than this (in memory) will not be merged
+    block_size: u64,
+    /// Max number of blocks that a compacted segment contains
+    segment_block_count: usize,
+}
+
+impl CompactThresholds {
+    fn try_create(ctx: &QueryContext) -> Result<Self> {
+        let settings = ctx.get_settings();
+        let segment_block_count = settings.get_compact_segment_block_count_threshold()?;
+        if segment_block_count == 0 {
+            return Err(ErrorCode::BadArguments(
+                "compact_segment_block_count_threshold must be greater than 0",
+            ));
+        }
+        Ok(CompactThresholds {
+            block_row_count: settings.get_compact_block_row_count_threshold()?,
+            block_size: settings.get_compact_block_size_threshold()?,
+            segment_block_count: segment_block_count as usize,
+        })
+    }
+
+    fn is_small_block(&self, block_meta: &BlockMeta) -> bool {
+        block_meta.row_count < self.block_row_count && block_meta.block_size < self.block_size
+    }
+}
+
+impl FuseTable {
+    #[inline]
+    pub async fn do_optimize(
+        &self,
+        ctx: Arc<QueryContext>,
+        optimize_plan: OptimizeTablePlan,
+    ) -> Result<()> {
+        let snapshot = match self.table_snapshot(ctx.clone()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(()),
+        };
+
+        let thresholds = CompactThresholds::try_create(&ctx)?;
+        let da = ctx.get_data_accessor()?;
+        let mut block_metas = Vec::with_capacity(snapshot.summary.block_count as usize);
+        for loc in &snapshot.segments {
+            let segment: SegmentInfo = io::read_meta(da.clone(), loc).await?;
+            block_metas.extend(segment.blocks);
+        }
+
+        // groups of blocks to be merged: the small blocks, and for table with cluster key, the
+        // ones of which the key ranges overlap with each other as well (i.e. recluster)
+        let cluster_keys = self.cluster_keys(ctx.clone()).await?;
+        let groups = match optimize_plan.operation {
+            Optimization::Compact if cluster_keys.is_empty() => {
+                let (small_blocks, others): (Vec<_>, Vec<_>) = block_metas
+                    .into_iter()
+                    .partition(|meta| thresholds.is_small_block(meta));
+                let mut groups = vec![small_blocks];
+                groups.extend(others.into_iter().map(|meta| vec![meta]));
+                groups
+            }
+            Optimization::Compact => {
+                statistics::reclustering_groups(block_metas, |meta| thresholds.is_small_block(meta))
+            }
+            Optimization::Segment => block_metas.into_iter().map(|meta| vec![meta]).collect(),
+        };
+        // blocks of the groups of a single block are left untouched
+        let (groups, singles): (Vec<_>, Vec<_>) =
+            groups.into_iter().partition(|group| group.len() > 1);
+        let mut block_metas = singles.into_iter().flatten().collect::<Vec<_>>();
+
+        let num_of_blocks = block_metas.len() + groups.iter().map(|g| g.len()).sum::<usize>();
+        let segment_block_count = thresholds.segment_block_count;
+        let num_of_segments = (num_of_blocks + segment_block_count - 1) / segment_block_count;
+        if groups.is_empty() && snapshot.segments.len() <= num_of_segments {
+            // nothing to compact
+            return Ok(());
+        }
+
+        // 1. merge each group of blocks into new ones, which are sorted by the cluster key (if any)
+        //    as a whole, or in batches of `cluster_key_sort_batch_bytes` if the group is larger
+        let sort_batch_bytes = ctx.get_settings().get_cluster_key_sort_batch_bytes()? as usize;
+        for group in groups {
+            let merged = self
+                .merge_blocks(
+                    da.clone(),
+                    group,
+                    &cluster_keys,
+                    thresholds.block_row_count,
+                    sort_batch_bytes,
+                )
+                .await?;
+            block_metas.extend(merged);
+        }
+
+        // 2. re-organize all the blocks into new segments
+        let schema = self.table_info.schema();
+        let mut segments = Vec::with_capacity(num_of_segments);
+        for blocks in block_metas.chunks(thresholds.segment_block_count) {
+            let segment = SegmentInfo::new(
+                blocks.to_vec(),
+                statistics::reduce_block_metas(blocks, schema.as_ref())?,
+            );
+            let seg_loc = io::gen_segment_info_location(&self.meta_location_prefix());
+            io::write_meta(da.clone(), &seg_loc, &segment).await?;
+            segments.push(seg_loc);
+        }
+
+        // 3. commit the compacted segments
+        let summary = statistics::reduce_block_metas(&block_metas, schema.as_ref())?;
+        self.commit_replacement(ctx, da, &snapshot.segments, segments, summary, "compaction")
+            .await
+    }
+
+    async fn merge_blocks(
+        &self,
+        da: Arc<dyn DataAccessor>,
+        block_metas: Vec<BlockMeta>,
+        cluster_keys: &[Expression],
+        block_row_count: u64,
+        sort_batch_bytes: usize,
+    ) -> Result<Vec<BlockMeta>> {
+        let arrow_schema = self.table_info.schema().to_arrow();
+        let table_schema = Arc::new(DataSchema::from(arrow_schema));
+        let projection = (0..table_schema.fields().len()).collect::<Vec<usize>>();
+
+        let da_clone = da.clone();
+        let schema = table_schema.clone();
+        let stream = try_stream! {
+            let mut buffer = vec![];
+            let mut buffered_rows = 0;
+            for block_meta in block_metas {
+                let mut source = ParquetSource::new(
+                    da_clone.clone(),
+                    block_meta.location.location,
+                    schema.clone(),
+                    projection.clone(),
+                );
+                while let Some(block) = source.read().await? {
+                    buffered_rows += block.num_rows() as u64;
+                    buffer.push(block);
+                }
+
+                if buffered_rows >= block_row_count {
+                    yield DataBlock::concat_blocks(&buffer)?;
+                    buffer.clear();
+                    buffered_rows = 0;
+                }
+            }
+
+            if !buffer.is_empty() {
+                yield DataBlock::concat_blocks(&buffer)?;
+            }
+        };
+
+        let segment = BlockAppender::append_blocks(
+            da,
+            Box::pin(stream),
+            &table_schema,
+            cluster_keys,
+            &self.bloom_filter_columns()?,
+            &self.meta_location_prefix(),
+            sort_batch_bytes,
+        )
+        .await?;
+        Ok(segment.map(|seg| seg.blocks).unwrap_or_default())
+    }
+}
@@ -24,6 +24,7 @@ use common_streams::SendableDataBlockStream;
 use common_streams::Source;
 use futures::StreamExt;
 
+use crate::datasources::table::fuse::io;
 use crate::datasources::table::fuse::FuseTable;
 use crate::sessions::QueryContext;
 
@@ -67,15 +68,26 @@ impl FuseTable {
         let arrow_schema = self.table_info.schema().to_arrow();
         let table_schema = Arc::new(DataSchema::from(arrow_schema));
 
+        let block_meta_cache = ctx.get_storage_cache_manager().get_block_meta_cache();
+
         let mut iter = futures::stream::iter(iter);
         let stream = stream! {
             while let Some(part) = iter.next().await {
+                let metadata = io::read_cached_block_meta(da.clone(), &part.name, &block_meta_cache);
+                let metadata = match metadata.await {
+                    Ok(metadata) => metadata,
+                    Err(e) => {
+                        yield(Err(e));
+                        continue;
+                    }
+                };
                 let mut source = ParquetSource::new(
                     da.clone(),
                     part.name.clone(),
                     table_schema.clone(),
                     projection.clone(),
-                );
+                )
+                .with_metadata(metadata);
                 loop {
                     let block = source.read().await;
                     match block {
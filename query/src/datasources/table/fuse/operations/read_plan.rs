@@ -13,18 +13,23 @@
 //  limitations under the License.
 //
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::Arc;
 
-use common_dal::read_obj;
+use common_datavalues::DataSchema;
+use common_datavalues::DataValue;
 use common_exception::Result;
+use common_planners::ColumnStatistics;
 use common_planners::Extras;
 use common_planners::Part;
 use common_planners::Partitions;
 use common_planners::Statistics;
 
 use crate::datasources::table::fuse::index;
+use crate::datasources::table::fuse::io;
 use crate::datasources::table::fuse::meta::BlockMeta;
+use crate::datasources::table::fuse::statistics;
 use crate::datasources::table::fuse::FuseTable;
 use crate::sessions::QueryContext;
 
@@ -39,10 +44,16 @@ impl FuseTable {
         if let Some(loc) = location {
             let da = ctx.get_data_accessor()?;
             let schema = self.table_info.schema();
+            let table_schema = schema.clone();
             let push_downs_c = push_downs.clone();
-            let snapshot = read_obj(da.clone(), loc).await?;
-            let block_metas = index::range_filter(&snapshot, schema, push_downs_c, da).await?;
-            let (statistics, parts) = Self::to_partitions(&block_metas, push_downs);
+            let cache_manager = ctx.get_storage_cache_manager();
+            let snapshot_cache = cache_manager.get_table_snapshot_cache();
+            let snapshot = io::read_cached_meta(da.clone(), &loc, &snapshot_cache).await?;
+            let segment_cache = cache_manager.get_segment_info_cache();
+            let block_metas =
+                index::range_filter(&snapshot, schema, push_downs_c, da, segment_cache).await?;
+            let (mut statistics, parts) = Self::to_partitions(&block_metas, push_downs);
+            statistics.column_statistics = Self::to_column_statistics(&block_metas, &table_schema)?;
             Ok((statistics, parts))
         } else {
             Ok((Statistics::default(), vec![]))
@@ -81,4 +92,47 @@ impl FuseTable {
             },
         )
     }
+
+    /// Merges the column statistics of the blocks read, keyed by the column name.
+    pub(crate) fn to_column_statistics(
+        blocks_metas: &[BlockMeta],
+        schema: &DataSchema,
+    ) -> Result<HashMap<String, ColumnStatistics>> {
+        let blocks_stats = blocks_metas
+            .iter()
+            .map(|block_meta| &block_meta.col_stats)
+            .collect::<Vec<_>>();
+        let rows = blocks_metas
+            .iter()
+            .map(|block_meta| block_meta.row_count)
+            .sum::<u64>();
+
+        let col_stats = statistics::reduce_block_stats(&blocks_stats, schema)?;
+        Ok(col_stats
+            .into_iter()
+            .map(|(column_id, col_stats)| {
+                let values = rows.saturating_sub(col_stats.null_count);
+                let distinct_count =
+                    Self::estimate_distinct_count(&col_stats.min, &col_stats.max, values);
+                let name = schema.field(column_id as usize).name().clone();
+                (name, ColumnStatistics {
+                    min: col_stats.min,
+                    max: col_stats.max,
+                    null_count: col_stats.null_count,
+                    distinct_count,
+                })
+            })
+            .collect())
+    }
+
+    // The blocks keep no sketches of the distinct values, the distinct count of the
+    // integers is bounded by their range, and it is unknown for the others.
+    fn estimate_distinct_count(min: &DataValue, max: &DataValue, values: u64) -> Option<u64> {
+        match (min.as_i64(), max.as_i64()) {
+            (Ok(min), Ok(max)) if max >= min => max
+                .checked_sub(min)
+                .map(|range| (range as u64).saturating_add(1).min(values)),
+            _ => None,
+        }
+    }
 }
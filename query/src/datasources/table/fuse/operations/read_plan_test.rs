@@ -48,11 +48,14 @@ fn test_to_partitions() -> Result<()> {
             .iter()
             .map(|(_, col_stats)| col_stats.in_memory_size)
             .sum(),
+        file_size: 0,
         col_stats: cols_stats.clone(),
         location: BlockLocation {
             location: "".to_string(),
             meta_size: 0,
         },
+        cluster_stats: None,
+        bloom_filters: HashMap::new(),
     };
 
     let blocks_metas = (0..num_of_block)
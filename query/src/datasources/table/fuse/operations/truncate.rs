@@ -15,6 +15,7 @@
 
 use std::sync::Arc;
 
+use chrono::Utc;
 use common_exception::Result;
 use common_meta_types::UpsertTableOptionReq;
 use common_planners::TruncateTablePlan;
@@ -22,6 +23,7 @@ use uuid::Uuid;
 
 use crate::catalogs::Catalog;
 use crate::datasources::table::fuse::io;
+use crate::datasources::table::fuse::meta::CURRENT_FORMAT_VERSION;
 use crate::datasources::table::fuse::FuseTable;
 use crate::datasources::table::fuse::TBL_OPT_KEY_SNAPSHOT_LOC;
 use crate::sessions::QueryContext;
@@ -35,16 +37,23 @@ impl FuseTable {
     ) -> Result<()> {
         if let Some(prev_snapshot) = self.table_snapshot(ctx.clone()).await? {
             let prev_id = prev_snapshot.snapshot_id;
+            let prev_format_version = prev_snapshot.format_version;
             let mut new_snapshot = prev_snapshot;
             new_snapshot.segments = vec![];
+            new_snapshot.format_version = CURRENT_FORMAT_VERSION;
             new_snapshot.prev_snapshot_id = Some(prev_id);
+            new_snapshot.prev_snapshot_format_version = prev_format_version;
             new_snapshot.summary = Default::default();
             new_snapshot.snapshot_id = Uuid::new_v4();
-            let new_snapshot_loc =
-                io::snapshot_location(new_snapshot.snapshot_id.to_simple().to_string().as_str()); // TODO refine this
+            new_snapshot.timestamp = Some(Utc::now());
+            let new_snapshot_loc = io::snapshot_location(
+                &self.meta_location_prefix(),
+                new_snapshot.snapshot_id.to_simple().to_string().as_str(),
+                new_snapshot.format_version,
+            ); // TODO refine this
             let da = ctx.get_data_accessor()?;
-            let bytes = serde_json::to_vec(&new_snapshot)?;
-            da.put(&new_snapshot_loc, bytes).await?;
+            let cache = ctx.get_storage_cache_manager().get_table_snapshot_cache();
+            io::write_cached_meta(da, &new_snapshot_loc, new_snapshot, &cache).await?;
 
             let catalog = ctx.get_catalog();
             // TODO backoff retry
@@ -0,0 +1,114 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::UpdatePlan;
+
+use crate::datasources::table::fuse::operations::mutation::BlockMutator;
+use crate::datasources::table::fuse::operations::mutation::Selection;
+use crate::datasources::table::fuse::FuseTable;
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::QueryContext;
+
+/// Updates the selected rows (or all the rows, if no selection) of blocks
+struct UpdateMutator {
+    selection: Option<Selection>,
+    /// Evaluates the new values of the updated columns, which are named after the columns
+    executor: ExpressionExecutor,
+}
+
+impl UpdateMutator {
+    fn try_create(
+        schema: &DataSchemaRef,
+        assignments: &[(String, Expression)],
+        selection: Option<&Expression>,
+    ) -> Result<Self> {
+        let selection = match selection {
+            Some(expr) => Some(Selection::try_create(schema, expr)?),
+            None => None,
+        };
+
+        let mut fields = Vec::with_capacity(assignments.len());
+        let mut exprs = Vec::with_capacity(assignments.len());
+        for (column, expr) in assignments {
+            fields.push(schema.field_with_name(column)?.clone());
+            exprs.push(Expression::Alias(column.clone(), Box::new(expr.clone())));
+        }
+        let executor = ExpressionExecutor::try_create(
+            "update assignments executor",
+            schema.clone(),
+            DataSchemaRefExt::create(fields),
+            exprs,
+            true,
+        )?;
+        executor.validate()?;
+
+        Ok(Self {
+            selection,
+            executor,
+        })
+    }
+}
+
+impl BlockMutator for UpdateMutator {
+    fn mutate(&self, block: &DataBlock) -> Result<Option<DataBlock>> {
+        let selected = match &self.selection {
+            None => None,
+            Some(selection) => match selection.eval(block)? {
+                (_, 0) => return Ok(None),
+                (_, n) if n == block.num_rows() => None,
+                (mask, _) => Some(mask.into_series()),
+            },
+        };
+
+        let values = self.executor.execute(block)?;
+        let mut columns = Vec::with_capacity(block.num_columns());
+        for (idx, field) in block.schema().fields().iter().enumerate() {
+            let column = match values.try_column_by_name(field.name()) {
+                Err(_) => block.column(idx).clone(),
+                Ok(value) => match &selected {
+                    // all the rows are updated
+                    None => DataColumn::Array(value.to_array()?),
+                    Some(mask) => {
+                        let value = value.to_array()?;
+                        let origin = block.column(idx).to_array()?;
+                        DataColumn::Array(value.if_then_else(&origin, mask)?)
+                    }
+                },
+            };
+            columns.push(column);
+        }
+
+        Ok(Some(DataBlock::create(block.schema().clone(), columns)))
+    }
+}
+
+impl FuseTable {
+    #[inline]
+    pub async fn do_update(&self, ctx: Arc<QueryContext>, update_plan: UpdatePlan) -> Result<()> {
+        let selection = update_plan.selection.as_ref();
+        let mutator = UpdateMutator::try_create(
+            &self.table_info.schema(),
+            &update_plan.assignments,
+            selection,
+        )?;
+        self.mutate_blocks(ctx, selection, &mutator, "update").await
+    }
+}
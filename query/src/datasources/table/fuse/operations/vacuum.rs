@@ -0,0 +1,190 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::Duration;
+use chrono::Utc;
+use common_exception::Result;
+use common_planners::VacuumTablePlan;
+use common_tracing::tracing;
+
+use crate::datasources::table::fuse::io;
+use crate::datasources::table::fuse::meta::Location;
+use crate::datasources::table::fuse::meta::SegmentInfo;
+use crate::datasources::table::fuse::meta::TableSnapshot;
+use crate::datasources::table::fuse::FuseTable;
+use crate::sessions::QueryContext;
+
+/// Objects referenced by none of the snapshots may be written by a statement in progress, e.g. a long
+/// running INSERT or OPTIMIZE, which has not committed yet. They are kept for at least this long,
+/// whatever the retention period is.
+const ORPHAN_MIN_AGE_HOURS: u64 = 24;
+
+impl FuseTable {
+    #[inline]
+    pub async fn do_vacuum(
+        &self,
+        ctx: Arc<QueryContext>,
+        vacuum_plan: VacuumTablePlan,
+    ) -> Result<Vec<Location>> {
+        let latest = match self.table_snapshot(ctx.clone()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(vec![]),
+        };
+
+        // 1. walk the snapshot chain backwards, and split it into the retained part and the expired part.
+        //
+        // Besides the ones committed within the retention period, the snapshot which is the latest one
+        // at the beginning of the retention period is retained as well, so that time travel to any point
+        // of the retention period still works. The latest snapshot is always retained.
+        let da = ctx.get_data_accessor()?;
+        let prefix = self.meta_location_prefix();
+        let boundary = Utc::now() - Duration::hours(vacuum_plan.retention_hours as i64);
+        let mut retained: Vec<TableSnapshot> = vec![];
+        let mut expired: Vec<TableSnapshot> = vec![];
+        let mut boundary_reached = false;
+        let mut next = Some(latest);
+        while let Some(snapshot) = next {
+            let prev_loc = snapshot.prev_snapshot_id.map(|id| {
+                io::snapshot_location(
+                    &prefix,
+                    id.to_simple().to_string().as_str(),
+                    snapshot.prev_snapshot_format_version,
+                )
+            });
+
+            if boundary_reached {
+                expired.push(snapshot);
+            } else {
+                // snapshots written by earlier versions have no commit time, take them as out of the period
+                boundary_reached = !matches!(snapshot.timestamp, Some(ts) if ts > boundary);
+                retained.push(snapshot);
+            }
+
+            next = match prev_loc {
+                None => None,
+                Some(loc) => match io::read_meta(da.clone(), &loc).await {
+                    Ok(prev) => Some(prev),
+                    // the history may have been vacuumed already, and this is where it ends. it is
+                    // safe to stop here anyway, since only the snapshots walked through are expired
+                    Err(e) => {
+                        tracing::debug!("history of table ends at {}, {}", loc, e);
+                        None
+                    }
+                },
+            };
+        }
+
+        // 2. collect the segments and blocks, which are reachable from the retained snapshots
+        let mut known_snapshots: HashSet<Location> = HashSet::new();
+        let mut reachable_segments: HashSet<Location> = HashSet::new();
+        let mut reachable_blocks: HashSet<Location> = HashSet::new();
+        for snapshot in &retained {
+            known_snapshots.insert(io::snapshot_location(
+                &prefix,
+                snapshot.snapshot_id.to_simple().to_string().as_str(),
+                snapshot.format_version,
+            ));
+            for seg_loc in &snapshot.segments {
+                if reachable_segments.insert(seg_loc.clone()) {
+                    let segment: SegmentInfo = io::read_meta(da.clone(), seg_loc).await?;
+                    for block_meta in segment.blocks {
+                        reachable_blocks.insert(block_meta.location.location);
+                    }
+                }
+            }
+        }
+
+        // 3. collect the garbage of each expired snapshot (from the latest to the oldest), i.e. the
+        //    objects which are referenced by it, but not by any of the later snapshots
+        let mut garbage: Vec<Vec<Location>> = Vec::with_capacity(expired.len());
+        for snapshot in &expired {
+            let mut files = vec![];
+            for seg_loc in &snapshot.segments {
+                if !reachable_segments.insert(seg_loc.clone()) {
+                    continue;
+                }
+
+                match io::read_meta::<SegmentInfo>(da.clone(), seg_loc).await {
+                    Ok(segment) => {
+                        for block_meta in segment.blocks {
+                            let block_loc = block_meta.location.location;
+                            if reachable_blocks.insert(block_loc.clone()) {
+                                files.push(block_loc);
+                            }
+                        }
+                    }
+                    // removed by an interrupted vacuum, after the blocks of it
+                    Err(e) => tracing::debug!("segment {} of table not found, {}", seg_loc, e),
+                }
+                files.push(seg_loc.clone());
+            }
+            let snapshot_loc = io::snapshot_location(
+                &prefix,
+                snapshot.snapshot_id.to_simple().to_string().as_str(),
+                snapshot.format_version,
+            );
+            known_snapshots.insert(snapshot_loc.clone());
+            files.push(snapshot_loc);
+            garbage.push(files);
+        }
+
+        // 4. collect the orphans, i.e. the objects which are referenced by none of the snapshots
+        //    walked through, e.g. the snapshots written by the failed attempts of committing, the
+        //    segments and blocks of the aborted appends. the recent ones may belong to the commits
+        //    in progress, only those older than both the retention period and the minimum age of
+        //    orphans are collected.
+        let orphan_age = vacuum_plan.retention_hours.max(ORPHAN_MIN_AGE_HOURS);
+        let orphan_boundary = Utc::now() - Duration::hours(orphan_age as i64);
+        let mut orphans = vec![];
+        if prefix.is_empty() {
+            // objects of the tables written before they were prefixed are mixed up together
+            tracing::debug!(
+                "orphans of table {} are not collected",
+                self.table_info.name
+            );
+        } else {
+            for dir in io::object_dirs(&prefix) {
+                for object in da.list(&dir).await? {
+                    let known = known_snapshots.contains(&object.path)
+                        || reachable_segments.contains(&object.path)
+                        || reachable_blocks.contains(&object.path);
+                    if !known && object.last_modified < orphan_boundary {
+                        orphans.push(object.path);
+                    }
+                }
+            }
+        }
+
+        // 5. remove the orphans, then the garbage from the oldest snapshot to the latest one, and
+        //    for each snapshot, blocks first, then segments, the snapshot itself at last, so that
+        //    if the vacuum is interrupted, the remaining history is still a well-formed chain
+        let files = orphans
+            .into_iter()
+            .chain(garbage.into_iter().rev().flatten())
+            .collect::<Vec<_>>();
+        if !vacuum_plan.dry_run {
+            let cache_manager = ctx.get_storage_cache_manager();
+            for file in &files {
+                da.remove(file).await?;
+                cache_manager.evict(file).await;
+            }
+        }
+
+        Ok(files)
+    }
+}
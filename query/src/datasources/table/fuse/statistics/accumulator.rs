@@ -17,7 +17,9 @@ use std::collections::HashMap;
 
 use common_datablocks::DataBlock;
 
+use crate::datasources::index::BloomFilter;
 use crate::datasources::table::fuse::meta::BlockMeta;
+use crate::datasources::table::fuse::meta::ClusterStats;
 use crate::datasources::table::fuse::meta::ColStats;
 use crate::datasources::table::fuse::meta::ColumnId;
 use crate::datasources::table::fuse::statistics::util;
@@ -36,6 +38,8 @@ pub struct StatisticsAccumulator {
     pub last_block_rows: u64,
     pub last_block_size: u64,
     pub last_block_col_stats: Option<HashMap<ColumnId, ColStats>>,
+    pub last_block_cluster_stats: Option<ClusterStats>,
+    pub last_block_bloom_filters: HashMap<ColumnId, BloomFilter>,
 }
 
 impl StatisticsAccumulator {
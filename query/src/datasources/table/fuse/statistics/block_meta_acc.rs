@@ -38,7 +38,10 @@ impl BlockMetaAccumulator {
             },
             row_count: stats.last_block_rows,
             block_size: stats.last_block_size,
+            file_size,
             col_stats: stats.last_block_col_stats.take().unwrap_or_default(),
+            cluster_stats: stats.last_block_cluster_stats.take(),
+            bloom_filters: std::mem::take(&mut stats.last_block_bloom_filters),
         };
         self.blocks_metas.push(block_meta);
     }
@@ -0,0 +1,207 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+
+use common_datavalues::DataValue;
+
+use crate::datasources::table::fuse::meta::BlockMeta;
+use crate::datasources::table::fuse::meta::ClusterStats;
+
+/// Clustering information of table, which tells how well the blocks are clustered by the cluster key.
+///
+/// The key range of a block is the min/max of the cluster key of it. Blocks overlap with each other
+/// if their key ranges intersect, and the depth of a point is the number of blocks whose key ranges
+/// cover it. The smaller the overlaps and the depth are, the more blocks could be pruned.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClusteringInformation {
+    pub total_block_count: u64,
+    /// Blocks of which the values of the cluster key are all the same
+    pub constant_block_count: u64,
+    /// Blocks without the min/max of the cluster key, e.g. the ones written by earlier versions,
+    /// which are not taken into account while calculating the overlaps and the depth
+    pub unclustered_block_count: u64,
+    /// Average number of the other blocks, which a block overlaps with
+    pub average_overlaps: f64,
+    /// Average depth of the boundaries (min or max of the cluster key) of blocks
+    pub average_depth: f64,
+    pub max_depth: u64,
+}
+
+pub fn clustering_information(block_metas: &[BlockMeta]) -> ClusteringInformation {
+    let ranges = block_metas
+        .iter()
+        .filter_map(|block_meta| block_meta.cluster_stats.as_ref())
+        .collect::<Vec<_>>();
+
+    let mut info = ClusteringInformation {
+        total_block_count: block_metas.len() as u64,
+        unclustered_block_count: (block_metas.len() - ranges.len()) as u64,
+        ..Default::default()
+    };
+    if ranges.is_empty() {
+        return info;
+    }
+
+    let mut mins = ranges.iter().map(|r| r.min.as_slice()).collect::<Vec<_>>();
+    let mut maxs = ranges.iter().map(|r| r.max.as_slice()).collect::<Vec<_>>();
+    mins.sort_by(|l, r| compare_keys(l, r));
+    maxs.sort_by(|l, r| compare_keys(l, r));
+
+    // number of the keys which are less than (or equal to, if `inclusive`) the given one
+    let count = |sorted: &[&[DataValue]], key: &[DataValue], inclusive: bool| {
+        sorted.partition_point(|v| match compare_keys(v, key) {
+            Ordering::Less => true,
+            Ordering::Equal => inclusive,
+            Ordering::Greater => false,
+        })
+    };
+
+    // a block overlaps with all the others, except the ones end before it begins, and the ones
+    // begin after it ends
+    let n = ranges.len();
+    let mut overlaps = 0;
+    for range in &ranges {
+        let end_before = count(&maxs, &range.min, false);
+        let begin_after = n - count(&mins, &range.max, true);
+        overlaps += n - 1 - end_before - begin_after;
+        if compare_keys(&range.min, &range.max) == Ordering::Equal {
+            info.constant_block_count += 1;
+        }
+    }
+
+    let mut points = mins.iter().chain(maxs.iter()).collect::<Vec<_>>();
+    points.sort_by(|l, r| compare_keys(l, r));
+    points.dedup_by(|l, r| compare_keys(l, r) == Ordering::Equal);
+    let mut total_depth = 0;
+    for point in &points {
+        let depth = count(&mins, point, true) - count(&maxs, point, false);
+        total_depth += depth;
+        info.max_depth = info.max_depth.max(depth as u64);
+    }
+
+    info.average_overlaps = overlaps as f64 / n as f64;
+    info.average_depth = total_depth as f64 / points.len() as f64;
+    info
+}
+
+/// Splits the blocks into the groups to be merged (and sorted as a whole) while compacting a table
+/// with cluster key.
+///
+/// Blocks are walked in the order of the min of their key ranges, a block joins the current group
+/// if its key range overlaps with the ones of the group, or both of them are small. Key ranges of
+/// the groups do not overlap with each other, so do the blocks merged from each of them. Blocks
+/// without the min/max of the cluster key are merged as a group of their own.
+pub fn reclustering_groups(
+    block_metas: Vec<BlockMeta>,
+    is_small_block: impl Fn(&BlockMeta) -> bool,
+) -> Vec<Vec<BlockMeta>> {
+    let mut clustered = vec![];
+    let mut unclustered = vec![];
+    for block_meta in block_metas {
+        match block_meta.cluster_stats.clone() {
+            Some(range) => clustered.push((range, block_meta)),
+            None => unclustered.push(block_meta),
+        }
+    }
+    clustered.sort_by(|(l, _), (r, _)| compare_keys(&l.min, &r.min));
+
+    let mut groups = vec![];
+    let mut group: Vec<BlockMeta> = vec![];
+    let mut group_max = vec![];
+    let mut group_small = false;
+    for (ClusterStats { min, max }, block_meta) in clustered {
+        let small = is_small_block(&block_meta);
+        let overlaps = compare_keys(&min, &group_max) == Ordering::Less;
+        if group.is_empty() || !(overlaps || (small && group_small)) {
+            if !group.is_empty() {
+                groups.push(std::mem::take(&mut group));
+            }
+            group_max = max;
+            group_small = small;
+        } else {
+            if compare_keys(&max, &group_max) == Ordering::Greater {
+                group_max = max;
+            }
+            group_small &= small;
+        }
+        group.push(block_meta);
+    }
+    if !group.is_empty() {
+        groups.push(group);
+    }
+    if !unclustered.is_empty() {
+        groups.push(unclustered);
+    }
+    groups
+}
+
+/// Compares the cluster keys lexicographically
+pub fn compare_keys(l: &[DataValue], r: &[DataValue]) -> Ordering {
+    l.iter()
+        .zip(r.iter())
+        .map(|(l, r)| compare_values(l, r))
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or_else(|| l.len().cmp(&r.len()))
+}
+
+/// Compares the values of the cluster key, NULLs are less than the others.
+///
+/// Values which are not comparable (e.g. of containers) are taken as equal.
+fn compare_values(l: &DataValue, r: &DataValue) -> Ordering {
+    match (l.is_null(), r.is_null()) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Less,
+        (false, true) => return Ordering::Greater,
+        (false, false) => {}
+    }
+
+    match (l, r) {
+        (DataValue::Boolean(Some(l)), DataValue::Boolean(Some(r))) => l.cmp(r),
+        (DataValue::String(Some(l)), DataValue::String(Some(r))) => l.cmp(r),
+        _ => match (as_i128(l), as_i128(r)) {
+            (Some(l), Some(r)) => l.cmp(&r),
+            _ => match (as_f64(l), as_f64(r)) {
+                (Some(l), Some(r)) => l.partial_cmp(&r).unwrap_or(Ordering::Equal),
+                _ => Ordering::Equal,
+            },
+        },
+    }
+}
+
+/// Values of the date/time types are kept as their underlying integers, i.e. `Date16` as `UInt16`,
+/// `Date32` as `Int32`, `DateTime32` as `UInt32` and `DateTime64` as `Int64`, and are compared as
+/// such.
+fn as_i128(v: &DataValue) -> Option<i128> {
+    match v {
+        DataValue::Int8(Some(v)) => Some(*v as i128),
+        DataValue::Int16(Some(v)) => Some(*v as i128),
+        DataValue::Int32(Some(v)) => Some(*v as i128),
+        DataValue::Int64(Some(v)) => Some(*v as i128),
+        DataValue::UInt8(Some(v)) => Some(*v as i128),
+        DataValue::UInt16(Some(v)) => Some(*v as i128),
+        DataValue::UInt32(Some(v)) => Some(*v as i128),
+        DataValue::UInt64(Some(v)) => Some(*v as i128),
+        DataValue::Decimal128(Some(v)) => Some(*v),
+        _ => None,
+    }
+}
+
+fn as_f64(v: &DataValue) -> Option<f64> {
+    match v {
+        DataValue::Float32(Some(v)) => Some(*v as f64),
+        DataValue::Float64(Some(v)) => Some(*v),
+        _ => as_i128(v).map(|v| v as f64),
+    }
+}
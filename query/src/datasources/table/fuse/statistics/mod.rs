@@ -15,13 +15,18 @@
 
 mod accumulator;
 mod block_meta_acc;
+mod clustering;
 mod util;
 
 pub use accumulator::BlockStats;
 pub use accumulator::StatisticsAccumulator;
 pub use block_meta_acc::BlockMetaAccumulator;
+pub use clustering::clustering_information;
+pub use clustering::reclustering_groups;
+pub use clustering::ClusteringInformation;
 pub use util::merge_append_operations;
 pub use util::merge_stats;
+pub use util::reduce_block_metas;
 pub use util::reduce_block_stats;
 
 #[cfg(test)]
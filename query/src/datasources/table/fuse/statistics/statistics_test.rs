@@ -13,6 +13,9 @@
 //  limitations under the License.
 //
 
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::SeriesFrom;
 use common_datavalues::series::Series;
@@ -22,8 +25,12 @@ use common_datavalues::DataType;
 use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 
+use crate::datasources::table::fuse::meta::BlockLocation;
+use crate::datasources::table::fuse::meta::BlockMeta;
+use crate::datasources::table::fuse::meta::ClusterStats;
 use crate::datasources::table::fuse::statistics::accumulator;
 use crate::datasources::table::fuse::statistics::block_meta_acc;
+use crate::datasources::table::fuse::statistics::clustering;
 use crate::datasources::table::fuse::statistics::util;
 use crate::datasources::table::fuse::table_test_fixture::TestFixture;
 
@@ -72,3 +79,150 @@ fn test_ft_stats_accumulator() -> common_exception::Result<()> {
     // TODO more cases here pls
     Ok(())
 }
+
+#[test]
+fn test_ft_stats_block_metas_reduce() -> common_exception::Result<()> {
+    let blocks = TestFixture::gen_block_stream(10);
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
+    let mut stats_acc = accumulator::StatisticsAccumulator::new();
+    let mut meta_acc = block_meta_acc::BlockMetaAccumulator::new();
+    blocks.iter().try_for_each(|item| {
+        let item = item.clone().unwrap();
+        stats_acc.acc(&item)?;
+        meta_acc.acc(1, "".to_owned(), &mut stats_acc);
+        Ok::<_, ErrorCode>(())
+    })?;
+
+    let r = util::reduce_block_metas(&meta_acc.blocks_metas, &schema)?;
+    assert_eq!(30, r.row_count);
+    assert_eq!(10, r.block_count);
+    assert_eq!(10, r.compressed_byte_size);
+    assert_eq!(stats_acc.in_memory_size, r.uncompressed_byte_size);
+    let col_stats = r.col_stats.get(&0).unwrap();
+    assert_eq!(col_stats.min, DataValue::Int32(Some(1)));
+    assert_eq!(col_stats.max, DataValue::Int32(Some(3)));
+    Ok(())
+}
+
+#[test]
+fn test_ft_stats_clustering_information() -> common_exception::Result<()> {
+    let block_meta = |range: Option<(i32, i32)>| BlockMeta {
+        row_count: 1,
+        block_size: 1,
+        file_size: 1,
+        col_stats: Default::default(),
+        location: BlockLocation {
+            location: "".to_string(),
+            meta_size: 0,
+        },
+        cluster_stats: range.map(|(min, max)| ClusterStats {
+            min: vec![DataValue::Int32(Some(min))],
+            max: vec![DataValue::Int32(Some(max))],
+        }),
+        bloom_filters: HashMap::new(),
+    };
+
+    // no blocks
+    let info = clustering::clustering_information(&[]);
+    assert_eq!(info, clustering::ClusteringInformation::default());
+
+    // [1, 3] and [2, 5] overlap, [6, 6] is a constant block
+    let block_metas = vec![
+        block_meta(Some((1, 3))),
+        block_meta(Some((2, 5))),
+        block_meta(Some((6, 6))),
+        block_meta(None),
+    ];
+    let info = clustering::clustering_information(&block_metas);
+    assert_eq!(info.total_block_count, 4);
+    assert_eq!(info.constant_block_count, 1);
+    assert_eq!(info.unclustered_block_count, 1);
+    assert!((info.average_overlaps - 2.0 / 3.0).abs() < f64::EPSILON);
+    // depths of the points 1, 2, 3, 5, 6 are 1, 2, 2, 1, 1
+    assert!((info.average_depth - 7.0 / 5.0).abs() < f64::EPSILON);
+    assert_eq!(info.max_depth, 2);
+
+    // keys are compared lexicographically, NULLs first
+    let key = |a: Option<i32>, b: &str| {
+        vec![
+            DataValue::Int32(a),
+            DataValue::String(Some(b.as_bytes().to_vec())),
+        ]
+    };
+    assert_eq!(
+        clustering::compare_keys(&key(Some(1), "b"), &key(Some(2), "a")),
+        Ordering::Less
+    );
+    assert_eq!(
+        clustering::compare_keys(&key(Some(1), "b"), &key(Some(1), "a")),
+        Ordering::Greater
+    );
+    assert_eq!(
+        clustering::compare_keys(&key(None, "b"), &key(Some(1), "a")),
+        Ordering::Less
+    );
+
+    // values of the date/time types are compared as their underlying integers
+    for data_type in [
+        DataType::Date16,
+        DataType::Date32,
+        DataType::DateTime32(None),
+    ] {
+        let dates = Series::new(vec![18000u16, 17000u16]).cast_with_type(&data_type)?;
+        let (later, earlier) = (dates.try_get(0)?, dates.try_get(1)?);
+        assert_eq!(
+            clustering::compare_keys(&[earlier], &[later]),
+            Ordering::Less,
+            "{:?}",
+            data_type
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_ft_stats_reclustering_groups() -> common_exception::Result<()> {
+    let block_meta = |row_count: u64, range: Option<(i32, i32)>| BlockMeta {
+        row_count,
+        block_size: 1,
+        file_size: 1,
+        col_stats: Default::default(),
+        location: BlockLocation {
+            location: format!("{:?}", range),
+            meta_size: 0,
+        },
+        cluster_stats: range.map(|(min, max)| ClusterStats {
+            min: vec![DataValue::Int32(Some(min))],
+            max: vec![DataValue::Int32(Some(max))],
+        }),
+        bloom_filters: HashMap::new(),
+    };
+
+    // [1, 3] and [2, 5] overlap, [5, 6] only touches them, the small [7, 7] and [8, 9] are merged
+    // though they do not overlap, the unclustered block is a group of its own
+    let block_metas = vec![
+        block_meta(10, Some((5, 6))),
+        block_meta(10, Some((2, 5))),
+        block_meta(1, Some((8, 9))),
+        block_meta(10, None),
+        block_meta(10, Some((1, 3))),
+        block_meta(1, Some((7, 7))),
+    ];
+    let groups = clustering::reclustering_groups(block_metas, |meta| meta.row_count < 5);
+    let groups = groups
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .map(|meta| meta.location.location.as_str())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(groups, vec![
+        vec!["Some((1, 3))", "Some((2, 5))"],
+        vec!["Some((5, 6))"],
+        vec!["Some((7, 7))", "Some((8, 9))"],
+        vec!["None"],
+    ]);
+    Ok(())
+}
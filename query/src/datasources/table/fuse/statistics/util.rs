@@ -20,9 +20,12 @@ use std::collections::HashMap;
 use common_datablocks::DataBlock;
 use common_datavalues::columns::DataColumn;
 use common_datavalues::DataSchema;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
+use crate::datasources::table::fuse::meta::BlockMeta;
 use crate::datasources::table::fuse::meta::ColStats;
 use crate::datasources::table::fuse::meta::ColumnId;
 use crate::datasources::table::fuse::meta::Stats;
@@ -37,15 +40,13 @@ pub fn block_stats(data_block: &DataBlock) -> Result<BlockStats> {
         .into_iter()
         .zip(data_block.columns().iter())
         .map(|(idx, col)| {
-            let min = match col {
-                DataColumn::Array(s) => s.min(),
-                DataColumn::Constant(v, _) => Ok(v.clone()),
-            }?;
-
-            let max = match col {
-                DataColumn::Array(s) => s.max(),
-                DataColumn::Constant(v, _) => Ok(v.clone()),
-            }?;
+            let data_type = data_block.schema().field(idx as usize).data_type();
+            let (min, max) = match (col, is_comparable(data_type)) {
+                // nested values have no order, the min/max are left null
+                (_, false) => (DataValue::from(data_type), DataValue::from(data_type)),
+                (DataColumn::Array(s), true) => (s.min()?, s.max()?),
+                (DataColumn::Constant(v, _), true) => (v.clone(), v.clone()),
+            };
 
             let null_count = match col {
                 DataColumn::Array(s) => s.null_count(),
@@ -72,6 +73,13 @@ pub fn block_stats(data_block: &DataBlock) -> Result<BlockStats> {
         .collect()
 }
 
+fn is_comparable(data_type: &DataType) -> bool {
+    !matches!(
+        data_type,
+        DataType::List(_) | DataType::Struct(_) | DataType::Map(_, _)
+    )
+}
+
 pub fn reduce_block_stats<T: Borrow<BlockStats>>(
     stats: &[T],
     schema: &DataSchema,
@@ -117,6 +125,15 @@ pub fn reduce_block_stats<T: Borrow<BlockStats>>(
 
             // TODO panic
             let data_type = schema.field((*id) as usize).data_type();
+            if !is_comparable(data_type) {
+                acc.insert(*id, ColStats {
+                    min: DataValue::from(data_type),
+                    max: DataValue::from(data_type),
+                    null_count,
+                    in_memory_size,
+                });
+                return Ok(acc);
+            }
 
             // TODO
             // for some data types, we shall balance the accuracy and the length
@@ -152,7 +169,7 @@ pub fn merge_stats(schema: &DataSchema, l: &Stats, r: &Stats) -> Result<Stats> {
 
 pub fn merge_append_operations(
     schema: &DataSchema,
-    append_log_entries: Vec<AppendOperationLogEntry>,
+    append_log_entries: &[AppendOperationLogEntry],
 ) -> Result<(Vec<String>, Stats)> {
     let (s, seg_locs) = append_log_entries.iter().try_fold(
         (
@@ -174,3 +191,28 @@ pub fn merge_append_operations(
 
     Ok((seg_locs, s))
 }
+
+pub fn reduce_block_metas<T: Borrow<BlockMeta>>(
+    block_metas: &[T],
+    schema: &DataSchema,
+) -> Result<Stats> {
+    let mut row_count = 0;
+    let mut uncompressed_byte_size = 0;
+    let mut compressed_byte_size = 0;
+    let mut blocks_stats = Vec::with_capacity(block_metas.len());
+    for block_meta in block_metas {
+        let block_meta = block_meta.borrow();
+        row_count += block_meta.row_count;
+        uncompressed_byte_size += block_meta.block_size;
+        compressed_byte_size += block_meta.file_size;
+        blocks_stats.push(&block_meta.col_stats);
+    }
+
+    Ok(Stats {
+        row_count,
+        block_count: block_metas.len() as u64,
+        uncompressed_byte_size,
+        compressed_byte_size,
+        col_stats: reduce_block_stats(&blocks_stats, schema)?,
+    })
+}
@@ -17,24 +17,35 @@ use std::any::Any;
 use std::convert::TryFrom;
 use std::sync::Arc;
 
-use common_dal::read_obj;
 use common_datablocks::DataBlock;
 use common_exception::Result;
 use common_meta_types::TableInfo;
+use common_planners::DeletePlan;
+use common_planners::Expression;
 use common_planners::Extras;
+use common_planners::OptimizeTablePlan;
 use common_planners::Partitions;
 use common_planners::ReadDataSourcePlan;
 use common_planners::Statistics;
+use common_planners::TimeTravelPoint;
 use common_planners::TruncateTablePlan;
+use common_planners::UpdatePlan;
+use common_planners::VacuumTablePlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
 use crate::catalogs::Table;
 use crate::datasources::context::DataSourceContext;
+use crate::datasources::table::fuse::io;
+use crate::datasources::table::fuse::meta::ColumnId;
 use crate::datasources::table::fuse::meta::TableSnapshot;
 use crate::datasources::table::fuse::operations::AppendOperationLogEntry;
+use crate::datasources::table::fuse::TBL_OPT_KEY_BLOOM_FILTER_COLUMNS;
+use crate::datasources::table::fuse::TBL_OPT_KEY_CLUSTER_KEY;
 use crate::datasources::table::fuse::TBL_OPT_KEY_SNAPSHOT_LOC;
 use crate::sessions::QueryContext;
+use crate::sql::statements::ExpressionAnalyzer;
+use crate::sql::DfParser;
 
 pub struct FuseTable {
     pub(crate) table_info: TableInfo,
@@ -106,6 +117,14 @@ impl Table for FuseTable {
         self.do_commit(_ctx, append_log_entries).await
     }
 
+    async fn delete(&self, ctx: Arc<QueryContext>, delete_plan: DeletePlan) -> Result<()> {
+        self.do_delete(ctx, delete_plan).await
+    }
+
+    async fn update(&self, ctx: Arc<QueryContext>, update_plan: UpdatePlan) -> Result<()> {
+        self.do_update(ctx, update_plan).await
+    }
+
     async fn truncate(
         &self,
         ctx: Arc<QueryContext>,
@@ -113,6 +132,30 @@ impl Table for FuseTable {
     ) -> Result<()> {
         self.do_truncate(ctx, truncate_plan).await
     }
+
+    async fn optimize(
+        &self,
+        ctx: Arc<QueryContext>,
+        optimize_plan: OptimizeTablePlan,
+    ) -> Result<()> {
+        self.do_optimize(ctx, optimize_plan).await
+    }
+
+    async fn vacuum(
+        &self,
+        ctx: Arc<QueryContext>,
+        vacuum_plan: VacuumTablePlan,
+    ) -> Result<Vec<String>> {
+        self.do_vacuum(ctx, vacuum_plan).await
+    }
+
+    async fn navigate_to(
+        &self,
+        ctx: Arc<QueryContext>,
+        point: &TimeTravelPoint,
+    ) -> Result<Arc<dyn Table>> {
+        self.do_navigate_to(ctx, point).await
+    }
 }
 
 impl FuseTable {
@@ -123,15 +166,66 @@ impl FuseTable {
             .cloned()
     }
 
+    /// Prefix of the locations of the blocks, segments and snapshots of the table, i.e. its id.
+    ///
+    /// Tables, of which the snapshot was written before the objects were prefixed, keep using the
+    /// empty prefix, so that the history of them is still reachable.
+    pub(crate) fn meta_location_prefix(&self) -> String {
+        match self.snapshot_loc() {
+            Some(loc) => io::location_prefix(&loc).to_string(),
+            None => self.table_info.ident.table_id.to_string(),
+        }
+    }
+
     pub(crate) async fn table_snapshot(
         &self,
         ctx: Arc<QueryContext>,
     ) -> Result<Option<TableSnapshot>> {
         if let Some(loc) = self.snapshot_loc() {
             let da = ctx.get_data_accessor()?;
-            Ok(Some(read_obj(da, loc.to_string()).await?))
+            let cache = ctx.get_storage_cache_manager().get_table_snapshot_cache();
+            let snapshot = io::read_cached_meta(da, &loc, &cache).await?;
+            Ok(Some(snapshot.as_ref().clone()))
         } else {
             Ok(None)
         }
     }
+
+    /// The cluster key of table, e.g. `(a, b)`, if any
+    pub(crate) fn cluster_key_def(&self) -> Option<&String> {
+        self.table_info.options().get(TBL_OPT_KEY_CLUSTER_KEY)
+    }
+
+    /// Expressions of the cluster key, empty if the table has no cluster key
+    pub(crate) async fn cluster_keys(&self, ctx: Arc<QueryContext>) -> Result<Vec<Expression>> {
+        let keys = match self.cluster_key_def() {
+            None => return Ok(vec![]),
+            Some(def) => DfParser::parse_cluster_keys(def)?,
+        };
+
+        let analyzer = ExpressionAnalyzer::create(ctx);
+        let mut cluster_keys = Vec::with_capacity(keys.len());
+        for key in &keys {
+            cluster_keys.push(analyzer.analyze(key).await?);
+        }
+        Ok(cluster_keys)
+    }
+
+    /// Ids of the columns, of which the bloom filters are built for each block
+    pub(crate) fn bloom_filter_columns(&self) -> Result<Vec<ColumnId>> {
+        match self
+            .table_info
+            .options()
+            .get(TBL_OPT_KEY_BLOOM_FILTER_COLUMNS)
+        {
+            None => Ok(vec![]),
+            Some(columns) => {
+                let schema = self.table_info.schema();
+                columns
+                    .split(',')
+                    .map(|name| Ok(schema.index_of(name.trim())? as ColumnId))
+                    .collect()
+            }
+        }
+    }
 }
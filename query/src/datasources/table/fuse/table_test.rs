@@ -13,15 +13,43 @@
 //  limitations under the License.
 //
 
+use std::path::Path;
+
+use chrono::Duration;
+use chrono::Utc;
 use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::SeriesFrom;
+use common_datavalues::series::Series;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::col;
+use common_planners::lit;
+use common_planners::DeletePlan;
+use common_planners::Expression;
+use common_planners::Extras;
+use common_planners::Optimization;
+use common_planners::OptimizeTablePlan;
 use common_planners::ReadDataSourcePlan;
+use common_planners::TimeTravelPoint;
 use common_planners::TruncateTablePlan;
+use common_planners::UpdatePlan;
+use common_planners::VacuumTablePlan;
+use filetime::FileTime;
 use futures::TryStreamExt;
 
 use crate::catalogs::Catalog;
 use crate::catalogs::ToReadDataSourcePlan;
+use crate::datasources::table::fuse::io::read_meta;
+use crate::datasources::table::fuse::meta::SegmentInfo;
+use crate::datasources::table::fuse::meta::TableSnapshot;
 use crate::datasources::table::fuse::table_test_fixture::TestFixture;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::TBL_OPT_KEY_BLOOM_FILTER_COLUMNS;
+use crate::datasources::table::fuse::TBL_OPT_KEY_CLUSTER_KEY;
+use crate::datasources::table::fuse::TBL_OPT_KEY_SNAPSHOT_LOC;
 
 #[tokio::test]
 async fn test_fuse_table_simple_case() -> Result<()> {
@@ -213,3 +241,944 @@ async fn test_fuse_table_truncate() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_fuse_table_time_travel() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    let create_table_plan = fixture.default_crate_table_plan();
+    let db = create_table_plan.db.clone();
+    let catalog = ctx.get_catalog();
+    catalog
+        .get_database(&db)
+        .await?
+        .create_table(create_table_plan.into())
+        .await?;
+
+    // commit twice, 2 blocks first, then 3 blocks
+    for num_blocks in [2, 3] {
+        let table = catalog
+            .get_database(&db)
+            .await?
+            .get_table(
+                fixture.default_db().as_str(),
+                fixture.default_table().as_str(),
+            )
+            .await?;
+        let stream = Box::pin(futures::stream::iter(TestFixture::gen_block_stream(
+            num_blocks,
+        )));
+        let r = table.append_data(ctx.clone(), stream).await?;
+        table.commit(ctx.clone(), r.try_collect().await?).await?;
+    }
+
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    let snapshot_loc = table
+        .get_table_info()
+        .options()
+        .get(TBL_OPT_KEY_SNAPSHOT_LOC)
+        .cloned()
+        .unwrap();
+
+    let da = ctx.get_data_accessor()?;
+    let latest: TableSnapshot = read_meta(da, snapshot_loc).await?;
+    let first_snapshot_id = latest.prev_snapshot_id.unwrap();
+
+    // travel to the first snapshot
+    let point = TimeTravelPoint::Snapshot(first_snapshot_id.to_simple().to_string());
+    let historical = table.navigate_to(ctx.clone(), &point).await?;
+    let (stats, parts) = historical.read_partitions(ctx.clone(), None).await?;
+    assert_eq!(parts.len(), 2);
+    assert_eq!(stats.read_rows, 2 * 3);
+
+    // travel to a point later than the latest commit
+    let now = latest.timestamp.unwrap().timestamp() as u32 + 1;
+    let point = TimeTravelPoint::Timestamp(now);
+    let historical = table.navigate_to(ctx.clone(), &point).await?;
+    let (_, parts) = historical.read_partitions(ctx.clone(), None).await?;
+    assert_eq!(parts.len(), 5);
+
+    // travel to a point earlier than the first commit
+    let point = TimeTravelPoint::Timestamp(0);
+    let r = table.navigate_to(ctx.clone(), &point).await;
+    assert!(r.is_err());
+
+    // travel to an unknown snapshot
+    let point = TimeTravelPoint::Snapshot(uuid::Uuid::new_v4().to_simple().to_string());
+    let r = table.navigate_to(ctx.clone(), &point).await;
+    assert!(r.is_err());
+
+    // travel to the points before the retention boundary, after the history is vacuumed
+    table
+        .vacuum(ctx.clone(), VacuumTablePlan {
+            db: "".to_string(),
+            table: "".to_string(),
+            retention_hours: 0,
+            dry_run: false,
+        })
+        .await?;
+    for point in [
+        TimeTravelPoint::Snapshot(first_snapshot_id.to_simple().to_string()),
+        TimeTravelPoint::Timestamp(0),
+    ] {
+        let e = table.navigate_to(ctx.clone(), &point).await.err().unwrap();
+        assert_eq!(e.code(), ErrorCode::TableHistoricalDataNotFound("").code());
+        assert!(e.message().contains(&format!("{:?}", point)));
+        assert!(e.message().contains("is not available"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_concurrent_commit() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    let create_table_plan = fixture.default_crate_table_plan();
+    let db = create_table_plan.db.clone();
+    let catalog = ctx.get_catalog();
+    catalog
+        .get_database(&db)
+        .await?
+        .create_table(create_table_plan.into())
+        .await?;
+
+    // two writers, which load the same version of table
+    let mut writers = vec![];
+    for _ in 0..2 {
+        writers.push(
+            catalog
+                .get_database(&db)
+                .await?
+                .get_table(
+                    fixture.default_db().as_str(),
+                    fixture.default_table().as_str(),
+                )
+                .await?,
+        );
+    }
+
+    for (idx, table) in writers.iter().enumerate() {
+        let num_blocks = idx as u32 + 1;
+        let stream = Box::pin(futures::stream::iter(TestFixture::gen_block_stream(
+            num_blocks,
+        )));
+        let r = table.append_data(ctx.clone(), stream).await?;
+        // the second commit conflicts with the first one, and is expected to be retried
+        table.commit(ctx.clone(), r.try_collect().await?).await?;
+    }
+
+    // both of the commits are kept
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    let (stats, parts) = table.read_partitions(ctx.clone(), None).await?;
+    assert_eq!(parts.len(), 1 + 2);
+    assert_eq!(stats.read_rows, (1 + 2) * 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_optimize() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    let create_table_plan = fixture.default_crate_table_plan();
+    let db = create_table_plan.db.clone();
+    let catalog = ctx.get_catalog();
+    catalog
+        .get_database(&db)
+        .await?
+        .create_table(create_table_plan.into())
+        .await?;
+
+    // 5 trickle inserts, each of them commits a segment of 1 block
+    for _ in 0..5 {
+        let table = catalog
+            .get_database(&db)
+            .await?
+            .get_table(
+                fixture.default_db().as_str(),
+                fixture.default_table().as_str(),
+            )
+            .await?;
+        let stream = Box::pin(futures::stream::iter(TestFixture::gen_block_stream(1)));
+        let r = table.append_data(ctx.clone(), stream).await?;
+        table.commit(ctx.clone(), r.try_collect().await?).await?;
+    }
+
+    // the table to be compacted, which will be stale after the next insert
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+
+    // insert again, concurrently with the compaction
+    {
+        let table = catalog
+            .get_database(&db)
+            .await?
+            .get_table(
+                fixture.default_db().as_str(),
+                fixture.default_table().as_str(),
+            )
+            .await?;
+        let stream = Box::pin(futures::stream::iter(TestFixture::gen_block_stream(1)));
+        let r = table.append_data(ctx.clone(), stream).await?;
+        table.commit(ctx.clone(), r.try_collect().await?).await?;
+    }
+
+    let mut optimize_plan = OptimizeTablePlan {
+        db: "".to_string(),
+        table: "".to_string(),
+        operation: Optimization::Compact,
+    };
+
+    // 1. compact: the small blocks of the first 5 segments are merged into one block,
+    //    and the concurrently appended segment is kept
+    table.optimize(ctx.clone(), optimize_plan.clone()).await?;
+
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    let da = ctx.get_data_accessor()?;
+    let snapshot_loc = table
+        .get_table_info()
+        .options()
+        .get(TBL_OPT_KEY_SNAPSHOT_LOC)
+        .cloned()
+        .unwrap();
+    let snapshot: TableSnapshot = read_meta(da.clone(), snapshot_loc).await?;
+    assert_eq!(snapshot.segments.len(), 2);
+    assert_eq!(snapshot.summary.row_count, 6 * 3);
+    assert_eq!(snapshot.summary.block_count, 2);
+
+    let (stats, parts) = table.read_partitions(ctx.clone(), None).await?;
+    assert_eq!(parts.len(), 2);
+    assert_eq!(stats.read_rows, 6 * 3);
+
+    // 2. merge segments only
+    optimize_plan.operation = Optimization::Segment;
+    table.optimize(ctx.clone(), optimize_plan.clone()).await?;
+
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    let snapshot_loc = table
+        .get_table_info()
+        .options()
+        .get(TBL_OPT_KEY_SNAPSHOT_LOC)
+        .cloned()
+        .unwrap();
+    let snapshot: TableSnapshot = read_meta(da.clone(), snapshot_loc).await?;
+    assert_eq!(snapshot.segments.len(), 1);
+    assert_eq!(snapshot.summary.block_count, 2);
+
+    // data are not changed
+    let (stats, parts) = table.read_partitions(ctx.clone(), None).await?;
+    assert_eq!(parts.len(), 2);
+    assert_eq!(stats.read_rows, 6 * 3);
+    ctx.try_set_partitions(parts)?;
+    let stream = table
+        .read(ctx.clone(), &ReadDataSourcePlan {
+            table_info: Default::default(),
+            scan_fields: None,
+            parts: Default::default(),
+            statistics: Default::default(),
+            description: "".to_string(),
+            tbl_args: None,
+            push_downs: None,
+        })
+        .await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    let rows: usize = blocks.iter().map(|block| block.num_rows()).sum();
+    assert_eq!(rows, 6 * 3);
+
+    // 3. the block of 15 rows is no longer small, and the other one has nothing to merge with
+    ctx.get_settings()
+        .set_compact_block_row_count_threshold(10)?;
+    optimize_plan.operation = Optimization::Compact;
+    table.optimize(ctx.clone(), optimize_plan).await?;
+
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    let snapshot_loc = table
+        .get_table_info()
+        .options()
+        .get(TBL_OPT_KEY_SNAPSHOT_LOC)
+        .cloned()
+        .unwrap();
+    let snapshot: TableSnapshot = read_meta(da.clone(), snapshot_loc).await?;
+    assert_eq!(snapshot.segments.len(), 1);
+    assert_eq!(snapshot.summary.block_count, 2);
+
+    // 4. the 3 blocks of 2 segments fit in exactly 1 segment
+    let stream = Box::pin(futures::stream::iter(TestFixture::gen_block_stream(1)));
+    let r = table.append_data(ctx.clone(), stream).await?;
+    table.commit(ctx.clone(), r.try_collect().await?).await?;
+    ctx.get_settings()
+        .set_compact_segment_block_count_threshold(3)?;
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    table
+        .optimize(ctx.clone(), OptimizeTablePlan {
+            db: "".to_string(),
+            table: "".to_string(),
+            operation: Optimization::Segment,
+        })
+        .await?;
+
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    let snapshot_loc = table
+        .get_table_info()
+        .options()
+        .get(TBL_OPT_KEY_SNAPSHOT_LOC)
+        .cloned()
+        .unwrap();
+    let snapshot: TableSnapshot = read_meta(da, snapshot_loc).await?;
+    assert_eq!(snapshot.segments.len(), 1);
+    assert_eq!(snapshot.summary.block_count, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_vacuum() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    let create_table_plan = fixture.default_crate_table_plan();
+    let db = create_table_plan.db.clone();
+    let catalog = ctx.get_catalog();
+    catalog
+        .get_database(&db)
+        .await?
+        .create_table(create_table_plan.into())
+        .await?;
+
+    // 3 trickle inserts, each of them commits a segment of 1 block
+    for _ in 0..3 {
+        let table = catalog
+            .get_database(&db)
+            .await?
+            .get_table(
+                fixture.default_db().as_str(),
+                fixture.default_table().as_str(),
+            )
+            .await?;
+        let stream = Box::pin(futures::stream::iter(TestFixture::gen_block_stream(1)));
+        let r = table.append_data(ctx.clone(), stream).await?;
+        table.commit(ctx.clone(), r.try_collect().await?).await?;
+    }
+
+    // compact them into 1 segment of 1 block
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    table
+        .optimize(ctx.clone(), OptimizeTablePlan {
+            db: "".to_string(),
+            table: "".to_string(),
+            operation: Optimization::Compact,
+        })
+        .await?;
+
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    let mut vacuum_plan = VacuumTablePlan {
+        db: "".to_string(),
+        table: "".to_string(),
+        retention_hours: 0,
+        dry_run: true,
+    };
+
+    // 1. dry run: the 3 blocks, 3 segments and 3 snapshots of the inserts are listed, but not removed
+    let files = table.vacuum(ctx.clone(), vacuum_plan.clone()).await?;
+    assert_eq!(files.len(), 3 * 3);
+    let da = ctx.get_data_accessor()?;
+    for file in &files {
+        assert!(da.read(file).await.is_ok());
+    }
+
+    // 2. vacuum: all of them are removed, and the data are not changed
+    vacuum_plan.dry_run = false;
+    let removed = table.vacuum(ctx.clone(), vacuum_plan.clone()).await?;
+    assert_eq!(removed, files);
+    for file in &files {
+        assert!(da.read(file).await.is_err());
+    }
+
+    let (stats, parts) = table.read_partitions(ctx.clone(), None).await?;
+    assert_eq!(parts.len(), 1);
+    assert_eq!(stats.read_rows, 3 * 3);
+
+    // 3. nothing left to vacuum
+    let removed = table.vacuum(ctx.clone(), vacuum_plan.clone()).await?;
+    assert!(removed.is_empty());
+
+    // 4. orphans, e.g. the segments and blocks of an aborted append, are removed as well, once they
+    //    are old enough not to belong to a write in progress
+    let prefix = table.get_table_info().ident.table_id.to_string();
+    let mut orphans = vec![
+        format!("{}/_b/orphan.parquet", prefix),
+        format!("{}/_sg/orphan_v1", prefix),
+    ];
+    for orphan in &orphans {
+        da.put(orphan, vec![1]).await?;
+    }
+    let removed = table.vacuum(ctx.clone(), vacuum_plan.clone()).await?;
+    assert!(removed.is_empty());
+
+    let data_path = ctx.get_config().storage.disk.data_path;
+    let two_days_ago = FileTime::from_unix_time((Utc::now() - Duration::hours(48)).timestamp(), 0);
+    for orphan in &orphans {
+        filetime::set_file_mtime(Path::new(&data_path).join(orphan), two_days_ago)?;
+    }
+    let mut removed = table.vacuum(ctx.clone(), vacuum_plan).await?;
+    removed.sort();
+    orphans.sort();
+    assert_eq!(removed, orphans);
+    let (stats, _) = table.read_partitions(ctx.clone(), None).await?;
+    assert_eq!(stats.read_rows, 3 * 3);
+
+    // 5. history and orphans within the retention period are kept
+    da.put(&format!("{}/_b/orphan.parquet", prefix), vec![1])
+        .await?;
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    let stream = Box::pin(futures::stream::iter(TestFixture::gen_block_stream(1)));
+    let r = table.append_data(ctx.clone(), stream).await?;
+    table.commit(ctx.clone(), r.try_collect().await?).await?;
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    let removed = table
+        .vacuum(ctx.clone(), VacuumTablePlan {
+            db: "".to_string(),
+            table: "".to_string(),
+            retention_hours: 12,
+            dry_run: false,
+        })
+        .await?;
+    assert!(removed.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_delete() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    let create_table_plan = fixture.default_crate_table_plan();
+    let db = create_table_plan.db.clone();
+    let catalog = ctx.get_catalog();
+    catalog
+        .get_database(&db)
+        .await?
+        .create_table(create_table_plan.into())
+        .await?;
+
+    // 2 inserts, each of them commits a segment of 1 block, which contains rows 1, 2, 3
+    for _ in 0..2 {
+        let table = catalog
+            .get_database(&db)
+            .await?
+            .get_table(
+                fixture.default_db().as_str(),
+                fixture.default_table().as_str(),
+            )
+            .await?;
+        let stream = Box::pin(futures::stream::iter(TestFixture::gen_block_stream(1)));
+        let r = table.append_data(ctx.clone(), stream).await?;
+        table.commit(ctx.clone(), r.try_collect().await?).await?;
+    }
+
+    let da = ctx.get_data_accessor()?;
+    let delete = |op: &str, value: i32| DeletePlan {
+        db: "".to_string(),
+        table: "".to_string(),
+        selection: Some(Expression::create_binary_expression(op, vec![
+            col("id"),
+            lit(value),
+        ])),
+    };
+
+    // 1. blocks pruned by the min/max index are not touched, nothing is committed
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    let snapshot_loc = table
+        .get_table_info()
+        .options()
+        .get(TBL_OPT_KEY_SNAPSHOT_LOC)
+        .cloned();
+    table.delete(ctx.clone(), delete(">", 3)).await?;
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    assert_eq!(
+        table
+            .get_table_info()
+            .options()
+            .get(TBL_OPT_KEY_SNAPSHOT_LOC)
+            .cloned(),
+        snapshot_loc
+    );
+
+    // 2. some of the rows of each block are deleted, the blocks are rewritten
+    table.delete(ctx.clone(), delete("=", 2)).await?;
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    let snapshot_loc = table
+        .get_table_info()
+        .options()
+        .get(TBL_OPT_KEY_SNAPSHOT_LOC)
+        .cloned()
+        .unwrap();
+    let snapshot: TableSnapshot = read_meta(da.clone(), snapshot_loc).await?;
+    assert_eq!(snapshot.segments.len(), 2);
+    assert_eq!(snapshot.summary.block_count, 2);
+    assert_eq!(snapshot.summary.row_count, 2 * 2);
+
+    let (stats, parts) = table.read_partitions(ctx.clone(), None).await?;
+    assert_eq!(parts.len(), 2);
+    assert_eq!(stats.read_rows, 2 * 2);
+    ctx.try_set_partitions(parts)?;
+    let stream = table
+        .read(ctx.clone(), &ReadDataSourcePlan {
+            table_info: Default::default(),
+            scan_fields: None,
+            parts: Default::default(),
+            statistics: Default::default(),
+            description: "".to_string(),
+            tbl_args: None,
+            push_downs: None,
+        })
+        .await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+----+", //
+        "| id |", //
+        "+----+", //
+        "| 1  |", //
+        "| 1  |", //
+        "| 3  |", //
+        "| 3  |", //
+        "+----+", //
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, blocks.as_slice());
+
+    // 3. all the rows of each block are deleted, the blocks (and the segments) are dropped
+    table.delete(ctx.clone(), delete(">", 0)).await?;
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    let snapshot_loc = table
+        .get_table_info()
+        .options()
+        .get(TBL_OPT_KEY_SNAPSHOT_LOC)
+        .cloned()
+        .unwrap();
+    let snapshot: TableSnapshot = read_meta(da, snapshot_loc).await?;
+    assert!(snapshot.segments.is_empty());
+    assert_eq!(snapshot.summary.row_count, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_update() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    let create_table_plan = fixture.default_crate_table_plan();
+    let db = create_table_plan.db.clone();
+    let catalog = ctx.get_catalog();
+    catalog
+        .get_database(&db)
+        .await?
+        .create_table(create_table_plan.into())
+        .await?;
+
+    // 2 inserts, each of them commits a segment of 1 block, which contains rows 1, 2, 3
+    for _ in 0..2 {
+        let table = catalog
+            .get_database(&db)
+            .await?
+            .get_table(
+                fixture.default_db().as_str(),
+                fixture.default_table().as_str(),
+            )
+            .await?;
+        let stream = Box::pin(futures::stream::iter(TestFixture::gen_block_stream(1)));
+        let r = table.append_data(ctx.clone(), stream).await?;
+        table.commit(ctx.clone(), r.try_collect().await?).await?;
+    }
+
+    // update table set id = id + 10 where id = 2
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    let update_plan = UpdatePlan {
+        db: "".to_string(),
+        table: "".to_string(),
+        assignments: vec![("id".to_string(), Expression::Cast {
+            expr: Box::new(Expression::create_binary_expression("+", vec![
+                col("id"),
+                lit(10i32),
+            ])),
+            data_type: DataType::Int32,
+        })],
+        selection: Some(Expression::create_binary_expression("=", vec![
+            col("id"),
+            lit(2i32),
+        ])),
+    };
+    table.update(ctx.clone(), update_plan).await?;
+
+    // the statistics are re-calculated
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    let da = ctx.get_data_accessor()?;
+    let snapshot_loc = table
+        .get_table_info()
+        .options()
+        .get(TBL_OPT_KEY_SNAPSHOT_LOC)
+        .cloned()
+        .unwrap();
+    let snapshot: TableSnapshot = read_meta(da, snapshot_loc).await?;
+    assert_eq!(snapshot.segments.len(), 2);
+    assert_eq!(snapshot.summary.row_count, 2 * 3);
+    let col_stats = snapshot.summary.col_stats.get(&0).unwrap();
+    assert_eq!(col_stats.min, DataValue::Int32(Some(1)));
+    assert_eq!(col_stats.max, DataValue::Int32(Some(12)));
+
+    let (_, parts) = table.read_partitions(ctx.clone(), None).await?;
+    ctx.try_set_partitions(parts)?;
+    let stream = table
+        .read(ctx.clone(), &ReadDataSourcePlan {
+            table_info: Default::default(),
+            scan_fields: None,
+            parts: Default::default(),
+            statistics: Default::default(),
+            description: "".to_string(),
+            tbl_args: None,
+            push_downs: None,
+        })
+        .await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+----+", //
+        "| id |", //
+        "+----+", //
+        "| 1  |", //
+        "| 1  |", //
+        "| 12 |", //
+        "| 12 |", //
+        "| 3  |", //
+        "| 3  |", //
+        "+----+", //
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, blocks.as_slice());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_cluster_key() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    let mut create_table_plan = fixture.default_crate_table_plan();
+    create_table_plan
+        .table_meta
+        .options
+        .insert(TBL_OPT_KEY_CLUSTER_KEY.to_string(), "(id)".to_string());
+    let db = create_table_plan.db.clone();
+    let catalog = ctx.get_catalog();
+    catalog
+        .get_database(&db)
+        .await?
+        .create_table(create_table_plan.into())
+        .await?;
+
+    // 2 inserts of unsorted blocks, the ranges of the cluster key are [1, 3] and [2, 5]
+    for rows in [vec![3, 1, 2], vec![4, 2, 5]] {
+        let table = catalog
+            .get_database(&db)
+            .await?
+            .get_table(
+                fixture.default_db().as_str(),
+                fixture.default_table().as_str(),
+            )
+            .await?;
+        let block =
+            DataBlock::create_by_array(TestFixture::default_schema(), vec![Series::new(rows)]);
+        let stream = Box::pin(futures::stream::iter(vec![Ok(block)]));
+        let r = table.append_data(ctx.clone(), stream).await?;
+        table.commit(ctx.clone(), r.try_collect().await?).await?;
+    }
+
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+
+    // blocks are sorted by the cluster key while being written
+    let da = ctx.get_data_accessor()?;
+    let snapshot_loc = table
+        .get_table_info()
+        .options()
+        .get(TBL_OPT_KEY_SNAPSHOT_LOC)
+        .cloned()
+        .unwrap();
+    let snapshot: TableSnapshot = read_meta(da.clone(), snapshot_loc).await?;
+    let mut ranges = vec![];
+    for seg_loc in &snapshot.segments {
+        let segment: SegmentInfo = read_meta(da.clone(), seg_loc).await?;
+        for block_meta in segment.blocks {
+            let cluster_stats = block_meta.cluster_stats.unwrap();
+            ranges.push((cluster_stats.min, cluster_stats.max));
+        }
+    }
+    ranges.sort_by_key(|(min, _)| min[0].as_i64().unwrap());
+    assert_eq!(ranges, vec![
+        (vec![DataValue::Int32(Some(1))], vec![DataValue::Int32(
+            Some(3)
+        )]),
+        (vec![DataValue::Int32(Some(2))], vec![DataValue::Int32(
+            Some(5)
+        )]),
+    ]);
+
+    let (_, parts) = table.read_partitions(ctx.clone(), None).await?;
+    ctx.try_set_partitions(parts)?;
+    let stream = table
+        .read(ctx.clone(), &ReadDataSourcePlan {
+            table_info: Default::default(),
+            scan_fields: None,
+            parts: Default::default(),
+            statistics: Default::default(),
+            description: "".to_string(),
+            tbl_args: None,
+            push_downs: None,
+        })
+        .await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    assert_eq!(blocks.len(), 2);
+    for block in blocks {
+        let values = (0..block.num_rows())
+            .map(|idx| block.column(0).try_get(idx).and_then(|v| v.as_i64()))
+            .collect::<Result<Vec<_>>>()?;
+        let mut sorted = values.clone();
+        sorted.sort_unstable();
+        assert_eq!(values, sorted);
+    }
+
+    // the two blocks overlap with each other
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let info = fuse_table.clustering_information(ctx.clone()).await?;
+    assert_eq!(info.total_block_count, 2);
+    assert_eq!(info.constant_block_count, 0);
+    assert_eq!(info.unclustered_block_count, 0);
+    assert!((info.average_overlaps - 1.0).abs() < f64::EPSILON);
+    // depths of the points 1, 2, 3, 5 are 1, 2, 2, 1
+    assert!((info.average_depth - 1.5).abs() < f64::EPSILON);
+    assert_eq!(info.max_depth, 2);
+
+    // optimize reclusters the overlapping blocks, even if they are not small
+    ctx.get_settings()
+        .set_compact_block_row_count_threshold(1)?;
+    table
+        .optimize(ctx.clone(), OptimizeTablePlan {
+            db: "".to_string(),
+            table: "".to_string(),
+            operation: Optimization::Compact,
+        })
+        .await?;
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+    let fuse_table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+    let info = fuse_table.clustering_information(ctx.clone()).await?;
+    assert_eq!(info.total_block_count, 2);
+    assert!(info.average_overlaps.abs() < f64::EPSILON);
+    assert_eq!(info.max_depth, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fuse_table_bloom_filter() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+
+    let mut create_table_plan = fixture.default_crate_table_plan();
+    create_table_plan.table_meta.options.insert(
+        TBL_OPT_KEY_BLOOM_FILTER_COLUMNS.to_string(),
+        "id".to_string(),
+    );
+    let db = create_table_plan.db.clone();
+    let catalog = ctx.get_catalog();
+    catalog
+        .get_database(&db)
+        .await?
+        .create_table(create_table_plan.into())
+        .await?;
+
+    // the ranges of the 2 blocks are [1, 5] and [2, 6], which could not be pruned by min/max
+    for rows in [vec![1, 3, 5], vec![2, 4, 6]] {
+        let table = catalog
+            .get_database(&db)
+            .await?
+            .get_table(
+                fixture.default_db().as_str(),
+                fixture.default_table().as_str(),
+            )
+            .await?;
+        let block =
+            DataBlock::create_by_array(TestFixture::default_schema(), vec![Series::new(rows)]);
+        let stream = Box::pin(futures::stream::iter(vec![Ok(block)]));
+        let r = table.append_data(ctx.clone(), stream).await?;
+        table.commit(ctx.clone(), r.try_collect().await?).await?;
+    }
+
+    let table = catalog
+        .get_database(&db)
+        .await?
+        .get_table(
+            fixture.default_db().as_str(),
+            fixture.default_table().as_str(),
+        )
+        .await?;
+
+    for (filter, expected_parts) in [
+        (col("id").eq(lit(4)), 1),
+        // the rewritten `id IN (4, 8)`
+        (col("id").eq(lit(4)).or(col("id").eq(lit(8))), 1),
+        (col("id").eq(lit(3)).or(col("id").eq(lit(4))), 2),
+        (col("id").eq(lit(4)).and(col("id").gt(lit(1))), 1),
+        (col("id").gt(lit(3)), 2),
+    ] {
+        let extras = Extras {
+            filters: vec![filter.clone()],
+            ..Default::default()
+        };
+        let (_, parts) = table.read_partitions(ctx.clone(), Some(extras)).await?;
+        assert_eq!(parts.len(), expected_parts, "filter: {:?}", filter);
+    }
+
+    Ok(())
+}
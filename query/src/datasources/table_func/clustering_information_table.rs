@@ -0,0 +1,163 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::TableIdent;
+use common_meta_types::TableInfo;
+use common_meta_types::TableMeta;
+use common_planners::Expression;
+use common_planners::ReadDataSourcePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Table;
+use crate::catalogs::TableFunction;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table_func_engine::TableArgs;
+use crate::sessions::QueryContext;
+
+/// `clustering_information('db', 'table')`, reports how well the blocks of a Fuse table
+/// are clustered by the cluster key
+pub struct ClusteringInformationTable {
+    table_info: TableInfo,
+    database: String,
+    table: String,
+}
+
+impl ClusteringInformationTable {
+    pub fn create(
+        database_name: &str,
+        table_func_name: &str,
+        table_id: u64,
+        table_args: TableArgs,
+    ) -> Result<Arc<dyn TableFunction>> {
+        let args = table_args
+            .unwrap_or_default()
+            .iter()
+            .map(|arg| match arg {
+                Expression::Literal {
+                    value: DataValue::String(Some(v)),
+                    ..
+                } => Ok(String::from_utf8(v.clone())?),
+                _ => Err(ErrorCode::BadArguments(format!(
+                    "Unexpected argument {:?} of table function {}",
+                    arg, table_func_name
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if args.len() != 2 {
+            return Err(ErrorCode::BadArguments(format!(
+                "Must have exactly two string arguments (database and table) for table function {}",
+                table_func_name
+            )));
+        }
+
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("cluster_by_keys", DataType::String, false),
+            DataField::new("total_block_count", DataType::UInt64, false),
+            DataField::new("constant_block_count", DataType::UInt64, false),
+            DataField::new("unclustered_block_count", DataType::UInt64, false),
+            DataField::new("average_overlaps", DataType::Float64, false),
+            DataField::new("average_depth", DataType::Float64, false),
+            DataField::new("max_depth", DataType::UInt64, false),
+        ]);
+
+        let table_info = TableInfo {
+            ident: TableIdent::new(table_id, 0),
+            desc: format!("'{}'.'{}'", database_name, table_func_name),
+            name: table_func_name.to_string(),
+            meta: TableMeta {
+                schema,
+                engine: "ClusteringInformation".to_string(),
+                options: Default::default(),
+            },
+        };
+
+        Ok(Arc::new(ClusteringInformationTable {
+            table_info,
+            database: args[0].clone(),
+            table: args[1].clone(),
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for ClusteringInformationTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn table_args(&self) -> Option<Vec<Expression>> {
+        Some(vec![
+            Expression::create_literal(DataValue::String(Some(self.database.as_bytes().to_vec()))),
+            Expression::create_literal(DataValue::String(Some(self.table.as_bytes().to_vec()))),
+        ])
+    }
+
+    async fn read(
+        &self,
+        ctx: Arc<QueryContext>,
+        _plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let table = ctx.get_table(&self.database, &self.table).await?;
+        let fuse_table = table.as_any().downcast_ref::<FuseTable>().ok_or_else(|| {
+            ErrorCode::BadArguments(format!(
+                "clustering information of table {}.{} is not available, table engine is {}",
+                self.database,
+                self.table,
+                table.engine()
+            ))
+        })?;
+
+        let info = fuse_table.clustering_information(ctx.clone()).await?;
+        let cluster_key_def = fuse_table.cluster_key_def().cloned().unwrap_or_default();
+        let block = DataBlock::create_by_array(self.table_info.schema(), vec![
+            Series::new(vec![cluster_key_def.as_bytes()]),
+            Series::new(vec![info.total_block_count]),
+            Series::new(vec![info.constant_block_count]),
+            Series::new(vec![info.unclustered_block_count]),
+            Series::new(vec![info.average_overlaps]),
+            Series::new(vec![info.average_depth]),
+            Series::new(vec![info.max_depth]),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.table_info.schema(),
+            None,
+            vec![block],
+        )))
+    }
+}
+
+impl TableFunction for ClusteringInformationTable {
+    fn function_name(&self) -> &str {
+        self.name()
+    }
+
+    fn as_table<'a>(self: Arc<Self>) -> Arc<dyn Table + 'a>
+    where Self: 'a {
+        self
+    }
+}
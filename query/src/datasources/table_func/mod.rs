@@ -13,8 +13,10 @@
 //  limitations under the License.
 //
 
+pub use clustering_information_table::ClusteringInformationTable;
 pub use numbers_table::NumbersTable;
 
+mod clustering_information_table;
 mod numbers_stream;
 mod numbers_table;
 #[cfg(test)]
@@ -26,6 +26,7 @@ use common_exception::Result;
 use common_meta_types::TableIdent;
 use common_meta_types::TableInfo;
 use common_meta_types::TableMeta;
+use common_planners::ColumnStatistics;
 use common_planners::Expression;
 use common_planners::Extras;
 use common_planners::Partitions;
@@ -115,10 +116,19 @@ impl Table for NumbersTable {
         ctx: Arc<QueryContext>,
         _push_downs: Option<Extras>,
     ) -> Result<(Statistics, Partitions)> {
-        let statistics = Statistics::new_exact(
+        let mut statistics = Statistics::new_exact(
             self.total as usize,
             ((self.total) * size_of::<u64>() as u64) as usize,
         );
+
+        if self.total > 0 {
+            statistics = statistics.with_column_statistics("number", ColumnStatistics {
+                min: DataValue::UInt64(Some(0)),
+                max: DataValue::UInt64(Some(self.total - 1)),
+                null_count: 0,
+                distinct_count: Some(self.total),
+            });
+        }
         let parts = generate_parts(0, ctx.get_settings().get_max_threads()? as u64, self.total);
 
         Ok((statistics, parts))
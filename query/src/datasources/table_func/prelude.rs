@@ -20,6 +20,7 @@ use common_meta_types::MetaId;
 
 use crate::catalogs::SYS_TBL_FUC_ID_END;
 use crate::catalogs::SYS_TBL_FUNC_ID_BEGIN;
+use crate::datasources::table_func::ClusteringInformationTable;
 use crate::datasources::table_func::NumbersTable;
 use crate::datasources::table_func_engine::TableFuncEngine;
 use crate::datasources::table_func_engine_registry::TableFuncEngineRegistry;
@@ -52,5 +53,12 @@ pub fn prelude_func_engines() -> TableFuncEngineRegistry {
         "numbers_local".to_string(),
         (next_id(), number_table_func_factory),
     );
+
+    let clustering_information_func_factory: Arc<dyn TableFuncEngine> =
+        Arc::new(ClusteringInformationTable::create);
+    func_factory_registry.insert(
+        "clustering_information".to_string(),
+        (next_id(), clustering_information_func_factory),
+    );
     func_factory_registry
 }
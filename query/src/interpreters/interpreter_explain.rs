@@ -19,12 +19,14 @@ use common_datavalues::prelude::*;
 use common_exception::Result;
 use common_planners::ExplainPlan;
 use common_planners::ExplainType;
+use common_planners::PlanNode;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
 use crate::interpreters::utils::apply_plan_rewrite;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
+use crate::optimizers::CostModel;
 use crate::optimizers::Optimizers;
 use crate::pipelines::processors::PipelineBuilder;
 use crate::sessions::QueryContext;
@@ -50,6 +52,7 @@ impl Interpreter for ExplainInterpreter {
             ExplainType::Graph => self.explain_graph(),
             ExplainType::Syntax => self.explain_syntax(),
             ExplainType::Pipeline => self.explain_pipeline(),
+            ExplainType::Cost => self.explain_cost(),
         }?;
 
         Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
@@ -89,6 +92,24 @@ impl ExplainInterpreter {
         Ok(DataBlock::create_by_array(schema, vec![formatted_plan]))
     }
 
+    fn explain_cost(&self) -> Result<DataBlock> {
+        let schema = self.schema();
+        let plan = apply_plan_rewrite(Optimizers::create(self.ctx.clone()), &self.explain.input)?;
+        let annotation = |node: &PlanNode| {
+            let estimation = CostModel::estimate(node);
+            let (rows, cost) = (estimation.rows, estimation.cost);
+            format!(" (rows: {:.0}, cost: {:.0})", rows, cost)
+        };
+        let formatted_plan = Series::new(
+            plan.display_indent_format_with_annotation(&annotation)
+                .to_string()
+                .lines()
+                .map(|s| s.as_bytes())
+                .collect::<Vec<_>>(),
+        );
+        Ok(DataBlock::create_by_array(schema, vec![formatted_plan]))
+    }
+
     fn explain_pipeline(&self) -> Result<DataBlock> {
         let schema = self.schema();
         let optimizer = Optimizers::without_scatters(self.ctx.clone());
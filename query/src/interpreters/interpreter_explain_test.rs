@@ -57,3 +57,31 @@ async fn test_explain_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_explain_cost_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    static TEST_QUERY: &str = "EXPLAIN COST SELECT number FROM numbers_mt(10) WHERE number < 5";
+
+    if let PlanNode::Explain(plan) = parse_query(TEST_QUERY, &ctx)? {
+        let executor = ExplainInterpreter::try_create(ctx, plan)?;
+        let stream = executor.execute(None).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+
+        let expected = vec![
+            "+--------------------------------------------------------------------------------------------------------------------------------------------------------------------------+",
+            "| explain                                                                                                                                                                  |",
+            "+--------------------------------------------------------------------------------------------------------------------------------------------------------------------------+",
+            "| Projection: number:UInt64 (rows: 6, cost: 26)                                                                                                                            |",
+            "|   Filter: (number < 5) (rows: 6, cost: 20)                                                                                                                               |",
+            "|     ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]] (rows: 10, cost: 10) |",
+            "+--------------------------------------------------------------------------------------------------------------------------------------------------------------------------+",
+        ];
+        common_datablocks::assert_blocks_eq(expected, result.as_slice());
+    } else {
+        panic!()
+    }
+
+    Ok(())
+}
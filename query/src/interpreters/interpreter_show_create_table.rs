@@ -27,6 +27,8 @@ use common_streams::SendableDataBlockStream;
 use log::debug;
 
 use crate::catalogs::Catalog;
+use crate::datasources::table::fuse::TBL_OPT_KEY_BLOOM_FILTER_COLUMNS;
+use crate::datasources::table::fuse::TBL_OPT_KEY_CLUSTER_KEY;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::QueryContext;
@@ -71,6 +73,14 @@ impl Interpreter for ShowCreateTableInterpreter {
         }
         let table_engine = format!(") ENGINE={}", engine);
         table_info.push_str(table_engine.as_str());
+        let options = table.get_table_info().options();
+        if let Some(cluster_keys) = options.get(TBL_OPT_KEY_CLUSTER_KEY) {
+            table_info.push_str(format!(" CLUSTER BY {}", cluster_keys).as_str());
+        }
+        if let Some(columns) = options.get(TBL_OPT_KEY_BLOOM_FILTER_COLUMNS) {
+            let option = format!(" {}='{}'", TBL_OPT_KEY_BLOOM_FILTER_COLUMNS, columns);
+            table_info.push_str(option.as_str());
+        }
 
         let show_fields = vec![
             DataField::new("Table", DataType::String, false),
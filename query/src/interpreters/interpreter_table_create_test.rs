@@ -13,10 +13,15 @@
 // limitations under the License.
 
 use common_base::tokio;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::*;
 use futures::stream::StreamExt;
+use futures::TryStreamExt;
+use tempfile::TempDir;
 
+use crate::configs::Config;
+use crate::datasources::table::fuse::TBL_OPT_KEY_CLUSTER_KEY;
 use crate::interpreters::*;
 use crate::tests::parse_query;
 
@@ -40,3 +45,69 @@ async fn test_create_table_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_create_table_with_cluster_key_interpreter() -> Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let mut config = Config::default();
+    config.storage.storage_type = "Disk".to_string();
+    config.storage.disk.data_path = tmp_dir.path().to_str().unwrap().to_string();
+    config.storage.disk.temp_data_path = tmp_dir.path().to_str().unwrap().to_string();
+    let ctx = crate::tests::try_create_context_with_config(config)?;
+
+    static TEST_CREATE_QUERY: &str =
+        "CREATE TABLE default.a(a Int32, b Int32) Engine = Fuse CLUSTER BY (b, a + 1)";
+    if let PlanNode::CreateTable(plan) = parse_query(TEST_CREATE_QUERY, &ctx)? {
+        assert_eq!(
+            plan.table_meta.options.get(TBL_OPT_KEY_CLUSTER_KEY),
+            Some(&"(b, a + 1)".to_string())
+        );
+        let interpreter = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+        let _ = interpreter.execute(None).await?;
+    } else {
+        panic!()
+    }
+
+    // cluster key is only supported by the Fuse engine, and should refer to existing columns
+    for (query, code) in [
+        (
+            "CREATE TABLE default.b(a Int32) Engine = Null CLUSTER BY (a)",
+            ErrorCode::BadArguments("").code(),
+        ),
+        (
+            "CREATE TABLE default.b(a Int32) Engine = Fuse CLUSTER BY (c)",
+            ErrorCode::UnknownColumn("").code(),
+        ),
+    ] {
+        assert_eq!(parse_query(query, &ctx).unwrap_err().code(), code);
+    }
+
+    // insert twice, the ranges of the cluster key (b, a + 1) of the blocks overlap
+    for query in [
+        "INSERT INTO default.a VALUES(1, 3), (2, 1), (3, 2)",
+        "INSERT INTO default.a VALUES(4, 2), (5, 5)",
+    ] {
+        let plan = parse_query(query, &ctx)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute(None).await?;
+    }
+
+    let plan = parse_query(
+        "SELECT cluster_by_keys, total_block_count, average_overlaps, max_depth \
+            FROM clustering_information('default', 'a')",
+        &ctx,
+    )?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let stream = executor.execute(None).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+-----------------+-------------------+------------------+-----------+",
+        "| cluster_by_keys | total_block_count | average_overlaps | max_depth |",
+        "+-----------------+-------------------+------------------+-----------+",
+        "| (b, a + 1)      | 2                 | 1                | 2         |",
+        "+-----------------+-------------------+------------------+-----------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
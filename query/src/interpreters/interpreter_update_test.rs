@@ -0,0 +1,101 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+use crate::configs::Config;
+use crate::interpreters::*;
+use crate::tests::parse_query;
+
+#[tokio::test]
+async fn test_update_interpreter() -> Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let mut config = Config::default();
+    config.storage.storage_type = "Disk".to_string();
+    config.storage.disk.data_path = tmp_dir.path().to_str().unwrap().to_string();
+    config.storage.disk.temp_data_path = tmp_dir.path().to_str().unwrap().to_string();
+    let ctx = crate::tests::try_create_context_with_config(config)?;
+
+    // Create table, and insert into it twice.
+    for query in [
+        "CREATE TABLE default.a(a Int32, b String) Engine = Fuse",
+        "INSERT INTO default.a VALUES(1, 'x'), (2, 'y'), (3, 'z')",
+        "INSERT INTO default.a VALUES(4, 'x'), (5, 'y')",
+    ] {
+        let plan = parse_query(query, &ctx)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute(None).await?;
+    }
+
+    // update table, the rows of the first block only.
+    {
+        static TEST_UPDATE_QUERY: &str = "UPDATE default.a SET a = a * 10, b = 'w' WHERE a < 3";
+        if let PlanNode::Update(plan) = parse_query(TEST_UPDATE_QUERY, &ctx)? {
+            let interpreter = UpdateInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(interpreter.name(), "UpdateInterpreter");
+
+            let stream = interpreter.execute(None).await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec!["++", "++"];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            panic!()
+        }
+    }
+
+    // update all.
+    {
+        static TEST_UPDATE_QUERY: &str = "UPDATE default.a SET b = 'v'";
+        let plan = parse_query(TEST_UPDATE_QUERY, &ctx)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute(None).await?;
+    }
+
+    // select.
+    {
+        static TEST_SELECT_QUERY: &str = "SELECT * FROM default.a";
+        if let PlanNode::Select(plan) = parse_query(TEST_SELECT_QUERY, &ctx)? {
+            let executor = SelectInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute(None).await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+----+---+",
+                "| a  | b |",
+                "+----+---+",
+                "| 10 | v |",
+                "| 20 | v |",
+                "| 3  | v |",
+                "| 4  | v |",
+                "| 5  | v |",
+                "+----+---+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            panic!()
+        }
+    }
+
+    // unknown column.
+    {
+        static TEST_UPDATE_QUERY: &str = "UPDATE default.a SET c = 1";
+        assert!(parse_query(TEST_UPDATE_QUERY, &ctx).is_err());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+use crate::configs::Config;
+use crate::interpreters::*;
+use crate::tests::parse_query;
+
+#[tokio::test]
+async fn test_vacuum_table_interpreter() -> Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let mut config = Config::default();
+    config.storage.storage_type = "Disk".to_string();
+    config.storage.disk.data_path = tmp_dir.path().to_str().unwrap().to_string();
+    config.storage.disk.temp_data_path = tmp_dir.path().to_str().unwrap().to_string();
+    let ctx = crate::tests::try_create_context_with_config(config)?;
+
+    // Create table.
+    {
+        static TEST_CREATE_QUERY: &str = "\
+            CREATE TABLE default.a(\
+                a String, b String\
+            ) Engine = Fuse\
+        ";
+
+        if let PlanNode::CreateTable(plan) = parse_query(TEST_CREATE_QUERY, &ctx)? {
+            let interpreter = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = interpreter.execute(None).await?;
+        }
+    }
+
+    // Insert into twice, then compact.
+    for query in [
+        "INSERT INTO default.a VALUES('1,1', '2,2')",
+        "INSERT INTO default.a VALUES('1,1', '2,2')",
+        "OPTIMIZE TABLE default.a",
+    ] {
+        let plan = parse_query(query, &ctx)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute(None).await?;
+    }
+
+    // vacuum table, dry run: the blocks, segments and snapshots of the inserts are listed.
+    {
+        static TEST_VACUUM_QUERY: &str = "VACUUM TABLE default.a RETAIN 0 HOURS DRY RUN";
+        if let PlanNode::VacuumTable(plan) = parse_query(TEST_VACUUM_QUERY, &ctx)? {
+            let interpreter = VacuumTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(interpreter.name(), "VacuumTableInterpreter");
+
+            let stream = interpreter.execute(None).await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let rows: usize = result.iter().map(|block| block.num_rows()).sum();
+            assert_eq!(rows, 2 * 3);
+        } else {
+            panic!()
+        }
+    }
+
+    // vacuum table.
+    {
+        static TEST_VACUUM_QUERY: &str = "VACUUM TABLE default.a RETAIN 0 HOURS";
+        if let PlanNode::VacuumTable(plan) = parse_query(TEST_VACUUM_QUERY, &ctx)? {
+            let interpreter = VacuumTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = interpreter.execute(None).await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec!["++", "++"];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            panic!()
+        }
+    }
+
+    // select.
+    {
+        static TEST_SELECT_QUERY: &str = "SELECT * FROM default.a";
+        if let PlanNode::Select(plan) = parse_query(TEST_SELECT_QUERY, &ctx)? {
+            let executor = SelectInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute(None).await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+-----+-----+",
+                "| a   | b   |",
+                "+-----+-----+",
+                "| 1,1 | 2,2 |",
+                "| 1,1 | 2,2 |",
+                "+-----+-----+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            panic!()
+        }
+    }
+
+    Ok(())
+}
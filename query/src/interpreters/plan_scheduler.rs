@@ -22,26 +22,33 @@ use common_exception::Result;
 use common_meta_types::NodeInfo;
 use common_planners::AggregatorFinalPlan;
 use common_planners::AggregatorPartialPlan;
+use common_planners::ArrayJoinPlan;
 use common_planners::BroadcastPlan;
+use common_planners::DistinctPlan;
 use common_planners::EmptyPlan;
 use common_planners::Expression;
 use common_planners::ExpressionPlan;
 use common_planners::Expressions;
 use common_planners::FilterPlan;
 use common_planners::HavingPlan;
+use common_planners::JoinPlan;
 use common_planners::LimitByPlan;
 use common_planners::LimitPlan;
+use common_planners::MaterializedCtePlan;
 use common_planners::Partitions;
 use common_planners::PlanNode;
 use common_planners::ProjectionPlan;
 use common_planners::ReadDataSourcePlan;
 use common_planners::RemotePlan;
 use common_planners::SelectPlan;
+use common_planners::SetOperationPlan;
 use common_planners::SinkPlan;
 use common_planners::SortPlan;
 use common_planners::StageKind;
 use common_planners::StagePlan;
 use common_planners::SubQueriesSetPlan;
+use common_planners::UnionPlan;
+use common_planners::WindowPlan;
 use common_tracing::tracing;
 
 use crate::api::BroadcastAction;
@@ -302,12 +309,19 @@ impl PlanScheduler {
             PlanNode::Sort(plan) => self.visit_sort(plan, tasks),
             PlanNode::Limit(plan) => self.visit_limit(plan, tasks),
             PlanNode::LimitBy(plan) => self.visit_limit_by(plan, tasks),
+            PlanNode::ArrayJoin(plan) => self.visit_array_join(plan, tasks),
+            PlanNode::Window(plan) => self.visit_window(plan, tasks),
+            PlanNode::Union(plan) => self.visit_union(plan, tasks),
+            PlanNode::Distinct(plan) => self.visit_distinct(plan, tasks),
+            PlanNode::SetOperation(plan) => self.visit_set_operation(plan, tasks),
+            PlanNode::MaterializedCte(plan) => self.visit_materialized_cte(plan, tasks),
             PlanNode::ReadSource(plan) => self.visit_data_source(plan, tasks),
             PlanNode::Sink(plan) => self.visit_sink(plan, tasks),
             PlanNode::Select(plan) => self.visit_select(plan, tasks),
             PlanNode::Stage(plan) => self.visit_stage(plan, tasks),
             PlanNode::Broadcast(plan) => self.visit_broadcast(plan, tasks),
             PlanNode::Having(plan) => self.visit_having(plan, tasks),
+            PlanNode::Join(plan) => self.visit_join(plan, tasks),
             PlanNode::Expression(plan) => self.visit_expression(plan, tasks),
             PlanNode::SubQueryExpression(plan) => self.visit_subqueries_set(plan, tasks),
             _ => Err(ErrorCode::UnImplement("")),
@@ -645,6 +659,57 @@ impl PlanScheduler {
         Ok(subquery_scheduler.nodes_plan)
     }
 
+    fn visit_join(&mut self, plan: &JoinPlan, tasks: &mut Tasks) -> Result<()> {
+        self.visit_plan_node(plan.left.as_ref(), tasks)?;
+
+        let right_context = QueryContext::new(self.query_context.clone());
+        let mut right_scheduler = PlanScheduler::try_create(right_context)?;
+        right_scheduler.visit_plan_node(plan.right.as_ref(), tasks)?;
+
+        // The scatters optimizer makes both sides run in the same mode, either both are
+        // converged to the local node or both are broadcast/shuffled to all nodes.
+        match (&self.running_mode, &right_scheduler.running_mode) {
+            (RunningMode::Standalone, RunningMode::Standalone) => {
+                self.visit_local_join(plan, &right_scheduler.nodes_plan)
+            }
+            (RunningMode::Cluster, RunningMode::Cluster) => {
+                self.visit_cluster_join(plan, &right_scheduler.nodes_plan)
+            }
+            _ => Err(ErrorCode::LogicalError(
+                "Both sides of join must work on the same mode",
+            )),
+        }
+    }
+
+    fn join_node(plan: &JoinPlan, left: &PlanNode, right: &PlanNode) -> PlanNode {
+        PlanNode::Join(JoinPlan {
+            join_type: plan.join_type,
+            left_keys: plan.left_keys.clone(),
+            right_keys: plan.right_keys.clone(),
+            other_conditions: plan.other_conditions.clone(),
+            left: Arc::new(left.clone()),
+            right: Arc::new(right.clone()),
+            schema: plan.schema(),
+        })
+    }
+
+    fn visit_local_join(&mut self, plan: &JoinPlan, right_nodes_plan: &[PlanNode]) -> Result<()> {
+        let left = &self.nodes_plan[self.local_pos];
+        let right = &right_nodes_plan[self.local_pos];
+        self.nodes_plan[self.local_pos] = Self::join_node(plan, left, right);
+        Ok(())
+    }
+
+    fn visit_cluster_join(&mut self, plan: &JoinPlan, right_nodes_plan: &[PlanNode]) -> Result<()> {
+        for index in 0..self.nodes_plan.len() {
+            let left = &self.nodes_plan[index];
+            let right = &right_nodes_plan[index];
+            self.nodes_plan[index] = Self::join_node(plan, left, right);
+        }
+
+        Ok(())
+    }
+
     fn visit_filter(&mut self, plan: &FilterPlan, tasks: &mut Tasks) -> Result<()> {
         self.visit_plan_node(plan.input.as_ref(), tasks)?;
         match self.running_mode {
@@ -780,6 +845,163 @@ impl PlanScheduler {
         }
     }
 
+    fn visit_array_join(&mut self, plan: &ArrayJoinPlan, tasks: &mut Tasks) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref(), tasks)?;
+        match self.running_mode {
+            RunningMode::Cluster => self.visit_cluster_array_join(plan),
+            RunningMode::Standalone => self.visit_local_array_join(plan),
+        };
+        Ok(())
+    }
+
+    fn visit_local_array_join(&mut self, plan: &ArrayJoinPlan) {
+        self.nodes_plan[self.local_pos] = PlanNode::ArrayJoin(ArrayJoinPlan {
+            array_join_exprs: plan.array_join_exprs.clone(),
+            schema: plan.schema.clone(),
+            input: Arc::new(self.nodes_plan[self.local_pos].clone()),
+        });
+    }
+
+    fn visit_cluster_array_join(&mut self, plan: &ArrayJoinPlan) {
+        for index in 0..self.nodes_plan.len() {
+            self.nodes_plan[index] = PlanNode::ArrayJoin(ArrayJoinPlan {
+                array_join_exprs: plan.array_join_exprs.clone(),
+                schema: plan.schema.clone(),
+                input: Arc::new(self.nodes_plan[index].clone()),
+            });
+        }
+    }
+
+    fn visit_window(&mut self, plan: &WindowPlan, tasks: &mut Tasks) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref(), tasks)?;
+        match self.running_mode {
+            RunningMode::Cluster => self.visit_cluster_window(plan),
+            RunningMode::Standalone => self.visit_local_window(plan),
+        };
+        Ok(())
+    }
+
+    fn visit_local_window(&mut self, plan: &WindowPlan) {
+        self.nodes_plan[self.local_pos] = PlanNode::Window(WindowPlan {
+            window_exprs: plan.window_exprs.clone(),
+            schema: plan.schema.clone(),
+            input: Arc::new(self.nodes_plan[self.local_pos].clone()),
+        });
+    }
+
+    fn visit_cluster_window(&mut self, plan: &WindowPlan) {
+        for index in 0..self.nodes_plan.len() {
+            self.nodes_plan[index] = PlanNode::Window(WindowPlan {
+                window_exprs: plan.window_exprs.clone(),
+                schema: plan.schema.clone(),
+                input: Arc::new(self.nodes_plan[index].clone()),
+            });
+        }
+    }
+
+    fn visit_union(&mut self, plan: &UnionPlan, tasks: &mut Tasks) -> Result<()> {
+        let mut new_inputs = Vec::with_capacity(plan.inputs.len());
+        for input in &plan.inputs {
+            let input_context = QueryContext::new(self.query_context.clone());
+            let mut input_scheduler = PlanScheduler::try_create(input_context)?;
+            input_scheduler.visit_plan_node(input.as_ref(), tasks)?;
+
+            // The inputs are converged to the local node by the scatters optimizer.
+            if let RunningMode::Cluster = input_scheduler.running_mode {
+                return Err(ErrorCode::LogicalError(
+                    "Union cannot work on cluster mode for the time being",
+                ));
+            }
+
+            let new_input = input_scheduler.nodes_plan[self.local_pos].clone();
+            new_inputs.push(Arc::new(new_input));
+        }
+
+        self.running_mode = RunningMode::Standalone;
+        self.nodes_plan[self.local_pos] = PlanNode::Union(UnionPlan {
+            inputs: new_inputs,
+            schema: plan.schema(),
+        });
+
+        Ok(())
+    }
+
+    fn visit_distinct(&mut self, plan: &DistinctPlan, tasks: &mut Tasks) -> Result<()> {
+        self.visit_plan_node(plan.input.as_ref(), tasks)?;
+        match self.running_mode {
+            RunningMode::Cluster => self.visit_cluster_distinct(),
+            RunningMode::Standalone => self.visit_local_distinct(),
+        };
+        Ok(())
+    }
+
+    fn visit_local_distinct(&mut self) {
+        self.nodes_plan[self.local_pos] = PlanNode::Distinct(DistinctPlan {
+            input: Arc::new(self.nodes_plan[self.local_pos].clone()),
+        });
+    }
+
+    fn visit_cluster_distinct(&mut self) {
+        for index in 0..self.nodes_plan.len() {
+            self.nodes_plan[index] = PlanNode::Distinct(DistinctPlan {
+                input: Arc::new(self.nodes_plan[index].clone()),
+            });
+        }
+    }
+
+    fn visit_set_operation(&mut self, plan: &SetOperationPlan, tasks: &mut Tasks) -> Result<()> {
+        self.visit_plan_node(plan.left.as_ref(), tasks)?;
+
+        let right_context = QueryContext::new(self.query_context.clone());
+        let mut right_scheduler = PlanScheduler::try_create(right_context)?;
+        right_scheduler.visit_plan_node(plan.right.as_ref(), tasks)?;
+
+        // Both inputs are converged to the local node by the scatters optimizer.
+        if let (RunningMode::Standalone, RunningMode::Standalone) =
+            (&self.running_mode, &right_scheduler.running_mode)
+        {
+            let new_right = right_scheduler.nodes_plan[self.local_pos].clone();
+            self.nodes_plan[self.local_pos] = PlanNode::SetOperation(SetOperationPlan {
+                op: plan.op,
+                all: plan.all,
+                left: Arc::new(self.nodes_plan[self.local_pos].clone()),
+                right: Arc::new(new_right),
+                schema: plan.schema(),
+            });
+            return Ok(());
+        }
+
+        Err(ErrorCode::LogicalError(format!(
+            "{} cannot work on cluster mode for the time being",
+            plan.op
+        )))
+    }
+
+    fn visit_materialized_cte(
+        &mut self,
+        plan: &MaterializedCtePlan,
+        tasks: &mut Tasks,
+    ) -> Result<()> {
+        let input_context = QueryContext::new(self.query_context.clone());
+        let mut input_scheduler = PlanScheduler::try_create(input_context)?;
+        input_scheduler.visit_plan_node(plan.input.as_ref(), tasks)?;
+
+        // The CTEs in cluster mode are inlined by the scatters optimizer.
+        if let RunningMode::Cluster = input_scheduler.running_mode {
+            return Err(ErrorCode::LogicalError(
+                "Materialized CTE cannot work on cluster mode for the time being",
+            ));
+        }
+
+        self.running_mode = RunningMode::Standalone;
+        self.nodes_plan[self.local_pos] = PlanNode::MaterializedCte(MaterializedCtePlan {
+            name: plan.name.clone(),
+            input: Arc::new(input_scheduler.nodes_plan[self.local_pos].clone()),
+        });
+
+        Ok(())
+    }
+
     fn visit_data_source(&mut self, plan: &ReadDataSourcePlan, _: &mut Tasks) -> Result<()> {
         let table = self.query_context.build_table_from_source_plan(plan)?;
 
@@ -15,6 +15,7 @@
 use std::sync::Arc;
 
 use common_base::tokio;
+use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataValue;
 use common_exception::Result;
 use common_planners::*;
@@ -324,6 +325,66 @@ async fn test_scheduler_plan_with_convergent_and_normal_stage() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_scheduler_plan_with_cluster_join() -> Result<()> {
+    // The left side is shuffled in cluster and the right side is broadcast to all nodes,
+    // each node joins its own partition of the left side with the whole right side.
+    let context = create_env().await?;
+    let plan_scheduler = PlanScheduler::try_create(context)?;
+    let scheduled_tasks = plan_scheduler.reschedule(&PlanNode::Stage(StagePlan {
+        kind: StageKind::Convergent,
+        scatters_expr: Expression::create_literal(DataValue::UInt64(Some(1))),
+        input: Arc::new(PlanNode::Join(JoinPlan {
+            join_type: JoinType::Inner,
+            left_keys: vec![],
+            right_keys: vec![],
+            other_conditions: None,
+            schema: DataSchemaRefExt::create(vec![]),
+            left: Arc::new(PlanNode::Stage(StagePlan {
+                kind: StageKind::Normal,
+                scatters_expr: Expression::create_literal(DataValue::UInt64(Some(0))),
+                input: Arc::new(PlanNode::Empty(EmptyPlan::cluster())),
+            })),
+            right: Arc::new(PlanNode::Broadcast(BroadcastPlan {
+                input: Arc::new(PlanNode::Empty(EmptyPlan::cluster())),
+            })),
+        })),
+    }))?;
+
+    let mut broadcast_actions = vec![];
+    let mut converge_actions = vec![];
+    for (node, remote_action) in scheduled_tasks.get_tasks()? {
+        match remote_action {
+            FlightAction::CancelAction(_) => panic!(),
+            FlightAction::BroadcastAction(action) => broadcast_actions.push((node, action)),
+            FlightAction::PrepareShuffleAction(action) => {
+                if action.sinks == vec![String::from("dummy_local")] {
+                    converge_actions.push((node, action));
+                }
+            }
+        }
+    }
+
+    assert_eq!(broadcast_actions.len(), 2);
+    assert_eq!(converge_actions.len(), 2);
+    for (node, action) in converge_actions {
+        match &action.plan {
+            PlanNode::Join(join) => match (&*join.left, &*join.right) {
+                (PlanNode::Remote(left), PlanNode::Remote(right)) => {
+                    assert_eq!(left.stream_id, node.id);
+                    assert_eq!(right.stream_id, node.id);
+                    assert_eq!(left.fetch_nodes, ["dummy_local", "dummy"]);
+                    assert_eq!(right.fetch_nodes, ["dummy_local", "dummy"]);
+                }
+                _ => panic!("test_scheduler_plan_with_cluster_join must be have Remote plan!"),
+            },
+            _ => panic!("test_scheduler_plan_with_cluster_join must be have Join plan!"),
+        }
+    }
+
+    Ok(())
+}
+
 async fn create_env() -> Result<Arc<QueryContext>> {
     try_create_cluster_context(
         ClusterDescriptor::new()
@@ -0,0 +1,468 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::DataValue;
+use common_planners::Expression;
+use common_planners::JoinPlan;
+use common_planners::JoinType;
+use common_planners::PlanNode;
+use common_planners::ReadDataSourcePlan;
+use common_planners::SetOperator;
+
+// The selectivities of the predicates which cannot be estimated by the statistics.
+const DEFAULT_EQUAL_SELECTIVITY: f64 = 0.1;
+const DEFAULT_RANGE_SELECTIVITY: f64 = 1.0 / 3.0;
+const DEFAULT_SELECTIVITY: f64 = 0.25;
+// Building the hash table of the right side of a join costs more than probing it.
+const JOIN_BUILD_COST_FACTOR: f64 = 2.0;
+
+/// The estimated statistics of an output column of a plan.
+#[derive(Clone, Debug)]
+pub struct ColumnEstimation {
+    pub distinct_count: f64,
+    pub null_fraction: f64,
+    /// The range of the numeric columns, if it is known.
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+/// The estimated output of a plan, and the cost to compute it, which is measured by the
+/// number of rows processed.
+#[derive(Clone, Debug)]
+pub struct PlanEstimation {
+    pub rows: f64,
+    pub row_bytes: f64,
+    pub cost: f64,
+    pub columns: HashMap<String, ColumnEstimation>,
+}
+
+impl ColumnEstimation {
+    /// Every value is assumed to be distinct if there is no statistics.
+    fn unknown(rows: f64) -> ColumnEstimation {
+        ColumnEstimation {
+            distinct_count: rows,
+            null_fraction: 0.0,
+            min: None,
+            max: None,
+        }
+    }
+}
+
+impl PlanEstimation {
+    pub fn bytes(&self) -> f64 {
+        self.rows * self.row_bytes
+    }
+
+    pub fn column(&self, name: &str) -> ColumnEstimation {
+        match self.columns.get(name) {
+            Some(column) => column.clone(),
+            None => ColumnEstimation::unknown(self.rows),
+        }
+    }
+
+    /// The estimation of the expression, the expressions other than columns are unknown.
+    pub fn expression(&self, expr: &Expression) -> ColumnEstimation {
+        match self.known_expression(expr) {
+            Some(column) => column,
+            None => ColumnEstimation::unknown(self.rows),
+        }
+    }
+
+    // The estimation of the expression, if it is known by the statistics.
+    fn known_expression(&self, expr: &Expression) -> Option<ColumnEstimation> {
+        match expr {
+            Expression::Alias(_, expr) | Expression::Cast { expr, .. } => {
+                self.known_expression(expr)
+            }
+            Expression::Literal { .. } => Some(ColumnEstimation {
+                distinct_count: 1.0,
+                ..ColumnEstimation::unknown(self.rows)
+            }),
+            expr => self.columns.get(&expr.column_name()).cloned(),
+        }
+    }
+
+    // The output of the filters, the distinct values are no more than the rows.
+    fn with_rows(mut self, rows: f64) -> PlanEstimation {
+        self.rows = rows.max(0.0);
+        for column in self.columns.values_mut() {
+            column.distinct_count = column.distinct_count.min(self.rows);
+        }
+
+        self
+    }
+
+    fn with_cost(mut self, cost: f64) -> PlanEstimation {
+        self.cost += cost;
+        self
+    }
+}
+
+/// Estimates the cardinality of the plans by the statistics of the sources and the
+/// selectivity of the predicates, and the cost of the plans for the cost-based optimizers.
+pub struct CostModel;
+
+impl CostModel {
+    pub fn estimate(plan: &PlanNode) -> PlanEstimation {
+        match plan {
+            PlanNode::ReadSource(plan) => Self::estimate_read_source(plan),
+            PlanNode::Filter(plan) => Self::estimate_filter(&plan.predicate, &plan.input),
+            PlanNode::Having(plan) => Self::estimate_filter(&plan.predicate, &plan.input),
+            PlanNode::Expression(plan) => Self::estimate_expressions(&plan.exprs, &plan.input),
+            PlanNode::Projection(plan) => Self::estimate_expressions(&plan.expr, &plan.input),
+            PlanNode::AggregatorPartial(plan) => {
+                Self::estimate_aggregate(&plan.group_expr, &plan.input)
+            }
+            PlanNode::AggregatorFinal(plan) => {
+                Self::estimate_aggregate(&plan.group_expr, &plan.input)
+            }
+            PlanNode::Join(plan) => Self::estimate_join(plan),
+            PlanNode::Sort(plan) => {
+                let input = Self::estimate(&plan.input);
+                let cost = input.rows * input.rows.max(2.0).log2();
+                input.with_cost(cost)
+            }
+            PlanNode::Limit(plan) => {
+                let input = Self::estimate(&plan.input);
+                let rows = (input.rows - plan.offset as f64).max(0.0);
+                let rows = plan.n.map_or(rows, |n| rows.min(n as f64));
+                input.with_rows(rows)
+            }
+            PlanNode::Distinct(plan) => {
+                let input = Self::estimate(&plan.input);
+                let fields = plan.input.schema().fields().clone();
+                let distinct_counts = fields.iter().map(|f| input.column(f.name()).distinct_count);
+                let rows = Self::groups(distinct_counts.product(), input.rows);
+                let cost = input.rows;
+                input.with_rows(rows).with_cost(cost)
+            }
+            PlanNode::Union(plan) => {
+                let inputs = plan.inputs.iter().map(|input| Self::estimate(input));
+                inputs
+                    .reduce(Self::estimate_union)
+                    .unwrap_or_else(Self::estimate_empty)
+            }
+            PlanNode::SetOperation(plan) => {
+                let left = Self::estimate(&plan.left);
+                let right = Self::estimate(&plan.right);
+                match plan.op {
+                    SetOperator::Union => Self::estimate_union(left, right),
+                    // The intersection and the difference are no more than the left side.
+                    _ => {
+                        let cost = left.rows + right.rows + right.cost;
+                        left.with_cost(cost)
+                    }
+                }
+            }
+            // Sending the rows to the other nodes costs as much as processing them.
+            PlanNode::Stage(_) | PlanNode::Broadcast(_) => {
+                let input = Self::estimate(&plan.inputs()[0]);
+                let cost = input.rows;
+                input.with_cost(cost)
+            }
+            plan => match plan.inputs().first() {
+                Some(input) => Self::estimate(input),
+                None => Self::estimate_empty(),
+            },
+        }
+    }
+
+    /// The fraction of the input rows which satisfy the predicate.
+    pub fn selectivity(predicate: &Expression, input: &PlanEstimation) -> f64 {
+        let selectivity = match predicate {
+            Expression::Alias(_, expr) => Self::selectivity(expr, input),
+            Expression::Literal { value, .. } => match value {
+                DataValue::Boolean(Some(true)) => 1.0,
+                DataValue::Boolean(Some(false)) => 0.0,
+                value if value.is_null() => 0.0,
+                _ => DEFAULT_SELECTIVITY,
+            },
+            Expression::BinaryExpression { op, left, right } => match op.to_lowercase().as_str() {
+                "and" => Self::selectivity(left, input) * Self::selectivity(right, input),
+                "or" => {
+                    let left = Self::selectivity(left, input);
+                    let right = Self::selectivity(right, input);
+                    left + right - left * right
+                }
+                op @ ("=" | "<>" | "!=" | "<" | "<=" | ">" | ">=") => {
+                    Self::comparison_selectivity(op, left, right, input)
+                }
+                _ => DEFAULT_SELECTIVITY,
+            },
+            Expression::UnaryExpression { op, expr } if op.to_lowercase() == "not" => {
+                1.0 - Self::selectivity(expr, input)
+            }
+            Expression::ScalarFunction { op, args } if args.len() == 1 => {
+                let column = input.expression(&args[0]);
+                match op.to_lowercase().as_str() {
+                    "isnull" => column.null_fraction,
+                    "isnotnull" => 1.0 - column.null_fraction,
+                    _ => DEFAULT_SELECTIVITY,
+                }
+            }
+            _ => DEFAULT_SELECTIVITY,
+        };
+
+        selectivity.clamp(0.0, 1.0)
+    }
+
+    fn comparison_selectivity(
+        op: &str,
+        left: &Expression,
+        right: &Expression,
+        input: &PlanEstimation,
+    ) -> f64 {
+        // Normalized to `expr op literal`.
+        let (expr, op, value) = match (Self::literal(left), Self::literal(right)) {
+            (None, Some(value)) => (left, op, value),
+            (Some(value), None) => (right, Self::flip(op), value),
+            (Some(_), Some(_)) => return DEFAULT_SELECTIVITY,
+            (None, None) => {
+                let left = input.expression(left);
+                let right = input.expression(right);
+                let distinct_count = left.distinct_count.max(right.distinct_count);
+                return match op {
+                    "=" => 1.0 / distinct_count.max(1.0),
+                    "<>" | "!=" => 1.0 - 1.0 / distinct_count.max(1.0),
+                    _ => DEFAULT_RANGE_SELECTIVITY,
+                };
+            }
+        };
+
+        // Comparing with NULL is never true.
+        if value.is_null() {
+            return 0.0;
+        }
+
+        let column = input.expression(expr);
+        let not_null = 1.0 - column.null_fraction;
+        match op {
+            "=" => not_null / column.distinct_count.max(1.0),
+            "<>" | "!=" => not_null * (1.0 - 1.0 / column.distinct_count.max(1.0)),
+            op => not_null * Self::range_selectivity(op, &column, Self::numeric(value)),
+        }
+    }
+
+    // The values are assumed to be uniformly distributed in the range of the column.
+    fn range_selectivity(op: &str, column: &ColumnEstimation, value: Option<f64>) -> f64 {
+        let (min, max, value) = match (column.min, column.max, value) {
+            (Some(min), Some(max), Some(value)) => (min, max, value),
+            _ => return DEFAULT_RANGE_SELECTIVITY,
+        };
+
+        if max <= min {
+            let satisfied = match op {
+                "<" => min < value,
+                "<=" => min <= value,
+                ">" => min > value,
+                _ => min >= value,
+            };
+            return match satisfied {
+                true => 1.0,
+                false => 0.0,
+            };
+        }
+
+        let fraction = match op {
+            "<" | "<=" => (value - min) / (max - min),
+            _ => (max - value) / (max - min),
+        };
+        fraction.clamp(0.0, 1.0)
+    }
+
+    fn literal(expr: &Expression) -> Option<&DataValue> {
+        match expr {
+            Expression::Literal { value, .. } => Some(value),
+            Expression::Cast { expr, .. } => Self::literal(expr),
+            _ => None,
+        }
+    }
+
+    fn numeric(value: &DataValue) -> Option<f64> {
+        match value {
+            DataValue::Int8(Some(v)) => Some(*v as f64),
+            DataValue::Int16(Some(v)) => Some(*v as f64),
+            DataValue::Int32(Some(v)) => Some(*v as f64),
+            DataValue::Int64(Some(v)) => Some(*v as f64),
+            DataValue::UInt8(Some(v)) => Some(*v as f64),
+            DataValue::UInt16(Some(v)) => Some(*v as f64),
+            DataValue::UInt32(Some(v)) => Some(*v as f64),
+            DataValue::UInt64(Some(v)) => Some(*v as f64),
+            DataValue::Float32(Some(v)) => Some(*v as f64),
+            DataValue::Float64(Some(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    // `literal op expr` is `expr flip(op) literal`.
+    fn flip(op: &str) -> &str {
+        match op {
+            "<" => ">",
+            "<=" => ">=",
+            ">" => "<",
+            ">=" => "<=",
+            op => op,
+        }
+    }
+
+    /// The ratio of the groups to the input rows of the aggregation, it is known only if the
+    /// distinct counts of all the group keys are known by the statistics.
+    pub fn groups_ratio(group_exprs: &[Expression], input: &PlanNode) -> Option<f64> {
+        let input = Self::estimate(input);
+        let mut distinct_count = 1.0;
+        for expr in group_exprs {
+            distinct_count *= input.known_expression(expr)?.distinct_count;
+        }
+
+        Some(Self::groups(distinct_count, input.rows) / input.rows.max(1.0))
+    }
+
+    fn groups(distinct_count: f64, rows: f64) -> f64 {
+        distinct_count.min(rows).max(rows.min(1.0))
+    }
+
+    fn estimate_empty() -> PlanEstimation {
+        PlanEstimation {
+            rows: 1.0,
+            row_bytes: 0.0,
+            cost: 0.0,
+            columns: HashMap::new(),
+        }
+    }
+
+    fn estimate_read_source(plan: &ReadDataSourcePlan) -> PlanEstimation {
+        let statistics = &plan.statistics;
+        let rows = statistics.read_rows as f64;
+        let mut columns = HashMap::with_capacity(statistics.column_statistics.len());
+        for (name, column) in &statistics.column_statistics {
+            let not_null_rows = (rows - column.null_count as f64).max(0.0);
+            let distinct_count = column.distinct_count.map(|count| count as f64);
+            columns.insert(name.clone(), ColumnEstimation {
+                distinct_count: distinct_count.unwrap_or(not_null_rows),
+                null_fraction: column.null_count as f64 / rows.max(1.0),
+                min: Self::numeric(&column.min),
+                max: Self::numeric(&column.max),
+            });
+        }
+
+        PlanEstimation {
+            rows,
+            row_bytes: statistics.read_bytes as f64 / rows.max(1.0),
+            cost: rows,
+            columns,
+        }
+    }
+
+    fn estimate_filter(predicate: &Expression, input: &PlanNode) -> PlanEstimation {
+        let input = Self::estimate(input);
+        let rows = input.rows * Self::selectivity(predicate, &input);
+        let cost = input.rows;
+        input.with_rows(rows).with_cost(cost)
+    }
+
+    fn estimate_expressions(exprs: &[Expression], input: &PlanNode) -> PlanEstimation {
+        let mut estimation = Self::estimate(input);
+        for expr in exprs {
+            if let Some(column) = estimation.known_expression(expr) {
+                estimation.columns.insert(expr.column_name(), column);
+            }
+        }
+
+        let cost = estimation.rows;
+        estimation.with_cost(cost)
+    }
+
+    fn estimate_aggregate(group_exprs: &[Expression], input: &PlanNode) -> PlanEstimation {
+        let input = Self::estimate(input);
+        let groups: f64 = match group_exprs.is_empty() {
+            true => 1.0,
+            false => group_exprs
+                .iter()
+                .map(|expr| input.expression(expr).distinct_count)
+                .product(),
+        };
+
+        let cost = input.rows;
+        let rows = Self::groups(groups, input.rows);
+        input.with_rows(rows).with_cost(cost)
+    }
+
+    fn estimate_union(left: PlanEstimation, right: PlanEstimation) -> PlanEstimation {
+        let mut columns = left.columns;
+        for (name, column) in columns.iter_mut() {
+            if let Some(right) = right.columns.get(name) {
+                column.distinct_count += right.distinct_count;
+                column.min = column.min.zip(right.min).map(|(l, r)| l.min(r));
+                column.max = column.max.zip(right.max).map(|(l, r)| l.max(r));
+            }
+        }
+
+        let rows = left.rows + right.rows;
+        PlanEstimation {
+            rows,
+            row_bytes: left.row_bytes.max(right.row_bytes),
+            cost: left.cost + right.cost + rows,
+            columns,
+        }
+    }
+
+    fn estimate_join(plan: &JoinPlan) -> PlanEstimation {
+        let left = Self::estimate(&plan.left);
+        let right = Self::estimate(&plan.right);
+
+        // The rows of the right side matched by each distinct key of the left side.
+        let mut key_selectivity = 1.0;
+        let mut semi_selectivity: f64 = 1.0;
+        for (left_key, right_key) in plan.left_keys.iter().zip(plan.right_keys.iter()) {
+            let left_distinct = left.expression(left_key).distinct_count.max(1.0);
+            let right_distinct = right.expression(right_key).distinct_count.max(1.0);
+            key_selectivity /= left_distinct.max(right_distinct);
+            semi_selectivity = semi_selectivity.min(right_distinct / left_distinct);
+        }
+
+        let mut joined = PlanEstimation {
+            rows: left.rows * right.rows,
+            row_bytes: left.row_bytes + right.row_bytes,
+            cost: 0.0,
+            columns: left.columns.clone(),
+        };
+        joined.columns.extend(right.columns.clone());
+
+        let other_selectivity = match &plan.other_conditions {
+            None => 1.0,
+            Some(conditions) => Self::selectivity(conditions, &joined),
+        };
+
+        let matched = left.rows * right.rows * key_selectivity * other_selectivity;
+        let semi = match right.rows > 0.0 {
+            true => left.rows * semi_selectivity.min(1.0) * other_selectivity,
+            false => 0.0,
+        };
+
+        let (rows, mut estimation) = match plan.join_type {
+            JoinType::Inner => (matched, joined),
+            JoinType::Left => (matched.max(left.rows), joined),
+            JoinType::Right => (matched.max(right.rows), joined),
+            JoinType::Full => (matched.max(left.rows).max(right.rows), joined),
+            JoinType::Semi => (semi, left.clone()),
+            JoinType::Anti | JoinType::NullAwareAnti => (left.rows - semi, left.clone()),
+        };
+
+        estimation.cost =
+            left.cost + right.cost + left.rows + right.rows * JOIN_BUILD_COST_FACTOR + rows;
+        estimation.with_rows(rows)
+    }
+}
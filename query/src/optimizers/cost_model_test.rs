@@ -0,0 +1,158 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use common_planners::PlanNode;
+use pretty_assertions::assert_eq;
+
+use crate::optimizers::CostModel;
+use crate::tests::parse_query;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_cost_model_estimate() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        query: &'static str,
+        expect_rows: &'static str,
+    }
+
+    let tests = vec![
+        Test {
+            name: "Table scan",
+            query: "SELECT number FROM numbers_mt(100)",
+            expect_rows: "100",
+        },
+        Test {
+            name: "Equal predicate",
+            query: "SELECT number FROM numbers_mt(100) WHERE number = 5",
+            expect_rows: "1",
+        },
+        Test {
+            name: "Range predicate",
+            query: "SELECT number FROM numbers_mt(100) WHERE number < 10",
+            expect_rows: "10",
+        },
+        Test {
+            name: "Disjunctive predicates",
+            query: "SELECT number FROM numbers_mt(100) WHERE number < 50 OR number = 60",
+            expect_rows: "51",
+        },
+        Test {
+            name: "Null predicate",
+            query: "SELECT number FROM numbers_mt(100) WHERE number = NULL",
+            expect_rows: "0",
+        },
+        Test {
+            name: "Aggregate without group by",
+            query: "SELECT count() FROM numbers_mt(100)",
+            expect_rows: "1",
+        },
+        Test {
+            name: "Aggregate with group by column",
+            query: "SELECT count() FROM numbers_mt(100) WHERE number < 10 GROUP BY number",
+            expect_rows: "10",
+        },
+        Test {
+            name: "Limit",
+            query: "SELECT number FROM numbers_mt(100) LIMIT 3",
+            expect_rows: "3",
+        },
+        Test {
+            name: "Join on keys",
+            query: "SELECT a.number FROM numbers_mt(10) AS a JOIN numbers_mt(100) AS b ON a.number = b.number",
+            expect_rows: "10",
+        },
+        Test {
+            name: "Cross join",
+            query: "SELECT a.number FROM numbers_mt(10) AS a, numbers_mt(100) AS b",
+            expect_rows: "1000",
+        },
+    ];
+
+    for test in tests {
+        let ctx = crate::tests::try_create_context()?;
+        let plan = parse_query(test.query, &ctx)?;
+        let estimation = CostModel::estimate(&plan);
+        let actual = format!("{:.0}", estimation.rows);
+        assert_eq!(test.expect_rows, actual, "{:#?}", test.name);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_cost_model_join_cost() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    // Building the hash table on the smaller side is cheaper.
+    let small_build_side = parse_query(
+        "SELECT a.number FROM numbers_mt(100) AS a JOIN numbers_mt(10) AS b ON a.number = b.number",
+        &ctx,
+    )?;
+    let large_build_side = parse_query(
+        "SELECT a.number FROM numbers_mt(10) AS a JOIN numbers_mt(100) AS b ON a.number = b.number",
+        &ctx,
+    )?;
+
+    let small_build_side = CostModel::estimate(&small_build_side);
+    let large_build_side = CostModel::estimate(&large_build_side);
+    assert_eq!(small_build_side.rows, large_build_side.rows);
+    assert!(small_build_side.cost < large_build_side.cost);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_cost_model_groups_ratio() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        query: &'static str,
+        expect_ratio: Option<&'static str>,
+    }
+
+    let tests = vec![
+        Test {
+            name: "Group by distinct column",
+            query: "SELECT count() FROM numbers_mt(100) GROUP BY number",
+            expect_ratio: Some("1.00"),
+        },
+        Test {
+            name: "Group by expression",
+            query: "SELECT count() FROM numbers_mt(100) GROUP BY number % 3",
+            expect_ratio: None,
+        },
+    ];
+
+    for test in tests {
+        let ctx = crate::tests::try_create_context()?;
+        let mut plan = parse_query(test.query, &ctx)?;
+        while !matches!(plan, PlanNode::AggregatorPartial(_)) {
+            plan = plan.inputs()[0].as_ref().clone();
+        }
+
+        if let PlanNode::AggregatorPartial(plan) = plan {
+            let ratio = CostModel::groups_ratio(&plan.group_expr, &plan.input);
+            let actual = ratio.map(|ratio| format!("{:.2}", ratio));
+            assert_eq!(
+                test.expect_ratio.map(String::from),
+                actual,
+                "{:#?}",
+                test.name
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -12,11 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod cost_model_test;
 #[cfg(test)]
 mod optimizer_constant_folding_test;
 #[cfg(test)]
 mod optimizer_expression_transform_test;
 #[cfg(test)]
+mod optimizer_join_reorder_test;
+#[cfg(test)]
 mod optimizer_scatters_test;
 #[cfg(test)]
 mod optimizer_statistics_exact_test;
@@ -28,19 +32,25 @@ mod utils_test;
 #[cfg(test)]
 mod optimizer_top_n_push_down_test;
 
+mod cost_model;
 mod metrics;
 mod optimizer;
 mod optimizer_constant_folding;
 mod optimizer_expression_transform;
+mod optimizer_join_reorder;
 mod optimizer_scatters;
 mod optimizer_statistics_exact;
 mod optimizer_top_n_push_down;
 mod utils;
 
+pub use cost_model::ColumnEstimation;
+pub use cost_model::CostModel;
+pub use cost_model::PlanEstimation;
 pub use optimizer::Optimizer;
 pub use optimizer::Optimizers;
 pub use optimizer_constant_folding::ConstantFoldingOptimizer;
 pub use optimizer_expression_transform::ExprTransformOptimizer;
+pub use optimizer_join_reorder::JoinReorderOptimizer;
 pub use optimizer_scatters::ScattersOptimizer;
 pub use optimizer_statistics_exact::StatisticsExactOptimizer;
 pub use optimizer_top_n_push_down::TopNPushDownOptimizer;
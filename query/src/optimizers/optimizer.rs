@@ -22,6 +22,7 @@ use metrics::histogram;
 use crate::optimizers::optimizer_scatters::ScattersOptimizer;
 use crate::optimizers::ConstantFoldingOptimizer;
 use crate::optimizers::ExprTransformOptimizer;
+use crate::optimizers::JoinReorderOptimizer;
 use crate::optimizers::StatisticsExactOptimizer;
 use crate::optimizers::TopNPushDownOptimizer;
 use crate::sessions::QueryContext;
@@ -50,7 +51,8 @@ impl Optimizers {
                 Box::new(ConstantFoldingOptimizer::create(ctx.clone())),
                 Box::new(ExprTransformOptimizer::create(ctx.clone())),
                 Box::new(TopNPushDownOptimizer::create(ctx.clone())),
-                Box::new(StatisticsExactOptimizer::create(ctx)),
+                Box::new(StatisticsExactOptimizer::create(ctx.clone())),
+                Box::new(JoinReorderOptimizer::create(ctx)),
             ],
         }
     }
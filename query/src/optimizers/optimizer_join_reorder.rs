@@ -0,0 +1,363 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_planners::AggregatorFinalPlan;
+use common_planners::AggregatorPartialPlan;
+use common_planners::Expression;
+use common_planners::FilterPlan;
+use common_planners::JoinPlan;
+use common_planners::JoinType;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::PlanRewriter;
+
+use crate::optimizers::CostModel;
+use crate::optimizers::Optimizer;
+use crate::optimizers::RequireColumnsVisitor;
+use crate::sessions::QueryContext;
+
+/// Picks the order of the inner joins by the estimated cost, and puts the smaller side of
+/// the joins on the right side, which is collected into the hash table.
+pub struct JoinReorderOptimizer {}
+
+struct JoinReorderImpl {}
+
+// The inputs of a tree of inner joins, and the conjunctions of the conditions to join them.
+#[derive(Default)]
+struct InnerJoins {
+    inputs: Vec<PlanNode>,
+    conditions: Vec<Expression>,
+}
+
+// A join of the inputs, with the conditions which cannot be evaluated by it yet.
+struct JoinCandidate {
+    plan: PlanNode,
+    remaining_conditions: Vec<Expression>,
+    cost: f64,
+}
+
+impl PlanRewriter for JoinReorderImpl {
+    fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        PlanBuilder::from(&new_input)
+            .aggregate_partial(&plan.aggr_expr, &plan.group_expr)?
+            .build()
+    }
+
+    fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        let schema_before_group_by = plan.schema_before_group_by.clone();
+        PlanBuilder::from(&new_input)
+            .aggregate_final(schema_before_group_by, &plan.aggr_expr, &plan.group_expr)?
+            .build()
+    }
+
+    fn rewrite_filter(&mut self, plan: &FilterPlan) -> Result<PlanNode> {
+        let join = match plan.input.as_ref() {
+            PlanNode::Join(join) if join.join_type == JoinType::Inner => join,
+            input => {
+                let new_input = self.rewrite_plan_node(input)?;
+                let new_predicate = self.rewrite_expr(&new_input.schema(), &plan.predicate)?;
+                return PlanBuilder::from(&new_input).filter(new_predicate)?.build();
+            }
+        };
+
+        // The equalities of the columns in the WHERE of comma separated tables are the keys.
+        let mut joins = InnerJoins::default();
+        self.collect_inner_joins(join, &mut joins)?;
+        let mut predicates = Vec::new();
+        for conjunction in Self::split_conjunctions(&plan.predicate) {
+            match Self::is_columns_equality(&conjunction) {
+                true => joins.conditions.push(conjunction),
+                false => predicates.push(conjunction),
+            }
+        }
+
+        let new_input = Self::reorder(joins, &join.schema)?;
+        match predicates.into_iter().reduce(|left, right| left.and(right)) {
+            None => Ok(new_input),
+            Some(predicate) => {
+                let new_predicate = self.rewrite_expr(&new_input.schema(), &predicate)?;
+                PlanBuilder::from(&new_input).filter(new_predicate)?.build()
+            }
+        }
+    }
+
+    fn rewrite_join(&mut self, plan: &JoinPlan) -> Result<PlanNode> {
+        if plan.join_type == JoinType::Inner {
+            let mut joins = InnerJoins::default();
+            self.collect_inner_joins(plan, &mut joins)?;
+            return Self::reorder(joins, &plan.schema);
+        }
+
+        let new_left = self.rewrite_plan_node(&plan.left)?;
+        let new_right = self.rewrite_plan_node(&plan.right)?;
+        let left_rows = CostModel::estimate(&new_left).rows;
+        let right_rows = CostModel::estimate(&new_right).rows;
+
+        // The probe side of the RIGHT (and FULL) joins is merged into a single processor to emit the
+        // unmatched rows of the build side, which the cost model does not take into account. So a
+        // RIGHT join with the larger build side is swapped into a LEFT join, but never the reverse.
+        let swapped_join_type = match plan.join_type {
+            JoinType::Right if right_rows > left_rows => JoinType::Left,
+            _ => {
+                return PlanBuilder::from(&new_left)
+                    .join(
+                        &new_right,
+                        plan.join_type,
+                        &plan.left_keys,
+                        &plan.right_keys,
+                        plan.other_conditions.clone(),
+                    )?
+                    .build();
+            }
+        };
+
+        let swapped = PlanBuilder::from(&new_right)
+            .join(
+                &new_left,
+                swapped_join_type,
+                &plan.right_keys,
+                &plan.left_keys,
+                plan.other_conditions.clone(),
+            )?
+            .build()?;
+        Self::restore_columns_order(swapped, &plan.schema)
+    }
+}
+
+impl JoinReorderImpl {
+    fn collect_inner_joins(&mut self, plan: &JoinPlan, joins: &mut InnerJoins) -> Result<()> {
+        for input in [&plan.left, &plan.right] {
+            match input.as_ref() {
+                PlanNode::Join(join) if join.join_type == JoinType::Inner => {
+                    self.collect_inner_joins(join, joins)?
+                }
+                input => joins.inputs.push(self.rewrite_plan_node(input)?),
+            }
+        }
+
+        for (left_key, right_key) in plan.left_keys.iter().zip(plan.right_keys.iter()) {
+            joins.conditions.push(left_key.eq(right_key.clone()));
+        }
+
+        if let Some(conditions) = &plan.other_conditions {
+            joins
+                .conditions
+                .extend(Self::split_conjunctions(conditions));
+        }
+
+        Ok(())
+    }
+
+    // Starts from the cheapest join of two inputs, then joins the input which makes the
+    // cheapest join one by one.
+    fn reorder(joins: InnerJoins, schema: &DataSchemaRef) -> Result<PlanNode> {
+        let mut inputs = joins.inputs;
+        let mut best: Option<(usize, usize, JoinCandidate)> = None;
+        for left in 0..inputs.len() {
+            for right in left + 1..inputs.len() {
+                let candidate = Self::join(&inputs[left], &inputs[right], &joins.conditions)?;
+                if best
+                    .as_ref()
+                    .map_or(true, |(_, _, best)| candidate.cost < best.cost)
+                {
+                    best = Some((left, right, candidate));
+                }
+            }
+        }
+
+        let mut joined = match best {
+            None => return Ok(inputs.remove(0)),
+            Some((left, right, candidate)) => {
+                inputs.remove(right);
+                inputs.remove(left);
+                candidate
+            }
+        };
+
+        while !inputs.is_empty() {
+            let mut best: Option<(usize, JoinCandidate)> = None;
+            for (index, input) in inputs.iter().enumerate() {
+                let candidate = Self::join(&joined.plan, input, &joined.remaining_conditions)?;
+                if best
+                    .as_ref()
+                    .map_or(true, |(_, best)| candidate.cost < best.cost)
+                {
+                    best = Some((index, candidate));
+                }
+            }
+
+            if let Some((index, candidate)) = best {
+                inputs.remove(index);
+                joined = candidate;
+            }
+        }
+
+        let mut plan = joined.plan;
+        let remaining_conditions = joined.remaining_conditions.into_iter();
+        if let Some(predicate) = remaining_conditions.reduce(|left, right| left.and(right)) {
+            plan = PlanBuilder::from(&plan).filter(predicate)?.build()?;
+        }
+
+        Self::restore_columns_order(plan, schema)
+    }
+
+    fn join(left: &PlanNode, right: &PlanNode, conditions: &[Expression]) -> Result<JoinCandidate> {
+        let (left, right) = match CostModel::estimate(right).rows > CostModel::estimate(left).rows {
+            true => (right, left),
+            false => (left, right),
+        };
+
+        let left_columns = Self::columns_name(&left.schema());
+        let right_columns = Self::columns_name(&right.schema());
+        let mut left_keys = Vec::new();
+        let mut right_keys = Vec::new();
+        let mut other_conditions = Vec::new();
+        let mut remaining_conditions = Vec::new();
+        for condition in conditions {
+            let columns = RequireColumnsVisitor::collect_columns_from_expr(condition)?;
+            if !columns
+                .iter()
+                .all(|column| left_columns.contains(column) || right_columns.contains(column))
+            {
+                remaining_conditions.push(condition.clone());
+                continue;
+            }
+
+            if let Expression::BinaryExpression {
+                op,
+                left: left_expr,
+                right: right_expr,
+            } = condition
+            {
+                if op == "=" {
+                    let left_refer = RequireColumnsVisitor::collect_columns_from_expr(left_expr)?;
+                    let right_refer = RequireColumnsVisitor::collect_columns_from_expr(right_expr)?;
+                    if !left_refer.is_empty() && !right_refer.is_empty() {
+                        if left_refer.is_subset(&left_columns)
+                            && right_refer.is_subset(&right_columns)
+                        {
+                            left_keys.push(left_expr.as_ref().clone());
+                            right_keys.push(right_expr.as_ref().clone());
+                            continue;
+                        }
+
+                        if left_refer.is_subset(&right_columns)
+                            && right_refer.is_subset(&left_columns)
+                        {
+                            left_keys.push(right_expr.as_ref().clone());
+                            right_keys.push(left_expr.as_ref().clone());
+                            continue;
+                        }
+                    }
+                }
+            }
+
+            other_conditions.push(condition.clone());
+        }
+
+        let other_conditions = other_conditions
+            .into_iter()
+            .reduce(|left, right| left.and(right));
+        let plan = PlanBuilder::from(left)
+            .join(
+                right,
+                JoinType::Inner,
+                &left_keys,
+                &right_keys,
+                other_conditions,
+            )?
+            .build()?;
+
+        let cost = CostModel::estimate(&plan).cost;
+        Ok(JoinCandidate {
+            plan,
+            remaining_conditions,
+            cost,
+        })
+    }
+
+    // The columns are projected in the order before reordering, if they are reordered.
+    fn restore_columns_order(plan: PlanNode, schema: &DataSchemaRef) -> Result<PlanNode> {
+        let columns_name = |schema: &DataSchemaRef| {
+            let fields = schema.fields().iter();
+            fields.map(|field| field.name().clone()).collect::<Vec<_>>()
+        };
+
+        let expected_columns = columns_name(schema);
+        match columns_name(&plan.schema()) == expected_columns {
+            true => Ok(plan),
+            false => {
+                let exprs = expected_columns
+                    .into_iter()
+                    .map(Expression::Column)
+                    .collect::<Vec<_>>();
+                PlanBuilder::from(&plan).project(&exprs)?.build()
+            }
+        }
+    }
+
+    fn columns_name(schema: &DataSchemaRef) -> HashSet<String> {
+        let fields = schema.fields().iter();
+        fields.map(|field| field.name().clone()).collect()
+    }
+
+    fn is_columns_equality(expr: &Expression) -> bool {
+        let is_column = |expr: &Expression| match expr {
+            Expression::Cast { expr, .. } => matches!(expr.as_ref(), Expression::Column(_)),
+            expr => matches!(expr, Expression::Column(_)),
+        };
+
+        match expr {
+            Expression::BinaryExpression { op, left, right } => {
+                op == "=" && is_column(left) && is_column(right)
+            }
+            _ => false,
+        }
+    }
+
+    fn split_conjunctions(expr: &Expression) -> Vec<Expression> {
+        match expr {
+            Expression::BinaryExpression { op, left, right } if op.to_lowercase() == "and" => {
+                let mut conjunctions = Self::split_conjunctions(left);
+                conjunctions.extend(Self::split_conjunctions(right));
+                conjunctions
+            }
+            expr => vec![expr.clone()],
+        }
+    }
+}
+
+impl Optimizer for JoinReorderOptimizer {
+    fn name(&self) -> &str {
+        "JoinReorder"
+    }
+
+    fn optimize(&mut self, plan: &PlanNode) -> Result<PlanNode> {
+        let mut visitor = JoinReorderImpl {};
+        visitor.rewrite_plan_node(plan)
+    }
+}
+
+impl JoinReorderOptimizer {
+    pub fn create(_ctx: Arc<QueryContext>) -> JoinReorderOptimizer {
+        JoinReorderOptimizer {}
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::optimizers::JoinReorderOptimizer;
+use crate::optimizers::Optimizer;
+use crate::tests::parse_query;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_join_reorder_optimizer() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        query: &'static str,
+        expect: &'static str,
+    }
+
+    let tests = vec![
+        Test {
+            name: "Smaller build side",
+            query: "SELECT a.number FROM numbers(100) AS a JOIN numbers(10) AS b ON a.number = b.number",
+            expect: "\
+            Projection: a.number:UInt64\
+            \n  HashJoin: INNER JOIN, keys: [a.number = b.number]\
+            \n    Projection: number as a.number:UInt64\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100, read_bytes: 800], push_downs: [projections: [0]]\
+            \n    Projection: number as b.number:UInt64\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]",
+        },
+        Test {
+            name: "Larger build side",
+            query: "SELECT a.number FROM numbers(10) AS a JOIN numbers(100) AS b ON a.number = b.number",
+            expect: "\
+            Projection: a.number:UInt64\
+            \n  Projection: a.number:UInt64, b.number:UInt64\
+            \n    HashJoin: INNER JOIN, keys: [b.number = a.number]\
+            \n      Projection: number as b.number:UInt64\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100, read_bytes: 800], push_downs: [projections: [0]]\
+            \n      Projection: number as a.number:UInt64\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]",
+        },
+        Test {
+            name: "Comma separated tables",
+            query: "SELECT a.number FROM numbers(1000) AS a, numbers(10) AS b, numbers(100) AS c \
+                WHERE a.number = c.number AND b.number = c.number",
+            expect: "\
+            Projection: a.number:UInt64\
+            \n  Projection: a.number:UInt64, b.number:UInt64, c.number:UInt64\
+            \n    HashJoin: INNER JOIN, keys: [a.number = c.number]\
+            \n      Projection: number as a.number:UInt64\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 1000, read_bytes: 8000], push_downs: [projections: [0]]\
+            \n      HashJoin: INNER JOIN, keys: [c.number = b.number]\
+            \n        Projection: number as c.number:UInt64\
+            \n          ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100, read_bytes: 800], push_downs: [projections: [0]]\
+            \n        Projection: number as b.number:UInt64\
+            \n          ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]",
+        },
+        Test {
+            name: "Left join with larger build side",
+            query: "SELECT a.number FROM numbers(10) AS a LEFT JOIN numbers(100) AS b ON a.number = b.number",
+            expect: "\
+            Projection: a.number:UInt64\
+            \n  HashJoin: LEFT JOIN, keys: [a.number = b.number]\
+            \n    Projection: number as a.number:UInt64\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]\
+            \n    Projection: number as b.number:UInt64\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100, read_bytes: 800], push_downs: [projections: [0]]",
+        },
+        Test {
+            name: "Right join with larger build side",
+            query: "SELECT b.number FROM numbers(10) AS a RIGHT JOIN numbers(100) AS b ON a.number = b.number",
+            expect: "\
+            Projection: b.number:UInt64\
+            \n  Projection: a.number:UInt64, b.number:UInt64\
+            \n    HashJoin: LEFT JOIN, keys: [b.number = a.number]\
+            \n      Projection: number as b.number:UInt64\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100, read_bytes: 800], push_downs: [projections: [0]]\
+            \n      Projection: number as a.number:UInt64\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]",
+        },
+    ];
+
+    for test in tests {
+        let ctx = crate::tests::try_create_context()?;
+        let plan = parse_query(test.query, &ctx)?;
+        let mut optimizer = JoinReorderOptimizer::create(ctx);
+        let optimized = optimizer.optimize(&plan)?;
+        let actual = format!("{:?}", optimized);
+        assert_eq!(test.expect, actual, "{:#?}", test.name);
+    }
+
+    Ok(())
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp::Ordering;
 use std::sync::Arc;
 
 use common_datavalues::DataSchemaRef;
@@ -21,20 +22,31 @@ use common_exception::Result;
 use common_planners::AggregatorFinalPlan;
 use common_planners::AggregatorPartialPlan;
 use common_planners::BroadcastPlan;
+use common_planners::DistinctPlan;
 use common_planners::Expression;
+use common_planners::JoinPlan;
 use common_planners::LimitByPlan;
 use common_planners::LimitPlan;
+use common_planners::MaterializedCtePlan;
 use common_planners::PlanBuilder;
 use common_planners::PlanNode;
 use common_planners::PlanRewriter;
 use common_planners::ReadDataSourcePlan;
+use common_planners::SetOperationPlan;
 use common_planners::SortPlan;
 use common_planners::StageKind;
 use common_planners::StagePlan;
+use common_planners::UnionPlan;
+use common_planners::WindowPlan;
 
+use crate::optimizers::CostModel;
 use crate::optimizers::Optimizer;
 use crate::sessions::QueryContext;
 
+// The partial aggregation is not worth running before the shuffle if the groups are more than
+// the fraction of the rows, as the aggregate states are sent instead of the rows.
+const MAX_PARTIAL_AGGREGATION_GROUPS_RATIO: f64 = 0.5;
+
 pub struct ScattersOptimizer {
     ctx: Arc<QueryContext>,
 }
@@ -45,6 +57,15 @@ enum RunningMode {
     Cluster,
 }
 
+enum JoinStrategy {
+    /// Broadcast the right side to every node which has the left side.
+    Broadcast,
+    /// Repartition both sides by the hash of the join keys.
+    Shuffle,
+    /// Converge both sides to the local node.
+    Converge,
+}
+
 struct ScattersOptimizerImpl {
     ctx: Arc<QueryContext>,
     running_mode: RunningMode,
@@ -79,20 +100,59 @@ impl ScattersOptimizerImpl {
     }
 
     fn cluster_aggregate_with_key(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
+        let input = match self.input.take() {
+            None => return Err(ErrorCode::LogicalError("Cluster aggr input is None")),
+            Some(input) => input,
+        };
+
+        let partial = PlanBuilder::from(input.as_ref())
+            .aggregate_partial(&plan.aggr_expr, &plan.group_expr)?
+            .build()?;
+
+        // If the estimated groups fit in a block, we convergent them in local node
+        // rather than shuffling them by the key.
+        let max_block_size = self.ctx.get_settings().get_max_block_size()?;
+        if CostModel::estimate(&partial).rows <= max_block_size as f64 {
+            self.running_mode = RunningMode::Standalone;
+            return Self::convergent_shuffle_stage(partial);
+        }
+
         // Keep running in cluster mode
         self.running_mode = RunningMode::Cluster;
-
-        match self.input.take() {
-            None => Err(ErrorCode::LogicalError("Cluster aggr input is None")),
-            Some(input) => Self::normal_shuffle_stage(
-                "_group_by_key",
-                PlanBuilder::from(input.as_ref())
+        match CostModel::groups_ratio(&plan.group_expr, &input) {
+            // The rows are shuffled by the group key, and aggregated after the shuffle.
+            Some(ratio) if ratio > MAX_PARTIAL_AGGREGATION_GROUPS_RATIO => {
+                PlanBuilder::from(&Self::group_key_shuffle_stage(&plan.group_expr, input))
                     .aggregate_partial(&plan.aggr_expr, &plan.group_expr)?
-                    .build()?,
-            ),
+                    .build()
+            }
+            _ => Self::normal_shuffle_stage("_group_by_key", partial),
         }
     }
 
+    // The rows of a group are sent to the same node by the hash of any group key, the key with
+    // the most distinct values is picked to spread the groups evenly.
+    fn group_key_shuffle_stage(group_exprs: &[Expression], input: Arc<PlanNode>) -> PlanNode {
+        let estimation = CostModel::estimate(&input);
+        let distinct_count = |expr: &Expression| estimation.expression(expr).distinct_count;
+        let key = group_exprs
+            .iter()
+            .max_by(|left, right| {
+                let ordering = distinct_count(left).partial_cmp(&distinct_count(right));
+                ordering.unwrap_or(Ordering::Equal)
+            })
+            .unwrap_or(&group_exprs[0]);
+
+        PlanNode::Stage(StagePlan {
+            kind: StageKind::Normal,
+            scatters_expr: Expression::ScalarFunction {
+                op: String::from("sipHash"),
+                args: vec![key.clone()],
+            },
+            input,
+        })
+    }
+
     fn cluster_aggregate(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
         match plan.group_expr.len() {
             0 => self.cluster_aggregate_without_key(plan),
@@ -174,6 +234,43 @@ impl ScattersOptimizerImpl {
         }
     }
 
+    fn cluster_window(&mut self, plan: &WindowPlan) -> Result<PlanNode> {
+        let input = match self.input.take() {
+            None => return Err(ErrorCode::LogicalError("Cluster window input is None.")),
+            Some(input) => input,
+        };
+
+        match plan.partition_by().first() {
+            // The rows of a partition are sent to the same node, keep running in cluster mode
+            Some(key) => PlanBuilder::from(&PlanNode::Stage(StagePlan {
+                kind: StageKind::Normal,
+                scatters_expr: Expression::ScalarFunction {
+                    op: String::from("sipHash"),
+                    args: vec![key.clone()],
+                },
+                input,
+            }))
+            .window(&plan.window_exprs)?
+            .build(),
+            // Window without partition we convergent it in local node
+            None => {
+                self.running_mode = RunningMode::Standalone;
+                Self::convergent_shuffle_stage_builder(input)
+                    .window(&plan.window_exprs)?
+                    .build()
+            }
+        }
+    }
+
+    fn standalone_window(&mut self, plan: &WindowPlan) -> Result<PlanNode> {
+        match self.input.take() {
+            None => Err(ErrorCode::LogicalError("Standalone window input is None.")),
+            Some(input) => PlanBuilder::from(input.as_ref())
+                .window(&plan.window_exprs)?
+                .build(),
+        }
+    }
+
     fn convergent_shuffle_stage_builder(input: Arc<PlanNode>) -> PlanBuilder {
         PlanBuilder::from(&PlanNode::Stage(StagePlan {
             kind: StageKind::Convergent,
@@ -190,6 +287,60 @@ impl ScattersOptimizerImpl {
         }))
     }
 
+    fn join_strategy(
+        &self,
+        plan: &JoinPlan,
+        left_mode: &RunningMode,
+        right: &PlanNode,
+    ) -> Result<JoinStrategy> {
+        // The unmatched rows of the build side cannot be found on any single node
+        // if the build side is broadcast, so it's only for the left side in cluster.
+        if let RunningMode::Cluster = left_mode {
+            let max_broadcast_bytes = self.ctx.get_settings().get_max_broadcast_join_bytes()?;
+            if !plan.join_type.keeps_unmatched_right()
+                && CostModel::estimate(right).bytes() <= max_broadcast_bytes as f64
+            {
+                return Ok(JoinStrategy::Broadcast);
+            }
+        }
+
+        match plan.left_keys.is_empty() {
+            true => Ok(JoinStrategy::Converge),
+            false => Ok(JoinStrategy::Shuffle),
+        }
+    }
+
+    fn broadcast_join_side(input: PlanNode) -> PlanNode {
+        PlanNode::Broadcast(BroadcastPlan {
+            input: Arc::new(input),
+        })
+    }
+
+    fn shuffle_join_side(mode: &RunningMode, key: &Expression, input: PlanNode) -> PlanNode {
+        // Rows with the same key are sent to the same node, the keys of both sides
+        // are coerced to the same type so that they have the same hash.
+        let kind = match mode {
+            RunningMode::Cluster => StageKind::Normal,
+            RunningMode::Standalone => StageKind::Expansive,
+        };
+
+        PlanNode::Stage(StagePlan {
+            kind,
+            scatters_expr: Expression::ScalarFunction {
+                op: String::from("sipHash"),
+                args: vec![key.clone()],
+            },
+            input: Arc::new(input),
+        })
+    }
+
+    fn converge_join_side(mode: &RunningMode, input: PlanNode) -> Result<PlanNode> {
+        match mode {
+            RunningMode::Cluster => Self::convergent_shuffle_stage(input),
+            RunningMode::Standalone => Ok(input),
+        }
+    }
+
     fn normal_shuffle_stage(key: impl Into<String>, input: PlanNode) -> Result<PlanNode> {
         let scatters_expr = Expression::ScalarFunction {
             op: String::from("sipHash"),
@@ -228,6 +379,49 @@ impl PlanRewriter for ScattersOptimizerImpl {
         }
     }
 
+    fn rewrite_join(&mut self, plan: &JoinPlan) -> Result<PlanNode> {
+        let new_left = self.rewrite_plan_node(plan.left.as_ref())?;
+
+        let mut right_optimizer = ScattersOptimizerImpl::create(self.ctx.clone());
+        let new_right = right_optimizer.rewrite_plan_node(plan.right.as_ref())?;
+
+        let left_mode = self.running_mode.clone();
+        let right_mode = right_optimizer.running_mode;
+        let (new_left, new_right) = match (&left_mode, &right_mode) {
+            (RunningMode::Standalone, RunningMode::Standalone) => (new_left, new_right),
+            _ => match self.join_strategy(plan, &left_mode, &new_right)? {
+                JoinStrategy::Broadcast => {
+                    self.running_mode = RunningMode::Cluster;
+                    (new_left, Self::broadcast_join_side(new_right))
+                }
+                JoinStrategy::Shuffle => {
+                    self.running_mode = RunningMode::Cluster;
+                    (
+                        Self::shuffle_join_side(&left_mode, &plan.left_keys[0], new_left),
+                        Self::shuffle_join_side(&right_mode, &plan.right_keys[0], new_right),
+                    )
+                }
+                JoinStrategy::Converge => {
+                    self.running_mode = RunningMode::Standalone;
+                    (
+                        Self::converge_join_side(&left_mode, new_left)?,
+                        Self::converge_join_side(&right_mode, new_right)?,
+                    )
+                }
+            },
+        };
+
+        PlanBuilder::from(&new_left)
+            .join(
+                &new_right,
+                plan.join_type,
+                &plan.left_keys,
+                &plan.right_keys,
+                plan.other_conditions.clone(),
+            )?
+            .build()
+    }
+
     fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
         let new_input = Arc::new(self.rewrite_plan_node(&plan.input)?);
 
@@ -278,6 +472,77 @@ impl PlanRewriter for ScattersOptimizerImpl {
         }
     }
 
+    fn rewrite_window(&mut self, plan: &WindowPlan) -> Result<PlanNode> {
+        self.input = Some(Arc::new(self.rewrite_plan_node(plan.input.as_ref())?));
+
+        match self.running_mode {
+            RunningMode::Cluster => self.cluster_window(plan),
+            RunningMode::Standalone => self.standalone_window(plan),
+        }
+    }
+
+    fn rewrite_union(&mut self, plan: &UnionPlan) -> Result<PlanNode> {
+        // Union runs in the local node for the time being, the inputs are converged.
+        let mut new_inputs = Vec::with_capacity(plan.inputs.len());
+        for input in &plan.inputs {
+            let mut input_optimizer = ScattersOptimizerImpl::create(self.ctx.clone());
+            let new_input = input_optimizer.rewrite_plan_node(input.as_ref())?;
+            let input_mode = input_optimizer.running_mode;
+            new_inputs.push(Self::converge_join_side(&input_mode, new_input)?);
+        }
+
+        self.running_mode = RunningMode::Standalone;
+        PlanBuilder::from(&new_inputs[0])
+            .union(&new_inputs[1..])?
+            .build()
+    }
+
+    fn rewrite_distinct(&mut self, plan: &DistinctPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+
+        match self.running_mode {
+            RunningMode::Standalone => PlanBuilder::from(&new_input).distinct()?.build(),
+            RunningMode::Cluster => {
+                // The duplicates are removed in each node before they are converged.
+                self.running_mode = RunningMode::Standalone;
+                let distinct = PlanBuilder::from(&new_input).distinct()?.build()?;
+                PlanBuilder::from(&Self::convergent_shuffle_stage(distinct)?)
+                    .distinct()?
+                    .build()
+            }
+        }
+    }
+
+    fn rewrite_set_operation(&mut self, plan: &SetOperationPlan) -> Result<PlanNode> {
+        // Set operations run in the local node for the time being, both inputs are converged.
+        let mut left_optimizer = ScattersOptimizerImpl::create(self.ctx.clone());
+        let new_left = left_optimizer.rewrite_plan_node(plan.left.as_ref())?;
+        let new_left = Self::converge_join_side(&left_optimizer.running_mode, new_left)?;
+
+        let mut right_optimizer = ScattersOptimizerImpl::create(self.ctx.clone());
+        let new_right = right_optimizer.rewrite_plan_node(plan.right.as_ref())?;
+        let new_right = Self::converge_join_side(&right_optimizer.running_mode, new_right)?;
+
+        self.running_mode = RunningMode::Standalone;
+        PlanBuilder::from(&new_left)
+            .set_operation(plan.op, plan.all, &new_right)?
+            .build()
+    }
+
+    fn rewrite_materialized_cte(&mut self, plan: &MaterializedCtePlan) -> Result<PlanNode> {
+        let mut input_optimizer = ScattersOptimizerImpl::create(self.ctx.clone());
+        let new_input = input_optimizer.rewrite_plan_node(plan.input.as_ref())?;
+
+        self.running_mode = input_optimizer.running_mode;
+        match self.running_mode {
+            RunningMode::Standalone => PlanBuilder::from(&new_input)
+                .materialized_cte(&plan.name)?
+                .build(),
+            // The stages of the CTE are scheduled by every reference, so it is inlined.
+            RunningMode::Cluster => Ok(new_input),
+        }
+    }
+
     fn rewrite_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<PlanNode> {
         let t = self.ctx.build_table_from_source_plan(plan)?;
 
@@ -142,6 +142,29 @@ async fn test_scatter_optimizer() -> Result<()> {
             \n          Expression: (number % 3):UInt8, (number % 2):UInt8, number:UInt64 (Before GroupBy)\
             \n            ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100000000, read_bytes: 800000000], push_downs: [projections: [0]]",
         },
+        Test {
+            name: "Large cluster table aggregate query with distinct group by key",
+            query: "SELECT SUM(number) FROM numbers(100000000) GROUP BY number",
+            expect: "\
+            RedistributeStage[expr: 0]\
+            \n  Projection: SUM(number):UInt64\
+            \n    AggregatorFinal: groupBy=[[number]], aggr=[[SUM(number)]]\
+            \n      AggregatorPartial: groupBy=[[number]], aggr=[[SUM(number)]]\
+            \n        RedistributeStage[expr: sipHash(number)]\
+            \n          ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100000000, read_bytes: 800000000], push_downs: [projections: [0]]",
+        },
+        Test {
+            name: "Large cluster table aggregate query with few groups",
+            query: "SELECT SUM(number) FROM numbers(100000000) WHERE number < 10 GROUP BY number % 3",
+            expect: "\
+            Projection: SUM(number):UInt64\
+            \n  AggregatorFinal: groupBy=[[(number % 3)]], aggr=[[SUM(number)]]\
+            \n    RedistributeStage[expr: 0]\
+            \n      AggregatorPartial: groupBy=[[(number % 3)]], aggr=[[SUM(number)]]\
+            \n        Expression: (number % 3):UInt8, number:UInt64 (Before GroupBy)\
+            \n          Filter: (number < 10)\
+            \n            ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100000000, read_bytes: 800000000], push_downs: [projections: [0]]",
+        },
         Test {
             name: "Large cluster table aggregate query without group by",
             query: "SELECT SUM(number) FROM numbers(100000000)",
@@ -200,6 +223,58 @@ async fn test_scatter_optimizer() -> Result<()> {
             \n            ReadDataSource: scan partitions: [1], scan schema: [number:UInt64], statistics: [read_rows: 1, read_bytes: 8], push_downs: [projections: [0]]\
             \n        ReadDataSource: scan partitions: [1], scan schema: [number:UInt64], statistics: [read_rows: 1, read_bytes: 8], push_downs: [projections: [0]]",
         },
+        Test {
+            name: "Cluster join with small build side",
+            query: "SELECT a.number FROM numbers(100000000) AS a JOIN numbers(10) AS b ON a.number = b.number",
+            expect: "\
+            RedistributeStage[expr: 0]\
+            \n  Projection: a.number:UInt64\
+            \n    HashJoin: INNER JOIN, keys: [a.number = b.number]\
+            \n      Projection: number as a.number:UInt64\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100000000, read_bytes: 800000000], push_downs: [projections: [0]]\
+            \n      Broadcast in cluster\
+            \n        Projection: number as b.number:UInt64\
+            \n          ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]",
+        },
+        Test {
+            name: "Cluster join with large build side",
+            query: "SELECT a.number FROM numbers(100000000) AS a JOIN numbers(100000000) AS b ON a.number = b.number",
+            expect: "\
+            RedistributeStage[expr: 0]\
+            \n  Projection: a.number:UInt64\
+            \n    HashJoin: INNER JOIN, keys: [a.number = b.number]\
+            \n      RedistributeStage[expr: sipHash(a.number)]\
+            \n        Projection: number as a.number:UInt64\
+            \n          ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100000000, read_bytes: 800000000], push_downs: [projections: [0]]\
+            \n      RedistributeStage[expr: sipHash(b.number)]\
+            \n        Projection: number as b.number:UInt64\
+            \n          ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100000000, read_bytes: 800000000], push_downs: [projections: [0]]",
+        },
+        Test {
+            name: "Cluster right join with small local build side",
+            query: "SELECT a.number FROM numbers(100000000) AS a RIGHT JOIN numbers_local(10) AS b ON a.number = b.number",
+            expect: "\
+            RedistributeStage[expr: 0]\
+            \n  Projection: a.number:UInt64\
+            \n    HashJoin: RIGHT JOIN, keys: [a.number = b.number]\
+            \n      RedistributeStage[expr: sipHash(a.number)]\
+            \n        Projection: number as a.number:UInt64\
+            \n          ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100000000, read_bytes: 800000000], push_downs: [projections: [0]]\
+            \n      RedistributeStage[expr: sipHash(b.number)]\
+            \n        Projection: number as b.number:UInt64\
+            \n          ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]",
+        },
+        Test {
+            name: "Local join",
+            query: "SELECT a.number FROM numbers_local(10) AS a JOIN numbers_local(10) AS b ON a.number = b.number",
+            expect: "\
+            Projection: a.number:UInt64\
+            \n  HashJoin: INNER JOIN, keys: [a.number = b.number]\
+            \n    Projection: number as a.number:UInt64\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]\
+            \n    Projection: number as b.number:UInt64\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]",
+        },
     ];
 
     for test in tests {
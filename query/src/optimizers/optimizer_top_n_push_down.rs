@@ -97,6 +97,128 @@ impl PlanRewriter for TopNPushDownImpl {
         plan_node
     }
 
+    fn rewrite_join(&mut self, plan: &JoinPlan) -> Result<PlanNode> {
+        // The limit can't be pushed down to either side of join, see the TODO above.
+        let current_limit = self.limit.take();
+        let current_order_by = std::mem::take(&mut self.order_by);
+
+        let new_left = self.rewrite_plan_node(plan.left.as_ref())?;
+        let new_right = self.rewrite_plan_node(plan.right.as_ref())?;
+        let plan_node = PlanBuilder::from(&new_left)
+            .join(
+                &new_right,
+                plan.join_type,
+                &plan.left_keys,
+                &plan.right_keys,
+                plan.other_conditions.clone(),
+            )?
+            .build();
+
+        self.limit = current_limit; // recover back to previous state
+        self.order_by = current_order_by;
+
+        plan_node
+    }
+
+    fn rewrite_array_join(&mut self, plan: &ArrayJoinPlan) -> Result<PlanNode> {
+        // The number of the rows is changed by the array join.
+        let current_limit = self.limit.take();
+        let current_order_by = std::mem::take(&mut self.order_by);
+
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        let plan_node = PlanBuilder::from(&new_input)
+            .array_join(&plan.array_join_exprs)?
+            .build();
+
+        self.limit = current_limit; // recover back to previous state
+        self.order_by = current_order_by;
+
+        plan_node
+    }
+
+    fn rewrite_window(&mut self, plan: &WindowPlan) -> Result<PlanNode> {
+        // The window functions are evaluated over all the rows of the partitions.
+        let current_limit = self.limit.take();
+        let current_order_by = std::mem::take(&mut self.order_by);
+
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        let plan_node = PlanBuilder::from(&new_input)
+            .window(&plan.window_exprs)?
+            .build();
+
+        self.limit = current_limit; // recover back to previous state
+        self.order_by = current_order_by;
+
+        plan_node
+    }
+
+    fn rewrite_union(&mut self, plan: &UnionPlan) -> Result<PlanNode> {
+        // The inputs are named differently from the union, the order by can't be pushed down.
+        let current_limit = self.limit.take();
+        let current_order_by = std::mem::take(&mut self.order_by);
+
+        let mut new_inputs = Vec::with_capacity(plan.inputs.len());
+        for input in &plan.inputs {
+            new_inputs.push(self.rewrite_plan_node(input.as_ref())?);
+        }
+
+        let plan_node = PlanBuilder::from(&new_inputs[0])
+            .union(&new_inputs[1..])?
+            .build();
+
+        self.limit = current_limit; // recover back to previous state
+        self.order_by = current_order_by;
+
+        plan_node
+    }
+
+    fn rewrite_distinct(&mut self, plan: &DistinctPlan) -> Result<PlanNode> {
+        // The limit is applied to the distinct rows.
+        let current_limit = self.limit.take();
+        let current_order_by = std::mem::take(&mut self.order_by);
+
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        let plan_node = PlanBuilder::from(&new_input).distinct()?.build();
+
+        self.limit = current_limit; // recover back to previous state
+        self.order_by = current_order_by;
+
+        plan_node
+    }
+
+    fn rewrite_set_operation(&mut self, plan: &SetOperationPlan) -> Result<PlanNode> {
+        // All the rows of both inputs are required to find the matched rows.
+        let current_limit = self.limit.take();
+        let current_order_by = std::mem::take(&mut self.order_by);
+
+        let new_left = self.rewrite_plan_node(plan.left.as_ref())?;
+        let new_right = self.rewrite_plan_node(plan.right.as_ref())?;
+        let plan_node = PlanBuilder::from(&new_left)
+            .set_operation(plan.op, plan.all, &new_right)?
+            .build();
+
+        self.limit = current_limit; // recover back to previous state
+        self.order_by = current_order_by;
+
+        plan_node
+    }
+
+    fn rewrite_materialized_cte(&mut self, plan: &MaterializedCtePlan) -> Result<PlanNode> {
+        // The result is shared by the references, a reference can't limit it.
+        let current_limit = self.limit.take();
+        let current_order_by = std::mem::take(&mut self.order_by);
+
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        let plan_node = PlanBuilder::from(&new_input)
+            .materialized_cte(&plan.name)?
+            .build();
+
+        self.limit = current_limit; // recover back to previous state
+        self.order_by = current_order_by;
+
+        plan_node
+    }
+
     fn rewrite_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<PlanNode> {
         // push the limit and order_by down to read_source_plan
         if let Some(n) = self.limit {
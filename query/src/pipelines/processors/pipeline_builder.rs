@@ -17,42 +17,58 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::AggregatorFinalPlan;
 use common_planners::AggregatorPartialPlan;
+use common_planners::ArrayJoinPlan;
 use common_planners::BroadcastPlan;
+use common_planners::DistinctPlan;
 use common_planners::ExpressionPlan;
 use common_planners::FilterPlan;
 use common_planners::HavingPlan;
+use common_planners::JoinPlan;
 use common_planners::LimitByPlan;
 use common_planners::LimitPlan;
+use common_planners::MaterializedCtePlan;
 use common_planners::PlanNode;
 use common_planners::ProjectionPlan;
 use common_planners::ReadDataSourcePlan;
 use common_planners::RemotePlan;
 use common_planners::SelectPlan;
+use common_planners::SetOperationPlan;
 use common_planners::SinkPlan;
 use common_planners::SortPlan;
 use common_planners::StagePlan;
 use common_planners::SubQueriesSetPlan;
+use common_planners::UnionPlan;
+use common_planners::WindowPlan;
 use common_tracing::tracing;
 
 use crate::api::FlightTicket;
+use crate::pipelines::processors::MergeProcessor;
 use crate::pipelines::processors::Pipeline;
+use crate::pipelines::processors::Processor;
 use crate::pipelines::transforms::AggregatorFinalTransform;
 use crate::pipelines::transforms::AggregatorPartialTransform;
+use crate::pipelines::transforms::ArrayJoinTransform;
 use crate::pipelines::transforms::CreateSetsTransform;
+use crate::pipelines::transforms::DistinctTransform;
 use crate::pipelines::transforms::ExpressionTransform;
 use crate::pipelines::transforms::GroupByFinalTransform;
 use crate::pipelines::transforms::GroupByPartialTransform;
+use crate::pipelines::transforms::HashJoinTransform;
 use crate::pipelines::transforms::HavingTransform;
+use crate::pipelines::transforms::JoinHashTablePuller;
 use crate::pipelines::transforms::LimitByTransform;
 use crate::pipelines::transforms::LimitTransform;
+use crate::pipelines::transforms::MaterializedCteTransform;
 use crate::pipelines::transforms::ProjectionTransform;
 use crate::pipelines::transforms::RemoteTransform;
+use crate::pipelines::transforms::SetOperationTransform;
 use crate::pipelines::transforms::SinkTransform;
 use crate::pipelines::transforms::SortMergeTransform;
 use crate::pipelines::transforms::SortPartialTransform;
 use crate::pipelines::transforms::SourceTransform;
 use crate::pipelines::transforms::SubQueriesPuller;
 use crate::pipelines::transforms::WhereTransform;
+use crate::pipelines::transforms::WindowTransform;
 use crate::sessions::QueryContext;
 
 pub struct PipelineBuilder {
@@ -79,6 +95,13 @@ impl PipelineBuilder {
         Ok(pipeline)
     }
 
+    /// Builds the pipeline of a plan which is executed on its own, e.g. the build side of a join.
+    ///
+    /// Partitions are bound to the context, so the plan needs a context of its own.
+    pub fn build_sub_pipeline(ctx: &Arc<QueryContext>, node: &PlanNode) -> Result<Pipeline> {
+        PipelineBuilder::create(QueryContext::new(ctx.clone())).build(node)
+    }
+
     fn visit(&mut self, node: &PlanNode) -> Result<Pipeline> {
         match node {
             PlanNode::Select(node) => self.visit_select(node),
@@ -91,9 +114,16 @@ impl PipelineBuilder {
             PlanNode::AggregatorFinal(node) => self.visit_aggregator_final(node),
             PlanNode::Filter(node) => self.visit_filter(node),
             PlanNode::Having(node) => self.visit_having(node),
+            PlanNode::Join(node) => self.visit_join(node),
             PlanNode::Sort(node) => self.visit_sort(node),
             PlanNode::Limit(node) => self.visit_limit(node),
             PlanNode::LimitBy(node) => self.visit_limit_by(node),
+            PlanNode::ArrayJoin(node) => self.visit_array_join(node),
+            PlanNode::Window(node) => self.visit_window(node),
+            PlanNode::Union(node) => self.visit_union(node),
+            PlanNode::Distinct(node) => self.visit_distinct(node),
+            PlanNode::SetOperation(node) => self.visit_set_operation(node),
+            PlanNode::MaterializedCte(node) => self.visit_materialized_cte(node),
             PlanNode::ReadSource(node) => self.visit_read_data_source(node),
             PlanNode::SubQueryExpression(node) => self.visit_create_sets(node),
             PlanNode::Sink(node) => self.visit_sink(node),
@@ -176,6 +206,7 @@ impl PipelineBuilder {
         } else {
             pipeline.add_simple_transform(|| {
                 Ok(Box::new(GroupByPartialTransform::create(
+                    self.ctx.clone(),
                     node.schema(),
                     node.input.schema(),
                     node.aggr_expr.clone(),
@@ -202,6 +233,7 @@ impl PipelineBuilder {
             let max_block_size = self.ctx.get_settings().get_max_block_size()? as usize;
             pipeline.add_simple_transform(|| {
                 Ok(Box::new(GroupByFinalTransform::create(
+                    self.ctx.clone(),
                     node.schema(),
                     max_block_size,
                     node.schema_before_group_by.clone(),
@@ -236,6 +268,30 @@ impl PipelineBuilder {
         Ok(pipeline)
     }
 
+    fn visit_join(&mut self, plan: &JoinPlan) -> Result<Pipeline> {
+        let mut pipeline = self.visit(&*plan.left)?;
+
+        // The unmatched rows of the build side are known only after all the rows are probed,
+        // so there must be only one probe processor.
+        if plan.join_type.keeps_unmatched_right() {
+            pipeline.merge_processor()?;
+        }
+
+        let hash_table_puller = JoinHashTablePuller::create(
+            self.ctx.clone(),
+            plan.right.clone(),
+            plan.right_keys.clone(),
+        );
+        pipeline.add_simple_transform(|| {
+            Ok(Box::new(HashJoinTransform::try_create(
+                self.ctx.clone(),
+                plan,
+                hash_table_puller.clone(),
+            )?))
+        })?;
+        Ok(pipeline)
+    }
+
     fn visit_sort(&mut self, plan: &SortPlan) -> Result<Pipeline> {
         let mut pipeline = self.visit(&*plan.input)?;
 
@@ -260,6 +316,7 @@ impl PipelineBuilder {
         // processor 3: [sorted blocks ...] ---> merge to one sorted block
         pipeline.add_simple_transform(|| {
             Ok(Box::new(SortMergeTransform::try_create(
+                self.ctx.clone(),
                 plan.schema(),
                 plan.order_by.clone(),
                 rows_limit,
@@ -275,6 +332,7 @@ impl PipelineBuilder {
             pipeline.merge_processor()?;
             pipeline.add_simple_transform(|| {
                 Ok(Box::new(SortMergeTransform::try_create(
+                    self.ctx.clone(),
                     plan.schema(),
                     plan.order_by.clone(),
                     rows_limit,
@@ -308,6 +366,65 @@ impl PipelineBuilder {
         Ok(pipeline)
     }
 
+    fn visit_array_join(&mut self, plan: &ArrayJoinPlan) -> Result<Pipeline> {
+        let mut pipeline = self.visit(&*plan.input)?;
+        pipeline.add_simple_transform(|| Ok(Box::new(ArrayJoinTransform::create(plan))))?;
+        Ok(pipeline)
+    }
+
+    fn visit_window(&mut self, plan: &WindowPlan) -> Result<Pipeline> {
+        let mut pipeline = self.visit(&*plan.input)?;
+
+        // The rows of a partition must be in the same processor.
+        pipeline.merge_processor()?;
+        pipeline.add_simple_transform(|| Ok(Box::new(WindowTransform::create(plan))))?;
+        Ok(pipeline)
+    }
+
+    fn visit_union(&mut self, plan: &UnionPlan) -> Result<Pipeline> {
+        let mut merge = MergeProcessor::create(self.ctx.clone());
+        for input in &plan.inputs {
+            let input_pipeline = Self::build_sub_pipeline(&self.ctx, input)?;
+            for processor in input_pipeline.last_pipe()?.processors() {
+                merge.connect_to(processor)?;
+            }
+        }
+
+        let mut pipeline = Pipeline::create(self.ctx.clone());
+        pipeline.add_source(Arc::new(merge))?;
+        Ok(pipeline)
+    }
+
+    fn visit_distinct(&mut self, plan: &DistinctPlan) -> Result<Pipeline> {
+        let mut pipeline = self.visit(&*plan.input)?;
+
+        // The rows are deduplicated by one hash set.
+        pipeline.merge_processor()?;
+        pipeline.add_simple_transform(|| Ok(Box::new(DistinctTransform::create())))?;
+        Ok(pipeline)
+    }
+
+    fn visit_set_operation(&mut self, plan: &SetOperationPlan) -> Result<Pipeline> {
+        let mut pipeline = self.visit(&*plan.left)?;
+
+        // The counts of the right rows are consumed by the left rows in one processor.
+        pipeline.merge_processor()?;
+        pipeline.add_simple_transform(|| {
+            Ok(Box::new(SetOperationTransform::create(
+                self.ctx.clone(),
+                plan,
+            )))
+        })?;
+        Ok(pipeline)
+    }
+
+    fn visit_materialized_cte(&mut self, plan: &MaterializedCtePlan) -> Result<Pipeline> {
+        let mut pipeline = Pipeline::create(self.ctx.clone());
+        let source = MaterializedCteTransform::create(self.ctx.clone(), plan);
+        pipeline.add_source(Arc::new(source))?;
+        Ok(pipeline)
+    }
+
     fn visit_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<Pipeline> {
         // Bind plan partitions to context.
         self.ctx.try_set_partitions(plan.parts.clone())?;
@@ -47,12 +47,17 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
 
     // If we set it to inline(performance degradation).
     // Because it will make other internal functions to no inline
+    //
+    // Returns the state and whether the stream is finished. If `max_bytes` is not 0, the
+    // state is returned once its allocated bytes exceed `max_bytes`, so that it can be spilled
+    // before going on with a new state.
     #[inline(never)]
     pub async fn aggregate(
         &self,
-        group_cols: Vec<String>,
-        mut stream: SendableDataBlockStream,
-    ) -> Result<Method::State> {
+        group_cols: &[String],
+        stream: &mut SendableDataBlockStream,
+        max_bytes: usize,
+    ) -> Result<(Method::State, bool)> {
         // This may be confusing
         // It will help us improve performance ~10% when we declare local references for them.
         let hash_method = &self.method;
@@ -66,9 +71,13 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
                     let block = block?;
 
                     // 1.1 and 1.2.
-                    let group_columns = Self::group_columns(&group_cols, &block)?;
+                    let group_columns = Self::group_columns(group_cols, &block)?;
                     let group_keys = hash_method.build_keys(&group_columns, block.num_rows())?;
                     self.lookup_key(group_keys, &mut state);
+
+                    if max_bytes != 0 && state.allocated_bytes() > max_bytes {
+                        return Ok((state, false));
+                    }
                 }
             }
             false => {
@@ -76,16 +85,20 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
                     let block = block?;
 
                     // 1.1 and 1.2.
-                    let group_columns = Self::group_columns(&group_cols, &block)?;
+                    let group_columns = Self::group_columns(group_cols, &block)?;
                     let group_keys = hash_method.build_keys(&group_columns, block.num_rows())?;
 
                     let places = self.lookup_state(group_keys, &mut state);
                     Self::execute(aggregator_params, &block, &places)?;
+
+                    if max_bytes != 0 && state.allocated_bytes() > max_bytes {
+                        return Ok((state, false));
+                    }
                 }
             }
         }
 
-        Ok(state)
+        Ok((state, true))
     }
 
     #[inline(always)]
@@ -188,6 +201,16 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
             )));
         }
 
+        let block = self.serialize_states(groups, schema.clone())?;
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+
+    /// Serialize the aggregate function states and the keys of the groups into a block.
+    pub fn serialize_states(
+        &self,
+        groups: &Method::State,
+        schema: DataSchemaRef,
+    ) -> Result<DataBlock> {
         let aggregator_params = self.params.as_ref();
         let funcs = &aggregator_params.aggregate_functions;
         let aggr_len = funcs.len();
@@ -221,7 +244,6 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
 
         columns.push(group_key_builder.finish());
 
-        let block = DataBlock::create_by_array(schema.clone(), columns);
-        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+        Ok(DataBlock::create_by_array(schema, columns))
     }
 }
@@ -0,0 +1,75 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use ahash::AHasher;
+use common_arrow::arrow::array::Array;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// The number of the radix partitions of the groups if the states may be spilled.
+pub const SPILL_PARTITIONS: usize = 16;
+
+/// Scatter the serialized states into the radix partitions by their group keys in the column
+/// `key_index`, the partial and the final use the same partitions, so that a group is always in
+/// the same partition. The block is not copied if all its groups are in one partition.
+pub fn scatter_by_group_keys(block: &DataBlock, key_index: usize) -> Result<Vec<DataBlock>> {
+    let keys = block.column(key_index).to_array()?;
+    let indices = match keys.data_type() {
+        DataType::UInt8 => partitions_of(keys.u8()?.inner(), |keys, row| keys.value(row)),
+        DataType::UInt16 => partitions_of(keys.u16()?.inner(), |keys, row| keys.value(row)),
+        DataType::UInt32 => partitions_of(keys.u32()?.inner(), |keys, row| keys.value(row)),
+        DataType::UInt64 => partitions_of(keys.u64()?.inner(), |keys, row| keys.value(row)),
+        DataType::String => partitions_of(keys.string()?.inner(), |keys, row| keys.value(row)),
+        other => {
+            return Err(ErrorCode::LogicalError(format!(
+                "Unexpected group key type {:?}",
+                other
+            )))
+        }
+    };
+
+    match indices.first() {
+        Some(first) if indices.iter().all(|index| index == first) => {
+            let mut scattered = (0..SPILL_PARTITIONS)
+                .map(|_| DataBlock::empty_with_schema(block.schema().clone()))
+                .collect::<Vec<_>>();
+            scattered[*first as usize] = block.clone();
+            Ok(scattered)
+        }
+        _ => {
+            let indices = DataColumn::Array(Series::new(indices));
+            DataBlock::scatter_block(block, &indices, SPILL_PARTITIONS)
+        }
+    }
+}
+
+fn partitions_of<'a, Keys, Key, F>(keys: &'a Keys, key_of: F) -> Vec<u64>
+where
+    Keys: Array,
+    Key: Hash + 'a,
+    F: Fn(&'a Keys, usize) -> Key,
+{
+    (0..keys.len())
+        .map(|row| {
+            let mut hasher = AHasher::default();
+            key_of(keys, row).hash(&mut hasher);
+            hasher.finish() % SPILL_PARTITIONS as u64
+        })
+        .collect()
+}
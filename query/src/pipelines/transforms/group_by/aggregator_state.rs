@@ -47,6 +47,9 @@ pub trait AggregatorState<Method: HashMethod>: Sync + Send {
 
     fn len(&self) -> usize;
 
+    /// The estimated bytes of the keys and the aggregate function states.
+    fn allocated_bytes(&self) -> usize;
+
     fn iter(&self) -> Self::Iterator;
 
     fn alloc_layout(&self, params: &AggregatorParams) -> StateAddr;
@@ -117,6 +120,12 @@ where
         self.size
     }
 
+    #[inline(always)]
+    fn allocated_bytes(&self) -> usize {
+        let entities = self.max_size * std::mem::size_of::<ShortFixedKeysStateEntity<T>>();
+        self.area.allocated_bytes() + entities
+    }
+
     #[inline(always)]
     fn iter(&self) -> Self::Iterator {
         Self::Iterator::create(self.data, self.max_size as isize)
@@ -185,6 +194,11 @@ where
         self.data.len()
     }
 
+    #[inline(always)]
+    fn allocated_bytes(&self) -> usize {
+        self.area.allocated_bytes() + self.data.allocated_bytes()
+    }
+
     #[inline(always)]
     fn iter(&self) -> Self::Iterator {
         self.data.iter()
@@ -234,6 +248,12 @@ impl AggregatorState<HashMethodSerializer> for SerializedKeysAggregatorState {
         self.data_state_map.len()
     }
 
+    fn allocated_bytes(&self) -> usize {
+        self.keys_area.allocated_bytes()
+            + self.state_area.allocated_bytes()
+            + self.data_state_map.allocated_bytes()
+    }
+
     fn iter(&self) -> Self::Iterator {
         self.data_state_map.iter()
     }
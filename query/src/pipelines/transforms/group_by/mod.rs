@@ -15,6 +15,7 @@
 mod aggregator;
 mod aggregator_keys_builder;
 mod aggregator_params;
+mod aggregator_partitions;
 mod aggregator_polymorphic_keys;
 mod aggregator_state;
 mod aggregator_state_entity;
@@ -24,5 +25,8 @@ mod keys_ref;
 pub use aggregator::Aggregator;
 pub use aggregator_params::AggregatorParams;
 pub use aggregator_params::AggregatorParamsRef;
+pub use aggregator_partitions::scatter_by_group_keys;
+pub use aggregator_partitions::SPILL_PARTITIONS;
 pub use aggregator_polymorphic_keys::PolymorphicKeysHelper;
 pub use aggregator_state::AggregatorState;
+pub use keys_ref::KeysRef;
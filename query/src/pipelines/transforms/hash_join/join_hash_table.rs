@@ -0,0 +1,277 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bumpalo::Bump;
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datablocks::HashMethodKind;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::common::HashMap;
+use crate::common::HashTableEntity;
+use crate::common::HashTableKeyable;
+use crate::pipelines::transforms::group_by::KeysRef;
+
+const END_OF_CHAIN: usize = usize::MAX;
+
+enum JoinHashKeys {
+    /// Joins without keys, every row matches all the rows of the build side.
+    Cross,
+    KeysU8(HashMap<u8, usize>),
+    KeysU16(HashMap<u16, usize>),
+    KeysU32(HashMap<u32, usize>),
+    KeysU64(HashMap<u64, usize>),
+    Serializer(Bump, HashMap<KeysRef, usize>),
+}
+
+/// The hash table of the build side of hash join.
+///
+/// The keys are built by the same hash methods of group by, the value of a key is the last
+/// row of the key, and the rows of the same key are chained by `next`:
+///     key -> row 5 -> row 3 -> row 0
+///
+/// Rows with NULL keys are never inserted, and never match any row.
+pub struct JoinHashTable {
+    block: DataBlock,
+    keys: JoinHashKeys,
+    next: Vec<usize>,
+    has_null_keys: bool,
+}
+
+// The table is read only once it's built, the raw pointers of the entities are never
+// modified by probing, so it's safe to share the table between the probe processors.
+unsafe impl Send for JoinHashTable {}
+
+unsafe impl Sync for JoinHashTable {}
+
+impl JoinHashTable {
+    /// Build the table from all the rows of the build side, and the keys evaluated against them.
+    /// A cross join (without keys) is built with an empty `keys_block`.
+    pub fn try_create(block: DataBlock, keys_block: &DataBlock) -> Result<JoinHashTable> {
+        let rows = block.num_rows();
+        let mut next = vec![END_OF_CHAIN; rows];
+
+        if keys_block.num_columns() == 0 {
+            return Ok(JoinHashTable {
+                block,
+                keys: JoinHashKeys::Cross,
+                next,
+                has_null_keys: false,
+            });
+        }
+
+        let valid = Self::valid_rows(keys_block)?;
+        let columns = keys_block.columns().iter().collect::<Vec<_>>();
+        let keys = match Self::choose_hash_method(keys_block)? {
+            HashMethodKind::KeysU8(method) => {
+                let keys = method.build_keys(&columns, rows)?;
+                JoinHashKeys::KeysU8(Self::build_fixed_keys(&keys, &valid, &mut next))
+            }
+            HashMethodKind::KeysU16(method) => {
+                let keys = method.build_keys(&columns, rows)?;
+                JoinHashKeys::KeysU16(Self::build_fixed_keys(&keys, &valid, &mut next))
+            }
+            HashMethodKind::KeysU32(method) => {
+                let keys = method.build_keys(&columns, rows)?;
+                JoinHashKeys::KeysU32(Self::build_fixed_keys(&keys, &valid, &mut next))
+            }
+            HashMethodKind::KeysU64(method) => {
+                let keys = method.build_keys(&columns, rows)?;
+                JoinHashKeys::KeysU64(Self::build_fixed_keys(&keys, &valid, &mut next))
+            }
+            HashMethodKind::Serializer(method) => {
+                let keys = method.build_keys(&columns, rows)?;
+                let keys_area = Bump::new();
+                let mut map = HashMap::<KeysRef, usize>::create();
+
+                for (row, key) in keys.iter().enumerate() {
+                    if !valid[row] {
+                        continue;
+                    }
+
+                    let mut inserted = false;
+                    let mut keys_ref = KeysRef::create(key.as_ptr() as usize, key.len());
+                    let entity = map.insert_key(&keys_ref, &mut inserted);
+
+                    match inserted {
+                        true => unsafe {
+                            // Keys will be destroyed after building, copy them to the memory pool.
+                            let global_keys = keys_area.alloc_slice_copy(key);
+                            let inserted_hash = entity.get_hash();
+                            keys_ref.address = global_keys.as_ptr() as usize;
+                            entity.set_key_and_hash(&keys_ref, inserted_hash);
+                        },
+                        false => next[row] = *entity.get_value(),
+                    }
+
+                    entity.set_value(row);
+                }
+
+                JoinHashKeys::Serializer(keys_area, map)
+            }
+        };
+
+        Ok(JoinHashTable {
+            block,
+            keys,
+            next,
+            has_null_keys: valid.contains(&false),
+        })
+    }
+
+    /// All the rows of the build side.
+    pub fn block(&self) -> &DataBlock {
+        &self.block
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.block.num_rows()
+    }
+
+    /// Whether any row of the build side has a NULL key.
+    pub fn has_null_keys(&self) -> bool {
+        self.has_null_keys
+    }
+
+    /// Find the matched rows of the build side for the `rows` rows of the probe side, by the
+    /// keys evaluated against the probe side, returns the pairs of (probe row, build row).
+    pub fn probe(&self, keys_block: &DataBlock, rows: usize) -> Result<(Vec<u32>, Vec<u32>)> {
+        let mut probe_indices = Vec::with_capacity(rows);
+        let mut build_indices = Vec::with_capacity(rows);
+
+        if let JoinHashKeys::Cross = &self.keys {
+            for probe_row in 0..rows {
+                for build_row in 0..self.num_rows() {
+                    probe_indices.push(probe_row as u32);
+                    build_indices.push(build_row as u32);
+                }
+            }
+
+            return Ok((probe_indices, build_indices));
+        }
+
+        let valid = Self::valid_rows(keys_block)?;
+        let columns = keys_block.columns().iter().collect::<Vec<_>>();
+        let mut heads = Vec::with_capacity(rows);
+        match (Self::choose_hash_method(keys_block)?, &self.keys) {
+            (HashMethodKind::KeysU8(method), JoinHashKeys::KeysU8(map)) => {
+                let keys = method.build_keys(&columns, rows)?;
+                Self::probe_fixed_keys(map, &keys, &valid, &mut heads);
+            }
+            (HashMethodKind::KeysU16(method), JoinHashKeys::KeysU16(map)) => {
+                let keys = method.build_keys(&columns, rows)?;
+                Self::probe_fixed_keys(map, &keys, &valid, &mut heads);
+            }
+            (HashMethodKind::KeysU32(method), JoinHashKeys::KeysU32(map)) => {
+                let keys = method.build_keys(&columns, rows)?;
+                Self::probe_fixed_keys(map, &keys, &valid, &mut heads);
+            }
+            (HashMethodKind::KeysU64(method), JoinHashKeys::KeysU64(map)) => {
+                let keys = method.build_keys(&columns, rows)?;
+                Self::probe_fixed_keys(map, &keys, &valid, &mut heads);
+            }
+            (HashMethodKind::Serializer(method), JoinHashKeys::Serializer(_, map)) => {
+                let keys = method.build_keys(&columns, rows)?;
+                for (row, key) in keys.iter().enumerate() {
+                    let keys_ref = KeysRef::create(key.as_ptr() as usize, key.len());
+                    heads.push(match valid[row] {
+                        true => map.find_key(&keys_ref).map(|entity| *entity.get_value()),
+                        false => None,
+                    });
+                }
+            }
+            (method, _) => {
+                return Err(ErrorCode::LogicalError(format!(
+                    "Hash method {} of the probe side mismatch with the build side",
+                    method.name()
+                )));
+            }
+        };
+
+        for (probe_row, head) in heads.into_iter().enumerate() {
+            let mut build_row = head.unwrap_or(END_OF_CHAIN);
+            while build_row != END_OF_CHAIN {
+                probe_indices.push(probe_row as u32);
+                build_indices.push(build_row as u32);
+                build_row = self.next[build_row];
+            }
+        }
+
+        Ok((probe_indices, build_indices))
+    }
+
+    fn choose_hash_method(keys_block: &DataBlock) -> Result<HashMethodKind> {
+        let key_names = keys_block
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect::<Vec<_>>();
+        DataBlock::choose_hash_method(keys_block, &key_names)
+    }
+
+    /// The rows without NULL keys.
+    pub fn valid_rows(keys_block: &DataBlock) -> Result<Vec<bool>> {
+        let mut valid = vec![true; keys_block.num_rows()];
+        for column in keys_block.columns() {
+            let series = column.to_array()?;
+            if series.null_count() > 0 {
+                for (row, row_valid) in valid.iter_mut().enumerate() {
+                    if series.is_null(row) {
+                        *row_valid = false;
+                    }
+                }
+            }
+        }
+
+        Ok(valid)
+    }
+
+    fn build_fixed_keys<T: HashTableKeyable>(
+        keys: &[T],
+        valid: &[bool],
+        next: &mut [usize],
+    ) -> HashMap<T, usize> {
+        let mut map = HashMap::<T, usize>::create();
+        for (row, key) in keys.iter().enumerate() {
+            if !valid[row] {
+                continue;
+            }
+
+            let mut inserted = false;
+            let entity = map.insert_key(key, &mut inserted);
+            if !inserted {
+                next[row] = *entity.get_value();
+            }
+            entity.set_value(row);
+        }
+
+        map
+    }
+
+    fn probe_fixed_keys<T: HashTableKeyable>(
+        map: &HashMap<T, usize>,
+        keys: &[T],
+        valid: &[bool],
+        heads: &mut Vec<Option<usize>>,
+    ) {
+        for (row, key) in keys.iter().enumerate() {
+            heads.push(match valid[row] {
+                true => map.find_key(key).map(|entity| *entity.get_value()),
+                false => None,
+            });
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::pipelines::transforms::hash_join::JoinHashTable;
+
+fn sorted_pairs(pairs: (Vec<u32>, Vec<u32>)) -> Vec<(u32, u32)> {
+    let mut pairs = pairs
+        .0
+        .into_iter()
+        .zip(pairs.1.into_iter())
+        .collect::<Vec<_>>();
+    pairs.sort_unstable();
+    pairs
+}
+
+#[test]
+fn test_join_hash_table_fixed_keys() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, true)]);
+    let build_block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![
+        Some(1i32),
+        Some(2),
+        None,
+        Some(1),
+    ])]);
+    let table = JoinHashTable::try_create(build_block.clone(), &build_block)?;
+    assert_eq!(table.num_rows(), 4);
+
+    let probe_block = DataBlock::create_by_array(schema, vec![Series::new(vec![
+        Some(1i32),
+        None,
+        Some(3),
+        Some(2),
+    ])]);
+    let pairs = sorted_pairs(table.probe(&probe_block, 4)?);
+    // NULL keys never match.
+    assert_eq!(pairs, vec![(0, 0), (0, 3), (3, 1)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_join_hash_table_serialized_keys() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+    let build_block = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![1i64, 1, 2]),
+        Series::new(vec!["x", "y", "x"]),
+    ]);
+    let table = JoinHashTable::try_create(build_block.clone(), &build_block)?;
+
+    let probe_block = DataBlock::create_by_array(schema, vec![
+        Series::new(vec![2i64, 1, 1]),
+        Series::new(vec!["x", "x", "z"]),
+    ]);
+    let pairs = sorted_pairs(table.probe(&probe_block, 3)?);
+    assert_eq!(pairs, vec![(0, 2), (1, 0)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_join_hash_table_cross() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
+    let build_block = DataBlock::create_by_array(schema, vec![Series::new(vec![1i32, 2])]);
+    let empty_keys = DataBlock::create(DataSchemaRefExt::create(vec![]), vec![]);
+    let table = JoinHashTable::try_create(build_block, &empty_keys)?;
+
+    // Every row of the probe side matches all the rows of the build side.
+    let pairs = sorted_pairs(table.probe(&empty_keys, 2)?);
+    assert_eq!(pairs, vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+
+    Ok(())
+}
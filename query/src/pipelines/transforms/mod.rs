@@ -14,29 +14,40 @@
 
 pub use transform_aggregator_final::AggregatorFinalTransform;
 pub use transform_aggregator_partial::AggregatorPartialTransform;
+pub use transform_array_join::ArrayJoinTransform;
 pub use transform_create_sets::CreateSetsTransform;
 pub use transform_create_sets::SubQueriesPuller;
+pub use transform_distinct::DistinctTransform;
 pub use transform_expression::ExpressionTransform;
 pub use transform_expression_executor::ExpressionExecutor;
 pub use transform_filter::HavingTransform;
 pub use transform_filter::WhereTransform;
 pub use transform_group_by_final::GroupByFinalTransform;
 pub use transform_group_by_partial::GroupByPartialTransform;
+pub use transform_hash_join::HashJoinTransform;
+pub use transform_hash_join::JoinHashTablePuller;
 pub use transform_limit::LimitTransform;
 pub use transform_limit_by::LimitByTransform;
+pub use transform_materialized_cte::MaterializedCteTransform;
 pub use transform_projection::ProjectionTransform;
 pub use transform_remote::RemoteTransform;
+pub use transform_set_operation::SetOperationTransform;
 pub use transform_sink::SinkTransform;
 pub use transform_sort_merge::SortMergeTransform;
 pub use transform_sort_partial::get_sort_descriptions;
 pub use transform_sort_partial::SortPartialTransform;
 pub use transform_source::SourceTransform;
+pub use transform_window::WindowTransform;
 
 #[cfg(test)]
 mod transform_aggregator_final_test;
 #[cfg(test)]
 mod transform_aggregator_partial_test;
 #[cfg(test)]
+mod transform_array_join_test;
+#[cfg(test)]
+mod transform_distinct_test;
+#[cfg(test)]
 mod transform_expression_test;
 #[cfg(test)]
 mod transform_filter_test;
@@ -45,31 +56,46 @@ mod transform_group_by_final_test;
 #[cfg(test)]
 mod transform_group_by_partial_test;
 #[cfg(test)]
+mod transform_hash_join_test;
+#[cfg(test)]
 mod transform_limit_by_test;
 #[cfg(test)]
 mod transform_limit_test;
 #[cfg(test)]
+mod transform_materialized_cte_test;
+#[cfg(test)]
 mod transform_projection_test;
 #[cfg(test)]
+mod transform_set_operation_test;
+#[cfg(test)]
 mod transform_sort_test;
 #[cfg(test)]
 mod transform_source_test;
+#[cfg(test)]
+mod transform_window_test;
 
 mod transform_aggregator_final;
 mod transform_aggregator_partial;
+mod transform_array_join;
 mod transform_create_sets;
+mod transform_distinct;
 mod transform_expression;
 mod transform_expression_executor;
 mod transform_filter;
 mod transform_group_by_final;
 mod transform_group_by_partial;
+mod transform_hash_join;
 mod transform_limit;
 mod transform_limit_by;
+mod transform_materialized_cte;
 mod transform_projection;
 mod transform_remote;
+mod transform_set_operation;
 mod transform_sort_merge;
 mod transform_sort_partial;
 mod transform_source;
+mod transform_window;
 
 mod group_by;
+mod hash_join;
 mod transform_sink;
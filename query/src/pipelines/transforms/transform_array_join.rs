@@ -0,0 +1,147 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::ArrayJoinPlan;
+use common_planners::Expression;
+use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::Processor;
+
+/// Flattens the arrays of the `arrayJoin` functions into rows, the other columns of the row
+/// are repeated for each element. The arrays of a row are joined element-wise.
+pub struct ArrayJoinTransform {
+    schema: DataSchemaRef,
+    array_join_exprs: Vec<Expression>,
+    input: Arc<dyn Processor>,
+}
+
+impl ArrayJoinTransform {
+    pub fn create(plan: &ArrayJoinPlan) -> Self {
+        ArrayJoinTransform {
+            schema: plan.schema(),
+            array_join_exprs: plan.array_join_exprs.clone(),
+            input: Arc::new(EmptyProcessor::create()),
+        }
+    }
+
+    fn array_join_block(
+        schema: &DataSchemaRef,
+        array_join_exprs: &[Expression],
+        block: &DataBlock,
+    ) -> Result<DataBlock> {
+        let lists = array_join_exprs
+            .iter()
+            .map(|expr| Self::array_join_argument(expr, block))
+            .collect::<Result<Vec<_>>>()?;
+
+        // The rows to repeat and the positions of the elements in the flattened items.
+        let mut rows = vec![];
+        let mut positions = vec![vec![]; lists.len()];
+        for row in 0..block.num_rows() {
+            let mut length = None;
+            for list in &lists {
+                let row_length = match list.is_null(row) {
+                    true => 0,
+                    false => (list.offsets()[row + 1] - list.offsets()[row]) as usize,
+                };
+
+                match length {
+                    Some(length) if length != row_length => {
+                        return Err(ErrorCode::BadArguments(format!(
+                            "Sizes of the arrays passed to arrayJoin must be equal, \
+                             but got {} and {}",
+                            length, row_length
+                        )));
+                    }
+                    _ => length = Some(row_length),
+                }
+            }
+
+            for index in 0..length.unwrap_or(0) {
+                rows.push(row as u32);
+                for (list, positions) in lists.iter().zip(positions.iter_mut()) {
+                    positions.push(list.offsets()[row] as usize + index);
+                }
+            }
+        }
+
+        let repeated = DataBlock::block_take_by_indices(block, &[], &rows)?;
+        let mut columns = repeated.columns().to_vec();
+        for (list, positions) in lists.iter().zip(positions.into_iter()) {
+            let mut positions = positions.into_iter();
+            let items = unsafe { list.values().take_iter_unchecked(&mut positions) }?;
+            columns.push(DataColumn::Array(items));
+        }
+
+        Ok(DataBlock::create(schema.clone(), columns))
+    }
+
+    fn array_join_argument(expr: &Expression, block: &DataBlock) -> Result<DFListArray> {
+        let column_name = match expr {
+            Expression::ScalarFunction { args, .. } => args[0].column_name(),
+            _ => unreachable!(),
+        };
+
+        let series = block.try_column_by_name(&column_name)?.to_array()?;
+        match series.data_type() {
+            DataType::List(_) => Ok(DFListArray::from_arrow_array(
+                series.get_array_ref().as_ref(),
+            )),
+            other => Err(ErrorCode::IllegalDataType(format!(
+                "Expected array type as the argument of arrayJoin, but got {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for ArrayJoinTransform {
+    fn name(&self) -> &str {
+        "ArrayJoinTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let schema = self.schema.clone();
+        let array_join_exprs = self.array_join_exprs.clone();
+        let input_stream = self.input.execute().await?;
+
+        let stream = input_stream.map(move |block| {
+            block.and_then(|block| Self::array_join_block(&schema, &array_join_exprs, &block))
+        });
+        Ok(Box::pin(stream))
+    }
+}
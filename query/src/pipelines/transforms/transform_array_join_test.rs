@@ -0,0 +1,85 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::pipelines::processors::*;
+use crate::pipelines::transforms::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_array_join() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    let mut pipeline = Pipeline::create(ctx.clone());
+    let source = test_source.number_source_transform_for_test(3)?;
+    pipeline.add_source(Arc::new(source))?;
+
+    // arraySlice(array(number, number + 10), 1, number) is [], [1] and [2, 12].
+    let array = Expression::create_scalar_function("arraySlice", vec![
+        Expression::create_scalar_function("array", vec![
+            col("number"),
+            add(col("number"), lit(10u64)),
+        ]),
+        lit(1u64),
+        col("number"),
+    ]);
+    let array_join =
+        Expression::create_scalar_function("arrayJoin", vec![col(&array.column_name())]);
+
+    let plan = PlanBuilder::create(test_source.number_schema_for_test()?)
+        .expression(&[array], "")?
+        .array_join(&[array_join])?
+        .build()?;
+
+    if let PlanNode::ArrayJoin(array_join_plan) = &plan {
+        if let PlanNode::Expression(expression_plan) = array_join_plan.input.as_ref() {
+            pipeline.add_simple_transform(|| {
+                Ok(Box::new(ExpressionTransform::try_create(
+                    expression_plan.input.schema(),
+                    expression_plan.schema.clone(),
+                    expression_plan.exprs.clone(),
+                )?))
+            })?;
+        }
+
+        pipeline.merge_processor()?;
+        pipeline
+            .add_simple_transform(|| Ok(Box::new(ArrayJoinTransform::create(array_join_plan))))?;
+    }
+
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    assert_eq!(result.len(), 1);
+
+    let block = &result[0];
+    assert_eq!(block.num_columns(), 3);
+
+    let values = |values: Vec<u64>| values.into_iter().map(DataValue::from).collect::<Vec<_>>();
+
+    // The row of number 0 is dropped as its array is empty.
+    let number = block.column(0).to_array()?.to_values()?;
+    assert_eq!(number, values(vec![1, 2, 2]));
+    let element = block.column(2).to_array()?.to_values()?;
+    assert_eq!(element, values(vec![1, 2, 12]));
+
+    Ok(())
+}
@@ -133,7 +133,9 @@ type SubqueryData = Result<DataValue>;
 type SharedFuture<'a> = Shared<BoxFuture<'a, SubqueryData>>;
 
 pub struct SubQueriesPuller<'a> {
-    ctx: Arc<QueryContext>,
+    // Released once the pipelines of the sub queries are built, the puller of a materialized
+    // CTE is kept by the query context.
+    ctx: Option<Arc<QueryContext>>,
     expressions: Vec<Expression>,
     sub_queries: Vec<SharedFuture<'a>>,
 }
@@ -145,7 +147,7 @@ impl<'a> SubQueriesPuller<'a> {
     ) -> Arc<Mutex<SubQueriesPuller<'a>>> {
         let expression_len = expressions.len();
         Arc::new(Mutex::new(SubQueriesPuller {
-            ctx,
+            ctx: Some(ctx),
             expressions,
             sub_queries: Vec::with_capacity(expression_len),
         }))
@@ -167,21 +169,19 @@ impl<'a> SubQueriesPuller<'a> {
     }
 
     fn init(&mut self) -> Result<()> {
-        for query_expression in &self.expressions {
-            let subquery_ctx = QueryContext::new(self.ctx.clone());
+        let ctx = self.ctx.take().ok_or_else(|| {
+            ErrorCode::LogicalError("Sub queries of the puller are already initialized")
+        })?;
 
+        for query_expression in &self.expressions {
             match query_expression {
                 Expression::Subquery { query_plan, .. } => {
-                    let plan = query_plan.as_ref().clone();
-                    let builder = PipelineBuilder::create(subquery_ctx);
-                    let pipeline = builder.build(&plan)?;
-                    let shared_future = Self::receive_subquery_res(plan.schema(), pipeline);
+                    let pipeline = PipelineBuilder::build_sub_pipeline(&ctx, query_plan)?;
+                    let shared_future = Self::receive_subquery_res(query_plan.schema(), pipeline);
                     self.sub_queries.push(shared_future);
                 }
                 Expression::ScalarSubquery { query_plan, .. } => {
-                    let plan = query_plan.as_ref().clone();
-                    let builder = PipelineBuilder::create(subquery_ctx);
-                    let pipeline = builder.build(&plan)?;
+                    let pipeline = PipelineBuilder::build_sub_pipeline(&ctx, query_plan)?;
                     let shared_future = Self::receive_scalar_subquery_res(pipeline);
                     self.sub_queries.push(shared_future);
                 }
@@ -0,0 +1,134 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_stream::stream;
+use common_datablocks::DataBlock;
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::Processor;
+
+/// Removes the duplicate rows by a hash set of the rows.
+///
+/// The pipeline is merged to one processor before this transform, so the rows of all the
+/// blocks are deduplicated by the same set.
+pub struct DistinctTransform {
+    input: Arc<dyn Processor>,
+}
+
+impl DistinctTransform {
+    pub fn create() -> Self {
+        DistinctTransform {
+            input: Arc::new(EmptyProcessor::create()),
+        }
+    }
+
+    fn distinct_block(block: &DataBlock, rows: &mut HashSet<Vec<u8>>) -> Result<Option<DataBlock>> {
+        let mut indices = vec![];
+        for (row, key) in rows_keys(block)?.into_iter().enumerate() {
+            if rows.insert(key) {
+                indices.push(row as u32);
+            }
+        }
+
+        take_rows(block, &indices)
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for DistinctTransform {
+    fn name(&self) -> &str {
+        "DistinctTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let mut input_stream = self.input.execute().await?;
+
+        let stream = stream! {
+            let mut rows = HashSet::new();
+            while let Some(block) = input_stream.next().await {
+                match block.and_then(|block| Self::distinct_block(&block, &mut rows)) {
+                    Ok(None) => {}
+                    Ok(Some(block)) => {
+                        yield Ok(block);
+                    }
+                    Err(cause) => {
+                        yield Err(cause);
+                        return;
+                    }
+                }
+            }
+        };
+
+        Ok(Box::pin(stream))
+    }
+}
+
+/// The keys of the rows, a value is serialized after a flag of NULL, so NULLs are equal to
+/// each other, which is different from the keys of group by.
+pub fn rows_keys(block: &DataBlock) -> Result<Vec<Vec<u8>>> {
+    let rows = block.num_rows();
+    let mut keys = vec![vec![]; rows];
+
+    for column in block.columns() {
+        let series = column.to_array()?;
+        if series.data_type() == &DataType::Null {
+            keys.iter_mut().for_each(|key| key.push(0));
+            continue;
+        }
+
+        let mut values = vec![vec![]; rows];
+        series.serialize(&mut values)?;
+        for (row, (key, value)) in keys.iter_mut().zip(values.iter()).enumerate() {
+            match series.is_null(row) {
+                true => key.push(0),
+                false => {
+                    key.push(1);
+                    key.extend_from_slice(value);
+                }
+            }
+        }
+    }
+
+    Ok(keys)
+}
+
+/// Take the rows of the block, None if there are no rows.
+pub fn take_rows(block: &DataBlock, indices: &[u32]) -> Result<Option<DataBlock>> {
+    match indices.len() {
+        0 => Ok(None),
+        len if len == block.num_rows() => Ok(Some(block.clone())),
+        _ => Ok(Some(DataBlock::block_take_by_indices(block, &[], indices)?)),
+    }
+}
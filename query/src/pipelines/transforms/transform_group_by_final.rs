@@ -18,9 +18,11 @@ use std::sync::Arc;
 use std::time::Instant;
 
 use bumpalo::Bump;
+use common_base::TrySpawn;
 use common_datablocks::DataBlock;
 use common_datablocks::HashMethodKind;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::aggregates::get_layout_offsets;
 use common_functions::aggregates::StateAddr;
@@ -30,11 +32,17 @@ use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 use futures::stream::StreamExt;
+use futures::stream::TryStreamExt;
 
+use crate::common::SpillFile;
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::group_by::scatter_by_group_keys;
+use crate::pipelines::transforms::group_by::SPILL_PARTITIONS;
+use crate::sessions::QueryContext;
 
 pub struct GroupByFinalTransform {
+    ctx: Arc<QueryContext>,
     max_block_size: usize,
     aggr_exprs: Vec<Expression>,
     group_exprs: Vec<Expression>,
@@ -45,6 +53,7 @@ pub struct GroupByFinalTransform {
 
 impl GroupByFinalTransform {
     pub fn create(
+        ctx: Arc<QueryContext>,
         schema: DataSchemaRef,
         max_block_size: usize,
         schema_before_group_by: DataSchemaRef,
@@ -52,6 +61,7 @@ impl GroupByFinalTransform {
         group_exprs: Vec<Expression>,
     ) -> Self {
         Self {
+            ctx,
             max_block_size,
             aggr_exprs,
             group_exprs,
@@ -92,6 +102,12 @@ impl Processor for GroupByFinalTransform {
         let aggr_funcs_len = funcs.len();
         let group_expr_len = self.group_exprs.len();
 
+        let aggr_types = self
+            .aggr_exprs
+            .iter()
+            .map(|x| x.to_data_type(&self.schema_before_group_by))
+            .collect::<Result<Vec<_>>>()?;
+
         let group_cols = self
             .group_exprs
             .iter()
@@ -104,125 +120,228 @@ impl Processor for GroupByFinalTransform {
             .map(|c| c.to_data_field(&self.schema_before_group_by))
             .collect::<Result<Vec<_>>>()?;
 
+        let schema = self.schema.clone();
+        let max_block_size = self.max_block_size;
+        let settings = self.ctx.get_settings();
+        let max_bytes = settings.get_max_bytes_before_external_group_by()? as usize;
+        let max_threads = (settings.get_max_threads()? as usize).max(1);
+
         let start = Instant::now();
-        let arena = Bump::new();
 
         let mut stream = self.input.execute().await?;
         let sample_block = DataBlock::empty_with_schema(self.schema_before_group_by.clone());
         let method = DataBlock::choose_hash_method(&sample_block, &group_cols)?;
 
         let (layout, offsets_aggregate_states) = unsafe { get_layout_offsets(&funcs) };
+        // The groups are not pulled by the sources, so the memory limit is checked here.
+        let memory_tracker = self.ctx.get_memory_tracker()?;
 
         macro_rules! apply {
             ($hash_method: ident, $key_array_type: ty, $downcast_fn: ident, $group_func_table: ty) => {{
                 type GroupFuncTable = $group_func_table;
-                let groups_locker = GroupFuncTable::default();
-
-                while let Some(block) = stream.next().await {
-                    let mut groups = groups_locker.write();
-                    let block = block?;
-
-                    let key_array = block.column(aggr_funcs_len).to_array()?;
-                    let key_array: $key_array_type = key_array.$downcast_fn()?;
-
-                    let states_series = (0..aggr_funcs_len)
-                        .map(|i| block.column(i).to_array())
-                        .collect::<Result<Vec<_>>>()?;
-                    let mut states_binary_arrays = Vec::with_capacity(states_series.len());
-
-                    for agg in states_series.iter().take(aggr_funcs_len) {
-                        let aggr_array: &DFStringArray = agg.string()?;
-                        let aggr_array = aggr_array.inner();
-                        states_binary_arrays.push(aggr_array);
-                    }
+                let hash_method = Arc::new($hash_method);
+
+                // Merge the partial states of the block into the groups.
+                let merge_block = {
+                    let funcs = funcs.clone();
+                    let offsets_aggregate_states = offsets_aggregate_states.clone();
+                    let hash_method = hash_method.clone();
+                    let memory_tracker = memory_tracker.clone();
+
+                    move |groups_locker: &GroupFuncTable,
+                          arena: &Bump,
+                          block: DataBlock|
+                          -> Result<()> {
+                        let mut groups = groups_locker.write();
+
+                        let key_array = block.column(aggr_funcs_len).to_array()?;
+                        let key_array: $key_array_type = key_array.$downcast_fn()?;
+
+                        let states_series = (0..aggr_funcs_len)
+                            .map(|i| block.column(i).to_array())
+                            .collect::<Result<Vec<_>>>()?;
+                        let mut states_binary_arrays = Vec::with_capacity(states_series.len());
+
+                        for agg in states_series.iter().take(aggr_funcs_len) {
+                            let aggr_array: &DFStringArray = agg.string()?;
+                            let aggr_array = aggr_array.inner();
+                            states_binary_arrays.push(aggr_array);
+                        }
+
+                        for row in 0..block.num_rows() {
+                            let group_key = hash_method.get_key(&key_array, row);
+                            match groups.get(&group_key) {
+                                None => {
+                                    if aggr_funcs_len == 0 {
+                                        groups.insert(group_key, 0usize);
+                                    } else {
+                                        let place: StateAddr = arena.alloc_layout(layout).into();
+                                        for (idx, func) in funcs.iter().enumerate() {
+                                            let arg_place =
+                                                place.next(offsets_aggregate_states[idx]);
+
+                                            let mut data = states_binary_arrays[idx].value(row);
+                                            func.init_state(arg_place);
+                                            func.deserialize(arg_place, &mut data)?;
+                                        }
+                                        groups.insert(group_key, place.addr());
+                                    }
+                                }
+                                Some(place) => {
+                                    let place: StateAddr = (*place).into();
 
-                    for row in 0..block.num_rows() {
-                        let group_key = $hash_method.get_key(&key_array, row);
-                        match groups.get(&group_key) {
-                            None => {
-                                if aggr_funcs_len == 0 {
-                                    groups.insert(group_key, 0usize);
-                                } else {
-                                    let place: StateAddr = arena.alloc_layout(layout).into();
                                     for (idx, func) in funcs.iter().enumerate() {
                                         let arg_place = place.next(offsets_aggregate_states[idx]);
 
                                         let mut data = states_binary_arrays[idx].value(row);
-                                        func.init_state(arg_place);
-                                        func.deserialize(arg_place, &mut data)?;
+                                        let temp = arena.alloc_layout(funcs[idx].state_layout());
+                                        let temp_addr = temp.into();
+
+                                        funcs[idx].init_state(temp_addr);
+                                        func.deserialize(temp_addr, &mut data)?;
+                                        func.merge(arg_place, temp_addr)?;
                                     }
-                                    groups.insert(group_key, place.addr());
                                 }
-                            }
-                            Some(place) => {
-                                let place: StateAddr = (*place).into();
+                            };
+                        }
+
+                        memory_tracker.check_limit()
+                    }
+                };
 
-                                for (idx, func) in funcs.iter().enumerate() {
-                                    let arg_place = place.next(offsets_aggregate_states[idx]);
+                // Collect the merged states into the final blocks.
+                let finalize = {
+                    let funcs = funcs.clone();
+                    let offsets_aggregate_states = offsets_aggregate_states.clone();
+                    let hash_method = hash_method.clone();
+                    let memory_tracker = memory_tracker.clone();
 
-                                    let mut data = states_binary_arrays[idx].value(row);
-                                    let temp = arena.alloc_layout(funcs[idx].state_layout());
-                                    let temp_addr = temp.into();
+                    move |groups_locker: &GroupFuncTable| -> Result<Vec<DataBlock>> {
+                        let groups = groups_locker.read();
 
-                                    funcs[idx].init_state(temp_addr);
-                                    func.deserialize(temp_addr, &mut data)?;
-                                    func.merge(arg_place, temp_addr)?;
-                                }
+                        let mut aggr_values: Vec<Vec<DataValue>> = {
+                            let mut values = vec![];
+                            for _i in 0..aggr_funcs_len {
+                                values.push(vec![])
                             }
+                            values
                         };
+                        let mut keys = Vec::with_capacity(groups.len());
+                        for (key, place) in groups.iter() {
+                            keys.push(key.clone());
+
+                            let place: StateAddr = (*place).into();
+                            for (idx, func) in funcs.iter().enumerate() {
+                                let arg_place = place.next(offsets_aggregate_states[idx]);
+                                let merge = func.merge_result(arg_place)?;
+                                aggr_values[idx].push(merge);
+                            }
+                        }
+
+                        // Build final state block.
+                        let mut columns: Vec<Series> =
+                            Vec::with_capacity(aggr_funcs_len + group_expr_len);
+
+                        for (i, value) in aggr_values.iter().enumerate() {
+                            columns.push(DataValue::try_into_data_array(
+                                value.as_slice(),
+                                &aggr_types[i],
+                            )?);
+                        }
+
+                        {
+                            let group_columns =
+                                hash_method.de_group_columns(keys, &group_fields)?;
+                            columns.extend_from_slice(&group_columns);
+                        }
+
+                        let mut blocks = vec![];
+                        if !columns.is_empty() {
+                            let block = DataBlock::create_by_array(schema.clone(), columns);
+                            blocks = DataBlock::split_block_by_size(&block, max_block_size)?;
+                        }
+
+                        memory_tracker.check_limit()?;
+                        Ok(blocks)
                     }
-                }
-                let delta = start.elapsed();
-                tracing::debug!("Group by final cost: {:?}", delta);
-
-                // Collect the merge states.
-                let groups = groups_locker.read();
-
-                let mut aggr_values: Vec<Vec<DataValue>> = {
-                    let mut values = vec![];
-                    for _i in 0..aggr_funcs_len {
-                        values.push(vec![])
-                    }
-                    values
                 };
-                let mut keys = Vec::with_capacity(groups.len());
-                for (key, place) in groups.iter() {
-                    keys.push(key.clone());
-
-                    let place: StateAddr = (*place).into();
-                    for (idx, func) in funcs.iter().enumerate() {
-                        let arg_place = place.next(offsets_aggregate_states[idx]);
-                        let merge = func.merge_result(arg_place)?;
-                        aggr_values[idx].push(merge);
-                    }
-                }
 
-                // Build final state block.
-                let mut columns: Vec<Series> = Vec::with_capacity(aggr_funcs_len + group_expr_len);
+                if max_bytes == 0 {
+                    let arena = Bump::new();
+                    let groups_locker = GroupFuncTable::default();
 
-                for (i, value) in aggr_values.iter().enumerate() {
-                    columns.push(DataValue::try_into_data_array(
-                        value.as_slice(),
-                        &self.aggr_exprs[i].to_data_type(&self.schema_before_group_by)?,
-                    )?);
+                    while let Some(block) = stream.next().await {
+                        merge_block(&groups_locker, &arena, block?)?;
+                    }
+                    let delta = start.elapsed();
+                    tracing::debug!("Group by final cost: {:?}", delta);
+
+                    let blocks = finalize(&groups_locker)?;
+                    return Ok(Box::pin(DataBlockStream::create(
+                        self.schema.clone(),
+                        None,
+                        blocks,
+                    )));
                 }
 
-                {
-                    let group_columns = $hash_method.de_group_columns(keys, &group_fields)?;
-                    columns.extend_from_slice(&group_columns);
-                }
+                // Two-level aggregation: the states are radix partitioned by the group keys, and
+                // the partitions exceeding the memory limit are spilled.
+                let mut partitions =
+                    GroupByPartitions::create(self.ctx.clone(), SPILL_PARTITIONS, max_bytes);
 
-                let mut blocks = vec![];
-                if !columns.is_empty() {
-                    let block = DataBlock::create_by_array(self.schema.clone(), columns);
-                    blocks = DataBlock::split_block_by_size(&block, self.max_block_size)?;
+                // The partial radix partitions the states in the same way, its blocks are in one
+                // partition and not copied.
+                while let Some(block) = stream.next().await {
+                    let scattered = scatter_by_group_keys(&block?, aggr_funcs_len)?;
+                    partitions.push(scattered).await?;
                 }
-
-                Ok(Box::pin(DataBlockStream::create(
-                    self.schema.clone(),
-                    None,
-                    blocks,
-                )))
+                let delta = start.elapsed();
+                tracing::debug!("Group by final partition cost: {:?}", delta);
+
+                // A group is in only one partition, so the partitions are merged and finalized in
+                // parallel, only the groups of the `max_threads` running partitions are kept in
+                // memory.
+                let ctx = self.ctx.clone();
+                let merge_block = Arc::new(merge_block);
+                let finalize = Arc::new(finalize);
+                let results = futures::stream::iter(partitions.finish()).map(move |mut blocks| {
+                    let merge_block = merge_block.clone();
+                    let finalize = finalize.clone();
+                    let task = ctx.try_spawn(async move {
+                        let arena = Bump::new();
+                        let groups_locker = GroupFuncTable::default();
+
+                        while let Some(block) = blocks.next().await {
+                            merge_block(&groups_locker, &arena, block?)?;
+                        }
+
+                        finalize(&groups_locker)
+                    });
+
+                    async move {
+                        match task?.await {
+                            Ok(blocks) => blocks,
+                            Err(cause) => Err(ErrorCode::TokioError(format!(
+                                "Cannot join the group by partition task, cause: {}",
+                                cause
+                            ))),
+                        }
+                    }
+                });
+                let results = results.buffered(max_threads);
+                let results = results.flat_map(|blocks| {
+                    let blocks = match blocks {
+                        Ok(blocks) => blocks
+                            .into_iter()
+                            .filter(|block| !block.is_empty())
+                            .map(Ok)
+                            .collect::<Vec<_>>(),
+                        Err(cause) => vec![Err(cause)],
+                    };
+                    futures::stream::iter(blocks)
+                });
+
+                Ok(Box::pin(results))
             }};
         }
 
@@ -251,3 +370,66 @@ impl Processor for GroupByFinalTransform {
         match_hash_method_and_apply! {method, apply}
     }
 }
+
+/// The partial states of the groups, radix partitioned by the group keys.
+struct GroupByPartitions {
+    ctx: Arc<QueryContext>,
+    max_bytes: usize,
+    buffered_bytes: usize,
+    buffered: Vec<Vec<DataBlock>>,
+    spilled: Vec<Vec<SpillFile>>,
+}
+
+impl GroupByPartitions {
+    fn create(ctx: Arc<QueryContext>, partitions: usize, max_bytes: usize) -> Self {
+        GroupByPartitions {
+            ctx,
+            max_bytes,
+            buffered_bytes: 0,
+            buffered: (0..partitions).map(|_| vec![]).collect(),
+            spilled: (0..partitions).map(|_| vec![]).collect(),
+        }
+    }
+
+    /// Buffer the scattered blocks, all the buffered partitions are spilled once they exceed
+    /// the memory limit.
+    async fn push(&mut self, scattered: Vec<DataBlock>) -> Result<()> {
+        for (partition, block) in scattered.into_iter().enumerate() {
+            if !block.is_empty() {
+                self.buffered_bytes += block.memory_size();
+                self.buffered[partition].push(block);
+            }
+        }
+
+        if self.buffered_bytes > self.max_bytes {
+            for (partition, blocks) in self.buffered.iter_mut().enumerate() {
+                if !blocks.is_empty() {
+                    let schema = blocks[0].schema().clone();
+                    let blocks = std::mem::take(blocks);
+                    let spill_file = SpillFile::create(&self.ctx, &schema, blocks).await?;
+                    self.spilled[partition].push(spill_file);
+                }
+            }
+
+            self.buffered_bytes = 0;
+        }
+
+        Ok(())
+    }
+
+    /// The blocks of each partition, the spilled blocks are read back when they are reached.
+    fn finish(self) -> Vec<SendableDataBlockStream> {
+        self.spilled
+            .into_iter()
+            .zip(self.buffered.into_iter())
+            .map(|(spilled, buffered)| {
+                let spilled = futures::stream::iter(spilled)
+                    .then(|spill_file| spill_file.into_stream())
+                    .try_flatten();
+                let buffered = futures::stream::iter(buffered.into_iter().map(Ok));
+
+                Box::pin(spilled.chain(buffered)) as SendableDataBlockStream
+            })
+            .collect()
+    }
+}
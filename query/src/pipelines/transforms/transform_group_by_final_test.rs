@@ -20,7 +20,9 @@ use common_planners::*;
 use common_planners::{self};
 use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
+use tempfile::TempDir;
 
+use crate::configs::Config;
 use crate::pipelines::processors::*;
 use crate::pipelines::transforms::*;
 
@@ -51,6 +53,7 @@ async fn test_transform_final_group_by() -> Result<()> {
     pipeline.add_source(Arc::new(source))?;
     pipeline.add_simple_transform(|| {
         Ok(Box::new(GroupByPartialTransform::create(
+            ctx.clone(),
             aggr_partial.schema(),
             source_schema.clone(),
             aggr_exprs.to_vec(),
@@ -62,6 +65,7 @@ async fn test_transform_final_group_by() -> Result<()> {
     let max_block_size = ctx.get_settings().get_max_block_size()? as usize;
     pipeline.add_simple_transform(|| {
         Ok(Box::new(GroupByFinalTransform::create(
+            ctx.clone(),
             aggr_final.schema(),
             max_block_size,
             source_schema.clone(),
@@ -92,3 +96,52 @@ async fn test_transform_final_group_by() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_final_group_by_with_spill() -> Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let mut config = Config::default();
+    config.storage.disk.temp_data_path = tmp_dir.path().to_str().unwrap().to_string();
+    let ctx = crate::tests::try_create_context_with_config(config)?;
+    ctx.get_settings().set_max_threads(2)?;
+    ctx.get_settings().set_max_block_size(2)?;
+    // The states of every block are spilled.
+    ctx.get_settings()
+        .set_max_bytes_before_external_group_by(1)?;
+
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+    let source = PlanNode::ReadSource(test_source.number_read_source_plan_for_test(6)?);
+    let source_schema = test_source.number_schema_for_test()?;
+
+    // SELECT SUM(number), AVG(number), number FROM numbers(6) GROUP BY number;
+    let aggr_exprs = &[sum(col("number")), avg(col("number"))];
+    let group_exprs = &[col("number")];
+    let plan = PlanBuilder::from(&source)
+        .aggregate_partial(aggr_exprs, group_exprs)?
+        .aggregate_final(source_schema, aggr_exprs, group_exprs)?
+        .build()?;
+
+    let pipeline_builder = PipelineBuilder::create(ctx.clone());
+    let mut pipeline = pipeline_builder.build(&plan)?;
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+
+    let expected = vec![
+        "+-------------+-------------+--------+",
+        "| sum(number) | avg(number) | number |",
+        "+-------------+-------------+--------+",
+        "| 0           | 0           | 0      |",
+        "| 1           | 1           | 1      |",
+        "| 2           | 2           | 2      |",
+        "| 3           | 3           | 3      |",
+        "| 4           | 4           | 4      |",
+        "| 5           | 5           | 5      |",
+        "+-------------+-------------+--------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    // The spill files are removed after the merge.
+    assert_eq!(std::fs::read_dir(tmp_dir.path())?.count(), 0);
+
+    Ok(())
+}
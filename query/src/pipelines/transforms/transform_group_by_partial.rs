@@ -22,16 +22,22 @@ use common_datablocks::HashMethodKind;
 use common_datavalues::prelude::*;
 use common_exception::Result;
 use common_planners::Expression;
+use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
+use futures::StreamExt;
 
+use crate::common::SpillFile;
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::group_by::scatter_by_group_keys;
 use crate::pipelines::transforms::group_by::Aggregator;
 use crate::pipelines::transforms::group_by::AggregatorParams;
 use crate::pipelines::transforms::group_by::PolymorphicKeysHelper;
+use crate::sessions::QueryContext;
 
 pub struct GroupByPartialTransform {
+    ctx: Arc<QueryContext>,
     aggr_exprs: Vec<Expression>,
     group_exprs: Vec<Expression>,
 
@@ -42,12 +48,14 @@ pub struct GroupByPartialTransform {
 
 impl GroupByPartialTransform {
     pub fn create(
+        ctx: Arc<QueryContext>,
         schema: DataSchemaRef,
         schema_before_group_by: DataSchemaRef,
         aggr_exprs: Vec<Expression>,
         group_exprs: Vec<Expression>,
     ) -> Self {
         Self {
+            ctx,
             aggr_exprs,
             group_exprs,
             schema,
@@ -71,19 +79,68 @@ impl GroupByPartialTransform {
     ) -> Result<SendableDataBlockStream> {
         let start = Instant::now();
 
-        let stream = self.input.execute().await?;
+        let mut stream = self.input.execute().await?;
         let aggr_exprs = &self.aggr_exprs;
         let schema = self.schema_before_group_by.clone();
         let aggregator_params = AggregatorParams::try_create(schema, aggr_exprs)?;
 
         let aggregator = Aggregator::create(method, aggregator_params);
-        let state = aggregator.aggregate(group_cols, stream).await?;
+        let finalized_schema = self.schema.clone();
+
+        // The states exceeding the memory limit are spilled, and read back by the final.
+        let max_bytes = self
+            .ctx
+            .get_settings()
+            .get_max_bytes_before_external_group_by()? as usize;
+        let mut spill_files = vec![];
+        let state = loop {
+            let (state, finished) = aggregator
+                .aggregate(&group_cols, &mut stream, max_bytes)
+                .await?;
+            if finished {
+                break state;
+            }
+
+            let blocks = self.partitioned_states(&aggregator, &state)?;
+            let spill_file = SpillFile::create(&self.ctx, &finalized_schema, blocks).await?;
+            spill_files.push(spill_file);
+        };
 
         let delta = start.elapsed();
         tracing::debug!("Group by partial cost: {:?}", delta);
 
-        let finalized_schema = self.schema.clone();
-        aggregator.aggregate_finalized(&state, finalized_schema)
+        if max_bytes == 0 {
+            return aggregator.aggregate_finalized(&state, finalized_schema);
+        }
+
+        let blocks = self.partitioned_states(&aggregator, &state)?;
+        let stream = Box::pin(DataBlockStream::create(finalized_schema, None, blocks));
+        if spill_files.is_empty() {
+            return Ok(stream);
+        }
+
+        tracing::debug!("Group by partial spilled {} states", spill_files.len());
+        let mut streams = Vec::with_capacity(spill_files.len() + 1);
+        for spill_file in spill_files {
+            streams.push(spill_file.into_stream().await?);
+        }
+        streams.push(stream);
+        Ok(Box::pin(futures::stream::iter(streams).flatten()))
+    }
+
+    /// Serialize the states radix partitioned by the group keys, one block per partition, so
+    /// that the final could merge the partitions without scattering the blocks again.
+    fn partitioned_states<Method: HashMethod + PolymorphicKeysHelper<Method>>(
+        &self,
+        aggregator: &Aggregator<Method>,
+        state: &Method::State,
+    ) -> Result<Vec<DataBlock>> {
+        let block = aggregator.serialize_states(state, self.schema.clone())?;
+        let blocks = scatter_by_group_keys(&block, self.aggr_exprs.len())?;
+        Ok(blocks
+            .into_iter()
+            .filter(|block| !block.is_empty())
+            .collect())
     }
 }
 
@@ -22,6 +22,7 @@ use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
 
 use crate::pipelines::processors::*;
+use crate::pipelines::transforms::group_by;
 use crate::pipelines::transforms::*;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
@@ -44,6 +45,7 @@ async fn test_transform_partial_group_by() -> Result<()> {
     pipeline.add_source(Arc::new(source))?;
     pipeline.add_simple_transform(|| {
         Ok(Box::new(GroupByPartialTransform::create(
+            ctx.clone(),
             aggr_partial.schema(),
             source_schema.clone(),
             aggr_exprs.clone(),
@@ -75,3 +77,52 @@ async fn test_transform_partial_group_by() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_partial_group_by_radix_partitioned() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    // The states may be spilled, so they are radix partitioned.
+    ctx.get_settings()
+        .set_max_bytes_before_external_group_by(1024 * 1024 * 1024)?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    let aggr_exprs = vec![sum(col("number"))];
+    let group_exprs = vec![col("number")];
+    let aggr_partial = PlanBuilder::create(test_source.number_schema_for_test()?)
+        .aggregate_partial(&aggr_exprs, &group_exprs)?
+        .build()?;
+
+    // Pipeline.
+    let mut pipeline = Pipeline::create(ctx.clone());
+    let source = test_source.number_source_transform_for_test(100)?;
+    let source_schema = test_source.number_schema_for_test()?;
+
+    pipeline.add_source(Arc::new(source))?;
+    pipeline.add_simple_transform(|| {
+        Ok(Box::new(GroupByPartialTransform::create(
+            ctx.clone(),
+            aggr_partial.schema(),
+            source_schema.clone(),
+            aggr_exprs.clone(),
+            group_exprs.clone(),
+        )))
+    })?;
+
+    // Result.
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    assert!(result.len() > 1);
+    assert_eq!(
+        result.iter().map(|block| block.num_rows()).sum::<usize>(),
+        100
+    );
+
+    // Every block is in one partition.
+    for block in &result {
+        let scattered = group_by::scatter_by_group_keys(block, 1)?;
+        assert_eq!(scattered.len(), group_by::SPILL_PARTITIONS);
+        assert_eq!(scattered.iter().filter(|b| !b.is_empty()).count(), 1);
+    }
+
+    Ok(())
+}
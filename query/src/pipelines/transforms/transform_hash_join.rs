@@ -0,0 +1,442 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_stream::stream;
+use common_base::TrySpawn;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_planners::Expression;
+use common_planners::JoinPlan;
+use common_planners::JoinType;
+use common_planners::PlanNode;
+use common_streams::SendableDataBlockStream;
+use futures::future::BoxFuture;
+use futures::future::Shared;
+use futures::Future;
+use futures::FutureExt;
+use futures::StreamExt;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::PipelineBuilder;
+use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::hash_join::JoinHashTable;
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::QueryContext;
+
+/// Probes the blocks of the left side against the hash table of the right (build) side.
+///
+/// The hash table is built once, and shared by all the probe processors. For RIGHT and FULL
+/// joins, the pipeline of the probe side is merged to one processor, which emits the unmatched
+/// rows of the build side after all the rows are probed.
+pub struct HashJoinTransform {
+    ctx: Arc<QueryContext>,
+    joiner: Arc<HashJoiner>,
+    input: Arc<dyn Processor>,
+    hash_table_puller: Arc<Mutex<JoinHashTablePuller>>,
+}
+
+impl HashJoinTransform {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        plan: &JoinPlan,
+        hash_table_puller: Arc<Mutex<JoinHashTablePuller>>,
+    ) -> Result<HashJoinTransform> {
+        let probe_schema = plan.left.schema();
+        let build_schema = plan.right.schema();
+        let joined_schema = JoinPlan::joined_schema(&probe_schema, &build_schema);
+
+        let probe_keys = keys_executor(&probe_schema, &plan.left_keys)?;
+        let conditions = match &plan.other_conditions {
+            None => None,
+            Some(conditions) => {
+                let field = conditions.to_data_field(&joined_schema)?;
+                Some(ExpressionExecutor::try_create(
+                    "join conditions executor",
+                    joined_schema.clone(),
+                    DataSchemaRefExt::create(vec![field]),
+                    vec![conditions.clone()],
+                    false,
+                )?)
+            }
+        };
+
+        Ok(HashJoinTransform {
+            ctx,
+            joiner: Arc::new(HashJoiner {
+                join_type: plan.join_type,
+                probe_schema,
+                build_schema,
+                joined_schema,
+                schema: plan.schema(),
+                probe_keys,
+                conditions,
+            }),
+            input: Arc::new(EmptyProcessor::create()),
+            hash_table_puller,
+        })
+    }
+
+    async fn pull_hash_table(&self) -> Result<Arc<JoinHashTable>> {
+        let hash_table = self.hash_table_puller.lock().take_hash_table()?;
+
+        match self.ctx.try_spawn(hash_table)?.await {
+            Ok(hash_table) => hash_table,
+            Err(cause) => Err(ErrorCode::TokioError(format!(
+                "Cannot build the hash table of join. cause: {}",
+                cause
+            ))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for HashJoinTransform {
+    fn name(&self) -> &str {
+        "HashJoinTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let hash_table = self.pull_hash_table().await?;
+        let mut input_stream = self.input.execute().await?;
+        let joiner = self.joiner.clone();
+
+        let stream = stream! {
+            let keeps_unmatched_build = joiner.join_type.keeps_unmatched_right();
+            let mut build_matched = match keeps_unmatched_build {
+                true => vec![false; hash_table.num_rows()],
+                false => vec![],
+            };
+
+            while let Some(block) = input_stream.next().await {
+                match block.and_then(|block| joiner.join(&hash_table, &block, &mut build_matched)) {
+                    Ok(blocks) => {
+                        for block in blocks {
+                            yield Ok(block);
+                        }
+                    }
+                    Err(cause) => {
+                        yield Err(cause);
+                        return;
+                    }
+                }
+            }
+
+            if keeps_unmatched_build {
+                match joiner.unmatched_build_block(&hash_table, &build_matched) {
+                    Ok(None) => {}
+                    Ok(Some(block)) => {
+                        yield Ok(block);
+                    }
+                    Err(cause) => {
+                        yield Err(cause);
+                    }
+                }
+            }
+        };
+
+        Ok(Box::pin(stream))
+    }
+}
+
+struct HashJoiner {
+    join_type: JoinType,
+    probe_schema: DataSchemaRef,
+    build_schema: DataSchemaRef,
+    joined_schema: DataSchemaRef,
+    schema: DataSchemaRef,
+    probe_keys: Option<ExpressionExecutor>,
+    conditions: Option<ExpressionExecutor>,
+}
+
+impl HashJoiner {
+    fn join(
+        &self,
+        hash_table: &JoinHashTable,
+        probe_block: &DataBlock,
+        build_matched: &mut [bool],
+    ) -> Result<Vec<DataBlock>> {
+        let rows = probe_block.num_rows();
+        let keys_block = evaluate_keys(&self.probe_keys, probe_block)?;
+        let (mut probe_indices, mut build_indices) = hash_table.probe(&keys_block, rows)?;
+
+        let mut joined_block = None;
+        if let (Some(conditions), false) = (&self.conditions, probe_indices.is_empty()) {
+            let block =
+                self.joined_block(probe_block, hash_table, &probe_indices, &build_indices)?;
+            let predicate = conditions.execute(&block)?.column(0).to_array()?;
+            let predicate = predicate.cast_with_type(&DataType::Boolean)?;
+
+            let mut filtered_probe_indices = Vec::with_capacity(probe_indices.len());
+            let mut filtered_build_indices = Vec::with_capacity(build_indices.len());
+            for (index, value) in predicate.bool()?.collect_values().iter().enumerate() {
+                if let Some(true) = value {
+                    filtered_probe_indices.push(probe_indices[index]);
+                    filtered_build_indices.push(build_indices[index]);
+                }
+            }
+
+            probe_indices = filtered_probe_indices;
+            build_indices = filtered_build_indices;
+            joined_block = Some(DataBlock::filter_block(&block, predicate)?);
+        }
+
+        if self.join_type.keeps_unmatched_right() {
+            for build_row in &build_indices {
+                build_matched[*build_row as usize] = true;
+            }
+        }
+
+        let mut probe_matched = vec![false; rows];
+        for probe_row in &probe_indices {
+            probe_matched[*probe_row as usize] = true;
+        }
+
+        let mut blocks = vec![];
+        match self.join_type {
+            JoinType::Semi | JoinType::Anti | JoinType::NullAwareAnti => {
+                let keeps_matched = self.join_type == JoinType::Semi;
+                let mut indices = Self::rows_of(&probe_matched, keeps_matched);
+                if self.join_type == JoinType::NullAwareAnti && hash_table.num_rows() > 0 {
+                    indices = match hash_table.has_null_keys() {
+                        true => vec![],
+                        false => {
+                            let valid = JoinHashTable::valid_rows(&keys_block)?;
+                            let indices = indices.into_iter();
+                            indices.filter(|row| valid[*row as usize]).collect()
+                        }
+                    };
+                }
+
+                if !indices.is_empty() {
+                    let columns = take_columns(probe_block, &self.probe_schema, &indices)?;
+                    blocks.push(DataBlock::create(self.schema.clone(), columns));
+                }
+            }
+            _ => {
+                let joined_block = match joined_block {
+                    Some(joined_block) => joined_block,
+                    None => {
+                        self.joined_block(probe_block, hash_table, &probe_indices, &build_indices)?
+                    }
+                };
+
+                if joined_block.num_rows() > 0 {
+                    let columns = joined_block.columns().to_vec();
+                    blocks.push(DataBlock::create(self.schema.clone(), columns));
+                }
+
+                let indices = match self.join_type.keeps_unmatched_left() {
+                    true => Self::rows_of(&probe_matched, false),
+                    false => vec![],
+                };
+
+                if !indices.is_empty() {
+                    let mut columns = take_columns(probe_block, &self.probe_schema, &indices)?;
+                    columns.extend(null_columns(&self.build_schema, indices.len()));
+                    blocks.push(DataBlock::create(self.schema.clone(), columns));
+                }
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    fn unmatched_build_block(
+        &self,
+        hash_table: &JoinHashTable,
+        build_matched: &[bool],
+    ) -> Result<Option<DataBlock>> {
+        let indices = Self::rows_of(build_matched, false);
+        if indices.is_empty() {
+            return Ok(None);
+        }
+
+        let mut columns = null_columns(&self.probe_schema, indices.len());
+        columns.extend(take_columns(
+            hash_table.block(),
+            &self.build_schema,
+            &indices,
+        )?);
+        Ok(Some(DataBlock::create(self.schema.clone(), columns)))
+    }
+
+    fn joined_block(
+        &self,
+        probe_block: &DataBlock,
+        hash_table: &JoinHashTable,
+        probe_indices: &[u32],
+        build_indices: &[u32],
+    ) -> Result<DataBlock> {
+        let mut columns = take_columns(probe_block, &self.probe_schema, probe_indices)?;
+        columns.extend(take_columns(
+            hash_table.block(),
+            &self.build_schema,
+            build_indices,
+        )?);
+        Ok(DataBlock::create(self.joined_schema.clone(), columns))
+    }
+
+    fn rows_of(matched: &[bool], keeps_matched: bool) -> Vec<u32> {
+        matched
+            .iter()
+            .enumerate()
+            .filter(|(_, matched)| **matched == keeps_matched)
+            .map(|(row, _)| row as u32)
+            .collect()
+    }
+}
+
+type HashTableFuture = Shared<BoxFuture<'static, Result<Arc<JoinHashTable>>>>;
+
+/// Builds the hash table from the pipeline of the build side, shared by all the probe processors.
+pub struct JoinHashTablePuller {
+    ctx: Arc<QueryContext>,
+    build_plan: Arc<PlanNode>,
+    build_keys: Vec<Expression>,
+    hash_table: Option<HashTableFuture>,
+}
+
+impl JoinHashTablePuller {
+    pub fn create(
+        ctx: Arc<QueryContext>,
+        build_plan: Arc<PlanNode>,
+        build_keys: Vec<Expression>,
+    ) -> Arc<Mutex<JoinHashTablePuller>> {
+        Arc::new(Mutex::new(JoinHashTablePuller {
+            ctx,
+            build_plan,
+            build_keys,
+            hash_table: None,
+        }))
+    }
+
+    pub fn take_hash_table(
+        &mut self,
+    ) -> Result<impl Future<Output = Result<Arc<JoinHashTable>>> + 'static> {
+        if self.hash_table.is_none() {
+            self.init()?;
+        }
+
+        match &self.hash_table {
+            Some(hash_table) => Ok(hash_table.clone()),
+            None => Err(ErrorCode::LogicalError(
+                "Hash table of join is not initialized",
+            )),
+        }
+    }
+
+    fn init(&mut self) -> Result<()> {
+        let schema = self.build_plan.schema();
+        let mut pipeline = PipelineBuilder::build_sub_pipeline(&self.ctx, &self.build_plan)?;
+        let build_keys = keys_executor(&schema, &self.build_keys)?;
+        // The build side is buffered, so the memory limit is checked as it grows.
+        let memory_tracker = self.ctx.get_memory_tracker()?;
+
+        let hash_table_future = async move {
+            let mut stream = pipeline.execute().await?;
+
+            let mut blocks = vec![];
+            while let Some(block) = stream.next().await {
+                let block = block?;
+                if !block.is_empty() {
+                    blocks.push(DataBlock::create(schema.clone(), block.columns().to_vec()));
+                }
+                memory_tracker.check_limit()?;
+            }
+
+            let block = match blocks.is_empty() {
+                true => DataBlock::empty_with_schema(schema.clone()),
+                false => DataBlock::concat_blocks(&blocks)?,
+            };
+
+            let keys_block = evaluate_keys(&build_keys, &block)?;
+            let hash_table = JoinHashTable::try_create(block, &keys_block)?;
+            memory_tracker.check_limit()?;
+            Ok(Arc::new(hash_table))
+        };
+
+        self.hash_table = Some(hash_table_future.boxed().shared());
+        Ok(())
+    }
+}
+
+fn keys_executor(
+    schema: &DataSchemaRef,
+    keys: &[Expression],
+) -> Result<Option<ExpressionExecutor>> {
+    if keys.is_empty() {
+        return Ok(None);
+    }
+
+    let fields = keys
+        .iter()
+        .map(|key| key.to_data_field(schema))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Some(ExpressionExecutor::try_create(
+        "join keys executor",
+        schema.clone(),
+        DataSchemaRefExt::create(fields),
+        keys.to_vec(),
+        false,
+    )?))
+}
+
+fn evaluate_keys(executor: &Option<ExpressionExecutor>, block: &DataBlock) -> Result<DataBlock> {
+    match executor {
+        // Cross join, without keys.
+        None => Ok(DataBlock::create(DataSchemaRefExt::create(vec![]), vec![])),
+        Some(executor) => executor.execute(block),
+    }
+}
+
+fn take_columns(
+    block: &DataBlock,
+    schema: &DataSchemaRef,
+    indices: &[u32],
+) -> Result<Vec<DataColumn>> {
+    let taken_block = DataBlock::block_take_by_indices(block, &[], indices)?;
+    schema
+        .fields()
+        .iter()
+        .map(|f| taken_block.try_column_by_name(f.name()).map(|c| c.clone()))
+        .collect()
+}
+
+fn null_columns(schema: &DataSchemaRef, rows: usize) -> Vec<DataColumn> {
+    schema
+        .fields()
+        .iter()
+        .map(|f| DataColumn::Constant(DataValue::from(f.data_type()), rows))
+        .collect()
+}
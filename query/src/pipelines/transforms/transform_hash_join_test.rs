@@ -0,0 +1,143 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+
+use crate::pipelines::processors::*;
+
+async fn execute_join(
+    join_type: JoinType,
+    other_conditions: Option<Expression>,
+) -> Result<Vec<common_datablocks::DataBlock>> {
+    let ctx = crate::tests::try_create_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    // left: number in [0, 5), right: r in [0, 3)
+    let left = PlanNode::ReadSource(test_source.number_read_source_plan_for_test(5)?);
+    let right = PlanNode::ReadSource(test_source.number_read_source_plan_for_test(3)?);
+    let right = PlanBuilder::from(&right)
+        .project(&[col("number").alias("r")])?
+        .build()?;
+
+    let plan = PlanBuilder::from(&left)
+        .join(
+            &right,
+            join_type,
+            &[col("number")],
+            &[col("r")],
+            other_conditions,
+        )?
+        .build()?;
+
+    let pipeline_builder = PipelineBuilder::create(ctx);
+    let mut pipeline = pipeline_builder.build(&plan)?;
+    let stream = pipeline.execute().await?;
+    stream.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_hash_join_inner() -> Result<()> {
+    let result = execute_join(JoinType::Inner, None).await?;
+    let expected = vec![
+        "+--------+---+",
+        "| number | r |",
+        "+--------+---+",
+        "| 0      | 0 |",
+        "| 1      | 1 |",
+        "| 2      | 2 |",
+        "+--------+---+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_hash_join_outer() -> Result<()> {
+    let result = execute_join(JoinType::Left, None).await?;
+    let expected = vec![
+        "+--------+------+",
+        "| number | r    |",
+        "+--------+------+",
+        "| 0      | 0    |",
+        "| 1      | 1    |",
+        "| 2      | 2    |",
+        "| 3      | NULL |",
+        "| 4      | NULL |",
+        "+--------+------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    let conditions = Some(col("number").gt(lit(0u64)));
+    let result = execute_join(JoinType::Full, conditions).await?;
+    let expected = vec![
+        "+--------+------+",
+        "| number | r    |",
+        "+--------+------+",
+        "| 0      | NULL |",
+        "| 1      | 1    |",
+        "| 2      | 2    |",
+        "| 3      | NULL |",
+        "| 4      | NULL |",
+        "| NULL   | 0    |",
+        "+--------+------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_hash_join_semi_anti() -> Result<()> {
+    let conditions = Some(col("number").gt(lit(0u64)));
+    let result = execute_join(JoinType::Semi, conditions.clone()).await?;
+    let expected = vec![
+        "+--------+",
+        "| number |",
+        "+--------+",
+        "| 1      |",
+        "| 2      |",
+        "+--------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    let result = execute_join(JoinType::Anti, conditions).await?;
+    let expected = vec![
+        "+--------+",
+        "| number |",
+        "+--------+",
+        "| 0      |",
+        "| 3      |",
+        "| 4      |",
+        "+--------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    // The NULL keys are covered by the stateless tests.
+    let result = execute_join(JoinType::NullAwareAnti, None).await?;
+    let expected = vec![
+        "+--------+",
+        "| number |",
+        "+--------+",
+        "| 3      |",
+        "| 4      |",
+        "+--------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
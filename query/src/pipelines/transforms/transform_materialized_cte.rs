@@ -0,0 +1,137 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_base::TrySpawn;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::DataColumn;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::MaterializedCtePlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::SubQueriesPuller;
+use crate::sessions::MaterializedCte;
+use crate::sessions::QueryContext;
+
+/// Reads the result of a materialized CTE.
+///
+/// The CTE is pulled as a subquery of the query, by the [`SubQueriesPuller`] shared by all the
+/// references to it (kept in the query context by the name of the CTE), so it is executed once
+/// and the result is read by every reference.
+pub struct MaterializedCteTransform {
+    ctx: Arc<QueryContext>,
+    name: String,
+    schema: DataSchemaRef,
+    sub_queries_puller: MaterializedCte,
+}
+
+impl MaterializedCteTransform {
+    pub fn create(ctx: Arc<QueryContext>, plan: &MaterializedCtePlan) -> Self {
+        let sub_queries_puller = ctx.get_or_materialize_cte(&plan.name, || {
+            let expression = Expression::Subquery {
+                name: plan.name.clone(),
+                query_plan: plan.input.clone(),
+            };
+            SubQueriesPuller::create(ctx.clone(), vec![expression])
+        });
+
+        MaterializedCteTransform {
+            ctx,
+            name: plan.name.clone(),
+            schema: plan.schema(),
+            sub_queries_puller,
+        }
+    }
+
+    // The result of the subquery is a list of values for each column, or a struct of them
+    // if there is more than one column.
+    fn to_block(&self, data: DataValue) -> Result<DataBlock> {
+        let lists = match data {
+            DataValue::Struct(lists) => lists,
+            list => vec![list],
+        };
+
+        let mut columns = Vec::with_capacity(lists.len());
+        for (field, list) in self.schema.fields().iter().zip(lists) {
+            match list {
+                DataValue::List(Some(values), _) => {
+                    let series = DataValue::try_into_data_array(&values, field.data_type())?;
+                    columns.push(DataColumn::Array(series));
+                }
+                _ => {
+                    return Err(ErrorCode::LogicalError(format!(
+                        "The result of the CTE {} must be lists of values",
+                        self.name
+                    )))
+                }
+            }
+        }
+
+        Ok(DataBlock::create(self.schema.clone(), columns))
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for MaterializedCteTransform {
+    fn name(&self) -> &str {
+        "MaterializedCteTransform"
+    }
+
+    fn connect_to(&mut self, _: Arc<dyn Processor>) -> Result<()> {
+        Result::Err(ErrorCode::LogicalError(
+            "Cannot call MaterializedCteTransform connect_to",
+        ))
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![Arc::new(EmptyProcessor::create())]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let data = self.sub_queries_puller.lock().take_subquery_data(0)?;
+        let data = match self.ctx.try_spawn(data)?.await {
+            Ok(data) => data?,
+            Err(cause) => {
+                return Err(ErrorCode::TokioError(format!(
+                    "Cannot materialize the CTE {}. cause: {}",
+                    self.name, cause
+                )));
+            }
+        };
+
+        let block = self.to_block(data)?;
+        let blocks = match block.is_empty() {
+            true => vec![],
+            false => vec![block],
+        };
+        Ok(Box::pin(DataBlockStream::create(
+            self.schema.clone(),
+            None,
+            blocks,
+        )))
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::pipelines::processors::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_materialized_cte() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    // Both inputs of the union reference the same CTE.
+    let source = PlanNode::ReadSource(test_source.number_read_source_plan_for_test(3)?);
+    let cte = PlanBuilder::from(&source)
+        .materialized_cte("_cte_t_1")?
+        .build()?;
+    let plan = PlanBuilder::from(&cte).union(&[cte.clone()])?.build()?;
+
+    let pipeline_builder = PipelineBuilder::create(ctx.clone());
+    let mut pipeline = pipeline_builder.build(&plan)?;
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+
+    let expected = vec![
+        "+--------+",
+        "| number |",
+        "+--------+",
+        "| 0      |",
+        "| 0      |",
+        "| 1      |",
+        "| 1      |",
+        "| 2      |",
+        "| 2      |",
+        "+--------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    // The numbers are read only once.
+    assert_eq!(ctx.get_progress_value().read_rows, 3);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_materialized_cte_columns() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    // The CTE of more than one column is pulled as a struct of them.
+    let source = PlanNode::ReadSource(test_source.number_read_source_plan_for_test(3)?);
+    let cte = PlanBuilder::from(&source)
+        .project(&[col("number"), modular(col("number"), lit(2u64)).alias("a")])?
+        .materialized_cte("_cte_t_1")?
+        .build()?;
+    let plan = PlanBuilder::from(&cte).union(&[cte.clone()])?.build()?;
+
+    let pipeline_builder = PipelineBuilder::create(ctx.clone());
+    let mut pipeline = pipeline_builder.build(&plan)?;
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+
+    let expected = vec![
+        "+--------+---+",
+        "| number | a |",
+        "+--------+---+",
+        "| 0      | 0 |",
+        "| 0      | 0 |",
+        "| 1      | 1 |",
+        "| 1      | 1 |",
+        "| 2      | 0 |",
+        "| 2      | 0 |",
+        "+--------+---+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+    assert_eq!(ctx.get_progress_value().read_rows, 3);
+
+    Ok(())
+}
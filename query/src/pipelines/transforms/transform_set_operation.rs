@@ -0,0 +1,155 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_stream::stream;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_planners::PlanNode;
+use common_planners::SetOperationPlan;
+use common_planners::SetOperator;
+use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::PipelineBuilder;
+use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::transform_distinct::rows_keys;
+use crate::pipelines::transforms::transform_distinct::take_rows;
+use crate::sessions::QueryContext;
+
+/// INTERSECT and EXCEPT of the left input and the right input.
+///
+/// The rows of the right input are collected into a hash map from the rows to their counts
+/// before the left input is read. The pipeline of the left input is merged to one processor
+/// before this transform, the counts are consumed by the matched rows of the left input.
+pub struct SetOperationTransform {
+    ctx: Arc<QueryContext>,
+    op: SetOperator,
+    all: bool,
+    right: Arc<PlanNode>,
+    input: Arc<dyn Processor>,
+}
+
+impl SetOperationTransform {
+    pub fn create(ctx: Arc<QueryContext>, plan: &SetOperationPlan) -> Self {
+        SetOperationTransform {
+            ctx,
+            op: plan.op,
+            all: plan.all,
+            right: plan.right.clone(),
+            input: Arc::new(EmptyProcessor::create()),
+        }
+    }
+
+    async fn right_rows(&self) -> Result<HashMap<Vec<u8>, usize>> {
+        let mut pipeline = PipelineBuilder::build_sub_pipeline(&self.ctx, &self.right)?;
+        let mut stream = pipeline.execute().await?;
+
+        let mut rows = HashMap::new();
+        while let Some(block) = stream.next().await {
+            for key in rows_keys(&block?)? {
+                *rows.entry(key).or_insert(0) += 1;
+            }
+        }
+
+        Ok(rows)
+    }
+
+    fn filter_block(
+        op: SetOperator,
+        all: bool,
+        block: &DataBlock,
+        right_rows: &mut HashMap<Vec<u8>, usize>,
+    ) -> Result<Option<DataBlock>> {
+        let mut indices = vec![];
+        for (row, key) in rows_keys(block)?.into_iter().enumerate() {
+            let keeps_row = match op {
+                SetOperator::Intersect => match right_rows.get_mut(&key) {
+                    // Without ALL, a row is matched once at most.
+                    Some(count) if *count > 0 => {
+                        *count = if all { *count - 1 } else { 0 };
+                        true
+                    }
+                    _ => false,
+                },
+                _ => match right_rows.get_mut(&key) {
+                    Some(count) if all && *count > 0 => {
+                        *count -= 1;
+                        false
+                    }
+                    Some(_) => all,
+                    // Without ALL, the row is recorded to drop the duplicates of it.
+                    None if !all => {
+                        right_rows.insert(key, 0);
+                        true
+                    }
+                    None => true,
+                },
+            };
+
+            if keeps_row {
+                indices.push(row as u32);
+            }
+        }
+
+        take_rows(block, &indices)
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for SetOperationTransform {
+    fn name(&self) -> &str {
+        "SetOperationTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let mut right_rows = self.right_rows().await?;
+        let mut input_stream = self.input.execute().await?;
+        let (op, all) = (self.op, self.all);
+
+        let stream = stream! {
+            while let Some(block) = input_stream.next().await {
+                match block.and_then(|block| Self::filter_block(op, all, &block, &mut right_rows)) {
+                    Ok(None) => {}
+                    Ok(Some(block)) => {
+                        yield Ok(block);
+                    }
+                    Err(cause) => {
+                        yield Err(cause);
+                        return;
+                    }
+                }
+            }
+        };
+
+        Ok(Box::pin(stream))
+    }
+}
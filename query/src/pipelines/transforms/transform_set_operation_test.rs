@@ -0,0 +1,75 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+
+use crate::pipelines::processors::*;
+
+async fn execute_set_operation(op: SetOperator, all: bool) -> Result<Vec<DataBlock>> {
+    let ctx = crate::tests::try_create_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    // left: a in [0, 1, 2, 0, 1, 2], right: b in [0, 1, 0]
+    let left = PlanNode::ReadSource(test_source.number_read_source_plan_for_test(6)?);
+    let left = PlanBuilder::from(&left)
+        .project(&[modular(col("number"), lit(3u64)).alias("a")])?
+        .build()?;
+    let right = PlanNode::ReadSource(test_source.number_read_source_plan_for_test(3)?);
+    let right = PlanBuilder::from(&right)
+        .project(&[modular(col("number"), lit(2u64)).alias("b")])?
+        .build()?;
+
+    let plan = PlanBuilder::from(&left)
+        .set_operation(op, all, &right)?
+        .build()?;
+
+    let pipeline_builder = PipelineBuilder::create(ctx);
+    let mut pipeline = pipeline_builder.build(&plan)?;
+    let stream = pipeline.execute().await?;
+    stream.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_intersect() -> Result<()> {
+    let result = execute_set_operation(SetOperator::Intersect, false).await?;
+    let expected = vec!["+---+", "| a |", "+---+", "| 0 |", "| 1 |", "+---+"];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    let result = execute_set_operation(SetOperator::Intersect, true).await?;
+    let expected = vec![
+        "+---+", "| a |", "+---+", "| 0 |", "| 0 |", "| 1 |", "+---+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_except() -> Result<()> {
+    let result = execute_set_operation(SetOperator::Except, false).await?;
+    let expected = vec!["+---+", "| a |", "+---+", "| 2 |", "+---+"];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    let result = execute_set_operation(SetOperator::Except, true).await?;
+    let expected = vec![
+        "+---+", "| a |", "+---+", "| 1 |", "| 2 |", "| 2 |", "+---+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
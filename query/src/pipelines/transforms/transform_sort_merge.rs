@@ -26,11 +26,20 @@ use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 use futures::StreamExt;
 
+use crate::common::SortedRun;
+use crate::common::SortedRunsMerger;
+use crate::common::SpillFile;
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
 use crate::pipelines::transforms::transform_sort_partial::get_sort_descriptions;
+use crate::sessions::QueryContext;
 
+/// Merges the sorted blocks into one sorted block.
+///
+/// If the buffered blocks exceed `max_bytes_before_external_sort`, they are merged into a
+/// sorted run and spilled to a temporary file, and the runs are k-way merged at the end.
 pub struct SortMergeTransform {
+    ctx: Arc<QueryContext>,
     schema: DataSchemaRef,
     exprs: Vec<Expression>,
     limit: Option<usize>,
@@ -39,11 +48,13 @@ pub struct SortMergeTransform {
 
 impl SortMergeTransform {
     pub fn try_create(
+        ctx: Arc<QueryContext>,
         schema: DataSchemaRef,
         exprs: Vec<Expression>,
         limit: Option<usize>,
     ) -> Result<Self> {
         Ok(SortMergeTransform {
+            ctx,
             schema,
             exprs,
             limit,
@@ -74,12 +85,46 @@ impl Processor for SortMergeTransform {
     async fn execute(&self) -> Result<SendableDataBlockStream> {
         tracing::debug!("execute...");
 
+        let settings = self.ctx.get_settings();
+        let max_block_size = settings.get_max_block_size()? as usize;
+        let max_bytes = settings.get_max_bytes_before_external_sort()? as usize;
+
+        // The buffered blocks are not pulled by the sources, so the memory limit is checked here.
+        let memory_tracker = self.ctx.get_memory_tracker()?;
+
         let sort_columns_descriptions = get_sort_descriptions(&self.schema, &self.exprs)?;
         let mut blocks = vec![];
+        let mut blocks_bytes = 0;
+        let mut spill_files = vec![];
         let mut stream = self.input.execute().await?;
 
         while let Some(block) = stream.next().await {
-            blocks.push(block?);
+            let block = block?;
+            blocks_bytes += block.memory_size();
+            blocks.push(block);
+            memory_tracker.check_limit()?;
+
+            if max_bytes == 0 || blocks_bytes <= max_bytes {
+                continue;
+            }
+
+            let merged =
+                DataBlock::merge_sort_blocks(&blocks, &sort_columns_descriptions, self.limit)?;
+            blocks.clear();
+            blocks_bytes = 0;
+
+            match self.limit {
+                // At most limit rows are kept, which are not worth spilling.
+                Some(_) => {
+                    blocks_bytes = merged.memory_size();
+                    blocks.push(merged);
+                }
+                None => {
+                    let run = DataBlock::split_block_by_size(&merged, max_block_size)?;
+                    let spill_file = SpillFile::create(&self.ctx, merged.schema(), run).await?;
+                    spill_files.push(spill_file);
+                }
+            }
         }
 
         let results = match blocks.len() {
@@ -90,6 +135,32 @@ impl Processor for SortMergeTransform {
                 self.limit,
             )?],
         };
+        memory_tracker.check_limit()?;
+
+        if !spill_files.is_empty() {
+            tracing::debug!("Merge {} spilled sorted runs", spill_files.len());
+
+            let mut runs = Vec::with_capacity(spill_files.len() + 1);
+            for spill_file in spill_files {
+                runs.push(spill_file.into_stream().await?);
+            }
+            runs.push(
+                Box::pin(DataBlockStream::create(self.schema.clone(), None, results)) as SortedRun,
+            );
+
+            let merger = SortedRunsMerger::create(
+                self.schema.clone(),
+                sort_columns_descriptions,
+                max_block_size,
+                self.limit,
+                runs,
+            );
+
+            return Ok(Box::pin(CorrectWithSchemaStream::new(
+                merger.into_stream(),
+                self.schema.clone(),
+            )));
+        }
 
         Ok(Box::pin(CorrectWithSchemaStream::new(
             Box::pin(DataBlockStream::create(self.schema.clone(), None, results)),
@@ -20,7 +20,9 @@ use common_planners::*;
 use common_planners::{self};
 use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
+use tempfile::TempDir;
 
+use crate::configs::Config;
 use crate::pipelines::processors::*;
 use crate::pipelines::transforms::*;
 
@@ -49,6 +51,7 @@ async fn test_transform_sort() -> Result<()> {
 
     pipeline.add_simple_transform(|| {
         Ok(Box::new(SortMergeTransform::try_create(
+            ctx.clone(),
             plan.schema(),
             sort_expression.to_vec(),
             None,
@@ -59,6 +62,7 @@ async fn test_transform_sort() -> Result<()> {
         pipeline.merge_processor()?;
         pipeline.add_simple_transform(|| {
             Ok(Box::new(SortMergeTransform::try_create(
+                ctx.clone(),
                 plan.schema(),
                 sort_expression.to_vec(),
                 None,
@@ -90,3 +94,51 @@ async fn test_transform_sort() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_external_sort() -> Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let spill_dir = tmp_dir.path().join("spill");
+    let mut config = Config::default();
+    config.storage.disk.temp_data_path = spill_dir.to_str().unwrap().to_string();
+    let ctx = crate::tests::try_create_context_with_config(config)?;
+    ctx.get_settings().set_max_threads(2)?;
+    ctx.get_settings().set_max_block_size(3)?;
+    // Every block is spilled.
+    ctx.get_settings().set_max_bytes_before_external_sort(1)?;
+
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+    let source = PlanNode::ReadSource(test_source.number_read_source_plan_for_test(10)?);
+    let plan = PlanBuilder::from(&source)
+        .sort(&[sort("number", false, false)])?
+        .build()?;
+
+    let pipeline_builder = PipelineBuilder::create(ctx.clone());
+    let mut pipeline = pipeline_builder.build(&plan)?;
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+
+    let expected = vec![
+        "+--------+",
+        "| number |",
+        "+--------+",
+        "| 9      |",
+        "| 8      |",
+        "| 7      |",
+        "| 6      |",
+        "| 5      |",
+        "| 4      |",
+        "| 3      |",
+        "| 2      |",
+        "| 1      |",
+        "| 0      |",
+        "+--------+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, result.as_slice());
+
+    // The spill files are removed after the merge.
+    assert!(spill_dir.exists());
+    assert_eq!(std::fs::read_dir(&spill_dir)?.count(), 0);
+
+    Ok(())
+}
@@ -0,0 +1,489 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::ops::Range;
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::aggregates::StateAddr;
+use common_planners::sort_to_inner_expr;
+use common_planners::Expression;
+use common_planners::WindowFrame;
+use common_planners::WindowFrameBound;
+use common_planners::WindowFrameUnits;
+use common_planners::WindowPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use futures::StreamExt;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::Processor;
+
+/// Evaluates the window functions of a window plan, all the rows are required to be in this
+/// transform, so it must be placed after a merge processor.
+pub struct WindowTransform {
+    schema: DataSchemaRef,
+    window_exprs: Vec<Expression>,
+    partition_by: Vec<Expression>,
+    order_by: Vec<Expression>,
+    input: Arc<dyn Processor>,
+}
+
+impl WindowTransform {
+    pub fn create(plan: &WindowPlan) -> Self {
+        WindowTransform {
+            schema: plan.schema(),
+            window_exprs: plan.window_exprs.clone(),
+            partition_by: plan.partition_by().to_vec(),
+            order_by: plan.order_by().to_vec(),
+            input: Arc::new(EmptyProcessor::create()),
+        }
+    }
+
+    // Sort by the PARTITION BY expressions and then the ORDER BY expressions,
+    // so that the rows of a partition are contiguous and in the order of the window.
+    fn sort_block(&self, block: &DataBlock) -> Result<DataBlock> {
+        let mut sort_columns_descriptions = vec![];
+        for expr in &self.partition_by {
+            if !matches!(expr, Expression::Literal { .. }) {
+                sort_columns_descriptions.push(SortColumnDescription {
+                    column_name: expr.column_name(),
+                    asc: true,
+                    nulls_first: true,
+                });
+            }
+        }
+
+        for order_by_expr in &self.order_by {
+            if let Expression::Sort {
+                expr,
+                asc,
+                nulls_first,
+                ..
+            } = order_by_expr
+            {
+                if !matches!(expr.as_ref(), Expression::Literal { .. }) {
+                    sort_columns_descriptions.push(SortColumnDescription {
+                        column_name: expr.column_name(),
+                        asc: *asc,
+                        nulls_first: *nulls_first,
+                    });
+                }
+            }
+        }
+
+        match sort_columns_descriptions.is_empty() {
+            true => Ok(block.clone()),
+            false => DataBlock::sort_block(block, &sort_columns_descriptions, None),
+        }
+    }
+
+    fn evaluate(&self, block: &DataBlock) -> Result<DataBlock> {
+        let window_block = WindowBlock::try_create(block, &self.partition_by, &self.order_by)?;
+
+        let mut columns = Vec::with_capacity(self.schema.fields().len());
+        for column in block.columns() {
+            columns.push(column.to_array()?);
+        }
+
+        for window_expr in &self.window_exprs {
+            let data_type = window_expr.to_data_type(&self.schema)?;
+            let values = window_block
+                .evaluate(window_expr, &self.schema)?
+                .into_iter()
+                .map(|value| match value.is_null() {
+                    true => DataValue::from(&data_type),
+                    false => value,
+                })
+                .collect::<Vec<_>>();
+
+            columns.push(DataValue::try_into_data_array(&values, &data_type)?);
+        }
+
+        Ok(DataBlock::create_by_array(self.schema.clone(), columns))
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for WindowTransform {
+    fn name(&self) -> &str {
+        "WindowTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        tracing::debug!("execute...");
+
+        let mut blocks = vec![];
+        let mut stream = self.input.execute().await?;
+        while let Some(block) = stream.next().await {
+            let block = block?;
+            if block.num_rows() > 0 {
+                blocks.push(block);
+            }
+        }
+
+        let results = match blocks.is_empty() {
+            true => vec![],
+            false => {
+                let block = self.sort_block(&DataBlock::concat_blocks(&blocks)?)?;
+                vec![self.evaluate(&block)?]
+            }
+        };
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.schema.clone(),
+            None,
+            results,
+        )))
+    }
+}
+
+/// A block sorted by the window, with the rows of each partition and the peers of each row,
+/// the peers are the rows of the same partition with the same ORDER BY values.
+struct WindowBlock<'a> {
+    block: &'a DataBlock,
+    order_by: &'a [Expression],
+    partitions: Vec<Range<usize>>,
+    peers: Vec<Range<usize>>,
+}
+
+impl<'a> WindowBlock<'a> {
+    fn try_create(
+        block: &'a DataBlock,
+        partition_by: &[Expression],
+        order_by: &'a [Expression],
+    ) -> Result<WindowBlock<'a>> {
+        let rows = block.num_rows();
+        let partition_values = Self::key_values(block, partition_by)?;
+        let order_by_exprs = order_by.iter().map(sort_to_inner_expr).collect::<Vec<_>>();
+        let order_values = Self::key_values(block, &order_by_exprs)?;
+
+        let mut partitions = vec![];
+        let mut peers = Vec::with_capacity(rows);
+        let mut partition_start = 0;
+        let mut peer_start = 0;
+        for row in 1..=rows {
+            let new_partition = row == rows || Self::is_new_key(&partition_values, row);
+            if new_partition || Self::is_new_key(&order_values, row) {
+                peers.extend(std::iter::repeat(peer_start..row).take(row - peer_start));
+                peer_start = row;
+            }
+
+            if new_partition {
+                partitions.push(partition_start..row);
+                partition_start = row;
+            }
+        }
+
+        Ok(WindowBlock {
+            block,
+            order_by,
+            partitions,
+            peers,
+        })
+    }
+
+    fn key_values(block: &DataBlock, exprs: &[Expression]) -> Result<Vec<Vec<DataValue>>> {
+        exprs
+            .iter()
+            .map(|expr| Self::column(block, expr)?.to_values())
+            .collect()
+    }
+
+    fn is_new_key(values: &[Vec<DataValue>], row: usize) -> bool {
+        values.iter().any(|values| values[row] != values[row - 1])
+    }
+
+    fn column(block: &DataBlock, expr: &Expression) -> Result<Series> {
+        match expr {
+            Expression::Literal { value, .. } => value.to_series_with_size(block.num_rows()),
+            _ => block.try_array_by_name(&expr.column_name()),
+        }
+    }
+
+    fn evaluate(&self, expr: &Expression, schema: &DataSchemaRef) -> Result<Vec<DataValue>> {
+        let (op, args, frame) = match expr {
+            Expression::WindowFunction {
+                op, args, frame, ..
+            } => (
+                op.to_lowercase(),
+                args,
+                frame.unwrap_or_else(WindowFrame::default_frame),
+            ),
+            _ => {
+                return Err(ErrorCode::LogicalError(format!(
+                    "Window transform expects window functions, but got {:?}",
+                    expr
+                )));
+            }
+        };
+
+        // RANGE frame with offset compares the values of the ORDER BY expression.
+        let range_keys = match frame.units == WindowFrameUnits::Range
+            && (frame.start_bound.has_offset() || frame.end_bound.has_offset())
+        {
+            true => self.range_keys()?,
+            false => vec![],
+        };
+
+        match op.as_str() {
+            "row_number" | "rank" | "dense_rank" => Ok(self.rank_values(&op)),
+            "lag" | "lead" => self.offset_values(&op, args),
+            "first_value" | "last_value" => {
+                let column = Self::column(self.block, &args[0])?;
+                let mut values = Vec::with_capacity(column.len());
+                for partition in &self.partitions {
+                    for row in partition.clone() {
+                        let rows = self.frame_rows(&frame, partition, row, &range_keys);
+                        values.push(match (rows.is_empty(), op.as_str()) {
+                            (true, _) => DataValue::Null,
+                            (false, "first_value") => column.try_get(rows.start)?,
+                            (false, _) => column.try_get(rows.end - 1)?,
+                        });
+                    }
+                }
+
+                Ok(values)
+            }
+            _ => self.aggregate_values(expr, args, &frame, &range_keys, schema),
+        }
+    }
+
+    fn rank_values(&self, op: &str) -> Vec<DataValue> {
+        let mut values = Vec::with_capacity(self.peers.len());
+        for partition in &self.partitions {
+            let mut dense_rank = 0;
+            for row in partition.clone() {
+                let peers = &self.peers[row];
+                if peers.start == row {
+                    dense_rank += 1;
+                }
+
+                let value = match op {
+                    "row_number" => row - partition.start + 1,
+                    "rank" => peers.start - partition.start + 1,
+                    _ => dense_rank,
+                };
+                values.push(DataValue::UInt64(Some(value as u64)));
+            }
+        }
+
+        values
+    }
+
+    // lag(expr, offset, default) and lead(expr, offset, default)
+    fn offset_values(&self, op: &str, args: &[Expression]) -> Result<Vec<DataValue>> {
+        let column = Self::column(self.block, &args[0])?;
+        let offset = match args.get(1) {
+            Some(Expression::Literal { value, .. }) => value.as_u64()? as usize,
+            _ => 1,
+        };
+
+        let default_value = match args.get(2) {
+            Some(Expression::Literal { value, .. }) => value
+                .to_array()?
+                .cast_with_type(column.data_type())?
+                .try_get(0)?,
+            _ => DataValue::Null,
+        };
+
+        let mut values = Vec::with_capacity(column.len());
+        for partition in &self.partitions {
+            for row in partition.clone() {
+                let offset_row = match op {
+                    "lag" => row.checked_sub(offset).filter(|r| *r >= partition.start),
+                    _ => row.checked_add(offset).filter(|r| *r < partition.end),
+                };
+
+                values.push(match offset_row {
+                    Some(offset_row) => column.try_get(offset_row)?,
+                    None => default_value.clone(),
+                });
+            }
+        }
+
+        Ok(values)
+    }
+
+    fn aggregate_values(
+        &self,
+        expr: &Expression,
+        args: &[Expression],
+        frame: &WindowFrame,
+        range_keys: &[Option<f64>],
+        schema: &DataSchemaRef,
+    ) -> Result<Vec<DataValue>> {
+        let func = expr.to_aggregate_function(schema)?;
+        let arg_columns = args
+            .iter()
+            .map(|arg| Self::column(self.block, arg))
+            .collect::<Result<Vec<_>>>()?;
+
+        let arena = Bump::new();
+        let place: StateAddr = arena.alloc_layout(func.state_layout()).into();
+
+        // The frames starting at UNBOUNDED PRECEDING only grow in a partition,
+        // so the rows are accumulated incrementally, the others are accumulated for each row.
+        let incremental = frame.start_bound == WindowFrameBound::Preceding(None);
+        let mut values = Vec::with_capacity(self.peers.len());
+        for partition in &self.partitions {
+            let mut accumulated_end = partition.start;
+            func.init_state(place);
+
+            for row in partition.clone() {
+                let rows = self.frame_rows(frame, partition, row, range_keys);
+                if !incremental {
+                    accumulated_end = rows.start;
+                    func.init_state(place);
+                }
+
+                if rows.end > accumulated_end {
+                    let num_rows = rows.end - accumulated_end;
+                    let arrays = arg_columns
+                        .iter()
+                        .map(|column| column.slice(accumulated_end, num_rows))
+                        .collect::<Vec<_>>();
+
+                    func.accumulate(place, &arrays, num_rows)?;
+                    accumulated_end = rows.end;
+                }
+
+                values.push(func.merge_result(place)?);
+            }
+        }
+
+        Ok(values)
+    }
+
+    fn frame_rows(
+        &self,
+        frame: &WindowFrame,
+        partition: &Range<usize>,
+        row: usize,
+        range_keys: &[Option<f64>],
+    ) -> Range<usize> {
+        let start = self.frame_bound(frame, &frame.start_bound, partition, row, range_keys, true);
+        let end = self.frame_bound(frame, &frame.end_bound, partition, row, range_keys, false);
+        start..std::cmp::max(start, end)
+    }
+
+    // The first row of the frame if `is_start`, otherwise the row after the last row of the frame.
+    fn frame_bound(
+        &self,
+        frame: &WindowFrame,
+        bound: &WindowFrameBound,
+        partition: &Range<usize>,
+        row: usize,
+        range_keys: &[Option<f64>],
+        is_start: bool,
+    ) -> usize {
+        let (offset, peers) = match (frame.units, bound) {
+            (_, WindowFrameBound::Preceding(None)) => return partition.start,
+            (_, WindowFrameBound::Following(None)) => return partition.end,
+            (WindowFrameUnits::Rows, WindowFrameBound::CurrentRow) => {
+                return if is_start { row } else { row + 1 };
+            }
+            (WindowFrameUnits::Rows, WindowFrameBound::Preceding(Some(n))) => {
+                let bound = row as i64 - *n as i64 + if is_start { 0 } else { 1 };
+                return bound.clamp(partition.start as i64, partition.end as i64) as usize;
+            }
+            (WindowFrameUnits::Rows, WindowFrameBound::Following(Some(n))) => {
+                let bound = row as u64 + *n + if is_start { 0 } else { 1 };
+                return std::cmp::min(bound, partition.end as u64) as usize;
+            }
+            (WindowFrameUnits::Range, WindowFrameBound::CurrentRow) => (0.0, &self.peers[row]),
+            (WindowFrameUnits::Range, WindowFrameBound::Preceding(Some(n))) => {
+                (-(*n as f64), &self.peers[row])
+            }
+            (WindowFrameUnits::Range, WindowFrameBound::Following(Some(n))) => {
+                (*n as f64, &self.peers[row])
+            }
+        };
+
+        // The NULLs are peers of each other, they are not in the frames of the other rows.
+        let key = match range_keys.get(row) {
+            Some(Some(key)) if bound.has_offset() => key + offset,
+            _ => return if is_start { peers.start } else { peers.end },
+        };
+
+        // The NULLs are either at the beginning or at the end of a partition.
+        let keys = &range_keys[partition.clone()];
+        let not_null = match keys.first() {
+            Some(None) => keys.partition_point(|k| k.is_none())..keys.len(),
+            _ => 0..keys.partition_point(|k| k.is_some()),
+        };
+
+        let keys = &keys[not_null.clone()];
+        partition.start
+            + not_null.start
+            + match is_start {
+                true => keys.partition_point(|k| matches!(k, Some(k) if *k < key)),
+                false => keys.partition_point(|k| matches!(k, Some(k) if *k <= key)),
+            }
+    }
+
+    // The values of the ORDER BY expression, which are negated if it's in descending order,
+    // so that the values are in ascending order in each partition.
+    fn range_keys(&self) -> Result<Vec<Option<f64>>> {
+        let (expr, asc) = match self.order_by {
+            [Expression::Sort { expr, asc, .. }] => (expr, *asc),
+            _ => {
+                return Err(ErrorCode::BadArguments(
+                    "RANGE window frame with offset requires exactly one ORDER BY expression",
+                ));
+            }
+        };
+
+        let column = Self::column(self.block, expr)?;
+        if !column.data_type().is_numeric() {
+            return Err(ErrorCode::BadArguments(format!(
+                "RANGE window frame with offset requires a numeric ORDER BY expression, got {:?}",
+                column.data_type()
+            )));
+        }
+
+        column
+            .cast_with_type(&DataType::Float64)?
+            .to_values()?
+            .into_iter()
+            .map(|value| match value {
+                DataValue::Float64(key) => Ok(key.map(|key| if asc { key } else { -key })),
+                other => Err(ErrorCode::LogicalError(format!(
+                    "Unexpected value {:?} of RANGE window frame",
+                    other
+                ))),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::pipelines::processors::*;
+use crate::pipelines::transforms::*;
+
+fn window_function(op: &str, args: Vec<Expression>, frame: Option<WindowFrame>) -> Expression {
+    Expression::WindowFunction {
+        op: op.to_string(),
+        params: vec![],
+        args,
+        partition_by: vec![modular(col("number"), lit(2u64))],
+        order_by: vec![Expression::Sort {
+            expr: Box::new(col("number")),
+            asc: false,
+            nulls_first: false,
+            origin_expr: Box::new(col("number")),
+        }],
+        frame,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_window() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    let mut pipeline = Pipeline::create(ctx.clone());
+    let source = test_source.number_source_transform_for_test(7)?;
+    pipeline.add_source(Arc::new(source))?;
+
+    let rows_frame = WindowFrame {
+        units: WindowFrameUnits::Rows,
+        start_bound: WindowFrameBound::Preceding(Some(1)),
+        end_bound: WindowFrameBound::CurrentRow,
+    };
+    let range_frame = WindowFrame {
+        units: WindowFrameUnits::Range,
+        start_bound: WindowFrameBound::CurrentRow,
+        end_bound: WindowFrameBound::Following(Some(2)),
+    };
+
+    let window_exprs = vec![
+        window_function("row_number", vec![], None),
+        window_function("rank", vec![], None),
+        window_function("lag", vec![col("number")], None),
+        window_function("sum", vec![col("number")], None),
+        window_function("sum", vec![col("number")], Some(rows_frame)),
+        window_function("count", vec![col("number")], Some(range_frame)),
+        window_function("last_value", vec![col("number")], Some(rows_frame)),
+    ];
+
+    let plan = PlanBuilder::create(test_source.number_schema_for_test()?)
+        .expression(&[modular(col("number"), lit(2u64))], "")?
+        .window(&window_exprs)?
+        .build()?;
+
+    if let PlanNode::Window(window_plan) = &plan {
+        if let PlanNode::Expression(expression_plan) = window_plan.input.as_ref() {
+            pipeline.add_simple_transform(|| {
+                Ok(Box::new(ExpressionTransform::try_create(
+                    expression_plan.input.schema(),
+                    expression_plan.schema.clone(),
+                    expression_plan.exprs.clone(),
+                )?))
+            })?;
+        }
+
+        pipeline.merge_processor()?;
+        pipeline.add_simple_transform(|| Ok(Box::new(WindowTransform::create(window_plan))))?;
+    }
+
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    assert_eq!(result.len(), 1);
+
+    let block = &result[0];
+    assert_eq!(block.num_columns(), 9);
+
+    let values = |values: Vec<u64>| values.into_iter().map(DataValue::from).collect::<Vec<_>>();
+
+    // Partition 0 is 6, 4, 2, 0 and partition 1 is 5, 3, 1.
+    let expected = vec![
+        values(vec![6, 4, 2, 0, 5, 3, 1]),
+        values(vec![1, 2, 3, 4, 1, 2, 3]),
+        values(vec![1, 2, 3, 4, 1, 2, 3]),
+        vec![None, Some(6u64), Some(4), Some(2), None, Some(5), Some(3)]
+            .into_iter()
+            .map(DataValue::from)
+            .collect(),
+        values(vec![6, 10, 12, 12, 5, 8, 9]),
+        values(vec![6, 10, 6, 2, 5, 8, 4]),
+        values(vec![2, 2, 2, 1, 2, 2, 1]),
+        values(vec![6, 4, 2, 0, 5, 3, 1]),
+    ];
+
+    // The first column is number, the second column is (number % 2).
+    let columns = block.columns().iter().enumerate().filter(|(i, _)| *i != 1);
+    for ((_, column), expected_values) in columns.zip(expected) {
+        assert_eq!(column.to_array()?.to_values()?, expected_values);
+    }
+
+    Ok(())
+}
@@ -105,12 +105,19 @@ impl ClickHouseSession for InteractiveWorker {
         futures::executor::block_on(async move {
             // TODO: use get_users and check client address
             let res = match user_manager.get_user(user, "%").await {
-                Ok(user_info) => user_manager.auth_user(user_info, info).await,
+                Ok(user_info) => {
+                    let quota = user_info.quota.clone();
+                    let authed = user_manager.auth_user(user_info, info).await;
+                    authed.map(|authed| (authed, quota))
+                }
                 Err(err) => Err(err),
             };
             match res {
-                Ok(res) => {
+                Ok((res, quota)) => {
                     self.session.set_current_user(user.to_string());
+                    if res {
+                        self.session.set_current_user_quota(quota);
+                    }
                     res
                 }
                 Err(failure) => {
@@ -22,12 +22,14 @@ use common_clickhouse_srv::connection::Connection;
 use common_clickhouse_srv::errors::Error as CHError;
 use common_clickhouse_srv::errors::Result as CHResult;
 use common_clickhouse_srv::errors::ServerError;
+use common_clickhouse_srv::types::column::datetime64::DateTime64;
 use common_clickhouse_srv::types::column::ArcColumnData;
 use common_clickhouse_srv::types::column::ArcColumnWrapper;
 use common_clickhouse_srv::types::column::ColumnFrom;
 use common_clickhouse_srv::types::column::{self};
 use common_clickhouse_srv::types::Block;
 use common_clickhouse_srv::types::DateTimeType;
+use common_clickhouse_srv::types::Decimal;
 use common_clickhouse_srv::types::SqlType;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
@@ -187,6 +189,13 @@ pub fn from_clickhouse_block(schema: DataSchemaRef, block: Block) -> Result<Data
             SqlType::UInt32 | SqlType::DateTime(DateTimeType::DateTime32) => {
                 Ok(DFUInt32Array::new_from_iter(col.iter::<u32>()?.copied()).into_series())
             }
+            SqlType::DateTime(DateTimeType::DateTime64(precision, _)) => {
+                Ok(DFInt64Array::new_from_iter(
+                    col.iter::<DateTime<Tz>>()?
+                        .map(|v| date_time_to_datetime64(&v, precision)),
+                )
+                .into_series())
+            }
             SqlType::UInt64 => {
                 Ok(DFUInt64Array::new_from_iter(col.iter::<u64>()?.copied()).into_series())
             }
@@ -208,6 +217,10 @@ pub fn from_clickhouse_block(schema: DataSchemaRef, block: Block) -> Result<Data
             SqlType::Float64 => {
                 Ok(DFFloat64Array::new_from_iter(col.iter::<f64>()?.copied()).into_series())
             }
+            SqlType::Decimal(_, _) => Ok(DFDecimal128Array::new_from_iter(
+                col.iter::<Decimal>()?.map(|v| v.internal::<i64>() as i128),
+            )
+            .into_series()),
             SqlType::String => Ok(DFStringArray::new_from_iter(col.iter::<&[u8]>()?).into_series()),
             SqlType::FixedString(_) => {
                 Ok(DFStringArray::new_from_iter(col.iter::<&[u8]>()?).into_series())
@@ -226,6 +239,13 @@ pub fn from_clickhouse_block(schema: DataSchemaRef, block: Block) -> Result<Data
                 DFUInt32Array::new_from_opt_iter(col.iter::<Option<u32>>()?.map(|c| c.copied()))
                     .into_series(),
             ),
+            SqlType::Nullable(SqlType::DateTime(DateTimeType::DateTime64(precision, _))) => {
+                Ok(DFInt64Array::new_from_opt_iter(
+                    col.iter::<Option<DateTime<Tz>>>()?
+                        .map(|c| c.map(|v| date_time_to_datetime64(&v, *precision))),
+                )
+                .into_series())
+            }
             SqlType::Nullable(SqlType::UInt64) => Ok(DFUInt64Array::new_from_opt_iter(
                 col.iter::<Option<u64>>()?.map(|c| c.copied()),
             )
@@ -254,6 +274,11 @@ pub fn from_clickhouse_block(schema: DataSchemaRef, block: Block) -> Result<Data
                 col.iter::<Option<f64>>()?.map(|c| c.copied()),
             )
             .into_series()),
+            SqlType::Nullable(SqlType::Decimal(_, _)) => Ok(DFDecimal128Array::new_from_opt_iter(
+                col.iter::<Option<Decimal>>()?
+                    .map(|c| c.map(|v| v.internal::<i64>() as i128)),
+            )
+            .into_series()),
             SqlType::Nullable(SqlType::String) => {
                 Ok(DFStringArray::new_from_opt_iter(col.iter::<Option<&[u8]>>()?).into_series())
             }
@@ -277,6 +302,10 @@ pub fn from_clickhouse_block(schema: DataSchemaRef, block: Block) -> Result<Data
     Ok(DataBlock::create_by_array(schema, arrays))
 }
 
+// The ClickHouse protocol implementation keeps decimals in 64 bits,
+// wider decimals are sent as strings.
+const MAX_CLICKHOUSE_DECIMAL: usize = 18;
+
 fn to_clickhouse_column(field: &DataField, column: &Series) -> Result<ArcColumnData> {
     let is_nullable = field.is_nullable();
     let utc: Tz = "UTC".parse().unwrap();
@@ -322,6 +351,16 @@ fn to_clickhouse_column(field: &DataField, column: &Series) -> Result<ArcColumnD
 
                 Vec::column_from::<ArcColumnWrapper>(c)
             }
+            DataType::DateTime64(precision, tz) => {
+                let tz = get_timezone(tz)?;
+                let c: Vec<Option<DateTime64>> = column
+                    .i64()?
+                    .into_iter()
+                    .map(|x| x.map(|v| DateTime64(*v, *precision, tz)))
+                    .collect();
+
+                Vec::column_from::<ArcColumnWrapper>(c)
+            }
             DataType::UInt64 => {
                 Vec::column_from::<ArcColumnWrapper>(column.u64()?.collect_values())
             }
@@ -331,9 +370,35 @@ fn to_clickhouse_column(field: &DataField, column: &Series) -> Result<ArcColumnD
             DataType::Float64 => {
                 Vec::column_from::<ArcColumnWrapper>(column.f64()?.collect_values())
             }
-            DataType::String => {
+            DataType::Decimal128(precision, scale) if *precision <= MAX_CLICKHOUSE_DECIMAL => {
+                let c: Vec<Option<Decimal>> = column
+                    .decimal128()?
+                    .into_iter()
+                    .map(|x| x.map(|v| Decimal::new(*v as i64, *scale as u8)))
+                    .collect();
+                Vec::column_from::<ArcColumnWrapper>(c)
+            }
+            DataType::Decimal128(_, scale) => {
+                let c: Vec<Option<String>> = column
+                    .decimal128()?
+                    .into_iter()
+                    .map(|x| x.map(|v| decimal_to_string(*v, *scale)))
+                    .collect();
+                Vec::column_from::<ArcColumnWrapper>(c)
+            }
+            DataType::String | DataType::Variant => {
                 Vec::column_from::<ArcColumnWrapper>(column.string()?.collect_values())
             }
+            DataType::List(_) | DataType::Map(_, _) | DataType::Decimal256(_, _) => {
+                let serializer = field.data_type().create_serializer();
+                let c: Vec<Option<String>> = serializer
+                    .serialize_column(&DataColumn::Array(column.clone()))?
+                    .into_iter()
+                    .enumerate()
+                    .map(|(row, v)| if column.is_null(row) { None } else { Some(v) })
+                    .collect();
+                Vec::column_from::<ArcColumnWrapper>(c)
+            }
             DataType::Boolean => {
                 let v: Vec<Option<u8>> = column
                     .bool()?
@@ -413,6 +478,16 @@ fn to_clickhouse_column(field: &DataField, column: &Series) -> Result<ArcColumnD
 
                 Vec::column_from::<ArcColumnWrapper>(c)
             }
+            DataType::DateTime64(precision, tz) => {
+                let tz = get_timezone(tz)?;
+                let c: Vec<DateTime64> = column
+                    .i64()?
+                    .into_no_null_iter()
+                    .map(|v| DateTime64(*v, *precision, tz))
+                    .collect();
+
+                Vec::column_from::<ArcColumnWrapper>(c)
+            }
 
             DataType::UInt64 => Vec::column_from::<ArcColumnWrapper>(
                 column.u64()?.inner().values().as_slice().to_vec(),
@@ -423,10 +498,31 @@ fn to_clickhouse_column(field: &DataField, column: &Series) -> Result<ArcColumnD
             DataType::Float64 => Vec::column_from::<ArcColumnWrapper>(
                 column.f64()?.inner().values().as_slice().to_vec(),
             ),
-            DataType::String => {
+            DataType::Decimal128(precision, scale) if *precision <= MAX_CLICKHOUSE_DECIMAL => {
+                let c: Vec<Decimal> = column
+                    .decimal128()?
+                    .into_no_null_iter()
+                    .map(|v| Decimal::new(*v as i64, *scale as u8))
+                    .collect();
+                Vec::column_from::<ArcColumnWrapper>(c)
+            }
+            DataType::Decimal128(_, scale) => {
+                let c: Vec<String> = column
+                    .decimal128()?
+                    .into_no_null_iter()
+                    .map(|v| decimal_to_string(*v, *scale))
+                    .collect();
+                Vec::column_from::<ArcColumnWrapper>(c)
+            }
+            DataType::String | DataType::Variant => {
                 let vs: Vec<&[u8]> = column.string()?.into_no_null_iter().collect();
                 Vec::column_from::<ArcColumnWrapper>(vs)
             }
+            DataType::List(_) | DataType::Map(_, _) | DataType::Decimal256(_, _) => {
+                let serializer = field.data_type().create_serializer();
+                let vs = serializer.serialize_column(&DataColumn::Array(column.clone()))?;
+                Vec::column_from::<ArcColumnWrapper>(vs)
+            }
             DataType::Boolean => {
                 let vs: Vec<u8> = column
                     .bool()?
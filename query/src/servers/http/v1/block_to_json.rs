@@ -18,6 +18,11 @@ use common_datablocks::DataBlock;
 use common_datavalues::arrays::DFPrimitiveArray;
 use common_datavalues::chrono::TimeZone;
 use common_datavalues::chrono::Utc;
+use common_datavalues::datetime64_to_string;
+use common_datavalues::decimal256_from_bytes;
+use common_datavalues::decimal256_to_string;
+use common_datavalues::decimal_to_string;
+use common_datavalues::get_timezone;
 use common_datavalues::DFPrimitiveType;
 use common_datavalues::DataType;
 use common_exception::ErrorCode;
@@ -117,7 +122,24 @@ pub(crate) fn block_to_json(block: &DataBlock) -> Result<Vec<Vec<JsonValue>>> {
                 DataType::UInt64 => primitive_array_to_json(series.u64()?),
                 DataType::Float32 => primitive_array_to_json(series.f32()?),
                 DataType::Float64 => primitive_array_to_json(series.f64()?),
-                DataType::String => series
+                DataType::Decimal128(_, scale) => series
+                    .decimal128()?
+                    .into_iter()
+                    .map(|o| o.map(|v| decimal_to_string(*v, *scale)))
+                    .map(to_json_value)
+                    .collect(),
+                DataType::Decimal256(_, scale) => series
+                    .string()?
+                    .into_iter()
+                    .map(|o| {
+                        o.map(|v| Ok(decimal256_to_string(decimal256_from_bytes(v)?, *scale)))
+                            .transpose()
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .map(to_json_value)
+                    .collect(),
+                DataType::String | DataType::Variant => series
                     .string()?
                     .collect_values()
                     .iter()
@@ -129,6 +151,15 @@ pub(crate) fn block_to_json(block: &DataBlock) -> Result<Vec<Vec<JsonValue>>> {
                 DataType::Date32 => date_array_to_string_array(series.i32()?, DATE_FMT),
                 // TODO(youngsofun): add time zone?
                 DataType::DateTime32(_) => date_array_to_string_array(series.i32()?, TIME_FMT),
+                DataType::DateTime64(precision, tz) => {
+                    let tz = get_timezone(tz)?;
+                    series
+                        .i64()?
+                        .into_iter()
+                        .map(|o| o.map(|v| datetime64_to_string(*v, *precision, &tz)))
+                        .map(to_json_value)
+                        .collect()
+                }
                 // TODO(youngsofun): support other DataType
                 _ => return Err(bad_type(data_type)),
             },
@@ -143,12 +174,26 @@ pub(crate) fn block_to_json(block: &DataBlock) -> Result<Vec<Vec<JsonValue>>> {
                 DataType::UInt64 => primitive_array_to_json_not_null(series.u64()?),
                 DataType::Float32 => primitive_array_to_json_not_null(series.f32()?),
                 DataType::Float64 => primitive_array_to_json_not_null(series.f64()?),
+                DataType::Decimal128(_, scale) => series
+                    .decimal128()?
+                    .into_no_null_iter()
+                    .map(|v| decimal_to_string(*v, *scale))
+                    .map(to_json_value)
+                    .collect(),
+                DataType::Decimal256(_, scale) => series
+                    .string()?
+                    .into_no_null_iter()
+                    .map(|v| Ok(decimal256_to_string(decimal256_from_bytes(v)?, *scale)))
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .map(to_json_value)
+                    .collect(),
                 DataType::Boolean => series
                     .bool()?
                     .into_no_null_iter()
                     .map(to_json_value)
                     .collect(),
-                DataType::String => series
+                DataType::String | DataType::Variant => series
                     .string()?
                     .into_no_null_iter()
                     .map(|v| String::from_utf8(v.to_vec()).unwrap())
@@ -159,6 +204,15 @@ pub(crate) fn block_to_json(block: &DataBlock) -> Result<Vec<Vec<JsonValue>>> {
                 DataType::DateTime32(_) => {
                     date_array_to_string_array_not_null(series.i32()?, TIME_FMT)
                 }
+                DataType::DateTime64(precision, tz) => {
+                    let tz = get_timezone(tz)?;
+                    series
+                        .i64()?
+                        .into_no_null_iter()
+                        .map(|v| datetime64_to_string(*v, *precision, &tz))
+                        .map(to_json_value)
+                        .collect()
+                }
                 _ => return Err(bad_type(data_type)),
             },
         };
@@ -31,6 +31,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::interpreters::InterpreterFactory;
+use crate::servers::http::v1::attach_default_user;
 use crate::sessions::SessionManager;
 use crate::sql::PlanParser;
 
@@ -50,6 +51,7 @@ pub async fn streaming_load(
 ) -> PoemResult<Json<LoadResponse>> {
     let session_manager = sessions_extension.0;
     let session = session_manager.create_session("Streaming load")?;
+    attach_default_user(&session).await?;
     let context = session.create_context().await?;
     let insert_sql = req
         .headers()
@@ -25,6 +25,18 @@ pub mod statement;
 #[cfg(test)]
 mod statement_test;
 
+use common_exception::Result;
 pub(super) use http_query_handlers::query_route;
 pub(super) use load::streaming_load;
 pub(super) use statement::statement_router;
+
+use crate::sessions::SessionRef;
+
+/// The HTTP handlers do not authenticate yet, so the queries run as the default user, under
+/// its quota.
+pub(crate) async fn attach_default_user(session: &SessionRef) -> Result<()> {
+    let user_info = session.get_user_manager().get_user("root", "%").await?;
+    session.set_current_user(user_info.name.clone());
+    session.set_current_user_quota(user_info.quota);
+    Ok(())
+}
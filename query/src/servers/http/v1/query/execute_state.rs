@@ -30,6 +30,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::interpreters::InterpreterFactory;
+use crate::servers::http::v1::attach_default_user;
 use crate::sessions::QueryContext;
 use crate::sessions::SessionManager;
 use crate::sessions::SessionRef;
@@ -144,6 +145,7 @@ impl ExecuteState {
     ) -> Result<(ExecutorRef, DataSchemaRef)> {
         let sql = &request.sql;
         let session = session_manager.create_session("http-statement")?;
+        attach_default_user(&session).await?;
         let context = session.create_context().await?;
         if let Some(db) = &request.session.database {
             context.set_current_database(db.clone()).await?;
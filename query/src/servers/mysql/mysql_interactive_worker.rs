@@ -215,6 +215,7 @@ impl<W: std::io::Write> InteractiveWorkerBase<W> {
         let input = &info.user_password;
         let saved = &user_info.password;
         let encode_password = Self::encoding_password(auth_plugin, salt, input, saved)?;
+        let user_quota = user_info.quota.clone();
 
         let authed = user_manager
             .auth_user(
@@ -224,6 +225,7 @@ impl<W: std::io::Write> InteractiveWorkerBase<W> {
             .await?;
         if authed {
             self.session.set_current_user(user_name.clone());
+            self.session.set_current_user_quota(user_quota);
         }
 
         Ok(authed)
@@ -72,13 +72,20 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
                 DataType::UInt64 => Ok(ColumnType::MYSQL_TYPE_LONG),
                 DataType::Float32 => Ok(ColumnType::MYSQL_TYPE_FLOAT),
                 DataType::Float64 => Ok(ColumnType::MYSQL_TYPE_FLOAT),
-                DataType::String => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
+                DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => {
+                    Ok(ColumnType::MYSQL_TYPE_NEWDECIMAL)
+                }
+                DataType::String | DataType::Variant => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
                 DataType::Boolean => Ok(ColumnType::MYSQL_TYPE_SHORT),
                 DataType::Date16 | DataType::Date32 => Ok(ColumnType::MYSQL_TYPE_DATE),
-                DataType::DateTime32(_) => Ok(ColumnType::MYSQL_TYPE_DATETIME),
+                DataType::DateTime32(_) | DataType::DateTime64(_, _) => {
+                    Ok(ColumnType::MYSQL_TYPE_DATETIME)
+                }
                 DataType::Null => Ok(ColumnType::MYSQL_TYPE_NULL),
                 DataType::Interval(_) => Ok(ColumnType::MYSQL_TYPE_LONG),
-                DataType::Struct(_) => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
+                DataType::Struct(_) | DataType::List(_) | DataType::Map(_, _) => {
+                    Ok(ColumnType::MYSQL_TYPE_VARCHAR)
+                }
                 _ => Err(ErrorCode::UnImplement(format!(
                     "Unsupported column type:{:?}",
                     field.data_type()
@@ -151,6 +158,11 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
                                 (DataType::Float64, DataValue::Float64(Some(v))) => {
                                     row_writer.write_col(v)?
                                 }
+                                (DataType::Decimal128(_, _), DataValue::Decimal128(Some(_)))
+                                | (DataType::Decimal256(_, _), DataValue::String(Some(_))) => {
+                                    let serializer = data_type.create_serializer();
+                                    row_writer.write_col(serializer.serialize_value(&val)?)?
+                                }
                                 (DataType::Date16, DataValue::UInt16(Some(v))) => {
                                     row_writer.write_col(v.to_date(&utc).naive_local())?
                                 }
@@ -163,10 +175,19 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
                                     let tz: Tz = tz.parse().unwrap();
                                     row_writer.write_col(v.to_date_time(&tz).naive_local())?
                                 }
-                                (DataType::String, DataValue::String(Some(v))) => {
-                                    row_writer.write_col(v)?
+                                (DataType::DateTime64(_, _), DataValue::Int64(Some(_))) => {
+                                    let serializer = data_type.create_serializer();
+                                    row_writer.write_col(serializer.serialize_value(&val)?)?
                                 }
-                                (DataType::Struct(_), DataValue::Struct(_)) => {
+                                (
+                                    DataType::String | DataType::Variant,
+                                    DataValue::String(Some(v)),
+                                ) => row_writer.write_col(v)?,
+                                (DataType::Struct(_), DataValue::Struct(_))
+                                | (
+                                    DataType::List(_) | DataType::Map(_, _),
+                                    DataValue::List(_, _),
+                                ) => {
                                     let serializer = data_type.create_serializer();
                                     row_writer.write_col(serializer.serialize_value(&val)?)?
                                 }
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::hash_map::Entry;
 use std::collections::VecDeque;
 use std::future::Future;
 use std::str::FromStr;
@@ -20,6 +21,7 @@ use std::sync::atomic::Ordering::Acquire;
 use std::sync::Arc;
 
 use common_base::tokio::task::JoinHandle;
+use common_base::MemoryTracker;
 use common_base::ProgressCallback;
 use common_base::ProgressValues;
 use common_base::Runtime;
@@ -41,6 +43,7 @@ use common_planners::ReadDataSourcePlan;
 use common_planners::Statistics;
 use common_streams::AbortStream;
 use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
 
 use crate::catalogs::impls::DatabaseCatalog;
 use crate::catalogs::Catalog;
@@ -48,7 +51,9 @@ use crate::catalogs::Table;
 use crate::clusters::Cluster;
 use crate::configs::AzureStorageBlobConfig;
 use crate::configs::Config;
+use crate::datasources::table::fuse::CacheManager;
 use crate::servers::http::v1::query::HttpQueryHandle;
+use crate::sessions::MaterializedCte;
 use crate::sessions::QueryContextShared;
 use crate::sessions::SessionManager;
 use crate::sessions::Settings;
@@ -195,11 +200,20 @@ impl QueryContext {
     }
 
     pub fn try_create_abortable(&self, input: SendableDataBlockStream) -> Result<AbortStream> {
-        let (abort_handle, abort_stream) = AbortStream::try_create(input)?;
+        // The allocator can only mark the overrun, so the query is cancelled by its sources, and
+        // by the processors buffering their inputs (sort, group by final and join build).
+        let memory_tracker = self.get_memory_tracker()?;
+        let input = input.map(move |block| memory_tracker.check_limit().and(block));
+        let (abort_handle, abort_stream) = AbortStream::try_create(Box::pin(input))?;
         self.shared.add_source_abort_handle(abort_handle);
         Ok(abort_stream)
     }
 
+    pub fn get_memory_tracker(&self) -> Result<Arc<MemoryTracker>> {
+        let runtime = self.shared.try_get_runtime()?;
+        Ok(runtime.get_tracker().get_memory_tracker())
+    }
+
     pub fn get_current_database(&self) -> String {
         self.shared.get_current_database()
     }
@@ -240,10 +254,32 @@ impl QueryContext {
         format!("_subquery_{}", index)
     }
 
+    pub fn get_materialized_cte_name(&self, cte: &str) -> String {
+        let index = self.shared.subquery_index.fetch_add(1, Ordering::Relaxed);
+        format!("_cte_{}_{}", cte, index)
+    }
+
+    /// Get the puller of the materialized CTE, `materialize` is called by the first reference.
+    pub fn get_or_materialize_cte(
+        &self,
+        name: &str,
+        materialize: impl FnOnce() -> MaterializedCte,
+    ) -> MaterializedCte {
+        let mut materialized_ctes = self.shared.materialized_ctes.lock();
+        match materialized_ctes.entry(name.to_string()) {
+            Entry::Occupied(v) => v.get().clone(),
+            Entry::Vacant(v) => v.insert(materialize()).clone(),
+        }
+    }
+
     pub fn get_sessions_manager(self: &Arc<Self>) -> Arc<SessionManager> {
         self.shared.session.get_sessions_manager()
     }
 
+    pub fn get_storage_cache_manager(&self) -> Arc<CacheManager> {
+        self.shared.session.get_storage_cache_manager()
+    }
+
     pub fn get_shared_runtime(&self) -> Result<Arc<Runtime>> {
         self.shared.try_get_runtime()
     }
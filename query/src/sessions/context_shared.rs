@@ -33,12 +33,16 @@ use crate::catalogs::Catalog;
 use crate::catalogs::Table;
 use crate::clusters::Cluster;
 use crate::configs::Config;
+use crate::pipelines::transforms::SubQueriesPuller;
 use crate::servers::http::v1::query::HttpQueryHandle;
 use crate::sessions::Session;
 use crate::sessions::Settings;
 
 type DatabaseAndTable = (String, String);
 
+/// The puller of a materialized CTE, shared by the references to it.
+pub type MaterializedCte = Arc<Mutex<SubQueriesPuller<'static>>>;
+
 /// Data that needs to be shared in a query context.
 /// This is very useful, for example, for queries:
 ///     USE database_1;
@@ -63,6 +67,7 @@ pub struct QueryContextShared {
     pub(in crate::sessions) running_plan: Arc<RwLock<Option<PlanNode>>>,
     pub(in crate::sessions) tables_refs: Arc<Mutex<HashMap<DatabaseAndTable, Arc<dyn Table>>>>,
     pub(in crate::sessions) dal_ctx: Arc<DalContext>,
+    pub(in crate::sessions) materialized_ctes: Arc<Mutex<HashMap<String, MaterializedCte>>>,
 }
 
 impl QueryContextShared {
@@ -86,6 +91,7 @@ impl QueryContextShared {
             running_plan: Arc::new(RwLock::new(None)),
             tables_refs: Arc::new(Mutex::new(HashMap::new())),
             dal_ctx: Arc::new(Default::default()),
+            materialized_ctes: Arc::new(Mutex::new(HashMap::new())),
         })
     }
 
@@ -172,12 +178,25 @@ impl QueryContextShared {
                 let settings = self.get_settings();
                 let max_threads = settings.get_max_threads()? as usize;
                 let runtime = Arc::new(Runtime::with_worker_threads(max_threads)?);
+                let memory_tracker = runtime.get_tracker().get_memory_tracker();
+                memory_tracker.set_limit(self.get_max_memory_usage()?);
                 *query_runtime = Some(runtime.clone());
                 Ok(runtime)
             }
         }
     }
 
+    // The lower of the max_memory_usage setting and the user memory quota, 0 is no limited.
+    fn get_max_memory_usage(&self) -> Result<usize> {
+        let max_memory_usage = self.get_settings().get_max_memory_usage()?;
+        let quota = self.session.get_current_user_quota().max_memory_in_bytes;
+
+        Ok(match (max_memory_usage, quota) {
+            (0, limit) | (limit, 0) => limit as usize,
+            (max_memory_usage, quota) => max_memory_usage.min(quota) as usize,
+        })
+    }
+
     pub fn attach_http_query(&self, handle: HttpQueryHandle) {
         let mut http_query = self.http_query.write();
         *http_query = Some(handle);
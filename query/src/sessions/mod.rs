@@ -32,6 +32,7 @@ mod sessions_info;
 mod settings;
 
 pub use context::QueryContext;
+pub use context_shared::MaterializedCte;
 pub use context_shared::QueryContextShared;
 pub use session::Session;
 pub use session_info::ProcessInfo;
@@ -20,10 +20,12 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_macros::MallocSizeOf;
 use common_mem_allocator::malloc_size;
+use common_meta_types::UserQuota;
 use futures::channel::*;
 
 use crate::catalogs::impls::DatabaseCatalog;
 use crate::configs::Config;
+use crate::datasources::table::fuse::CacheManager;
 use crate::sessions::context_shared::QueryContextShared;
 use crate::sessions::MutableStatus;
 use crate::sessions::QueryContext;
@@ -159,6 +161,16 @@ impl Session {
         self.mutable_state.set_current_user(user)
     }
 
+    pub fn get_current_user_quota(self: &Arc<Self>) -> UserQuota {
+        self.mutable_state
+            .get_current_user_quota()
+            .unwrap_or_else(UserQuota::no_limit)
+    }
+
+    pub fn set_current_user_quota(self: &Arc<Self>, quota: UserQuota) {
+        self.mutable_state.set_current_user_quota(quota)
+    }
+
     pub fn get_settings(self: &Arc<Self>) -> Arc<Settings> {
         self.mutable_state.get_settings()
     }
@@ -175,6 +187,10 @@ impl Session {
         self.sessions.get_user_manager()
     }
 
+    pub fn get_storage_cache_manager(self: &Arc<Self>) -> Arc<CacheManager> {
+        self.sessions.get_storage_cache_manager()
+    }
+
     pub fn get_memory_usage(self: &Arc<Self>) -> usize {
         malloc_size(self)
     }
@@ -30,6 +30,7 @@ pub struct ProcessInfo {
     pub client_address: Option<SocketAddr>,
     pub session_extra_info: Option<String>,
     pub memory_usage: u64,
+    pub peak_memory_usage: u64,
 }
 
 impl Session {
@@ -40,12 +41,14 @@ impl Session {
 
     fn to_process_info(self: &Arc<Self>, status: &MutableStatus) -> ProcessInfo {
         let mut memory_usage = 0;
+        let mut peak_memory_usage = 0;
 
         if let Some(shared) = &status.get_context_shared() {
             if let Ok(runtime) = shared.try_get_runtime() {
                 let runtime_tracker = runtime.get_tracker();
                 let runtime_memory_tracker = runtime_tracker.get_memory_tracker();
                 memory_usage = runtime_memory_tracker.get_memory_usage() as u64;
+                peak_memory_usage = runtime_memory_tracker.get_peak_memory_usage() as u64;
             }
         }
 
@@ -59,6 +62,7 @@ impl Session {
             client_address: status.get_client_host(),
             session_extra_info: self.process_extra_info(status),
             memory_usage,
+            peak_memory_usage,
         }
     }
 
@@ -20,6 +20,7 @@ use std::sync::Arc;
 use common_exception::Result;
 use common_infallible::RwLock;
 use common_macros::MallocSizeOf;
+use common_meta_types::UserQuota;
 use futures::channel::oneshot::Sender;
 
 use crate::sessions::context_shared::QueryContextShared;
@@ -32,6 +33,8 @@ pub struct MutableStatus {
     session_settings: RwLock<Settings>,
     current_user: RwLock<Option<String>>,
     #[ignore_malloc_size_of = "insignificant"]
+    current_user_quota: RwLock<Option<UserQuota>>,
+    #[ignore_malloc_size_of = "insignificant"]
     client_host: RwLock<Option<SocketAddr>>,
     #[ignore_malloc_size_of = "insignificant"]
     io_shutdown_tx: RwLock<Option<Sender<Sender<()>>>>,
@@ -44,6 +47,7 @@ impl MutableStatus {
         Ok(MutableStatus {
             abort: Default::default(),
             current_user: Default::default(),
+            current_user_quota: Default::default(),
             client_host: Default::default(),
             current_database: RwLock::new("default".to_string()),
             session_settings: RwLock::new(Settings::try_create()?.as_ref().clone()),
@@ -86,6 +90,18 @@ impl MutableStatus {
         lock.clone()
     }
 
+    // Set the quota of the current user after authentication
+    pub fn set_current_user_quota(&self, quota: UserQuota) {
+        let mut lock = self.current_user_quota.write();
+        *lock = Some(quota);
+    }
+
+    // Get the quota of current user
+    pub fn get_current_user_quota(&self) -> Option<UserQuota> {
+        let lock = self.current_user_quota.read();
+        lock.clone()
+    }
+
     pub fn get_settings(&self) -> Arc<Settings> {
         let lock = self.session_settings.read();
         Arc::new(lock.clone())
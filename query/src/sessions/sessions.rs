@@ -31,6 +31,7 @@ use futures::StreamExt;
 use crate::catalogs::impls::DatabaseCatalog;
 use crate::clusters::ClusterDiscovery;
 use crate::configs::Config;
+use crate::datasources::table::fuse::CacheManager;
 use crate::servers::http::v1::query::HttpQueryManager;
 use crate::servers::http::v1::query::HttpQueryManagerRef;
 use crate::sessions::session::Session;
@@ -43,6 +44,7 @@ pub struct SessionManager {
     pub(in crate::sessions) catalog: Arc<DatabaseCatalog>,
     pub(in crate::sessions) user: Arc<UserApiProvider>,
     pub(in crate::sessions) http_query_manager: HttpQueryManagerRef,
+    pub(in crate::sessions) storage_cache_manager: Arc<CacheManager>,
 
     pub(in crate::sessions) max_sessions: usize,
     pub(in crate::sessions) active_sessions: Arc<RwLock<HashMap<String, Arc<Session>>>>,
@@ -60,6 +62,9 @@ impl SessionManager {
 
         let http_query_manager = HttpQueryManager::create_global(conf.clone()).await?;
 
+        // Caches of the table meta, shared by all the sessions.
+        let storage_cache_manager = CacheManager::init(&conf.query)?;
+
         let max_active_sessions = conf.query.max_active_sessions as usize;
         Ok(Arc::new(SessionManager {
             catalog,
@@ -67,6 +72,7 @@ impl SessionManager {
             discovery,
             user,
             http_query_manager,
+            storage_cache_manager,
             max_sessions: max_active_sessions,
             active_sessions: Arc::new(RwLock::new(HashMap::with_capacity(max_active_sessions))),
         }))
@@ -93,6 +99,10 @@ impl SessionManager {
         self.catalog.clone()
     }
 
+    pub fn get_storage_cache_manager(self: &Arc<Self>) -> Arc<CacheManager> {
+        self.storage_cache_manager.clone()
+    }
+
     pub fn create_session(self: &Arc<Self>, typ: impl Into<String>) -> Result<SessionRef> {
         let mut sessions = self.active_sessions.write();
         match sessions.len() == self.max_sessions {
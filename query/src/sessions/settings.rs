@@ -31,7 +31,17 @@ impl Settings {
         ("max_threads", u64, 16, "The maximum number of threads to execute the request. By default, it is determined automatically."),
         ("flight_client_timeout", u64, 60, "Max duration the flight client request is allowed to take in seconds. By default, it is 60 seconds"),
         ("min_distributed_rows", u64, 100000000, "Minimum distributed read rows. In cluster mode, when read rows exceeds this value, the local table converted to distributed query."),
-        ("min_distributed_bytes", u64, 500 * 1024 * 1024, "Minimum distributed read bytes. In cluster mode, when read bytes exceeds this value, the local table converted to distributed query.")
+        ("min_distributed_bytes", u64, 500 * 1024 * 1024, "Minimum distributed read bytes. In cluster mode, when read bytes exceeds this value, the local table converted to distributed query."),
+        ("retention_period", u64, 12, "The retention period (in hours) of table history, which is kept by VACUUM TABLE if RETAIN is not specified."),
+        ("max_broadcast_join_bytes", u64, 100 * 1024 * 1024, "Maximum estimated bytes of the join build side to be broadcast. In cluster mode, larger build sides are shuffled by the join keys."),
+        ("materialize_cte", u64, 0, "Materialize the common table expressions referenced more than once, so that each of them is executed only once. By default, they are inlined into every reference."),
+        ("max_bytes_before_external_sort", u64, 0, "Maximum bytes of the blocks buffered by each sort merging processor. When exceeded, the sorted blocks are spilled to the temp_data_path of the disk storage, or the temporary directory of the system if it is not configured. By default, it is 0 and sorting never spills."),
+        ("max_bytes_before_external_group_by", u64, 0, "Maximum bytes of the aggregate states of each group by processor. When exceeded, the states are spilled to the temp_data_path of the disk storage (or the temporary directory of the system if it is not configured) radix partitioned by the group keys, and the partitions are merged in parallel. By default, it is 0 and group by never spills."),
+        ("max_memory_usage", u64, 0, "Maximum memory usage (in bytes) of a query. When exceeded, the query is cancelled. The memory quota of the user also applies if it is lower. By default, it is 0 and the memory is unlimited."),
+        ("compact_block_row_count_threshold", u64, 100 * 1000, "Blocks of fuse tables which have fewer rows than this are considered small, and are merged by OPTIMIZE TABLE."),
+        ("compact_block_size_threshold", u64, 100 * 1024 * 1024, "Blocks of fuse tables which are larger than this (in bytes) are not merged by OPTIMIZE TABLE."),
+        ("compact_segment_block_count_threshold", u64, 1000, "Maximum number of blocks that a segment compacted by OPTIMIZE TABLE contains."),
+        ("cluster_key_sort_batch_bytes", u64, 256 * 1024 * 1024, "Maximum bytes of the rows which are sorted by the cluster key as a whole, when written to fuse tables with cluster key. Larger writes are sorted in batches, and the overlapping blocks of different batches are reclustered by OPTIMIZE TABLE.")
     }
 
     pub fn try_create() -> Result<Arc<Settings>> {
@@ -25,8 +25,10 @@ use common_planners::SelectPlan;
 use crate::sessions::QueryContext;
 use crate::sql::statements::AnalyzableStatement;
 use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::JoinRelation;
 use crate::sql::statements::QueryAnalyzeState;
 use crate::sql::statements::QueryRelation;
+use crate::sql::statements::SetOperationRelation;
 use crate::sql::DfHint;
 use crate::sql::DfParser;
 use crate::sql::DfStatement;
@@ -73,10 +75,21 @@ impl PlanParser {
     pub fn build_query_plan(data: &QueryAnalyzeState) -> Result<PlanNode> {
         let from = Self::build_from_plan(data)?;
         let filter = Self::build_filter_plan(from, data)?;
-        let group_by = Self::build_group_by_plan(filter, data)?;
-        let before_order = Self::build_before_order(group_by, data)?;
-        let having = Self::build_having_plan(before_order, data)?;
-        let order_by = Self::build_order_by_plan(having, data)?;
+        let array_join = Self::build_array_join_plan(filter, data)?;
+        let group_by = Self::build_group_by_plan(array_join, data)?;
+        let before_order = match data.window_expressions.is_empty() {
+            true => {
+                let before_order = Self::build_before_order(group_by, data)?;
+                Self::build_having_plan(before_order, data)?
+            }
+            false => {
+                let before_window = Self::build_before_window(group_by, data)?;
+                let having = Self::build_having_plan(before_window, data)?;
+                let window = Self::build_window_plan(having, data)?;
+                Self::build_before_order(window, data)?
+            }
+        };
+        let order_by = Self::build_order_by_plan(before_order, data)?;
         let projection = Self::build_projection_plan(order_by, data)?;
         let limit = Self::build_limit_plan(projection, data)?;
 
@@ -86,10 +99,55 @@ impl PlanParser {
     }
 
     fn build_from_plan(data: &QueryAnalyzeState) -> Result<PlanNode> {
-        match &data.relation {
+        Self::build_relation_plan(&data.relation)
+    }
+
+    fn build_relation_plan(relation: &QueryRelation) -> Result<PlanNode> {
+        match relation {
             QueryRelation::None => Err(ErrorCode::LogicalError("Not from in select query")),
             QueryRelation::Nested(data) => Self::build_query_plan(data),
             QueryRelation::FromTable(plan) => Ok(PlanNode::ReadSource(plan.as_ref().clone())),
+            QueryRelation::Join(join) => Self::build_join_plan(join),
+            QueryRelation::SetOperation(set_operation) => {
+                Self::build_set_operation_plan(set_operation)
+            }
+            QueryRelation::MaterializedCte(name, data) => {
+                let cte = Self::build_query_plan(data)?;
+                PlanBuilder::from(&cte).materialized_cte(name)?.build()
+            }
+        }
+    }
+
+    fn build_set_operation_plan(set_operation: &SetOperationRelation) -> Result<PlanNode> {
+        let left = Self::build_query_plan(&set_operation.left)?;
+        let right = Self::build_query_plan(&set_operation.right)?;
+        PlanBuilder::from(&left)
+            .set_operation(set_operation.op, set_operation.all, &right)?
+            .build()
+    }
+
+    fn build_join_plan(join: &JoinRelation) -> Result<PlanNode> {
+        let left = Self::build_join_side_plan(&join.left, &join.left_projection)?;
+        let right = Self::build_join_side_plan(&join.right, &join.right_projection)?;
+        PlanBuilder::from(&left)
+            .join(
+                &right,
+                join.join_type,
+                &join.left_keys,
+                &join.right_keys,
+                join.other_conditions.clone(),
+            )?
+            .build()
+    }
+
+    fn build_join_side_plan(
+        relation: &QueryRelation,
+        projection: &[Expression],
+    ) -> Result<PlanNode> {
+        let plan = Self::build_relation_plan(relation)?;
+        match projection.is_empty() {
+            true => Ok(plan),
+            false => PlanBuilder::from(&plan).project(projection)?.build(),
         }
     }
 
@@ -105,6 +163,33 @@ impl PlanParser {
         }
     }
 
+    fn build_array_join_plan(plan: PlanNode, data: &QueryAnalyzeState) -> Result<PlanNode> {
+        fn is_all_column(exprs: &[Expression]) -> bool {
+            exprs
+                .iter()
+                .all(|expr| matches!(expr, Expression::Column(_)))
+        }
+
+        match data.array_join_expressions.is_empty() {
+            true => Ok(plan),
+            false => {
+                let mut builder = PlanBuilder::from(&plan);
+                // if all expression is column expression expression, we skip this expression
+                if !is_all_column(&data.before_array_join_expressions) {
+                    let exprs = &data.before_array_join_expressions;
+                    builder = builder.expression(exprs, "Before ArrayJoin")?;
+                }
+
+                builder = builder.array_join(&data.array_join_expressions)?;
+                if let Some(predicate) = &data.array_join_filter {
+                    builder = builder.filter(predicate.clone())?;
+                }
+
+                builder.build()
+            }
+        }
+    }
+
     fn build_group_by_plan(plan: PlanNode, data: &QueryAnalyzeState) -> Result<PlanNode> {
         // S0: Apply a partial aggregator plan.
         // S1: Apply a fragment plan for distributed planners split.
@@ -142,6 +227,49 @@ impl PlanParser {
         }
     }
 
+    fn build_before_window(plan: PlanNode, data: &QueryAnalyzeState) -> Result<PlanNode> {
+        fn is_all_column(exprs: &[Expression]) -> bool {
+            exprs
+                .iter()
+                .all(|expr| matches!(expr, Expression::Column(_)))
+        }
+
+        match data.before_window_expressions.is_empty() {
+            true => Ok(plan),
+            // if all expression is column expression expression, we skip this expression
+            false if is_all_column(&data.before_window_expressions) => Ok(plan),
+            false => PlanBuilder::from(&plan)
+                .expression(&data.before_window_expressions, "Before Window")?
+                .build(),
+        }
+    }
+
+    // The window functions with the same PARTITION BY and ORDER BY are evaluated in one plan.
+    fn build_window_plan(plan: PlanNode, data: &QueryAnalyzeState) -> Result<PlanNode> {
+        let mut windows: Vec<(_, Vec<Expression>)> = vec![];
+        for window_expression in &data.window_expressions {
+            if let Expression::WindowFunction {
+                partition_by,
+                order_by,
+                ..
+            } = window_expression
+            {
+                let window = (partition_by, order_by);
+                match windows.iter_mut().find(|(w, _)| w == &window) {
+                    Some((_, exprs)) => exprs.push(window_expression.clone()),
+                    None => windows.push((window, vec![window_expression.clone()])),
+                }
+            }
+        }
+
+        let mut builder = PlanBuilder::from(&plan);
+        for (_, window_expressions) in &windows {
+            builder = builder.window(window_expressions)?;
+        }
+
+        builder.build()
+    }
+
     fn build_having_plan(plan: PlanNode, data: &QueryAnalyzeState) -> Result<PlanNode> {
         match &data.having {
             None => Ok(plan),
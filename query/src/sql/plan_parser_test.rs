@@ -184,10 +184,27 @@ fn test_plan_parser() -> Result<()> {
             error: "",
         },
         Test {
-            name: "unimplemented-cte",
-            sql: "with t as ( select sum(number) n from numbers_mt(1000) )select * from t",
+            name: "cte",
+            sql: "with t(a) as (select number from numbers(10) where number > 1) select a from t",
+            expect: "\
+            Projection: a:UInt64\
+            \n  Projection: number as a:UInt64\
+            \n    Projection: number:UInt64\
+            \n      Filter: (number > 1)\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]",
+            error: "",
+        },
+        Test {
+            name: "cte-with-different-columns",
+            sql: "with t(a, b) as (select number from numbers(10)) select * from t",
             expect: "",
-            error: "Code: 5, displayText = sql parser error: CTE is not yet implement.",
+            error: "Code: 6, displayText = WITH query t has 1 columns available but 2 columns specified.",
+        },
+        Test {
+            name: "unimplemented-recursive-cte",
+            sql: "with recursive t as (select number from numbers(10)) select * from t",
+            expect: "",
+            error: "Code: 5, displayText = sql parser error: Recursive CTE is not yet implement.",
         },
         Test {
             name: "kleene-logic-null",
@@ -207,6 +224,88 @@ fn test_plan_parser() -> Result<()> {
             \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]",
             error: "",
         },
+        Test {
+            name: "join",
+            sql: "select a.number, b.number from numbers(10) as a join numbers(10) as b on a.number = b.number",
+            expect: "\
+            Projection: a.number:UInt64, b.number:UInt64\
+            \n  HashJoin: INNER JOIN, keys: [a.number = b.number]\
+            \n    Projection: number as a.number:UInt64\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]\
+            \n    Projection: number as b.number:UInt64\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]",
+            error: "",
+        },
+        Test {
+            name: "join-without-condition",
+            sql: "select a.number from numbers(10) as a left join numbers(10) as b",
+            expect: "",
+            error: "Code: 5, displayText = LEFT JOIN must have ON or USING clause.",
+        },
+        Test {
+            name: "window",
+            sql: "select number, row_number() over (partition by number % 3 order by number desc) as rn from numbers(10)",
+            expect: "\
+            Projection: number:UInt64, row_number() OVER (PARTITION BY (number % 3) ORDER BY number DESC) as rn:UInt64\
+            \n  Window: row_number() OVER (PARTITION BY (number % 3) ORDER BY number DESC):UInt64\
+            \n    Expression: (number % 3):UInt8, number:UInt64 (Before Window)\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]",
+            error: "",
+        },
+        Test {
+            name: "window-without-over",
+            sql: "select row_number() from numbers(10)",
+            expect: "",
+            error: "Code: 5, displayText = Window function row_number requires an OVER clause (while in analyze select projection).",
+        },
+        Test {
+            name: "window-in-filter",
+            sql: "select number from numbers(10) where row_number() over (order by number) > 1",
+            expect: "",
+            error: "Code: 5, displayText = filter cannot contain window functions.",
+        },
+        Test {
+            name: "array-join-clause",
+            sql: "select n from numbers(10) array join [number] as n where n > 1",
+            expect: "\
+            Projection: arrayJoin(array(number)) as n:UInt64\
+            \n  Filter: (arrayJoin(array(number)) > 1)\
+            \n    ArrayJoin: arrayJoin(array(number))\
+            \n      Expression: array(number):List(DataField { name: \"item\", data_type: UInt64, nullable: true }) (Before ArrayJoin)\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]",
+            error: "",
+        },
+        Test {
+            name: "array-join-clause-not-array",
+            sql: "select number from numbers(10) array join number",
+            expect: "",
+            error: "Code: 7, displayText = Expected array type as the argument of arrayJoin, but got UInt64 (while in select array join).",
+        },
+        Test {
+            name: "nested-array-join",
+            sql: "select arrayJoin(array(arrayJoin(array(number)))) from numbers(10)",
+            expect: "",
+            error: "Code: 5, displayText = arrayJoin cannot contain arrayJoin.",
+        },
+        Test {
+            name: "union-all",
+            sql: "select number from numbers(10) union all select number from numbers(10)",
+            expect: "\
+            Projection: number:UInt64\
+            \n  Projection: number:UInt64\
+            \n    UnionAll: 2 inputs\
+            \n      Projection: number:UInt64\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]\
+            \n      Projection: number:UInt64\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80], push_downs: [projections: [0]]",
+            error: "",
+        },
+        Test {
+            name: "union-with-different-columns",
+            sql: "select number from numbers(10) union select number, number + 1 from numbers(10)",
+            expect: "",
+            error: "Code: 6, displayText = Each UNION query must have the same number of columns, but got 1 and 2.",
+        },
         Test {
             name: "show-metrics",
             sql: "show metrics",
@@ -219,8 +318,8 @@ fn test_plan_parser() -> Result<()> {
             name: "show-processlist",
             sql: "show processlist",
             expect: "\
-            Projection: id:String, type:String, host:String, user:String, state:String, database:String, extra_info:String, memory_usage:UInt64\
-            \n  ReadDataSource: scan partitions: [1], scan schema: [id:String, type:String, host:String;N, user:String;N, state:String, database:String, extra_info:String;N, memory_usage:UInt64;N], statistics: [read_rows: 0, read_bytes: 0], push_downs: [projections: [0, 1, 2, 3, 4, 5, 6, 7]]",
+            Projection: id:String, type:String, host:String, user:String, state:String, database:String, extra_info:String, memory_usage:UInt64, peak_memory_usage:UInt64\
+            \n  ReadDataSource: scan partitions: [1], scan schema: [id:String, type:String, host:String;N, user:String;N, state:String, database:String, extra_info:String;N, memory_usage:UInt64;N, peak_memory_usage:UInt64;N], statistics: [read_rows: 0, read_bytes: 0], push_downs: [projections: [0, 1, 2, 3, 4, 5, 6, 7, 8]]",
             error: "",
         },
     ];
@@ -17,9 +17,17 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use sqlparser::ast::DataType as SQLDataType;
 
+use crate::sql::DfParser;
+
 pub struct SQLCommon;
 
 impl SQLCommon {
+    /// Maps the SQL type text like `Map(String, Int64)` to the corresponding `DataType`
+    pub fn make_data_type_from_text(text: &str) -> Result<DataType> {
+        let sql_type = DfParser::parse_data_type_text(text)?;
+        Self::make_data_type(&sql_type)
+    }
+
     /// Maps the SQL type to the corresponding Arrow `DataType`
     pub fn make_data_type(sql_type: &SQLDataType) -> Result<DataType> {
         match sql_type {
@@ -31,7 +39,12 @@ impl SQLCommon {
             SQLDataType::Varchar(_) => Ok(DataType::String),
             SQLDataType::String => Ok(DataType::String),
             SQLDataType::Text => Ok(DataType::String),
-            SQLDataType::Decimal(_, _) => Ok(DataType::Float64),
+            SQLDataType::Decimal(precision, scale) => {
+                // DECIMAL without arguments is DECIMAL(10, 0), the same as MySQL.
+                let precision = precision.unwrap_or(10) as usize;
+                let scale = scale.unwrap_or(0) as usize;
+                check_decimal_type(precision, scale)
+            }
             SQLDataType::Float(_) => Ok(DataType::Float32),
             SQLDataType::Real | SQLDataType::Double => Ok(DataType::Float64),
             SQLDataType::Boolean => Ok(DataType::Boolean),
@@ -54,10 +67,37 @@ impl SQLCommon {
                     "FLOAT32" => Ok(DataType::Float32),
                     "FLOAT64" => Ok(DataType::Float64),
                     "STRING" => Ok(DataType::String),
+                    "VARIANT" | "JSON" => Ok(DataType::Variant),
                     "DATE16" => Ok(DataType::Date16),
                     "DATE32" => Ok(DataType::Date32),
                     "DATETIME" => Ok(DataType::DateTime32(None)),
                     "DATETIME32" => Ok(DataType::DateTime32(None)),
+                    "DATETIME64" => {
+                        let precision = match obj.0.get(1) {
+                            Some(p) => p.value.parse::<u32>().map_err(|_| {
+                                ErrorCode::IllegalDataType(format!(
+                                    "Invalid DateTime64 precision: {}",
+                                    p.value
+                                ))
+                            })?,
+                            None => DATETIME64_DEFAULT_PRECISION,
+                        };
+                        check_datetime64_precision(precision)?;
+
+                        let tz = obj.0.get(2).map(|tz| tz.value.clone());
+                        get_timezone(&tz)?;
+                        Ok(DataType::DateTime64(precision, tz))
+                    }
+                    "MAP" if obj.0.len() == 3 => {
+                        let key_type = Self::make_data_type_from_text(&obj.0[1].value)?;
+                        let value_type = Self::make_data_type_from_text(&obj.0[2].value)?;
+                        check_map_type(key_type, value_type)
+                    }
+                    "ARRAY" if obj.0.len() == 2 => {
+                        let item_type = Self::make_data_type_from_text(&obj.0[1].value)?;
+                        let item = DataField::new("item", item_type, true);
+                        Ok(DataType::List(Box::new(item)))
+                    }
                     "SIGNED" => Ok(DataType::Int64),
                     "UNSIGNED" => Ok(DataType::UInt64),
 
@@ -23,12 +23,15 @@ use common_meta_types::AuthType;
 use common_meta_types::UserPrivilege;
 use common_meta_types::UserPrivilegeType;
 use common_planners::ExplainType;
+use common_planners::Optimization;
 use metrics::histogram;
 use sqlparser::ast::BinaryOperator;
 use sqlparser::ast::ColumnDef;
 use sqlparser::ast::ColumnOptionDef;
+use sqlparser::ast::DataType;
 use sqlparser::ast::Expr;
 use sqlparser::ast::Ident;
+use sqlparser::ast::ObjectName;
 use sqlparser::ast::SqlOption;
 use sqlparser::ast::Statement;
 use sqlparser::ast::TableConstraint;
@@ -42,12 +45,14 @@ use sqlparser::parser::ParserError;
 use sqlparser::tokenizer::Token;
 use sqlparser::tokenizer::Tokenizer;
 use sqlparser::tokenizer::Whitespace;
+use sqlparser::tokenizer::Word;
 
 use super::statements::DfCopy;
 use crate::sql::statements::DfAlterUser;
 use crate::sql::statements::DfCreateDatabase;
 use crate::sql::statements::DfCreateTable;
 use crate::sql::statements::DfCreateUser;
+use crate::sql::statements::DfDeleteStatement;
 use crate::sql::statements::DfDescribeTable;
 use crate::sql::statements::DfDropDatabase;
 use crate::sql::statements::DfDropTable;
@@ -57,6 +62,7 @@ use crate::sql::statements::DfGrantObject;
 use crate::sql::statements::DfGrantStatement;
 use crate::sql::statements::DfInsertStatement;
 use crate::sql::statements::DfKillStatement;
+use crate::sql::statements::DfOptimizeTable;
 use crate::sql::statements::DfQueryStatement;
 use crate::sql::statements::DfSetVariable;
 use crate::sql::statements::DfShowCreateTable;
@@ -67,7 +73,9 @@ use crate::sql::statements::DfShowSettings;
 use crate::sql::statements::DfShowTables;
 use crate::sql::statements::DfShowUsers;
 use crate::sql::statements::DfTruncateTable;
+use crate::sql::statements::DfUpdateStatement;
 use crate::sql::statements::DfUseDatabase;
+use crate::sql::statements::DfVacuumTable;
 use crate::sql::DfHint;
 use crate::sql::DfStatement;
 
@@ -93,13 +101,266 @@ impl<'a> DfParser<'a> {
     /// Parse the specified tokens with dialect
     pub fn new_with_dialect(sql: &str, dialect: &'a dyn Dialect) -> Result<Self, ParserError> {
         let mut tokenizer = Tokenizer::new(dialect, sql);
-        let tokens = tokenizer.tokenize()?;
+        let tokens = Self::rewrite_time_travel(tokenizer.tokenize()?);
+        let tokens = Self::rewrite_array_literals(tokens);
+        let tokens = Self::rewrite_array_join(tokens);
 
         Ok(DfParser {
             parser: Parser::new(tokens, dialect),
         })
     }
 
+    /// Rewrite the time travel clauses `AT (SNAPSHOT => expr)` and `AT (TIMESTAMP => expr)`
+    /// into table hints `WITH (SNAPSHOT = expr)` and `WITH (TIMESTAMP = expr)`,
+    /// which are understood by sqlparser and resolved while analyzing the table references.
+    fn rewrite_time_travel(mut tokens: Vec<Token>) -> Vec<Token> {
+        fn next_non_whitespace(tokens: &[Token], from: usize) -> Option<usize> {
+            (from..tokens.len()).find(|idx| !matches!(tokens[*idx], Token::Whitespace(_)))
+        }
+
+        let mut idx = 0;
+        while idx < tokens.len() {
+            let at_clause = match &tokens[idx] {
+                Token::Word(w) if w.quote_style.is_none() && w.value.eq_ignore_ascii_case("AT") => {
+                    let lparen = next_non_whitespace(&tokens, idx + 1)
+                        .filter(|pos| tokens[*pos] == Token::LParen);
+                    let kind = lparen.and_then(|pos| next_non_whitespace(&tokens, pos + 1));
+                    let arrow = kind.and_then(|pos| next_non_whitespace(&tokens, pos + 1));
+                    match (kind, arrow) {
+                        (Some(kind), Some(arrow)) if tokens[arrow] == Token::RArrow => {
+                            match &tokens[kind] {
+                                Token::Word(w)
+                                    if w.quote_style.is_none()
+                                        && (w.value.eq_ignore_ascii_case("SNAPSHOT")
+                                            || w.value.eq_ignore_ascii_case("TIMESTAMP")) =>
+                                {
+                                    Some((kind, w.value.to_uppercase(), arrow))
+                                }
+                                _ => None,
+                            }
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+
+            if let Some((kind, value, arrow)) = at_clause {
+                tokens[idx] = Token::make_keyword("WITH");
+                // TIMESTAMP is a keyword, make sure it is parsed as a plain identifier
+                tokens[kind] = Token::Word(Word {
+                    value,
+                    quote_style: None,
+                    keyword: Keyword::NoKeyword,
+                });
+                tokens[arrow] = Token::Eq;
+                idx = arrow;
+            }
+            idx += 1;
+        }
+
+        tokens
+    }
+
+    /// Rewrite the array literals `[x1, x2, ...]` into `array(x1, x2, ...)`, the brackets after
+    /// an expression are left as they are, e.g. the element access `arr[1]`.
+    fn rewrite_array_literals(tokens: Vec<Token>) -> Vec<Token> {
+        // A bracket after the end of an expression accesses an element, otherwise it starts
+        // an array literal.
+        fn ends_expression(token: &Token) -> bool {
+            match token {
+                Token::Word(w) if w.quote_style.is_none() => !matches!(
+                    w.keyword,
+                    Keyword::SELECT
+                        | Keyword::WHERE
+                        | Keyword::HAVING
+                        | Keyword::BY
+                        | Keyword::ON
+                        | Keyword::JOIN
+                        | Keyword::DISTINCT
+                        | Keyword::ALL
+                        | Keyword::AND
+                        | Keyword::OR
+                        | Keyword::NOT
+                        | Keyword::LIKE
+                        | Keyword::BETWEEN
+                        | Keyword::CASE
+                        | Keyword::WHEN
+                        | Keyword::THEN
+                        | Keyword::ELSE
+                ),
+                Token::Word(_)
+                | Token::Number(..)
+                | Token::SingleQuotedString(_)
+                | Token::RParen
+                | Token::RBracket => true,
+                _ => false,
+            }
+        }
+
+        let mut rewritten = Vec::with_capacity(tokens.len());
+        // Whether the open brackets are array literals.
+        let mut literals = vec![];
+        for token in tokens {
+            match token {
+                Token::LBracket => {
+                    let previous = rewritten
+                        .iter()
+                        .rev()
+                        .find(|token| !matches!(token, Token::Whitespace(_)));
+                    let literal = !matches!(previous, Some(token) if ends_expression(token));
+                    literals.push(literal);
+                    match literal {
+                        true => {
+                            rewritten.push(Token::Word(Word {
+                                value: String::from("array"),
+                                quote_style: None,
+                                keyword: Keyword::NoKeyword,
+                            }));
+                            rewritten.push(Token::LParen);
+                        }
+                        false => rewritten.push(Token::LBracket),
+                    }
+                }
+                Token::RBracket => match literals.pop() {
+                    Some(true) => rewritten.push(Token::RParen),
+                    _ => rewritten.push(Token::RBracket),
+                },
+                token => rewritten.push(token),
+            }
+        }
+
+        rewritten
+    }
+
+    /// Rewrite the clause `ARRAY JOIN expr [AS alias], ...` into the cross join of a table
+    /// function `CROSS JOIN "ARRAY JOIN"(alias => expr, ...)`, which is understood by sqlparser
+    /// and taken out of the joins while converting the query.
+    fn rewrite_array_join(tokens: Vec<Token>) -> Vec<Token> {
+        fn next_non_whitespace(tokens: &[Token], from: usize) -> Option<usize> {
+            (from..tokens.len()).find(|idx| !matches!(tokens[*idx], Token::Whitespace(_)))
+        }
+
+        fn is_word(token: &Token, value: &str) -> bool {
+            match token {
+                Token::Word(w) => w.quote_style.is_none() && w.value.eq_ignore_ascii_case(value),
+                _ => false,
+            }
+        }
+
+        fn is_array_join(tokens: &[Token], idx: usize) -> bool {
+            let next = next_non_whitespace(tokens, idx + 1);
+            is_word(&tokens[idx], "ARRAY")
+                && matches!(next, Some(pos) if is_word(&tokens[pos], "JOIN"))
+        }
+
+        // The clauses and the joins following the ARRAY JOIN clause.
+        fn ends_clause(tokens: &[Token], idx: usize) -> bool {
+            match &tokens[idx] {
+                Token::Word(w) if w.quote_style.is_none() => {
+                    let keyword = matches!(
+                        w.value.to_uppercase().as_str(),
+                        "WHERE"
+                            | "GROUP"
+                            | "HAVING"
+                            | "ORDER"
+                            | "LIMIT"
+                            | "OFFSET"
+                            | "UNION"
+                            | "EXCEPT"
+                            | "INTERSECT"
+                            | "FORMAT"
+                            | "JOIN"
+                            | "INNER"
+                            | "LEFT"
+                            | "RIGHT"
+                            | "FULL"
+                            | "CROSS"
+                    );
+                    keyword || is_array_join(tokens, idx)
+                }
+                Token::SemiColon | Token::EOF => true,
+                _ => false,
+            }
+        }
+
+        let mut rewritten = Vec::with_capacity(tokens.len());
+        let mut idx = 0;
+        while idx < tokens.len() {
+            if !is_array_join(&tokens, idx) {
+                rewritten.push(tokens[idx].clone());
+                idx += 1;
+                continue;
+            }
+
+            rewritten.push(Token::make_keyword("CROSS"));
+            rewritten.push(Token::Whitespace(Whitespace::Space));
+            rewritten.push(Token::make_keyword("JOIN"));
+            rewritten.push(Token::Whitespace(Whitespace::Space));
+            rewritten.push(Token::Word(Word {
+                value: String::from(DfQueryStatement::ARRAY_JOIN_TABLE_FUNCTION),
+                quote_style: Some('"'),
+                keyword: Keyword::NoKeyword,
+            }));
+            rewritten.push(Token::LParen);
+
+            // The arguments are the expressions of the clause, named by their aliases.
+            let mut depth = 0;
+            let mut expr = vec![];
+            let mut alias = None;
+            // Skip the JOIN of ARRAY JOIN.
+            idx = next_non_whitespace(&tokens, idx + 1).unwrap_or(idx) + 1;
+            loop {
+                let token = tokens.get(idx);
+                let end_of_expr = match token {
+                    None => true,
+                    Some(Token::RParen | Token::RBracket) if depth == 0 => true,
+                    Some(Token::Comma) => depth == 0,
+                    Some(_) => depth == 0 && ends_clause(&tokens, idx),
+                };
+
+                if end_of_expr {
+                    if let Some(alias) = alias.take() {
+                        rewritten.push(alias);
+                        rewritten.push(Token::RArrow);
+                    }
+
+                    rewritten.append(&mut expr);
+                    match token {
+                        Some(Token::Comma) => {
+                            rewritten.push(Token::Comma);
+                            idx += 1;
+                        }
+                        _ => break,
+                    }
+                    continue;
+                }
+
+                match &tokens[idx] {
+                    Token::LParen | Token::LBracket => depth += 1,
+                    Token::RParen | Token::RBracket => depth -= 1,
+                    _ => {}
+                }
+
+                match next_non_whitespace(&tokens, idx + 1) {
+                    Some(pos) if depth == 0 && is_word(&tokens[idx], "AS") => {
+                        alias = Some(tokens[pos].clone());
+                        idx = pos + 1;
+                    }
+                    _ => {
+                        expr.push(tokens[idx].clone());
+                        idx += 1;
+                    }
+                }
+            }
+
+            rewritten.push(Token::RParen);
+            rewritten.push(Token::Whitespace(Whitespace::Space));
+        }
+
+        rewritten
+    }
+
     /// Parse a SQL statement and produce a set of statements with dialect
     pub fn parse_sql(sql: &str) -> Result<(Vec<DfStatement>, Vec<DfHint>), ErrorCode> {
         let dialect = &GenericDialect {};
@@ -227,6 +488,8 @@ impl<'a> DfParser<'a> {
                     Keyword::TRUNCATE => self.parse_truncate(),
                     Keyword::SET => self.parse_set(),
                     Keyword::INSERT => self.parse_insert(),
+                    Keyword::DELETE => self.parse_delete(),
+                    Keyword::UPDATE => self.parse_update(),
                     Keyword::SELECT | Keyword::WITH | Keyword::VALUES => self.parse_query(),
                     Keyword::GRANT => {
                         self.parser.next_token();
@@ -240,6 +503,8 @@ impl<'a> DfParser<'a> {
                         // Use database
                         "USE" => self.parse_use_database(),
                         "KILL" => self.parse_kill_query(),
+                        "OPTIMIZE" => self.parse_optimize(),
+                        "VACUUM" => self.parse_vacuum(),
                         _ => self.expected("Keyword", self.parser.peek_token()),
                     },
                     _ => self.expected("an SQL statement", Token::Word(w)),
@@ -304,6 +569,36 @@ impl<'a> DfParser<'a> {
         }
     }
 
+    fn parse_delete(&mut self) -> Result<DfStatement, ParserError> {
+        self.parser.next_token();
+        match self.parser.parse_delete()? {
+            Statement::Delete {
+                table_name,
+                selection,
+            } => Ok(DfStatement::Delete(DfDeleteStatement {
+                name: table_name,
+                selection,
+            })),
+            _ => parser_err!("Expect delete statement"),
+        }
+    }
+
+    fn parse_update(&mut self) -> Result<DfStatement, ParserError> {
+        self.parser.next_token();
+        match self.parser.parse_update()? {
+            Statement::Update {
+                table_name,
+                assignments,
+                selection,
+            } => Ok(DfStatement::Update(DfUpdateStatement {
+                name: table_name,
+                assignments,
+                selection,
+            })),
+            _ => parser_err!("Expect update statement"),
+        }
+    }
+
     /// Parse an SQL EXPLAIN statement.
     pub fn parse_explain(&mut self) -> Result<DfStatement, ParserError> {
         // Parser is at the token immediately after EXPLAIN
@@ -318,6 +613,10 @@ impl<'a> DfParser<'a> {
                     self.parser.next_token();
                     ExplainType::Graph
                 }
+                "COST" => {
+                    self.parser.next_token();
+                    ExplainType::Cost
+                }
                 _ => ExplainType::Syntax,
             },
             _ => ExplainType::Syntax,
@@ -425,7 +724,7 @@ impl<'a> DfParser<'a> {
 
     fn parse_column_def(&mut self) -> Result<ColumnDef, ParserError> {
         let name = self.parser.parse_identifier()?;
-        let data_type = self.parser.parse_data_type()?;
+        let data_type = self.parse_data_type()?;
         let collation = if self.parser.parse_keyword(Keyword::COLLATE) {
             Some(self.parser.parse_object_name()?)
         } else {
@@ -457,6 +756,86 @@ impl<'a> DfParser<'a> {
         })
     }
 
+    // sqlparser doesn't know DateTime64(precision[, 'timezone']), so the arguments are
+    // kept as the trailing parts of a custom type name.
+    fn parse_data_type(&mut self) -> Result<DataType, ParserError> {
+        match self.parser.peek_token() {
+            Token::Word(w) if w.value.to_uppercase() == "DATETIME64" => {
+                self.parser.next_token();
+                let mut idents = vec![Ident::new(w.value)];
+                if self.parser.consume_token(&Token::LParen) {
+                    let precision = self.parser.parse_literal_uint()?;
+                    idents.push(Ident::new(precision.to_string()));
+                    if self.parser.consume_token(&Token::Comma) {
+                        idents.push(Ident::new(self.parser.parse_literal_string()?));
+                    }
+                    self.parser.expect_token(&Token::RParen)?;
+                }
+                Ok(DataType::Custom(ObjectName(idents)))
+            }
+            Token::Word(w) if w.value.to_uppercase() == "MAP" => {
+                self.parser.next_token();
+                self.parser.expect_token(&Token::LParen)?;
+                let key_type = self.parse_data_type()?;
+                self.parser.expect_token(&Token::Comma)?;
+                let value_type = self.parse_data_type()?;
+                self.parser.expect_token(&Token::RParen)?;
+
+                // the key and value types are kept as SQL texts, which are parsed back by
+                // `DfParser::parse_data_type_text` when making the map type
+                Ok(DataType::Custom(ObjectName(vec![
+                    Ident::new(w.value),
+                    Ident::new(Self::data_type_text(&key_type)),
+                    Ident::new(Self::data_type_text(&value_type)),
+                ])))
+            }
+            Token::Word(w) if w.value.to_uppercase() == "ARRAY" => {
+                self.parser.next_token();
+                self.parser.expect_token(&Token::LParen)?;
+                let item_type = self.parse_data_type()?;
+                self.parser.expect_token(&Token::RParen)?;
+
+                // the item type is kept as SQL text, the same as the map types
+                Ok(DataType::Custom(ObjectName(vec![
+                    Ident::new(w.value),
+                    Ident::new(Self::data_type_text(&item_type)),
+                ])))
+            }
+            _ => self.parser.parse_data_type(),
+        }
+    }
+
+    /// Parse a data type from its SQL text, like `Map(String, DateTime64(3))`.
+    pub fn parse_data_type_text(text: &str) -> Result<DataType, ParserError> {
+        let dialect = &GenericDialect {};
+        let mut parser = DfParser::new_with_dialect(text, dialect)?;
+        let data_type = parser.parse_data_type()?;
+        match parser.parser.peek_token() {
+            Token::EOF => Ok(data_type),
+            unexpected => parser.expected("end of data type", unexpected),
+        }
+    }
+
+    /// The SQL text of a data type returned by `parse_data_type`.
+    fn data_type_text(data_type: &DataType) -> String {
+        match data_type {
+            DataType::Custom(ObjectName(idents)) if idents.len() > 1 => {
+                let name = &idents[0].value;
+                let args = idents[1..]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, arg)| match (name.to_uppercase().as_str(), i) {
+                        // the timezone of DateTime64
+                        ("DATETIME64", 1) => format!("'{}'", arg.value),
+                        _ => arg.value.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                format!("{}({})", name, args.join(", "))
+            }
+            other => other.to_string(),
+        }
+    }
+
     fn parse_create(&mut self) -> Result<DfStatement, ParserError> {
         match self.parser.next_token() {
             Token::Word(w) => match w.keyword {
@@ -569,6 +948,58 @@ impl<'a> DfParser<'a> {
         }
     }
 
+    // Parse 'OPTIMIZE TABLE [db.]table [COMPACT | SEGMENT]'.
+    fn parse_optimize(&mut self) -> Result<DfStatement, ParserError> {
+        if !self.consume_token("OPTIMIZE") {
+            return self.expected("Must OPTIMIZE", self.parser.peek_token());
+        }
+
+        self.parser.expect_keyword(Keyword::TABLE)?;
+        let name = self.parser.parse_object_name()?;
+        let operation = if self.consume_token("SEGMENT") {
+            Optimization::Segment
+        } else {
+            // COMPACT is the default optimization
+            self.consume_token("COMPACT");
+            Optimization::Compact
+        };
+
+        Ok(DfStatement::OptimizeTable(DfOptimizeTable {
+            name,
+            operation,
+        }))
+    }
+
+    // Parse 'VACUUM TABLE [db.]table [RETAIN n HOURS] [DRY RUN]'.
+    fn parse_vacuum(&mut self) -> Result<DfStatement, ParserError> {
+        if !self.consume_token("VACUUM") {
+            return self.expected("Must VACUUM", self.parser.peek_token());
+        }
+
+        self.parser.expect_keyword(Keyword::TABLE)?;
+        let name = self.parser.parse_object_name()?;
+        let retain_hours = if self.consume_token("RETAIN") {
+            let hours = self.parser.parse_literal_uint()?;
+            if !self.consume_token("HOURS") {
+                return self.expected("HOURS", self.parser.peek_token());
+            }
+            Some(hours)
+        } else {
+            None
+        };
+
+        let dry_run = self.consume_token("DRY");
+        if dry_run && !self.consume_token("RUN") {
+            return self.expected("RUN", self.parser.peek_token());
+        }
+
+        Ok(DfStatement::VacuumTable(DfVacuumTable {
+            name,
+            retain_hours,
+            dry_run,
+        }))
+    }
+
     fn parse_create_user(&mut self) -> Result<DfStatement, ParserError> {
         let if_not_exists =
             self.parser
@@ -682,6 +1113,7 @@ impl<'a> DfParser<'a> {
         let table_name = self.parser.parse_object_name()?;
         let (columns, _) = self.parse_columns()?;
         let engine = self.parse_table_engine()?;
+        let cluster_keys = self.parse_cluster_by()?;
 
         let mut table_properties = vec![];
 
@@ -695,11 +1127,21 @@ impl<'a> DfParser<'a> {
             })
         }
 
+        if self.consume_token("BLOOM_FILTER_COLUMNS") {
+            self.parser.expect_token(&Token::Eq)?;
+            let value = self.parse_value()?;
+            table_properties.push(SqlOption {
+                name: Ident::new("BLOOM_FILTER_COLUMNS"),
+                value,
+            })
+        }
+
         let create = DfCreateTable {
             if_not_exists,
             name: table_name,
             columns,
             engine,
+            cluster_keys,
             options: table_properties,
         };
 
@@ -727,6 +1169,33 @@ impl<'a> DfParser<'a> {
         Ok(self.parser.next_token().to_string())
     }
 
+    /// Parses the optional `CLUSTER BY (expr, ...)` clause of table definition
+    fn parse_cluster_by(&mut self) -> Result<Vec<Expr>, ParserError> {
+        if !self.consume_token("CLUSTER") {
+            return Ok(vec![]);
+        }
+
+        self.parser.expect_keyword(Keyword::BY)?;
+        self.parse_cluster_keys_list()
+    }
+
+    fn parse_cluster_keys_list(&mut self) -> Result<Vec<Expr>, ParserError> {
+        self.parser.expect_token(&Token::LParen)?;
+        let keys = self.parser.parse_comma_separated(Parser::parse_expr)?;
+        self.parser.expect_token(&Token::RParen)?;
+        Ok(keys)
+    }
+
+    /// Parses the cluster key of table, e.g. `(a, b)`, which is kept in the table options
+    pub fn parse_cluster_keys(sql: &str) -> Result<Vec<Expr>, ParserError> {
+        let mut parser = DfParser::new(sql)?;
+        let keys = parser.parse_cluster_keys_list()?;
+        match parser.parser.peek_token() {
+            Token::EOF => Ok(keys),
+            unexpected => parser.expected("end of cluster keys", unexpected),
+        }
+    }
+
     fn parse_show_create(&mut self) -> Result<DfStatement, ParserError> {
         match self.parser.next_token() {
             Token::Word(w) => match w.keyword {
@@ -16,6 +16,7 @@ use common_exception::Result;
 use common_meta_types::AuthType;
 use common_meta_types::UserPrivilege;
 use common_meta_types::UserPrivilegeType;
+use common_planners::Optimization;
 use sqlparser::ast::*;
 
 use crate::sql::statements::DfAlterUser;
@@ -23,16 +24,21 @@ use crate::sql::statements::DfCopy;
 use crate::sql::statements::DfCreateDatabase;
 use crate::sql::statements::DfCreateTable;
 use crate::sql::statements::DfCreateUser;
+use crate::sql::statements::DfDeleteStatement;
 use crate::sql::statements::DfDescribeTable;
 use crate::sql::statements::DfDropDatabase;
 use crate::sql::statements::DfDropTable;
 use crate::sql::statements::DfDropUser;
 use crate::sql::statements::DfGrantObject;
 use crate::sql::statements::DfGrantStatement;
+use crate::sql::statements::DfOptimizeTable;
+use crate::sql::statements::DfQueryStatement;
 use crate::sql::statements::DfShowDatabases;
 use crate::sql::statements::DfShowTables;
 use crate::sql::statements::DfTruncateTable;
+use crate::sql::statements::DfUpdateStatement;
 use crate::sql::statements::DfUseDatabase;
+use crate::sql::statements::DfVacuumTable;
 use crate::sql::*;
 
 fn expect_parse_ok(sql: &str, expected: DfStatement) -> Result<()> {
@@ -133,6 +139,7 @@ fn create_table() -> Result<()> {
         name: ObjectName(vec![Ident::new("t")]),
         columns: vec![make_column_def("c1", DataType::Int(None))],
         engine: "CSV".to_string(),
+        cluster_keys: vec![],
         options: vec![SqlOption {
             name: Ident::new("LOCATION".to_string()),
             value: Value::SingleQuotedString("/data/33.csv".into()),
@@ -151,6 +158,7 @@ fn create_table() -> Result<()> {
             make_column_def("c3", DataType::Varchar(Some(255))),
         ],
         engine: "Parquet".to_string(),
+        cluster_keys: vec![],
         options: vec![SqlOption {
             name: Ident::new("LOCATION".to_string()),
             value: Value::SingleQuotedString("foo.parquet".into()),
@@ -158,6 +166,61 @@ fn create_table() -> Result<()> {
     });
     expect_parse_ok(sql, expected)?;
 
+    // positive case: cluster key
+    let sql = "CREATE TABLE t(a int, b int) ENGINE = FUSE CLUSTER BY (a, b + 1)";
+    let expected = DfStatement::CreateTable(DfCreateTable {
+        if_not_exists: false,
+        name: ObjectName(vec![Ident::new("t")]),
+        columns: vec![
+            make_column_def("a", DataType::Int(None)),
+            make_column_def("b", DataType::Int(None)),
+        ],
+        engine: "FUSE".to_string(),
+        cluster_keys: vec![Expr::Identifier(Ident::new("a")), Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("b"))),
+            op: BinaryOperator::Plus,
+            right: Box::new(Expr::Value(Value::Number("1".to_string(), false))),
+        }],
+        options: vec![],
+    });
+    expect_parse_ok(sql, expected)?;
+
+    // positive case: bloom filter columns
+    let sql = "CREATE TABLE t(a int, b varchar) ENGINE = FUSE BLOOM_FILTER_COLUMNS = 'b'";
+    let expected = DfStatement::CreateTable(DfCreateTable {
+        if_not_exists: false,
+        name: ObjectName(vec![Ident::new("t")]),
+        columns: vec![
+            make_column_def("a", DataType::Int(None)),
+            make_column_def("b", DataType::Varchar(None)),
+        ],
+        engine: "FUSE".to_string(),
+        cluster_keys: vec![],
+        options: vec![SqlOption {
+            name: Ident::new("BLOOM_FILTER_COLUMNS".to_string()),
+            value: Value::SingleQuotedString("b".into()),
+        }],
+    });
+    expect_parse_ok(sql, expected)?;
+
+    // negative case: cluster key without parentheses
+    let sql = "CREATE TABLE t(a int) ENGINE = FUSE CLUSTER BY a";
+    expect_parse_err(sql, "sql parser error: Expected (, found: a".to_string())?;
+
+    Ok(())
+}
+
+#[test]
+fn cluster_keys() -> Result<()> {
+    let keys = DfParser::parse_cluster_keys("(tenant_id, event_date)")?;
+    assert_eq!(keys, vec![
+        Expr::Identifier(Ident::new("tenant_id")),
+        Expr::Identifier(Ident::new("event_date")),
+    ]);
+
+    let r = DfParser::parse_cluster_keys("(a) b");
+    assert!(r.is_err());
+
     Ok(())
 }
 
@@ -309,6 +372,269 @@ fn truncate_table() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn optimize_table() -> Result<()> {
+    {
+        let sql = "OPTIMIZE TABLE t1";
+        let expected = DfStatement::OptimizeTable(DfOptimizeTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            operation: Optimization::Compact,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "OPTIMIZE TABLE db1.t1 COMPACT";
+        let expected = DfStatement::OptimizeTable(DfOptimizeTable {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            operation: Optimization::Compact,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "optimize table t1 segment";
+        let expected = DfStatement::OptimizeTable(DfOptimizeTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            operation: Optimization::Segment,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn vacuum_table() -> Result<()> {
+    {
+        let sql = "VACUUM TABLE t1";
+        let expected = DfStatement::VacuumTable(DfVacuumTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            retain_hours: None,
+            dry_run: false,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "VACUUM TABLE db1.t1 RETAIN 24 HOURS DRY RUN";
+        let expected = DfStatement::VacuumTable(DfVacuumTable {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            retain_hours: Some(24),
+            dry_run: true,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "vacuum table t1 dry run";
+        let expected = DfStatement::VacuumTable(DfVacuumTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+            retain_hours: None,
+            dry_run: true,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    expect_parse_err(
+        "VACUUM TABLE t1 RETAIN 24",
+        "sql parser error: Expected HOURS, found: EOF".to_string(),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn delete_from() -> Result<()> {
+    {
+        let sql = "DELETE FROM t1";
+        let expected = DfStatement::Delete(DfDeleteStatement {
+            name: ObjectName(vec![Ident::new("t1")]),
+            selection: None,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "DELETE FROM db1.t1 WHERE a = 1";
+        let expected = DfStatement::Delete(DfDeleteStatement {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            selection: Some(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("a"))),
+                op: BinaryOperator::Eq,
+                right: Box::new(Expr::Value(Value::Number("1".to_owned(), false))),
+            }),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    expect_parse_err(
+        "DELETE t1",
+        "sql parser error: Expected FROM, found: t1".to_string(),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn update_table() -> Result<()> {
+    {
+        let sql = "UPDATE t1 SET a = 1";
+        let expected = DfStatement::Update(DfUpdateStatement {
+            name: ObjectName(vec![Ident::new("t1")]),
+            assignments: vec![Assignment {
+                id: Ident::new("a"),
+                value: Expr::Value(Value::Number("1".to_owned(), false)),
+            }],
+            selection: None,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "UPDATE db1.t1 SET a = a + 1, b = 'x' WHERE a = 1";
+        let expected = DfStatement::Update(DfUpdateStatement {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            assignments: vec![
+                Assignment {
+                    id: Ident::new("a"),
+                    value: Expr::BinaryOp {
+                        left: Box::new(Expr::Identifier(Ident::new("a"))),
+                        op: BinaryOperator::Plus,
+                        right: Box::new(Expr::Value(Value::Number("1".to_owned(), false))),
+                    },
+                },
+                Assignment {
+                    id: Ident::new("b"),
+                    value: Expr::Value(Value::SingleQuotedString("x".to_string())),
+                },
+            ],
+            selection: Some(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("a"))),
+                op: BinaryOperator::Eq,
+                right: Box::new(Expr::Value(Value::Number("1".to_owned(), false))),
+            }),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    expect_parse_err(
+        "UPDATE t1 WHERE a = 1",
+        "sql parser error: Expected SET, found: WHERE".to_string(),
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn time_travel_test() -> Result<()> {
+    fn table_hints(sql: &str) -> Result<Vec<Expr>> {
+        let (statements, _) = DfParser::parse_sql(sql)?;
+        match &statements[0] {
+            DfStatement::Query(query) => match &query.from[0].relation {
+                TableFactor::Table { with_hints, .. } => Ok(with_hints.clone()),
+                other => panic!("unexpected table factor {:?}", other),
+            },
+            other => panic!("unexpected statement {:?}", other),
+        }
+    }
+
+    {
+        let sql = "SELECT * FROM t1 AT (SNAPSHOT => 'a9b8c7')";
+        let expected = vec![Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("SNAPSHOT"))),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Value(Value::SingleQuotedString("a9b8c7".to_string()))),
+        }];
+        assert_eq!(table_hints(sql)?, expected);
+    }
+
+    {
+        let sql = "SELECT * FROM db1.t1 AS t AT(timestamp => '2021-12-01 00:00:00') WHERE a > 1";
+        let expected = vec![Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("TIMESTAMP"))),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Value(Value::SingleQuotedString(
+                "2021-12-01 00:00:00".to_string(),
+            ))),
+        }];
+        assert_eq!(table_hints(sql)?, expected);
+    }
+
+    {
+        let sql = "SELECT * FROM t1 WHERE a = 'AT'";
+        assert!(table_hints(sql)?.is_empty());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn array_join_test() -> Result<()> {
+    fn query(sql: &str) -> Result<DfQueryStatement> {
+        let (statements, _) = DfParser::parse_sql(sql)?;
+        match &statements[0] {
+            DfStatement::Query(query) => Ok(query.clone()),
+            other => panic!("unexpected statement {:?}", other),
+        }
+    }
+
+    fn to_strings<T: ToString>(items: &[T]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    {
+        let query = query("SELECT [1, [2, 3]], [], (['a'])")?;
+        let expected = vec!["array(1, array(2, 3))", "array()", "(array('a'))"];
+        assert_eq!(to_strings(&query.projection), expected);
+    }
+
+    {
+        let query = query("SELECT tags[1], map['a'] FROM t WHERE [1] = arr")?;
+        assert!(matches!(
+            &query.projection[0],
+            SelectItem::UnnamedExpr(Expr::MapAccess { .. })
+        ));
+        assert!(matches!(
+            &query.projection[1],
+            SelectItem::UnnamedExpr(Expr::MapAccess { .. })
+        ));
+        let selection = query.selection.map(|expr| expr.to_string());
+        assert_eq!(selection, Some(String::from("array(1) = arr")));
+    }
+
+    {
+        let sql =
+            "SELECT id, tag FROM t ARRAY JOIN tags AS tag, [1, 2] WHERE tag = 'a' ORDER BY id";
+        let query = query(sql)?;
+        assert_eq!(to_strings(&query.array_join), vec![
+            "tags AS tag",
+            "array(1, 2)"
+        ]);
+        assert!(query.from[0].joins.is_empty());
+        assert!(query.selection.is_some());
+        assert_eq!(query.order_by.len(), 1);
+    }
+
+    {
+        let query = query("SELECT * FROM t1 JOIN t2 ON t1.a = t2.a ARRAY JOIN f(t1.tags, 1)")?;
+        assert_eq!(to_strings(&query.array_join), vec!["f(t1.tags, 1)"]);
+        assert_eq!(query.from[0].joins.len(), 1);
+    }
+
+    {
+        let query = query("SELECT * FROM (SELECT x FROM t ARRAY JOIN tags AS x) AS s")?;
+        assert!(query.array_join.is_empty());
+    }
+
+    expect_parse_err(
+        "SELECT * FROM t ARRAY JOIN a ARRAY JOIN b",
+        "sql parser error: Multiple ARRAY JOIN clauses are not yet implement".to_string(),
+    )?;
+
+    Ok(())
+}
+
 #[test]
 fn hint_test() -> Result<()> {
     {
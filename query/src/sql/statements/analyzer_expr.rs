@@ -15,23 +15,34 @@
 use std::convert::TryFrom;
 use std::sync::Arc;
 
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::aggregates::AggregateFunctionFactory;
+use common_planners::is_window_only_function;
 use common_planners::Expression;
+use common_planners::WindowFrame;
+use common_planners::WindowFrameBound;
+use common_planners::WindowFrameUnits;
 use sqlparser::ast::BinaryOperator;
 use sqlparser::ast::DataType;
 use sqlparser::ast::Expr;
 use sqlparser::ast::Function;
 use sqlparser::ast::FunctionArg;
 use sqlparser::ast::Ident;
+use sqlparser::ast::OrderByExpr;
 use sqlparser::ast::Query;
 use sqlparser::ast::UnaryOperator;
 use sqlparser::ast::Value;
+use sqlparser::ast::WindowFrame as SqlparserWindowFrame;
+use sqlparser::ast::WindowFrameBound as SqlparserWindowFrameBound;
+use sqlparser::ast::WindowFrameUnits as SqlparserWindowFrameUnits;
+use sqlparser::ast::WindowSpec;
 
 use crate::functions::ContextFunction;
 use crate::sessions::QueryContext;
 use crate::sql::statements::analyzer_value_expr::ValueExprAnalyzer;
+use crate::sql::statements::query::CommonTableExpr;
 use crate::sql::statements::AnalyzableStatement;
 use crate::sql::statements::AnalyzedResult;
 use crate::sql::statements::DfQueryStatement;
@@ -40,11 +51,20 @@ use crate::sql::SQLCommon;
 
 pub struct ExpressionAnalyzer {
     context: Arc<QueryContext>,
+    // The CTEs visible to the subqueries.
+    ctes: Vec<CommonTableExpr>,
 }
 
 impl ExpressionAnalyzer {
     pub fn create(context: Arc<QueryContext>) -> ExpressionAnalyzer {
-        ExpressionAnalyzer { context }
+        Self::create_with_ctes(context, vec![])
+    }
+
+    pub fn create_with_ctes(
+        context: Arc<QueryContext>,
+        ctes: Vec<CommonTableExpr>,
+    ) -> ExpressionAnalyzer {
+        ExpressionAnalyzer { context, ctes }
     }
 
     pub async fn analyze(&self, expr: &Expr) -> Result<Expression> {
@@ -62,6 +82,7 @@ impl ExpressionAnalyzer {
                 ExprRPNItem::Subquery(v) => self.analyze_scalar_subquery(v, &mut stack).await?,
                 ExprRPNItem::Cast(v) => self.analyze_cast(v, &mut stack)?,
                 ExprRPNItem::Between(negated) => self.analyze_between(*negated, &mut stack)?,
+                ExprRPNItem::InList(v) => self.analyze_in_list(v, &mut stack)?,
             }
         }
 
@@ -79,6 +100,12 @@ impl ExpressionAnalyzer {
     }
 
     fn analyze_function(&self, info: &FunctionExprInfo, args: &mut Vec<Expression>) -> Result<()> {
+        // The PARTITION BY and ORDER BY expressions are pushed after the arguments.
+        let window_exprs = match &info.over {
+            None => None,
+            Some(over) => Some(Self::window_exprs(over, args)?),
+        };
+
         let mut arguments = Vec::with_capacity(info.args_count);
         for _index in 0..info.args_count {
             match args.pop() {
@@ -91,19 +118,182 @@ impl ExpressionAnalyzer {
             }
         }
 
-        args.push(
-            match AggregateFunctionFactory::instance().check(&info.name) {
+        args.push(match (&info.over, window_exprs) {
+            (Some(over), Some((partition_by, order_by))) => {
+                Self::window_function(info, over, &arguments, partition_by, order_by)
+            }
+            _ if is_window_only_function(&info.name) => Err(ErrorCode::SyntaxException(format!(
+                "Window function {} requires an OVER clause",
+                info.name
+            ))),
+            _ => match AggregateFunctionFactory::instance().check(&info.name) {
                 true => self.aggr_function(info, &arguments),
                 false => match (info.unary_operator, info.binary_operator) {
                     (true, _) => Self::unary_function(info, &arguments),
                     (_, true) => Self::binary_function(info, &arguments),
                     _ => self.function(info, &arguments),
                 },
-            }?,
-        );
+            },
+        }?);
         Ok(())
     }
 
+    fn window_exprs(
+        over: &WindowExprInfo,
+        args: &mut Vec<Expression>,
+    ) -> Result<(Vec<Expression>, Vec<Expression>)> {
+        let order_by_count = over.order_by.len();
+        if args.len() < over.partition_by_count + order_by_count {
+            return Err(ErrorCode::LogicalError("It's a bug."));
+        }
+
+        let order_by_exprs = args.split_off(args.len() - order_by_count);
+        let partition_by = args.split_off(args.len() - over.partition_by_count);
+        let order_by = order_by_exprs
+            .into_iter()
+            .zip(&over.order_by)
+            .map(|(expr, (asc, nulls_first))| {
+                let asc = asc.unwrap_or(true);
+                Expression::Sort {
+                    expr: Box::new(expr.clone()),
+                    asc,
+                    nulls_first: nulls_first.unwrap_or(asc),
+                    origin_expr: Box::new(expr),
+                }
+            })
+            .collect();
+
+        Ok((partition_by, order_by))
+    }
+
+    fn window_function(
+        info: &FunctionExprInfo,
+        over: &WindowExprInfo,
+        args: &[Expression],
+        partition_by: Vec<Expression>,
+        order_by: Vec<Expression>,
+    ) -> Result<Expression> {
+        if info.distinct {
+            return Err(ErrorCode::SyntaxException(format!(
+                "DISTINCT is unsupported in window function {}",
+                info.name
+            )));
+        }
+
+        let args = match is_window_only_function(&info.name) {
+            true => {
+                Self::check_window_only_args(&info.name, args)?;
+                args.to_vec()
+            }
+            false if AggregateFunctionFactory::instance().check(&info.name) => {
+                Self::aggr_function_args(info, args)
+            }
+            false => {
+                return Err(ErrorCode::SyntaxException(format!(
+                    "{} is neither a window function nor an aggregate function",
+                    info.name
+                )));
+            }
+        };
+
+        let frame = match &over.frame {
+            None => None,
+            Some(frame) => Some(Self::window_frame(frame, &order_by)?),
+        };
+
+        Ok(Expression::WindowFunction {
+            op: info.name.clone(),
+            params: Self::aggr_function_params(info)?,
+            args,
+            partition_by,
+            order_by,
+            frame,
+        })
+    }
+
+    fn check_window_only_args(name: &str, args: &[Expression]) -> Result<()> {
+        let (min_args, max_args) = match name.to_lowercase().as_str() {
+            "row_number" | "rank" | "dense_rank" => (0, 0),
+            "first_value" | "last_value" => (1, 1),
+            _ => (1, 3),
+        };
+
+        if args.len() < min_args || args.len() > max_args {
+            return Err(ErrorCode::NumberArgumentsNotMatch(format!(
+                "{} expect to have [{}, {}] arguments, but got {}",
+                name,
+                min_args,
+                max_args,
+                args.len()
+            )));
+        }
+
+        // The offset and the default value of lag and lead.
+        for arg in args.iter().skip(1) {
+            if !matches!(arg, Expression::Literal { .. }) {
+                return Err(ErrorCode::SyntaxException(format!(
+                    "The offset and the default value of {} must be literals, but got {:?}",
+                    name, arg
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn window_frame(frame: &SqlparserWindowFrame, order_by: &[Expression]) -> Result<WindowFrame> {
+        let units = match frame.units {
+            SqlparserWindowFrameUnits::Rows => WindowFrameUnits::Rows,
+            SqlparserWindowFrameUnits::Range => WindowFrameUnits::Range,
+            SqlparserWindowFrameUnits::Groups => {
+                return Err(ErrorCode::SyntaxException(
+                    "GROUPS window frame is unsupported",
+                ));
+            }
+        };
+
+        let start_bound = Self::window_frame_bound(&frame.start_bound);
+        let end_bound = match &frame.end_bound {
+            None => WindowFrameBound::CurrentRow,
+            Some(bound) => Self::window_frame_bound(bound),
+        };
+
+        if matches!(start_bound, WindowFrameBound::Following(None)) {
+            return Err(ErrorCode::SyntaxException(
+                "Window frame start cannot be UNBOUNDED FOLLOWING",
+            ));
+        }
+
+        if matches!(end_bound, WindowFrameBound::Preceding(None)) {
+            return Err(ErrorCode::SyntaxException(
+                "Window frame end cannot be UNBOUNDED PRECEDING",
+            ));
+        }
+
+        if units == WindowFrameUnits::Range
+            && (start_bound.has_offset() || end_bound.has_offset())
+            && order_by.len() != 1
+        {
+            return Err(ErrorCode::SyntaxException(
+                "RANGE window frame with offset requires exactly one ORDER BY expression",
+            ));
+        }
+
+        Ok(WindowFrame {
+            units,
+            start_bound,
+            end_bound,
+        })
+    }
+
+    fn window_frame_bound(bound: &SqlparserWindowFrameBound) -> WindowFrameBound {
+        match bound {
+            SqlparserWindowFrameBound::CurrentRow => WindowFrameBound::CurrentRow,
+            SqlparserWindowFrameBound::Preceding(offset) => WindowFrameBound::Preceding(*offset),
+            SqlparserWindowFrameBound::Following(offset) => WindowFrameBound::Following(*offset),
+        }
+    }
+
     fn unary_function(info: &FunctionExprInfo, args: &[Expression]) -> Result<Expression> {
         match args.is_empty() {
             true => Err(ErrorCode::LogicalError(
@@ -154,6 +344,15 @@ impl ExpressionAnalyzer {
     }
 
     fn aggr_function(&self, info: &FunctionExprInfo, args: &[Expression]) -> Result<Expression> {
+        Ok(Expression::AggregateFunction {
+            op: info.name.clone(),
+            distinct: info.distinct,
+            args: Self::aggr_function_args(info, args),
+            params: Self::aggr_function_params(info)?,
+        })
+    }
+
+    fn aggr_function_params(info: &FunctionExprInfo) -> Result<Vec<DataValue>> {
         let mut parameters = Vec::with_capacity(info.parameters.len());
 
         for parameter in &info.parameters {
@@ -170,23 +369,17 @@ impl ExpressionAnalyzer {
             };
         }
 
+        Ok(parameters)
+    }
+
+    fn aggr_function_args(info: &FunctionExprInfo, args: &[Expression]) -> Vec<Expression> {
         if info.name.eq_ignore_ascii_case("count")
             && !args.is_empty()
             && matches!(args[0], Expression::Wildcard)
         {
-            Ok(Expression::AggregateFunction {
-                op: info.name.clone(),
-                distinct: info.distinct,
-                args: vec![common_planners::lit(0i64)],
-                params: parameters,
-            })
+            vec![common_planners::lit(0i64)]
         } else {
-            Ok(Expression::AggregateFunction {
-                op: info.name.clone(),
-                distinct: info.distinct,
-                args: args.to_owned(),
-                params: parameters,
-            })
+            args.to_owned()
         }
     }
 
@@ -217,7 +410,8 @@ impl ExpressionAnalyzer {
     }
 
     async fn analyze_subquery(&self, subquery: &Query) -> Result<Expression> {
-        let statement = DfQueryStatement::try_from(subquery.clone())?;
+        let mut statement = DfQueryStatement::try_from(subquery.clone())?;
+        statement.outer_ctes = self.ctes.clone();
 
         let query_context = self.context.clone();
         let subquery_context = QueryContext::new(query_context.clone());
@@ -242,7 +436,8 @@ impl ExpressionAnalyzer {
         subquery: &Query,
         args: &mut Vec<Expression>,
     ) -> Result<()> {
-        let statement = DfQueryStatement::try_from(subquery.clone())?;
+        let mut statement = DfQueryStatement::try_from(subquery.clone())?;
+        statement.outer_ctes = self.ctes.clone();
 
         let query_context = self.context.clone();
         let subquery_context = QueryContext::new(query_context.clone());
@@ -314,6 +509,47 @@ impl ExpressionAnalyzer {
 
         Ok(())
     }
+
+    fn analyze_in_list(&self, info: &InListInfo, args: &mut Vec<Expression>) -> Result<()> {
+        if info.list_size == 0 {
+            return Err(ErrorCode::SyntaxException(
+                "IN list must have at least one element.",
+            ));
+        }
+
+        if args.len() < info.list_size + 1 {
+            return Err(ErrorCode::LogicalError(
+                "IN list must have the expression and the list.",
+            ));
+        }
+
+        // `expr IN (a, b)` is rewritten to `expr = a OR expr = b`,
+        // and `expr NOT IN (a, b)` is rewritten to `expr <> a AND expr <> b`
+        let mut items = args.split_off(args.len() - info.list_size).into_iter();
+        let (expression, first) = match (args.pop(), items.next()) {
+            (Some(expression), Some(first)) => (expression, first),
+            _ => {
+                return Err(ErrorCode::LogicalError(
+                    "IN list must have the expression and the list.",
+                ))
+            }
+        };
+        args.push(match info.negated {
+            false => items.fold(expression.eq(first), |acc, item| {
+                acc.or(expression.eq(item))
+            }),
+            true => items.fold(expression.not_eq(first), |acc, item| {
+                acc.and(expression.not_eq(item))
+            }),
+        });
+
+        Ok(())
+    }
+}
+
+struct InListInfo {
+    list_size: usize,
+    negated: bool,
 }
 
 struct FunctionExprInfo {
@@ -323,6 +559,14 @@ struct FunctionExprInfo {
     unary_operator: bool,
     binary_operator: bool,
     parameters: Vec<Value>,
+    over: Option<WindowExprInfo>,
+}
+
+struct WindowExprInfo {
+    partition_by_count: usize,
+    // The ASC and NULLS FIRST of the ORDER BY expressions.
+    order_by: Vec<(Option<bool>, Option<bool>)>,
+    frame: Option<SqlparserWindowFrame>,
 }
 
 enum ExprRPNItem {
@@ -335,6 +579,7 @@ enum ExprRPNItem {
     Subquery(Box<Query>),
     Cast(common_datavalues::DataType),
     Between(bool),
+    InList(InListInfo),
 }
 
 impl ExprRPNItem {
@@ -346,6 +591,7 @@ impl ExprRPNItem {
             unary_operator: false,
             binary_operator: false,
             parameters: Vec::new(),
+            over: None,
         })
     }
 
@@ -357,6 +603,7 @@ impl ExprRPNItem {
             unary_operator: false,
             binary_operator: true,
             parameters: Vec::new(),
+            over: None,
         })
     }
 
@@ -368,6 +615,7 @@ impl ExprRPNItem {
             unary_operator: true,
             binary_operator: false,
             parameters: Vec::new(),
+            over: None,
         })
     }
 }
@@ -411,6 +659,12 @@ impl ExprRPNBuilder {
                 high,
             } => self.visit_between(expr, negated, low, high),
             Expr::Tuple(exprs) => self.visit_tuple(exprs),
+            Expr::MapAccess { column, keys } => self.visit_map_access(column, keys),
+            Expr::InList {
+                expr,
+                list,
+                negated,
+            } => self.visit_in_list(expr, list, negated),
             other => Result::Err(ErrorCode::SyntaxException(format!(
                 "Unsupported expression: {}, type: {:?}",
                 expr, other
@@ -418,6 +672,17 @@ impl ExprRPNBuilder {
         }
     }
 
+    // map['a']['b'] => element_at(element_at(map, 'a'), 'b'), and arr[1] => element_at(arr, 1)
+    fn visit_map_access(&mut self, column: &Expr, keys: &[Value]) -> Result<()> {
+        self.visit(column)?;
+        for key in keys {
+            self.rpn.push(ExprRPNItem::Value(key.clone()));
+            self.rpn
+                .push(ExprRPNItem::function(String::from("element_at"), 2));
+        }
+        Ok(())
+    }
+
     fn visit_tuple(&mut self, exprs: &[Expr]) -> Result<()> {
         match exprs.len() {
             0 => Err(ErrorCode::SyntaxException(
@@ -478,6 +743,11 @@ impl ExprRPNBuilder {
             };
         }
 
+        let over = match &function.over {
+            None => None,
+            Some(over) => Some(self.visit_window_spec(over)?),
+        };
+
         self.rpn.push(ExprRPNItem::Function(FunctionExprInfo {
             name: function.name.to_string(),
             distinct: function.distinct,
@@ -485,10 +755,31 @@ impl ExprRPNBuilder {
             unary_operator: false,
             binary_operator: false,
             parameters: function.params.to_owned(),
+            over,
         }));
         Ok(())
     }
 
+    fn visit_window_spec(&mut self, over: &WindowSpec) -> Result<WindowExprInfo> {
+        for expr in &over.partition_by {
+            self.visit(expr)?;
+        }
+
+        for OrderByExpr { expr, .. } in &over.order_by {
+            self.visit(expr)?;
+        }
+
+        Ok(WindowExprInfo {
+            partition_by_count: over.partition_by.len(),
+            order_by: over
+                .order_by
+                .iter()
+                .map(|order_by| (order_by.asc, order_by.nulls_first))
+                .collect(),
+            frame: over.window_frame.clone(),
+        })
+    }
+
     fn visit_cast(&mut self, expr: &Expr, data_type: &DataType) -> Result<()> {
         self.visit(expr)?;
         self.rpn
@@ -538,6 +829,24 @@ impl ExprRPNBuilder {
         Ok(())
     }
 
+    fn visit_in_list(&mut self, expr: &Expr, list: &[Expr], negated: &bool) -> Result<()> {
+        if list.is_empty() {
+            return Err(ErrorCode::SyntaxException(
+                "IN list must have at least one element.",
+            ));
+        }
+
+        self.visit(expr)?;
+        for item in list {
+            self.visit(item)?;
+        }
+        self.rpn.push(ExprRPNItem::InList(InListInfo {
+            list_size: list.len(),
+            negated: *negated,
+        }));
+        Ok(())
+    }
+
     fn visit_substring(
         &mut self,
         expr: &Expr,
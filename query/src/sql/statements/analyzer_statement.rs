@@ -21,8 +21,10 @@ use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_planners::ExplainType;
 use common_planners::Expression;
+use common_planners::JoinType;
 use common_planners::PlanNode;
 use common_planners::ReadDataSourcePlan;
+use common_planners::SetOperator;
 
 use crate::sessions::QueryContext;
 use crate::sql::DfStatement;
@@ -39,6 +41,31 @@ pub enum QueryRelation {
     None,
     FromTable(Box<ReadDataSourcePlan>),
     Nested(Box<QueryAnalyzeState>),
+    Join(Box<JoinRelation>),
+    SetOperation(Box<SetOperationRelation>),
+    // The name of the materialized CTE and the analyzed CTE.
+    MaterializedCte(String, Box<QueryAnalyzeState>),
+}
+
+#[derive(Clone)]
+pub struct JoinRelation {
+    pub join_type: JoinType,
+    pub left: QueryRelation,
+    pub right: QueryRelation,
+    // Rename the ambiguous columns of the tables to their full names, empty if not required.
+    pub left_projection: Vec<Expression>,
+    pub right_projection: Vec<Expression>,
+    pub left_keys: Vec<Expression>,
+    pub right_keys: Vec<Expression>,
+    pub other_conditions: Option<Expression>,
+}
+
+#[derive(Clone)]
+pub struct SetOperationRelation {
+    pub op: SetOperator,
+    pub all: bool,
+    pub left: Box<QueryAnalyzeState>,
+    pub right: Box<QueryAnalyzeState>,
 }
 
 #[derive(Clone)]
@@ -50,10 +77,21 @@ pub struct QueryAnalyzeState {
     pub expressions: Vec<Expression>,
     pub projection_expressions: Vec<Expression>,
 
+    // The arrayJoin functions are evaluated after filter, the before array join expressions
+    // are their arguments. The filter of the elements is applied after array join.
+    pub array_join_expressions: Vec<Expression>,
+    pub before_array_join_expressions: Vec<Expression>,
+    pub array_join_filter: Option<Expression>,
+
     pub group_by_expressions: Vec<Expression>,
     pub aggregate_expressions: Vec<Expression>,
     pub before_group_by_expressions: Vec<Expression>,
 
+    // The window functions are evaluated after having, the before window expressions are the
+    // arguments, PARTITION BY and ORDER BY expressions of the window functions.
+    pub window_expressions: Vec<Expression>,
+    pub before_window_expressions: Vec<Expression>,
+
     pub limit: Option<usize>,
     pub offset: Option<usize>,
 
@@ -68,11 +106,23 @@ impl QueryAnalyzeState {
         }
     }
 
+    pub fn add_before_array_join_expression(&mut self, expr: &Expression) {
+        if !self.before_array_join_expressions.contains(expr) {
+            self.before_array_join_expressions.push(expr.clone());
+        }
+    }
+
     pub fn add_before_group_expression(&mut self, expr: &Expression) {
         if !self.before_group_by_expressions.contains(expr) {
             self.before_group_by_expressions.push(expr.clone());
         }
     }
+
+    pub fn add_before_window_expression(&mut self, expr: &Expression) {
+        if !self.before_window_expressions.contains(expr) {
+            self.before_window_expressions.push(expr.clone());
+        }
+    }
 }
 
 impl Default for QueryAnalyzeState {
@@ -83,9 +133,14 @@ impl Default for QueryAnalyzeState {
             order_by_expressions: vec![],
             expressions: vec![],
             projection_expressions: vec![],
+            array_join_expressions: vec![],
+            before_array_join_expressions: vec![],
+            array_join_filter: None,
             group_by_expressions: vec![],
             aggregate_expressions: vec![],
             before_group_by_expressions: vec![],
+            window_expressions: vec![],
+            before_window_expressions: vec![],
             limit: None,
             offset: None,
             relation: QueryRelation::None,
@@ -102,6 +157,18 @@ impl Debug for QueryAnalyzeState {
             debug_struct.field("filter", predicate);
         }
 
+        if !self.before_array_join_expressions.is_empty() {
+            debug_struct.field("before_array_join", &self.before_array_join_expressions);
+        }
+
+        if !self.array_join_expressions.is_empty() {
+            debug_struct.field("array_join", &self.array_join_expressions);
+        }
+
+        if let Some(predicate) = &self.array_join_filter {
+            debug_struct.field("array_join_filter", predicate);
+        }
+
         if !self.before_group_by_expressions.is_empty() {
             debug_struct.field("before_group_by", &self.before_group_by_expressions);
         }
@@ -114,6 +181,14 @@ impl Debug for QueryAnalyzeState {
             debug_struct.field("aggregate", &self.aggregate_expressions);
         }
 
+        if !self.before_window_expressions.is_empty() {
+            debug_struct.field("before_window", &self.before_window_expressions);
+        }
+
+        if !self.window_expressions.is_empty() {
+            debug_struct.field("window", &self.window_expressions);
+        }
+
         if !self.expressions.is_empty() {
             match self.order_by_expressions.is_empty() {
                 true => debug_struct.field("before_projection", &self.expressions),
@@ -155,6 +230,8 @@ impl AnalyzableStatement for DfStatement {
             DfStatement::DescribeTable(v) => v.analyze(ctx).await,
             DfStatement::DropTable(v) => v.analyze(ctx).await,
             DfStatement::TruncateTable(v) => v.analyze(ctx).await,
+            DfStatement::OptimizeTable(v) => v.analyze(ctx).await,
+            DfStatement::VacuumTable(v) => v.analyze(ctx).await,
             DfStatement::UseDatabase(v) => v.analyze(ctx).await,
             DfStatement::ShowCreateTable(v) => v.analyze(ctx).await,
             DfStatement::ShowTables(v) => v.analyze(ctx).await,
@@ -163,6 +240,8 @@ impl AnalyzableStatement for DfStatement {
             DfStatement::ShowMetrics(v) => v.analyze(ctx).await,
             DfStatement::KillStatement(v) => v.analyze(ctx).await,
             DfStatement::InsertQuery(v) => v.analyze(ctx).await,
+            DfStatement::Delete(v) => v.analyze(ctx).await,
+            DfStatement::Update(v) => v.analyze(ctx).await,
             DfStatement::SetVariable(v) => v.analyze(ctx).await,
             DfStatement::CreateUser(v) => v.analyze(ctx).await,
             DfStatement::AlterUser(v) => v.analyze(ctx).await,
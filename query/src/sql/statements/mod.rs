@@ -25,6 +25,7 @@ mod statement_copy;
 mod statement_create_database;
 mod statement_create_table;
 mod statement_create_user;
+mod statement_delete;
 mod statement_describe_table;
 mod statement_drop_database;
 mod statement_drop_table;
@@ -33,6 +34,7 @@ mod statement_explain;
 mod statement_grant;
 mod statement_insert;
 mod statement_kill;
+mod statement_optimize_table;
 mod statement_select;
 mod statement_select_convert;
 mod statement_set_variable;
@@ -44,18 +46,24 @@ mod statement_show_settings;
 mod statement_show_tables;
 mod statement_show_users;
 mod statement_truncate_table;
+mod statement_update;
 mod statement_use_database;
+mod statement_vacuum_table;
 
+pub use analyzer_expr::ExpressionAnalyzer;
 pub use analyzer_statement::AnalyzableStatement;
 pub use analyzer_statement::AnalyzedResult;
+pub use analyzer_statement::JoinRelation;
 pub use analyzer_statement::QueryAnalyzeState;
 pub use analyzer_statement::QueryRelation;
+pub use analyzer_statement::SetOperationRelation;
 pub use query::QueryASTIR;
 pub use statement_alter_user::DfAlterUser;
 pub use statement_copy::DfCopy;
 pub use statement_create_database::DfCreateDatabase;
 pub use statement_create_table::DfCreateTable;
 pub use statement_create_user::DfCreateUser;
+pub use statement_delete::DfDeleteStatement;
 pub use statement_describe_table::DfDescribeTable;
 pub use statement_drop_database::DfDropDatabase;
 pub use statement_drop_table::DfDropTable;
@@ -65,6 +73,7 @@ pub use statement_grant::DfGrantObject;
 pub use statement_grant::DfGrantStatement;
 pub use statement_insert::DfInsertStatement;
 pub use statement_kill::DfKillStatement;
+pub use statement_optimize_table::DfOptimizeTable;
 pub use statement_select::DfQueryStatement;
 pub use statement_set_variable::DfSetVariable;
 pub use statement_show_create_table::DfShowCreateTable;
@@ -75,4 +84,6 @@ pub use statement_show_settings::DfShowSettings;
 pub use statement_show_tables::DfShowTables;
 pub use statement_show_users::DfShowUsers;
 pub use statement_truncate_table::DfTruncateTable;
+pub use statement_update::DfUpdateStatement;
 pub use statement_use_database::DfUseDatabase;
+pub use statement_vacuum_table::DfVacuumTable;
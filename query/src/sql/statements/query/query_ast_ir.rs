@@ -21,6 +21,7 @@ use common_planners::Expression;
 // Intermediate representation for query AST(after normalize)
 pub struct QueryASTIR {
     pub filter_predicate: Option<Expression>,
+    pub array_join_expressions: Vec<Expression>,
     pub group_by_expressions: Vec<Expression>,
     pub having_predicate: Option<Expression>,
     pub aggregate_expressions: Vec<Expression>,
@@ -40,6 +41,7 @@ pub trait QueryASTIRVisitor<Data> {
             Self::visit_having(predicate, data)?;
         }
 
+        Self::visit_array_join(&mut ir.array_join_expressions, data)?;
         Self::visit_group_by(&mut ir.group_by_expressions, data)?;
         Self::visit_order_by(&mut ir.order_by_expressions, data)?;
         Self::visit_aggregates(&mut ir.aggregate_expressions, data)?;
@@ -73,6 +75,18 @@ pub trait QueryASTIRVisitor<Data> {
 
                 Ok(())
             }
+            Expression::WindowFunction {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                for arg in args.iter_mut().chain(partition_by).chain(order_by) {
+                    Self::visit_recursive_expr(arg, data)?;
+                }
+
+                Ok(())
+            }
             Expression::Sort {
                 expr, origin_expr, ..
             } => {
@@ -92,6 +106,14 @@ pub trait QueryASTIRVisitor<Data> {
         Self::visit_recursive_expr(predicate, data)
     }
 
+    fn visit_array_join(exprs: &mut Vec<Expression>, data: &mut Data) -> Result<()> {
+        for expr in exprs {
+            Self::visit_recursive_expr(expr, data)?;
+        }
+
+        Ok(())
+    }
+
     fn visit_group_by(exprs: &mut Vec<Expression>, data: &mut Data) -> Result<()> {
         for expr in exprs {
             Self::visit_recursive_expr(expr, data)?;
@@ -133,6 +155,10 @@ impl Debug for QueryASTIR {
             debug_struct.field("filter", predicate);
         }
 
+        if !self.array_join_expressions.is_empty() {
+            debug_struct.field("array join", &self.array_join_expressions);
+        }
+
         if !self.group_by_expressions.is_empty() {
             debug_struct.field("group by", &self.group_by_expressions);
         }
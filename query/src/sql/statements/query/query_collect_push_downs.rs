@@ -48,6 +48,17 @@ impl QueryCollectPushDowns {
             require_columns: HashSet::new(),
         };
         QueryCollectPushDowns::visit(ir, &mut push_downs_data)?;
+
+        for join_desc in schema.get_joins() {
+            let keys = join_desc.left_keys.iter().chain(&join_desc.right_keys);
+            for expr in keys.chain(&join_desc.other_conditions) {
+                QueryCollectPushDowns::visit_recursive_expr(
+                    &mut expr.clone(),
+                    &mut push_downs_data,
+                )?;
+            }
+        }
+
         push_downs_data.collect_push_downs(schema)
     }
 
@@ -71,7 +82,13 @@ impl QueryCollectPushDowns {
     fn collect_table_require_columns(&mut self, table_desc: &JoinedTableDesc) -> Vec<usize> {
         match self.require_columns.is_empty() {
             true => Self::collect_table_smallest_column(table_desc),
-            false => self.collect_table_projection_columns(table_desc),
+            false => match self.collect_table_projection_columns(table_desc) {
+                // No column of the table is required, e.g. SELECT t1.a FROM t1, t2.
+                projection if projection.is_empty() => {
+                    Self::collect_table_smallest_column(table_desc)
+                }
+                projection => projection,
+            },
         }
     }
 
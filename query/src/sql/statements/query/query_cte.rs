@@ -0,0 +1,200 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::slice;
+
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::col;
+use sqlparser::ast::ObjectName;
+use sqlparser::ast::Query;
+use sqlparser::ast::SetExpr;
+use sqlparser::ast::TableAlias;
+use sqlparser::ast::TableFactor;
+use sqlparser::ast::TableWithJoins;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::DfQueryStatement;
+use crate::sql::statements::QueryAnalyzeState;
+use crate::sql::statements::QueryRelation;
+
+/// A common table expression visible to a query.
+///
+/// The CTEs are inlined into their references. If `materialize_cte` is enabled, a CTE referenced
+/// more than once is materialized: the references read the result of it by the same name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommonTableExpr {
+    pub alias: TableAlias,
+    pub query: Query,
+    pub materialized_name: Option<String>,
+}
+
+impl CommonTableExpr {
+    /// The CTEs visible to the query, the CTEs of the outer queries are followed by the ones of
+    /// the WITH clause. A CTE can refer to the ones before it, and shadows them of the same name.
+    pub fn resolve(ctx: &QueryContext, query: &DfQueryStatement) -> Result<Vec<CommonTableExpr>> {
+        let materialize = ctx.get_settings().get_materialize_cte()? != 0;
+        let mut ctes = query.outer_ctes.clone();
+
+        for (index, cte) in query.ctes.iter().enumerate() {
+            let name = &cte.alias.name.value;
+            if query.ctes[..index]
+                .iter()
+                .any(|v| &v.alias.name.value == name)
+            {
+                return Err(ErrorCode::SyntaxException(format!(
+                    "WITH query name {} specified more than once",
+                    name
+                )));
+            }
+
+            let materialized_name = match materialize && Self::references(query, index) > 1 {
+                true => Some(ctx.get_materialized_cte_name(name)),
+                false => None,
+            };
+
+            ctes.push(CommonTableExpr {
+                alias: cte.alias.clone(),
+                query: cte.query.clone(),
+                materialized_name,
+            });
+        }
+
+        Ok(ctes)
+    }
+
+    /// The position of the CTE referenced by the table name, the last one wins.
+    pub fn find(ctes: &[CommonTableExpr], name: &ObjectName) -> Option<usize> {
+        match name.0.as_slice() {
+            [name] => ctes
+                .iter()
+                .rposition(|cte| cte.alias.name.value == name.value),
+            _ => None,
+        }
+    }
+
+    /// Wrap the analyzed CTE for a reference, the columns are renamed by the column aliases
+    /// of the CTE, and the materialized CTE is read by its name.
+    pub fn reference_state(&self, state: Box<QueryAnalyzeState>) -> Result<Box<QueryAnalyzeState>> {
+        let columns = &self.alias.columns;
+        let schema = state.finalize_schema.clone();
+        if !columns.is_empty() && columns.len() != schema.fields().len() {
+            return Err(ErrorCode::BadArguments(format!(
+                "WITH query {} has {} columns available but {} columns specified",
+                self.alias.name.value,
+                schema.fields().len(),
+                columns.len()
+            )));
+        }
+
+        if columns.is_empty() && self.materialized_name.is_none() {
+            return Ok(state);
+        }
+
+        let mut fields = Vec::with_capacity(schema.fields().len());
+        let mut projection_expressions = Vec::with_capacity(schema.fields().len());
+        for (index, field) in schema.fields().iter().enumerate() {
+            match columns.get(index) {
+                None => {
+                    fields.push(field.clone());
+                    projection_expressions.push(col(field.name()));
+                }
+                Some(column) => {
+                    let name = &column.value;
+                    let data_type = field.data_type().clone();
+                    fields.push(DataField::new(name, data_type, field.is_nullable()));
+                    projection_expressions.push(col(field.name()).alias(name));
+                }
+            }
+        }
+
+        let relation = match &self.materialized_name {
+            None => QueryRelation::Nested(state),
+            Some(name) => QueryRelation::MaterializedCte(name.clone(), state),
+        };
+
+        Ok(Box::new(QueryAnalyzeState {
+            projection_expressions,
+            relation,
+            finalize_schema: DataSchemaRefExt::create(fields),
+            ..Default::default()
+        }))
+    }
+
+    // The references to the CTE in the FROM clauses of the query and the CTEs after it.
+    fn references(query: &DfQueryStatement, index: usize) -> usize {
+        let name = &query.ctes[index].alias.name.value;
+        let in_ctes = query.ctes[index + 1..]
+            .iter()
+            .map(|cte| Self::references_in_query(&cte.query, name))
+            .sum::<usize>();
+
+        in_ctes + Self::references_in_tables(&query.from, name)
+    }
+
+    fn references_in_query(query: &Query, name: &str) -> usize {
+        let mut references = 0;
+        if let Some(with) = &query.with {
+            for cte in &with.cte_tables {
+                // The CTE is shadowed in the rest of the query.
+                if cte.alias.name.value == name {
+                    return references;
+                }
+
+                references += Self::references_in_query(&cte.query, name);
+            }
+        }
+
+        references + Self::references_in_set_expr(&query.body, name)
+    }
+
+    fn references_in_set_expr(body: &SetExpr, name: &str) -> usize {
+        match body {
+            SetExpr::Select(select) => Self::references_in_tables(&select.from, name),
+            SetExpr::Query(query) => Self::references_in_query(query, name),
+            SetExpr::SetOperation { left, right, .. } => {
+                Self::references_in_set_expr(left, name) + Self::references_in_set_expr(right, name)
+            }
+            _ => 0,
+        }
+    }
+
+    fn references_in_tables(tables: &[TableWithJoins], name: &str) -> usize {
+        let mut references = 0;
+        for table in tables {
+            references += Self::references_in_table(&table.relation, name);
+            for join in &table.joins {
+                references += Self::references_in_table(&join.relation, name);
+            }
+        }
+
+        references
+    }
+
+    fn references_in_table(table: &TableFactor, name: &str) -> usize {
+        match table {
+            TableFactor::Table { name: table, .. } => match table.0.as_slice() {
+                [table] if table.value == name => 1,
+                _ => 0,
+            },
+            TableFactor::Derived { subquery, .. } => Self::references_in_query(subquery, name),
+            TableFactor::NestedJoin(joins) => {
+                Self::references_in_tables(slice::from_ref(joins.as_ref()), name)
+            }
+            _ => 0,
+        }
+    }
+}
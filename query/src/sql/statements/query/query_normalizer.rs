@@ -27,6 +27,7 @@ use sqlparser::ast::SelectItem;
 
 use crate::sessions::QueryContext;
 use crate::sql::statements::analyzer_expr::ExpressionAnalyzer;
+use crate::sql::statements::query::CommonTableExpr;
 use crate::sql::statements::query::QueryASTIR;
 use crate::sql::statements::DfQueryStatement;
 
@@ -38,12 +39,13 @@ pub struct QueryNormalizer {
 
 /// Replace alias in query and collect aggregate functions
 impl QueryNormalizer {
-    fn create(ctx: Arc<QueryContext>) -> QueryNormalizer {
+    fn create(ctx: Arc<QueryContext>, ctes: Vec<CommonTableExpr>) -> QueryNormalizer {
         QueryNormalizer {
-            expression_analyzer: ExpressionAnalyzer::create(ctx),
+            expression_analyzer: ExpressionAnalyzer::create_with_ctes(ctx, ctes),
             aliases_map: HashMap::new(),
             query_ast_ir: QueryASTIR {
                 filter_predicate: None,
+                array_join_expressions: vec![],
                 group_by_expressions: vec![],
                 having_predicate: None,
                 aggregate_expressions: vec![],
@@ -55,12 +57,20 @@ impl QueryNormalizer {
         }
     }
 
-    pub async fn normalize(ctx: Arc<QueryContext>, v: &DfQueryStatement) -> Result<QueryASTIR> {
-        let query_normalizer = QueryNormalizer::create(ctx);
+    pub async fn normalize(
+        ctx: Arc<QueryContext>,
+        v: &DfQueryStatement,
+        ctes: Vec<CommonTableExpr>,
+    ) -> Result<QueryASTIR> {
+        let query_normalizer = QueryNormalizer::create(ctx, ctes);
         query_normalizer.transform(v).await
     }
 
     pub async fn transform(mut self, query: &DfQueryStatement) -> Result<QueryASTIR> {
+        if let Err(cause) = self.analyze_array_join(query).await {
+            return Err(cause.add_message_back(" (while in analyze select array join)"));
+        }
+
         if let Err(cause) = self.visit_filter(query).await {
             return Err(cause.add_message_back(" (while in analyze select filter)"));
         }
@@ -88,10 +98,48 @@ impl QueryNormalizer {
         Ok(self.query_ast_ir)
     }
 
+    // The elements of the arrays are named by the aliases of the ARRAY JOIN clause, or by the
+    // names of the array columns without alias, e.g. `ARRAY JOIN tags`.
+    async fn analyze_array_join(&mut self, query: &DfQueryStatement) -> Result<()> {
+        for item in &query.array_join {
+            let (expr, alias) = match item {
+                SelectItem::UnnamedExpr(expr) => (expr, None),
+                SelectItem::ExprWithAlias { expr, alias } => (expr, Some(alias.value.clone())),
+                _ => {
+                    return Err(ErrorCode::SyntaxException(format!(
+                        "ARRAY JOIN item: {:?} are not supported",
+                        item
+                    )));
+                }
+            };
+
+            let arg = self.expression_analyzer.analyze(expr).await?;
+            let name = match (alias, &arg) {
+                (Some(alias), _) => Some(alias),
+                (None, Expression::Column(name)) => Some(name.clone()),
+                (None, _) => None,
+            };
+
+            let array_join = Expression::ScalarFunction {
+                op: String::from("arrayJoin"),
+                args: vec![arg],
+            };
+
+            if let Some(name) = name {
+                self.aliases_map.insert(name, array_join.clone());
+            }
+
+            self.query_ast_ir.array_join_expressions.push(array_join);
+        }
+
+        Ok(())
+    }
+
     async fn visit_filter(&mut self, query: &DfQueryStatement) -> Result<()> {
         if let Some(predicate) = &query.selection {
-            let analyzer = &self.expression_analyzer;
-            self.query_ast_ir.filter_predicate = Some(analyzer.analyze(predicate).await?);
+            // Only the names of the ARRAY JOIN clause are resolved, the aliases of the
+            // projection are not visible in filter.
+            self.query_ast_ir.filter_predicate = Some(self.resolve_aliases(predicate).await?);
         }
 
         Ok(())
@@ -99,7 +147,8 @@ impl QueryNormalizer {
 
     async fn analyze_projection(&mut self, query: &DfQueryStatement) -> Result<()> {
         let projection_expressions = self.projection_exprs(query).await?;
-        self.aliases_map = extract_aliases(&projection_expressions);
+        self.aliases_map
+            .extend(extract_aliases(&projection_expressions));
 
         for projection_expression in &projection_expressions {
             self.add_aggregate_function(projection_expression)?;
@@ -187,13 +236,21 @@ impl QueryNormalizer {
                 SelectItem::Wildcard => {
                     output_columns.push(Expression::Wildcard);
                 }
-                SelectItem::UnnamedExpr(expr) => {
-                    output_columns.push(expr_analyzer.analyze(expr).await?);
-                }
+                SelectItem::UnnamedExpr(expr) => match expr_analyzer.analyze(expr).await? {
+                    // Keep the names of the elements of the ARRAY JOIN clause.
+                    Expression::Column(name) if self.aliases_map.contains_key(&name) => {
+                        let element = Box::new(self.aliases_map[&name].clone());
+                        output_columns.push(Expression::Alias(name, element));
+                    }
+                    expression => {
+                        let expression = resolve_aliases_to_exprs(&expression, &self.aliases_map)?;
+                        output_columns.push(expression);
+                    }
+                },
                 SelectItem::ExprWithAlias { expr, alias } => {
                     let expr_alias = alias.value.clone();
-                    let expr = Box::new(expr_analyzer.analyze(expr).await?);
-                    output_columns.push(Expression::Alias(expr_alias, expr));
+                    let expr = self.resolve_aliases(expr).await?;
+                    output_columns.push(Expression::Alias(expr_alias, Box::new(expr)));
                 }
                 _ => {
                     return Err(ErrorCode::SyntaxException(format!(
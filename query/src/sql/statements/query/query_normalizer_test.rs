@@ -115,6 +115,11 @@ async fn test_query_normalizer() -> Result<()> {
             query: "SELECT SUM(number) AS number1 FROM numbers(100) GROUP BY number ORDER BY number1",
             expect: "NormalQuery { group by: [number], aggregate: [SUM(number)], order by: [SUM(number)], projection: [SUM(number) as number1] }",
         },
+        TestCase {
+            name: "Array join alias query",
+            query: "SELECT tag, number FROM numbers(100) ARRAY JOIN [number] AS tag WHERE tag > 1 ORDER BY tag",
+            expect: "NormalQuery { filter: (arrayJoin(array(number)) > 1), array join: [arrayJoin(array(number))], order by: [arrayJoin(array(number))], projection: [arrayJoin(array(number)) as tag, number] }",
+        },
     ];
 
     for test_case in &tests {
@@ -123,7 +128,7 @@ async fn test_query_normalizer() -> Result<()> {
 
         match statements.remove(0) {
             DfStatement::Query(query) => {
-                let ir = QueryNormalizer::normalize(ctx, &query).await?;
+                let ir = QueryNormalizer::normalize(ctx, &query, vec![]).await?;
                 assert_eq!(
                     test_case.expect,
                     format!("{:?}", ir),
@@ -14,8 +14,10 @@
 
 use std::sync::Arc;
 
+use common_datavalues::DataType;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::lit;
 use common_planners::Expression;
 
 use crate::sessions::QueryContext;
@@ -82,8 +84,20 @@ impl QualifiedRewriter {
         QualifiedRewriter::visit(ir, &mut rewriter)
     }
 
+    pub fn rewrite_expr(
+        schema: &JoinedSchema,
+        ctx: Arc<QueryContext>,
+        expr: &mut Expression,
+    ) -> Result<()> {
+        let mut rewriter = QualifiedRewriter {
+            tables_schema: schema.clone(),
+            ctx,
+        };
+        QualifiedRewriter::visit_recursive_expr(expr, &mut rewriter)
+    }
+
     fn expand_wildcard(&self, columns_expression: &mut Vec<Expression>) {
-        for table_desc in self.tables_schema.get_tables_desc() {
+        for table_desc in self.tables_schema.get_visible_tables_desc() {
             for column_desc in table_desc.get_columns_desc() {
                 let name = column_desc.short_name.clone();
                 match column_desc.is_ambiguity {
@@ -106,23 +120,68 @@ impl QualifiedRewriter {
 
     fn rewrite_qualified_column(&self, ref_names: &[String]) -> Result<Expression> {
         match self.best_match_table(ref_names) {
-            None => Err(ErrorCode::UnknownColumn(format!(
-                "Unknown column {}",
-                ref_names.join(".")
-            ))),
+            None => match self.find_variant_column(&ref_names[0]) {
+                Some(table_ref) if ref_names.len() > 1 => Self::get_path(&table_ref, ref_names),
+                _ => Err(ErrorCode::UnknownColumn(format!(
+                    "Unknown column {}",
+                    ref_names.join(".")
+                ))),
+            },
             Some((pos, table_ref)) => {
                 let column_name = &ref_names[pos..];
                 match column_name.len() {
                     1 => Self::find_column(&table_ref, &column_name[0]),
-                    // TODO: column.field_a.field_b => GetField(field_b, GetField(field_a, column))
-                    _ => Err(ErrorCode::SyntaxException(
-                        "Unsupported complex type field access",
-                    )),
+                    _ => Self::get_path(&table_ref, column_name),
                 }
             }
         }
     }
 
+    fn find_variant_column(&self, name: &str) -> Option<JoinedTableDesc> {
+        self.tables_schema
+            .get_visible_tables_desc()
+            .find(|table_desc| {
+                table_desc.get_columns_desc().iter().any(|column_desc| {
+                    column_desc.short_name == name && column_desc.data_type == DataType::Variant
+                })
+            })
+            .cloned()
+    }
+
+    // variant_column.field_a.field_b => get_path(variant_column, 'field_a.field_b')
+    fn get_path(table_desc: &JoinedTableDesc, names: &[String]) -> Result<Expression> {
+        let column_desc = table_desc
+            .get_columns_desc()
+            .iter()
+            .find(|column_desc| column_desc.short_name == names[0]);
+
+        match column_desc {
+            Some(column_desc) if column_desc.data_type == DataType::Variant => {
+                let mut path = String::new();
+                for name in &names[1..] {
+                    match name.contains(|c| c == '.' || c == '[') {
+                        true => path.push_str(&format!("[\"{}\"]", name)),
+                        false if path.is_empty() => path.push_str(name),
+                        false => path.push_str(&format!(".{}", name)),
+                    }
+                }
+
+                Ok(Expression::ScalarFunction {
+                    op: "get_path".to_string(),
+                    args: vec![
+                        Self::find_column(table_desc, &names[0])?,
+                        lit(path.into_bytes()),
+                    ],
+                })
+            }
+            Some(_) => Err(ErrorCode::SyntaxException(format!(
+                "Field access is only supported on Variant columns, but got {}",
+                names.join(".")
+            ))),
+            None => Self::find_column(table_desc, &names[0]),
+        }
+    }
+
     fn find_column(table_desc: &JoinedTableDesc, name: &str) -> Result<Expression> {
         let name_parts = table_desc.get_name_parts();
         for column_desc in table_desc.get_columns_desc() {
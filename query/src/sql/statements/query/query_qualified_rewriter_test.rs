@@ -105,10 +105,10 @@ async fn test_query_qualified_rewriter() -> Result<()> {
 
         match statements.remove(0) {
             DfStatement::Query(query) => {
-                let analyzer = JoinedSchemaAnalyzer::create(ctx.clone());
+                let analyzer = JoinedSchemaAnalyzer::create(ctx.clone(), vec![]);
                 let schema = analyzer.analyze(&query).await?;
 
-                let mut ir = QueryNormalizer::normalize(ctx.clone(), &query).await?;
+                let mut ir = QueryNormalizer::normalize(ctx.clone(), &query, vec![]).await?;
                 QualifiedRewriter::rewrite(&schema, ctx, &mut ir)?;
 
                 assert_eq!(
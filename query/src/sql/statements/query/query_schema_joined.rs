@@ -14,8 +14,10 @@
 
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::ops::Range;
 use std::sync::Arc;
 
 use common_datavalues::DataField;
@@ -24,7 +26,11 @@ use common_datavalues::DataSchemaRef;
 use common_datavalues::DataType;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::Expression;
 use common_planners::Extras;
+use common_planners::JoinType;
+use common_planners::RewriteHelper;
+use sqlparser::ast::JoinConstraint;
 
 use crate::catalogs::Table;
 use crate::sql::statements::QueryAnalyzeState;
@@ -35,6 +41,11 @@ pub struct JoinedSchema {
     short_name_columns: HashMap<String, JoinedColumnDesc>,
     // Reference by full name, short name may be ambiguous.
     tables_long_name_columns: Vec<JoinedTableDesc>,
+    // Joins of the tables, in the order they are applied(post-order of the join tree).
+    joins: Vec<JoinedDesc>,
+    // Positions of the tables joined for the decorrelated subqueries, they are not expanded
+    // by the wildcard.
+    hidden_tables: Vec<usize>,
 }
 
 impl JoinedSchema {
@@ -42,6 +53,8 @@ impl JoinedSchema {
         JoinedSchema {
             short_name_columns: HashMap::new(),
             tables_long_name_columns: Vec::new(),
+            joins: Vec::new(),
+            hidden_tables: Vec::new(),
         }
     }
 
@@ -78,6 +91,8 @@ impl JoinedSchema {
         Ok(JoinedSchema {
             short_name_columns,
             tables_long_name_columns: vec![table_desc],
+            joins: Vec::new(),
+            hidden_tables: Vec::new(),
         })
     }
 
@@ -89,6 +104,19 @@ impl JoinedSchema {
         &self.tables_long_name_columns
     }
 
+    /// The tables expanded by the wildcard.
+    pub fn get_visible_tables_desc(&self) -> impl Iterator<Item = &JoinedTableDesc> {
+        self.tables_long_name_columns
+            .iter()
+            .enumerate()
+            .filter(|(pos, _)| !self.hidden_tables.contains(pos))
+            .map(|(_, table_desc)| table_desc)
+    }
+
+    pub fn hide_table(&mut self, table_pos: usize) {
+        self.hidden_tables.push(table_pos);
+    }
+
     pub fn set_table_push_downs(&mut self, table_pos: usize, extras: Extras) {
         let table_desc = &mut self.tables_long_name_columns[table_pos];
         if let JoinedTableDesc::Table { push_downs, .. } = table_desc {
@@ -100,6 +128,10 @@ impl JoinedSchema {
         self.tables_long_name_columns
     }
 
+    pub fn get_joins(&self) -> &[JoinedDesc] {
+        &self.joins
+    }
+
     pub fn to_data_schema(&self) -> DataSchemaRef {
         let mut fields = Vec::with_capacity(self.short_name_columns.len());
 
@@ -129,8 +161,187 @@ impl JoinedSchema {
         Arc::new(DataSchema::new(fields))
     }
 
-    pub fn join(&self, _joined_schema: &JoinedSchema) -> Result<Arc<JoinedSchema>> {
-        unimplemented!("")
+    pub fn join(
+        &self,
+        right: &JoinedSchema,
+        join_type: JoinType,
+        constraint: JoinConstraint,
+    ) -> Result<JoinedSchema> {
+        let offset = self.tables_long_name_columns.len();
+        let mut tables_desc = self.tables_long_name_columns.clone();
+        tables_desc.extend(right.tables_long_name_columns.iter().cloned());
+
+        // The columns of the outer side are filled with NULL if they are unmatched.
+        if join_type.keeps_unmatched_right() {
+            tables_desc[..offset]
+                .iter_mut()
+                .for_each(JoinedTableDesc::set_nullable);
+        }
+
+        if join_type.keeps_unmatched_left() {
+            tables_desc[offset..]
+                .iter_mut()
+                .for_each(JoinedTableDesc::set_nullable);
+        }
+
+        let mut hidden_tables = self.hidden_tables.clone();
+        hidden_tables.extend(right.hidden_tables.iter().map(|pos| pos + offset));
+
+        let mut joins = self.joins.clone();
+        for join_desc in &right.joins {
+            let mut join_desc = join_desc.clone();
+            join_desc.left_tables = Self::shift(&join_desc.left_tables, offset);
+            join_desc.right_tables = Self::shift(&join_desc.right_tables, offset);
+            joins.push(join_desc);
+        }
+
+        joins.push(JoinedDesc {
+            join_type,
+            constraint,
+            left_tables: 0..offset,
+            right_tables: offset..tables_desc.len(),
+            left_keys: vec![],
+            right_keys: vec![],
+            other_conditions: None,
+        });
+
+        Self::from_joined_tables_desc(tables_desc, joins, hidden_tables)
+    }
+
+    fn shift(tables: &Range<usize>, offset: usize) -> Range<usize> {
+        tables.start + offset..tables.end + offset
+    }
+
+    fn from_joined_tables_desc(
+        mut tables_desc: Vec<JoinedTableDesc>,
+        joins: Vec<JoinedDesc>,
+        hidden_tables: Vec<usize>,
+    ) -> Result<JoinedSchema> {
+        let mut short_names_count = HashMap::new();
+        for table_desc in &tables_desc {
+            for column_desc in table_desc.get_columns_desc() {
+                *short_names_count
+                    .entry(column_desc.short_name.clone())
+                    .or_insert(0) += 1;
+            }
+        }
+
+        let mut short_name_columns = HashMap::new();
+        let mut long_names = HashSet::new();
+        for table_desc in &mut tables_desc {
+            let name_parts = table_desc.get_name_parts().to_vec();
+            for column_desc in table_desc.get_columns_desc_mut() {
+                column_desc.is_ambiguity = short_names_count[&column_desc.short_name] > 1;
+
+                if !column_desc.is_ambiguity {
+                    short_name_columns.insert(column_desc.short_name.clone(), column_desc.clone());
+                    continue;
+                }
+
+                if name_parts.is_empty() {
+                    return Err(ErrorCode::SyntaxException(format!(
+                        "Column {} is ambiguous, the subquery of join must have an alias",
+                        column_desc.short_name
+                    )));
+                }
+
+                let long_name = format!("{}.{}", name_parts.join("."), column_desc.short_name);
+                if !long_names.insert(long_name.clone()) {
+                    return Err(ErrorCode::SyntaxException(format!(
+                        "Column {} is ambiguous, the tables of join must have different aliases",
+                        long_name
+                    )));
+                }
+            }
+        }
+
+        Ok(JoinedSchema {
+            short_name_columns,
+            tables_long_name_columns: tables_desc,
+            joins,
+            hidden_tables,
+        })
+    }
+
+    /// Find the column named `name` in the tables of one side of the join, for `USING (name)`.
+    pub fn get_join_column(&self, tables: &Range<usize>, name: &str) -> Result<Expression> {
+        let mut columns = Vec::new();
+        for table_desc in &self.tables_long_name_columns[tables.clone()] {
+            for column_desc in table_desc.get_columns_desc() {
+                if column_desc.short_name == name {
+                    columns.push(table_desc.get_column_name(column_desc));
+                }
+            }
+        }
+
+        match columns.len() {
+            1 => Ok(Expression::Column(columns.remove(0))),
+            0 => Err(ErrorCode::UnknownColumn(format!(
+                "Unknown column {} in USING clause",
+                name
+            ))),
+            _ => Err(ErrorCode::SyntaxException(format!(
+                "Column {} in USING clause is ambiguous",
+                name
+            ))),
+        }
+    }
+
+    /// Set the analyzed conditions of the join, the conjunctions of the form
+    /// `left_side_expr = right_side_expr` are the keys of hash join, the others are the
+    /// residual conditions which are evaluated against the matched rows.
+    pub fn set_join_conditions(
+        &mut self,
+        join_pos: usize,
+        conditions: Vec<Expression>,
+    ) -> Result<()> {
+        let left_columns = self.tables_columns_name(&self.joins[join_pos].left_tables);
+        let right_columns = self.tables_columns_name(&self.joins[join_pos].right_tables);
+        let only_refer = |expr: &Expression, columns: &HashSet<String>| -> Result<bool> {
+            let refer_columns = RewriteHelper::expression_plan_columns(expr)?;
+            Ok(!refer_columns.is_empty()
+                && refer_columns.iter().all(|column| match column {
+                    Expression::Column(name) => columns.contains(name),
+                    _ => false,
+                }))
+        };
+
+        let join_desc = &mut self.joins[join_pos];
+        for condition in conditions {
+            if let Expression::BinaryExpression { op, left, right } = &condition {
+                if op == "=" {
+                    if only_refer(left, &left_columns)? && only_refer(right, &right_columns)? {
+                        join_desc.left_keys.push(left.as_ref().clone());
+                        join_desc.right_keys.push(right.as_ref().clone());
+                        continue;
+                    }
+
+                    if only_refer(left, &right_columns)? && only_refer(right, &left_columns)? {
+                        join_desc.left_keys.push(right.as_ref().clone());
+                        join_desc.right_keys.push(left.as_ref().clone());
+                        continue;
+                    }
+                }
+            }
+
+            join_desc.other_conditions = match join_desc.other_conditions.take() {
+                None => Some(condition),
+                Some(other_conditions) => Some(other_conditions.and(condition)),
+            };
+        }
+
+        Ok(())
+    }
+
+    fn tables_columns_name(&self, tables: &Range<usize>) -> HashSet<String> {
+        let mut columns_name = HashSet::new();
+        for table_desc in &self.tables_long_name_columns[tables.clone()] {
+            for column_desc in table_desc.get_columns_desc() {
+                columns_name.insert(table_desc.get_column_name(column_desc));
+            }
+        }
+
+        columns_name
     }
 }
 
@@ -162,6 +373,10 @@ impl Debug for JoinedSchema {
             debug_struct.field("ambiguity_names", &ambiguity_names);
         }
 
+        if !self.joins.is_empty() {
+            debug_struct.field("joins", &self.joins);
+        }
+
         debug_struct.finish()
     }
 }
@@ -226,6 +441,32 @@ impl JoinedTableDesc {
             JoinedTableDesc::Subquery { columns_desc, .. } => columns_desc,
         }
     }
+
+    fn get_columns_desc_mut(&mut self) -> &mut [JoinedColumnDesc] {
+        match self {
+            JoinedTableDesc::Table { columns_desc, .. } => columns_desc,
+            JoinedTableDesc::Subquery { columns_desc, .. } => columns_desc,
+        }
+    }
+
+    fn set_nullable(&mut self) {
+        for column_desc in self.get_columns_desc_mut() {
+            column_desc.nullable = true;
+        }
+    }
+
+    /// The name of the column in the joined schema, the ambiguous column is named by its
+    /// full name.
+    pub fn get_column_name(&self, column_desc: &JoinedColumnDesc) -> String {
+        match column_desc.is_ambiguity {
+            true => format!(
+                "{}.{}",
+                self.get_name_parts().join("."),
+                column_desc.short_name
+            ),
+            false => column_desc.short_name.clone(),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -255,3 +496,33 @@ impl JoinedColumnDesc {
         }
     }
 }
+
+#[derive(Clone)]
+pub struct JoinedDesc {
+    pub join_type: JoinType,
+    pub constraint: JoinConstraint,
+    // Positions of the tables of both sides in the joined schema.
+    pub left_tables: Range<usize>,
+    pub right_tables: Range<usize>,
+    pub left_keys: Vec<Expression>,
+    pub right_keys: Vec<Expression>,
+    pub other_conditions: Option<Expression>,
+}
+
+impl Debug for JoinedDesc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut debug_struct = f.debug_struct("Join");
+        debug_struct.field("type", &self.join_type.to_string());
+
+        if !self.left_keys.is_empty() {
+            debug_struct.field("left_keys", &self.left_keys);
+            debug_struct.field("right_keys", &self.right_keys);
+        }
+
+        if let Some(conditions) = &self.other_conditions {
+            debug_struct.field("other_conditions", conditions);
+        }
+
+        debug_struct.finish()
+    }
+}
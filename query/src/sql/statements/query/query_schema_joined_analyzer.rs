@@ -14,32 +14,56 @@
 
 use std::sync::Arc;
 
+use common_datablocks::DataBlock;
+use common_datavalues::columns::DataColumn;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::col;
+use common_planners::Expression;
+use common_planners::JoinType;
+use common_planners::SetOperationPlan;
+use common_planners::SetOperator;
+use common_planners::TimeTravelPoint;
+use sqlparser::ast::BinaryOperator;
+use sqlparser::ast::Expr;
 use sqlparser::ast::FunctionArg;
 use sqlparser::ast::Ident;
+use sqlparser::ast::JoinConstraint;
 use sqlparser::ast::JoinOperator;
 use sqlparser::ast::ObjectName;
 use sqlparser::ast::Query;
+use sqlparser::ast::SetExpr;
+use sqlparser::ast::SetOperator as SqlparserSetOperator;
 use sqlparser::ast::TableAlias;
 use sqlparser::ast::TableFactor;
 use sqlparser::ast::TableWithJoins;
 
 use crate::catalogs::Catalog;
+use crate::pipelines::transforms::ExpressionExecutor;
 use crate::sessions::QueryContext;
 use crate::sql::statements::analyzer_expr::ExpressionAnalyzer;
 use crate::sql::statements::query::query_schema_joined::JoinedSchema;
+use crate::sql::statements::query::CommonTableExpr;
+use crate::sql::statements::query::QualifiedRewriter;
 use crate::sql::statements::AnalyzableStatement;
 use crate::sql::statements::AnalyzedResult;
 use crate::sql::statements::DfQueryStatement;
+use crate::sql::statements::QueryAnalyzeState;
+use crate::sql::statements::QueryRelation;
+use crate::sql::statements::SetOperationRelation;
 
 pub struct JoinedSchemaAnalyzer {
     ctx: Arc<QueryContext>,
+    ctes: Vec<CommonTableExpr>,
 }
 
 impl JoinedSchemaAnalyzer {
-    pub fn create(ctx: Arc<QueryContext>) -> JoinedSchemaAnalyzer {
-        JoinedSchemaAnalyzer { ctx }
+    pub fn create(ctx: Arc<QueryContext>, ctes: Vec<CommonTableExpr>) -> JoinedSchemaAnalyzer {
+        JoinedSchemaAnalyzer { ctx, ctes }
     }
 
     pub async fn analyze(&self, query: &DfQueryStatement) -> Result<JoinedSchema> {
@@ -49,8 +73,17 @@ impl JoinedSchemaAnalyzer {
         let rpn = RelationRPNBuilder::build(&query.from)?;
         for rpn_item in &rpn {
             match rpn_item {
-                RelationRPNItem::Join(_) => {
-                    return Err(ErrorCode::UnImplement("Unimplemented SELECT JOIN yet."));
+                RelationRPNItem::Join(join_operator) => {
+                    match (analyzed_tables.pop(), analyzed_tables.pop()) {
+                        (Some(right), Some(left)) => {
+                            analyzed_tables.push(Self::join(&left, &right, join_operator)?);
+                        }
+                        _ => {
+                            return Err(ErrorCode::LogicalError(
+                                "Logical error: this is relation rpn bug.",
+                            ));
+                        }
+                    }
                 }
                 RelationRPNItem::Table(v) => {
                     let schema = self.table(v);
@@ -73,30 +106,209 @@ impl JoinedSchemaAnalyzer {
             ));
         }
 
-        Ok(analyzed_tables.remove(0))
+        let mut joined_schema = analyzed_tables.remove(0);
+        self.analyze_join_conditions(&mut joined_schema).await?;
+        Ok(joined_schema)
+    }
+
+    fn join(
+        left: &JoinedSchema,
+        right: &JoinedSchema,
+        join_operator: &JoinOperator,
+    ) -> Result<JoinedSchema> {
+        let (join_type, constraint) = match join_operator {
+            JoinOperator::Inner(constraint) => (JoinType::Inner, constraint),
+            JoinOperator::LeftOuter(constraint) => (JoinType::Left, constraint),
+            JoinOperator::RightOuter(constraint) => (JoinType::Right, constraint),
+            JoinOperator::FullOuter(constraint) => (JoinType::Full, constraint),
+            JoinOperator::CrossJoin => (JoinType::Inner, &JoinConstraint::None),
+            _ => {
+                return Err(ErrorCode::UnImplement(
+                    "Unimplemented CROSS APPLY and OUTER APPLY yet.",
+                ));
+            }
+        };
+
+        match (join_type, constraint) {
+            (_, JoinConstraint::Natural) => {
+                Err(ErrorCode::UnImplement("Unimplemented NATURAL JOIN yet."))
+            }
+            (JoinType::Inner, _) | (_, JoinConstraint::On(_)) | (_, JoinConstraint::Using(_)) => {
+                left.join(right, join_type, constraint.clone())
+            }
+            _ => Err(ErrorCode::SyntaxException(format!(
+                "{} JOIN must have ON or USING clause",
+                join_type
+            ))),
+        }
+    }
+
+    // The conditions are analyzed against the final joined schema, so the ambiguous columns
+    // are named as same as the other parts of the query.
+    async fn analyze_join_conditions(&self, schema: &mut JoinedSchema) -> Result<()> {
+        for join_pos in 0..schema.get_joins().len() {
+            self.analyze_join_condition(schema, join_pos).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn analyze_join_condition(
+        &self,
+        schema: &mut JoinedSchema,
+        join_pos: usize,
+    ) -> Result<()> {
+        let analyzer = ExpressionAnalyzer::create_with_ctes(self.ctx.clone(), self.ctes.clone());
+
+        let join_desc = schema.get_joins()[join_pos].clone();
+        let conditions = match &join_desc.constraint {
+            JoinConstraint::On(expr) => {
+                let mut condition = analyzer.analyze(expr).await?;
+                QualifiedRewriter::rewrite_expr(schema, self.ctx.clone(), &mut condition)?;
+                Self::split_conjunctions(condition)
+            }
+            JoinConstraint::Using(idents) => {
+                let mut conditions = Vec::with_capacity(idents.len());
+                for ident in idents {
+                    let left = schema.get_join_column(&join_desc.left_tables, &ident.value)?;
+                    let right = schema.get_join_column(&join_desc.right_tables, &ident.value)?;
+                    conditions.push(left.eq(right));
+                }
+
+                conditions
+            }
+            _ => vec![],
+        };
+
+        schema.set_join_conditions(join_pos, conditions)
+    }
+
+    fn split_conjunctions(expr: Expression) -> Vec<Expression> {
+        match expr {
+            Expression::BinaryExpression { op, left, right } if op.to_lowercase() == "and" => {
+                let mut conjunctions = Self::split_conjunctions(*left);
+                conjunctions.extend(Self::split_conjunctions(*right));
+                conjunctions
+            }
+            other => vec![other],
+        }
     }
 
     async fn subquery(&self, v: &DerivedRPNItem) -> Result<JoinedSchema> {
         let subquery = &(*v.subquery);
-        let subquery = DfQueryStatement::try_from(subquery.clone())?;
+        let state = match Self::set_operation_of(subquery) {
+            Some((op, all, left, right)) => self.set_operation(op, all, left, right).await?,
+            None => self.subquery_state(subquery.clone(), &self.ctes).await?,
+        };
+
+        match &v.alias {
+            None => JoinedSchema::from_subquery(state, Vec::new()),
+            Some(alias) => {
+                let name_prefix = vec![alias.name.value.clone()];
+                JoinedSchema::from_subquery(state, name_prefix)
+            }
+        }
+    }
+
+    async fn subquery_state(
+        &self,
+        subquery: Query,
+        ctes: &[CommonTableExpr],
+    ) -> Result<Box<QueryAnalyzeState>> {
+        let mut subquery = DfQueryStatement::try_from(subquery)?;
+        subquery.outer_ctes = ctes.to_vec();
         match subquery.analyze(self.ctx.clone()).await? {
-            AnalyzedResult::SelectQuery(state) => match &v.alias {
-                None => JoinedSchema::from_subquery(state, Vec::new()),
-                Some(alias) => {
-                    let name_prefix = vec![alias.name.value.clone()];
-                    JoinedSchema::from_subquery(state, name_prefix)
-                }
-            },
+            AnalyzedResult::SelectQuery(state) => Ok(state),
             _ => Err(ErrorCode::LogicalError(
                 "Logical error, subquery analyzed data must be SelectQuery, it's a bug.",
             )),
         }
     }
 
+    // The set operation without ORDER BY and LIMIT, the others are converted to select from it.
+    fn set_operation_of(
+        query: &Query,
+    ) -> Option<(&SqlparserSetOperator, bool, &SetExpr, &SetExpr)> {
+        match &query.body {
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } if query.order_by.is_empty()
+                && query.limit.is_none()
+                && query.offset.is_none()
+                && query.fetch.is_none()
+                && query.with.is_none() =>
+            {
+                Some((op, *all, left.as_ref(), right.as_ref()))
+            }
+            _ => None,
+        }
+    }
+
+    async fn set_operation(
+        &self,
+        op: &SqlparserSetOperator,
+        all: bool,
+        left: &SetExpr,
+        right: &SetExpr,
+    ) -> Result<Box<QueryAnalyzeState>> {
+        let op = match op {
+            SqlparserSetOperator::Union => SetOperator::Union,
+            SqlparserSetOperator::Intersect => SetOperator::Intersect,
+            SqlparserSetOperator::Except => SetOperator::Except,
+        };
+
+        let left = self.set_operation_input(left).await?;
+        let right = self.set_operation_input(right).await?;
+        let schemas = [left.finalize_schema.clone(), right.finalize_schema.clone()];
+        let schema = SetOperationPlan::output_schema(op, &schemas)?;
+
+        Ok(Box::new(QueryAnalyzeState {
+            projection_expressions: schema.fields().iter().map(|f| col(f.name())).collect(),
+            relation: QueryRelation::SetOperation(Box::new(SetOperationRelation {
+                op,
+                all,
+                left,
+                right,
+            })),
+            finalize_schema: schema,
+            ..Default::default()
+        }))
+    }
+
+    async fn set_operation_input(&self, input: &SetExpr) -> Result<Box<QueryAnalyzeState>> {
+        let query = match input {
+            SetExpr::Query(query) => query.as_ref().clone(),
+            _ => Query {
+                with: None,
+                body: input.clone(),
+                order_by: vec![],
+                limit: None,
+                offset: None,
+                fetch: None,
+            },
+        };
+
+        self.subquery_state(query, &self.ctes).await
+    }
+
     async fn table(&self, item: &TableRPNItem) -> Result<JoinedSchema> {
+        if let Some(index) = CommonTableExpr::find(&self.ctes, &item.name) {
+            return self.cte(index, item).await;
+        }
+
         // TODO(Winter): await query_context.get_table
         let (database, table) = self.resolve_table(&item.name)?;
         let read_table = self.ctx.get_table(&database, &table).await?;
+        let read_table = match &item.time_travel {
+            None => read_table,
+            Some(hint) => {
+                let point = self.time_travel_point(hint).await?;
+                read_table.navigate_to(self.ctx.clone(), &point).await?
+            }
+        };
 
         match &item.alias {
             None => {
@@ -110,6 +322,29 @@ impl JoinedSchemaAnalyzer {
         }
     }
 
+    async fn cte(&self, index: usize, item: &TableRPNItem) -> Result<JoinedSchema> {
+        let cte = &self.ctes[index];
+        if item.time_travel.is_some() {
+            return Err(ErrorCode::SyntaxException(format!(
+                "Time travel of WITH query {} is unsupported",
+                cte.alias.name.value
+            )));
+        }
+
+        // The CTE can refer to the CTEs before it.
+        let state = self
+            .subquery_state(cte.query.clone(), &self.ctes[..index])
+            .await?;
+        let state = cte.reference_state(state)?;
+        match &item.alias {
+            None => JoinedSchema::from_subquery(state, vec![cte.alias.name.value.clone()]),
+            Some(alias) => {
+                let name_prefix = vec![alias.name.value.clone()];
+                JoinedSchema::from_subquery(state, name_prefix)
+            }
+        }
+    }
+
     async fn table_function(&self, item: &TableFunctionRPNItem) -> Result<JoinedSchema> {
         if item.name.0.len() >= 2 {
             return Result::Err(ErrorCode::BadArguments(
@@ -139,6 +374,53 @@ impl JoinedSchemaAnalyzer {
         }
     }
 
+    async fn time_travel_point(&self, hint: &TimeTravelHint) -> Result<TimeTravelPoint> {
+        let analyzer = ExpressionAnalyzer::create(self.ctx.clone());
+        match hint {
+            TimeTravelHint::Snapshot(expr) => match analyzer.analyze(expr).await? {
+                Expression::Literal {
+                    value: DataValue::String(Some(snapshot_id)),
+                    ..
+                } => Ok(TimeTravelPoint::Snapshot(String::from_utf8(snapshot_id)?)),
+                other => Err(ErrorCode::BadArguments(format!(
+                    "SNAPSHOT of time travel must be a string literal, but got {:?}",
+                    other
+                ))),
+            },
+            TimeTravelHint::Timestamp(expr) => {
+                let expr = Expression::Cast {
+                    expr: Box::new(analyzer.analyze(expr).await?),
+                    data_type: DataType::DateTime32(None),
+                };
+
+                match Self::eval_constant_expr(expr)? {
+                    DataValue::UInt32(Some(secs)) => Ok(TimeTravelPoint::Timestamp(secs)),
+                    other => Err(ErrorCode::BadArguments(format!(
+                        "TIMESTAMP of time travel must be a valid datetime, but got {:?}",
+                        other
+                    ))),
+                }
+            }
+        }
+    }
+
+    fn eval_constant_expr(expr: Expression) -> Result<DataValue> {
+        let input_schema =
+            DataSchemaRefExt::create(vec![DataField::new("_dummy", DataType::UInt8, false)]);
+        let output_schema = DataSchemaRefExt::create(vec![expr.to_data_field(&input_schema)?]);
+        let executor = ExpressionExecutor::try_create(
+            "Time travel point",
+            input_schema.clone(),
+            output_schema,
+            vec![expr],
+            false,
+        )?;
+
+        let dummy_columns = vec![DataColumn::Constant(DataValue::UInt8(Some(1)), 1)];
+        let block = executor.execute(&DataBlock::create(input_schema, dummy_columns))?;
+        block.column(0).try_get(0)
+    }
+
     fn resolve_table(&self, name: &ObjectName) -> Result<(String, String)> {
         match name.0.len() {
             0 => Err(ErrorCode::SyntaxException("Table name is empty")),
@@ -154,6 +436,12 @@ impl JoinedSchemaAnalyzer {
 struct TableRPNItem {
     name: ObjectName,
     alias: Option<TableAlias>,
+    time_travel: Option<TimeTravelHint>,
+}
+
+enum TimeTravelHint {
+    Snapshot(Expr),
+    Timestamp(Expr),
 }
 
 struct DerivedRPNItem {
@@ -193,6 +481,7 @@ impl RelationRPNBuilder {
         self.rpn.push(RelationRPNItem::Table(TableRPNItem {
             name: ObjectName(vec![Ident::new("system"), Ident::new("one")]),
             alias: None,
+            time_travel: None,
         }));
     }
 
@@ -233,15 +522,13 @@ impl RelationRPNBuilder {
                 alias,
                 with_hints,
             } => {
-                if !with_hints.is_empty() {
-                    return Err(ErrorCode::SyntaxException(
-                        "MSSQL-specific `WITH (...)` hints is unsupported.",
-                    ));
-                }
-
-                match args.is_empty() {
-                    true => self.visit_table(name, alias),
-                    false => self.visit_table_function(name, args, alias),
+                let time_travel = Self::time_travel_hint(with_hints)?;
+                match (args.is_empty(), time_travel) {
+                    (true, time_travel) => self.visit_table(name, alias, time_travel),
+                    (false, None) => self.visit_table_function(name, args, alias),
+                    (false, Some(_)) => Err(ErrorCode::SyntaxException(
+                        "Time travel of table function is unsupported.",
+                    )),
                 }
             }
             TableFactor::Derived {
@@ -266,10 +553,41 @@ impl RelationRPNBuilder {
         }
     }
 
-    fn visit_table(&mut self, name: &ObjectName, alias: &Option<TableAlias>) -> Result<()> {
+    // The time travel clause `AT (SNAPSHOT => expr)` is rewritten as hint `WITH (SNAPSHOT = expr)` by DfParser
+    fn time_travel_hint(with_hints: &[Expr]) -> Result<Option<TimeTravelHint>> {
+        match with_hints {
+            [] => Ok(None),
+            [Expr::BinaryOp {
+                left,
+                op: BinaryOperator::Eq,
+                right,
+            }] => match left.as_ref() {
+                Expr::Identifier(kind) if kind.value == "SNAPSHOT" => {
+                    Ok(Some(TimeTravelHint::Snapshot(right.as_ref().clone())))
+                }
+                Expr::Identifier(kind) if kind.value == "TIMESTAMP" => {
+                    Ok(Some(TimeTravelHint::Timestamp(right.as_ref().clone())))
+                }
+                _ => Err(ErrorCode::SyntaxException(
+                    "MSSQL-specific `WITH (...)` hints is unsupported.",
+                )),
+            },
+            _ => Err(ErrorCode::SyntaxException(
+                "MSSQL-specific `WITH (...)` hints is unsupported.",
+            )),
+        }
+    }
+
+    fn visit_table(
+        &mut self,
+        name: &ObjectName,
+        alias: &Option<TableAlias>,
+        time_travel: Option<TimeTravelHint>,
+    ) -> Result<()> {
         self.rpn.push(RelationRPNItem::Table(TableRPNItem {
             name: name.clone(),
             alias: alias.clone(),
+            time_travel,
         }));
         Ok(())
     }
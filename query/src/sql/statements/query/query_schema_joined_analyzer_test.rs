@@ -49,6 +49,21 @@ async fn test_joined_schema_analyzer() -> Result<()> {
             query: "SELECT * FROM (SELECT * FROM system.databases)",
             expect: "QuerySchema { short_names: [\"name\"] }",
         },
+        TestCase {
+            name: "Join query",
+            query: "SELECT * FROM system.databases AS a JOIN system.databases AS b ON a.name = b.name AND a.name > 'x'",
+            expect: "QuerySchema { ambiguity_names: [[\"a\", \"name\"], [\"b\", \"name\"]], joins: [Join { type: \"INNER\", left_keys: [a.name], right_keys: [b.name], other_conditions: (a.name > x) }] }",
+        },
+        TestCase {
+            name: "Left join query with using",
+            query: "SELECT * FROM system.databases AS a LEFT JOIN system.databases AS b USING (name)",
+            expect: "QuerySchema { ambiguity_names: [[\"a\", \"name\"], [\"b\", \"name\"]], joins: [Join { type: \"LEFT\", left_keys: [a.name], right_keys: [b.name] }] }",
+        },
+        TestCase {
+            name: "Comma separated tables query",
+            query: "SELECT * FROM numbers(2) AS a, numbers(3) AS b",
+            expect: "QuerySchema { ambiguity_names: [[\"a\", \"number\"], [\"b\", \"number\"]], joins: [Join { type: \"INNER\" }] }",
+        },
     ];
 
     for test_case in &tests {
@@ -57,7 +72,7 @@ async fn test_joined_schema_analyzer() -> Result<()> {
 
         match statements.remove(0) {
             DfStatement::Query(query) => {
-                let analyzer = JoinedSchemaAnalyzer::create(ctx);
+                let analyzer = JoinedSchemaAnalyzer::create(ctx, vec![]);
                 assert_eq!(
                     test_case.expect,
                     format!("{:?}", analyzer.analyze(&query).await?),
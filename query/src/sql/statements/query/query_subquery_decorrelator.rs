@@ -0,0 +1,732 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::aggregates::AggregateFunctionFactory;
+use common_planners::Expression;
+use common_planners::JoinType;
+use sqlparser::ast::BinaryOperator;
+use sqlparser::ast::Expr;
+use sqlparser::ast::Function;
+use sqlparser::ast::FunctionArg;
+use sqlparser::ast::Ident;
+use sqlparser::ast::JoinConstraint;
+use sqlparser::ast::ObjectName;
+use sqlparser::ast::Query;
+use sqlparser::ast::SelectItem;
+use sqlparser::ast::UnaryOperator;
+use sqlparser::ast::Value;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::query::CommonTableExpr;
+use crate::sql::statements::query::JoinedSchema;
+use crate::sql::statements::query::JoinedSchemaAnalyzer;
+use crate::sql::statements::query::QualifiedRewriter;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::DfQueryStatement;
+
+/// Rewrites the subqueries of the WHERE conjunctions and the scalar subqueries of the projection
+/// into joins, so that the correlated subqueries are not executed once per row:
+///
+///   [NOT] EXISTS (SELECT ... WHERE inner = outer)    => SEMI (ANTI) JOIN ON outer = inner
+///   expr [NOT] IN (SELECT value FROM ...)            => SEMI (NULL AWARE ANTI) JOIN
+///                                                       ON expr = value
+///   expr NOT IN (SELECT value ... WHERE inner = outer)
+///                                                    => ANTI JOIN ON outer = inner AND
+///                                                       (expr = value OR expr IS NULL OR
+///                                                        value IS NULL)
+///   expr < (SELECT agg(..) ... WHERE inner = outer)  => LEFT JOIN (SELECT agg(..), inner ...
+///                                                       GROUP BY inner) ON outer = inner
+///   (SELECT COUNT(..) ... WHERE inner = outer)       => the same LEFT JOIN, the value of the
+///                                                       outer rows without groups is 0
+///
+/// The uncorrelated EXISTS and scalar subqueries are kept, they are executed only once.
+pub struct SubqueryDecorrelator {
+    ctx: Arc<QueryContext>,
+    ctes: Vec<CommonTableExpr>,
+}
+
+// The conjunctions of the subquery WHERE which reference the columns of the outer query.
+struct Correlation {
+    // The subquery without the correlated conjunctions.
+    subquery: DfQueryStatement,
+    // The `inner = outer` conjunctions, the inner expressions are projected by the subquery.
+    equi_conditions: Vec<(Expr, Expr)>,
+    // The other correlated conjunctions, and the inner columns they reference.
+    other_conditions: Vec<Expr>,
+    other_columns: Vec<Expr>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ColumnsScope {
+    Constant,
+    Inner,
+    Outer,
+    Mixed,
+}
+
+impl SubqueryDecorrelator {
+    pub fn create(ctx: Arc<QueryContext>, ctes: Vec<CommonTableExpr>) -> SubqueryDecorrelator {
+        SubqueryDecorrelator { ctx, ctes }
+    }
+
+    /// Joins the subqueries to the schema, returns the query with the rewritten WHERE and
+    /// projection.
+    pub async fn decorrelate(
+        &self,
+        query: &DfQueryStatement,
+        schema: &mut JoinedSchema,
+    ) -> Result<DfQueryStatement> {
+        let mut query = query.clone();
+
+        if let Some(selection) = query.selection.take() {
+            let mut conjunctions = Vec::new();
+            for conjunction in Self::split_conjunctions(selection) {
+                let conjunction = self.decorrelate_conjunction(conjunction, schema).await?;
+                conjunctions.extend(conjunction);
+            }
+
+            query.selection = Self::conjunction(conjunctions);
+        }
+
+        // The joined subquery column is neither grouped nor aggregated in the aggregate query.
+        if query.group_by.is_empty() && query.having.is_none() && !self.has_aggregate(&query) {
+            for item in &mut query.projection {
+                self.decorrelate_projection(item, schema).await?;
+            }
+        }
+
+        Ok(query)
+    }
+
+    // Returns the conjunction that remains in the WHERE.
+    async fn decorrelate_conjunction(
+        &self,
+        conjunction: Expr,
+        schema: &mut JoinedSchema,
+    ) -> Result<Option<Expr>> {
+        match conjunction {
+            Expr::Exists(subquery) => {
+                let exists = Expr::Exists(subquery.clone());
+                match self
+                    .decorrelate_exists(*subquery, JoinType::Semi, schema)
+                    .await?
+                {
+                    true => Ok(None),
+                    false => Ok(Some(exists)),
+                }
+            }
+            Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                expr,
+            } => match *expr {
+                Expr::Exists(subquery) => {
+                    let not_exists = Expr::UnaryOp {
+                        op: UnaryOperator::Not,
+                        expr: Box::new(Expr::Exists(subquery.clone())),
+                    };
+                    match self
+                        .decorrelate_exists(*subquery, JoinType::Anti, schema)
+                        .await?
+                    {
+                        true => Ok(None),
+                        false => Ok(Some(not_exists)),
+                    }
+                }
+                expr => Ok(Some(Expr::UnaryOp {
+                    op: UnaryOperator::Not,
+                    expr: Box::new(expr),
+                })),
+            },
+            Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => {
+                self.decorrelate_in(*expr, *subquery, negated, schema)
+                    .await?;
+                Ok(None)
+            }
+            mut conjunction => {
+                self.decorrelate_scalars(&mut conjunction, schema).await?;
+                Ok(Some(conjunction))
+            }
+        }
+    }
+
+    async fn decorrelate_projection(
+        &self,
+        item: &mut SelectItem,
+        schema: &mut JoinedSchema,
+    ) -> Result<()> {
+        match item {
+            SelectItem::UnnamedExpr(expr) => {
+                // Keep the name of the projection.
+                let alias = Ident::new(expr.to_string());
+                let mut expr = expr.clone();
+                if self.decorrelate_scalars(&mut expr, schema).await? {
+                    *item = SelectItem::ExprWithAlias { expr, alias };
+                }
+            }
+            SelectItem::ExprWithAlias { expr, .. } => {
+                self.decorrelate_scalars(expr, schema).await?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    // Returns true if any scalar subquery of the expression is replaced.
+    async fn decorrelate_scalars(
+        &self,
+        expr: &mut Expr,
+        schema: &mut JoinedSchema,
+    ) -> Result<bool> {
+        let mut replaced = false;
+        for subquery in Self::subqueries_of(expr) {
+            if let Some(value) = self.decorrelate_scalar(&subquery, schema).await? {
+                Self::replace_subquery(expr, &subquery, value);
+                replaced = true;
+            }
+        }
+
+        Ok(replaced)
+    }
+
+    // Returns false if the subquery is uncorrelated, which is executed by the EXISTS function.
+    async fn decorrelate_exists(
+        &self,
+        subquery: Query,
+        join_type: JoinType,
+        schema: &mut JoinedSchema,
+    ) -> Result<bool> {
+        let subquery = DfQueryStatement::try_from(subquery)?;
+        match self.correlation(subquery, schema).await? {
+            None => Ok(false),
+            Some(correlation) => {
+                self.join_subquery(schema, join_type, correlation, None, false)
+                    .await?;
+                Ok(true)
+            }
+        }
+    }
+
+    // `expr IN (SELECT value ...)` is `EXISTS (SELECT ... WHERE value = expr)`.
+    //
+    // `expr NOT IN (SELECT value ...)` is not true if `expr` or any value is NULL, unless the
+    // subquery is empty. The NULL AWARE ANTI join does it for the uncorrelated subquery, the
+    // correlated one is checked by the other conditions, as the values of each outer row are
+    // different.
+    async fn decorrelate_in(
+        &self,
+        expr: Expr,
+        subquery: Query,
+        negated: bool,
+        schema: &mut JoinedSchema,
+    ) -> Result<()> {
+        let subquery = DfQueryStatement::try_from(subquery)?;
+        let value = Self::single_projection(&subquery, "IN")?;
+        let (mut correlation, correlated) = match self.correlation(subquery.clone(), schema).await?
+        {
+            Some(correlation) => (correlation, true),
+            None => (
+                Correlation {
+                    subquery,
+                    equi_conditions: vec![],
+                    other_conditions: vec![],
+                    other_columns: vec![],
+                },
+                false,
+            ),
+        };
+
+        if negated && correlated {
+            // The value is projected as the first column of the subquery.
+            let value_column =
+                Expr::Identifier(Ident::new(format!("{}_0", Self::subquery_alias(schema))));
+            let is_null = |expr: &Expr| Expr::IsNull(Box::new(expr.clone()));
+            let condition = Self::binary(
+                Self::binary(
+                    Self::binary(expr.clone(), BinaryOperator::Eq, value_column.clone()),
+                    BinaryOperator::Or,
+                    is_null(&expr),
+                ),
+                BinaryOperator::Or,
+                is_null(&value_column),
+            );
+
+            correlation
+                .other_conditions
+                .push(Expr::Nested(Box::new(condition)));
+            self.join_subquery(schema, JoinType::Anti, correlation, Some(value), false)
+                .await?;
+            return Ok(());
+        }
+
+        let join_type = match negated {
+            true => JoinType::NullAwareAnti,
+            false => JoinType::Semi,
+        };
+
+        correlation.equi_conditions.insert(0, (value, expr));
+        self.join_subquery(schema, join_type, correlation, None, false)
+            .await?;
+        Ok(())
+    }
+
+    // Returns the value of the scalar subquery if it is correlated.
+    async fn decorrelate_scalar(
+        &self,
+        subquery: &Query,
+        schema: &mut JoinedSchema,
+    ) -> Result<Option<Expr>> {
+        let subquery = DfQueryStatement::try_from(subquery.clone())?;
+        let correlation = match self.correlation(subquery, schema).await? {
+            None => return Ok(None),
+            Some(correlation) => correlation,
+        };
+
+        if !correlation.other_conditions.is_empty() {
+            return Err(ErrorCode::UnImplement(
+                "Unimplemented non-equal correlated conditions in scalar subquery yet.",
+            ));
+        }
+
+        let value = Self::single_projection(&correlation.subquery, "Scalar")?;
+        let mut functions = vec![];
+        Self::visit_expr(&mut value.clone(), &mut |expr| match expr {
+            Expr::Function(function) => {
+                functions.push(function.name.to_string().to_lowercase());
+                true
+            }
+            expr => !Self::is_subquery(expr),
+        });
+
+        // The outer rows without groups are NULL after the left join, COUNT of them is 0.
+        let is_count = matches!(value, Expr::Function(_)) && functions[0] == "count";
+        if !is_count && functions.iter().any(|name| name == "count") {
+            return Err(ErrorCode::UnImplement(
+                "Unimplemented COUNT in the expression of correlated scalar subquery yet.",
+            ));
+        }
+
+        let factory = AggregateFunctionFactory::instance();
+        if !functions.iter().any(|name| factory.check(name)) {
+            return Err(ErrorCode::UnImplement(
+                "Unimplemented correlated scalar subquery without aggregate function yet.",
+            ));
+        }
+
+        let column = self
+            .join_subquery(schema, JoinType::Left, correlation, Some(value), true)
+            .await?;
+        let column = Expr::Identifier(Ident::new(column));
+        match is_count {
+            false => Ok(Some(column)),
+            true => Ok(Some(Self::function("if", vec![
+                Expr::IsNull(Box::new(column.clone())),
+                Expr::Value(Value::Number(String::from("0"), false)),
+                column,
+            ]))),
+        }
+    }
+
+    async fn correlation(
+        &self,
+        mut subquery: DfQueryStatement,
+        outer: &JoinedSchema,
+    ) -> Result<Option<Correlation>> {
+        subquery.outer_ctes = self.ctes.clone();
+        let ctes = CommonTableExpr::resolve(&self.ctx, &subquery)?;
+        let analyzer = JoinedSchemaAnalyzer::create(self.ctx.clone(), ctes);
+        let inner = analyzer.analyze(&subquery).await?;
+
+        let mut uncorrelated = vec![];
+        let mut equi_conditions = vec![];
+        let mut other_conditions = vec![];
+        let mut other_columns = vec![];
+        let conjunctions = match subquery.selection.take() {
+            None => vec![],
+            Some(selection) => Self::split_conjunctions(selection),
+        };
+
+        for conjunction in conjunctions {
+            match self.scope_of(&conjunction, &inner, outer) {
+                ColumnsScope::Constant | ColumnsScope::Inner => uncorrelated.push(conjunction),
+                _ => match self.equi_condition(&conjunction, &inner, outer) {
+                    Some(equi_condition) => equi_conditions.push(equi_condition),
+                    None => {
+                        for column in Self::columns_of(&conjunction) {
+                            let is_inner = self.is_inner_column(&column, &inner, outer);
+                            if is_inner && !other_columns.contains(&column) {
+                                other_columns.push(column);
+                            }
+                        }
+
+                        other_conditions.push(conjunction);
+                    }
+                },
+            }
+        }
+
+        if equi_conditions.is_empty() && other_conditions.is_empty() {
+            return Ok(None);
+        }
+
+        if !subquery.group_by.is_empty()
+            || subquery.having.is_some()
+            || subquery.limit.is_some()
+            || subquery.offset.is_some()
+        {
+            return Err(ErrorCode::UnImplement(
+                "Unimplemented correlated subquery with GROUP BY, HAVING, LIMIT or OFFSET yet.",
+            ));
+        }
+
+        subquery.selection = Self::conjunction(uncorrelated);
+        Ok(Some(Correlation {
+            subquery,
+            equi_conditions,
+            other_conditions,
+            other_columns,
+        }))
+    }
+
+    // Joins the subquery which projects the value, the inner expressions of the equi-conditions
+    // and the inner columns of the other conditions, returns the name of the first column.
+    async fn join_subquery(
+        &self,
+        schema: &mut JoinedSchema,
+        join_type: JoinType,
+        correlation: Correlation,
+        value: Option<Expr>,
+        group_by_correlation: bool,
+    ) -> Result<String> {
+        let alias = Self::subquery_alias(schema);
+        let column_name = |index: usize| format!("{}_{}", alias, index);
+        let column = |index: usize| Expr::Identifier(Ident::new(column_name(index)));
+
+        let mut subquery = correlation.subquery;
+        let mut projection = value.into_iter().collect::<Vec<_>>();
+        let mut conditions = Vec::new();
+        for (inner, outer) in correlation.equi_conditions {
+            conditions.push(Self::binary(
+                outer,
+                BinaryOperator::Eq,
+                column(projection.len()),
+            ));
+            if group_by_correlation {
+                subquery.group_by.push(inner.clone());
+            }
+
+            projection.push(inner);
+        }
+
+        let offset = projection.len();
+        for mut condition in correlation.other_conditions {
+            Self::visit_expr(&mut condition, &mut |expr| {
+                match correlation.other_columns.iter().position(|c| *c == *expr) {
+                    Some(index) => *expr = column(offset + index),
+                    None => return !Self::is_subquery(expr),
+                };
+                false
+            });
+            conditions.push(condition);
+        }
+
+        projection.extend(correlation.other_columns);
+        if projection.is_empty() {
+            projection.push(Expr::Value(Value::Number(String::from("1"), false)));
+        }
+
+        subquery.projection = projection
+            .into_iter()
+            .enumerate()
+            .map(|(index, expr)| SelectItem::ExprWithAlias {
+                expr,
+                alias: Ident::new(column_name(index)),
+            })
+            .collect();
+
+        // The ORDER BY may reference the replaced projection, it is useless without LIMIT.
+        if subquery.limit.is_none() {
+            subquery.order_by.clear();
+        }
+
+        subquery.outer_ctes = self.ctes.clone();
+        let state = match subquery.analyze(self.ctx.clone()).await? {
+            AnalyzedResult::SelectQuery(state) => state,
+            _ => {
+                return Err(ErrorCode::LogicalError(
+                    "Logical error, subquery analyzed data must be SelectQuery, it's a bug.",
+                ));
+            }
+        };
+
+        let subquery_schema = JoinedSchema::from_subquery(state, vec![alias])?;
+        let constraint = match Self::conjunction(conditions) {
+            None => JoinConstraint::None,
+            Some(conditions) => JoinConstraint::On(conditions),
+        };
+
+        *schema = schema.join(&subquery_schema, join_type, constraint)?;
+        schema.hide_table(schema.get_tables_desc().len() - 1);
+
+        let analyzer = JoinedSchemaAnalyzer::create(self.ctx.clone(), self.ctes.clone());
+        let join_pos = schema.get_joins().len() - 1;
+        analyzer.analyze_join_condition(schema, join_pos).await?;
+        Ok(column_name(0))
+    }
+
+    fn subquery_alias(schema: &JoinedSchema) -> String {
+        format!("_subquery_{}", schema.get_tables_desc().len())
+    }
+
+    // The `inner = outer` conjunction, returns (inner, outer).
+    fn equi_condition(
+        &self,
+        conjunction: &Expr,
+        inner: &JoinedSchema,
+        outer: &JoinedSchema,
+    ) -> Option<(Expr, Expr)> {
+        if let Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Eq,
+            right,
+        } = conjunction
+        {
+            let left_scope = self.scope_of(left, inner, outer);
+            let right_scope = self.scope_of(right, inner, outer);
+            match (left_scope, right_scope) {
+                (ColumnsScope::Inner, ColumnsScope::Outer) => {
+                    return Some((left.as_ref().clone(), right.as_ref().clone()));
+                }
+                (ColumnsScope::Outer, ColumnsScope::Inner) => {
+                    return Some((right.as_ref().clone(), left.as_ref().clone()));
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    fn scope_of(&self, expr: &Expr, inner: &JoinedSchema, outer: &JoinedSchema) -> ColumnsScope {
+        let mut scope = ColumnsScope::Constant;
+        for column in Self::columns_of(expr) {
+            let column_scope = match self.is_inner_column(&column, inner, outer) {
+                true => ColumnsScope::Inner,
+                false => ColumnsScope::Outer,
+            };
+
+            scope = match scope {
+                ColumnsScope::Constant => column_scope,
+                scope if scope == column_scope => scope,
+                _ => ColumnsScope::Mixed,
+            };
+        }
+
+        scope
+    }
+
+    // The columns are resolved in the subquery first, the unknown columns are reported by the
+    // analysis of the subquery.
+    fn is_inner_column(&self, column: &Expr, inner: &JoinedSchema, outer: &JoinedSchema) -> bool {
+        let mut expression = match column {
+            Expr::Identifier(ident) => Expression::Column(ident.value.clone()),
+            Expr::CompoundIdentifier(idents) => Expression::QualifiedColumn(
+                idents.iter().map(|ident| ident.value.clone()).collect(),
+            ),
+            _ => return true,
+        };
+
+        let mut outer_expression = expression.clone();
+        QualifiedRewriter::rewrite_expr(inner, self.ctx.clone(), &mut expression).is_ok()
+            || QualifiedRewriter::rewrite_expr(outer, self.ctx.clone(), &mut outer_expression)
+                .is_err()
+    }
+
+    fn single_projection(subquery: &DfQueryStatement, kind: &str) -> Result<Expr> {
+        match subquery.projection.as_slice() {
+            [SelectItem::UnnamedExpr(expr)] => Ok(expr.clone()),
+            [SelectItem::ExprWithAlias { expr, .. }] => Ok(expr.clone()),
+            _ => Err(ErrorCode::SyntaxException(format!(
+                "{} subquery must return exactly one column",
+                kind
+            ))),
+        }
+    }
+
+    fn split_conjunctions(expr: Expr) -> Vec<Expr> {
+        match expr {
+            Expr::Nested(expr) => match *expr {
+                expr
+                @
+                Expr::BinaryOp {
+                    op: BinaryOperator::And,
+                    ..
+                } => Self::split_conjunctions(expr),
+                expr => vec![Expr::Nested(Box::new(expr))],
+            },
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::And,
+                right,
+            } => {
+                let mut conjunctions = Self::split_conjunctions(*left);
+                conjunctions.extend(Self::split_conjunctions(*right));
+                conjunctions
+            }
+            other => vec![other],
+        }
+    }
+
+    fn conjunction(conjunctions: Vec<Expr>) -> Option<Expr> {
+        conjunctions
+            .into_iter()
+            .reduce(|left, right| Self::binary(left, BinaryOperator::And, right))
+    }
+
+    fn binary(left: Expr, op: BinaryOperator, right: Expr) -> Expr {
+        Expr::BinaryOp {
+            left: Box::new(left),
+            op,
+            right: Box::new(right),
+        }
+    }
+
+    fn function(name: &str, args: Vec<Expr>) -> Expr {
+        Expr::Function(Function {
+            name: ObjectName(vec![Ident::new(name)]),
+            args: args.into_iter().map(FunctionArg::Unnamed).collect(),
+            over: None,
+            distinct: false,
+        })
+    }
+
+    fn has_aggregate(&self, query: &DfQueryStatement) -> bool {
+        let factory = AggregateFunctionFactory::instance();
+        let mut has_aggregate = false;
+        for item in &query.projection {
+            if let SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } = item {
+                Self::visit_expr(&mut expr.clone(), &mut |expr| match expr {
+                    Expr::Function(function) if factory.check(&function.name.to_string()) => {
+                        has_aggregate = true;
+                        false
+                    }
+                    expr => !Self::is_subquery(expr),
+                });
+            }
+        }
+
+        has_aggregate
+    }
+
+    fn is_subquery(expr: &Expr) -> bool {
+        matches!(
+            expr,
+            Expr::Exists(_) | Expr::Subquery(_) | Expr::InSubquery { .. }
+        )
+    }
+
+    // The columns of the expression, the columns of the subqueries are not included.
+    fn columns_of(expr: &Expr) -> Vec<Expr> {
+        let mut columns = vec![];
+        Self::visit_expr(&mut expr.clone(), &mut |expr| match expr {
+            Expr::Identifier(_) | Expr::CompoundIdentifier(_) => {
+                columns.push(expr.clone());
+                false
+            }
+            expr => !Self::is_subquery(expr),
+        });
+        columns
+    }
+
+    fn subqueries_of(expr: &Expr) -> Vec<Query> {
+        let mut subqueries = vec![];
+        Self::visit_expr(&mut expr.clone(), &mut |expr| match expr {
+            Expr::Subquery(subquery) => {
+                subqueries.push(subquery.as_ref().clone());
+                false
+            }
+            expr => !Self::is_subquery(expr),
+        });
+        subqueries
+    }
+
+    fn replace_subquery(expr: &mut Expr, subquery: &Query, value: Expr) {
+        Self::visit_expr(expr, &mut |expr| match expr {
+            Expr::Subquery(v) if v.as_ref() == subquery => {
+                *expr = value.clone();
+                false
+            }
+            expr => !Self::is_subquery(expr),
+        });
+    }
+
+    // Visits the expression in pre-order, the children are visited if `f` returns true.
+    fn visit_expr<F: FnMut(&mut Expr) -> bool>(expr: &mut Expr, f: &mut F) {
+        if !f(expr) {
+            return;
+        }
+
+        match expr {
+            Expr::Nested(expr)
+            | Expr::IsNull(expr)
+            | Expr::IsNotNull(expr)
+            | Expr::UnaryOp { expr, .. }
+            | Expr::Cast { expr, .. } => Self::visit_expr(expr, f),
+            Expr::BinaryOp { left, right, .. } => {
+                Self::visit_expr(left, f);
+                Self::visit_expr(right, f);
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                Self::visit_expr(expr, f);
+                Self::visit_expr(low, f);
+                Self::visit_expr(high, f);
+            }
+            Expr::InList { expr, list, .. } => {
+                Self::visit_expr(expr, f);
+                list.iter_mut().for_each(|item| Self::visit_expr(item, f));
+            }
+            Expr::Substring {
+                expr,
+                substring_from,
+                substring_for,
+            } => {
+                Self::visit_expr(expr, f);
+                let args = substring_from.iter_mut().chain(substring_for.iter_mut());
+                args.for_each(|arg| Self::visit_expr(arg, f));
+            }
+            Expr::Tuple(exprs) => exprs.iter_mut().for_each(|expr| Self::visit_expr(expr, f)),
+            Expr::Function(function) => {
+                for arg in &mut function.args {
+                    match arg {
+                        FunctionArg::Named { arg, .. } => Self::visit_expr(arg, f),
+                        FunctionArg::Unnamed(arg) => Self::visit_expr(arg, f),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
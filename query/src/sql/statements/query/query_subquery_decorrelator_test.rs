@@ -0,0 +1,165 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::sql::statements::query::JoinedSchemaAnalyzer;
+use crate::sql::statements::query::SubqueryDecorrelator;
+use crate::sql::DfParser;
+use crate::sql::DfStatement;
+use crate::tests::try_create_context;
+
+#[tokio::test]
+async fn test_subquery_decorrelator() -> Result<()> {
+    struct TestCase {
+        name: &'static str,
+        query: &'static str,
+        expect_schema: &'static str,
+        expect_selection: &'static str,
+        expect_projection: &'static str,
+    }
+    let tests = vec![
+        TestCase {
+            name: "Uncorrelated exists query",
+            query: "SELECT * FROM numbers(3) AS a WHERE EXISTS (SELECT * FROM numbers(5) WHERE number > 1)",
+            expect_schema: "QuerySchema { short_names: [\"number\"] }",
+            expect_selection: "EXISTS (SELECT * FROM numbers(5) WHERE number > 1)",
+            expect_projection: "*",
+        },
+        TestCase {
+            name: "Correlated exists query",
+            query: "SELECT * FROM numbers(3) AS a WHERE EXISTS (SELECT * FROM numbers(5) AS b WHERE b.number = a.number)",
+            expect_schema: "QuerySchema { short_names: [\"number\", \"_subquery_1_0\"], joins: [Join { type: \"SEMI\", left_keys: [number], right_keys: [_subquery_1_0] }] }",
+            expect_selection: "",
+            expect_projection: "*",
+        },
+        TestCase {
+            name: "Correlated not exists query with non-equal condition",
+            query: "SELECT * FROM numbers(3) AS a WHERE a.number > 0 AND NOT EXISTS (SELECT * FROM numbers(5) AS b WHERE b.number > a.number)",
+            expect_schema: "QuerySchema { short_names: [\"number\", \"_subquery_1_0\"], joins: [Join { type: \"ANTI\", other_conditions: (_subquery_1_0 > number) }] }",
+            expect_selection: "a.number > 0",
+            expect_projection: "*",
+        },
+        TestCase {
+            name: "In subquery",
+            query: "SELECT * FROM numbers(3) AS a WHERE a.number IN (SELECT number + 1 FROM numbers(2))",
+            expect_schema: "QuerySchema { short_names: [\"number\", \"_subquery_1_0\"], joins: [Join { type: \"SEMI\", left_keys: [number], right_keys: [_subquery_1_0] }] }",
+            expect_selection: "",
+            expect_projection: "*",
+        },
+        TestCase {
+            name: "Correlated scalar subquery",
+            query: "SELECT * FROM numbers(3) AS a WHERE a.number < (SELECT max(b.number) FROM numbers(5) AS b WHERE b.number = a.number)",
+            expect_schema: "QuerySchema { short_names: [\"number\", \"_subquery_1_0\", \"_subquery_1_1\"], joins: [Join { type: \"LEFT\", left_keys: [number], right_keys: [_subquery_1_1] }] }",
+            expect_selection: "a.number < _subquery_1_0",
+            expect_projection: "*",
+        },
+        TestCase {
+            name: "Not in subquery",
+            query: "SELECT * FROM numbers(3) AS a WHERE a.number NOT IN (SELECT number + 1 FROM numbers(2))",
+            expect_schema: "QuerySchema { short_names: [\"number\", \"_subquery_1_0\"], joins: [Join { type: \"NULL AWARE ANTI\", left_keys: [number], right_keys: [_subquery_1_0] }] }",
+            expect_selection: "",
+            expect_projection: "*",
+        },
+        TestCase {
+            name: "Correlated not in subquery",
+            query: "SELECT * FROM numbers(3) AS a WHERE a.number NOT IN (SELECT b.number + 1 FROM numbers(5) AS b WHERE b.number = a.number)",
+            expect_schema: "QuerySchema { short_names: [\"number\", \"_subquery_1_0\", \"_subquery_1_1\"], joins: [Join { type: \"ANTI\", left_keys: [number], right_keys: [_subquery_1_1], other_conditions: (((number = _subquery_1_0) OR isnull(number)) OR isnull(_subquery_1_0)) }] }",
+            expect_selection: "",
+            expect_projection: "*",
+        },
+        TestCase {
+            name: "Correlated count scalar subquery",
+            query: "SELECT * FROM numbers(3) AS a WHERE a.number = (SELECT count(*) FROM numbers(5) AS b WHERE b.number = a.number)",
+            expect_schema: "QuerySchema { short_names: [\"number\", \"_subquery_1_0\", \"_subquery_1_1\"], joins: [Join { type: \"LEFT\", left_keys: [number], right_keys: [_subquery_1_1] }] }",
+            expect_selection: "a.number = if(_subquery_1_0 IS NULL, 0, _subquery_1_0)",
+            expect_projection: "*",
+        },
+        TestCase {
+            name: "Correlated scalar subquery in projection",
+            query: "SELECT a.number, (SELECT count(*) FROM numbers(5) AS b WHERE b.number = a.number) AS c FROM numbers(3) AS a",
+            expect_schema: "QuerySchema { short_names: [\"number\", \"_subquery_1_0\", \"_subquery_1_1\"], joins: [Join { type: \"LEFT\", left_keys: [number], right_keys: [_subquery_1_1] }] }",
+            expect_selection: "",
+            expect_projection: "a.number, if(_subquery_1_0 IS NULL, 0, _subquery_1_0) AS c",
+        },
+    ];
+
+    for test_case in &tests {
+        let ctx = try_create_context()?;
+        let (mut statements, _) = DfParser::parse_sql(test_case.query)?;
+
+        match statements.remove(0) {
+            DfStatement::Query(query) => {
+                let analyzer = JoinedSchemaAnalyzer::create(ctx.clone(), vec![]);
+                let mut schema = analyzer.analyze(&query).await?;
+                let decorrelator = SubqueryDecorrelator::create(ctx, vec![]);
+                let query = decorrelator.decorrelate(&query, &mut schema).await?;
+                let selection = query.selection.map(|expr| expr.to_string());
+                let projection = query.projection.iter().map(|item| item.to_string());
+
+                assert_eq!(
+                    test_case.expect_schema,
+                    format!("{:?}", schema),
+                    "{:#?}",
+                    test_case.name
+                );
+                assert_eq!(
+                    test_case.expect_selection,
+                    selection.unwrap_or_default(),
+                    "{:#?}",
+                    test_case.name
+                );
+                assert_eq!(
+                    test_case.expect_projection,
+                    projection.collect::<Vec<_>>().join(", "),
+                    "{:#?}",
+                    test_case.name
+                );
+            }
+            _ => {
+                return Err(ErrorCode::LogicalError("Cannot get analyze query state."));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_subquery_decorrelator_unimplemented() -> Result<()> {
+    let tests = vec![
+        "SELECT * FROM numbers(3) AS a WHERE a.number = (SELECT count(*) + 1 FROM numbers(5) AS b WHERE b.number = a.number)",
+        "SELECT * FROM numbers(3) AS a WHERE EXISTS (SELECT * FROM numbers(5) AS b WHERE b.number = a.number LIMIT 1)",
+    ];
+
+    for query in tests {
+        let ctx = try_create_context()?;
+        let (mut statements, _) = DfParser::parse_sql(query)?;
+
+        if let DfStatement::Query(query) = statements.remove(0) {
+            let analyzer = JoinedSchemaAnalyzer::create(ctx.clone(), vec![]);
+            let mut schema = analyzer.analyze(&query).await?;
+            let decorrelator = SubqueryDecorrelator::create(ctx, vec![]);
+            let result = decorrelator.decorrelate(&query, &mut schema).await;
+            assert_eq!(
+                ErrorCode::UnImplement("").code(),
+                result.unwrap_err().code()
+            );
+        }
+    }
+
+    Ok(())
+}
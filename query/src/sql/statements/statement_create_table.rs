@@ -21,16 +21,22 @@ use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::TableMeta;
+use common_planners::find_aggregate_exprs_in_expr;
 use common_planners::CreateTablePlan;
 use common_planners::PlanNode;
 use common_tracing::tracing;
 use sqlparser::ast::ColumnDef;
+use sqlparser::ast::Expr;
 use sqlparser::ast::ObjectName;
 use sqlparser::ast::SqlOption;
 
+use crate::datasources::index::BloomFilter;
+use crate::datasources::table::fuse::TBL_OPT_KEY_BLOOM_FILTER_COLUMNS;
+use crate::datasources::table::fuse::TBL_OPT_KEY_CLUSTER_KEY;
 use crate::sessions::QueryContext;
 use crate::sql::statements::AnalyzableStatement;
 use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::ExpressionAnalyzer;
 use crate::sql::SQLCommon;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -40,6 +46,8 @@ pub struct DfCreateTable {
     pub name: ObjectName,
     pub columns: Vec<ColumnDef>,
     pub engine: String,
+    /// Expressions of the `CLUSTER BY` clause, empty if the table has no cluster key
+    pub cluster_keys: Vec<Expr>,
     pub options: Vec<SqlOption>,
 }
 
@@ -47,7 +55,23 @@ pub struct DfCreateTable {
 impl AnalyzableStatement for DfCreateTable {
     #[tracing::instrument(level = "info", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
-        let table_meta = self.table_meta()?;
+        let mut table_meta = self.table_meta()?;
+        if !self.cluster_keys.is_empty() {
+            self.validate_cluster_keys(ctx.clone(), &table_meta.schema)
+                .await?;
+            table_meta
+                .options
+                .insert(TBL_OPT_KEY_CLUSTER_KEY.to_string(), self.cluster_keys_def());
+        }
+        // names of the options are lower-cased, see `table_options`
+        let bloom_filter_columns = TBL_OPT_KEY_BLOOM_FILTER_COLUMNS.to_lowercase();
+        if let Some(columns) = table_meta.options.remove(&bloom_filter_columns) {
+            let columns = self.validate_bloom_filter_columns(&columns, &table_meta.schema)?;
+            table_meta
+                .options
+                .insert(TBL_OPT_KEY_BLOOM_FILTER_COLUMNS.to_string(), columns);
+        }
+
         let if_not_exists = self.if_not_exists;
         let (db, table) = self.resolve_table(ctx)?;
 
@@ -78,6 +102,81 @@ impl DfCreateTable {
         }
     }
 
+    async fn validate_cluster_keys(
+        &self,
+        ctx: Arc<QueryContext>,
+        schema: &DataSchemaRef,
+    ) -> Result<()> {
+        if !self.engine.eq_ignore_ascii_case("FUSE") {
+            return Err(ErrorCode::BadArguments(format!(
+                "CLUSTER BY is not supported by table engine {}",
+                self.engine
+            )));
+        }
+
+        let analyzer = ExpressionAnalyzer::create(ctx);
+        for key in &self.cluster_keys {
+            let expression = analyzer.analyze(key).await?;
+            if !find_aggregate_exprs_in_expr(&expression).is_empty() {
+                return Err(ErrorCode::SyntaxException(
+                    "Aggregate functions are not allowed in the CLUSTER BY clause",
+                ));
+            }
+            // all the columns referenced should exist
+            expression.to_data_type(schema)?;
+        }
+        Ok(())
+    }
+
+    /// Validates the columns of `BLOOM_FILTER_COLUMNS`, e.g. `a, b`, and returns the normalized
+    /// ones kept in the table options, e.g. `a,b`
+    fn validate_bloom_filter_columns(
+        &self,
+        columns: &str,
+        schema: &DataSchemaRef,
+    ) -> Result<String> {
+        if !self.engine.eq_ignore_ascii_case("FUSE") {
+            return Err(ErrorCode::BadArguments(format!(
+                "BLOOM_FILTER_COLUMNS is not supported by table engine {}",
+                self.engine
+            )));
+        }
+
+        let mut names: Vec<&str> = vec![];
+        for name in columns.split(',').map(|name| name.trim()) {
+            let field = match schema.column_with_name(name) {
+                Some((_, field)) => field,
+                None => {
+                    return Err(ErrorCode::UnknownColumn(format!(
+                        "Unknown column {} in BLOOM_FILTER_COLUMNS",
+                        name
+                    )))
+                }
+            };
+            if !BloomFilter::is_supported_type(field.data_type()) {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Bloom filter is not supported by column {} of type {}",
+                    name,
+                    field.data_type()
+                )));
+            }
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        Ok(names.join(","))
+    }
+
+    /// The cluster key kept in the table options, e.g. `(a, b)`
+    fn cluster_keys_def(&self) -> String {
+        let keys = self
+            .cluster_keys
+            .iter()
+            .map(|key| key.to_string())
+            .collect::<Vec<_>>();
+        format!("({})", keys.join(", "))
+    }
+
     fn table_options(&self) -> HashMap<String, String> {
         self.options
             .iter()
@@ -70,7 +70,9 @@ impl AnalyzableStatement for DfInsertStatement {
             None => self.analyze_insert_without_source(&ctx).await,
             Some(source) => match &source.body {
                 SetExpr::Values(v) => self.analyze_insert_values(ctx.clone(), v).await,
-                SetExpr::Select(_) => self.analyze_insert_select(&ctx, source).await,
+                SetExpr::Select(_) | SetExpr::SetOperation { .. } | SetExpr::Query(_) => {
+                    self.analyze_insert_select(&ctx, source).await
+                }
                 _ => Err(ErrorCode::SyntaxException(
                     "Insert must be have values or select.",
                 )),
@@ -12,18 +12,28 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use common_datablocks::DataBlock;
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::col;
 use common_planners::expand_aggregate_arg_exprs;
+use common_planners::expand_window_arg_exprs;
 use common_planners::find_aggregate_exprs;
 use common_planners::find_aggregate_exprs_in_expr;
+use common_planners::find_array_join_exprs;
+use common_planners::find_array_join_exprs_in_expr;
+use common_planners::find_window_exprs;
+use common_planners::find_window_exprs_in_expr;
 use common_planners::rebase_expr;
 use common_planners::Expression;
 use common_tracing::tracing;
+use sqlparser::ast::Cte;
 use sqlparser::ast::Expr;
 use sqlparser::ast::Offset;
 use sqlparser::ast::OrderByExpr;
@@ -33,6 +43,7 @@ use sqlparser::ast::TableWithJoins;
 use crate::catalogs::ToReadDataSourcePlan;
 use crate::sessions::QueryContext;
 use crate::sql::statements::analyzer_statement::QueryAnalyzeState;
+use crate::sql::statements::query::CommonTableExpr;
 use crate::sql::statements::query::JoinedSchema;
 use crate::sql::statements::query::JoinedSchemaAnalyzer;
 use crate::sql::statements::query::JoinedTableDesc;
@@ -40,13 +51,21 @@ use crate::sql::statements::query::QualifiedRewriter;
 use crate::sql::statements::query::QueryASTIR;
 use crate::sql::statements::query::QueryCollectPushDowns;
 use crate::sql::statements::query::QueryNormalizer;
+use crate::sql::statements::query::SubqueryDecorrelator;
 use crate::sql::statements::AnalyzableStatement;
 use crate::sql::statements::AnalyzedResult;
+use crate::sql::statements::JoinRelation;
 use crate::sql::statements::QueryRelation;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct DfQueryStatement {
+    // The CTEs of the WITH clause.
+    pub ctes: Vec<Cte>,
+    // The CTEs of the outer queries.
+    pub outer_ctes: Vec<CommonTableExpr>,
     pub from: Vec<TableWithJoins>,
+    // The expressions of the ARRAY JOIN clause.
+    pub array_join: Vec<SelectItem>,
     pub projection: Vec<SelectItem>,
     pub selection: Option<Expr>,
     pub group_by: Vec<Expr>,
@@ -60,23 +79,28 @@ pub struct DfQueryStatement {
 impl AnalyzableStatement for DfQueryStatement {
     #[tracing::instrument(level = "info", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
-        let analyzer = JoinedSchemaAnalyzer::create(ctx.clone());
+        let ctes = CommonTableExpr::resolve(&ctx, self)?;
+        let analyzer = JoinedSchemaAnalyzer::create(ctx.clone(), ctes.clone());
         let mut joined_schema = analyzer.analyze(self).await?;
 
-        let mut ir = QueryNormalizer::normalize(ctx.clone(), self).await?;
+        let decorrelator = SubqueryDecorrelator::create(ctx.clone(), ctes.clone());
+        let query = decorrelator.decorrelate(self, &mut joined_schema).await?;
+
+        let mut ir = QueryNormalizer::normalize(ctx.clone(), &query, ctes).await?;
 
         QualifiedRewriter::rewrite(&joined_schema, ctx.clone(), &mut ir)?;
 
         QueryCollectPushDowns::collect_extras(&mut ir, &mut joined_schema)?;
 
-        let analyze_state = self.analyze_query(ir).await?;
-        self.check_and_finalize(joined_schema, analyze_state, ctx)
+        let analyze_state = query.analyze_query(ir).await?;
+        query
+            .check_and_finalize(joined_schema, analyze_state, ctx)
             .await
     }
 }
 
 impl DfQueryStatement {
-    async fn analyze_query(&self, ir: QueryASTIR) -> Result<QueryAnalyzeState> {
+    async fn analyze_query(&self, mut ir: QueryASTIR) -> Result<QueryAnalyzeState> {
         let limit = ir.limit;
         let offset = ir.offset;
         let mut analyze_state = QueryAnalyzeState {
@@ -87,13 +111,21 @@ impl DfQueryStatement {
 
         if let Some(predicate) = &ir.filter_predicate {
             Self::verify_no_aggregate(predicate, "filter")?;
-            analyze_state.filter = Some(predicate.clone());
+            Self::verify_no_window(predicate, "filter")?;
+
+            // The filter of the elements is applied after array join.
+            if find_array_join_exprs_in_expr(predicate).is_empty() {
+                analyze_state.filter = Some(predicate.clone());
+            }
         }
 
+        Self::analyze_array_join(&mut ir, &mut analyze_state)?;
+
         Self::analyze_projection(&ir.projection_expressions, &mut analyze_state)?;
 
         // Allow `SELECT name FROM system.databases HAVING name = 'xxx'`
         if let Some(predicate) = &ir.having_predicate {
+            Self::verify_no_window(predicate, "having")?;
             analyze_state.having = Some(rebase_expr(predicate, &analyze_state.expressions)?);
         }
 
@@ -124,6 +156,14 @@ impl DfQueryStatement {
             }
         }
 
+        for group_by_expression in &ir.group_by_expressions {
+            Self::verify_no_window(group_by_expression, "group by")?;
+        }
+
+        for aggregate_expression in &ir.aggregate_expressions {
+            Self::verify_no_window(aggregate_expression, "aggregate functions")?;
+        }
+
         if !ir.aggregate_expressions.is_empty() || !ir.group_by_expressions.is_empty() {
             // Rebase expressions using aggregate expressions and group by expressions
             let mut expressions = Vec::with_capacity(analyze_state.expressions.len());
@@ -145,9 +185,109 @@ impl DfQueryStatement {
             Self::analyze_aggregate(&ir.aggregate_expressions, &mut analyze_state)?;
         }
 
+        let window_expressions = find_window_exprs(&analyze_state.expressions);
+        if !window_expressions.is_empty() {
+            Self::analyze_window(&window_expressions, &mut analyze_state)?;
+        }
+
         Ok(analyze_state)
     }
 
+    // The arrayJoin functions are evaluated right after filter, the expressions of the query are
+    // rebased to their columns, so that they refer to the elements of the arrays. The arrays of
+    // the ARRAY JOIN clause are flattened even if their elements are not referred.
+    fn analyze_array_join(ir: &mut QueryASTIR, state: &mut QueryAnalyzeState) -> Result<()> {
+        let filter = ir
+            .filter_predicate
+            .clone()
+            .filter(|predicate| !find_array_join_exprs_in_expr(predicate).is_empty());
+
+        let mut exprs = ir.array_join_expressions.clone();
+        exprs.extend(filter.iter().cloned());
+        exprs.extend(ir.projection_expressions.iter().cloned());
+        exprs.extend(ir.group_by_expressions.iter().cloned());
+        exprs.extend(ir.order_by_expressions.iter().cloned());
+        exprs.extend(ir.having_predicate.iter().cloned());
+
+        let array_join_exprs = find_array_join_exprs(&exprs);
+        if array_join_exprs.is_empty() {
+            return Ok(());
+        }
+
+        for array_join_expr in &array_join_exprs {
+            if let Expression::ScalarFunction { args, .. } = array_join_expr {
+                for arg in args {
+                    Self::verify_no_aggregate(arg, "arrayJoin")?;
+                    Self::verify_no_window(arg, "arrayJoin")?;
+                    Self::verify_no_array_join(arg, "arrayJoin")?;
+                    state.add_before_array_join_expression(arg);
+                }
+            }
+
+            let base_exprs = &state.before_array_join_expressions;
+            state
+                .array_join_expressions
+                .push(rebase_expr(array_join_expr, base_exprs)?);
+        }
+
+        let ir_exprs = ir
+            .projection_expressions
+            .iter_mut()
+            .chain(ir.group_by_expressions.iter_mut())
+            .chain(ir.aggregate_expressions.iter_mut())
+            .chain(ir.order_by_expressions.iter_mut())
+            .chain(ir.having_predicate.iter_mut());
+
+        for expr in ir_exprs {
+            *expr = rebase_expr(expr, &array_join_exprs)?;
+        }
+
+        if let Some(predicate) = &filter {
+            state.array_join_filter = Some(rebase_expr(predicate, &array_join_exprs)?);
+        }
+
+        Ok(())
+    }
+
+    fn analyze_window(exprs: &[Expression], state: &mut QueryAnalyzeState) -> Result<()> {
+        // Literals are kept in the window functions, e.g. the offset of lag.
+        fn is_literal(expr: &Expression) -> bool {
+            matches!(expr, Expression::Literal { .. })
+        }
+
+        for window_function_arg in &expand_window_arg_exprs(exprs) {
+            Self::verify_no_window(window_function_arg, "window functions")?;
+            if !is_literal(window_function_arg) {
+                state.add_before_window_expression(window_function_arg);
+            }
+        }
+
+        // The expressions without window functions are evaluated before window,
+        // so that having can refer to them.
+        for expression in &state.expressions.clone() {
+            if find_window_exprs_in_expr(expression).is_empty() && !is_literal(expression) {
+                state.add_before_window_expression(expression);
+            }
+        }
+
+        for window_expression in exprs {
+            let base_exprs = &state.before_window_expressions;
+            state
+                .window_expressions
+                .push(rebase_expr(window_expression, base_exprs)?);
+        }
+
+        // Rebase expressions using before window expressions and window expressions
+        let mut expressions = Vec::with_capacity(state.expressions.len());
+        for expression in &state.expressions {
+            let expression = rebase_expr(expression, &state.before_window_expressions)?;
+            expressions.push(rebase_expr(&expression, &state.window_expressions)?);
+        }
+
+        state.expressions = expressions;
+        Ok(())
+    }
+
     fn analyze_aggregate(exprs: &[Expression], state: &mut QueryAnalyzeState) -> Result<()> {
         let aggregate_functions = find_aggregate_exprs(exprs);
         let aggregate_functions_args = expand_aggregate_arg_exprs(&aggregate_functions);
@@ -189,6 +329,26 @@ impl DfQueryStatement {
             ))),
         }
     }
+
+    fn verify_no_array_join(expr: &Expression, info: &str) -> Result<()> {
+        match find_array_join_exprs_in_expr(expr).is_empty() {
+            true => Ok(()),
+            false => Err(ErrorCode::SyntaxException(format!(
+                "{} cannot contain arrayJoin",
+                info
+            ))),
+        }
+    }
+
+    fn verify_no_window(expr: &Expression, info: &str) -> Result<()> {
+        match find_window_exprs_in_expr(expr).is_empty() {
+            true => Ok(()),
+            false => Err(ErrorCode::SyntaxException(format!(
+                "{} cannot contain window functions",
+                info
+            ))),
+        }
+    }
 }
 
 impl DfQueryStatement {
@@ -201,29 +361,131 @@ impl DfQueryStatement {
         let dry_run_res = Self::verify_with_dry_run(&schema, &state)?;
         state.finalize_schema = dry_run_res.schema().clone();
 
-        let mut tables_desc = schema.take_tables_desc();
+        state.relation = match schema.get_joins().is_empty() {
+            true => {
+                let mut tables_desc = schema.take_tables_desc();
 
-        if tables_desc.len() != 1 {
-            return Err(ErrorCode::UnImplement("Select join unimplemented yet."));
-        }
+                if tables_desc.len() != 1 {
+                    return Err(ErrorCode::LogicalError(
+                        "Logical error: tables without join must be single table, it's a bug.",
+                    ));
+                }
 
-        match tables_desc.remove(0) {
+                Self::table_relation(tables_desc.remove(0), ctx).await?
+            }
+            false => Self::join_relation(schema, ctx).await?,
+        };
+
+        Ok(AnalyzedResult::SelectQuery(Box::new(state)))
+    }
+
+    async fn table_relation(
+        table_desc: JoinedTableDesc,
+        ctx: Arc<QueryContext>,
+    ) -> Result<QueryRelation> {
+        match table_desc {
             JoinedTableDesc::Table {
                 table, push_downs, ..
             } => {
                 let source_plan = table.read_plan(ctx.clone(), push_downs).await?;
-                state.relation = QueryRelation::FromTable(Box::new(source_plan));
+                Ok(QueryRelation::FromTable(Box::new(source_plan)))
             }
             JoinedTableDesc::Subquery {
                 state: subquery_state,
                 ..
             } => {
                 // TODO: maybe need reanalyze subquery.
-                state.relation = QueryRelation::Nested(subquery_state);
+                Ok(QueryRelation::Nested(subquery_state))
             }
         }
+    }
 
-        Ok(AnalyzedResult::SelectQuery(Box::new(state)))
+    async fn join_relation(schema: JoinedSchema, ctx: Arc<QueryContext>) -> Result<QueryRelation> {
+        let joins = schema.get_joins().to_vec();
+        let mut tables_relation = Vec::with_capacity(schema.get_tables_desc().len());
+        for table_desc in schema.take_tables_desc() {
+            let relation = Self::table_relation(table_desc.clone(), ctx.clone()).await?;
+            let relation_schema = Self::relation_schema(&relation);
+            let projection = Self::qualified_projection(&table_desc, &relation_schema);
+            tables_relation.push((relation, projection));
+        }
+
+        // The joins are in post-order of the join tree, so a join is applied once all the
+        // tables of its right side have been pushed.
+        let mut tables_relation = tables_relation.into_iter();
+        let mut stack = Vec::with_capacity(joins.len() + 1);
+        for (index, join_desc) in joins.into_iter().enumerate() {
+            while stack.len() + index < join_desc.right_tables.end {
+                match tables_relation.next() {
+                    Some(table_relation) => stack.push(table_relation),
+                    None => break,
+                }
+            }
+
+            match (stack.pop(), stack.pop()) {
+                (Some((right, right_projection)), Some((left, left_projection))) => {
+                    let join_relation = JoinRelation {
+                        join_type: join_desc.join_type,
+                        left,
+                        right,
+                        left_projection,
+                        right_projection,
+                        left_keys: join_desc.left_keys,
+                        right_keys: join_desc.right_keys,
+                        other_conditions: join_desc.other_conditions,
+                    };
+                    stack.push((QueryRelation::Join(Box::new(join_relation)), vec![]));
+                }
+                _ => {
+                    return Err(ErrorCode::LogicalError(
+                        "Logical error: join must have two sides, it's a bug.",
+                    ));
+                }
+            }
+        }
+
+        match (stack.pop(), stack.is_empty()) {
+            (Some((relation, _)), true) => Ok(relation),
+            _ => Err(ErrorCode::LogicalError(
+                "Logical error: joins must be a tree, it's a bug.",
+            )),
+        }
+    }
+
+    fn relation_schema(relation: &QueryRelation) -> DataSchemaRef {
+        match relation {
+            QueryRelation::FromTable(plan) => plan.schema(),
+            QueryRelation::Nested(state) => state.finalize_schema.clone(),
+            _ => Arc::new(DataSchema::empty()),
+        }
+    }
+
+    // Rename the ambiguous columns of the table, e.g. `a` of t1 is renamed as `t1.a`.
+    fn qualified_projection(
+        table_desc: &JoinedTableDesc,
+        schema: &DataSchemaRef,
+    ) -> Vec<Expression> {
+        let columns_name = table_desc
+            .get_columns_desc()
+            .iter()
+            .map(|desc| (desc.short_name.clone(), table_desc.get_column_name(desc)))
+            .collect::<HashMap<_, _>>();
+
+        let require_rename = schema.fields().iter().any(
+            |field| matches!(columns_name.get(field.name()), Some(name) if name != field.name()),
+        );
+
+        match require_rename {
+            false => vec![],
+            true => schema
+                .fields()
+                .iter()
+                .map(|field| match columns_name.get(field.name()) {
+                    Some(name) if name != field.name() => col(field.name()).alias(name),
+                    _ => col(field.name()),
+                })
+                .collect(),
+        }
     }
 
     fn verify_with_dry_run(schema: &JoinedSchema, state: &QueryAnalyzeState) -> Result<DataBlock> {
@@ -235,6 +497,23 @@ impl DfQueryStatement {
             }
         }
 
+        if !state.array_join_expressions.is_empty() {
+            match Self::dry_run_array_join(state, &data_block) {
+                Ok(res) => {
+                    data_block = res;
+                }
+                Err(cause) => {
+                    return Err(cause.add_message_back(" (while in select array join)"));
+                }
+            }
+        }
+
+        if let Some(predicate) = &state.array_join_filter {
+            if let Err(cause) = Self::dry_run_expr(predicate, &data_block) {
+                return Err(cause.add_message_back(" (while in select array join filter)"));
+            }
+        }
+
         if !state.before_group_by_expressions.is_empty() {
             match Self::dry_run_exprs(&state.before_group_by_expressions, &data_block) {
                 Ok(res) => {
@@ -268,6 +547,17 @@ impl DfQueryStatement {
             }
         }
 
+        if !state.window_expressions.is_empty() {
+            match Self::dry_run_window(state, &data_block) {
+                Ok(res) => {
+                    data_block = res;
+                }
+                Err(cause) => {
+                    return Err(cause.add_message_back(" (while in select window)"));
+                }
+            }
+        }
+
         if !state.expressions.is_empty() {
             match Self::dry_run_exprs(&state.expressions, &data_block) {
                 Ok(res) => {
@@ -308,6 +598,40 @@ impl DfQueryStatement {
         Ok(data_block)
     }
 
+    // The before array join expressions and the arrayJoin functions are appended to the input
+    // columns.
+    fn dry_run_array_join(state: &QueryAnalyzeState, data: &DataBlock) -> Result<DataBlock> {
+        let mut schema = data.schema().clone();
+        for exprs in [
+            &state.before_array_join_expressions,
+            &state.array_join_expressions,
+        ] {
+            let mut fields = schema.fields().clone();
+            for expr in exprs {
+                fields.push(expr.to_data_field(&schema)?);
+            }
+
+            schema = DataSchemaRefExt::create(fields);
+        }
+
+        Ok(DataBlock::empty_with_schema(schema))
+    }
+
+    // The before window expressions and the window functions are appended to the input columns.
+    fn dry_run_window(state: &QueryAnalyzeState, data: &DataBlock) -> Result<DataBlock> {
+        let mut schema = data.schema().clone();
+        for exprs in [&state.before_window_expressions, &state.window_expressions] {
+            let mut fields = schema.fields().clone();
+            for expr in exprs {
+                fields.push(expr.to_data_field(&schema)?);
+            }
+
+            schema = DataSchemaRefExt::create(fields);
+        }
+
+        Ok(DataBlock::empty_with_schema(schema))
+    }
+
     fn dry_run_expr(expr: &Expression, data: &DataBlock) -> Result<DataBlock> {
         let schema = data.schema();
         let data_field = expr.to_data_field(schema)?;
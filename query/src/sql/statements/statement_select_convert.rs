@@ -14,9 +14,15 @@
 
 use std::convert::TryFrom;
 
+use sqlparser::ast::Cte;
+use sqlparser::ast::FunctionArg;
+use sqlparser::ast::Join;
 use sqlparser::ast::Query;
 use sqlparser::ast::Select;
+use sqlparser::ast::SelectItem;
 use sqlparser::ast::SetExpr;
+use sqlparser::ast::TableFactor;
+use sqlparser::ast::TableWithJoins;
 use sqlparser::parser::ParserError;
 
 use crate::sql::statements::DfQueryStatement;
@@ -25,13 +31,15 @@ impl TryFrom<Query> for DfQueryStatement {
     type Error = ParserError;
 
     fn try_from(query: Query) -> Result<Self, Self::Error> {
-        let query_body = Self::get_body(&query)?;
-
-        if query.with.is_some() {
-            return Err(ParserError::ParserError(String::from(
-                "CTE is not yet implement",
-            )));
-        }
+        let ctes = match &query.with {
+            None => vec![],
+            Some(with) if !with.recursive => with.cte_tables.clone(),
+            Some(_) => {
+                return Err(ParserError::ParserError(String::from(
+                    "Recursive CTE is not yet implement",
+                )));
+            }
+        };
 
         if query.fetch.is_some() {
             return Err(ParserError::ParserError(String::from(
@@ -39,6 +47,12 @@ impl TryFrom<Query> for DfQueryStatement {
             )));
         }
 
+        if let Some(subquery) = Self::get_subquery(&query) {
+            return Ok(Self::select_from_subquery(ctes, subquery, &query));
+        }
+
+        let query_body = Self::get_body(&query)?;
+
         if query_body.top.is_some() {
             return Err(ParserError::ParserError(String::from(
                 "TOP is not yet implement",
@@ -63,8 +77,14 @@ impl TryFrom<Query> for DfQueryStatement {
             )));
         }
 
+        let mut from = query_body.from.clone();
+        let array_join = Self::take_array_join(&mut from)?;
+
         Ok(DfQueryStatement {
-            from: query_body.from.clone(),
+            ctes,
+            outer_ctes: vec![],
+            from,
+            array_join,
             projection: query_body.projection.clone(),
             selection: query_body.selection.clone(),
             group_by: query_body.group_by.clone(),
@@ -77,6 +97,99 @@ impl TryFrom<Query> for DfQueryStatement {
 }
 
 impl DfQueryStatement {
+    /// The table function of the cross join that the parser rewrites the ARRAY JOIN clause into.
+    pub const ARRAY_JOIN_TABLE_FUNCTION: &'static str = "ARRAY JOIN";
+
+    // The set operations and the parenthesized queries are selected from as subqueries,
+    // the ORDER BY and LIMIT of the query are applied to the subquery.
+    fn get_subquery(query: &Query) -> Option<Query> {
+        match &query.body {
+            SetExpr::SetOperation { .. } => Some(Query {
+                with: None,
+                body: query.body.clone(),
+                order_by: vec![],
+                limit: None,
+                offset: None,
+                fetch: None,
+            }),
+            SetExpr::Query(subquery) => Some(subquery.as_ref().clone()),
+            _ => None,
+        }
+    }
+
+    fn select_from_subquery(ctes: Vec<Cte>, subquery: Query, query: &Query) -> DfQueryStatement {
+        DfQueryStatement {
+            ctes,
+            outer_ctes: vec![],
+            from: vec![TableWithJoins {
+                relation: TableFactor::Derived {
+                    lateral: false,
+                    subquery: Box::new(subquery),
+                    alias: None,
+                },
+                joins: vec![],
+            }],
+            array_join: vec![],
+            projection: vec![SelectItem::Wildcard],
+            selection: None,
+            group_by: vec![],
+            having: None,
+            order_by: query.order_by.clone(),
+            limit: query.limit.clone(),
+            offset: query.offset.clone(),
+        }
+    }
+
+    // Take the ARRAY JOIN clause out of the joins, the expressions are the arguments of the
+    // table function, named by their aliases.
+    fn take_array_join(from: &mut [TableWithJoins]) -> Result<Vec<SelectItem>, ParserError> {
+        fn is_array_join(join: &Join) -> bool {
+            match &join.relation {
+                TableFactor::Table { name, .. } => matches!(&name.0[..], [ident]
+                    if ident.quote_style.is_some()
+                        && ident.value == DfQueryStatement::ARRAY_JOIN_TABLE_FUNCTION),
+                _ => false,
+            }
+        }
+
+        let mut array_join = vec![];
+        for table_with_joins in from.iter_mut() {
+            let (array_joins, joins): (Vec<_>, Vec<_>) =
+                std::mem::take(&mut table_with_joins.joins)
+                    .into_iter()
+                    .partition(is_array_join);
+            table_with_joins.joins = joins;
+
+            for join in array_joins {
+                if !array_join.is_empty() {
+                    return Err(ParserError::ParserError(String::from(
+                        "Multiple ARRAY JOIN clauses are not yet implement",
+                    )));
+                }
+
+                if let TableFactor::Table { args, .. } = join.relation {
+                    if args.is_empty() {
+                        return Err(ParserError::ParserError(String::from(
+                            "ARRAY JOIN requires at least one expression",
+                        )));
+                    }
+
+                    for arg in args {
+                        array_join.push(match arg {
+                            FunctionArg::Named { name, arg } => SelectItem::ExprWithAlias {
+                                expr: arg,
+                                alias: name,
+                            },
+                            FunctionArg::Unnamed(arg) => SelectItem::UnnamedExpr(arg),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(array_join)
+    }
+
     fn get_body(query: &Query) -> Result<&Select, ParserError> {
         match &query.body {
             SetExpr::Select(query) => Ok(query),
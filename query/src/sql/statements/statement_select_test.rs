@@ -41,6 +41,16 @@ async fn test_statement_select_analyze() -> Result<()> {
             query: "SELECT * FROM system.databases WHERE name = 'xxx'",
             expect: "QueryAnalyzeState { filter: (name = xxx), before_projection: [name], projection: [name] }",
         },
+        TestCase {
+            name: "Filter query with IN list",
+            query: "SELECT * FROM system.databases WHERE name IN ('a', 'b')",
+            expect: "QueryAnalyzeState { filter: ((name = a) or (name = b)), before_projection: [name], projection: [name] }",
+        },
+        TestCase {
+            name: "Filter query with NOT IN list",
+            query: "SELECT * FROM system.databases WHERE name NOT IN ('a', 'b')",
+            expect: "QueryAnalyzeState { filter: ((name != a) and (name != b)), before_projection: [name], projection: [name] }",
+        },
         TestCase {
             name: "Simple having query",
             query: "SELECT * FROM system.databases HAVING name = 'xxx'",
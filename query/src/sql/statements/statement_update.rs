@@ -0,0 +1,125 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::find_aggregate_exprs_in_expr;
+use common_planners::Expression;
+use common_planners::PlanNode;
+use common_planners::UpdatePlan;
+use common_tracing::tracing;
+use sqlparser::ast::Assignment;
+use sqlparser::ast::Expr;
+use sqlparser::ast::ObjectName;
+
+use crate::sessions::QueryContext;
+use crate::sql::statements::analyzer_expr::ExpressionAnalyzer;
+use crate::sql::statements::AnalyzableStatement;
+use crate::sql::statements::AnalyzedResult;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfUpdateStatement {
+    pub name: ObjectName,
+    pub assignments: Vec<Assignment>,
+    pub selection: Option<Expr>,
+}
+
+#[async_trait::async_trait]
+impl AnalyzableStatement for DfUpdateStatement {
+    #[tracing::instrument(level = "info", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<QueryContext>) -> Result<AnalyzedResult> {
+        let (db, table) = self.resolve_table(ctx.clone())?;
+        let schema = ctx.get_table(&db, &table).await?.schema();
+        let expression_analyzer = ExpressionAnalyzer::create(ctx);
+
+        let mut assignments: Vec<(String, Expression)> = Vec::with_capacity(self.assignments.len());
+        for Assignment { id, value } in &self.assignments {
+            let column = id.value.clone();
+            let field = match schema.column_with_name(&column) {
+                Some((_, field)) => field,
+                None => {
+                    return Err(ErrorCode::UnknownColumn(format!(
+                        "Unknown column {} of table {}",
+                        column, table
+                    )))
+                }
+            };
+            if assignments.iter().any(|(name, _)| name == &column) {
+                return Err(ErrorCode::SyntaxException(format!(
+                    "Multiple assignments to the same column {}",
+                    column
+                )));
+            }
+
+            let expr = expression_analyzer.analyze(value).await?;
+            Self::check_no_aggregate(&expr, "SET")?;
+            let expr = if &expr.to_data_type(&schema)? != field.data_type() {
+                Expression::Cast {
+                    expr: Box::new(expr),
+                    data_type: field.data_type().clone(),
+                }
+            } else {
+                expr
+            };
+            assignments.push((column, expr));
+        }
+
+        let selection = match &self.selection {
+            None => None,
+            Some(expr) => {
+                let expression = expression_analyzer.analyze(expr).await?;
+                Self::check_no_aggregate(&expression, "WHERE")?;
+                // fails early if the selection refers to unknown columns
+                expression.to_data_type(&schema)?;
+                Some(expression)
+            }
+        };
+
+        Ok(AnalyzedResult::SimpleQuery(PlanNode::Update(UpdatePlan {
+            db,
+            table,
+            assignments,
+            selection,
+        })))
+    }
+}
+
+impl DfUpdateStatement {
+    fn resolve_table(&self, ctx: Arc<QueryContext>) -> Result<(String, String)> {
+        let DfUpdateStatement {
+            name: ObjectName(idents),
+            ..
+        } = self;
+        match idents.len() {
+            0 => Err(ErrorCode::SyntaxException("Update table name is empty")),
+            1 => Ok((ctx.get_current_database(), idents[0].value.clone())),
+            2 => Ok((idents[0].value.clone(), idents[1].value.clone())),
+            _ => Err(ErrorCode::SyntaxException(
+                "Update table name must be [`db`].`table`",
+            )),
+        }
+    }
+
+    fn check_no_aggregate(expr: &Expression, clause: &str) -> Result<()> {
+        match find_aggregate_exprs_in_expr(expr).is_empty() {
+            true => Ok(()),
+            false => Err(ErrorCode::SyntaxException(format!(
+                "Aggregate functions are not allowed in the {} clause of UPDATE",
+                clause
+            ))),
+        }
+    }
+}
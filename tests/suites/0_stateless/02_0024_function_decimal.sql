@@ -0,0 +1,44 @@
+SELECT toTypeName(CAST('1.235' AS DECIMAL(10, 2)));
+SELECT CAST('1.235' AS DECIMAL(10, 2)), CAST('-1.235' AS DECIMAL(10, 2));
+SELECT CAST(12 AS DECIMAL(5, 1)), CAST(1.5 AS DECIMAL(5, 3));
+SELECT CAST(CAST('9.99' AS DECIMAL(3, 2)) AS Int32);
+SELECT toTypeName(CAST('1.5' AS DECIMAL(10, 2)) + CAST('1.5' AS DECIMAL(5, 1)));
+SELECT CAST('1.5' AS DECIMAL(10, 2)) + CAST('0.25' AS DECIMAL(5, 2));
+SELECT CAST('1.5' AS DECIMAL(10, 2)) - 2;
+SELECT toTypeName(CAST('1.5' AS DECIMAL(10, 2)) * CAST('1.5' AS DECIMAL(5, 1)));
+SELECT CAST('1.5' AS DECIMAL(10, 2)) * CAST('1.5' AS DECIMAL(5, 1));
+SELECT toTypeName(CAST('1' AS DECIMAL(10, 2)) / CAST('3' AS DECIMAL(5, 1)));
+SELECT CAST('1' AS DECIMAL(10, 2)) / CAST('3' AS DECIMAL(5, 1));
+SELECT CAST('2' AS DECIMAL(10, 2)) / CAST('3' AS DECIMAL(5, 1)), CAST('-2' AS DECIMAL(10, 2)) / CAST('3' AS DECIMAL(5, 1));
+SELECT -CAST('1.5' AS DECIMAL(10, 2));
+SELECT CAST('1.5' AS DECIMAL(10, 2)) = CAST('1.50' AS DECIMAL(5, 1));
+SELECT CAST('1.5' AS DECIMAL(10, 2)) < 2, CAST('1.5' AS DECIMAL(10, 2)) > '1.49';
+SELECT CAST('1' AS DECIMAL(10, 2)) / 0; -- {ErrorCode 6}
+SELECT CAST('1000' AS DECIMAL(3, 0)); -- {ErrorCode 49}
+SELECT CAST('1' AS DECIMAL(77, 2)); -- {ErrorCode 7}
+
+SELECT toTypeName(CAST('1' AS DECIMAL(50, 2)));
+SELECT CAST('12345678901234567890123456789012345678901.235' AS DECIMAL(50, 2));
+SELECT toTypeName(CAST('1.5' AS DECIMAL(40, 2)) * CAST('1.5' AS DECIMAL(5, 1)));
+SELECT CAST('99999999999999999999999999999999999999' AS DECIMAL(40, 0)) * 100;
+SELECT CAST('-1.5' AS DECIMAL(40, 2)) + 2, -CAST('1.5' AS DECIMAL(40, 2));
+SELECT toTypeName(CAST('1' AS DECIMAL(40, 2)) / CAST('3' AS DECIMAL(5, 1)));
+SELECT CAST('1' AS DECIMAL(40, 2)) / CAST('3' AS DECIMAL(5, 1));
+SELECT CAST(CAST('123.456' AS DECIMAL(40, 3)) AS Int64), CAST(CAST('123.456' AS DECIMAL(40, 3)) AS DECIMAL(10, 1));
+SELECT CAST('-1.5' AS DECIMAL(40, 2)) < CAST('1' AS DECIMAL(45, 0)), CAST('1.5' AS DECIMAL(40, 2)) = '1.50';
+
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+CREATE TABLE IF NOT EXISTS t(a Int32, b Decimal(10, 2)) Engine = fuse;
+INSERT INTO t(a, b) VALUES(1, '1.25'), (2, '-3.5'), (3, '100');
+SELECT * FROM t ORDER BY a;
+SELECT a FROM t WHERE b > 0 ORDER BY a;
+
+CREATE TABLE IF NOT EXISTS t2(a Int32, b Decimal(50, 2)) Engine = fuse;
+INSERT INTO t2(a, b) VALUES(1, '-12345678901234567890123456789012345678901.5'), (2, '0.25'), (3, '-3');
+SELECT * FROM t2 ORDER BY b;
+SELECT sum(a) FROM t2 WHERE b < 0;
+
+DROP DATABASE db1;
@@ -0,0 +1,19 @@
+SELECT toTypeName(toDateTime64('2021-03-05 01:01:01.123456'));
+SELECT toDateTime64('2021-03-05 01:01:01.123456');
+SELECT toHour(toDateTime64('2021-03-05 01:01:01.5')), toStartOfHour(toDateTime64('2021-03-05 01:31:01.5'));
+SELECT toDateTime64('2021-03-05 01:01:01.5') + INTERVAL 1 DAY;
+SELECT toTimeZone(toDateTime64('2021-03-05 01:01:01.5'), 'Asia/Shanghai');
+SELECT convert_tz(toDateTime('2021-03-05 09:01:01'), 'Asia/Shanghai', 'UTC');
+SELECT toTimeZone(toDateTime64('2021-03-05 01:01:01.5'), 'Mars/Olympus'); -- {ErrorCode 6}
+
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+CREATE TABLE IF NOT EXISTS t(id Int32, ts DateTime64(6), lt DateTime64(3, 'Asia/Shanghai')) Engine = fuse;
+INSERT INTO t VALUES(1, '2021-10-18 10:05:42.123456', '2021-10-18 18:05:42.123'), (2, '2021-10-18 23:59:59.999999', '2021-10-19 07:59:59.999');
+SELECT * FROM t ORDER BY id;
+SELECT id, toHour(ts), toHour(lt), toDayOfMonth(lt) FROM t ORDER BY id;
+CREATE TABLE IF NOT EXISTS t2(ts DateTime64(10)) Engine = fuse; -- {ErrorCode 7}
+
+DROP DATABASE db1;
@@ -0,0 +1,22 @@
+SELECT toTypeName(parse_json('1'));
+SELECT parse_json('{"a": 1, "b": [1, 2]}');
+SELECT try_parse_json('abc');
+SELECT parse_json('abc'); -- {ErrorCode 46}
+SELECT get_path(parse_json('{"a": {"b": [1, 2]}}'), 'a.b[1]');
+SELECT json_extract_int('{"a": {"b": 3}}', 'a.b'), json_extract_string('{"a": "x"}', 'a'), json_extract_float('{"a": 1.5}', 'a');
+SELECT CAST(parse_json('"2"') AS Int64), CAST(parse_json('[1, 2]') AS String);
+
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+CREATE TABLE IF NOT EXISTS t(id Int32, v Variant) Engine = fuse;
+INSERT INTO t VALUES(1, '{"a": {"b": 1}, "c": [10, 20]}'), (2, '{"a": {"b": "x"}}');
+SELECT id, v FROM t ORDER BY id;
+SELECT id, v.a.b, t.v.c, get_path(v, 'c[1]') FROM t ORDER BY id;
+SELECT id FROM t WHERE json_extract_string(v, 'a.b') = 'x';
+SELECT v.a FROM t WHERE id = 1;
+SELECT t.id.a FROM t; -- {ErrorCode 5}
+INSERT INTO t VALUES(3, 'abc'); -- {ErrorCode 46}
+
+DROP DATABASE db1;
@@ -0,0 +1,13 @@
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+CREATE TABLE IF NOT EXISTS t(id Int32, m Map(String, Int64)) Engine = fuse;
+INSERT INTO t VALUES(1, '{"a": 1, "b": 2}'), (2, '{"c": 3}');
+SELECT id, m FROM t ORDER BY id;
+SELECT id, m['a'], mapKeys(m), mapValues(m), mapContains(m, 'c') FROM t ORDER BY id;
+SELECT id FROM t WHERE m['b'] = 2;
+INSERT INTO t VALUES(3, '{"a": "x"}'); -- {ErrorCode 46}
+CREATE TABLE t1(m Map(Variant, Int64)) Engine = fuse; -- {ErrorCode 7}
+
+DROP DATABASE db1;
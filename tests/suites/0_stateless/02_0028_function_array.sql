@@ -0,0 +1,24 @@
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+CREATE TABLE IF NOT EXISTS t(id Int32, tags Array(String)) Engine = fuse;
+INSERT INTO t VALUES(1, '["a", "b", "a"]'), (2, '["c"]'), (3, '[]');
+SELECT id, tags FROM t ORDER BY id;
+SELECT id, length(tags), has(tags, 'a'), indexOf(tags, 'b'), tags[1] FROM t ORDER BY id;
+SELECT id, arrayDistinct(tags), arraySort(tags), arraySlice(tags, 2), arrayConcat(tags, array('d')) FROM t ORDER BY id;
+SELECT id, arrayJoin(tags) AS tag FROM t ORDER BY id, tag;
+SELECT unnest(tags) AS tag, count() FROM t GROUP BY tag ORDER BY tag;
+SELECT arrayJoin(array(1, 2, 3)) AS x ORDER BY x;
+SELECT arraySort(groupArray(number % 3)), arraySort(groupUniqArray(number % 3)) FROM numbers(5);
+SELECT id FROM t WHERE arrayJoin(tags) = 'a' ORDER BY id;
+SELECT [1, 2, 3], length([]), has(['a', 'b'], 'b'), [[1], [2, 3]];
+SELECT id, tag FROM t ARRAY JOIN tags AS tag ORDER BY id, tag;
+SELECT id, tags FROM t ARRAY JOIN tags WHERE tags != 'a' ORDER BY id;
+SELECT tag, count() FROM t ARRAY JOIN tags AS tag GROUP BY tag ORDER BY tag;
+SELECT id, tag, sorted FROM t ARRAY JOIN tags AS tag, arraySort(tags) AS sorted ORDER BY id, tag, sorted;
+SELECT number, x FROM numbers(2) ARRAY JOIN [10, 20] AS x ORDER BY number, x;
+SELECT id FROM t ARRAY JOIN tags AS a ARRAY JOIN tags AS b; -- {ErrorCode 5}
+INSERT INTO t VALUES(4, '{"a": 1}'); -- {ErrorCode 46}
+
+DROP DATABASE db1;
@@ -0,0 +1,21 @@
+DROP TABLE IF EXISTS t1;
+DROP TABLE IF EXISTS t2;
+
+CREATE TABLE t1 (a UInt32, b String) engine=Memory;
+CREATE TABLE t2 (a UInt64, c UInt32) engine=Memory;
+INSERT INTO t1 VALUES(1, 'x'), (2, 'y'), (3, 'z');
+INSERT INTO t2 VALUES(2, 20), (3, 30), (3, 31), (4, 40);
+
+SELECT t1.a, b, c FROM t1 JOIN t2 ON t1.a = t2.a ORDER BY t1.a, c;
+SELECT t1.a, b, c FROM t1 LEFT JOIN t2 ON t1.a = t2.a ORDER BY t1.a, c;
+SELECT t2.a, b, c FROM t1 RIGHT JOIN t2 USING (a) ORDER BY c;
+SELECT count(), count(t1.a), count(t2.a) FROM t1 FULL JOIN t2 ON t1.a = t2.a AND c > 30;
+SELECT count() FROM t1, t2 WHERE t1.a < t2.a;
+SELECT x.a, y.a FROM t1 AS x JOIN t1 AS y ON x.a + 1 = y.a ORDER BY x.a;
+SELECT number, c FROM numbers(5) AS n JOIN (SELECT a, c FROM t2) AS s ON n.number = s.a ORDER BY c;
+
+SELECT a FROM t1 JOIN t2 ON t1.a = t2.a; -- {ErrorCode 58}
+SELECT b FROM t1 LEFT JOIN t2; -- {ErrorCode 5}
+
+DROP TABLE t1;
+DROP TABLE t2;
@@ -0,0 +1,20 @@
+DROP TABLE IF EXISTS t;
+
+CREATE TABLE t (g String, v UInt32) engine=Memory;
+INSERT INTO t VALUES('a', 1), ('a', 2), ('a', 2), ('a', 4), ('b', 3), ('b', 5);
+
+SELECT g, v, row_number() OVER (PARTITION BY g ORDER BY v) AS rn, rank() OVER (PARTITION BY g ORDER BY v), dense_rank() OVER (PARTITION BY g ORDER BY v) FROM t ORDER BY g, v, rn;
+SELECT g, v, sum(v) OVER (PARTITION BY g ORDER BY v) FROM t ORDER BY g, v;
+SELECT g, v, sum(v) OVER (PARTITION BY g ORDER BY v ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING) AS s FROM t ORDER BY g, v, s;
+SELECT g, v FROM (SELECT g, v, row_number() OVER (PARTITION BY g ORDER BY v DESC) AS rn FROM t) WHERE rn <= 1 ORDER BY g;
+SELECT g, sum(v), rank() OVER (ORDER BY sum(v) DESC) FROM t GROUP BY g ORDER BY g;
+
+SELECT number, lag(number) OVER (ORDER BY number), lead(number, 2, 100) OVER (ORDER BY number) FROM numbers(5) ORDER BY number;
+SELECT number, first_value(number) OVER (ORDER BY number ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING), last_value(number) OVER (ORDER BY number ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING) FROM numbers(5) ORDER BY number;
+SELECT number, count() OVER (ORDER BY number DESC RANGE BETWEEN 2 PRECEDING AND CURRENT ROW) FROM numbers(5) ORDER BY number;
+
+SELECT row_number() FROM t; -- {ErrorCode 5}
+SELECT v FROM t WHERE rank() OVER (ORDER BY v) > 1; -- {ErrorCode 5}
+SELECT sum(v) OVER (ORDER BY g RANGE BETWEEN 1 PRECEDING AND CURRENT ROW) FROM t; -- {ErrorCode 6}
+
+DROP TABLE t;
@@ -0,0 +1,20 @@
+DROP TABLE IF EXISTS t;
+
+CREATE TABLE t (g String, v UInt32) engine=Memory;
+INSERT INTO t VALUES('a', 1), ('a', 2), ('b', 3);
+
+SELECT number FROM numbers(3) UNION ALL SELECT number FROM numbers(2) ORDER BY number;
+SELECT number FROM numbers(3) UNION SELECT number FROM numbers(5) ORDER BY number;
+SELECT number FROM numbers(10) UNION ALL SELECT number FROM numbers(10) ORDER BY number DESC LIMIT 3;
+SELECT v FROM t UNION SELECT number FROM numbers(3) ORDER BY v;
+SELECT g FROM t UNION ALL SELECT g FROM t WHERE v > 1 ORDER BY g;
+SELECT count() FROM (SELECT number FROM numbers(3) UNION ALL SELECT number FROM numbers(4));
+
+SELECT number % 3 AS a FROM numbers(6) INTERSECT SELECT number FROM numbers(2) ORDER BY a;
+SELECT number % 3 AS a FROM numbers(6) INTERSECT ALL SELECT number % 2 FROM numbers(3) ORDER BY a;
+SELECT number FROM numbers(5) EXCEPT SELECT number FROM numbers(3) ORDER BY number;
+SELECT number % 3 AS a FROM numbers(6) EXCEPT ALL SELECT number % 2 FROM numbers(3) ORDER BY a;
+
+SELECT number FROM numbers(2) UNION SELECT number, number FROM numbers(2); -- {ErrorCode 6}
+
+DROP TABLE t;
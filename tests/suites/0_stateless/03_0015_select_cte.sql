@@ -0,0 +1,12 @@
+WITH t AS (SELECT number FROM numbers(5) WHERE number > 1) SELECT number FROM t ORDER BY number;
+WITH t(a, b) AS (SELECT number, number * 2 FROM numbers(3)) SELECT a, b FROM t ORDER BY a;
+WITH t1 AS (SELECT number FROM numbers(5)), t2 AS (SELECT number + 1 AS n FROM t1 WHERE number < 2) SELECT n FROM t2 ORDER BY n;
+WITH t AS (SELECT number FROM numbers(3)) SELECT x.number FROM t AS x WHERE x.number IN (SELECT number + 1 FROM t) ORDER BY x.number;
+SELECT count() FROM (WITH t AS (SELECT number FROM numbers(10)) SELECT number FROM t WHERE number % 2 = 0);
+
+SET materialize_cte = 1;
+WITH t AS (SELECT number FROM numbers(3)) SELECT a.number, b.number FROM t AS a JOIN t AS b ON a.number = b.number ORDER BY a.number;
+WITH t AS (SELECT number % 2 AS a FROM numbers(4)) SELECT a FROM t UNION ALL SELECT a FROM t ORDER BY a;
+SET materialize_cte = 0;
+
+WITH t(a, b) AS (SELECT number FROM numbers(3)) SELECT a FROM t; -- {ErrorCode 6}
@@ -0,0 +1,10 @@
+SET max_block_size = 4;
+SET max_bytes_before_external_sort = 1;
+
+SELECT number % 3 AS a, number FROM numbers(10) ORDER BY a, number DESC;
+SELECT toString(number % 5) AS s FROM numbers(12) ORDER BY s DESC;
+SELECT number FROM numbers(100) ORDER BY number DESC LIMIT 3;
+SELECT count(), sum(number) FROM (SELECT number FROM numbers(1000) ORDER BY number DESC);
+
+SET max_bytes_before_external_sort = 0;
+SET max_block_size = 10000;
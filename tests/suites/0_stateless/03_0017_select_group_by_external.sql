@@ -0,0 +1,10 @@
+SET max_block_size = 3;
+SET max_bytes_before_external_group_by = 1;
+
+SELECT number % 3 AS a, count(), sum(number) FROM numbers(20) GROUP BY a ORDER BY a;
+SELECT toString(number % 4) AS s, max(number) FROM numbers(20) GROUP BY s ORDER BY s;
+SELECT count() FROM (SELECT number FROM numbers(1000) GROUP BY number);
+SELECT count(), sum(c) FROM (SELECT number % 100 AS k, count() AS c FROM numbers(1000) GROUP BY k);
+
+SET max_bytes_before_external_group_by = 0;
+SET max_block_size = 10000;
@@ -0,0 +1,5 @@
+SET max_threads = 2;
+SET max_memory_usage = 1;
+SELECT sum(number) FROM numbers_mt(100000); -- {ErrorCode 60}
+SET max_memory_usage = 0;
+SELECT sum(number) FROM numbers_mt(100000);
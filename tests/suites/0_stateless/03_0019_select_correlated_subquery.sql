@@ -0,0 +1,32 @@
+SELECT number FROM numbers(5) AS a WHERE EXISTS (SELECT * FROM numbers(3) AS b WHERE b.number = a.number + 1) ORDER BY number;
+SELECT number FROM numbers(5) AS a WHERE NOT EXISTS (SELECT * FROM numbers(3) AS b WHERE b.number = a.number) ORDER BY number;
+SELECT number FROM numbers(5) AS a WHERE EXISTS (SELECT * FROM numbers(3) AS b WHERE b.number > a.number) ORDER BY number;
+SELECT number FROM numbers(5) AS a WHERE EXISTS (SELECT * FROM numbers(3) WHERE number > 1) ORDER BY number;
+SELECT number FROM numbers(5) WHERE number IN (SELECT number * 2 FROM numbers(3)) ORDER BY number;
+SELECT number FROM numbers(5) WHERE number NOT IN (SELECT number * 2 FROM numbers(3)) ORDER BY number;
+SELECT number FROM numbers(5) AS a WHERE a.number IN (SELECT b.number FROM numbers(5) AS b WHERE b.number % 2 = a.number % 2 AND b.number > 1) ORDER BY number;
+SELECT number FROM numbers(5) AS a WHERE a.number >= (SELECT max(b.number) FROM numbers(5) AS b WHERE b.number % 2 = a.number % 2) ORDER BY number;
+SELECT number, (SELECT count(*) FROM numbers(3) AS b WHERE b.number = a.number) FROM numbers(5) AS a ORDER BY number;
+SELECT number, (SELECT max(b.number) FROM numbers(5) AS b WHERE b.number % 2 = a.number % 2) AS m FROM numbers(5) AS a ORDER BY number;
+SELECT number FROM numbers(5) AS a WHERE a.number = (SELECT count(*) + 1 FROM numbers(5) AS b WHERE b.number = a.number); -- {ErrorCode 2}
+SELECT number FROM numbers(5) WHERE number IN (SELECT number, number FROM numbers(3)); -- {ErrorCode 5}
+
+DROP TABLE IF EXISTS t1;
+DROP TABLE IF EXISTS t2;
+DROP TABLE IF EXISTS t3;
+CREATE TABLE t1 (k UInt32, v UInt32) engine=Memory;
+CREATE TABLE t2 (k UInt32, v UInt32) engine=Memory;
+CREATE TABLE t3 (k UInt32, v UInt32) engine=Memory;
+INSERT INTO t1 VALUES (1, 2), (2, 2), (2, 3), (3, Null);
+INSERT INTO t2 VALUES (1, 1), (1, Null), (2, 2);
+INSERT INTO t3 VALUES (1, 1), (2, 2);
+
+SELECT k, v FROM t1 WHERE v NOT IN (SELECT v FROM t3) ORDER BY k, v;
+SELECT k, v FROM t1 WHERE v NOT IN (SELECT v FROM t2) ORDER BY k, v;
+SELECT k, v FROM t1 WHERE v NOT IN (SELECT v FROM t2 WHERE k > 5) ORDER BY k, v;
+SELECT k, v FROM t1 WHERE v NOT IN (SELECT t2.v FROM t2 WHERE t2.k = t1.k) ORDER BY k, v;
+SELECT k, v FROM t1 WHERE v IN (SELECT v FROM t2) ORDER BY k, v;
+
+DROP TABLE t1;
+DROP TABLE t2;
+DROP TABLE t3;
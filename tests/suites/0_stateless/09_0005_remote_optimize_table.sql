@@ -0,0 +1,20 @@
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+CREATE TABLE IF NOT EXISTS t(a varchar, b varchar) Engine = fuse;
+INSERT INTO t(a,b) VALUES('1', 'v1'),('2','v2');
+INSERT INTO t(a,b) VALUES('3', 'v3');
+INSERT INTO t(a,b) VALUES('4', 'v4');
+OPTIMIZE TABLE t;
+SELECT * FROM t ORDER BY a;
+INSERT INTO t(a,b) VALUES('5', 'v5');
+OPTIMIZE TABLE t SEGMENT;
+SELECT * FROM t ORDER BY a;
+OPTIMIZE TABLE t COMPACT;
+SELECT count(*) FROM t;
+
+DROP TABLE t;
+OPTIMIZE TABLE t; -- {ErrorCode 25}
+
+DROP DATABASE db1;
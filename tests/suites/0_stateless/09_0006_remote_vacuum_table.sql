@@ -0,0 +1,19 @@
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+CREATE TABLE IF NOT EXISTS t(a varchar, b varchar) Engine = fuse;
+INSERT INTO t(a,b) VALUES('1', 'v1'),('2','v2');
+INSERT INTO t(a,b) VALUES('3', 'v3');
+OPTIMIZE TABLE t;
+VACUUM TABLE t DRY RUN;
+VACUUM TABLE t;
+SELECT * FROM t ORDER BY a;
+VACUUM TABLE t RETAIN 0 HOURS;
+SELECT * FROM t ORDER BY a;
+VACUUM TABLE t RETAIN 0 HOURS DRY RUN;
+
+DROP TABLE t;
+VACUUM TABLE t; -- {ErrorCode 25}
+
+DROP DATABASE db1;
@@ -0,0 +1,21 @@
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+CREATE TABLE IF NOT EXISTS t(a Int32, b varchar) Engine = fuse;
+INSERT INTO t(a,b) VALUES(1, 'v1'),(2, 'v2'),(3, 'v3');
+INSERT INTO t(a,b) VALUES(4, 'v4'),(5, 'v5');
+DELETE FROM t WHERE a = 2;
+SELECT * FROM t ORDER BY a;
+DELETE FROM t WHERE a >= 4;
+SELECT * FROM t ORDER BY a;
+DELETE FROM t WHERE a > 10;
+SELECT count(*) FROM t;
+DELETE FROM t WHERE sum(a) > 1; -- {ErrorCode 5}
+DELETE FROM t;
+SELECT count(*) FROM t;
+
+DROP TABLE t;
+DELETE FROM t; -- {ErrorCode 25}
+
+DROP DATABASE db1;
@@ -0,0 +1,21 @@
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+CREATE TABLE IF NOT EXISTS t(a Int32, b varchar) Engine = fuse;
+INSERT INTO t(a,b) VALUES(1, 'v1'),(2, 'v2'),(3, 'v3');
+INSERT INTO t(a,b) VALUES(4, 'v4'),(5, 'v5');
+UPDATE t SET b = 'x' WHERE a = 2;
+SELECT * FROM t ORDER BY a;
+UPDATE t SET a = a + 10, b = 'y' WHERE a >= 4;
+SELECT * FROM t ORDER BY a;
+UPDATE t SET b = 'z';
+SELECT * FROM t ORDER BY a;
+SELECT count(*) FROM t WHERE a > 10;
+UPDATE t SET c = 1; -- {ErrorCode 58}
+UPDATE t SET a = sum(a); -- {ErrorCode 5}
+
+DROP TABLE t;
+UPDATE t SET a = 1; -- {ErrorCode 25}
+
+DROP DATABASE db1;
@@ -0,0 +1,19 @@
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+CREATE TABLE IF NOT EXISTS t(a Int32, b varchar) Engine = fuse CLUSTER BY (a);
+INSERT INTO t(a,b) VALUES(3, 'v3'),(1, 'v1'),(2, 'v2');
+INSERT INTO t(a,b) VALUES(5, 'v5'),(2, 'v2'),(4, 'v4');
+SELECT * FROM t ORDER BY a;
+SELECT * FROM clustering_information('db1', 't');
+SELECT count(*) FROM t WHERE a > 3;
+
+CREATE TABLE t1(a Int32) Engine = Null CLUSTER BY (a); -- {ErrorCode 6}
+CREATE TABLE t1(a Int32) Engine = fuse CLUSTER BY (c); -- {ErrorCode 58}
+CREATE TABLE t1(a Int32) Engine = fuse;
+SELECT * FROM clustering_information('db1', 't1'); -- {ErrorCode 6}
+
+DROP TABLE t;
+DROP TABLE t1;
+DROP DATABASE db1;
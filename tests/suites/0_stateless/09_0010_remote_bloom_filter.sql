@@ -0,0 +1,21 @@
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+CREATE TABLE IF NOT EXISTS t(a Int32, b varchar) Engine = fuse BLOOM_FILTER_COLUMNS = 'a, b';
+INSERT INTO t(a,b) VALUES(1, 'v1'),(3, 'v3'),(5, 'v5');
+INSERT INTO t(a,b) VALUES(2, 'v2'),(4, 'v4'),(6, 'v6');
+SELECT * FROM t WHERE b = 'v4';
+SELECT * FROM t WHERE a IN (1, 4, 7) ORDER BY a;
+SELECT * FROM t WHERE a NOT IN (1, 2, 3, 4) ORDER BY a;
+SELECT * FROM t WHERE a IN (); -- {ErrorCode 5}
+SELECT count(*) FROM t WHERE b = 'v7';
+DELETE FROM t WHERE b IN ('v3', 'v4');
+SELECT * FROM t ORDER BY a;
+
+CREATE TABLE t1(a Int32) Engine = Memory BLOOM_FILTER_COLUMNS = 'a'; -- {ErrorCode 6}
+CREATE TABLE t1(a Int32) Engine = fuse BLOOM_FILTER_COLUMNS = 'c'; -- {ErrorCode 58}
+CREATE TABLE t1(a Date) Engine = fuse BLOOM_FILTER_COLUMNS = 'a'; -- {ErrorCode 6}
+
+DROP TABLE t;
+DROP DATABASE db1;